@@ -0,0 +1,133 @@
+// * ./src/bluetooth.rs
+
+use anyhow::Result;
+use std::collections::HashMap;
+use zbus::{Connection, Proxy};
+use zvariant::{OwnedObjectPath, OwnedValue};
+
+const BLUEZ_SERVICE: &str = "org.bluez";
+const OBJECT_MANAGER_IFACE: &str = "org.freedesktop.DBus.ObjectManager";
+const BLUEZ_DEVICE_IFACE: &str = "org.bluez.Device1";
+// * Network Access Point: the phone side of Bluetooth tethering
+const NAP_UUID: &str = "00001116-0000-1000-8000-00805f9b34fb";
+
+type ManagedObjects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanDevice {
+    pub name: String,
+    pub address: String,
+}
+
+// * Paired devices that offer tethering; Err when BlueZ isn't running.
+pub async fn list_pan_devices() -> Result<Vec<PanDevice>> {
+    let conn = Connection::system().await?;
+    let proxy = Proxy::new(&conn, BLUEZ_SERVICE, "/", OBJECT_MANAGER_IFACE).await?;
+    let objects: ManagedObjects = proxy.call("GetManagedObjects", &()).await?;
+    Ok(parse_pan_devices(&objects))
+}
+
+fn parse_pan_devices(objects: &ManagedObjects) -> Vec<PanDevice> {
+    let mut devices: Vec<PanDevice> = objects
+        .values()
+        .filter_map(|ifaces| ifaces.get(BLUEZ_DEVICE_IFACE))
+        .filter(|device| value_bool(device.get("Paired")))
+        .filter(|device| {
+            value_string_list(device.get("UUIDs"))
+                .iter()
+                .any(|uuid| uuid.eq_ignore_ascii_case(NAP_UUID))
+        })
+        .filter_map(|device| {
+            let address = value_string(device.get("Address"))?;
+            let name = value_string(device.get("Alias"))
+                .or_else(|| value_string(device.get("Name")))
+                .unwrap_or_else(|| address.clone());
+            Some(PanDevice { name, address })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+fn value_bool(value: Option<&OwnedValue>) -> bool {
+    value
+        .and_then(|value| bool::try_from(value).ok())
+        .unwrap_or(false)
+}
+
+fn value_string(value: Option<&OwnedValue>) -> Option<String> {
+    value
+        .and_then(|value| String::try_from(value.try_clone().ok()?).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn value_string_list(value: Option<&OwnedValue>) -> Vec<String> {
+    value
+        .and_then(|value| Vec::<String>::try_from(value.try_clone().ok()?).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zvariant::{Str, Value};
+
+    fn device(
+        alias: &str,
+        address: &str,
+        paired: bool,
+        uuids: &[&str],
+    ) -> HashMap<String, OwnedValue> {
+        let uuids: Vec<String> = uuids.iter().map(|uuid| uuid.to_string()).collect();
+        HashMap::from([
+            ("Alias".to_string(), OwnedValue::from(Str::from(alias))),
+            ("Address".to_string(), OwnedValue::from(Str::from(address))),
+            ("Paired".to_string(), OwnedValue::from(paired)),
+            (
+                "UUIDs".to_string(),
+                OwnedValue::try_from(Value::from(uuids)).unwrap(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn lists_only_paired_devices_offering_nap() {
+        let path = |p: &str| OwnedObjectPath::try_from(p).unwrap();
+        let objects: ManagedObjects = HashMap::from([
+            (
+                path("/org/bluez/hci0/dev_1"),
+                HashMap::from([(
+                    BLUEZ_DEVICE_IFACE.to_string(),
+                    device("Pixel", "AA:BB:CC:DD:EE:01", true, &[NAP_UUID]),
+                )]),
+            ),
+            (
+                path("/org/bluez/hci0/dev_2"),
+                HashMap::from([(
+                    BLUEZ_DEVICE_IFACE.to_string(),
+                    device(
+                        "Headphones",
+                        "AA:BB:CC:DD:EE:02",
+                        true,
+                        &["0000110b-0000-1000-8000-00805f9b34fb"],
+                    ),
+                )]),
+            ),
+            (
+                path("/org/bluez/hci0/dev_3"),
+                HashMap::from([(
+                    BLUEZ_DEVICE_IFACE.to_string(),
+                    device("Stranger", "AA:BB:CC:DD:EE:03", false, &[NAP_UUID]),
+                )]),
+            ),
+        ]);
+
+        assert_eq!(
+            parse_pan_devices(&objects),
+            vec![PanDevice {
+                name: "Pixel".to_string(),
+                address: "AA:BB:CC:DD:EE:01".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,406 @@
+// * ./src/captive_portal.rs
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::{sleep, timeout, Duration};
+
+use crate::nm::{self, InternetConnectivity};
+
+// * Plain HTTP page that captive portals reliably intercept.
+pub const DEFAULT_PORTAL_URL: &str = "http://neverssl.com/";
+
+const CONNECTIVITY_POLL_ATTEMPTS: u32 = 5;
+const CONNECTIVITY_POLL_INTERVAL_SECS: u64 = 2;
+const FORM_SUBMIT_TIMEOUT_SECS: u64 = 15;
+
+// ! Stored unencrypted in captive-portals.json; only ever a single static form POST.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct PortalForm {
+    pub action_url: String,
+    #[serde(default)]
+    pub fields: Vec<(String, String)>,
+    // * Anyone can broadcast the same SSID, so unless this is set the user confirms each submit
+    #[serde(default)]
+    pub auto_submit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct CaptivePortalEntry {
+    pub ssid: String,
+    #[serde(default)]
+    pub portal_url: Option<String>,
+    #[serde(default)]
+    pub auto_open: bool,
+    #[serde(default)]
+    pub form: Option<PortalForm>,
+    #[serde(default)]
+    pub last_seen: Option<i64>,
+}
+
+impl CaptivePortalEntry {
+    pub fn sign_in_url(&self) -> &str {
+        self.portal_url.as_deref().unwrap_or(DEFAULT_PORTAL_URL)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortalOutcome {
+    NoPortal,
+    FormSubmitted(String),
+    ConfirmForm(CaptivePortalEntry),
+    OpenSignIn(String),
+    Remembered(String),
+}
+
+pub fn captive_portals_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".config/adw-network/captive-portals.json"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-captive-portals.json"))
+}
+
+pub async fn load_portal_entries(path: &Path) -> Result<Vec<CaptivePortalEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let mut entries: Vec<CaptivePortalEntry> = serde_json::from_str(&content)?;
+    normalize_portal_entries(&mut entries);
+    Ok(entries)
+}
+
+pub async fn save_portal_entries(path: &Path, entries: &[CaptivePortalEntry]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let mut to_save = entries.to_vec();
+    normalize_portal_entries(&mut to_save);
+    let json = serde_json::to_string_pretty(&to_save)?;
+
+    // * Remembered form fields may include a room number or voucher code, so the file is
+    // * created 0600 from the start and renamed over the old one instead of chmod-ed after.
+    let partial = path.with_extension("json.partial");
+    let _ = fs::remove_file(&partial).await;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&partial).await?;
+    file.write_all(json.as_bytes()).await?;
+    file.sync_all().await?;
+    drop(file);
+    fs::rename(&partial, path).await?;
+    Ok(())
+}
+
+pub async fn portal_entry_for_ssid(ssid: &str) -> Result<Option<CaptivePortalEntry>> {
+    let entries = load_portal_entries(&captive_portals_path()).await?;
+    Ok(entries.into_iter().find(|e| e.ssid == ssid))
+}
+
+// * NM doesn't expose the portal's address, so use a remembered one or a page portals intercept.
+pub async fn sign_in_url_for(ssid: Option<&str>) -> String {
+    let entry = match ssid {
+        Some(ssid) => portal_entry_for_ssid(ssid).await.ok().flatten(),
+        None => None,
+    };
+    entry
+        .map(|entry| entry.sign_in_url().to_string())
+        .unwrap_or_else(|| DEFAULT_PORTAL_URL.to_string())
+}
+
+pub async fn store_portal_entry(entry: CaptivePortalEntry) -> Result<()> {
+    let path = captive_portals_path();
+    let mut entries = load_portal_entries(&path).await?;
+    entries.retain(|e| e.ssid != entry.ssid);
+    entries.push(entry);
+    save_portal_entries(&path, &entries).await
+}
+
+pub async fn forget_portal_entry(ssid: &str) -> Result<()> {
+    let path = captive_portals_path();
+    let mut entries = load_portal_entries(&path).await?;
+    entries.retain(|e| e.ssid != ssid);
+    save_portal_entries(&path, &entries).await
+}
+
+// * Called after connecting. NM's connectivity check needs a few seconds to settle.
+pub async fn handle_portal_after_connect(ssid: &str) -> Result<PortalOutcome> {
+    let mut portal = false;
+    for attempt in 0..CONNECTIVITY_POLL_ATTEMPTS {
+        match nm::get_internet_connectivity().await? {
+            InternetConnectivity::Portal => {
+                portal = true;
+                break;
+            }
+            InternetConnectivity::Full => break,
+            _ if attempt + 1 < CONNECTIVITY_POLL_ATTEMPTS => {
+                sleep(Duration::from_secs(CONNECTIVITY_POLL_INTERVAL_SECS)).await;
+            }
+            _ => {}
+        }
+    }
+    if !portal {
+        return Ok(PortalOutcome::NoPortal);
+    }
+
+    let existing = portal_entry_for_ssid(ssid).await?;
+    let first_seen = existing.is_none();
+    let mut entry = existing.unwrap_or_else(|| CaptivePortalEntry {
+        ssid: ssid.to_string(),
+        auto_open: true,
+        ..Default::default()
+    });
+    entry.last_seen = Some(chrono::Utc::now().timestamp());
+    store_portal_entry(entry.clone()).await?;
+
+    if let Some(form) = entry.form.as_ref() {
+        if !form.auto_submit {
+            return Ok(PortalOutcome::ConfirmForm(entry));
+        }
+        match submit_portal_form(form).await {
+            Ok(()) => {
+                nm::check_connectivity().await.ok();
+                return Ok(PortalOutcome::FormSubmitted(ssid.to_string()));
+            }
+            Err(e) => log::warn!("Remembered portal form for {} failed: {}", ssid, e),
+        }
+    }
+
+    if entry.auto_open || first_seen {
+        Ok(PortalOutcome::OpenSignIn(entry.sign_in_url().to_string()))
+    } else {
+        Ok(PortalOutcome::Remembered(ssid.to_string()))
+    }
+}
+
+pub async fn submit_portal_form(form: &PortalForm) -> Result<()> {
+    validate_form_url(&form.action_url)?;
+    let body = encode_form_body(&form.fields);
+
+    // * Body goes through stdin so field values never show up in the process list
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--max-redirs",
+            "3",
+            "--output",
+            "/dev/null",
+            "--header",
+            "Content-Type: application/x-www-form-urlencoded",
+            "--data-binary",
+            "@-",
+            "--",
+            form.action_url.as_str(),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run curl: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(body.as_bytes()).await?;
+    }
+
+    let output = timeout(
+        Duration::from_secs(FORM_SUBMIT_TIMEOUT_SECS),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| anyhow!("Portal sign-in timed out"))??;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Portal sign-in failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub fn validate_form_url(url: &str) -> Result<()> {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(|| anyhow!("Sign-in URL must start with http:// or https://"))?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(anyhow!("Invalid sign-in URL: {}", url));
+    }
+    Ok(())
+}
+
+// * One "name=value" pair per line; values are kept exactly as typed, commas and spaces included.
+pub fn parse_form_fields(input: &str) -> Result<Vec<(String, String)>> {
+    input
+        .lines()
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| {
+            let (name, value) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected name=value, got: {}", item))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(anyhow!("Missing field name in: {}", item));
+            }
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+pub fn format_form_fields(fields: &[(String, String)]) -> String {
+    fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn encode_form_body(fields: &[(String, String)]) -> String {
+    fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", form_urlencode(name), form_urlencode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn form_urlencode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'*' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn normalize_portal_entries(entries: &mut Vec<CaptivePortalEntry>) {
+    for entry in entries.iter_mut() {
+        entry.portal_url = entry
+            .portal_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string);
+        if entry
+            .form
+            .as_ref()
+            .is_some_and(|form| form.action_url.trim().is_empty())
+        {
+            entry.form = None;
+        }
+    }
+
+    entries.retain(|e| !e.ssid.is_empty());
+    entries.sort_by(|a, b| a.ssid.cmp(&b.ssid));
+    entries.dedup_by(|a, b| a.ssid == b.ssid);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_encodes_form_fields() {
+        let fields = parse_form_fields("accept=yes\n room =204\n\nname=J Doe&Co").unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("accept".to_string(), "yes".to_string()),
+                ("room".to_string(), "204".to_string()),
+                ("name".to_string(), "J Doe&Co".to_string()),
+            ]
+        );
+        assert_eq!(
+            encode_form_body(&fields),
+            "accept=yes&room=204&name=J+Doe%26Co"
+        );
+        assert!(parse_form_fields("novalue").is_err());
+        assert!(parse_form_fields("=value").is_err());
+    }
+
+    #[test]
+    fn form_fields_round_trip_with_commas_and_spaces() {
+        let fields = vec![
+            ("name".to_string(), "Doe, Jane".to_string()),
+            ("code".to_string(), " a=b ".to_string()),
+            ("empty".to_string(), String::new()),
+        ];
+        assert_eq!(
+            parse_form_fields(&format_form_fields(&fields)).unwrap(),
+            fields
+        );
+    }
+
+    #[test]
+    fn validates_sign_in_urls() {
+        assert!(validate_form_url("http://10.0.0.1/login").is_ok());
+        assert!(validate_form_url("https://portal.example/accept?x=1").is_ok());
+        assert!(validate_form_url("ftp://portal.example").is_err());
+        assert!(validate_form_url("http:///login").is_err());
+        assert!(validate_form_url("http://portal example").is_err());
+    }
+
+    #[test]
+    fn remembered_forms_need_opting_in_to_auto_submit() {
+        let form: PortalForm =
+            serde_json::from_str(r#"{"action_url":"http://10.0.0.1/login","fields":[]}"#).unwrap();
+        assert!(!form.auto_submit);
+    }
+
+    #[test]
+    fn drops_forms_without_action_url() {
+        let mut entries = vec![CaptivePortalEntry {
+            ssid: "Cafe".to_string(),
+            portal_url: Some("  ".to_string()),
+            form: Some(PortalForm {
+                action_url: " ".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+
+        normalize_portal_entries(&mut entries);
+
+        assert_eq!(entries[0].portal_url, None);
+        assert_eq!(entries[0].form, None);
+        assert_eq!(entries[0].sign_in_url(), DEFAULT_PORTAL_URL);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saved_entries_are_private_even_over_an_old_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("captive-portals.json");
+        std::fs::write(&path, "[]").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let entries = vec![CaptivePortalEntry {
+            ssid: "Cafe".to_string(),
+            ..Default::default()
+        }];
+        tokio_test::block_on(save_portal_entries(&path, &entries)).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let loaded = tokio_test::block_on(load_portal_entries(&path)).unwrap();
+        assert_eq!(loaded[0].ssid, "Cafe");
+    }
+}
@@ -0,0 +1,431 @@
+// * ./src/cli.rs
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+use nix::sys::signal::{self, SaFlags, SigAction, SigHandler, SigSet, Signal};
+use nix::sys::termios::{self, LocalFlags, SetArg, Termios};
+use std::io::{BufRead, IsTerminal, Stdin, Write};
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+
+use crate::config;
+use crate::hotspot;
+use crate::nm;
+use crate::qr;
+use crate::qr_dialog;
+
+// * Without a subcommand the GTK window starts as usual.
+#[derive(Debug, Parser)]
+#[command(
+    name = "adwaita-network",
+    version,
+    about = "Modern network management for GNOME"
+)]
+pub struct Cli {
+    #[arg(
+        long,
+        help = "Open a small window with the Wi-Fi and hotspot toggles instead of the full app"
+    )]
+    pub compact: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(about = "Scan for and join Wi-Fi networks")]
+    Wifi {
+        #[command(subcommand)]
+        command: WifiCommand,
+    },
+    #[command(about = "Start or stop the hotspot using the saved hotspot settings")]
+    Hotspot {
+        #[command(subcommand)]
+        command: HotspotCommand,
+    },
+    #[command(about = "Print a QR code that joins a saved Wi-Fi network")]
+    Qr { ssid: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WifiCommand {
+    #[command(about = "List nearby networks")]
+    List {
+        #[arg(long, help = "Use the last scan results instead of scanning again")]
+        cached: bool,
+    },
+    #[command(about = "Connect to a network, reusing its saved profile when there is one")]
+    Connect {
+        ssid: String,
+        #[arg(
+            long,
+            help = "Read the password for a network that isn't saved yet from standard input"
+        )]
+        password_stdin: bool,
+    },
+    #[command(about = "Disconnect from a network")]
+    Disconnect { ssid: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HotspotCommand {
+    #[command(about = "Start the hotspot")]
+    On {
+        #[arg(
+            short,
+            long,
+            help = "Wi-Fi adapter to share from; defaults to the first one"
+        )]
+        interface: Option<String>,
+    },
+    #[command(about = "Stop the hotspot")]
+    Off,
+    #[command(about = "Show whether the hotspot is on and who is connected")]
+    Status,
+}
+
+pub fn run(command: Command) -> glib::ExitCode {
+    env_logger::init();
+
+    let rt = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("Failed to create Tokio runtime: {}", e);
+            return glib::ExitCode::FAILURE;
+        }
+    };
+    crate::install_requested_backend();
+
+    let result = rt.block_on(async {
+        if let Err(e) = nm::detect_capabilities().await {
+            log::warn!("Failed to detect NetworkManager version: {}", e);
+        }
+        execute(command).await
+    });
+    match result {
+        Ok(()) => glib::ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            glib::ExitCode::FAILURE
+        }
+    }
+}
+
+async fn execute(command: Command) -> Result<()> {
+    match command {
+        Command::Wifi { command } => match command {
+            WifiCommand::List { cached } => list_networks(cached).await,
+            WifiCommand::Connect {
+                ssid,
+                password_stdin,
+            } => connect(&ssid, password_stdin).await,
+            WifiCommand::Disconnect { ssid } => {
+                nm::disconnect_network(&ssid).await?;
+                println!("Disconnected from {}", ssid);
+                Ok(())
+            }
+        },
+        Command::Hotspot { command } => match command {
+            HotspotCommand::On { interface } => start_hotspot(interface).await,
+            HotspotCommand::Off => {
+                hotspot::stop_hotspot().await?;
+                println!("Hotspot stopped");
+                Ok(())
+            }
+            HotspotCommand::Status => hotspot_status().await,
+        },
+        Command::Qr { ssid } => print_qr(&ssid).await,
+    }
+}
+
+async fn list_networks(cached: bool) -> Result<()> {
+    let networks = if cached {
+        nm::cached_networks().await?
+    } else {
+        nm::scan_networks().await?
+    };
+    if networks.is_empty() {
+        println!("No networks found");
+        return Ok(());
+    }
+
+    let width = networks
+        .iter()
+        .map(|network| network.ssid.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!("  {:<width$}  SIGNAL  BAND      SECURITY", "SSID");
+    for network in &networks {
+        let security = if network.secured {
+            network.security_type.as_str()
+        } else {
+            "Open"
+        };
+        println!(
+            "{} {:<width$}  {:>5}%  {:<8}  {}",
+            if network.connected { "*" } else { " " },
+            network.ssid,
+            network.signal,
+            network.band,
+            security,
+        );
+    }
+    Ok(())
+}
+
+// * Secrets never go on the command line, where other users can read them from ps or /proc.
+async fn connect(ssid: &str, password_stdin: bool) -> Result<()> {
+    let password = if password_stdin {
+        Some(read_password_line(&mut std::io::stdin().lock())?)
+    } else {
+        None
+    };
+
+    if password.is_none() && nm::is_network_saved(ssid).await? {
+        nm::activate_saved_connection(ssid).await?;
+    } else {
+        let network = nm::cached_networks()
+            .await?
+            .into_iter()
+            .find(|network| network.ssid == ssid);
+        let security_type = network
+            .as_ref()
+            .map(|network| network.security_type.clone());
+        let secured = network.as_ref().is_some_and(|network| network.secured);
+        let password = match password {
+            Some(password) => Some(password),
+            None if secured => Some(prompt_password(ssid)?),
+            None => None,
+        };
+        match password {
+            Some(password) => {
+                nm::connect_secured_network(ssid, &password, security_type.as_deref()).await?;
+            }
+            None => {
+                nm::connect_open_network(ssid, security_type.as_deref()).await?;
+            }
+        }
+    }
+    println!("Connected to {}", ssid);
+    Ok(())
+}
+
+// * Asks on the terminal with echo turned off, the way passwd and sudo do.
+fn prompt_password(ssid: &str) -> Result<Zeroizing<String>> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        return Err(anyhow!(
+            "{} is secured; pipe its password in with --password-stdin",
+            ssid
+        ));
+    }
+    eprint!("Password for {}: ", ssid);
+    std::io::stderr().flush()?;
+
+    let _echo_off = EchoOff::new(&stdin)?;
+    read_password_line(&mut stdin.lock())
+}
+
+// * Read by the signal handler, which can't take locks; set once before echo goes off.
+static SAVED_TERMIOS: OnceLock<libc::termios> = OnceLock::new();
+const PROMPT_SIGNALS: [Signal; 3] = [Signal::SIGINT, Signal::SIGTERM, Signal::SIGQUIT];
+
+extern "C" fn restore_echo_and_reraise(signal: libc::c_int) {
+    // * Only async-signal-safe calls here: put the terminal back, then die as the signal would
+    unsafe {
+        if let Some(original) = SAVED_TERMIOS.get() {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original);
+        }
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
+
+// * Turns echo back on however the prompt ends: a read error, a panic or Ctrl-C.
+struct EchoOff<'a> {
+    stdin: &'a Stdin,
+    original: Termios,
+    previous: Vec<(Signal, SigAction)>,
+}
+
+impl<'a> EchoOff<'a> {
+    fn new(stdin: &'a Stdin) -> Result<Self> {
+        let original = termios::tcgetattr(stdin).map_err(|e| {
+            anyhow!(
+                "Can't read the password from this terminal ({}); pipe it in with --password-stdin",
+                e
+            )
+        })?;
+        let _ = SAVED_TERMIOS.set(original.clone().into());
+
+        let action = SigAction::new(
+            SigHandler::Handler(restore_echo_and_reraise),
+            SaFlags::empty(),
+            SigSet::empty(),
+        );
+        // * Built first so a failure part way through still puts everything back
+        let mut guard = Self {
+            stdin,
+            original,
+            previous: Vec::new(),
+        };
+        for sig in PROMPT_SIGNALS {
+            // * SAFETY: the handler only makes async-signal-safe libc calls
+            let previous = unsafe { signal::sigaction(sig, &action) }?;
+            guard.previous.push((sig, previous));
+        }
+
+        let mut silent = guard.original.clone();
+        silent.local_flags.remove(LocalFlags::ECHO);
+        silent.local_flags.insert(LocalFlags::ECHONL);
+        termios::tcsetattr(stdin, SetArg::TCSANOW, &silent)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for EchoOff<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = termios::tcsetattr(self.stdin, SetArg::TCSANOW, &self.original) {
+            log::warn!("Failed to turn terminal echo back on: {}", e);
+        }
+        for (sig, action) in &self.previous {
+            // * SAFETY: puts back the disposition that was there before the prompt
+            unsafe { signal::sigaction(*sig, action) }.ok();
+        }
+    }
+}
+
+fn read_password_line(reader: &mut impl BufRead) -> Result<Zeroizing<String>> {
+    let mut line = Zeroizing::new(String::new());
+    reader.read_line(&mut line)?;
+    let password = Zeroizing::new(line.trim_end_matches(['\r', '\n']).to_string());
+    if password.is_empty() {
+        return Err(anyhow!("No password was given"));
+    }
+    Ok(password)
+}
+
+async fn start_hotspot(interface: Option<String>) -> Result<()> {
+    let (ssid, interface) = hotspot::start_saved_hotspot(interface).await?;
+    println!("Hotspot {} started on {}", ssid, interface);
+    Ok(())
+}
+
+async fn hotspot_status() -> Result<()> {
+    if !hotspot::is_hotspot_active().await? {
+        println!("Hotspot is off");
+        return Ok(());
+    }
+
+    let ssid = config::load_config(&config::hotspot_config_path())
+        .await
+        .map(|config| config.ssid)
+        .unwrap_or_default();
+    println!("Hotspot {} is on", ssid);
+    if let Some(ip) = hotspot::get_hotspot_ip().await? {
+        println!("Address: {}", ip);
+    }
+    for client in hotspot::list_connected_clients().await? {
+        println!(
+            "  {}  {}  {}",
+            client.mac,
+            client.ip,
+            client.hostname.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
+
+async fn print_qr(ssid: &str) -> Result<()> {
+    let connection = nm::get_saved_connections()
+        .await?
+        .into_iter()
+        .find(|connection| connection.ssid == ssid)
+        .ok_or_else(|| anyhow!("{} is not a saved network", ssid))?;
+    // * Open networks have no stored secret
+    let password = nm::get_saved_wifi_password(ssid).await?.unwrap_or_default();
+
+    let payload = qr_dialog::wifi_qr_string(ssid, &password, Some(&connection.security_type));
+    println!("{}", qr::render_terminal(&payload)?);
+    println!("{}", ssid);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_subcommands_and_defaults_to_gui() {
+        assert!(Cli::try_parse_from(["adwaita-network"])
+            .unwrap()
+            .command
+            .is_none());
+
+        let cli = Cli::try_parse_from([
+            "adwaita-network",
+            "wifi",
+            "connect",
+            "Cafe",
+            "--password-stdin",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Wifi {
+                command: WifiCommand::Connect { ref ssid, password_stdin: true }
+            }) if ssid == "Cafe"
+        ));
+        assert!(Cli::try_parse_from([
+            "adwaita-network",
+            "wifi",
+            "connect",
+            "Cafe",
+            "-p",
+            "secret"
+        ])
+        .is_err());
+
+        assert!(Cli::try_parse_from(["adwaita-network", "hotspot", "sideways"]).is_err());
+    }
+
+    #[test]
+    fn connect_runs_against_the_mock_backend() {
+        nm::use_backend_on_this_thread(Box::new(crate::nm_mock::MockBackend::demo()));
+
+        tokio_test::block_on(async {
+            // * Saved profile: activated without asking for a password
+            connect("Home", false).await.unwrap();
+            assert_eq!(
+                nm::get_active_wifi_ssid().await.unwrap().as_deref(),
+                Some("Home")
+            );
+
+            // * Open network that isn't saved yet: joined and remembered
+            assert!(!nm::is_network_saved("Cafe Guest").await.unwrap());
+            connect("Cafe Guest", false).await.unwrap();
+            assert!(nm::saved_wifi_ssids().await.unwrap().contains("Cafe Guest"));
+            assert!(nm::get_autoconnect_for_ssid("Cafe Guest").await.unwrap());
+
+            nm::set_autoconnect_for_ssid("Cafe Guest", false)
+                .await
+                .unwrap();
+            assert!(!nm::get_autoconnect_for_ssid("Cafe Guest").await.unwrap());
+
+            nm::delete_connection_by_ssid("Home").await.unwrap();
+            assert!(!nm::is_network_saved("Home").await.unwrap());
+            assert_eq!(
+                nm::get_active_wifi_ssid().await.unwrap().as_deref(),
+                Some("Cafe Guest")
+            );
+        });
+    }
+
+    #[test]
+    fn reads_one_password_line() {
+        let mut input = std::io::Cursor::new("hunter2 \r\nnext line\n");
+        assert_eq!(read_password_line(&mut input).unwrap().as_str(), "hunter2 ");
+        assert!(read_password_line(&mut std::io::Cursor::new("\n")).is_err());
+    }
+}
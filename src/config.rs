@@ -3,6 +3,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::net::Ipv4Addr;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
@@ -14,13 +15,51 @@ pub struct HotspotConfig {
     pub channel: String,
     pub hidden: bool,
     #[serde(default)]
+    pub security: HotspotSecurity,
+    // * Clients reach the internet but not each other
+    #[serde(default)]
+    pub isolate_clients: bool,
+    // * Hotspot's own address with prefix, e.g. "192.168.12.1/24"; None lets NM pick 10.42.x.1/24
+    #[serde(default)]
+    pub ipv4_address: Option<String>,
+    #[serde(default)]
+    pub dhcp_range_start: Option<String>,
+    #[serde(default)]
+    pub dhcp_range_end: Option<String>,
+    #[serde(default)]
+    pub dhcp_reservations: Vec<HotspotDhcpReservation>,
+    #[serde(default)]
     pub upload_limit_kbps: Option<u32>,
     #[serde(default)]
     pub download_limit_kbps: Option<u32>,
     #[serde(default)]
     pub max_connected_devices: Option<u32>,
+    // * Turn the hotspot off after this many minutes without connected devices
+    #[serde(default)]
+    pub idle_timeout_minutes: Option<u32>,
+    // * Replace the password with a generated one this often while the app runs
+    #[serde(default)]
+    pub password_rotation_hours: Option<u32>,
+    #[serde(default)]
+    pub schedule: Vec<HotspotScheduleRule>,
+    // * Adapter picked on the hotspot page; starts without an explicit one reuse it
+    #[serde(default)]
+    pub interface: Option<String>,
+    #[serde(default)]
+    pub start_on_launch: bool,
+    // * Connection whose internet the hotspot shares; None follows the default route
+    #[serde(default)]
+    pub upstream_interface: Option<String>,
+    #[serde(default)]
+    pub mode: HotspotMode,
+    // * Network a repeater joins and rebroadcasts
+    #[serde(default)]
+    pub repeater_ssid: Option<String>,
     #[serde(default)]
     pub mac_filter_mode: HotspotMacFilterMode,
+    // * MACs the filter mode applies to: the only ones let in, or the ones kept out
+    #[serde(default)]
+    pub mac_filter_list: Vec<String>,
     #[serde(default)]
     pub client_rules: Vec<HotspotClientRule>,
 }
@@ -34,12 +73,20 @@ pub struct AppSettings {
     pub expand_connected_details: bool,
     #[serde(default = "default_icons_only_navigation")]
     pub icons_only_navigation: bool,
+    #[serde(default = "default_enforce_minimum_window_size")]
+    pub enforce_minimum_window_size: bool,
     #[serde(default = "default_hotspot_password_storage")]
     pub hotspot_password_storage: HotspotPasswordStorage,
     #[serde(default = "default_hotspot_quota_reset_policy")]
     pub hotspot_quota_reset_policy: HotspotQuotaResetPolicy,
+    #[serde(default)]
+    pub hotspot_backend: HotspotBackend,
     #[serde(default = "default_plain_json_debug_opt_in")]
     pub plain_json_debug_opt_in: bool,
+    #[serde(default = "default_backup_hotspot_password_to_keyring")]
+    pub backup_hotspot_password_to_keyring: bool,
+    #[serde(default = "default_status_pill_action")]
+    pub status_pill_action: StatusPillAction,
     #[serde(default = "default_module_layout_customized")]
     pub module_layout_customized: bool,
     #[serde(default = "default_show_wifi_module")]
@@ -54,6 +101,30 @@ pub struct AppSettings {
     pub show_profiles_module: bool,
     #[serde(default = "default_module_order")]
     pub module_order: Vec<String>,
+    // * Upper bound for a single NetworkManager call or helper command before it is abandoned.
+    #[serde(default = "default_operation_timeout_secs")]
+    pub operation_timeout_secs: u64,
+    // * Off by default: looking up the public address contacts a third-party service.
+    #[serde(default)]
+    pub show_public_ip: bool,
+    #[serde(default = "default_public_ip_lookup_url")]
+    pub public_ip_lookup_url: String,
+    #[serde(default)]
+    pub show_tray_icon: bool,
+    // * Only takes effect while the tray icon is shown, so the window can always be brought back.
+    #[serde(default = "default_close_to_tray")]
+    pub close_to_tray: bool,
+    #[serde(default = "default_notify_enabled")]
+    pub notify_on_connect: bool,
+    #[serde(default = "default_notify_enabled")]
+    pub notify_on_disconnect: bool,
+    #[serde(default = "default_notify_enabled")]
+    pub notify_on_hotspot_client: bool,
+    #[serde(default = "default_notify_enabled")]
+    pub notify_on_captive_portal: bool,
+    // * View stack name of the page shown at launch; None picks the first visible module.
+    #[serde(default)]
+    pub start_page: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -65,6 +136,68 @@ pub enum HotspotMacFilterMode {
     Blocklist,
 }
 
+// * Nat gives clients their own subnet; Bridged puts them on the wired upstream's LAN;
+// * Repeater joins `repeater_ssid` over Wi-Fi and shares it like Nat.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HotspotMode {
+    #[default]
+    Nat,
+    Bridged,
+    Repeater,
+}
+
+// * Parsed addressing for the hotspot's shared network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotspotSubnet {
+    pub gateway: Ipv4Addr,
+    pub prefix: u8,
+    pub dhcp_range: Option<(Ipv4Addr, Ipv4Addr)>,
+}
+
+impl HotspotSubnet {
+    fn mask(&self) -> u32 {
+        u32::MAX << (32 - u32::from(self.prefix))
+    }
+
+    pub fn contains(&self, address: Ipv4Addr) -> bool {
+        u32::from(address) & self.mask() == u32::from(self.gateway) & self.mask()
+    }
+
+    // * Addresses handed out over DHCP: the configured range, or the subnet past its first hosts.
+    pub fn dhcp_pool(&self) -> (Ipv4Addr, Ipv4Addr) {
+        self.dhcp_range.unwrap_or_else(|| {
+            let network = u32::from(self.gateway) & self.mask();
+            let broadcast = network | !self.mask();
+            let mut first = if self.prefix <= 28 {
+                network + 10
+            } else {
+                network + 1
+            };
+            if first == u32::from(self.gateway) {
+                first += 1;
+            }
+            (Ipv4Addr::from(first), Ipv4Addr::from(broadcast - 1))
+        })
+    }
+
+    // * Network and broadcast addresses can't be handed to a host
+    fn is_host(&self, address: Ipv4Addr) -> bool {
+        let host_bits = u32::from(address) & !self.mask();
+        self.contains(address) && host_bits != 0 && host_bits != !self.mask()
+    }
+}
+
+// * WPA3 modes need SAE in the adapter's driver; mixed mode still lets WPA2-only devices join.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HotspotSecurity {
+    #[default]
+    Wpa2,
+    Wpa3,
+    Wpa2Wpa3,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum HotspotQuotaResetPolicy {
@@ -73,6 +206,26 @@ pub enum HotspotQuotaResetPolicy {
     DailyMidnight,
 }
 
+// * Hostapd drives hostapd and dnsmasq directly, for drivers where NM's AP mode doesn't work.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HotspotBackend {
+    #[default]
+    NetworkManager,
+    Hostapd,
+}
+
+// * What clicking the header status pill does.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusPillAction {
+    #[default]
+    ShowWifiPage,
+    RefreshAll,
+    ToggleDetails,
+    ShowConnectionDetails,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct HotspotClientRule {
     pub mac_address: String,
@@ -94,6 +247,112 @@ pub struct HotspotClientRule {
     pub blocked_domains: Vec<String>,
 }
 
+// * A fixed address for one hotspot client, handed out by NM's dnsmasq.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HotspotDhcpReservation {
+    pub mac_address: String,
+    pub ip_address: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl HotspotDhcpReservation {
+    // * Normalizes editor input; the name becomes the client's hostname, so it's held to DNS rules.
+    pub fn parse(mac: &str, ip: &str, name: &str) -> Result<Self> {
+        let mac_address = normalize_mac_address(mac)
+            .ok_or_else(|| anyhow::anyhow!("Invalid MAC address: {}", mac.trim()))?;
+        let ip_address = ip
+            .trim()
+            .parse::<Ipv4Addr>()
+            .map_err(|_| anyhow::anyhow!("Invalid IPv4 address: {}", ip.trim()))?
+            .to_string();
+        let name = name.trim();
+        let valid_name = name.len() <= 63
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid_name {
+            anyhow::bail!("Name may only use letters, digits and inner hyphens");
+        }
+        Ok(Self {
+            mac_address,
+            ip_address,
+            name: (!name.is_empty()).then(|| name.to_string()),
+        })
+    }
+}
+
+const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// * Keeps the hotspot on from `start` to `end` (local "HH:MM") on the listed days.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HotspotScheduleRule {
+    // * 0 is Monday, 6 is Sunday
+    pub days: Vec<u8>,
+    pub start: String,
+    pub end: String,
+}
+
+fn parse_clock_time(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+impl HotspotScheduleRule {
+    pub fn parse(days: &[u8], start: &str, end: &str) -> Result<Self> {
+        let mut days: Vec<u8> = days.iter().copied().filter(|day| *day < 7).collect();
+        days.sort_unstable();
+        days.dedup();
+        if days.is_empty() {
+            anyhow::bail!("Pick at least one day");
+        }
+        let start_minute = parse_clock_time(start)
+            .ok_or_else(|| anyhow::anyhow!("Invalid start time: {}", start.trim()))?;
+        let end_minute = parse_clock_time(end)
+            .ok_or_else(|| anyhow::anyhow!("Invalid end time: {}", end.trim()))?;
+        if start_minute == end_minute {
+            anyhow::bail!("Start and end time must differ");
+        }
+        let format = |minute: u32| format!("{:02}:{:02}", minute / 60, minute % 60);
+        Ok(Self {
+            days,
+            start: format(start_minute),
+            end: format(end_minute),
+        })
+    }
+
+    // * An end before the start runs past midnight into the next day
+    pub fn covers(&self, weekday: u8, minute_of_day: u32) -> bool {
+        let (Some(start), Some(end)) = (parse_clock_time(&self.start), parse_clock_time(&self.end))
+        else {
+            return false;
+        };
+        if start < end {
+            return self.days.contains(&weekday) && (start..end).contains(&minute_of_day);
+        }
+        let previous_day = (weekday + 6) % 7;
+        (self.days.contains(&weekday) && minute_of_day >= start)
+            || (self.days.contains(&previous_day) && minute_of_day < end)
+    }
+
+    pub fn describe(&self) -> String {
+        let days = match self.days.as_slice() {
+            [0, 1, 2, 3, 4, 5, 6] => "Every day".to_string(),
+            [0, 1, 2, 3, 4] => "Weekdays".to_string(),
+            [5, 6] => "Weekends".to_string(),
+            days => days
+                .iter()
+                .filter_map(|day| WEEKDAY_NAMES.get(*day as usize))
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        format!("{} {}–{}", days, self.start, self.end)
+    }
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -101,9 +360,13 @@ impl Default for AppSettings {
             auto_scan: true,
             expand_connected_details: false,
             icons_only_navigation: true,
+            enforce_minimum_window_size: true,
             hotspot_password_storage: HotspotPasswordStorage::Keyring,
             hotspot_quota_reset_policy: HotspotQuotaResetPolicy::Never,
+            hotspot_backend: HotspotBackend::default(),
             plain_json_debug_opt_in: false,
+            backup_hotspot_password_to_keyring: true,
+            status_pill_action: StatusPillAction::ShowWifiPage,
             module_layout_customized: false,
             show_wifi_module: true,
             show_ethernet_module: true,
@@ -111,6 +374,16 @@ impl Default for AppSettings {
             show_devices_module: false,
             show_profiles_module: true,
             module_order: default_module_order(),
+            operation_timeout_secs: default_operation_timeout_secs(),
+            show_public_ip: false,
+            public_ip_lookup_url: default_public_ip_lookup_url(),
+            show_tray_icon: false,
+            close_to_tray: default_close_to_tray(),
+            notify_on_connect: default_notify_enabled(),
+            notify_on_disconnect: default_notify_enabled(),
+            notify_on_hotspot_client: default_notify_enabled(),
+            notify_on_captive_portal: default_notify_enabled(),
+            start_page: None,
         }
     }
 }
@@ -193,10 +466,22 @@ fn default_icons_only_navigation() -> bool {
     true
 }
 
+fn default_enforce_minimum_window_size() -> bool {
+    true
+}
+
 fn default_plain_json_debug_opt_in() -> bool {
     false
 }
 
+fn default_backup_hotspot_password_to_keyring() -> bool {
+    true
+}
+
+fn default_status_pill_action() -> StatusPillAction {
+    StatusPillAction::ShowWifiPage
+}
+
 fn default_module_layout_customized() -> bool {
     false
 }
@@ -221,6 +506,22 @@ fn default_show_profiles_module() -> bool {
     true
 }
 
+fn default_operation_timeout_secs() -> u64 {
+    45
+}
+
+fn default_public_ip_lookup_url() -> String {
+    "https://ipinfo.io/json".to_string()
+}
+
+fn default_close_to_tray() -> bool {
+    true
+}
+
+fn default_notify_enabled() -> bool {
+    true
+}
+
 fn default_module_order() -> Vec<String> {
     vec![
         "Wi-Fi".to_string(),
@@ -262,10 +563,25 @@ impl Default for HotspotConfig {
             band: "Auto".to_string(),
             channel: "Auto".to_string(),
             hidden: false,
+            security: HotspotSecurity::Wpa2,
+            isolate_clients: false,
+            ipv4_address: None,
+            dhcp_range_start: None,
+            dhcp_range_end: None,
+            dhcp_reservations: Vec::new(),
             upload_limit_kbps: None,
             download_limit_kbps: None,
             max_connected_devices: None,
+            idle_timeout_minutes: None,
+            password_rotation_hours: None,
+            schedule: Vec::new(),
+            interface: None,
+            start_on_launch: false,
+            upstream_interface: None,
+            mode: HotspotMode::default(),
+            repeater_ssid: None,
             mac_filter_mode: HotspotMacFilterMode::Disabled,
+            mac_filter_list: Vec::new(),
             client_rules: Vec::new(),
         }
     }
@@ -313,6 +629,38 @@ impl HotspotConfig {
             .sort_by(|a, b| a.mac_address.cmp(&b.mac_address));
         self.client_rules
             .dedup_by(|a, b| a.mac_address == b.mac_address);
+        self.dhcp_reservations
+            .sort_by(|a, b| a.mac_address.cmp(&b.mac_address));
+        self.dhcp_reservations
+            .dedup_by(|a, b| a.mac_address == b.mac_address);
+        let mut mac_filter_list: Vec<String> = self
+            .mac_filter_list
+            .iter()
+            .filter_map(|mac| normalize_mac_address(mac))
+            .collect();
+        mac_filter_list.sort();
+        mac_filter_list.dedup();
+        self.mac_filter_list = mac_filter_list;
+    }
+
+    // * None without a schedule; otherwise whether any rule wants the hotspot on right now
+    pub fn scheduled_on_at(&self, weekday: u8, minute_of_day: u32) -> Option<bool> {
+        if self.schedule.is_empty() {
+            return None;
+        }
+        Some(
+            self.schedule
+                .iter()
+                .any(|rule| rule.covers(weekday, minute_of_day)),
+        )
+    }
+
+    pub fn is_6ghz(&self) -> bool {
+        self.band.trim() == "6 GHz"
+    }
+
+    pub fn is_mac_filter_listed(&self, mac_address: &str) -> bool {
+        normalize_mac_address(mac_address).is_some_and(|mac| self.mac_filter_list.contains(&mac))
     }
 
     pub fn validate_ssid(&self) -> Result<()> {
@@ -347,12 +695,135 @@ impl HotspotConfig {
         Ok(())
     }
 
+    // * None when NM should choose the subnet; errors describe the first field that's wrong.
+    pub fn subnet(&self) -> Result<Option<HotspotSubnet>> {
+        let field = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        let range = match (field(&self.dhcp_range_start), field(&self.dhcp_range_end)) {
+            (None, None) => None,
+            (Some(start), Some(end)) => Some((start, end)),
+            _ => anyhow::bail!("DHCP range needs both a start and an end address"),
+        };
+        let Some(address) = field(&self.ipv4_address) else {
+            if range.is_some() {
+                anyhow::bail!("Set the hotspot address before a DHCP range");
+            }
+            return Ok(None);
+        };
+
+        let (gateway, prefix) = address.split_once('/').unwrap_or((address.as_str(), "24"));
+        let gateway: Ipv4Addr = gateway
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Hotspot address must be an IPv4 address"))?;
+        let prefix: u8 = prefix
+            .trim()
+            .parse()
+            .ok()
+            .filter(|prefix| (16..=30).contains(prefix))
+            .ok_or_else(|| anyhow::anyhow!("Hotspot prefix must be between 16 and 30"))?;
+        if !gateway.is_private() {
+            anyhow::bail!(
+                "Hotspot address must be in a private range (10.x, 172.16-31.x, 192.168.x)"
+            );
+        }
+        let mut subnet = HotspotSubnet {
+            gateway,
+            prefix,
+            dhcp_range: None,
+        };
+        if !subnet.is_host(gateway) {
+            anyhow::bail!("Hotspot address can't be the network or broadcast address");
+        }
+
+        if let Some((start, end)) = range {
+            let parse = |value: &str| {
+                value
+                    .parse::<Ipv4Addr>()
+                    .ok()
+                    .filter(|address| subnet.is_host(*address))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "DHCP address {} is not inside {}/{}",
+                            value,
+                            gateway,
+                            prefix
+                        )
+                    })
+            };
+            let (start, end) = (parse(&start)?, parse(&end)?);
+            if start > end {
+                anyhow::bail!("DHCP range start must come before its end");
+            }
+            if (start..=end).contains(&gateway) {
+                anyhow::bail!(
+                    "DHCP range must not include the hotspot address {}",
+                    gateway
+                );
+            }
+            subnet.dhcp_range = Some((start, end));
+        }
+        Ok(Some(subnet))
+    }
+
+    fn validate_reservations(&self, subnet: Option<HotspotSubnet>) -> Result<()> {
+        let mut macs = HashSet::new();
+        let mut ips = HashSet::new();
+        for reservation in &self.dhcp_reservations {
+            let parsed = HotspotDhcpReservation::parse(
+                &reservation.mac_address,
+                &reservation.ip_address,
+                reservation.name.as_deref().unwrap_or_default(),
+            )?;
+            let ip: Ipv4Addr = parsed.ip_address.parse()?;
+            if let Some(subnet) = subnet {
+                if !subnet.is_host(ip) || ip == subnet.gateway {
+                    anyhow::bail!(
+                        "Reserved address {} is not a client address in {}/{}",
+                        ip,
+                        subnet.gateway,
+                        subnet.prefix
+                    );
+                }
+            }
+            if !macs.insert(parsed.mac_address.clone()) {
+                anyhow::bail!("{} has more than one reservation", parsed.mac_address);
+            }
+            if !ips.insert(ip) {
+                anyhow::bail!("{} is reserved more than once", ip);
+            }
+        }
+        Ok(())
+    }
+
     pub fn validate(&self) -> Result<()> {
         self.validate_ssid()?;
         self.validate_password()?;
+        let subnet = self.subnet()?;
+        self.validate_reservations(subnet)?;
         Self::validate_limit(self.upload_limit_kbps, "Upload limit")?;
         Self::validate_limit(self.download_limit_kbps, "Download limit")?;
         Self::validate_limit(self.max_connected_devices, "Device limit")?;
+        Self::validate_limit(self.idle_timeout_minutes, "Idle timeout")?;
+        Self::validate_limit(self.password_rotation_hours, "Password rotation interval")?;
+        for rule in &self.schedule {
+            HotspotScheduleRule::parse(&rule.days, &rule.start, &rule.end)?;
+        }
+        if self.mode == HotspotMode::Bridged && self.upstream_interface.is_none() {
+            anyhow::bail!("Bridged mode needs a wired internet source");
+        }
+        if self.mode == HotspotMode::Repeater && self.repeater_ssid.is_none() {
+            anyhow::bail!("Repeater mode needs a network to extend");
+        }
+        // * Wi-Fi 6E forbids WPA2 and open networks without OWE
+        if self.is_6ghz() && (self.security != HotspotSecurity::Wpa3 || self.password.is_empty()) {
+            anyhow::bail!("6 GHz hotspots need a password and WPA3 security");
+        }
 
         for rule in &self.client_rules {
             if normalize_mac_address(&rule.mac_address).is_none() {
@@ -611,6 +1082,57 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn parses_custom_hotspot_subnet_and_dhcp_range() {
+        let mut config = HotspotConfig {
+            ipv4_address: Some("192.168.12.1/24".to_string()),
+            dhcp_range_start: Some("192.168.12.100".to_string()),
+            dhcp_range_end: Some("192.168.12.150".to_string()),
+            ..HotspotConfig::default()
+        };
+        let subnet = config.subnet().unwrap().unwrap();
+        assert_eq!(subnet.prefix, 24);
+        assert_eq!(
+            subnet.dhcp_range,
+            Some((
+                Ipv4Addr::new(192, 168, 12, 100),
+                Ipv4Addr::new(192, 168, 12, 150)
+            ))
+        );
+
+        config.dhcp_range_end = Some("192.168.13.150".to_string());
+        assert!(config.subnet().is_err());
+        config.dhcp_range_start = Some("192.168.12.1".to_string());
+        config.dhcp_range_end = Some("192.168.12.20".to_string());
+        assert!(config.subnet().is_err());
+        config.ipv4_address = Some("8.8.8.1/24".to_string());
+        assert!(config.subnet().is_err());
+
+        assert_eq!(HotspotConfig::default().subnet().unwrap(), None);
+    }
+
+    #[test]
+    fn validates_dhcp_reservations() {
+        let reservation =
+            HotspotDhcpReservation::parse("aa-bb-cc-dd-ee-ff", " 192.168.12.20 ", "pi").unwrap();
+        assert_eq!(reservation.mac_address, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(reservation.ip_address, "192.168.12.20");
+        assert!(HotspotDhcpReservation::parse("aa:bb:cc:dd:ee:ff", "10.0.0.2", "my pi").is_err());
+
+        let mut config = HotspotConfig {
+            ssid: "TestNetwork".to_string(),
+            ipv4_address: Some("192.168.12.1/24".to_string()),
+            dhcp_reservations: vec![reservation.clone()],
+            ..HotspotConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        config.dhcp_reservations.push(HotspotDhcpReservation {
+            mac_address: "11:22:33:44:55:66".to_string(),
+            ..reservation
+        });
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_open_network() {
         let config = HotspotConfig {
@@ -643,6 +1165,19 @@ mod tests {
             HotspotPasswordStorage::Keyring
         );
         assert!(!settings.plain_json_debug_opt_in);
+        assert!(settings.backup_hotspot_password_to_keyring);
+        Ok(())
+    }
+
+    #[test]
+    fn test_status_pill_action_serde() -> Result<()> {
+        let settings: AppSettings = serde_json::from_str(
+            r#"{"color_scheme": "dark", "status_pill_action": "refresh-all"}"#,
+        )?;
+        assert_eq!(settings.status_pill_action, StatusPillAction::RefreshAll);
+
+        let legacy: AppSettings = serde_json::from_str(r#"{"color_scheme": "dark"}"#)?;
+        assert_eq!(legacy.status_pill_action, StatusPillAction::ShowWifiPage);
         Ok(())
     }
 
@@ -722,4 +1257,54 @@ mod tests {
         );
         assert!(normalize_mac_address("invalid").is_none());
     }
+
+    #[test]
+    fn evaluates_hotspot_schedules_across_midnight() {
+        let weekdays = HotspotScheduleRule::parse(&[4, 0, 1, 2, 3, 9], "9:00", "17:00").unwrap();
+        assert_eq!(weekdays.days, vec![0, 1, 2, 3, 4]);
+        assert_eq!(weekdays.describe(), "Weekdays 09:00–17:00");
+        assert!(weekdays.covers(0, 9 * 60));
+        assert!(!weekdays.covers(0, 17 * 60));
+        assert!(!weekdays.covers(5, 12 * 60));
+
+        let late = HotspotScheduleRule::parse(&[5], "22:30", "02:00").unwrap();
+        assert_eq!(late.describe(), "Sat 22:30–02:00");
+        assert!(late.covers(5, 23 * 60));
+        assert!(late.covers(6, 60));
+        assert!(!late.covers(6, 23 * 60));
+        assert!(!late.covers(5, 60));
+
+        assert!(HotspotScheduleRule::parse(&[], "09:00", "17:00").is_err());
+        assert!(HotspotScheduleRule::parse(&[0], "24:00", "17:00").is_err());
+        assert!(HotspotScheduleRule::parse(&[0], "09:00", "9:00").is_err());
+
+        let config = HotspotConfig {
+            schedule: vec![weekdays],
+            ..HotspotConfig::default()
+        };
+        assert_eq!(config.scheduled_on_at(2, 10 * 60), Some(true));
+        assert_eq!(config.scheduled_on_at(6, 10 * 60), Some(false));
+        assert_eq!(HotspotConfig::default().scheduled_on_at(2, 10 * 60), None);
+    }
+
+    #[test]
+    fn normalizes_mac_filter_list() {
+        let mut config = HotspotConfig {
+            mac_filter_list: vec![
+                "bb-bb-bb-bb-bb-bb".to_string(),
+                "not a mac".to_string(),
+                "aa:aa:aa:aa:aa:aa".to_string(),
+                "BB:BB:BB:BB:BB:BB".to_string(),
+            ],
+            ..HotspotConfig::default()
+        };
+        config.normalize();
+
+        assert_eq!(
+            config.mac_filter_list,
+            vec!["AA:AA:AA:AA:AA:AA", "BB:BB:BB:BB:BB:BB"]
+        );
+        assert!(!config.is_mac_filter_listed("aabbccddeeff"));
+        assert!(config.is_mac_filter_listed("aa-aa-aa-aa-aa-aa"));
+    }
 }
@@ -0,0 +1,194 @@
+// * ./src/dbus_service.rs
+
+use anyhow::Result;
+use gtk4::glib;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+use zbus::{fdo, interface, Connection};
+use zvariant::{OwnedValue, Str};
+
+use crate::hotspot;
+use crate::nm;
+use crate::tray::{TrayAction, TrayStatus};
+
+pub const SERVICE_NAME: &str = "org.github.adwnetwork";
+pub const OBJECT_PATH: &str = "/org/github/adwnetwork";
+
+fn failed(error: anyhow::Error) -> fdo::Error {
+    fdo::Error::Failed(error.to_string())
+}
+
+fn status_dict(status: &TrayStatus) -> HashMap<String, OwnedValue> {
+    HashMap::from([
+        (
+            "wifi-enabled".to_string(),
+            OwnedValue::from(status.wifi_enabled),
+        ),
+        (
+            "hotspot-active".to_string(),
+            OwnedValue::from(status.hotspot_active),
+        ),
+        (
+            "ssid".to_string(),
+            OwnedValue::from(Str::from(status.ssid.clone().unwrap_or_default())),
+        ),
+    ])
+}
+
+// * NM events fire for every device and property change; only a different status is worth a
+// * signal. Saved networks aren't part of it, so they're left out of the comparison.
+fn record_if_changed(last: &RefCell<Option<TrayStatus>>, mut status: TrayStatus) -> bool {
+    status.saved_networks.clear();
+    if last.borrow().as_ref() == Some(&status) {
+        return false;
+    }
+    *last.borrow_mut() = Some(status);
+    true
+}
+
+// * Requests are handed to the window like tray clicks, so they go through the same page logic.
+struct Service {
+    actions: mpsc::UnboundedSender<TrayAction>,
+}
+
+impl Service {
+    fn send(&self, action: TrayAction) -> fdo::Result<()> {
+        self.actions
+            .send(action)
+            .map_err(|_| fdo::Error::Failed("The application is shutting down".to_string()))
+    }
+}
+
+#[interface(name = "org.github.adwnetwork")]
+impl Service {
+    // * Returns the state the hotspot is being switched to
+    async fn toggle_hotspot(&self) -> fdo::Result<bool> {
+        let active = hotspot::is_hotspot_active().await.map_err(failed)?;
+        self.send(TrayAction::SetHotspotActive(!active))?;
+        Ok(!active)
+    }
+
+    async fn connect_ssid(&self, ssid: String) -> fdo::Result<()> {
+        if !nm::is_network_saved(&ssid).await.map_err(failed)? {
+            return Err(fdo::Error::InvalidArgs(format!(
+                "{} is not a saved network",
+                ssid
+            )));
+        }
+        self.send(TrayAction::ConnectSaved(ssid))
+    }
+
+    async fn get_status(&self) -> HashMap<String, OwnedValue> {
+        status_dict(&TrayStatus::current().await)
+    }
+
+    #[zbus(signal)]
+    async fn status_changed(
+        emitter: &SignalEmitter<'_>,
+        status: HashMap<String, OwnedValue>,
+    ) -> zbus::Result<()>;
+}
+
+async fn serve() -> Result<(Connection, mpsc::UnboundedReceiver<TrayAction>)> {
+    let (actions, receiver) = mpsc::unbounded_channel();
+    let conn = zbus::connection::Builder::session()?
+        .name(SERVICE_NAME)?
+        .serve_at(OBJECT_PATH, Service { actions })?
+        .build()
+        .await?;
+    Ok((conn, receiver))
+}
+
+// * Owns the bus name for as long as the window lives.
+#[derive(Clone, Default)]
+pub struct DbusService {
+    conn: Rc<RefCell<Option<Connection>>>,
+    last_status: Rc<RefCell<Option<TrayStatus>>>,
+}
+
+impl DbusService {
+    pub fn start(on_action: impl Fn(TrayAction) + 'static) -> Self {
+        let service = Self::default();
+
+        let service_ref = service.clone();
+        glib::spawn_future_local(async move {
+            let (conn, mut actions) = match serve().await {
+                Ok(served) => served,
+                Err(e) => {
+                    log::warn!("Failed to export the {} D-Bus service: {}", SERVICE_NAME, e);
+                    return;
+                }
+            };
+            *service_ref.conn.borrow_mut() = Some(conn);
+            while let Some(action) = actions.recv().await {
+                on_action(action);
+            }
+        });
+
+        service
+    }
+
+    pub fn emit_status_changed(&self) {
+        let Some(conn) = self.conn.borrow().clone() else {
+            return;
+        };
+        let last_status = self.last_status.clone();
+        glib::spawn_future_local(async move {
+            let status = TrayStatus::current().await;
+            if !record_if_changed(&last_status, status.clone()) {
+                return;
+            }
+            let status = status_dict(&status);
+            let result = async {
+                let iface = conn
+                    .object_server()
+                    .interface::<_, Service>(OBJECT_PATH)
+                    .await?;
+                Service::status_changed(iface.signal_emitter(), status).await
+            }
+            .await;
+            if let Err(e) = result {
+                log::debug!("Failed to emit StatusChanged: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_dict_uses_empty_ssid_when_disconnected() {
+        let status = TrayStatus {
+            wifi_enabled: true,
+            ..Default::default()
+        };
+        let dict = status_dict(&status);
+
+        assert!(bool::try_from(&dict["wifi-enabled"]).unwrap());
+        assert!(!bool::try_from(&dict["hotspot-active"]).unwrap());
+        assert_eq!(<&str>::try_from(&dict["ssid"]).unwrap(), "");
+    }
+
+    #[test]
+    fn only_a_different_status_is_recorded() {
+        let last = RefCell::new(None);
+        let status = TrayStatus {
+            wifi_enabled: true,
+            ssid: Some("Home".to_string()),
+            ..Default::default()
+        };
+        assert!(record_if_changed(&last, status.clone()));
+        assert!(!record_if_changed(&last, status.clone()));
+
+        let disconnected = TrayStatus {
+            ssid: None,
+            ..status
+        };
+        assert!(record_if_changed(&last, disconnected));
+    }
+}
@@ -0,0 +1,181 @@
+// * ./src/diagnostics.rs
+
+use anyhow::{anyhow, Result};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::nm;
+use crate::reachability::{self, ReachabilityTarget};
+
+// * Resolvers that answer ping; used to tell "no internet" apart from "no DNS".
+const INTERNET_PROBE_HOST: &str = "1.1.1.1";
+const DNS_PROBE_HOST: &str = "example.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Ping,
+    Traceroute,
+    DnsLookup,
+}
+
+impl Tool {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ping => "Ping",
+            Self::Traceroute => "Traceroute",
+            Self::DnsLookup => "DNS Lookup",
+        }
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            Self::Ping => "ping",
+            Self::Traceroute => "traceroute",
+            Self::DnsLookup => "resolvectl",
+        }
+    }
+
+    fn command(self, target: &str) -> Command {
+        let mut command = Command::new(self.program());
+        match self {
+            Self::Ping => command.args(["-c", "4", "--", target]),
+            // * Numeric hops with one probe each keep the output readable and fast
+            Self::Traceroute => command.args(["-n", "-q", "1", "-w", "2", "--", target]),
+            Self::DnsLookup => command.args(["query", "--", target]),
+        };
+        command.env("LC_ALL", "C");
+        command
+    }
+}
+
+// * Runs the tool and forwards each output line as it arrives; Ok(false) when it exited non-zero.
+pub async fn stream(
+    tool: Tool,
+    target: &str,
+    lines: mpsc::UnboundedSender<String>,
+) -> Result<bool> {
+    let mut child = tool
+        .command(target)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow!("{} is not installed", tool.program())
+            } else {
+                anyhow!("Failed to run {}: {}", tool.program(), e)
+            }
+        })?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("No output from {}", tool.program()))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| anyhow!("No output from {}", tool.program()))?;
+    let err_lines = lines.clone();
+    let stderr_task = tokio::spawn(async move {
+        let mut reader = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = reader.next_line().await {
+            let _ = err_lines.send(line);
+        }
+    });
+
+    let mut reader = BufReader::new(stdout).lines();
+    while let Some(line) = reader.next_line().await? {
+        if lines.send(line).is_err() {
+            // ? Nobody is listening anymore; dropping the child kills it
+            return Ok(false);
+        }
+    }
+    let _ = stderr_task.await;
+    Ok(child.wait().await?.success())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathCheck {
+    // * None when there is no gateway to test, e.g. while disconnected
+    pub gateway: Option<bool>,
+    pub internet: bool,
+    pub dns: bool,
+}
+
+impl PathCheck {
+    pub fn summary(&self) -> &'static str {
+        match (self.gateway, self.internet, self.dns) {
+            (None, false, _) => "No gateway is configured. Connect to a network first.",
+            (Some(false), false, _) => {
+                "The router isn't answering. Check the Wi-Fi signal or the cable."
+            }
+            (_, false, _) => {
+                "The router answers but the internet doesn't. The problem is with the router or the provider."
+            }
+            (_, true, false) => {
+                "The internet is reachable but names don't resolve. Try different DNS servers."
+            }
+            (_, true, true) => "Everything looks fine: router, internet and DNS all answer.",
+        }
+    }
+}
+
+// * Checks each hop in order so the summary can say where the path breaks.
+pub async fn check_path() -> PathCheck {
+    let gateway = match nm::default_gateway().await {
+        Ok(Some(gateway)) => Some(ping_ok(&gateway).await),
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("Failed to read the default gateway: {}", e);
+            None
+        }
+    };
+    let (internet, dns) = tokio::join!(ping_ok(INTERNET_PROBE_HOST), resolves(DNS_PROBE_HOST));
+    PathCheck {
+        gateway,
+        internet,
+        dns,
+    }
+}
+
+async fn ping_ok(host: &str) -> bool {
+    let target = ReachabilityTarget {
+        host: host.to_string(),
+        port: None,
+    };
+    reachability::check_reachability(&target).await.reachable
+}
+
+async fn resolves(host: &str) -> bool {
+    let host = host.to_string();
+    tokio::task::spawn_blocking(move || {
+        dns_lookup::lookup_host(&host).is_ok_and(|mut ips| ips.next().is_some())
+    })
+    .await
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_names_the_first_broken_hop() {
+        let check = |gateway, internet, dns| {
+            PathCheck {
+                gateway,
+                internet,
+                dns,
+            }
+            .summary()
+        };
+        assert!(check(Some(false), false, false).starts_with("The router isn't answering"));
+        assert!(check(Some(true), false, false).contains("router or the provider"));
+        assert!(check(Some(true), true, false).contains("names don't resolve"));
+        assert!(check(None, false, false).starts_with("No gateway"));
+        assert!(check(Some(true), true, true).starts_with("Everything looks fine"));
+    }
+}
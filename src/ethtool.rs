@@ -0,0 +1,229 @@
+// * ./src/ethtool.rs
+
+use anyhow::{anyhow, Result};
+use tokio::process::Command;
+
+use crate::nm::command_output;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplex {
+    Half,
+    Full,
+}
+
+impl Duplex {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "half" => Some(Self::Half),
+            "full" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    fn as_ethtool(self) -> &'static str {
+        match self {
+            Self::Half => "half",
+            Self::Full => "full",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Half => "half duplex",
+            Self::Full => "full duplex",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkMode {
+    pub speed_mbps: u32,
+    pub duplex: Duplex,
+}
+
+impl LinkMode {
+    pub fn label(&self) -> String {
+        format!(
+            "{} • {}",
+            format_speed(self.speed_mbps),
+            self.duplex.label()
+        )
+    }
+}
+
+// * What the NIC reports right now; speed and duplex are None while the cable is unplugged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkSettings {
+    pub speed_mbps: Option<u32>,
+    pub duplex: Option<Duplex>,
+    pub autoneg: bool,
+    pub link_detected: bool,
+    pub supported: Vec<LinkMode>,
+}
+
+impl LinkSettings {
+    pub fn summary(&self) -> String {
+        if !self.link_detected {
+            return "No link".to_string();
+        }
+        match (self.speed_mbps, self.duplex) {
+            (Some(speed), Some(duplex)) => format!("{} • {}", format_speed(speed), duplex.label()),
+            (Some(speed), None) => format_speed(speed),
+            _ => "Link up".to_string(),
+        }
+    }
+}
+
+pub fn format_speed(speed_mbps: u32) -> String {
+    if speed_mbps >= 1000 && speed_mbps.is_multiple_of(1000) {
+        format!("{} Gb/s", speed_mbps / 1000)
+    } else {
+        format!("{} Mb/s", speed_mbps)
+    }
+}
+
+pub async fn read_link_settings(iface: &str) -> Result<LinkSettings> {
+    let output = command_output(Command::new("ethtool").arg(iface).env("LC_ALL", "C")).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("ethtool failed: {}", stderr));
+    }
+    Ok(parse_ethtool_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+// * Forcing a mode turns auto-negotiation off; both ends must then agree or the link drops.
+pub async fn force_link_mode(iface: &str, mode: LinkMode) -> Result<()> {
+    let speed = mode.speed_mbps.to_string();
+    run_ethtool_set(&[
+        "-s",
+        iface,
+        "speed",
+        &speed,
+        "duplex",
+        mode.duplex.as_ethtool(),
+        "autoneg",
+        "off",
+    ])
+    .await
+}
+
+pub async fn enable_autonegotiation(iface: &str) -> Result<()> {
+    run_ethtool_set(&["-s", iface, "autoneg", "on"]).await
+}
+
+async fn run_ethtool_set(args: &[&str]) -> Result<()> {
+    let output = command_output(Command::new("ethtool").args(args)).await?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.contains("Operation not permitted") {
+        Err(anyhow!("Changing link settings needs root privileges"))
+    } else if stderr.is_empty() {
+        Err(anyhow!("ethtool command failed"))
+    } else {
+        Err(anyhow!(stderr))
+    }
+}
+
+// * "Supported link modes:" wraps onto indented continuation lines without a key.
+fn parse_ethtool_output(output: &str) -> LinkSettings {
+    let mut settings = LinkSettings::default();
+    let mut in_supported_modes = false;
+
+    for line in output.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (Some(key.trim()), value),
+            None => (None, line),
+        };
+        if key.is_some() {
+            in_supported_modes = key == Some("Supported link modes");
+        }
+
+        match key {
+            Some("Speed") => {
+                settings.speed_mbps = value
+                    .trim()
+                    .strip_suffix("Mb/s")
+                    .and_then(|v| v.parse().ok())
+            }
+            Some("Duplex") => settings.duplex = Duplex::parse(value),
+            Some("Auto-negotiation") => settings.autoneg = value.trim() == "on",
+            Some("Link detected") => settings.link_detected = value.trim() == "yes",
+            _ => {}
+        }
+
+        if in_supported_modes {
+            for mode in value.split_whitespace().filter_map(parse_link_mode) {
+                if !settings.supported.contains(&mode) {
+                    settings.supported.push(mode);
+                }
+            }
+        }
+    }
+
+    // * Fastest first, full duplex before half
+    settings.supported.sort_by(|a, b| {
+        b.speed_mbps
+            .cmp(&a.speed_mbps)
+            .then_with(|| (b.duplex == Duplex::Full).cmp(&(a.duplex == Duplex::Full)))
+    });
+    settings
+}
+
+// * "1000baseT/Full" -> 1000 Mb/s full duplex
+fn parse_link_mode(token: &str) -> Option<LinkMode> {
+    let (mode, duplex) = token.split_once('/')?;
+    let (speed, _) = mode.split_once("base")?;
+    Some(LinkMode {
+        speed_mbps: speed.parse().ok()?,
+        duplex: Duplex::parse(duplex)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Settings for enp3s0:
+\tSupported ports: [ TP ]
+\tSupported link modes:   10baseT/Half 10baseT/Full
+\t                        100baseT/Half 100baseT/Full
+\t                        1000baseT/Full
+\tSupported pause frame use: Symmetric
+\tSupports auto-negotiation: Yes
+\tAdvertised link modes:  10baseT/Half 10baseT/Full
+\tSpeed: 1000Mb/s
+\tDuplex: Full
+\tAuto-negotiation: on
+\tLink detected: yes
+";
+
+    #[test]
+    fn parses_ethtool_link_settings() {
+        let settings = parse_ethtool_output(SAMPLE);
+
+        assert_eq!(settings.speed_mbps, Some(1000));
+        assert_eq!(settings.duplex, Some(Duplex::Full));
+        assert!(settings.autoneg);
+        assert!(settings.link_detected);
+        assert_eq!(settings.supported.len(), 5);
+        assert_eq!(
+            settings.supported[0],
+            LinkMode {
+                speed_mbps: 1000,
+                duplex: Duplex::Full
+            }
+        );
+        assert_eq!(settings.summary(), "1 Gb/s • full duplex");
+
+        let unplugged = parse_ethtool_output(
+            "\tSpeed: Unknown!\n\tDuplex: Unknown! (255)\n\tLink detected: no\n",
+        );
+        assert_eq!(unplugged.speed_mbps, None);
+        assert_eq!(unplugged.summary(), "No link");
+    }
+}
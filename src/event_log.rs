@@ -0,0 +1,390 @@
+// * ./src/event_log.rs
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeZone};
+use gtk4::glib;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tokio::fs;
+
+use crate::config;
+use crate::hotspot;
+use crate::nm;
+use crate::polling;
+
+const RETENTION_DAYS: i64 = 30;
+const MAX_EVENTS: usize = 5000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    WifiConnected,
+    WifiDisconnected,
+    Roamed,
+    WiredConnected,
+    WiredDisconnected,
+    HotspotStarted,
+    HotspotStopped,
+}
+
+impl EventKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WifiConnected => "Connected",
+            Self::WifiDisconnected => "Disconnected",
+            Self::Roamed => "Roamed",
+            Self::WiredConnected => "Cable connected",
+            Self::WiredDisconnected => "Cable disconnected",
+            Self::HotspotStarted => "Hotspot started",
+            Self::HotspotStopped => "Hotspot stopped",
+        }
+    }
+
+    pub fn category(self) -> EventCategory {
+        match self {
+            Self::WifiConnected | Self::WifiDisconnected | Self::Roamed => EventCategory::Wifi,
+            Self::WiredConnected | Self::WiredDisconnected => EventCategory::Wired,
+            Self::HotspotStarted | Self::HotspotStopped => EventCategory::Hotspot,
+        }
+    }
+
+    pub fn is_drop(self) -> bool {
+        matches!(self, Self::WifiDisconnected | Self::WiredDisconnected)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Wifi,
+    Wired,
+    Hotspot,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkEvent {
+    // * Unix seconds
+    pub timestamp: i64,
+    pub kind: EventKind,
+    pub network: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl NetworkEvent {
+    pub fn local_time(&self) -> Option<DateTime<Local>> {
+        Local.timestamp_opt(self.timestamp, 0).single()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventHistory {
+    #[serde(default)]
+    pub events: Vec<NetworkEvent>,
+}
+
+impl EventHistory {
+    pub fn prune(&mut self, now: i64) {
+        let oldest = now - RETENTION_DAYS * 24 * 60 * 60;
+        self.events.retain(|event| event.timestamp >= oldest);
+        if self.events.len() > MAX_EVENTS {
+            let excess = self.events.len() - MAX_EVENTS;
+            self.events.drain(..excess);
+        }
+    }
+}
+
+// * What the app is connected to at one moment; events are the differences between two of these.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkSnapshot {
+    pub hotspot: Option<String>,
+    pub wired: Option<String>,
+    pub wifi: Option<String>,
+    pub bssid: Option<String>,
+}
+
+pub fn diff_snapshots(
+    previous: &NetworkSnapshot,
+    current: &NetworkSnapshot,
+) -> Vec<(EventKind, String, Option<String>)> {
+    let mut events = Vec::new();
+
+    if previous.hotspot != current.hotspot {
+        if let Some(ssid) = &previous.hotspot {
+            events.push((EventKind::HotspotStopped, ssid.clone(), None));
+        }
+        if let Some(ssid) = &current.hotspot {
+            events.push((EventKind::HotspotStarted, ssid.clone(), None));
+        }
+    }
+
+    if previous.wired != current.wired {
+        if let Some(name) = &previous.wired {
+            events.push((EventKind::WiredDisconnected, name.clone(), None));
+        }
+        if let Some(name) = &current.wired {
+            events.push((EventKind::WiredConnected, name.clone(), None));
+        }
+    }
+
+    if previous.wifi == current.wifi {
+        // * Same network, different access point: a mesh or repeater handed us over
+        if let (Some(ssid), Some(from), Some(to)) = (&current.wifi, &previous.bssid, &current.bssid)
+        {
+            if from != to {
+                events.push((
+                    EventKind::Roamed,
+                    ssid.clone(),
+                    Some(format!("{} → {}", from, to)),
+                ));
+            }
+        }
+    } else {
+        if let Some(ssid) = &previous.wifi {
+            events.push((EventKind::WifiDisconnected, ssid.clone(), None));
+        }
+        if let Some(ssid) = &current.wifi {
+            events.push((
+                EventKind::WifiConnected,
+                ssid.clone(),
+                current.bssid.clone(),
+            ));
+        }
+    }
+
+    events
+}
+
+pub async fn current_snapshot() -> NetworkSnapshot {
+    if hotspot::is_hotspot_active().await.unwrap_or(false) {
+        let ssid = config::load_config(&config::hotspot_config_path())
+            .await
+            .map(|config| config.ssid)
+            .unwrap_or_default();
+        return NetworkSnapshot {
+            hotspot: Some(ssid),
+            wired: nm::get_active_wired_connection().await.ok().flatten(),
+            ..Default::default()
+        };
+    }
+
+    let wifi = nm::get_active_wifi_ssid().await.ok().flatten();
+    let bssid = match &wifi {
+        Some(ssid) => nm::list_access_points_for_ssid(ssid)
+            .await
+            .ok()
+            .and_then(|aps| aps.into_iter().find(|ap| ap.active))
+            .map(|ap| ap.bssid),
+        None => None,
+    };
+    NetworkSnapshot {
+        hotspot: None,
+        wired: nm::get_active_wired_connection().await.ok().flatten(),
+        wifi,
+        bssid,
+    }
+}
+
+pub fn event_history_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".local/share/adw-network/events.json"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-events.json"))
+}
+
+pub async fn load_event_history(path: &Path) -> Result<EventHistory> {
+    if !path.exists() {
+        return Ok(EventHistory::default());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub async fn save_event_history(path: &Path, history: &EventHistory) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let json = serde_json::to_string(history)?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+type EventListener = Rc<dyn Fn(&NetworkEvent)>;
+
+// * Watches NM signals and appends an event whenever the connection picture changes.
+#[derive(Clone, Default)]
+pub struct EventRecorder {
+    history: Rc<RefCell<EventHistory>>,
+    snapshot: Rc<RefCell<Option<NetworkSnapshot>>>,
+    listeners: Rc<RefCell<Vec<EventListener>>>,
+}
+
+impl EventRecorder {
+    pub fn start() -> Self {
+        let recorder = Self::default();
+
+        let recorder_ref = recorder.clone();
+        glib::spawn_future_local(async move {
+            match load_event_history(&event_history_path()).await {
+                Ok(loaded) => {
+                    // * Keep anything recorded while the file was loading
+                    let mut history = recorder_ref.history.borrow_mut();
+                    let recorded = std::mem::replace(&mut *history, loaded);
+                    history.events.extend(recorded.events);
+                }
+                Err(e) => log::warn!("Failed to load network event history: {}", e),
+            }
+            // * The first snapshot is only a baseline; being connected at launch isn't an event
+            let snapshot = current_snapshot().await;
+            recorder_ref.snapshot.borrow_mut().get_or_insert(snapshot);
+        });
+
+        let recorder_ref = recorder.clone();
+        polling::forward_nm_events(move |events| {
+            if events
+                .iter()
+                .all(|event| *event == nm::NmEvent::AccessPointsChanged)
+            {
+                return;
+            }
+            let recorder = recorder_ref.clone();
+            glib::spawn_future_local(async move {
+                let snapshot = current_snapshot().await;
+                recorder.update(snapshot);
+            });
+        });
+
+        recorder
+    }
+
+    pub fn history(&self) -> EventHistory {
+        self.history.borrow().clone()
+    }
+
+    // * Called once per newly recorded event, after it has been added to the history.
+    pub fn connect_event(&self, listener: impl Fn(&NetworkEvent) + 'static) {
+        self.listeners.borrow_mut().push(Rc::new(listener));
+    }
+
+    pub fn clear(&self) {
+        self.history.borrow_mut().events.clear();
+        self.save();
+    }
+
+    fn update(&self, snapshot: NetworkSnapshot) {
+        let previous = self.snapshot.borrow_mut().replace(snapshot.clone());
+        let Some(previous) = previous else {
+            return;
+        };
+        let changes = diff_snapshots(&previous, &snapshot);
+        if changes.is_empty() {
+            return;
+        }
+
+        let now = Local::now().timestamp();
+        let events: Vec<NetworkEvent> = changes
+            .into_iter()
+            .map(|(kind, network, detail)| NetworkEvent {
+                timestamp: now,
+                kind,
+                network,
+                detail,
+            })
+            .collect();
+        {
+            let mut history = self.history.borrow_mut();
+            history.events.extend(events.iter().cloned());
+            history.prune(now);
+        }
+        self.save();
+
+        let listeners = self.listeners.borrow().clone();
+        for event in &events {
+            for listener in &listeners {
+                listener(event);
+            }
+        }
+    }
+
+    fn save(&self) {
+        let history = self.history.borrow().clone();
+        glib::spawn_future_local(async move {
+            if let Err(e) = save_event_history(&event_history_path(), &history).await {
+                log::warn!("Failed to save network event history: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi(ssid: &str, bssid: &str) -> NetworkSnapshot {
+        NetworkSnapshot {
+            wifi: Some(ssid.to_string()),
+            bssid: Some(bssid.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_reports_drops_switches_and_roams() {
+        let home = wifi("Home", "AA:AA:AA:AA:AA:01");
+        let offline = NetworkSnapshot::default();
+
+        let kinds = |events: Vec<(EventKind, String, Option<String>)>| {
+            events
+                .into_iter()
+                .map(|(kind, network, _)| (kind, network))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds(diff_snapshots(&home, &offline)),
+            vec![(EventKind::WifiDisconnected, "Home".to_string())]
+        );
+        assert_eq!(
+            kinds(diff_snapshots(&home, &wifi("Cafe", "BB:BB:BB:BB:BB:01"))),
+            vec![
+                (EventKind::WifiDisconnected, "Home".to_string()),
+                (EventKind::WifiConnected, "Cafe".to_string()),
+            ]
+        );
+
+        let roam = diff_snapshots(&home, &wifi("Home", "AA:AA:AA:AA:AA:02"));
+        assert_eq!(roam.len(), 1);
+        assert_eq!(roam[0].0, EventKind::Roamed);
+        assert_eq!(
+            roam[0].2.as_deref(),
+            Some("AA:AA:AA:AA:AA:01 → AA:AA:AA:AA:AA:02")
+        );
+
+        let hotspot = NetworkSnapshot {
+            hotspot: Some("Laptop".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            kinds(diff_snapshots(&offline, &hotspot)),
+            vec![(EventKind::HotspotStarted, "Laptop".to_string())]
+        );
+        assert!(diff_snapshots(&home, &home).is_empty());
+    }
+
+    #[test]
+    fn prune_keeps_recent_events() {
+        let event = |timestamp| NetworkEvent {
+            timestamp,
+            kind: EventKind::WifiConnected,
+            network: "Home".to_string(),
+            detail: None,
+        };
+        let now = 100 * 24 * 60 * 60;
+        let mut history = EventHistory {
+            events: vec![event(0), event(now - 60)],
+        };
+        history.prune(now);
+        assert_eq!(history.events, vec![event(now - 60)]);
+    }
+}
@@ -0,0 +1,399 @@
+// * ./src/hostapd.rs
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::config::{HotspotConfig, HotspotSecurity};
+
+const NAT_TABLE: &str = "adw_network_hostapd";
+// * Same subnet NM's shared mode picks, so both backends look alike to clients
+const DEFAULT_ADDRESS: &str = "10.42.0.1/24";
+
+// * What the running hotspot was started with; its presence means this backend is in charge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RunningHotspot {
+    interface: String,
+    address: String,
+}
+
+// * Created by the privileged script itself; only root can write under /run, so nothing the
+// * user (or anyone else) drops there can end up in root's hands.
+const RUNTIME_DIR: &str = "/run/adw-network-hostapd";
+// * Quoted heredoc marker; interface names and the generated configs never contain it.
+const HEREDOC_END: &str = "ADW_NETWORK_EOF";
+
+fn runtime_dir() -> PathBuf {
+    PathBuf::from(RUNTIME_DIR)
+}
+
+fn state_path() -> PathBuf {
+    runtime_dir().join("state.json")
+}
+
+pub fn lease_file_path() -> PathBuf {
+    runtime_dir().join("dnsmasq.leases")
+}
+
+fn running_hotspot() -> Option<RunningHotspot> {
+    let content = std::fs::read_to_string(state_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn pid_alive(pid_file: &Path) -> bool {
+    std::fs::read_to_string(pid_file)
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .is_some_and(|pid| Path::new(&format!("/proc/{}", pid)).exists())
+}
+
+pub fn is_running() -> bool {
+    running_hotspot().is_some() && pid_alive(&runtime_dir().join("hostapd.pid"))
+}
+
+pub fn running_interface() -> Option<String> {
+    running_hotspot()
+        .filter(|_| is_running())
+        .map(|running| running.interface)
+}
+
+pub fn running_address() -> Option<String> {
+    running_hotspot()
+        .filter(|_| is_running())
+        .map(|running| running.address)
+}
+
+// * Missing tools are reported up front instead of as a half-started hotspot.
+pub async fn check_available() -> Result<()> {
+    for tool in ["hostapd", "dnsmasq", "pkexec"] {
+        if !crate::hotspot::command_available(tool).await {
+            return Err(anyhow!(
+                "The hostapd hotspot backend needs {} installed",
+                tool
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn hostapd_conf(config: &HotspotConfig, iface: &str) -> String {
+    let channel = config
+        .channel
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|channel| *channel > 0);
+    // * 6 GHz channel numbers restart at 1, so only the band tells them apart from 2.4 GHz
+    let six_ghz = config.is_6ghz();
+    let five_ghz = six_ghz
+        || match channel {
+            Some(channel) => channel > 14,
+            None => config.band.contains('5'),
+        };
+    let channel = channel.unwrap_or(match (six_ghz, five_ghz) {
+        (true, _) => 5,
+        (false, true) => 36,
+        (false, false) => 6,
+    });
+
+    let mut lines = vec![
+        "# Managed by adw-network".to_string(),
+        format!("interface={}", iface),
+        "driver=nl80211".to_string(),
+        format!("ssid={}", config.ssid),
+        format!("hw_mode={}", if five_ghz { "a" } else { "g" }),
+        format!("channel={}", channel),
+        "ieee80211n=1".to_string(),
+        "wmm_enabled=1".to_string(),
+    ];
+    if six_ghz {
+        // * Operating class 131 is the 20 MHz 6 GHz set; SAE there must use hash-to-element
+        lines.push("op_class=131".to_string());
+        lines.push("ieee80211ax=1".to_string());
+        lines.push("sae_pwe=1".to_string());
+    } else if five_ghz {
+        lines.push("ieee80211ac=1".to_string());
+    }
+    if config.hidden {
+        lines.push("ignore_broadcast_ssid=1".to_string());
+    }
+    if config.isolate_clients {
+        lines.push("ap_isolate=1".to_string());
+    }
+    if !config.password.is_empty() {
+        let (key_mgmt, pmf) = match config.security {
+            HotspotSecurity::Wpa2 => ("WPA-PSK", 0),
+            HotspotSecurity::Wpa3 => ("SAE", 2),
+            HotspotSecurity::Wpa2Wpa3 => ("WPA-PSK SAE", 1),
+        };
+        lines.push("wpa=2".to_string());
+        lines.push(format!("wpa_key_mgmt={}", key_mgmt));
+        lines.push("rsn_pairwise=CCMP".to_string());
+        lines.push(format!("ieee80211w={}", pmf));
+        lines.push(format!("wpa_passphrase={}", config.password));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn dnsmasq_conf(
+    config: &HotspotConfig,
+    iface: &str,
+    subnet: &crate::config::HotspotSubnet,
+    dir: &Path,
+) -> String {
+    let (start, end) = subnet.dhcp_pool();
+    let mask = std::net::Ipv4Addr::from(u32::MAX << (32 - u32::from(subnet.prefix)));
+    let mut content = crate::hotspot::dnsmasq_reservations_conf(&config.dhcp_reservations);
+    content.push_str(&format!(
+        "interface={iface}\n\
+         bind-interfaces\n\
+         except-interface=lo\n\
+         dhcp-range={start},{end},{mask},1h\n\
+         dhcp-option=option:router,{gateway}\n\
+         dhcp-option=option:dns-server,{gateway}\n\
+         dhcp-leasefile={leases}\n\
+         pid-file={pid}\n",
+        gateway = subnet.gateway,
+        leases = lease_file_path().display(),
+        pid = dir.join("dnsmasq.pid").display(),
+    ));
+    content
+}
+
+// * Clients leave through whatever the host routes to, like NM's shared mode.
+fn nat_ruleset(iface: &str, subnet: &crate::config::HotspotSubnet) -> String {
+    format!(
+        "table ip {table} {{\n\
+         \tchain postrouting {{\n\
+         \t\ttype nat hook postrouting priority srcnat; policy accept;\n\
+         \t\tip saddr {gateway}/{prefix} oifname != \"{iface}\" masquerade\n\
+         \t}}\n\
+         }}\n",
+        table = NAT_TABLE,
+        gateway = subnet.gateway,
+        prefix = subnet.prefix,
+    )
+}
+
+// * `input` goes in on stdin, so secrets never show up in the world-readable argv.
+async fn run_privileged(script: &str, input: &[u8]) -> Result<()> {
+    let mut child = Command::new("pkexec")
+        .args(["sh", "-c", script])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run pkexec: {}", e))?;
+    let stdin = child.stdin.take();
+    let output = crate::nm::with_timeout(crate::nm::operation_timeout(), "pkexec", async {
+        if let Some(mut stdin) = stdin {
+            use tokio::io::AsyncWriteExt;
+            // * A script that never reads stdin closes it early; its exit status tells the rest
+            stdin.write_all(input).await.ok();
+        }
+        Ok(child.wait_with_output().await?)
+    })
+    .await?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        Err(anyhow!("Privileged hotspot command failed"))
+    } else {
+        Err(anyhow!(stderr))
+    }
+}
+
+// * Root recreates the runtime directory and writes every file itself. The state file goes
+// * first so stop() can clean up after a start that fails midway; hostapd.conf holds the
+// * passphrase, so it comes in on stdin and is created 0600.
+fn start_script(
+    config: &HotspotConfig,
+    iface: &str,
+    subnet: &crate::config::HotspotSubnet,
+) -> Result<String> {
+    let dir = runtime_dir();
+    let state = RunningHotspot {
+        interface: iface.to_string(),
+        address: subnet.gateway.to_string(),
+    };
+
+    let address = format!("{}/{}", subnet.gateway, subnet.prefix);
+    Ok(format!(
+        "set -e\n\
+         umask 022\n\
+         rm -rf {dir}\n\
+         mkdir -m 0755 {dir}\n\
+         cat > {state_path} <<'{end}'\n{state}\n{end}\n\
+         (umask 077; cat > {hostapd_conf})\n\
+         cat > {dnsmasq_conf} <<'{end}'\n{dnsmasq}{end}\n\
+         ip addr flush dev {iface}\n\
+         ip link set {iface} up\n\
+         ip addr add {address} dev {iface}\n\
+         hostapd -B -P {hostapd_pid} {hostapd_conf}\n\
+         dnsmasq --conf-file={dnsmasq_conf}\n\
+         sysctl -qw net.ipv4.ip_forward=1\n\
+         nft -f - <<'{end}'\n{nat}{end}\n",
+        dir = shell_quote(&dir.to_string_lossy()),
+        end = HEREDOC_END,
+        state_path = shell_quote(&state_path().to_string_lossy()),
+        state = serde_json::to_string_pretty(&state)?,
+        dnsmasq = dnsmasq_conf(config, iface, subnet, &dir),
+        nat = nat_ruleset(iface, subnet),
+        iface = shell_quote(iface),
+        hostapd_pid = shell_quote(&dir.join("hostapd.pid").to_string_lossy()),
+        hostapd_conf = shell_quote(&dir.join("hostapd.conf").to_string_lossy()),
+        dnsmasq_conf = shell_quote(&dir.join("dnsmasq.conf").to_string_lossy()),
+    ))
+}
+
+// * Takes the adapter away from NM, then brings up hostapd, dnsmasq and NAT in one pkexec call.
+pub async fn start(config: &HotspotConfig, iface: &str) -> Result<()> {
+    check_available().await?;
+    if running_hotspot().is_some() {
+        stop().await?;
+    }
+
+    let subnet = match config.subnet()? {
+        Some(subnet) => subnet,
+        None => HotspotConfig {
+            ipv4_address: Some(DEFAULT_ADDRESS.to_string()),
+            dhcp_range_start: None,
+            dhcp_range_end: None,
+            ..config.clone()
+        }
+        .subnet()?
+        .ok_or_else(|| anyhow!("Invalid default hotspot address"))?,
+    };
+
+    let client = crate::nm::dbus_client().await?;
+    client.set_device_managed(iface, false).await?;
+
+    let script = start_script(config, iface, &subnet)?;
+    let hostapd = zeroize::Zeroizing::new(hostapd_conf(config, iface));
+
+    if let Err(e) = run_privileged(&script, hostapd.as_bytes()).await {
+        warn!("hostapd hotspot failed to start: {}", e);
+        // * Nothing got recorded when the script never ran, e.g. a dismissed prompt
+        if !stop().await.unwrap_or(false) {
+            client.set_device_managed(iface, true).await.ok();
+        }
+        return Err(anyhow!("Failed to start hostapd: {}", e));
+    }
+    info!("Started hostapd hotspot {} on {}", config.ssid, iface);
+    Ok(())
+}
+
+// * Undoes start() step by step, tolerating pieces that never came up.
+// * Returns false when this backend had nothing running.
+pub async fn stop() -> Result<bool> {
+    let Some(running) = running_hotspot() else {
+        return Ok(false);
+    };
+    let dir = runtime_dir();
+    let script = format!(
+        "for pid_file in {hostapd_pid} {dnsmasq_pid}; do\n\
+         \t[ -f \"$pid_file\" ] && kill \"$(cat \"$pid_file\")\" 2>/dev/null\n\
+         \trm -f \"$pid_file\"\n\
+         done\n\
+         nft delete table ip {table} 2>/dev/null\n\
+         ip addr flush dev {iface} 2>/dev/null\n\
+         rm -rf {dir}\n\
+         true\n",
+        dir = shell_quote(&dir.to_string_lossy()),
+        hostapd_pid = shell_quote(&dir.join("hostapd.pid").to_string_lossy()),
+        dnsmasq_pid = shell_quote(&dir.join("dnsmasq.pid").to_string_lossy()),
+        table = NAT_TABLE,
+        iface = shell_quote(&running.interface),
+    );
+    run_privileged(&script, &[]).await?;
+
+    let client = crate::nm::dbus_client().await?;
+    if let Err(e) = client.set_device_managed(&running.interface, true).await {
+        warn!(
+            "Failed to hand {} back to NetworkManager: {}",
+            running.interface, e
+        );
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_hostapd_config_for_the_chosen_band_and_security() {
+        let config = HotspotConfig {
+            ssid: "Cafe".to_string(),
+            password: "secret123".to_string(),
+            band: "5 GHz".to_string(),
+            channel: "Auto".to_string(),
+            security: HotspotSecurity::Wpa2Wpa3,
+            hidden: true,
+            ..HotspotConfig::default()
+        };
+        let conf = hostapd_conf(&config, "wlan1");
+        for line in [
+            "interface=wlan1",
+            "ssid=Cafe",
+            "hw_mode=a",
+            "channel=36",
+            "ignore_broadcast_ssid=1",
+            "wpa_key_mgmt=WPA-PSK SAE",
+            "ieee80211w=1",
+            "wpa_passphrase=secret123",
+        ] {
+            assert!(conf.lines().any(|l| l == line), "missing {}", line);
+        }
+
+        let open = HotspotConfig {
+            password: String::new(),
+            channel: "11".to_string(),
+            ..config
+        };
+        let conf = hostapd_conf(&open, "wlan1");
+        assert!(conf.contains("hw_mode=g\nchannel=11\n"));
+        assert!(!conf.contains("wpa="));
+
+        let six_ghz = HotspotConfig {
+            band: "6 GHz".to_string(),
+            channel: "5".to_string(),
+            security: HotspotSecurity::Wpa3,
+            ..HotspotConfig::default()
+        };
+        let conf = hostapd_conf(&six_ghz, "wlan1");
+        assert!(conf.contains("hw_mode=a\nchannel=5\n"));
+        assert!(conf.contains("op_class=131\n"));
+    }
+
+    #[test]
+    fn start_script_keeps_the_passphrase_out_of_argv_and_writes_only_under_run() {
+        let config = HotspotConfig {
+            ssid: "Cafe".to_string(),
+            password: "secret123".to_string(),
+            ..HotspotConfig::default()
+        };
+        let subnet = HotspotConfig {
+            ipv4_address: Some(DEFAULT_ADDRESS.to_string()),
+            ..config.clone()
+        }
+        .subnet()
+        .unwrap()
+        .unwrap();
+        let script = start_script(&config, "wlan1", &subnet).unwrap();
+        assert!(!script.contains("secret123"));
+        assert!(script.contains("rm -rf '/run/adw-network-hostapd'\n"));
+        assert!(script.contains("(umask 077; cat > '/run/adw-network-hostapd/hostapd.conf')\n"));
+        assert!(!script.contains("/tmp"));
+        assert!(lease_file_path().starts_with(RUNTIME_DIR));
+    }
+}
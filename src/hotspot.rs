@@ -1,16 +1,25 @@
 // * ./src/hotspot.rs
 
 use anyhow::{anyhow, Result};
-use std::time::Instant;
+use log::{debug, info, warn};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::time::Instant;
 use tokio::fs;
 use tokio::process::Command;
 use tokio::time::{sleep, Duration};
-use serde::Serialize;
-use log::{debug, info, warn};
+
+use crate::nm::NmError;
 
 pub const HOTSPOT_UNSUPPORTED_TOAST: &str = "This Wi-Fi adapter does not support hotspot mode";
 const HOTSPOT_NFT_TABLE: &str = "adw_network_hotspot";
+const HOTSPOT_IFB_DEVICE: &str = "adw-hs-ifb0";
+// * Policy routing for a chosen internet source: hotspot traffic looks up its own table
+const HOTSPOT_ROUTE_TABLE: &str = "7342";
+const HOTSPOT_RULE_PRIORITIES: [&str; 3] = ["7340", "7341", "7342"];
+// * NM points the dnsmasq of every shared connection at this directory
+const DNSMASQ_RESERVATIONS_PATH: &str =
+    "/etc/NetworkManager/dnsmasq-shared.d/adw-network-reservations.conf";
 
 fn validate_interface_name(name: &str) -> bool {
     !name.is_empty()
@@ -54,6 +63,53 @@ pub struct ConnectedClientCountInfo {
     pub estimated: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamKind {
+    Ethernet,
+    Wifi,
+    Mobile,
+    Vpn,
+}
+
+impl UpstreamKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ethernet => "Ethernet",
+            Self::Wifi => "Wi-Fi",
+            Self::Mobile => "Mobile broadband",
+            Self::Vpn => "VPN tunnel",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamInterface {
+    pub name: String,
+    pub kind: UpstreamKind,
+}
+
+// * How far an adapter can run an access point next to its own Wi-Fi connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApStaConcurrency {
+    Unsupported,
+    // * One radio channel: the hotspot has to follow the connection's channel
+    SameChannel,
+    Independent,
+}
+
+// * What starting the hotspot will do to a Wi-Fi connection the user is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConcurrencyGuidance {
+    DropsWifi(String),
+    SharesChannel(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct UpstreamRoute {
+    device: String,
+    gateway: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HotspotClientDevice {
     pub ip: String,
@@ -68,6 +124,8 @@ struct RuntimePolicyPlan {
     blocked_macs: std::collections::BTreeMap<String, String>,
     domain_blocks: std::collections::BTreeMap<String, ResolvedDomainBlock>,
     resolved_client_ips: std::collections::BTreeMap<String, String>,
+    // * None while the chosen internet source is down or none was chosen
+    upstream_route: Option<UpstreamRoute>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -91,6 +149,10 @@ struct RuntimeRulesSignature {
     global_download_limit_kbps: Option<u32>,
     max_connected_devices: Option<u32>,
     mac_filter_mode: crate::config::HotspotMacFilterMode,
+    mac_filter_list: Vec<String>,
+    isolate_clients: bool,
+    upstream_interface: Option<String>,
+    upstream_route: Option<UpstreamRoute>,
     resolved_client_ips: Vec<(String, String)>,
     client_rules: Vec<ClientRuleSignature>,
     domain_blocks: Vec<DomainBlockSignature>,
@@ -177,6 +239,24 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
 
     effective_config.validate()?;
 
+    if hostapd_backend_selected().await {
+        return start_with_hostapd(&effective_config, iface).await;
+    }
+
+    let repeater = match effective_config.mode {
+        crate::config::HotspotMode::Repeater => {
+            Some(crate::repeater::prepare(&effective_config, iface).await?)
+        }
+        _ => None,
+    };
+    let (effective_config, iface) = match &repeater {
+        Some(setup) => (setup.config.clone(), setup.ap_interface.as_str()),
+        None => (effective_config, iface),
+    };
+    let mut state = load_runtime_state_or_default();
+    state.repeater_station = repeater.as_ref().map(|setup| setup.station.clone());
+    save_runtime_state_safe(&state);
+
     let overall_start = Instant::now();
 
     // Connect to NetworkManager with timeout
@@ -185,13 +265,22 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
     {
         Ok(Ok(c)) => c,
         Ok(Err(e)) => return Err(e),
-        Err(_) => return Err(anyhow!("Timed out connecting to NetworkManager")),
+        Err(_) => {
+            return Err(
+                NmError::Timeout("Timed out connecting to NetworkManager".to_string()).into(),
+            )
+        }
     };
     debug!("dbus_client init: {} ms", start.elapsed().as_millis());
 
     // Ensure device is ready with a timeout to avoid indefinite hangs
     let start = Instant::now();
-    match tokio::time::timeout(Duration::from_secs(8), client.ensure_wifi_device_ready(iface)).await {
+    match tokio::time::timeout(
+        Duration::from_secs(8),
+        client.ensure_wifi_device_ready(iface),
+    )
+    .await
+    {
         Ok(Ok(())) => {}
         Ok(Err(e)) => {
             let error_text = e.to_string();
@@ -205,21 +294,50 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
             ));
         }
         Err(_) => {
-            return Err(anyhow!(
+            return Err(NmError::Timeout(format!(
                 "Timed out waiting for Wi-Fi interface {} to become ready",
                 iface
-            ));
+            ))
+            .into());
+        }
+    }
+    debug!(
+        "ensure_wifi_device_ready: {} ms",
+        start.elapsed().as_millis()
+    );
+
+    if effective_config.security == crate::config::HotspotSecurity::Wpa3 {
+        if let Some(reason) =
+            crate::nm::capabilities().unsupported_reason(crate::nm::NmFeature::Wpa3Hotspot)
+        {
+            return Err(anyhow!(reason));
+        }
+    }
+    if effective_config.security == crate::config::HotspotSecurity::Wpa3
+        && !supports_wpa3(iface).await
+    {
+        return Err(anyhow!(
+            "{} does not support WPA3; choose WPA2 or WPA2/WPA3 security",
+            iface
+        ));
+    }
+    if effective_config.is_6ghz() {
+        if let Some(reason) =
+            crate::nm::capabilities().unsupported_reason(crate::nm::NmFeature::Band6Ghz)
+        {
+            return Err(anyhow!(reason));
+        }
+        if !supports_6ghz(iface).await {
+            return Err(anyhow!("{} can't host a 6 GHz hotspot", iface));
         }
     }
-    debug!("ensure_wifi_device_ready: {} ms", start.elapsed().as_millis());
 
     // List devices and find interface path
     let start = Instant::now();
-    let devices = match tokio::time::timeout(Duration::from_secs(3), client.list_devices()).await
-    {
+    let devices = match tokio::time::timeout(Duration::from_secs(3), client.list_devices()).await {
         Ok(Ok(list)) => list,
         Ok(Err(e)) => return Err(e),
-        Err(_) => return Err(anyhow!("Timed out listing devices")),
+        Err(_) => return Err(NmError::Timeout("Timed out listing devices".to_string()).into()),
     };
     debug!("list_devices: {} ms", start.elapsed().as_millis());
 
@@ -231,10 +349,17 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
 
     // Deactivate other connections on this interface (best-effort)
     let start = Instant::now();
-    let active = match tokio::time::timeout(Duration::from_secs(4), client.list_active_connections()).await {
+    let active = match tokio::time::timeout(
+        Duration::from_secs(4),
+        client.list_active_connections(),
+    )
+    .await
+    {
         Ok(Ok(list)) => list,
         Ok(Err(e)) => return Err(e),
-        Err(_) => return Err(anyhow!("Timed out listing active connections")),
+        Err(_) => {
+            return Err(NmError::Timeout("Timed out listing active connections".to_string()).into())
+        }
     };
     for connection in active {
         if connection.id == "Hotspot" {
@@ -244,20 +369,57 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
             continue;
         }
         if connection.devices.contains(&iface_path) {
-            let _ = tokio::time::timeout(Duration::from_secs(3), client.deactivate_connection_by_uuid(&connection.uuid)).await;
+            let _ = tokio::time::timeout(
+                Duration::from_secs(3),
+                client.deactivate_connection_by_uuid(&connection.uuid),
+            )
+            .await;
         }
     }
-    let _ = tokio::time::timeout(Duration::from_secs(3), client.deactivate_connection_by_id("Hotspot")).await;
-    debug!("deactivated conflicting connections: {} ms", start.elapsed().as_millis());
+    let _ = tokio::time::timeout(
+        Duration::from_secs(3),
+        client.deactivate_connection_by_id("Hotspot"),
+    )
+    .await;
+    debug!(
+        "deactivated conflicting connections: {} ms",
+        start.elapsed().as_millis()
+    );
+
+    // dnsmasq only reads its config when the hotspot (re)starts, so write it before activating
+    if let Err(e) = sync_dhcp_reservations(&effective_config.dhcp_reservations).await {
+        warn!("Failed to write hotspot DHCP reservations: {}", e);
+    }
 
     // Upsert hotspot connection with timeout
     let start = Instant::now();
-    match tokio::time::timeout(Duration::from_secs(6), client.upsert_hotspot_connection(&effective_config, iface)).await {
+    match tokio::time::timeout(
+        Duration::from_secs(6),
+        client.upsert_hotspot_connection(&effective_config, iface),
+    )
+    .await
+    {
         Ok(Ok(())) => {}
         Ok(Err(e)) => return Err(e),
-        Err(_) => return Err(anyhow!("Timed out creating/updating hotspot connection")),
+        Err(_) => {
+            return Err(NmError::Timeout(
+                "Timed out creating/updating hotspot connection".to_string(),
+            )
+            .into())
+        }
     }
-    debug!("upsert_hotspot_connection: {} ms", start.elapsed().as_millis());
+    debug!(
+        "upsert_hotspot_connection: {} ms",
+        start.elapsed().as_millis()
+    );
+
+    let start = Instant::now();
+    if effective_config.mode == crate::config::HotspotMode::Bridged {
+        bring_up_hotspot_bridge(&client, &effective_config, iface).await?;
+    } else if let Err(e) = client.remove_hotspot_bridge().await {
+        warn!("Failed to remove the hotspot bridge: {}", e);
+    }
+    debug!("hotspot bridge: {} ms", start.elapsed().as_millis());
 
     // Activate connection if not already active; wait for NM's active-connection signal.
     let start = Instant::now();
@@ -277,44 +439,206 @@ pub async fn create_hotspot_on(config: &crate::config::HotspotConfig, iface: &st
                 match activated {
                     Ok(Ok(())) => {}
                     Ok(Err(e)) => return Err(e),
-                    Err(_) => return Err(anyhow!("Timed out waiting for hotspot activation")),
+                    Err(_) => {
+                        return Err(NmError::Timeout(
+                            "Timed out waiting for hotspot activation".to_string(),
+                        )
+                        .into())
+                    }
                 }
             }
             Ok(Err(e)) => return Err(e),
-            Err(_) => return Err(anyhow!("Timed out activating hotspot connection")),
+            Err(_) => {
+                return Err(
+                    NmError::Timeout("Timed out activating hotspot connection".to_string()).into(),
+                )
+            }
         }
     }
     debug!("activate_connection: {} ms", start.elapsed().as_millis());
 
     // Apply runtime rules (nft/tc) with timeout
     let start = Instant::now();
-    match tokio::time::timeout(Duration::from_secs(10), apply_runtime_rules(&effective_config, iface)).await {
+    match tokio::time::timeout(
+        Duration::from_secs(10),
+        apply_runtime_rules(&effective_config, iface),
+    )
+    .await
+    {
         Ok(Ok(())) => {}
         Ok(Err(e)) => return Err(e),
         Err(_) => warn!("Timed out applying runtime rules (nft/tc)"),
     }
     debug!("apply_runtime_rules: {} ms", start.elapsed().as_millis());
 
-    debug!("hotspot overall: {} ms", overall_start.elapsed().as_millis());
+    debug!(
+        "hotspot overall: {} ms",
+        overall_start.elapsed().as_millis()
+    );
+
+    Ok(())
+}
+
+async fn hostapd_backend_selected() -> bool {
+    crate::config::load_app_settings(&crate::config::app_settings_path())
+        .await
+        .map(|settings| settings.hotspot_backend == crate::config::HotspotBackend::Hostapd)
+        .unwrap_or(false)
+}
+
+// * NM only has to let go of the adapter; hostapd and dnsmasq do the rest.
+async fn start_with_hostapd(config: &crate::config::HotspotConfig, iface: &str) -> Result<()> {
+    if config.mode != crate::config::HotspotMode::Nat {
+        return Err(anyhow!(
+            "Bridged and repeater modes need the NetworkManager hotspot backend"
+        ));
+    }
+    if config.security == crate::config::HotspotSecurity::Wpa3 && !supports_wpa3(iface).await {
+        return Err(anyhow!(
+            "{} does not support WPA3; choose WPA2 or WPA2/WPA3 security",
+            iface
+        ));
+    }
+    let client = crate::nm::dbus_client().await?;
+    let _ = client.deactivate_connection_by_id("Hotspot").await;
+
+    crate::hostapd::start(config, iface).await?;
+    match tokio::time::timeout(Duration::from_secs(10), apply_runtime_rules(config, iface)).await {
+        Ok(result) => result,
+        Err(_) => {
+            warn!("Timed out applying runtime rules (nft/tc)");
+            Ok(())
+        }
+    }
+}
 
+// * Activating the wired port brings the bridge up with it; the AP joins once it activates.
+async fn bring_up_hotspot_bridge(
+    client: &crate::nm_dbus::NmDbusClient,
+    config: &crate::config::HotspotConfig,
+    iface: &str,
+) -> Result<()> {
+    let uplink = config
+        .upstream_interface
+        .as_deref()
+        .ok_or_else(|| anyhow!("Bridged mode needs a wired internet source"))?;
+    if uplink == iface || upstream_kind(uplink).await != Some(UpstreamKind::Ethernet) {
+        return Err(anyhow!(
+            "Bridged mode needs a wired internet source; {} can't join a bridge",
+            uplink
+        ));
+    }
+    let timeout = crate::nm::operation_timeout();
+    crate::nm::with_timeout(
+        timeout,
+        "Creating hotspot bridge",
+        client.upsert_hotspot_bridge(uplink),
+    )
+    .await?;
+    crate::nm::with_timeout(
+        timeout,
+        "Attaching the wired port to the hotspot bridge",
+        client.activate_connection_by_id(crate::nm_dbus::HOTSPOT_UPLINK_ID, Some(uplink)),
+    )
+    .await?;
     Ok(())
 }
 
+// * Starts from hotspot.json alone, for callers without the hotspot page (CLI, compact window).
+// * Returns the SSID and the interface the hotspot came up on.
+pub async fn start_saved_hotspot(interface: Option<String>) -> Result<(String, String)> {
+    let mut config = crate::config::load_config(&crate::config::hotspot_config_path())
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "No usable hotspot settings; set up the hotspot in the app first ({})",
+                e
+            )
+        })?;
+    if config.password.is_empty() {
+        // * Keyring-stored passwords aren't in hotspot.json
+        config.password = crate::secrets::load_hotspot_password()
+            .ok()
+            .flatten()
+            .or_else(|| {
+                crate::secrets::load_hotspot_password_backup()
+                    .ok()
+                    .flatten()
+            })
+            .unwrap_or_default();
+    }
+
+    let interface = match interface {
+        Some(interface) => interface,
+        None => {
+            let devices = get_wifi_devices().await?;
+            // * The saved adapter may be a USB dongle that isn't plugged in right now
+            config
+                .interface
+                .clone()
+                .filter(|saved| devices.contains(saved))
+                .or_else(|| devices.into_iter().next())
+                .ok_or_else(|| anyhow!("No Wi-Fi adapter found"))?
+        }
+    };
+    create_hotspot_on(&config, &interface).await?;
+    backup_password_to_keyring(&config.password).await;
+    Ok((config.ssid, interface))
+}
+
+// * A namespaced keyring copy of the password, so switching storage backends can't strand it.
+// * Settings and keyring access both block, so they run off the caller's (often GTK) thread.
+pub async fn backup_password_to_keyring(password: &str) {
+    let password = zeroize::Zeroizing::new(password.to_string());
+    let result = tokio::task::spawn_blocking(move || {
+        let enabled = crate::config::load_app_settings_sync(&crate::config::app_settings_path())
+            .map(|settings| settings.backup_hotspot_password_to_keyring)
+            .unwrap_or(true);
+        if !enabled {
+            return Ok(());
+        }
+        crate::secrets::store_hotspot_password_backup(&password)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => (),
+        Ok(Err(e)) => warn!("Failed to back up hotspot password to keyring: {}", e),
+        Err(e) => log::error!("spawn_blocking panicked: {}", e),
+    }
+}
+
+// * Brings the hotspot back when the app starts, if the user asked for that.
+pub async fn restore_hotspot_on_launch() {
+    let start_on_launch = crate::config::load_config(&crate::config::hotspot_config_path())
+        .await
+        .map(|config| config.start_on_launch)
+        .unwrap_or(false);
+    if !start_on_launch || is_hotspot_active().await.unwrap_or(false) {
+        return;
+    }
+    match start_saved_hotspot(None).await {
+        Ok((ssid, interface)) => info!("Restored hotspot {} on {}", ssid, interface),
+        Err(e) => warn!("Failed to restore hotspot on launch: {}", e),
+    }
+}
+
 pub async fn stop_hotspot() -> Result<()> {
     let _guard = hotspot_lock()
         .try_lock()
         .map_err(|_| anyhow!("Hotspot operation already in progress"))?;
 
     let iface = get_hotspot_interface().await.ok().flatten();
-    let client = crate::nm::dbus_client().await?;
-    client.deactivate_connection_by_id("Hotspot").await?;
-    client.delete_connection_by_id("Hotspot").await?;
     if let Some(iface) = iface.as_deref() {
         cleanup_runtime_rules(iface).await.ok();
     }
+    if !crate::hostapd::stop().await? {
+        stop_nm_hotspot().await?;
+    }
     let mut state = load_runtime_state_or_default();
     state.temporary_password = None;
     state.last_applied_signature = None;
+    state.repeater_station = None;
+    state.end_session();
     for client in &mut state.clients {
         client.last_connected_at = None;
         client.last_upload_counter_bytes = 0;
@@ -325,7 +649,45 @@ pub async fn stop_hotspot() -> Result<()> {
     Ok(())
 }
 
+async fn stop_nm_hotspot() -> Result<()> {
+    let client = crate::nm::dbus_client().await?;
+    let timeout = crate::nm::operation_timeout();
+    crate::nm::with_timeout(
+        timeout,
+        "Stopping hotspot",
+        client.deactivate_connection_by_id("Hotspot"),
+    )
+    .await?;
+    crate::nm::with_timeout(
+        timeout,
+        "Removing hotspot profile",
+        client.delete_connection_by_id("Hotspot"),
+    )
+    .await?;
+    if let Err(e) = crate::nm::with_timeout(
+        timeout,
+        "Removing hotspot bridge",
+        client.remove_hotspot_bridge(),
+    )
+    .await
+    {
+        warn!("Failed to remove the hotspot bridge: {}", e);
+    }
+    crate::repeater::remove_virtual_interface().await;
+    Ok(())
+}
+
+// * The station a running repeater extends from and its current link, for the status header.
+pub async fn repeater_link() -> Option<(String, crate::repeater::StationLink)> {
+    let station = load_runtime_state_or_default().repeater_station?;
+    let link = crate::repeater::station_link(&station).await?;
+    Some((station, link))
+}
+
 pub async fn is_hotspot_active() -> Result<bool> {
+    if crate::hostapd::is_running() {
+        return Ok(true);
+    }
     // Query NetworkManager via nm_dbus helper to determine active Hotspot connection
     crate::nm::is_hotspot_active().await
 }
@@ -333,7 +695,12 @@ pub async fn is_hotspot_active() -> Result<bool> {
 pub async fn get_wifi_devices() -> Result<Vec<String>> {
     let client = crate::nm::dbus_client().await?;
     let devices = client.get_wifi_devices().await?;
-    let names = devices.into_iter().map(|d| d.interface).collect::<Vec<_>>();
+    // * A repeater's virtual AP interface isn't an adapter of its own
+    let names = devices
+        .into_iter()
+        .map(|d| d.interface)
+        .filter(|name| name != crate::repeater::REPEATER_AP_INTERFACE)
+        .collect::<Vec<_>>();
     if names.is_empty() {
         return Err(anyhow!(
             "No WiFi devices found. Make sure you have a wireless network adapter."
@@ -343,6 +710,9 @@ pub async fn get_wifi_devices() -> Result<Vec<String>> {
 }
 
 pub async fn get_hotspot_ip() -> Result<Option<String>> {
+    if let Some(address) = crate::hostapd::running_address() {
+        return Ok(Some(address));
+    }
     crate::nm::get_hotspot_ip().await
 }
 
@@ -432,6 +802,9 @@ pub async fn get_connected_device_count_info() -> Result<ConnectedClientCountInf
 }
 
 pub async fn get_hotspot_interface() -> Result<Option<String>> {
+    if let Some(iface) = crate::hostapd::running_interface() {
+        return Ok(Some(iface));
+    }
     let client = crate::nm::dbus_client().await?;
     let hotspot = crate::nm::get_active_hotspot_connection().await?;
 
@@ -460,6 +833,242 @@ pub async fn advanced_support() -> HotspotAdvancedSupport {
     }
 }
 
+// * `iw phy info` for the radio behind an interface; None when iw is missing or can't tell.
+async fn phy_info(iface: &str) -> Option<String> {
+    let dev_info =
+        match crate::nm::command_output(Command::new("iw").args(["dev", iface, "info"])).await {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).to_string()
+            }
+            _ => return None,
+        };
+    let phy = format!("phy{}", parse_wiphy_index(&dev_info)?);
+    match crate::nm::command_output(Command::new("iw").args(["phy", phy.as_str(), "info"])).await {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        _ => None,
+    }
+}
+
+// * Whether the adapter's driver can run SAE; assumed true when iw can't tell, NM has the last word.
+pub async fn supports_wpa3(iface: &str) -> bool {
+    phy_info(iface)
+        .await
+        .is_none_or(|info| phy_info_supports_sae(&info))
+}
+
+// * Only Wi-Fi 6E radios list 6 GHz frequencies; unlike WPA3 this is hidden when iw can't tell.
+pub async fn supports_6ghz(iface: &str) -> bool {
+    phy_info(iface)
+        .await
+        .is_some_and(|info| !phy_info_6ghz_channels(&info).is_empty())
+}
+
+fn parse_wiphy_index(dev_info: &str) -> Option<u32> {
+    dev_info
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("wiphy "))
+        .and_then(|index| index.trim().parse().ok())
+}
+
+// * mac80211 drivers advertise SAE via the AUTHENTICATE command; full-MAC ones need AP offload.
+fn phy_info_supports_sae(phy_info: &str) -> bool {
+    phy_info.contains("SAE with AUTHENTICATE command") || phy_info.contains("SAE_OFFLOAD_AP")
+}
+
+// * Best AP+STA combination in the "valid interface combinations" section, None when iw
+// * doesn't list any.
+fn phy_info_ap_sta_concurrency(phy_info: &str) -> Option<ApStaConcurrency> {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut lines = phy_info
+        .lines()
+        .skip_while(|line| line.trim() != "valid interface combinations:");
+    let depth = indent(lines.next()?);
+    let section = lines
+        .take_while(|line| indent(line) > depth)
+        .collect::<Vec<_>>()
+        .join(" ");
+    // * Reads the "<= n" right at the start of `text`
+    let limit = |text: &str| {
+        let digits = text.trim_start().strip_prefix("<=")?.trim_start();
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        digits[..end].parse::<u32>().ok()
+    };
+    let limit_after = |text: &str, marker: &str| limit(text.split_once(marker)?.1);
+
+    let mut combinations = 0;
+    let mut best_channels = None;
+    for combination in section.split("* ").skip(1) {
+        combinations += 1;
+        let (mut managed, mut ap) = (false, false);
+        for group in combination.split("#{").skip(1) {
+            let Some((types, rest)) = group.split_once('}') else {
+                continue;
+            };
+            let group_limit = limit(rest).unwrap_or(1);
+            let has_managed = types.split(',').any(|kind| kind.trim() == "managed");
+            let has_ap = types.split(',').any(|kind| kind.trim() == "AP");
+            // * Both types in one group only work together when the group allows two
+            if has_managed && has_ap && group_limit < 2 {
+                continue;
+            }
+            managed |= has_managed;
+            ap |= has_ap;
+        }
+        let total = limit_after(combination, "total").unwrap_or(1);
+        if managed && ap && total >= 2 {
+            let channels = limit_after(combination, "#channels").unwrap_or(1);
+            best_channels = best_channels.max(Some(channels));
+        }
+    }
+    if combinations == 0 {
+        return None;
+    }
+    Some(match best_channels {
+        None => ApStaConcurrency::Unsupported,
+        Some(1) => ApStaConcurrency::SameChannel,
+        Some(_) => ApStaConcurrency::Independent,
+    })
+}
+
+pub async fn ap_sta_concurrency(iface: &str) -> Option<ApStaConcurrency> {
+    phy_info(iface)
+        .await
+        .and_then(|info| phy_info_ap_sta_concurrency(&info))
+}
+
+// * Checked before a start while `iface` is connected to Wi-Fi; None when the connection
+// * is unaffected.
+pub async fn concurrency_guidance(
+    config: &crate::config::HotspotConfig,
+    iface: &str,
+) -> Option<ConcurrencyGuidance> {
+    let link = crate::repeater::station_link(iface).await?;
+    let concurrency = ap_sta_concurrency(iface).await;
+    let keeps_connection = matches!(
+        concurrency,
+        Some(ApStaConcurrency::SameChannel | ApStaConcurrency::Independent)
+    );
+    let hostapd = hostapd_backend_selected().await;
+    if config.mode != crate::config::HotspotMode::Repeater || hostapd {
+        let hint = if keeps_connection && !hostapd {
+            "; Wi-Fi Repeater mode can keep it connected"
+        } else {
+            ""
+        };
+        return Some(ConcurrencyGuidance::DropsWifi(format!(
+            "Starting the hotspot disconnects {} from {}{}",
+            iface, link.ssid, hint
+        )));
+    }
+
+    // * Repeaters with a second adapter leave this one's radio alone
+    if get_wifi_devices()
+        .await
+        .is_ok_and(|devices| devices.len() > 1)
+    {
+        return None;
+    }
+    // * The repeater itself refuses to start there
+    if concurrency == Some(ApStaConcurrency::Unsupported) {
+        return None;
+    }
+    let channel = crate::nm::channel_from_frequency(link.frequency);
+    let picked = config.channel.trim().parse::<u32>().ok();
+    let band = crate::nm::band_from_frequency(link.frequency);
+    let band_differs = !config.band.trim().eq_ignore_ascii_case("auto") && config.band != band;
+    (picked.is_some_and(|picked| picked != channel) || band_differs).then(|| {
+        ConcurrencyGuidance::SharesChannel(format!(
+            "The hotspot runs on {}'s channel {} ({}) instead of the one picked",
+            link.ssid, channel, band
+        ))
+    })
+}
+
+// * 6 GHz channels the radio may transmit on; "no IR" ones can't start a network, so an AP
+// * can't use them either.
+fn phy_info_6ghz_channels(phy_info: &str) -> Vec<u32> {
+    phy_info
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("* "))
+        .filter(|line| !line.contains("(disabled)") && !line.contains("no IR"))
+        .filter_map(|line| {
+            let (freq, rest) = line.split_once(" MHz [")?;
+            let freq = freq.trim().parse::<f32>().ok()? as u32;
+            let (channel, _) = rest.split_once(']')?;
+            (5955..=7115)
+                .contains(&freq)
+                .then(|| channel.parse().ok())
+                .flatten()
+        })
+        .collect()
+}
+
+pub(crate) fn dnsmasq_reservations_conf(
+    reservations: &[crate::config::HotspotDhcpReservation],
+) -> String {
+    let mut content = String::from("# Managed by adw-network; edit reservations in the app\n");
+    for reservation in reservations {
+        content.push_str(&format!(
+            "dhcp-host={},{}",
+            reservation.mac_address, reservation.ip_address
+        ));
+        if let Some(name) = &reservation.name {
+            content.push_str(&format!(",{}", name));
+        }
+        content.push('\n');
+    }
+    content
+}
+
+// * The directory is root-owned, so changes go through pkexec; unchanged files aren't rewritten.
+async fn sync_dhcp_reservations(
+    reservations: &[crate::config::HotspotDhcpReservation],
+) -> Result<()> {
+    let current = fs::read_to_string(DNSMASQ_RESERVATIONS_PATH).await.ok();
+    if reservations.is_empty() {
+        if current.is_none() {
+            return Ok(());
+        }
+        return run_command("pkexec", &["rm", "-f", DNSMASQ_RESERVATIONS_PATH]).await;
+    }
+
+    let content = dnsmasq_reservations_conf(reservations);
+    if current.as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+    let script = format!(
+        "mkdir -p \"$(dirname '{path}')\" && cat > '{path}'",
+        path = DNSMASQ_RESERVATIONS_PATH
+    );
+    let mut child = Command::new("pkexec")
+        .args(["sh", "-c", script.as_str()])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run pkexec: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use tokio::io::AsyncWriteExt;
+        stdin.write_all(content.as_bytes()).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        Err(anyhow!("Writing {} failed", DNSMASQ_RESERVATIONS_PATH))
+    } else {
+        Err(anyhow!(stderr))
+    }
+}
+
 pub async fn sync_runtime_rules_from_disk() -> Result<()> {
     runtime_tick(false).await
 }
@@ -467,10 +1076,14 @@ pub async fn sync_runtime_rules_from_disk() -> Result<()> {
 // Helper: determine if an error string indicates hotspot mode unsupported by the adapter
 pub fn is_hotspot_mode_not_supported_error(text: &str) -> bool {
     let lower = text.to_lowercase();
-    lower.contains("not supported") || lower.contains("hotspot mode") || lower.contains("operation not supported")
+    lower.contains("not supported")
+        || lower.contains("hotspot mode")
+        || lower.contains("operation not supported")
 }
 
-async fn get_connected_device_count_info_for_iface(iface: Option<&str>) -> Result<ConnectedClientCountInfo> {
+async fn get_connected_device_count_info_for_iface(
+    iface: Option<&str>,
+) -> Result<ConnectedClientCountInfo> {
     let arp_entries = read_arp_table(iface).await;
     let neighbor_count = arp_entries.len();
     let neighbor_available = !arp_entries.is_empty();
@@ -479,11 +1092,16 @@ async fn get_connected_device_count_info_for_iface(iface: Option<&str>) -> Resul
     let lease_count = lease_entries.len();
     let lease_available = !lease_entries.is_empty();
 
-    Ok(resolve_connected_client_count(neighbor_count, neighbor_available, lease_count, lease_available))
+    Ok(resolve_connected_client_count(
+        neighbor_count,
+        neighbor_available,
+        lease_count,
+        lease_available,
+    ))
 }
 
-async fn command_available(name: &str) -> bool {
-    match Command::new(name).arg("--help").output().await {
+pub(crate) async fn command_available(name: &str) -> bool {
+    match crate::nm::command_output(Command::new(name).arg("--help")).await {
         Ok(output) => {
             output.status.success() || !output.stderr.is_empty() || !output.stdout.is_empty()
         }
@@ -527,8 +1145,9 @@ async fn read_arp_table(iface: Option<&str>) -> Vec<(String, String)> {
         .collect()
 }
 
-fn apply_temporary_password_override(config: &crate::config::HotspotConfig) ->
-    crate::config::HotspotConfig {
+fn apply_temporary_password_override(
+    config: &crate::config::HotspotConfig,
+) -> crate::config::HotspotConfig {
     let mut effective = config.clone();
     if let Some(password) = load_temporary_password() {
         effective.password = password;
@@ -553,6 +1172,7 @@ async fn runtime_tick(force_apply: bool) -> Result<()> {
         if state.last_applied_signature.take().is_some() {
             changed = true;
         }
+        changed |= state.end_session();
         for client in &mut state.clients {
             changed |= client.last_connected_at.take().is_some();
             if client.last_upload_counter_bytes != 0 {
@@ -577,9 +1197,11 @@ async fn runtime_tick(force_apply: bool) -> Result<()> {
         return Ok(());
     };
 
-    let settings =
-        crate::config::load_app_settings(&crate::config::app_settings_path()).await.unwrap_or_default();
-    let config = crate::config::load_config(&crate::config::hotspot_config_path()).await
+    let settings = crate::config::load_app_settings(&crate::config::app_settings_path())
+        .await
+        .unwrap_or_default();
+    let config = crate::config::load_config(&crate::config::hotspot_config_path())
+        .await
         .map(|config| apply_temporary_password_override(&config))
         .unwrap_or_else(|_| crate::config::HotspotConfig::default());
     let clients = list_connected_clients().await.unwrap_or_default();
@@ -589,6 +1211,12 @@ async fn runtime_tick(force_apply: bool) -> Result<()> {
     changed |= update_runtime_activity_state(&mut state, &clients);
     let counters = read_runtime_counters().await;
     changed |= update_runtime_counter_state(&mut state, &counters);
+    changed |= state.record_session_tick(
+        chrono::Local::now().timestamp(),
+        &config.ssid,
+        clients.len(),
+        interface_byte_count(&iface).await,
+    );
 
     let plan = build_runtime_policy_plan(&config, &settings, &state, &clients).await;
     changed |= apply_blocked_reasons_to_state(&mut state, &plan.blocked_macs);
@@ -662,11 +1290,13 @@ fn update_runtime_activity_state(
         };
 
         if state.client_mut(&mac).is_none() {
-            state.clients.push(crate::hotspot_runtime::HotspotRuntimeClient {
-                mac_address: mac.clone(),
-                first_seen_at: now,
-                ..crate::hotspot_runtime::HotspotRuntimeClient::default()
-            });
+            state
+                .clients
+                .push(crate::hotspot_runtime::HotspotRuntimeClient {
+                    mac_address: mac.clone(),
+                    first_seen_at: now,
+                    ..crate::hotspot_runtime::HotspotRuntimeClient::default()
+                });
             state.normalize();
             changed = true;
         }
@@ -883,6 +1513,9 @@ async fn build_runtime_policy_plan(
     }
 
     plan.resolved_client_ips = resolved_client_ips(config).await.into_iter().collect();
+    if let Some(upstream) = &config.upstream_interface {
+        plan.upstream_route = upstream_route(upstream).await;
+    }
     plan
 }
 
@@ -897,7 +1530,10 @@ fn megabytes_to_bytes(value: u64) -> u64 {
     value.saturating_mul(1024).saturating_mul(1024)
 }
 
-fn build_runtime_signature(config: &crate::config::HotspotConfig, plan: &RuntimePolicyPlan) -> Result<String> {
+fn build_runtime_signature(
+    config: &crate::config::HotspotConfig,
+    plan: &RuntimePolicyPlan,
+) -> Result<String> {
     let signature = RuntimeRulesSignature {
         tracked_macs: plan.tracked_macs.iter().cloned().collect(),
         blocked_macs: plan.blocked_macs.keys().cloned().collect(),
@@ -905,6 +1541,10 @@ fn build_runtime_signature(config: &crate::config::HotspotConfig, plan: &Runtime
         global_download_limit_kbps: config.download_limit_kbps,
         max_connected_devices: config.max_connected_devices,
         mac_filter_mode: config.mac_filter_mode.clone(),
+        mac_filter_list: config.mac_filter_list.clone(),
+        isolate_clients: config.isolate_clients,
+        upstream_interface: config.upstream_interface.clone(),
+        upstream_route: plan.upstream_route.clone(),
         resolved_client_ips: plan
             .resolved_client_ips
             .iter()
@@ -966,12 +1606,59 @@ async fn resolve_blocked_domains(domains: &[String]) -> ResolvedDomainBlock {
     resolved
 }
 
+// * Everything the hotspot interface received and sent since it came up.
+async fn interface_byte_count(iface: &str) -> u64 {
+    let mut total = 0u64;
+    for counter in ["rx_bytes", "tx_bytes"] {
+        let path = format!("/sys/class/net/{}/statistics/{}", iface, counter);
+        total = total.saturating_add(
+            fs::read_to_string(path)
+                .await
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(0),
+        );
+    }
+    total
+}
+
+pub fn password_rotated_at() -> Option<i64> {
+    load_runtime_state_or_default().password_rotated_at
+}
+
+pub fn record_password_rotation(at: i64) {
+    let mut state = load_runtime_state_or_default();
+    state.password_rotated_at = Some(at);
+    save_runtime_state_safe(&state);
+}
+
+// * The next rotation check starts a fresh interval instead of measuring from an old rotation.
+pub fn reset_password_rotation() {
+    let mut state = load_runtime_state_or_default();
+    if state.password_rotated_at.take().is_some() {
+        save_runtime_state_safe(&state);
+    }
+}
+
+// * Past sessions, newest first, plus the one running now.
+pub fn session_history() -> (
+    Option<crate::hotspot_runtime::HotspotSession>,
+    Vec<crate::hotspot_runtime::HotspotSession>,
+) {
+    let state = load_runtime_state_or_default();
+    (state.current_session, state.past_sessions)
+}
+
 async fn read_runtime_counters() -> CounterSnapshot {
     let mut snapshot = CounterSnapshot::default();
-    let output = match Command::new("nft")
-        .args(["-j", "list", "table", "inet", HOTSPOT_NFT_TABLE])
-        .output()
-        .await
+    let output = match crate::nm::command_output(Command::new("nft").args([
+        "-j",
+        "list",
+        "table",
+        "inet",
+        HOTSPOT_NFT_TABLE,
+    ]))
+    .await
     {
         Ok(output) if output.status.success() => output,
         _ => return snapshot,
@@ -1016,8 +1703,9 @@ async fn read_runtime_counters() -> CounterSnapshot {
 }
 
 async fn apply_runtime_rules(config: &crate::config::HotspotConfig, iface: &str) -> Result<()> {
-    let settings =
-        crate::config::load_app_settings(&crate::config::app_settings_path()).await.unwrap_or_default();
+    let settings = crate::config::load_app_settings(&crate::config::app_settings_path())
+        .await
+        .unwrap_or_default();
     let state = load_runtime_state_or_default();
     let clients = list_connected_clients().await.unwrap_or_default();
     let plan = build_runtime_policy_plan(&config, &settings, &state, &clients).await;
@@ -1031,6 +1719,14 @@ async fn apply_runtime_rules_with_plan(
 ) -> Result<()> {
     cleanup_runtime_rules(iface).await.ok();
 
+    if config.mode == crate::config::HotspotMode::Bridged {
+        // * Bridged traffic never passes the host's routing, so there's nothing to hook into
+        return Ok(());
+    }
+    if let Some(upstream) = &config.upstream_interface {
+        apply_upstream_routing(iface, upstream, plan.upstream_route.as_ref()).await?;
+    }
+
     let support = advanced_support().await;
     let needs_tc = config.upload_limit_kbps.is_some()
         || config.download_limit_kbps.is_some()
@@ -1038,11 +1734,12 @@ async fn apply_runtime_rules_with_plan(
             .client_rules
             .iter()
             .any(|rule| rule.upload_limit_kbps.is_some() || rule.download_limit_kbps.is_some());
-    let needs_nft = !matches!(
-        config.mac_filter_mode,
-        crate::config::HotspotMacFilterMode::Disabled
-    ) && !config.client_rules.is_empty()
-        || config.max_connected_devices.is_some()
+    // * An empty allow list still has to shut everyone out
+    let needs_nft = match config.mac_filter_mode {
+        crate::config::HotspotMacFilterMode::Disabled => false,
+        crate::config::HotspotMacFilterMode::Allowlist => true,
+        crate::config::HotspotMacFilterMode::Blocklist => !config.mac_filter_list.is_empty(),
+    } || config.max_connected_devices.is_some()
         || !plan.blocked_macs.is_empty()
         || !plan.domain_blocks.is_empty()
         || config.client_rules.iter().any(|rule| {
@@ -1059,6 +1756,8 @@ async fn apply_runtime_rules_with_plan(
             "Hotspot policies require nftables (`nft`) for MAC, domain, and quota enforcement"
         ));
     }
+    // * Isolation still works at the AP without nft; the rule only adds routed traffic
+    let needs_nft = needs_nft || (config.isolate_clients && support.nft_available);
 
     if needs_nft && needs_tc {
         // Run nft and tc setup concurrently to reduce total startup time.
@@ -1079,9 +1778,174 @@ async fn cleanup_runtime_rules(iface: &str) -> Result<()> {
     let _ = run_command("tc", &["qdisc", "del", "dev", iface, "root"]).await;
     let _ = run_command("tc", &["qdisc", "del", "dev", iface, "ingress"]).await;
     let _ = run_command("nft", &["delete", "table", "inet", HOTSPOT_NFT_TABLE]).await;
+    let _ = run_command("ip", &["link", "del", HOTSPOT_IFB_DEVICE]).await;
+    for priority in HOTSPOT_RULE_PRIORITIES {
+        let _ = run_command("ip", &["-4", "rule", "del", "priority", priority]).await;
+    }
+    let _ = run_command(
+        "ip",
+        &["-4", "route", "flush", "table", HOTSPOT_ROUTE_TABLE],
+    )
+    .await;
     Ok(())
 }
 
+// * Connected interfaces that could carry the hotspot's traffic, from sysfs.
+pub async fn list_upstream_interfaces(exclude: &str) -> Vec<UpstreamInterface> {
+    let mut interfaces = Vec::new();
+    let Ok(mut entries) = fs::read_dir("/sys/class/net").await else {
+        return interfaces;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == exclude || name == "lo" || !validate_interface_name(&name) {
+            continue;
+        }
+        let path = entry.path();
+        let operstate = fs::read_to_string(path.join("operstate"))
+            .await
+            .unwrap_or_default();
+        if !matches!(operstate.trim(), "up" | "unknown") {
+            continue;
+        }
+        if let Some(kind) = upstream_kind(&name).await {
+            interfaces.push(UpstreamInterface { name, kind });
+        }
+    }
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+async fn upstream_kind(name: &str) -> Option<UpstreamKind> {
+    let path = std::path::Path::new("/sys/class/net").join(name);
+    let uevent = fs::read_to_string(path.join("uevent"))
+        .await
+        .unwrap_or_default();
+    let has_device = fs::try_exists(path.join("device")).await.unwrap_or(false);
+    let is_tun = fs::try_exists(path.join("tun_flags"))
+        .await
+        .unwrap_or(false);
+    classify_upstream(&uevent, has_device, is_tun)
+}
+
+// * Bridges, VLANs, veths and other virtual links aren't offered as an internet source
+fn classify_upstream(uevent: &str, has_device: bool, is_tun: bool) -> Option<UpstreamKind> {
+    let devtype = uevent
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("DEVTYPE="));
+    match devtype {
+        Some("wlan") => Some(UpstreamKind::Wifi),
+        Some("wwan") | Some("ppp") => Some(UpstreamKind::Mobile),
+        Some("wireguard") => Some(UpstreamKind::Vpn),
+        Some(_) => None,
+        None if is_tun => Some(UpstreamKind::Vpn),
+        None if has_device => Some(UpstreamKind::Ethernet),
+        None => None,
+    }
+}
+
+async fn upstream_route(upstream: &str) -> Option<UpstreamRoute> {
+    let operstate = fs::read_to_string(format!("/sys/class/net/{}/operstate", upstream))
+        .await
+        .ok()?;
+    if !matches!(operstate.trim(), "up" | "unknown") {
+        return None;
+    }
+    let output = crate::nm::command_output(
+        Command::new("ip").args(["-4", "route", "show", "default", "dev", upstream]),
+    )
+    .await
+    .ok()?;
+    Some(UpstreamRoute {
+        device: upstream.to_string(),
+        gateway: parse_default_gateway(&String::from_utf8_lossy(&output.stdout)),
+    })
+}
+
+fn parse_default_gateway(routes: &str) -> Option<String> {
+    routes.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        (words.next() == Some("default") && words.next() == Some("via"))
+            .then(|| words.next())
+            .flatten()
+            .filter(|gateway| is_ipv4(gateway))
+            .map(ToString::to_string)
+    })
+}
+
+// * Hotspot traffic keeps the main table for local subnets, then leaves through the chosen
+// * source; while that source is down it is dropped instead of leaking out the default route.
+async fn apply_upstream_routing(
+    iface: &str,
+    upstream: &str,
+    route: Option<&UpstreamRoute>,
+) -> Result<()> {
+    if !validate_interface_name(upstream) || upstream == iface {
+        return Err(anyhow!("Invalid internet source: {}", upstream));
+    }
+    let [local_priority, upstream_priority, fallback_priority] = HOTSPOT_RULE_PRIORITIES;
+
+    run_command(
+        "ip",
+        &[
+            "-4",
+            "rule",
+            "add",
+            "priority",
+            local_priority,
+            "iif",
+            iface,
+            "lookup",
+            "main",
+            "suppress_prefixlength",
+            "0",
+        ],
+    )
+    .await?;
+    if let Some(route) = route {
+        let mut args = vec!["-4", "route", "replace", "default"];
+        if let Some(gateway) = route.gateway.as_deref() {
+            args.extend(["via", gateway]);
+        }
+        args.extend(["dev", route.device.as_str(), "table", HOTSPOT_ROUTE_TABLE]);
+        run_command("ip", &args).await?;
+        run_command(
+            "ip",
+            &[
+                "-4",
+                "rule",
+                "add",
+                "priority",
+                upstream_priority,
+                "iif",
+                iface,
+                "lookup",
+                HOTSPOT_ROUTE_TABLE,
+            ],
+        )
+        .await?;
+    } else {
+        warn!(
+            "Internet source {} is down; hotspot clients are offline",
+            upstream
+        );
+    }
+    run_command(
+        "ip",
+        &[
+            "-4",
+            "rule",
+            "add",
+            "priority",
+            fallback_priority,
+            "iif",
+            iface,
+            "unreachable",
+        ],
+    )
+    .await
+}
+
 async fn apply_nft_policies(
     config: &crate::config::HotspotConfig,
     iface: &str,
@@ -1091,14 +1955,10 @@ async fn apply_nft_policies(
         return Err(anyhow!("Invalid interface name: {}", iface));
     }
 
-    let allowlist_macs: Vec<String> = config
-        .client_rules
-        .iter()
-        .filter_map(|rule| crate::config::normalize_mac_address(&rule.mac_address))
-        .collect();
     if plan.tracked_macs.is_empty()
         && plan.blocked_macs.is_empty()
         && plan.domain_blocks.is_empty()
+        && !config.isolate_clients
         && matches!(
             config.mac_filter_mode,
             crate::config::HotspotMacFilterMode::Disabled
@@ -1112,6 +1972,13 @@ async fn apply_nft_policies(
     script.push_str("  chain forward_filter {\n");
     script.push_str("    type filter hook forward priority 0; policy accept;\n");
 
+    if config.isolate_clients {
+        script.push_str(&format!(
+            "    iifname \"{}\" oifname \"{}\" drop\n",
+            iface, iface
+        ));
+    }
+
     for mac in &plan.tracked_macs {
         script.push_str(&format!(
             "    iifname \"{}\" ether saddr {} counter comment \"adw-quota-up:{}\"\n",
@@ -1155,7 +2022,7 @@ async fn apply_nft_policies(
 
     match config.mac_filter_mode {
         crate::config::HotspotMacFilterMode::Allowlist => {
-            for mac in &allowlist_macs {
+            for mac in &config.mac_filter_list {
                 script.push_str(&format!(
                     "    iifname \"{}\" ether saddr {} accept\n",
                     iface, mac
@@ -1169,7 +2036,7 @@ async fn apply_nft_policies(
             script.push_str(&format!("    oifname \"{}\" drop\n", iface));
         }
         crate::config::HotspotMacFilterMode::Blocklist => {
-            for mac in &allowlist_macs {
+            for mac in &config.mac_filter_list {
                 script.push_str(&format!(
                     "    iifname \"{}\" ether saddr {} drop\n",
                     iface, mac
@@ -1201,198 +2068,260 @@ async fn apply_tc_limits(
         return Err(anyhow!("Invalid interface name: {}", iface));
     }
 
-    if config.download_limit_kbps.is_some()
-        || config
-            .client_rules
-            .iter()
-            .any(|rule| rule.download_limit_kbps.is_some())
-    {
-        let parent_rate = config.download_limit_kbps.unwrap_or(1_000_000);
+    let download_limits =
+        client_rate_limits(config, resolved_clients, |rule| rule.download_limit_kbps);
+    if config.download_limit_kbps.is_some() || !download_limits.is_empty() {
+        apply_htb_limits(iface, config.download_limit_kbps, &download_limits, "dst").await?;
+    }
+
+    let upload_limits = client_rate_limits(config, resolved_clients, |rule| rule.upload_limit_kbps);
+    if config.upload_limit_kbps.is_some() || !upload_limits.is_empty() {
+        match redirect_ingress_to_ifb(iface).await {
+            Ok(()) => {
+                apply_htb_limits(
+                    HOTSPOT_IFB_DEVICE,
+                    config.upload_limit_kbps,
+                    &upload_limits,
+                    "src",
+                )
+                .await?;
+            }
+            Err(e) => {
+                warn!(
+                    "IFB redirect unavailable ({}); policing hotspot uploads instead",
+                    e
+                );
+                let _ = run_command("tc", &["qdisc", "del", "dev", iface, "ingress"]).await;
+                let _ = run_command("ip", &["link", "del", HOTSPOT_IFB_DEVICE]).await;
+                apply_upload_policing(iface, config.upload_limit_kbps, &upload_limits).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// * (rule index, client IPv4, kbit/s); the index keeps class IDs and filter priorities stable
+fn client_rate_limits<'a>(
+    config: &crate::config::HotspotConfig,
+    resolved_clients: &'a std::collections::BTreeMap<String, String>,
+    limit_for: impl Fn(&crate::config::HotspotClientRule) -> Option<u32>,
+) -> Vec<(usize, &'a str, u32)> {
+    config
+        .client_rules
+        .iter()
+        .enumerate()
+        .filter_map(|(index, rule)| {
+            let limit = limit_for(rule)?;
+            let ip = resolved_clients.get(&rule.mac_address.to_uppercase())?;
+            is_ipv4(ip).then_some((index, ip.as_str(), limit))
+        })
+        .collect()
+}
+
+// * Total cap on 1:1, one class per limited client under it, everything else in 1:999.
+async fn apply_htb_limits(
+    dev: &str,
+    total_kbps: Option<u32>,
+    client_limits: &[(usize, &str, u32)],
+    match_field: &str,
+) -> Result<()> {
+    let parent_rate = total_kbps.unwrap_or(1_000_000);
+    run_command(
+        "tc",
+        &[
+            "qdisc", "replace", "dev", dev, "root", "handle", "1:", "htb", "default", "999",
+        ],
+    )
+    .await?;
+    run_command(
+        "tc",
+        &[
+            "class",
+            "replace",
+            "dev",
+            dev,
+            "parent",
+            "1:",
+            "classid",
+            "1:1",
+            "htb",
+            "rate",
+            &format!("{}kbit", parent_rate),
+            "ceil",
+            &format!("{}kbit", parent_rate),
+        ],
+    )
+    .await?;
+    run_command(
+        "tc",
+        &[
+            "class",
+            "replace",
+            "dev",
+            dev,
+            "parent",
+            "1:1",
+            "classid",
+            "1:999",
+            "htb",
+            "rate",
+            &format!("{}kbit", parent_rate),
+            "ceil",
+            &format!("{}kbit", parent_rate),
+        ],
+    )
+    .await?;
+
+    for (index, ip, limit) in client_limits {
+        let classid = format!("1:{}", 10 + index);
         run_command(
             "tc",
             &[
-                "qdisc", "replace", "dev", iface, "root", "handle", "1:", "htb",
-                "default", "999",
+                "class",
+                "replace",
+                "dev",
+                dev,
+                "parent",
+                "1:1",
+                "classid",
+                &classid,
+                "htb",
+                "rate",
+                &format!("{}kbit", limit),
+                "ceil",
+                &format!("{}kbit", limit),
             ],
         )
         .await?;
         run_command(
             "tc",
             &[
-                "class",
+                "filter",
                 "replace",
                 "dev",
-                iface,
+                dev,
+                "protocol",
+                "ip",
                 "parent",
-                "1:",
-                "classid",
-                "1:1",
-                "htb",
-                "rate",
-                &format!("{}kbit", parent_rate),
-                "ceil",
-                &format!("{}kbit", parent_rate),
+                "1:0",
+                "prio",
+                &format!("{}", 10 + index),
+                "u32",
+                "match",
+                "ip",
+                match_field,
+                &format!("{}/32", ip),
+                "flowid",
+                &classid,
             ],
         )
         .await?;
+    }
+    Ok(())
+}
+
+// * Ingress traffic can't be queued, so uploads are shaped as egress of an IFB device.
+async fn redirect_ingress_to_ifb(iface: &str) -> Result<()> {
+    // * The device may survive from an earlier run; only bringing it up has to succeed
+    let _ = run_command("ip", &["link", "add", HOTSPOT_IFB_DEVICE, "type", "ifb"]).await;
+    run_command("ip", &["link", "set", "dev", HOTSPOT_IFB_DEVICE, "up"]).await?;
+    run_command("tc", &["qdisc", "replace", "dev", iface, "ingress"]).await?;
+    run_command(
+        "tc",
+        &[
+            "filter",
+            "replace",
+            "dev",
+            iface,
+            "parent",
+            "ffff:",
+            "protocol",
+            "all",
+            "prio",
+            "1",
+            "matchall",
+            "action",
+            "mirred",
+            "egress",
+            "redirect",
+            "dev",
+            HOTSPOT_IFB_DEVICE,
+        ],
+    )
+    .await
+}
+
+async fn apply_upload_policing(
+    iface: &str,
+    total_kbps: Option<u32>,
+    client_limits: &[(usize, &str, u32)],
+) -> Result<()> {
+    run_command("tc", &["qdisc", "replace", "dev", iface, "ingress"]).await?;
+
+    if let Some(limit) = total_kbps {
         run_command(
             "tc",
             &[
-                "class",
+                "filter",
                 "replace",
                 "dev",
                 iface,
                 "parent",
-                "1:1",
-                "classid",
-                "1:999",
-                "htb",
+                "ffff:",
+                "protocol",
+                "ip",
+                "prio",
+                "100",
+                "u32",
+                "match",
+                "u32",
+                "0",
+                "0",
+                "police",
                 "rate",
-                &format!("{}kbit", parent_rate),
-                "ceil",
-                &format!("{}kbit", parent_rate),
+                &format!("{}kbit", limit),
+                "burst",
+                "32k",
+                "drop",
+                "flowid",
+                ":1",
             ],
         )
         .await?;
-
-        for (index, rule) in config.client_rules.iter().enumerate() {
-            let Some(limit) = rule.download_limit_kbps else {
-                continue;
-            };
-            let Some(ip) = resolved_clients.get(&rule.mac_address.to_uppercase()) else {
-                continue;
-            };
-            if !is_ipv4(ip) {
-                continue;
-            }
-            let classid = format!("1:{}", 10 + index);
-            run_command(
-                "tc",
-                &[
-                    "class",
-                    "replace",
-                    "dev",
-                    iface,
-                    "parent",
-                    "1:1",
-                    "classid",
-                    &classid,
-                    "htb",
-                    "rate",
-                    &format!("{}kbit", limit),
-                    "ceil",
-                    &format!("{}kbit", limit),
-                ],
-            )
-            .await?;
-            run_command(
-                "tc",
-                &[
-                    "filter",
-                    "replace",
-                    "dev",
-                    iface,
-                    "protocol",
-                    "ip",
-                    "parent",
-                    "1:0",
-                    "prio",
-                    &format!("{}", 10 + index),
-                    "u32",
-                    "match",
-                    "ip",
-                    "dst",
-                    &format!("{}/32", ip),
-                    "flowid",
-                    &classid,
-                ],
-            )
-            .await?;
-        }
     }
 
-    if config.upload_limit_kbps.is_some()
-        || config
-            .client_rules
-            .iter()
-            .any(|rule| rule.upload_limit_kbps.is_some())
-    {
-        run_command("tc", &["qdisc", "replace", "dev", iface, "ingress"]).await?;
-
-        if let Some(limit) = config.upload_limit_kbps {
-            run_command(
-                "tc",
-                &[
-                    "filter",
-                    "replace",
-                    "dev",
-                    iface,
-                    "parent",
-                    "ffff:",
-                    "protocol",
-                    "ip",
-                    "prio",
-                    "100",
-                    "u32",
-                    "match",
-                    "u32",
-                    "0",
-                    "0",
-                    "police",
-                    "rate",
-                    &format!("{}kbit", limit),
-                    "burst",
-                    "32k",
-                    "drop",
-                    "flowid",
-                    ":1",
-                ],
-            )
-            .await?;
-        }
-
-        for (index, rule) in config.client_rules.iter().enumerate() {
-            let Some(limit) = rule.upload_limit_kbps else {
-                continue;
-            };
-            let Some(ip) = resolved_clients.get(&rule.mac_address.to_uppercase()) else {
-                continue;
-            };
-            if !is_ipv4(ip) {
-                continue;
-            }
-            run_command(
-                "tc",
-                &[
-                    "filter",
-                    "replace",
-                    "dev",
-                    iface,
-                    "parent",
-                    "ffff:",
-                    "protocol",
-                    "ip",
-                    "prio",
-                    &format!("{}", 10 + index),
-                    "u32",
-                    "match",
-                    "ip",
-                    "src",
-                    &format!("{}/32", ip),
-                    "police",
-                    "rate",
-                    &format!("{}kbit", limit),
-                    "burst",
-                    "32k",
-                    "drop",
-                    "flowid",
-                    ":1",
-                ],
-            )
-            .await?;
-        }
+    for (index, ip, limit) in client_limits {
+        run_command(
+            "tc",
+            &[
+                "filter",
+                "replace",
+                "dev",
+                iface,
+                "parent",
+                "ffff:",
+                "protocol",
+                "ip",
+                "prio",
+                &format!("{}", 10 + index),
+                "u32",
+                "match",
+                "ip",
+                "src",
+                &format!("{}/32", ip),
+                "police",
+                "rate",
+                &format!("{}kbit", limit),
+                "burst",
+                "32k",
+                "drop",
+                "flowid",
+                ":1",
+            ],
+        )
+        .await?;
     }
-
     Ok(())
 }
 
@@ -1432,7 +2361,7 @@ async fn reverse_lookup_hostname(ip: &str) -> Option<String> {
 }
 
 async fn run_command(command: &str, args: &[&str]) -> Result<()> {
-    let output = Command::new(command).args(args).output().await?;
+    let output = crate::nm::command_output(Command::new(command).args(args)).await?;
     if output.status.success() {
         return Ok(());
     }
@@ -1483,6 +2412,101 @@ fn resolve_connected_client_count(
 mod tests {
     use super::*;
 
+    #[test]
+    fn classifies_upstream_interfaces_from_sysfs() {
+        assert_eq!(
+            classify_upstream("INTERFACE=wlan1\nDEVTYPE=wlan\n", true, false),
+            Some(UpstreamKind::Wifi)
+        );
+        assert_eq!(
+            classify_upstream("INTERFACE=enp3s0\n", true, false),
+            Some(UpstreamKind::Ethernet)
+        );
+        assert_eq!(
+            classify_upstream("INTERFACE=tun0\n", false, true),
+            Some(UpstreamKind::Vpn)
+        );
+        assert_eq!(
+            classify_upstream("DEVTYPE=wireguard\n", false, false),
+            Some(UpstreamKind::Vpn)
+        );
+        assert_eq!(classify_upstream("DEVTYPE=bridge\n", false, false), None);
+        assert_eq!(classify_upstream("INTERFACE=veth0\n", false, false), None);
+    }
+
+    #[test]
+    fn parses_the_default_gateway_of_an_upstream() {
+        assert_eq!(
+            parse_default_gateway(
+                "default via 192.168.1.1 proto dhcp src 192.168.1.20 metric 100\n"
+            ),
+            Some("192.168.1.1".to_string())
+        );
+        assert_eq!(parse_default_gateway("default scope link\n"), None);
+        assert_eq!(parse_default_gateway(""), None);
+    }
+
+    #[test]
+    fn renders_dhcp_reservations_as_dnsmasq_hosts() {
+        let reservations = vec![
+            crate::config::HotspotDhcpReservation::parse("aa:bb:cc:dd:ee:ff", "10.42.0.20", "pi")
+                .unwrap(),
+            crate::config::HotspotDhcpReservation::parse("11:22:33:44:55:66", "10.42.0.21", "")
+                .unwrap(),
+        ];
+        let content = dnsmasq_reservations_conf(&reservations);
+        let hosts: Vec<&str> = content.lines().skip(1).collect();
+        assert_eq!(
+            hosts,
+            vec![
+                "dhcp-host=AA:BB:CC:DD:EE:FF,10.42.0.20,pi",
+                "dhcp-host=11:22:33:44:55:66,10.42.0.21"
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_sae_support_from_iw_output() {
+        let dev_info = "Interface wlan0\n\tifindex 3\n\twdev 0x1\n\taddr 00:11:22:33:44:55\n\ttype managed\n\twiphy 1\n";
+        assert_eq!(parse_wiphy_index(dev_info), Some(1));
+        assert_eq!(parse_wiphy_index("Interface wlan0\n"), None);
+
+        assert!(phy_info_supports_sae(
+            "Wiphy phy0\n\tDevice supports SAE with AUTHENTICATE command\n"
+        ));
+        assert!(phy_info_supports_sae("\tSupported extended features:\n\t\t* [ SAE_OFFLOAD_AP ]: AP mode SAE authentication offload\n"));
+        assert!(!phy_info_supports_sae(
+            "Wiphy phy0\n\tSupported interface modes:\n\t\t * AP\n"
+        ));
+    }
+
+    #[test]
+    fn reads_ap_sta_concurrency_from_interface_combinations() {
+        let single_channel = "Wiphy phy0\n\tvalid interface combinations:\n\t\t * #{ managed } <= 1, #{ AP, P2P-client, P2P-GO } <= 1, #{ P2P-device } <= 1,\n\t\t   total <= 3, #channels <= 1\n\tHT Capability overrides:\n";
+        assert_eq!(
+            phy_info_ap_sta_concurrency(single_channel),
+            Some(ApStaConcurrency::SameChannel)
+        );
+        let dual_channel = single_channel.replace("#channels <= 1", "#channels <= 2");
+        assert_eq!(
+            phy_info_ap_sta_concurrency(&dual_channel),
+            Some(ApStaConcurrency::Independent)
+        );
+        let exclusive = "\tvalid interface combinations:\n\t\t * #{ managed, AP } <= 1,\n\t\t   total <= 1, #channels <= 1\n";
+        assert_eq!(
+            phy_info_ap_sta_concurrency(exclusive),
+            Some(ApStaConcurrency::Unsupported)
+        );
+        assert_eq!(phy_info_ap_sta_concurrency("Wiphy phy0\n"), None);
+    }
+
+    #[test]
+    fn finds_usable_6ghz_channels_in_phy_info() {
+        let phy_info = "\tBand 2:\n\t\tFrequencies:\n\t\t\t* 5180.0 MHz [36] (22.0 dBm)\n\t\t\t* 5260.0 MHz [52] (22.0 dBm) (radar detection)\n\tBand 4:\n\t\tFrequencies:\n\t\t\t* 5955.0 MHz [1] (disabled)\n\t\t\t* 5975.0 MHz [5] (22.0 dBm)\n\t\t\t* 6055.0 MHz [21] (22.0 dBm) (no IR)\n\t\t\t* 6135.0 MHz [37] (22.0 dBm)\n";
+        assert_eq!(phy_info_6ghz_channels(phy_info), vec![5, 37]);
+        assert!(phy_info_6ghz_channels("\t\t\t* 2412 MHz [1] (20.0 dBm)\n").is_empty());
+    }
+
     #[test]
     fn resolves_count_without_estimate_for_small_delta() {
         let info = resolve_connected_client_count(3, true, 4, true);
@@ -0,0 +1,178 @@
+// * ./src/hotspot_automation.rs
+
+use chrono::{Datelike, Local, Timelike};
+use gtk4::glib;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::hotspot;
+use crate::notifications::{self, NotificationKind};
+use crate::polling::PollScheduler;
+
+const IDLE_POLL: Duration = Duration::from_secs(30);
+const SCHEDULE_POLL: Duration = Duration::from_secs(30);
+
+// * Remembers since when the hotspot has had no clients, across polls.
+#[derive(Debug, Default)]
+struct IdleTracker {
+    empty_since: Option<Instant>,
+}
+
+impl IdleTracker {
+    // * True once the hotspot has been empty for the whole timeout
+    fn observe(&mut self, clients: usize, now: Instant, timeout: Duration) -> bool {
+        if clients > 0 {
+            self.empty_since = None;
+            return false;
+        }
+        let since = *self.empty_since.get_or_insert(now);
+        now.duration_since(since) >= timeout
+    }
+
+    fn reset(&mut self) {
+        self.empty_since = None;
+    }
+}
+
+// * Stops the hotspot after the configured number of minutes without connected devices.
+pub fn watch_idle_hotspot(scheduler: &PollScheduler) {
+    let tracker = Rc::new(RefCell::new(IdleTracker::default()));
+    let in_flight = Rc::new(Cell::new(false));
+
+    scheduler.subscribe("hotspot-idle", IDLE_POLL, IDLE_POLL, move || {
+        if in_flight.replace(true) {
+            return;
+        }
+        let tracker = tracker.clone();
+        let in_flight = in_flight.clone();
+        glib::spawn_future_local(async move {
+            poll_idle_hotspot(&tracker).await;
+            in_flight.set(false);
+        });
+    });
+}
+
+async fn poll_idle_hotspot(tracker: &RefCell<IdleTracker>) {
+    let timeout_minutes = config::load_config(&config::hotspot_config_path())
+        .await
+        .ok()
+        .and_then(|config| config.idle_timeout_minutes);
+    let Some(minutes) = timeout_minutes else {
+        tracker.borrow_mut().reset();
+        return;
+    };
+    if !hotspot::is_hotspot_active().await.unwrap_or(false) {
+        tracker.borrow_mut().reset();
+        return;
+    }
+    let clients = match hotspot::get_connected_device_count().await {
+        Ok(clients) => clients,
+        Err(e) => {
+            log::debug!("Failed to count hotspot clients: {}", e);
+            return;
+        }
+    };
+
+    let timeout = Duration::from_secs(u64::from(minutes) * 60);
+    if !tracker
+        .borrow_mut()
+        .observe(clients, Instant::now(), timeout)
+    {
+        return;
+    }
+    tracker.borrow_mut().reset();
+
+    log::info!("Hotspot idle for {} minute(s), turning it off", minutes);
+    match hotspot::stop_hotspot().await {
+        Ok(()) => notifications::send(
+            NotificationKind::HotspotAutoOff,
+            "Hotspot Turned Off",
+            &format!(
+                "No devices were connected for {} minute{}",
+                minutes,
+                if minutes == 1 { "" } else { "s" }
+            ),
+        ),
+        Err(e) => log::warn!("Failed to turn off idle hotspot: {}", e),
+    }
+}
+
+// * Starts and stops the hotspot at the edges of its schedule; in between, manual toggles stick.
+pub fn watch_hotspot_schedule(scheduler: &PollScheduler) {
+    // * What the schedule asked for on the previous poll; None until the first one
+    let last_wanted: Rc<Cell<Option<bool>>> = Rc::new(Cell::new(None));
+    let in_flight = Rc::new(Cell::new(false));
+
+    scheduler.subscribe(
+        "hotspot-schedule",
+        SCHEDULE_POLL,
+        SCHEDULE_POLL,
+        move || {
+            if in_flight.replace(true) {
+                return;
+            }
+            let last_wanted = last_wanted.clone();
+            let in_flight = in_flight.clone();
+            glib::spawn_future_local(async move {
+                poll_hotspot_schedule(&last_wanted).await;
+                in_flight.set(false);
+            });
+        },
+    );
+}
+
+async fn poll_hotspot_schedule(last_wanted: &Cell<Option<bool>>) {
+    let Ok(hotspot_config) = config::load_config(&config::hotspot_config_path()).await else {
+        return;
+    };
+    let now = Local::now();
+    let Some(wanted) = hotspot_config.scheduled_on_at(
+        now.weekday().num_days_from_monday() as u8,
+        now.hour() * 60 + now.minute(),
+    ) else {
+        last_wanted.set(None);
+        return;
+    };
+    let previous = last_wanted.replace(Some(wanted));
+    // * On the first poll only bring a due hotspot up; never cut off one started by hand
+    let changed = match previous {
+        Some(previous) => previous != wanted,
+        None => wanted,
+    };
+    if !changed {
+        return;
+    }
+
+    let active = hotspot::is_hotspot_active().await.unwrap_or(false);
+    if wanted && !active {
+        log::info!("Hotspot schedule: starting");
+        if let Err(e) = hotspot::start_saved_hotspot(None).await {
+            log::warn!("Scheduled hotspot start failed: {}", e);
+        }
+    } else if !wanted && active {
+        log::info!("Hotspot schedule: stopping");
+        if let Err(e) = hotspot::stop_hotspot().await {
+            log::warn!("Scheduled hotspot stop failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_timer_restarts_when_a_client_shows_up() {
+        let timeout = Duration::from_secs(600);
+        let start = Instant::now();
+        let mut tracker = IdleTracker::default();
+
+        assert!(!tracker.observe(0, start, timeout));
+        assert!(!tracker.observe(0, start + Duration::from_secs(300), timeout));
+        assert!(!tracker.observe(1, start + Duration::from_secs(400), timeout));
+        assert!(!tracker.observe(0, start + Duration::from_secs(700), timeout));
+        assert!(tracker.observe(0, start + Duration::from_secs(1300), timeout));
+    }
+}
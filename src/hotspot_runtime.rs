@@ -4,6 +4,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+const SESSION_HISTORY_LEN: usize = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HotspotRuntimeState {
     #[serde(default)]
@@ -14,6 +16,39 @@ pub struct HotspotRuntimeState {
     pub last_applied_signature: Option<String>,
     #[serde(default)]
     pub clients: Vec<HotspotRuntimeClient>,
+    #[serde(default)]
+    pub current_session: Option<HotspotSession>,
+    // * Newest first
+    #[serde(default)]
+    pub past_sessions: Vec<HotspotSession>,
+    // * Interface a running repeater joined the extended network on
+    #[serde(default)]
+    pub repeater_station: Option<String>,
+    // * Unix seconds of the last automatic password change, or of when rotation was turned on
+    #[serde(default)]
+    pub password_rotated_at: Option<i64>,
+}
+
+// * One run of the hotspot from start to stop.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct HotspotSession {
+    pub ssid: String,
+    pub started_at: i64,
+    // * Last time the session was seen running; its end once it is in the history
+    pub last_seen_at: i64,
+    #[serde(default)]
+    pub peak_clients: u32,
+    #[serde(default)]
+    pub bytes_served: u64,
+    // * Hotspot interface rx+tx at the previous tick, to turn counters into a running total
+    #[serde(default)]
+    pub last_interface_bytes: u64,
+}
+
+impl HotspotSession {
+    pub fn uptime_seconds(&self) -> u64 {
+        self.last_seen_at.saturating_sub(self.started_at).max(0) as u64
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -82,6 +117,44 @@ impl HotspotRuntimeState {
             .dedup_by(|left, right| left.mac_address == right.mac_address);
     }
 
+    // * Folds one tick of a running hotspot into the current session, starting one if needed.
+    // * Returns true when the session changed.
+    pub fn record_session_tick(
+        &mut self,
+        now: i64,
+        ssid: &str,
+        client_count: usize,
+        interface_bytes: u64,
+    ) -> bool {
+        let before = self.current_session.clone();
+        let session = self.current_session.get_or_insert_with(|| HotspotSession {
+            ssid: ssid.to_string(),
+            started_at: now,
+            last_seen_at: now,
+            last_interface_bytes: interface_bytes,
+            ..HotspotSession::default()
+        });
+        // * Counters restart when the interface is recreated
+        let delta = interface_bytes
+            .checked_sub(session.last_interface_bytes)
+            .unwrap_or(interface_bytes);
+        session.bytes_served = session.bytes_served.saturating_add(delta);
+        session.last_interface_bytes = interface_bytes;
+        session.peak_clients = session.peak_clients.max(client_count as u32);
+        session.last_seen_at = now;
+        self.current_session != before
+    }
+
+    // * Moves the running session into the history; false when none was running.
+    pub fn end_session(&mut self) -> bool {
+        let Some(session) = self.current_session.take() else {
+            return false;
+        };
+        self.past_sessions.insert(0, session);
+        self.past_sessions.truncate(SESSION_HISTORY_LEN);
+        true
+    }
+
     pub fn client_mut(&mut self, mac_address: &str) -> Option<&mut HotspotRuntimeClient> {
         self.clients
             .iter_mut()
@@ -114,3 +187,27 @@ pub fn save_runtime_state(path: &std::path::Path, state: &HotspotRuntimeState) -
     std::fs::write(path, json)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_tracks_peak_clients_and_bytes_across_counter_resets() {
+        let mut state = HotspotRuntimeState::default();
+        state.record_session_tick(100, "Cafe", 1, 5_000);
+        state.record_session_tick(108, "Cafe", 3, 9_000);
+        // * Interface recreated: counters start over
+        state.record_session_tick(116, "Cafe", 2, 1_000);
+
+        let session = state.current_session.clone().unwrap();
+        assert_eq!(session.started_at, 100);
+        assert_eq!(session.uptime_seconds(), 16);
+        assert_eq!(session.peak_clients, 3);
+        assert_eq!(session.bytes_served, 5_000);
+
+        assert!(state.end_session());
+        assert!(!state.end_session());
+        assert_eq!(state.past_sessions, vec![session]);
+    }
+}
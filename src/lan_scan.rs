@@ -0,0 +1,222 @@
+// * ./src/lan_scan.rs
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::process::Stdio;
+use tokio::net::UdpSocket;
+use tokio::process::Command;
+use tokio::time::{sleep, timeout, Duration};
+
+use crate::nm;
+
+// * Larger subnets are narrowed to the /22 around our own address (1022 hosts)
+const MAX_SCAN_PREFIX: u32 = 22;
+// * Any UDP datagram makes the kernel ARP for the target; the discard port keeps it harmless
+const DISCARD_PORT: u16 = 9;
+const PROBE_BATCH: usize = 64;
+const ARP_SETTLE: Duration = Duration::from_secs(2);
+const MDNS_BROWSE_TIMEOUT: Duration = Duration::from_secs(4);
+const REVERSE_LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
+const ARP_FLAG_COMPLETE: u32 = 0x2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanDevice {
+    pub ip: Ipv4Addr,
+    pub mac: String,
+    pub hostname: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LanScanResult {
+    pub interface: String,
+    pub own_address: Option<Ipv4Addr>,
+    pub devices: Vec<LanDevice>,
+}
+
+// * ARP sweep of the primary connection's subnet, with names from mDNS and reverse DNS.
+pub async fn scan() -> Result<LanScanResult> {
+    let (iface, address, prefix) = nm::primary_ipv4_subnet()
+        .await?
+        .ok_or_else(|| anyhow!("Not connected to a network"))?;
+
+    probe_hosts(&scan_hosts(address, prefix)).await;
+    sleep(ARP_SETTLE).await;
+
+    let arp = tokio::fs::read_to_string("/proc/net/arp").await?;
+    let mut devices: Vec<LanDevice> = parse_proc_arp(&arp, &iface)
+        .into_iter()
+        .filter(|(ip, _)| *ip != address)
+        .map(|(ip, mac)| LanDevice {
+            ip,
+            mac,
+            hostname: None,
+        })
+        .collect();
+
+    let mdns = mdns_hostnames().await;
+    let lookups = devices.iter().map(|device| {
+        let known = mdns.get(&device.ip).cloned();
+        let ip = device.ip;
+        async move {
+            match known {
+                Some(name) => Some(name),
+                None => reverse_lookup(ip).await,
+            }
+        }
+    });
+    let names = futures::future::join_all(lookups).await;
+    for (device, name) in devices.iter_mut().zip(names) {
+        device.hostname = name;
+    }
+    devices.sort_by_key(|device| device.ip);
+
+    Ok(LanScanResult {
+        interface: iface,
+        own_address: Some(address),
+        devices,
+    })
+}
+
+pub fn scan_hosts(address: Ipv4Addr, prefix: u32) -> Vec<Ipv4Addr> {
+    let prefix = prefix.clamp(MAX_SCAN_PREFIX, 30);
+    let mask = u32::MAX << (32 - prefix);
+    let network = u32::from(address) & mask;
+    let broadcast = network | !mask;
+    ((network + 1)..broadcast)
+        .map(Ipv4Addr::from)
+        .filter(|host| *host != address)
+        .collect()
+}
+
+async fn probe_hosts(hosts: &[Ipv4Addr]) {
+    let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            log::warn!("Failed to open probe socket: {}", e);
+            return;
+        }
+    };
+    for batch in hosts.chunks(PROBE_BATCH) {
+        for host in batch {
+            // ? Unreachable hosts fail here once ARP gives up; that is the expected outcome
+            let _ = socket.send_to(&[], (*host, DISCARD_PORT)).await;
+        }
+        sleep(Duration::from_millis(20)).await;
+    }
+}
+
+// * Complete entries on `iface` from /proc/net/arp
+pub fn parse_proc_arp(content: &str, iface: &str) -> Vec<(Ipv4Addr, String)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 || fields[5] != iface {
+                return None;
+            }
+            let flags = u32::from_str_radix(fields[2].trim_start_matches("0x"), 16).ok()?;
+            if flags & ARP_FLAG_COMPLETE == 0 || fields[3] == "00:00:00:00:00:00" {
+                return None;
+            }
+            Some((fields[0].parse().ok()?, fields[3].to_ascii_uppercase()))
+        })
+        .collect()
+}
+
+async fn mdns_hostnames() -> HashMap<Ipv4Addr, String> {
+    let mut command = Command::new("avahi-browse");
+    command
+        .args(["--all", "--resolve", "--parsable", "--terminate"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    match timeout(MDNS_BROWSE_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => parse_avahi_browse(&String::from_utf8_lossy(&output.stdout)),
+        Ok(Err(e)) => {
+            log::debug!("avahi-browse unavailable: {}", e);
+            HashMap::new()
+        }
+        Err(_) => {
+            log::debug!("avahi-browse timed out");
+            HashMap::new()
+        }
+    }
+}
+
+// * Resolved lines look like `=;iface;IPv4;name;type;domain;host.local;address;port;txt`
+pub fn parse_avahi_browse(output: &str) -> HashMap<Ipv4Addr, String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with("=;"))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(';').collect();
+            if fields.len() < 8 || fields[2] != "IPv4" {
+                return None;
+            }
+            let ip = fields[7].parse().ok()?;
+            let host = fields[6].trim_end_matches(".local").trim();
+            (!host.is_empty()).then(|| (ip, host.to_string()))
+        })
+        .collect()
+}
+
+async fn reverse_lookup(ip: Ipv4Addr) -> Option<String> {
+    let lookup = tokio::task::spawn_blocking(move || dns_lookup::lookup_addr(&IpAddr::V4(ip)).ok());
+    let name = timeout(REVERSE_LOOKUP_TIMEOUT, lookup).await.ok()?.ok()??;
+    // * Resolvers without a PTR record echo the address back
+    (name != ip.to_string()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_hosts_skips_network_broadcast_and_self() {
+        let hosts = scan_hosts(Ipv4Addr::new(192, 168, 1, 10), 24);
+        assert_eq!(hosts.len(), 253);
+        assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(hosts[252], Ipv4Addr::new(192, 168, 1, 254));
+        assert!(!hosts.contains(&Ipv4Addr::new(192, 168, 1, 10)));
+
+        // * A /16 is narrowed to the /22 around us
+        let hosts = scan_hosts(Ipv4Addr::new(10, 0, 5, 7), 16);
+        assert_eq!(hosts.len(), 1021);
+        assert_eq!(hosts[0], Ipv4Addr::new(10, 0, 4, 1));
+    }
+
+    #[test]
+    fn parses_complete_arp_entries_for_the_interface() {
+        let arp = concat!(
+            "IP address       HW type     Flags       HW address            Mask     Device\n",
+            "192.168.1.1      0x1         0x2         aa:bb:cc:dd:ee:ff     *        wlan0\n",
+            "192.168.1.20     0x1         0x0         00:00:00:00:00:00     *        wlan0\n",
+            "10.0.0.2         0x1         0x2         11:22:33:44:55:66     *        eth0\n",
+        );
+        assert_eq!(
+            parse_proc_arp(arp, "wlan0"),
+            vec![(
+                Ipv4Addr::new(192, 168, 1, 1),
+                "AA:BB:CC:DD:EE:FF".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_avahi_resolved_lines() {
+        let output = concat!(
+            "+;wlan0;IPv4;Printer;_ipp._tcp;local\n",
+            "=;wlan0;IPv4;Printer;_ipp._tcp;local;printer.local;192.168.1.30;631;\"\"\n",
+            "=;wlan0;IPv6;Printer;_ipp._tcp;local;printer.local;fe80::1;631;\"\"\n",
+        );
+        let names = parse_avahi_browse(output);
+        assert_eq!(names.len(), 1);
+        assert_eq!(
+            names
+                .get(&Ipv4Addr::new(192, 168, 1, 30))
+                .map(String::as_str),
+            Some("printer")
+        );
+    }
+}
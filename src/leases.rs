@@ -120,6 +120,7 @@ pub async fn load_lease_entries_with_stats() -> LeaseLoadResult {
     for path in &fallback_paths {
         candidate_paths.insert(Path::new(path).to_path_buf());
     }
+    candidate_paths.insert(crate::hostapd::lease_file_path());
 
     for path in candidate_paths {
         if let Ok(content) = tokio::fs::read_to_string(&path).await {
@@ -1,27 +1,59 @@
 // * ./src/lib.rs
 
 use chrono::Local;
+use gtk4::gio;
 use gtk4::prelude::*;
 use libadwaita as adw;
 use std::fs::OpenOptions;
 use std::io::Write;
 
+use crate::ui::compact_window::{self, CompactLauncher, COMPACT_ACTION};
+use crate::ui::setup_wizard;
 use crate::window::AdwNetworkWindow;
 
+pub mod bluetooth;
+pub mod captive_portal;
+pub mod cli;
 pub mod config;
+pub mod dbus_service;
+pub mod diagnostics;
+pub mod ethtool;
+pub mod event_log;
+pub mod hostapd;
 pub mod hotspot;
+pub mod hotspot_automation;
 pub mod hotspot_runtime;
+pub mod lan_scan;
 pub mod leases;
 pub mod modem_manager;
+pub mod network_trust;
 pub mod nm;
 pub mod nm_dbus;
+pub mod nm_error;
+pub mod nm_mock;
+pub mod notifications;
+pub mod oui;
+pub mod polling;
 pub mod profiles;
+pub mod public_ip;
 pub mod qr;
 pub mod qr_dialog;
+pub mod quick_connect;
+pub mod reachability;
+pub mod repeater;
+pub mod resolved;
+pub mod secret_agent;
 pub mod secrets;
 pub mod state;
+pub mod tailscale;
+pub mod traffic;
+pub mod tray;
 mod ui;
+pub mod usage;
+pub mod wake_on_lan;
 mod window;
+pub mod wireguard;
+pub mod wpa_import;
 
 const APP_ID: &str = "com.github.adw-network";
 
@@ -117,7 +149,16 @@ async fn cleanup_hotspot_on_exit() {
     }
 }
 
-pub fn run() -> glib::ExitCode {
+pub(crate) fn install_requested_backend() {
+    if std::env::var(nm_mock::BACKEND_ENV).ok().as_deref() == Some("mock") {
+        log::info!("Using the in-memory mock network backend");
+        if let Err(e) = nm::install_backend(Box::new(nm_mock::MockBackend::demo())) {
+            log::warn!("Failed to install mock backend: {}", e);
+        }
+    }
+}
+
+pub fn run(compact: bool) -> glib::ExitCode {
     normalize_gsk_renderer_env();
     setup_logging();
     log::info!("Application starting...");
@@ -131,9 +172,26 @@ pub fn run() -> glib::ExitCode {
     };
     let _guard = rt.enter();
 
+    install_requested_backend();
+
     rt.block_on(async {
         if let Err(e) = nm::init_signal_listeners().await {
-            log::warn!("Failed to initialize NM signal listeners (polling fallback active): {}", e);
+            log::warn!(
+                "Failed to initialize NM signal listeners (polling fallback active): {}",
+                e
+            );
+        }
+        if let Err(e) = nm::detect_capabilities().await {
+            log::warn!(
+                "Failed to detect NetworkManager version (all options enabled): {}",
+                e
+            );
+        }
+        if let Err(e) = secret_agent::register(APP_ID).await {
+            log::warn!(
+                "Failed to register secret agent (password prompts unavailable): {}",
+                e
+            );
         }
     });
 
@@ -143,8 +201,41 @@ pub fn run() -> glib::ExitCode {
 
     let app = adw::Application::builder().application_id(APP_ID).build();
 
-    app.connect_activate(build_ui);
-    let result = app.run();
+    app.connect_startup(|app| {
+        glib::spawn_future_local(hotspot::restore_hotspot_on_launch());
+        let launcher = CompactLauncher::new();
+        let action = gio::SimpleAction::new(COMPACT_ACTION, None);
+        let app_weak = app.downgrade();
+        action.connect_activate(move |_, _| {
+            if let Some(app) = app_weak.upgrade() {
+                launcher.present(&app);
+            }
+        });
+        app.add_action(&action);
+    });
+
+    if compact {
+        if let Err(e) = app.register(gio::Cancellable::NONE) {
+            log::error!("Failed to register application: {}", e);
+            return glib::ExitCode::FAILURE;
+        }
+        // * Already running (maybe hidden to the tray): hand the request to that instance
+        if app.is_remote() {
+            app.activate_action(COMPACT_ACTION, None);
+            return glib::ExitCode::SUCCESS;
+        }
+    }
+
+    let start_compact = std::cell::Cell::new(compact);
+    app.connect_activate(move |app| {
+        if start_compact.replace(false) {
+            app.activate_action(COMPACT_ACTION, None);
+        } else {
+            build_ui(app);
+        }
+    });
+    // * Arguments were already parsed by clap
+    let result = app.run_with_args(&std::env::args().take(1).collect::<Vec<_>>());
 
     rt.shutdown_timeout(std::time::Duration::from_secs(5));
 
@@ -152,9 +243,27 @@ pub fn run() -> glib::ExitCode {
 }
 
 fn build_ui(app: &adw::Application) {
+    // * A window hidden to the tray is still alive; bring it back instead of building another
+    if let Some(window) = app
+        .windows()
+        .into_iter()
+        .find(|window| window.widget_name() != compact_window::WINDOW_NAME)
+    {
+        window.present();
+        return;
+    }
+    if setup_wizard::is_first_run() {
+        log::info!("First run, showing the setup wizard");
+        let app_weak = app.downgrade();
+        setup_wizard::present(app, move || {
+            if let Some(app) = app_weak.upgrade() {
+                build_ui(&app);
+            }
+        });
+        return;
+    }
     log::info!("Building UI...");
     let window = AdwNetworkWindow::new(app);
-    window.window.set_size_request(480, 640);
     window.present();
     log::info!("UI built and window presented");
 }
@@ -1,5 +1,12 @@
 // * ./src/main.rs
 
+use adwaita_network::cli::Cli;
+use clap::Parser;
+
 fn main() -> glib::ExitCode {
-    adwaita_network::run()
+    let cli = Cli::parse();
+    match cli.command {
+        Some(command) => adwaita_network::cli::run(command),
+        None => adwaita_network::run(cli.compact),
+    }
 }
@@ -14,6 +14,8 @@ const MM_MODEM_3GPP_IFACE: &str = "org.freedesktop.ModemManager1.Modem.Modem3gpp
 const MM_SIM_IFACE: &str = "org.freedesktop.ModemManager1.Sim";
 const MM_BEARER_IFACE: &str = "org.freedesktop.ModemManager1.Bearer";
 
+const MM_MODEM_LOCK_SIM_PIN: u32 = 2;
+
 type ManagedObjects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
 
 #[derive(Debug, Clone, Default)]
@@ -30,6 +32,9 @@ pub struct MobileDataStatus {
     pub operator_name: Option<String>,
     pub sim_status: String,
     pub pin_status: String,
+    // * Only a plain SIM PIN can be entered here; PUK and device locks need the carrier
+    pub pin_required: bool,
+    pub sim_path: Option<String>,
     pub apn: Option<String>,
 }
 
@@ -171,6 +176,21 @@ pub async fn set_radio_enabled(enabled: bool) -> Result<()> {
     Ok(())
 }
 
+pub async fn send_pin(pin: &str) -> Result<()> {
+    let client = ModemManagerClient::new().await?;
+    let modem = client.first_modem().await?;
+    let Some(sim_path) = modem.status.sim_path.as_deref() else {
+        return Err(anyhow!("No SIM card detected"));
+    };
+
+    let proxy = client.proxy(sim_path, MM_SIM_IFACE).await?;
+    let _: () = proxy
+        .call("SendPin", &(pin.trim()))
+        .await
+        .with_context(|| "The SIM did not accept the PIN")?;
+    Ok(())
+}
+
 pub fn is_modemmanager_unavailable_error(message: &str) -> bool {
     let lower = message.to_lowercase();
     lower.contains("org.freedesktop.modemmanager1")
@@ -246,7 +266,7 @@ fn parse_first_modem(objects: &ManagedObjects) -> Option<ParsedModem> {
         })
         .unwrap_or_else(|| {
             (
-                if unlock_required > 0 {
+                if unlock_required > 1 {
                     "SIM locked".to_string()
                 } else {
                     "SIM unavailable".to_string()
@@ -283,6 +303,8 @@ fn parse_first_modem(objects: &ManagedObjects) -> Option<ParsedModem> {
             operator_name,
             sim_status,
             pin_status,
+            pin_required: unlock_required == MM_MODEM_LOCK_SIM_PIN,
+            sim_path: sim_path.map(|path| path.to_string()),
             apn,
         },
     })
@@ -381,15 +403,14 @@ fn access_technology_label(access_tech: u32) -> &'static str {
     }
 }
 
+// * MMModemLock: 0 is unknown and 1 is none; the SIM locks come before carrier/device locks.
 fn unlock_required_label(code: u32) -> &'static str {
     match code {
-        0 => "Unlocked",
-        1 => "PIN required",
-        2 => "PIN2 required",
-        3 => "PUK required",
-        4 => "PUK2 required",
-        5 => "Device lock",
-        6 => "Network lock",
+        0 | 1 => "Unlocked",
+        2 => "PIN required",
+        3 => "PIN2 required",
+        4 => "PUK required",
+        5 => "PUK2 required",
         _ => "Locked",
     }
 }
@@ -405,6 +426,13 @@ mod tests {
         assert_eq!(modem_state_label(-1), "Failed");
     }
 
+    #[test]
+    fn unlock_labels_follow_mm_modem_lock() {
+        assert_eq!(unlock_required_label(1), "Unlocked");
+        assert_eq!(unlock_required_label(MM_MODEM_LOCK_SIM_PIN), "PIN required");
+        assert_eq!(unlock_required_label(4), "PUK required");
+    }
+
     #[test]
     fn access_technology_mapping_prefers_latest_generation() {
         assert_eq!(access_technology_label(0x0000_8000), "5G");
@@ -0,0 +1,171 @@
+// * ./src/network_trust.rs
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use crate::nm;
+
+// * Per-SSID firewall zone + DNS association, applied after connecting to that network.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct NetworkTrustProfile {
+    pub ssid: String,
+    #[serde(default)]
+    pub zone: Option<String>,
+    #[serde(default)]
+    pub dns_servers: Vec<String>,
+    #[serde(default)]
+    pub dns_search_domains: Vec<String>,
+    #[serde(default)]
+    pub apply_on_connect: bool,
+}
+
+impl NetworkTrustProfile {
+    pub fn has_settings(&self) -> bool {
+        self.zone.is_some() || !self.dns_servers.is_empty()
+    }
+}
+
+pub fn trust_profiles_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".config/adw-network/network-trust.json"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-network-trust.json"))
+}
+
+pub async fn load_trust_profiles(path: &Path) -> Result<Vec<NetworkTrustProfile>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let mut profiles: Vec<NetworkTrustProfile> = serde_json::from_str(&content)?;
+    normalize_trust_profiles(&mut profiles);
+    Ok(profiles)
+}
+
+pub async fn save_trust_profiles(path: &Path, profiles: &[NetworkTrustProfile]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let mut to_save = profiles.to_vec();
+    normalize_trust_profiles(&mut to_save);
+    let json = serde_json::to_string_pretty(&to_save)?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+pub async fn trust_profile_for_ssid(ssid: &str) -> Result<Option<NetworkTrustProfile>> {
+    let profiles = load_trust_profiles(&trust_profiles_path()).await?;
+    Ok(profiles.into_iter().find(|p| p.ssid == ssid))
+}
+
+pub async fn store_trust_profile(profile: NetworkTrustProfile) -> Result<()> {
+    let path = trust_profiles_path();
+    let mut profiles = load_trust_profiles(&path).await?;
+    profiles.retain(|p| p.ssid != profile.ssid);
+    profiles.push(profile);
+    save_trust_profiles(&path, &profiles).await
+}
+
+// * Returns true when something was applied to the active connection.
+pub async fn apply_trust_profile_for_ssid(ssid: &str) -> Result<bool> {
+    let Some(profile) = trust_profile_for_ssid(ssid).await? else {
+        return Ok(false);
+    };
+    if !profile.apply_on_connect || !profile.has_settings() {
+        return Ok(false);
+    }
+
+    if let Some(zone) = profile.zone.as_deref() {
+        nm::set_connection_zone_for_connection(ssid, zone).await?;
+    }
+    if !profile.dns_servers.is_empty() {
+        nm::set_custom_ipv4_dns_for_connection(
+            ssid,
+            &profile.dns_servers,
+            &profile.dns_search_domains,
+        )
+        .await?;
+    }
+    nm::reapply_connection(ssid).await?;
+    Ok(true)
+}
+
+fn normalize_trust_profiles(profiles: &mut Vec<NetworkTrustProfile>) {
+    for profile in profiles.iter_mut() {
+        profile.zone = profile
+            .zone
+            .as_deref()
+            .map(|zone| zone.trim().to_lowercase().replace([' ', '_'], "-"))
+            .filter(|zone| !zone.is_empty());
+        normalize_list(&mut profile.dns_servers);
+        normalize_list(&mut profile.dns_search_domains);
+    }
+
+    profiles.retain(|p| !p.ssid.is_empty() && p.has_settings());
+    profiles.sort_by(|a, b| a.ssid.cmp(&b.ssid));
+    profiles.dedup_by(|a, b| a.ssid == b.ssid);
+}
+
+fn normalize_list(values: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    *values = values
+        .iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && seen.insert(value.clone()))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_zone_names_and_drops_empty_profiles() {
+        let mut profiles = vec![
+            NetworkTrustProfile {
+                ssid: "Cafe".to_string(),
+                zone: Some(" Public Wifi ".to_string()),
+                dns_servers: vec![" 9.9.9.9 ".to_string(), "9.9.9.9".to_string()],
+                dns_search_domains: vec![String::new()],
+                apply_on_connect: true,
+            },
+            NetworkTrustProfile {
+                ssid: "Empty".to_string(),
+                zone: Some("  ".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        normalize_trust_profiles(&mut profiles);
+
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].zone.as_deref(), Some("public-wifi"));
+        assert_eq!(profiles[0].dns_servers, vec!["9.9.9.9".to_string()]);
+        assert!(profiles[0].dns_search_domains.is_empty());
+    }
+
+    #[test]
+    fn keeps_one_profile_per_ssid() {
+        let mut profiles = vec![
+            NetworkTrustProfile {
+                ssid: "Home".to_string(),
+                zone: Some("trusted".to_string()),
+                ..Default::default()
+            },
+            NetworkTrustProfile {
+                ssid: "Home".to_string(),
+                zone: Some("home".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        normalize_trust_profiles(&mut profiles);
+
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].zone.as_deref(), Some("trusted"));
+    }
+}
@@ -1,30 +1,42 @@
 // * ./src/nm.rs
 
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::{AbortHandle, Abortable};
 use std::cmp::Ordering as CmpOrdering;
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
 use tokio::fs;
 use tokio::process::Command;
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
 use tokio::time::{sleep, Duration};
 use uuid::Uuid;
 use zeroize::Zeroizing;
 use zvariant::{OwnedObjectPath, OwnedValue, Str};
 
 use crate::nm_dbus::{
-    DbusAccessPoint, DbusActiveConnection, DbusConnectionProfile, NmDbusClient, SettingsMap,
-    NM_ACTIVE_CONNECTION_STATE_ACTIVATED, NM_ACTIVE_CONNECTION_STATE_ACTIVATING,
-    NM_ACTIVE_CONNECTION_STATE_DEACTIVATED, NM_ACTIVE_CONNECTION_STATE_DEACTIVATING,
-    NM_ACTIVE_CONNECTION_STATE_UNKNOWN, NM_CONNECTIVITY_FULL, NM_CONNECTIVITY_LIMITED,
-    NM_CONNECTIVITY_NONE, NM_CONNECTIVITY_PORTAL, NM_DEVICE_STATE_ACTIVATED,
-    NM_DEVICE_STATE_CONFIG, NM_DEVICE_STATE_DEACTIVATING, NM_DEVICE_STATE_DISCONNECTED,
-    NM_DEVICE_STATE_FAILED, NM_DEVICE_STATE_IP_CHECK, NM_DEVICE_STATE_IP_CONFIG,
-    NM_DEVICE_STATE_NEED_AUTH, NM_DEVICE_STATE_PREPARE, NM_DEVICE_STATE_SECONDARIES,
-    NM_DEVICE_STATE_UNAVAILABLE, NM_DEVICE_STATE_UNMANAGED, NM_DEVICE_STATE_UNKNOWN,
-    NM_DEVICE_TYPE_ETHERNET, NM_DEVICE_TYPE_LOOPBACK, NM_DEVICE_TYPE_WIFI,
+    DbusAccessPoint, DbusActiveConnection, DbusConnectionProfile, DbusDevice, DbusIp4Info,
+    NmDbusClient, SettingsMap, NM_802_11_AP_FLAGS_WPS_PBC, NM_802_11_AP_SEC_KEY_MGMT_802_1X,
+    NM_802_11_AP_SEC_KEY_MGMT_OWE, NM_802_11_AP_SEC_KEY_MGMT_OWE_TM, NM_802_11_AP_SEC_KEY_MGMT_PSK,
+    NM_802_11_AP_SEC_KEY_MGMT_SAE, NM_ACTIVE_CONNECTION_STATE_ACTIVATED,
+    NM_ACTIVE_CONNECTION_STATE_ACTIVATING, NM_ACTIVE_CONNECTION_STATE_DEACTIVATED,
+    NM_ACTIVE_CONNECTION_STATE_DEACTIVATING, NM_ACTIVE_CONNECTION_STATE_UNKNOWN,
+    NM_CONNECTIVITY_FULL, NM_CONNECTIVITY_LIMITED, NM_CONNECTIVITY_NONE, NM_CONNECTIVITY_PORTAL,
+    NM_DEVICE_STATE_ACTIVATED, NM_DEVICE_STATE_CONFIG, NM_DEVICE_STATE_DEACTIVATING,
+    NM_DEVICE_STATE_DISCONNECTED, NM_DEVICE_STATE_FAILED, NM_DEVICE_STATE_IP_CHECK,
+    NM_DEVICE_STATE_IP_CONFIG, NM_DEVICE_STATE_NEED_AUTH, NM_DEVICE_STATE_PREPARE,
+    NM_DEVICE_STATE_SECONDARIES, NM_DEVICE_STATE_UNAVAILABLE, NM_DEVICE_STATE_UNKNOWN,
+    NM_DEVICE_STATE_UNMANAGED, NM_DEVICE_TYPE_ETHERNET, NM_DEVICE_TYPE_LOOPBACK,
+    NM_DEVICE_TYPE_MODEM, NM_DEVICE_TYPE_WIFI, NM_METERED_UNKNOWN, NM_METERED_YES,
+    NM_WAKE_ON_LAN_DEFAULT, NM_WAKE_ON_LAN_IGNORE, NM_WAKE_ON_LAN_MAGIC,
 };
+pub use crate::nm_error::NmError;
+use crate::wireguard::{self, TunnelStats};
 
 pub const NMCLI_RETRIEVAL_TOAST: &str =
     "Unable to retrieve data from NetworkManager – check your connection";
@@ -39,12 +51,27 @@ pub struct WifiNetwork {
     pub channel: u32,
     pub freq_mhz: u32,
     pub security_type: String,
+    // * NM object path of the adapter that sees this network; None when it isn't known
+    pub device: Option<String>,
+}
+
+// * One radio behind an SSID; mesh and repeater setups show several per network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessPoint {
+    pub bssid: String,
+    pub signal: u8,
+    pub band: String,
+    pub channel: u32,
+    pub freq_mhz: u32,
+    pub active: bool,
+    pub supports_wps: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeviceType {
     Ethernet,
     Wifi,
+    Modem,
     Loopback,
     Other(String),
 }
@@ -104,6 +131,41 @@ pub struct WireGuardConnectionConfig {
     pub allowed_ips: Vec<String>,
     pub mtu: Option<u32>,
     pub persistent_keepalive: Option<u32>,
+    // * Peers after the first; the editor only shows the first and carries these through as-is
+    pub extra_peers: Vec<WireGuardPeer>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WireGuardPeer {
+    pub public_key: String,
+    pub preshared_key: Option<Zeroizing<String>>,
+    pub endpoint: String,
+    pub allowed_ips: Vec<String>,
+    pub persistent_keepalive: Option<u32>,
+}
+
+impl WireGuardConnectionConfig {
+    fn first_peer(&self) -> WireGuardPeer {
+        WireGuardPeer {
+            public_key: self.public_key.clone(),
+            preshared_key: self.preshared_key.clone(),
+            endpoint: self.endpoint.clone(),
+            allowed_ips: self.allowed_ips.clone(),
+            persistent_keepalive: self.persistent_keepalive,
+        }
+    }
+
+    fn set_peers(&mut self, peers: Vec<WireGuardPeer>) {
+        let mut peers = peers.into_iter();
+        if let Some(first) = peers.next() {
+            self.public_key = first.public_key;
+            self.preshared_key = first.preshared_key;
+            self.endpoint = first.endpoint;
+            self.allowed_ips = first.allowed_ips;
+            self.persistent_keepalive = first.persistent_keepalive;
+        }
+        self.extra_peers = peers.collect();
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -111,18 +173,455 @@ pub struct OpenVpnConnectionConfig {
     pub name: String,
     pub remote: String,
     pub username: Option<String>,
+    pub autoconnect: bool,
+    // * ipv4/ipv6.never-default: only traffic for the VPN's own networks goes through it
+    pub never_default: bool,
+    pub ignore_auto_routes: bool,
 }
 
-pub struct NetworkManager;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ipv4Method {
+    Auto,
+    Manual,
+    LinkLocal,
+    // * "shared", "disabled" and anything newer; kept so reading a profile never loses it.
+    Other(String),
+}
+
+impl Ipv4Method {
+    pub fn from_nm(method: &str) -> Self {
+        match method {
+            "auto" | "" => Self::Auto,
+            "manual" => Self::Manual,
+            "link-local" => Self::LinkLocal,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_nm(&self) -> &str {
+        match self {
+            Self::Auto => "auto",
+            Self::Manual => "manual",
+            Self::LinkLocal => "link-local",
+            Self::Other(method) => method,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ipv4Config {
+    pub method: Ipv4Method,
+    pub address: Option<std::net::Ipv4Addr>,
+    pub prefix: u32,
+    pub gateway: Option<std::net::Ipv4Addr>,
+}
+
+// * One entry of ipv4.route-data / ipv6.route-data; the family follows the destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticRoute {
+    pub destination: std::net::IpAddr,
+    pub prefix: u32,
+    pub next_hop: Option<std::net::IpAddr>,
+    pub metric: Option<u32>,
+}
+
+impl StaticRoute {
+    fn section(&self) -> &'static str {
+        if self.destination.is_ipv4() {
+            "ipv4"
+        } else {
+            "ipv6"
+        }
+    }
+
+    fn same_target(&self, other: &StaticRoute) -> bool {
+        self.destination == other.destination
+            && self.prefix == other.prefix
+            && self.next_hop == other.next_hop
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.destination, self.prefix)
+    }
+
+    pub fn details(&self) -> String {
+        let via = match self.next_hop {
+            Some(hop) => format!("via {}", hop),
+            None => "on-link".to_string(),
+        };
+        match self.metric {
+            Some(metric) => format!("{} · metric {}", via, metric),
+            None => via,
+        }
+    }
+}
+
+// * How NM picks the MAC a profile connects with; maps to <section>.assigned-mac-address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacAddressPolicy {
+    Default,
+    Permanent,
+    Random,
+    Stable,
+    Preserve,
+    Fixed(String),
+}
+
+impl MacAddressPolicy {
+    fn from_nm(value: &str) -> Self {
+        match value {
+            "" => Self::Default,
+            "permanent" => Self::Permanent,
+            "random" => Self::Random,
+            "stable" => Self::Stable,
+            "preserve" => Self::Preserve,
+            mac => Self::Fixed(mac.to_uppercase()),
+        }
+    }
+
+    fn as_nm(&self) -> Option<&str> {
+        match self {
+            Self::Default => None,
+            Self::Permanent => Some("permanent"),
+            Self::Random => Some("random"),
+            Self::Stable => Some("stable"),
+            Self::Preserve => Some("preserve"),
+            Self::Fixed(mac) => Some(mac),
+        }
+    }
+}
+
+// * Outer EAP method of an 802.1X profile; maps to 802-1x.eap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EapMethod {
+    #[default]
+    Peap,
+    Ttls,
+    Tls,
+}
+
+impl EapMethod {
+    fn from_nm(value: &str) -> Option<Self> {
+        match value {
+            "peap" => Some(Self::Peap),
+            "ttls" => Some(Self::Ttls),
+            "tls" => Some(Self::Tls),
+            _ => None,
+        }
+    }
+
+    fn as_nm(self) -> &'static str {
+        match self {
+            Self::Peap => "peap",
+            Self::Ttls => "ttls",
+            Self::Tls => "tls",
+        }
+    }
+
+    // * TLS authenticates with a client certificate; the tunnelled methods take a password.
+    pub fn uses_password(self) -> bool {
+        !matches!(self, Self::Tls)
+    }
+}
+
+// * Inner authentication inside a PEAP or TTLS tunnel; maps to 802-1x.phase2-auth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Phase2Auth {
+    #[default]
+    Mschapv2,
+    Mschap,
+    Pap,
+    Gtc,
+}
 
-pub fn is_nmcli_retrieval_error(message: &str) -> bool {
-    message.contains(NMCLI_RETRIEVAL_TOAST)
+impl Phase2Auth {
+    fn from_nm(value: &str) -> Self {
+        match value {
+            "mschap" => Self::Mschap,
+            "pap" => Self::Pap,
+            "gtc" => Self::Gtc,
+            _ => Self::Mschapv2,
+        }
+    }
+
+    fn as_nm(self) -> &'static str {
+        match self {
+            Self::Mschapv2 => "mschapv2",
+            Self::Mschap => "mschap",
+            Self::Pap => "pap",
+            Self::Gtc => "gtc",
+        }
+    }
+}
+
+// * Everything the 802-1x section needs for eduroam-style PEAP/TTLS logins and certificate-based TLS.
+#[derive(Debug, Clone, Default)]
+pub struct EapConfig {
+    pub method: EapMethod,
+    pub identity: String,
+    pub anonymous_identity: Option<String>,
+    pub password: Option<Zeroizing<String>>,
+    pub phase2_auth: Phase2Auth,
+    // ? None trusts the system CA bundle rather than skipping server validation
+    pub ca_cert: Option<PathBuf>,
+    pub domain_suffix_match: Option<String>,
+    pub client_cert: Option<PathBuf>,
+    pub private_key: Option<PathBuf>,
+    pub private_key_password: Option<Zeroizing<String>>,
+}
+
+impl Default for Ipv4Config {
+    fn default() -> Self {
+        Self {
+            method: Ipv4Method::Auto,
+            address: None,
+            prefix: 24,
+            gateway: None,
+        }
+    }
 }
 
+pub struct NetworkManager;
+
 pub async fn dbus_client() -> Result<NmDbusClient> {
     NmDbusClient::new()
         .await
-        .map_err(|e| anyhow!("{} [{}]", NMCLI_RETRIEVAL_TOAST, e))
+        .map_err(|e| NmError::Unavailable(format!("{} [{}]", NMCLI_RETRIEVAL_TOAST, e)).into())
+}
+
+// * Everything the pages need from the network stack; swapped for `nm_mock::MockBackend` in tests.
+#[async_trait]
+pub trait NetworkBackend: Send + Sync {
+    async fn is_wifi_enabled(&self) -> Result<bool>;
+    async fn set_wifi_enabled(&self, enabled: bool) -> Result<()>;
+    async fn devices(&self) -> Result<Vec<Device>>;
+    async fn scan_networks(&self) -> Result<Vec<WifiNetwork>>;
+    async fn scan_networks_quick(&self) -> Result<Vec<WifiNetwork>>;
+    async fn cached_networks(&self) -> Result<Vec<WifiNetwork>>;
+    async fn connect_wifi(
+        &self,
+        ssid: &str,
+        password: Option<&str>,
+        security_type: Option<&str>,
+        hidden: bool,
+    ) -> Result<ConnectStatus>;
+    async fn activate_saved(&self, ssid: &str) -> Result<ConnectStatus>;
+    async fn disconnect(&self, ssid: &str) -> Result<()>;
+    async fn saved_connections(&self) -> Result<Vec<SavedConnection>>;
+    async fn forget(&self, ssid: &str) -> Result<()>;
+    async fn network_info(&self, ssid: &str) -> Result<NetworkInfo>;
+    async fn saved_wifi_ssids(&self) -> Result<HashSet<String>>;
+    async fn is_saved(&self, ssid: &str) -> Result<bool>;
+    async fn active_wifi_ssid(&self) -> Result<Option<String>>;
+    async fn autoconnect(&self, ssid: &str) -> Result<bool>;
+    async fn set_autoconnect(&self, ssid: &str, enabled: bool) -> Result<()>;
+}
+
+// * The real thing: NetworkManager over the system bus.
+pub struct NmBackend;
+
+#[async_trait]
+impl NetworkBackend for NmBackend {
+    async fn is_wifi_enabled(&self) -> Result<bool> {
+        dbus_client().await?.is_wifi_enabled().await
+    }
+
+    async fn set_wifi_enabled(&self, enabled: bool) -> Result<()> {
+        dbus_client().await?.set_wifi_enabled(enabled).await
+    }
+
+    async fn devices(&self) -> Result<Vec<Device>> {
+        NetworkManager::get_devices_via_dbus().await
+    }
+
+    async fn scan_networks(&self) -> Result<Vec<WifiNetwork>> {
+        scan_networks_via_dbus().await
+    }
+
+    async fn scan_networks_quick(&self) -> Result<Vec<WifiNetwork>> {
+        scan_networks_quick_via_dbus().await
+    }
+
+    async fn cached_networks(&self) -> Result<Vec<WifiNetwork>> {
+        cached_networks_via_dbus().await
+    }
+
+    async fn connect_wifi(
+        &self,
+        ssid: &str,
+        password: Option<&str>,
+        security_type: Option<&str>,
+        hidden: bool,
+    ) -> Result<ConnectStatus> {
+        let result = connect_wifi_network(ssid, password, security_type, hidden).await;
+        if result.is_err() && !self.is_wifi_enabled().await.unwrap_or(true) {
+            return Err(NmError::RadioOff.into());
+        }
+        result
+    }
+
+    async fn activate_saved(&self, ssid: &str) -> Result<ConnectStatus> {
+        dbus_client()
+            .await?
+            .activate_connection_by_id(ssid, None)
+            .await?;
+        Ok(ConnectStatus::Connected)
+    }
+
+    async fn disconnect(&self, ssid: &str) -> Result<()> {
+        dbus_client().await?.disconnect_connection_by_id(ssid).await
+    }
+
+    async fn saved_connections(&self) -> Result<Vec<SavedConnection>> {
+        let profiles = dbus_client().await?.list_connections().await?;
+        Ok(map_saved_connections(profiles).await)
+    }
+
+    async fn forget(&self, ssid: &str) -> Result<()> {
+        dbus_client().await?.delete_connection_by_id(ssid).await
+    }
+
+    async fn network_info(&self, ssid: &str) -> Result<NetworkInfo> {
+        get_network_info_via_dbus(ssid).await
+    }
+
+    async fn saved_wifi_ssids(&self) -> Result<HashSet<String>> {
+        Ok(dbus_client()
+            .await?
+            .list_connections()
+            .await?
+            .iter()
+            .filter_map(NmDbusClient::wifi_profile_ssid)
+            .collect())
+    }
+
+    async fn is_saved(&self, ssid: &str) -> Result<bool> {
+        let conn = dbus_client().await?.find_connection_by_id(ssid).await?;
+        Ok(conn
+            .map(|c| c.conn_type == "802-11-wireless")
+            .unwrap_or(false))
+    }
+
+    async fn active_wifi_ssid(&self) -> Result<Option<String>> {
+        dbus_client().await?.get_active_wifi_ssid().await
+    }
+
+    async fn autoconnect(&self, ssid: &str) -> Result<bool> {
+        dbus_client()
+            .await?
+            .get_connection_autoconnect_by_id(ssid)
+            .await
+    }
+
+    async fn set_autoconnect(&self, ssid: &str, enabled: bool) -> Result<()> {
+        dbus_client()
+            .await?
+            .set_connection_autoconnect_by_id(ssid, enabled)
+            .await
+    }
+}
+
+const MIN_OPERATION_TIMEOUT_SECS: u64 = 5;
+const MAX_OPERATION_TIMEOUT_SECS: u64 = 300;
+// * Scans are repeated by the poll scheduler anyway, so they never get the full budget.
+const SCAN_TIMEOUT_CAP: Duration = Duration::from_secs(15);
+// * WPS walk time is 120 s; the D-Bus side gives up on its own just before this.
+const WPS_TIMEOUT: Duration = Duration::from_secs(135);
+
+static OPERATION_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(45);
+
+pub fn set_operation_timeout(secs: u64) {
+    let secs = secs.clamp(MIN_OPERATION_TIMEOUT_SECS, MAX_OPERATION_TIMEOUT_SECS);
+    OPERATION_TIMEOUT_SECS.store(secs, AtomicOrdering::Relaxed);
+}
+
+pub fn operation_timeout() -> Duration {
+    Duration::from_secs(OPERATION_TIMEOUT_SECS.load(AtomicOrdering::Relaxed))
+}
+
+fn scan_timeout() -> Duration {
+    operation_timeout().min(SCAN_TIMEOUT_CAP)
+}
+
+pub async fn with_timeout<T>(
+    limit: Duration,
+    what: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match tokio::time::timeout(limit, future).await {
+        Ok(result) => result,
+        Err(_) => {
+            Err(NmError::Timeout(format!("{} timed out after {}s", what, limit.as_secs())).into())
+        }
+    }
+}
+
+// * Every external command goes through here so a hung tool can't keep a spinner running forever.
+pub async fn command_output(command: &mut Command) -> Result<Output> {
+    let program = command
+        .as_std()
+        .get_program()
+        .to_string_lossy()
+        .into_owned();
+    command.kill_on_drop(true);
+    with_timeout(operation_timeout(), &program, async {
+        command
+            .output()
+            .await
+            .map_err(|e| anyhow!("Failed to run {}: {}", program, e))
+    })
+    .await
+}
+
+// * Aborts a pending connect or scan; dropping the handle leaves the operation running.
+pub struct CancelHandle(AbortHandle);
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        self.0.abort();
+    }
+}
+
+pub fn cancellable<T>(
+    future: impl Future<Output = Result<T>>,
+) -> (impl Future<Output = Result<T>>, CancelHandle) {
+    let (handle, registration) = AbortHandle::new_pair();
+    let future = async move {
+        Abortable::new(future, registration)
+            .await
+            .unwrap_or_else(|_| Err(NmError::Cancelled.into()))
+    };
+    (future, CancelHandle(handle))
+}
+
+static BACKEND: OnceLock<Box<dyn NetworkBackend>> = OnceLock::new();
+
+#[cfg(test)]
+thread_local! {
+    // ? Each test runs on its own thread, so tests can swap backends without racing on BACKEND
+    static TEST_BACKEND: std::cell::Cell<Option<&'static dyn NetworkBackend>> =
+        const { std::cell::Cell::new(None) };
+}
+
+fn backend() -> &'static dyn NetworkBackend {
+    #[cfg(test)]
+    if let Some(backend) = TEST_BACKEND.with(std::cell::Cell::get) {
+        return backend;
+    }
+    BACKEND.get_or_init(|| Box::new(NmBackend)).as_ref()
+}
+
+// ? Only affects the calling thread; futures must be driven there too, e.g. with block_on.
+#[cfg(test)]
+pub(crate) fn use_backend_on_this_thread(backend: Box<dyn NetworkBackend>) {
+    TEST_BACKEND.with(|cell| cell.set(Some(Box::leak(backend))));
+}
+
+// * Must run before the first NM call; afterwards the NetworkManager backend is locked in.
+pub fn install_backend(backend: Box<dyn NetworkBackend>) -> Result<()> {
+    BACKEND
+        .set(backend)
+        .map_err(|_| anyhow!("Network backend already initialised"))
 }
 
 static SIGNAL_LISTENERS_INIT: AtomicBool = AtomicBool::new(false);
@@ -141,6 +640,33 @@ pub fn signal_polling_fallback_active() -> bool {
     SIGNAL_POLLING_FALLBACK.load(AtomicOrdering::Relaxed)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NmEvent {
+    StateChanged,
+    DevicesChanged,
+    AccessPointsChanged,
+    ActiveConnectionsChanged,
+    ConnectivityChanged,
+}
+
+fn event_sender() -> &'static broadcast::Sender<NmEvent> {
+    static SENDER: OnceLock<broadcast::Sender<NmEvent>> = OnceLock::new();
+    SENDER.get_or_init(|| broadcast::channel(64).0)
+}
+
+// * Receivers that fall behind get `Lagged`; treat that as "something changed".
+pub fn subscribe_events() -> broadcast::Receiver<NmEvent> {
+    event_sender().subscribe()
+}
+
+fn publish_event(event: NmEvent) {
+    // ? AP churn is constant during scans; only real state changes speed up polling
+    if event != NmEvent::AccessPointsChanged {
+        SIGNAL_DIRTY.store(true, AtomicOrdering::Relaxed);
+    }
+    let _ = event_sender().send(event);
+}
+
 pub async fn init_signal_listeners() -> Result<()> {
     if SIGNAL_LISTENERS_INIT
         .compare_exchange(false, true, AtomicOrdering::AcqRel, AtomicOrdering::Acquire)
@@ -155,8 +681,17 @@ pub async fn init_signal_listeners() -> Result<()> {
         let (state_tx, mut state_rx) = watch::channel(0u32);
         let (added_tx, mut added_rx) = watch::channel(root_path.clone());
         let (removed_tx, mut removed_rx) = watch::channel(root_path);
+        let (ap_tx, mut ap_rx) = watch::channel(());
+        let (active_tx, mut active_rx) = watch::channel(());
+        let (connectivity_tx, mut connectivity_rx) = watch::channel(0u32);
 
-        client.spawn_all_listeners(state_tx, added_tx, removed_tx).await?;
+        client
+            .spawn_all_listeners(state_tx, added_tx, removed_tx)
+            .await?;
+        client.spawn_access_point_listener(ap_tx).await?;
+        client
+            .spawn_active_connection_listener(active_tx, connectivity_tx)
+            .await?;
 
         tokio::spawn(async move {
             let _client = client;
@@ -169,7 +704,7 @@ pub async fn init_signal_listeners() -> Result<()> {
                             SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
                             break;
                         }
-                        SIGNAL_DIRTY.store(true, AtomicOrdering::Relaxed);
+                        publish_event(NmEvent::StateChanged);
                         log::debug!("NM signal: state changed");
                     }
                     changed = added_rx.changed() => {
@@ -181,7 +716,7 @@ pub async fn init_signal_listeners() -> Result<()> {
                             SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
                             break;
                         }
-                        SIGNAL_DIRTY.store(true, AtomicOrdering::Relaxed);
+                        publish_event(NmEvent::DevicesChanged);
                         log::debug!("NM signal: device added");
                     }
                     changed = removed_rx.changed() => {
@@ -193,9 +728,39 @@ pub async fn init_signal_listeners() -> Result<()> {
                             SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
                             break;
                         }
-                        SIGNAL_DIRTY.store(true, AtomicOrdering::Relaxed);
+                        publish_event(NmEvent::DevicesChanged);
                         log::debug!("NM signal: device removed");
                     }
+                    changed = ap_rx.changed() => {
+                        if changed.is_err() {
+                            log::warn!("NM access point listener stopped; polling fallback active");
+                            SIGNAL_LISTENERS_INIT.store(false, AtomicOrdering::Release);
+                            SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
+                            break;
+                        }
+                        publish_event(NmEvent::AccessPointsChanged);
+                    }
+                    changed = active_rx.changed() => {
+                        if changed.is_err() {
+                            log::warn!(
+                                "NM active connection listener stopped; polling fallback active"
+                            );
+                            SIGNAL_LISTENERS_INIT.store(false, AtomicOrdering::Release);
+                            SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
+                            break;
+                        }
+                        publish_event(NmEvent::ActiveConnectionsChanged);
+                        log::debug!("NM signal: active connections changed");
+                    }
+                    changed = connectivity_rx.changed() => {
+                        if changed.is_err() {
+                            log::warn!("NM connectivity listener stopped; polling fallback active");
+                            SIGNAL_LISTENERS_INIT.store(false, AtomicOrdering::Release);
+                            SIGNAL_POLLING_FALLBACK.store(true, AtomicOrdering::Relaxed);
+                            break;
+                        }
+                        publish_event(NmEvent::ConnectivityChanged);
+                    }
                 }
             }
         });
@@ -218,6 +783,10 @@ pub async fn init_signal_listeners() -> Result<()> {
 
 impl NetworkManager {
     pub async fn get_devices() -> Result<Vec<Device>> {
+        with_timeout(operation_timeout(), "Listing devices", backend().devices()).await
+    }
+
+    async fn get_devices_via_dbus() -> Result<Vec<Device>> {
         let client = dbus_client().await?;
         let active = client.list_active_connections().await?;
         let devices = client.list_devices().await?;
@@ -235,6 +804,7 @@ impl NetworkManager {
                 let device_type = match device.device_type {
                     NM_DEVICE_TYPE_ETHERNET => DeviceType::Ethernet,
                     NM_DEVICE_TYPE_WIFI => DeviceType::Wifi,
+                    NM_DEVICE_TYPE_MODEM => DeviceType::Modem,
                     NM_DEVICE_TYPE_LOOPBACK => DeviceType::Loopback,
                     other => DeviceType::Other(other.to_string()),
                 };
@@ -300,6 +870,10 @@ impl Connection {
         self.conn_type == "802-3-ethernet" || self.conn_type == "ethernet"
     }
 
+    pub fn is_vlan(&self) -> bool {
+        self.conn_type == "vlan"
+    }
+
     pub async fn activate(&self) -> Result<ConnectStatus> {
         let client = dbus_client().await?;
         client.activate_connection_by_id(&self.name, None).await?;
@@ -339,11 +913,21 @@ impl InternetConnectivity {
         match self {
             Self::Unknown => "Checking internet",
             Self::NoInternet => "No internet",
-            Self::Portal => "Login required",
+            Self::Portal => "Sign-in required",
             Self::Limited => "Limited internet",
             Self::Full => "Online",
         }
     }
+
+    // * Appended to the SSID in the header so a dead-end network doesn't look healthy.
+    pub fn status_suffix(self) -> &'static str {
+        match self {
+            Self::Unknown | Self::Full => "",
+            Self::NoInternet => " – no internet",
+            Self::Portal => " – sign-in required",
+            Self::Limited => " – limited",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -367,459 +951,2156 @@ pub struct NetworkInfo {
 pub struct SavedConnection {
     pub uuid: String,
     pub ssid: String,
+    // * Unix seconds of the last successful activation, 0 when never used.
+    pub last_used: u64,
+    pub security_type: String,
+    pub autoconnect: bool,
+    // * Set when the profile is bound to one adapter
+    pub interface_name: Option<String>,
 }
 
-pub async fn is_wifi_enabled() -> Result<bool> {
-    dbus_client().await?.is_wifi_enabled().await
+// * An access-point profile saved by another tool (GNOME Settings, nmcli), offered for import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedHotspotProfile {
+    pub id: String,
+    pub ssid: String,
+    pub password: Option<String>,
+    // * In the hotspot config's terms: "Auto", "2.4 GHz" or "5 GHz"
+    pub band: String,
+    pub hidden: bool,
 }
 
-pub async fn set_wifi_enabled(enabled: bool) -> Result<()> {
-    dbus_client().await?.set_wifi_enabled(enabled).await
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoconnectCandidate {
+    pub name: String,
+    pub uuid: String,
+    pub conn_type: String,
+    pub priority: i32,
+    // * Unix seconds of the last successful activation, 0 when never used.
+    pub last_connected: u64,
 }
 
-pub async fn is_ethernet_enabled() -> Result<bool> {
-    dbus_client().await?.is_ethernet_enabled().await
+impl AutoconnectCandidate {
+    pub fn type_label(&self) -> &'static str {
+        match self.conn_type.as_str() {
+            "802-11-wireless" | "wifi" => "Wi-Fi",
+            "802-3-ethernet" | "ethernet" => "Ethernet",
+            _ => "Other",
+        }
+    }
 }
 
-pub async fn set_ethernet_enabled(enabled: bool) -> Result<()> {
-    dbus_client().await?.set_ethernet_enabled(enabled).await
+pub async fn is_wifi_enabled() -> Result<bool> {
+    with_timeout(
+        operation_timeout(),
+        "Reading Wi-Fi state",
+        backend().is_wifi_enabled(),
+    )
+    .await
 }
 
-pub async fn has_wifi_device() -> Result<bool> {
-    if !is_wifi_present().await? {
-        return Ok(false);
+pub async fn set_wifi_enabled(enabled: bool) -> Result<()> {
+    if !enabled {
+        clear_scan_cache();
     }
+    with_timeout(
+        operation_timeout(),
+        "Switching Wi-Fi",
+        backend().set_wifi_enabled(enabled),
+    )
+    .await
+}
 
-    let devices = NetworkManager::get_devices().await?;
-    Ok(devices
-        .into_iter()
-        .any(|d| d.device_type == DeviceType::Wifi && d.state != "unmanaged"))
+// * NM's WWAN kill switch; it covers every modem, unlike ModemManager's per-modem Enable.
+pub async fn is_wwan_enabled() -> Result<bool> {
+    dbus_client().await?.is_wwan_enabled().await
 }
 
-pub async fn is_wifi_present() -> Result<bool> {
-    dbus_client().await?.is_wifi_present().await
+pub async fn set_wwan_enabled(enabled: bool) -> Result<()> {
+    dbus_client().await?.set_wwan_enabled(enabled).await
 }
 
-pub async fn has_ethernet_device() -> Result<bool> {
+pub async fn has_modem_device() -> Result<bool> {
     let devices = NetworkManager::get_devices().await?;
-    Ok(devices
-        .into_iter()
-        .any(|d| d.device_type == DeviceType::Ethernet))
+    Ok(devices.iter().any(|d| d.device_type == DeviceType::Modem))
 }
 
-pub async fn scan_networks() -> Result<Vec<WifiNetwork>> {
-    let client = dbus_client().await?;
+// * Bluetooth tethering profiles are matched to the phone by bdaddr, not by name.
+fn bluetooth_profile_address(settings: &SettingsMap) -> Option<String> {
+    settings
+        .get("bluetooth")
+        .and_then(|bt| bt.get("bdaddr"))
+        .and_then(|value| Vec::<u8>::try_from(value.try_clone().ok()?).ok())
+        .filter(|bytes| bytes.len() == 6)
+        .map(|bytes| format_mac_bytes(&bytes))
+}
 
-    // * Merge cached access points with a fresh scan because NM scan completion is asynchronous.
-    let mut aps = client.list_access_points().await.unwrap_or_default();
-    let _ = client.request_wifi_scan().await;
-    sleep(Duration::from_millis(1200)).await;
-    if let Ok(scanned) = client.list_access_points().await {
-        aps.extend(scanned);
-    }
+async fn find_bluetooth_pan_profile(
+    client: &NmDbusClient,
+    address: &str,
+) -> Result<Option<DbusConnectionProfile>> {
+    Ok(client
+        .list_connections()
+        .await?
+        .into_iter()
+        .find(|profile| {
+            profile.conn_type == "bluetooth"
+                && bluetooth_profile_address(&profile.settings)
+                    .is_some_and(|bdaddr| bdaddr.eq_ignore_ascii_case(address))
+        }))
+}
 
-    let mut networks_by_key: HashMap<(String, String, String), WifiNetwork> = HashMap::new();
+fn build_bluetooth_pan_settings(name: &str, address: &str) -> Result<SettingsMap> {
+    let mut settings = SettingsMap::new();
 
-    for ap in aps {
-        let normalized_freq = normalize_frequency_mhz(ap.frequency);
-        let band = band_from_frequency(normalized_freq);
-        let network = WifiNetwork {
-            ssid: ap.ssid.clone(),
-            signal: ap.strength,
-            secured: is_ap_secured(&ap),
-            connected: ap.active,
-            band: band.to_string(),
-            channel: channel_from_frequency(normalized_freq),
-            freq_mhz: normalized_freq,
-            security_type: ap_security_type(&ap),
-        };
+    let mut connection = HashMap::new();
+    connection.insert(
+        "id".to_string(),
+        owned_string(&format!("{} Tethering", name)),
+    );
+    connection.insert("type".to_string(), owned_string("bluetooth"));
+    connection.insert(
+        "uuid".to_string(),
+        owned_string(&Uuid::new_v4().to_string()),
+    );
+    connection.insert("autoconnect".to_string(), false.into());
+    settings.insert("connection".to_string(), connection);
 
-        // * Keep distinct entries for SSID + band + security because one SSID may expose variants.
-        let key = (
-            network.ssid.clone(),
-            network.band.clone(),
-            network.security_type.clone(),
-        );
-        match networks_by_key.get_mut(&key) {
-            None => {
-                networks_by_key.insert(key, network);
-            }
-            Some(existing) => {
-                if (network.connected && !existing.connected)
-                    || (network.connected == existing.connected && network.signal > existing.signal)
-                {
-                    *existing = network;
-                }
-            }
+    let mut bluetooth = HashMap::new();
+    bluetooth.insert(
+        "bdaddr".to_string(),
+        OwnedValue::try_from(zvariant::Value::from(mac_address_bytes(address)?))?,
+    );
+    bluetooth.insert("type".to_string(), owned_string("panu"));
+    settings.insert("bluetooth".to_string(), bluetooth);
+
+    for family in ["ipv4", "ipv6"] {
+        let mut section = HashMap::new();
+        section.insert("method".to_string(), owned_string("auto"));
+        settings.insert(family.to_string(), section);
+    }
+    Ok(settings)
+}
+
+// * Reuses the phone's existing PAN profile, or creates one the first time.
+pub async fn connect_bluetooth_pan(address: &str, name: &str) -> Result<()> {
+    let client = dbus_client().await?;
+    let uuid = match find_bluetooth_pan_profile(&client, address).await? {
+        Some(profile) => profile.uuid,
+        None => {
+            let settings = build_bluetooth_pan_settings(name, address)?;
+            client.add_connection(&settings).await?;
+            settings
+                .get("connection")
+                .and_then(|connection| connection.get("uuid"))
+                .and_then(value_string)
+                .unwrap_or_default()
         }
+    };
+    // * NM names Bluetooth devices after their address
+    client
+        .activate_connection_by_uuid(&uuid, Some(&address.to_uppercase()))
+        .await
+        .map_err(|e| {
+            if NmError::is_not_found(&e) {
+                anyhow!(
+                    "{} is not reachable over Bluetooth. Make sure it is nearby with tethering on",
+                    name
+                )
+            } else {
+                e
+            }
+        })?;
+    Ok(())
+}
+
+pub async fn disconnect_bluetooth_pan(address: &str) -> Result<()> {
+    let client = dbus_client().await?;
+    let profile = find_bluetooth_pan_profile(&client, address)
+        .await?
+        .ok_or_else(|| NmError::NotFound(format!("No tethering profile for {}", address)))?;
+    client.deactivate_connection_by_uuid(&profile.uuid).await
+}
+
+pub async fn active_bluetooth_pan_addresses() -> Result<Vec<String>> {
+    let client = dbus_client().await?;
+    let active: Vec<String> = client
+        .list_active_connections()
+        .await?
+        .into_iter()
+        .filter(|connection| connection.conn_type == "bluetooth")
+        .map(|connection| connection.uuid)
+        .collect();
+    if active.is_empty() {
+        return Ok(Vec::new());
     }
+    Ok(client
+        .list_connections()
+        .await?
+        .iter()
+        .filter(|profile| active.contains(&profile.uuid))
+        .filter_map(|profile| bluetooth_profile_address(&profile.settings))
+        .collect())
+}
 
-    let mut networks: Vec<WifiNetwork> = networks_by_key.into_values().collect();
-    networks.sort_by(compare_wifi_networks);
+// * Phones sharing their connection over USB show up as plain Ethernet; only the driver tells.
+const USB_TETHER_DRIVERS: [&str; 4] = ["rndis_host", "cdc_ether", "cdc_ncm", "ipheth"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbTether {
+    pub iface: String,
+    pub driver: String,
+    pub connected: bool,
+}
 
-    Ok(networks)
+pub fn is_usb_tether_driver(driver: &str) -> bool {
+    USB_TETHER_DRIVERS.contains(&driver)
 }
 
-pub async fn get_network_info(ssid: &str) -> Result<NetworkInfo> {
+fn interface_driver(iface: &str) -> Option<String> {
+    std::fs::read_link(format!("/sys/class/net/{}/device/driver", iface))
+        .ok()?
+        .file_name()?
+        .to_str()
+        .map(str::to_string)
+}
+
+pub async fn usb_tethers() -> Result<Vec<UsbTether>> {
+    Ok(NetworkManager::get_devices()
+        .await?
+        .into_iter()
+        .filter(|device| device.device_type == DeviceType::Ethernet)
+        .filter_map(|device| {
+            let driver = interface_driver(&device.name).filter(|d| is_usb_tether_driver(d))?;
+            Some(UsbTether {
+                connected: device.state == "activated",
+                iface: device.name,
+                driver,
+            })
+        })
+        .collect())
+}
+
+pub async fn connect_usb_tether(iface: &str) -> Result<()> {
     let client = dbus_client().await?;
+    let active_path = client.connect_device(iface).await?;
+    with_timeout(
+        operation_timeout(),
+        "Connecting USB tethering",
+        client.wait_for_active_connection_activated(&active_path),
+    )
+    .await
+}
 
-    let (profile, active, device, ip4_info) = client.get_network_info_by_id(ssid).await?;
+pub async fn disconnect_usb_tether(iface: &str) -> Result<()> {
+    dbus_client().await?.disconnect_device(iface).await
+}
 
-    let mut info = NetworkInfo::default();
+pub async fn is_ethernet_enabled() -> Result<bool> {
+    dbus_client().await?.is_ethernet_enabled().await
+}
 
-    if let Some(p) = profile.as_ref() {
-        info.connection_type = Some(p.conn_type.clone());
-        info.uuid = Some(p.uuid.clone());
-        info.interface = p.interface_name.clone();
+pub async fn set_ethernet_enabled(enabled: bool) -> Result<()> {
+    dbus_client().await?.set_ethernet_enabled(enabled).await
+}
 
-        if let Some(wifi) = p.settings.get("802-11-wireless") {
-            if let Some(value) = wifi.get("mac-address") {
-                info.mac_address = value
-                    .try_clone()
-                    .ok()
-                    .and_then(|v| String::try_from(v).ok());
-            }
-            if info.mac_address.is_none() {
-                if let Some(value) = wifi.get("seen-bssids") {
-                    info.mac_address = value
-                        .try_clone()
-                        .ok()
-                        .and_then(|v| String::try_from(v).ok());
-                }
-            }
-        }
+pub async fn has_wifi_device() -> Result<bool> {
+    if !is_wifi_present().await? {
+        return Ok(false);
     }
 
-    if let Some(active) = active.as_ref() {
-        info.state = Some(active_connection_state_label(active.state).to_string());
-        if info.connection_type.is_none() {
-            info.connection_type = Some(active.conn_type.clone());
+    let devices = NetworkManager::get_devices().await?;
+    Ok(devices
+        .into_iter()
+        .any(|d| d.device_type == DeviceType::Wifi && d.state != "unmanaged"))
+}
+
+pub async fn is_wifi_present() -> Result<bool> {
+    dbus_client().await?.is_wifi_present().await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NmVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub micro: u32,
+}
+
+impl NmVersion {
+    pub const fn new(major: u32, minor: u32, micro: u32) -> Self {
+        Self {
+            major,
+            minor,
+            micro,
         }
     }
 
-    if let Some(device) = device.as_ref() {
-        info.interface = Some(device.interface.clone());
-        if info.mac_address.is_none() {
-            info.mac_address = Some(device.interface.clone());
-        }
+    // * Accepts distro suffixes like "1.42.2-1.fc38" or "1.47.90-dev".
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split('.').map(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<u32>().ok()
+        });
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let micro = parts.next().flatten().unwrap_or(0);
+        Some(Self::new(major, minor, micro))
     }
+}
 
-    if let Some(ip) = ip4_info.addresses.first() {
-        info.ip_address = Some(ip.clone());
+impl std::fmt::Display for NmVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
     }
-    info.gateway = ip4_info.gateway;
-    info.dns = ip4_info.dns;
-    info.dhcp_lease_time_seconds = ip4_info.dhcp_lease_time_seconds;
+}
 
-    Ok(info)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NmFeature {
+    Sae,
+    Owe,
+    Wpa3Hotspot,
+    Band6Ghz,
+    SharedDhcpRange,
+    ApIsolation,
 }
 
-pub async fn get_active_wifi_ssid() -> Result<Option<String>> {
-    dbus_client().await?.get_active_wifi_ssid().await
+impl NmFeature {
+    pub fn label(self) -> &'static str {
+        match self {
+            NmFeature::Sae => "WPA3 Personal",
+            NmFeature::Owe => "Enhanced Open (OWE)",
+            NmFeature::Wpa3Hotspot => "WPA3 hotspots",
+            NmFeature::Band6Ghz => "6 GHz Wi-Fi",
+            NmFeature::SharedDhcpRange => "A custom DHCP range",
+            NmFeature::ApIsolation => "Wi-Fi client isolation",
+        }
+    }
+
+    // * First NM release whose settings accept the feature's key-mgmt or band value.
+    pub fn min_version(self) -> NmVersion {
+        match self {
+            NmFeature::Sae => NmVersion::new(1, 16, 0),
+            NmFeature::Owe => NmVersion::new(1, 20, 0),
+            NmFeature::Wpa3Hotspot => NmVersion::new(1, 24, 0),
+            NmFeature::Band6Ghz => NmVersion::new(1, 46, 0),
+            NmFeature::SharedDhcpRange => NmVersion::new(1, 46, 0),
+            NmFeature::ApIsolation => NmVersion::new(1, 28, 0),
+        }
+    }
 }
 
-pub async fn get_active_wired_connection() -> Result<Option<String>> {
-    dbus_client().await?.get_active_wired_connection().await
+// * Probed once at startup; an unknown version leaves every option enabled rather than guessing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NmCapabilities {
+    pub version: Option<NmVersion>,
 }
 
-fn vpn_kind_for_profile(profile: &DbusConnectionProfile) -> Option<VpnKind> {
-    if profile.conn_type == "wireguard" {
-        return Some(VpnKind::WireGuard);
+impl NmCapabilities {
+    pub fn supports(&self, feature: NmFeature) -> bool {
+        self.version
+            .is_none_or(|version| version >= feature.min_version())
     }
 
-    if profile.conn_type == "vpn" {
-        let service_type = profile
-            .settings
-            .get("vpn")
-            .and_then(|vpn| vpn.get("service-type"))
-            .and_then(value_string);
-        if matches!(
-            service_type.as_deref(),
-            Some("org.freedesktop.NetworkManager.openvpn")
-        ) {
-            return Some(VpnKind::OpenVpn);
+    pub fn unsupported_reason(&self, feature: NmFeature) -> Option<String> {
+        let version = self.version?;
+        if version >= feature.min_version() {
+            return None;
         }
+        Some(format!(
+            "{} needs NetworkManager {}.{} or newer (this system has {})",
+            feature.label(),
+            feature.min_version().major,
+            feature.min_version().minor,
+            version
+        ))
     }
+}
 
-    None
+fn capabilities_cell() -> &'static Mutex<NmCapabilities> {
+    static CAPABILITIES: OnceLock<Mutex<NmCapabilities>> = OnceLock::new();
+    CAPABILITIES.get_or_init(|| Mutex::new(NmCapabilities::default()))
 }
 
-pub async fn list_supported_vpn_connections() -> Result<Vec<VpnConnection>> {
-    let client = dbus_client().await?;
-    let profiles = client.list_connections().await?;
-    let active = client.list_active_connections().await?;
+pub async fn detect_capabilities() -> Result<NmCapabilities> {
+    let text = dbus_client().await?.get_version().await?;
+    let version = NmVersion::parse(&text)
+        .ok_or_else(|| anyhow!("Unrecognized NetworkManager version '{}'", text))?;
+    let detected = NmCapabilities {
+        version: Some(version),
+    };
+    *capabilities_cell()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = detected;
+    log::info!("Detected NetworkManager {}", version);
+    Ok(detected)
+}
 
-    let mut active_map = HashMap::new();
-    for connection in active {
-        active_map.insert(connection.uuid.clone(), connection);
+pub fn capabilities() -> NmCapabilities {
+    *capabilities_cell()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// * Wi-Fi interface carrying the active connection, or the first usable adapter.
+pub async fn get_active_wifi_interface() -> Result<Option<String>> {
+    let devices = dbus_client().await?.get_wifi_devices().await?;
+    Ok(devices
+        .iter()
+        .find(|d| d.state == NM_DEVICE_STATE_ACTIVATED)
+        .or_else(|| devices.first())
+        .map(|d| d.interface.clone()))
+}
+
+pub async fn get_wifi_power_save(iface: &str) -> Result<bool> {
+    let output =
+        command_output(Command::new("iw").args(["dev", iface, "get", "power_save"])).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("iw failed: {}", stderr));
     }
 
-    let mut out = Vec::new();
-    for profile in profiles {
-        let Some(kind) = vpn_kind_for_profile(&profile) else {
-            continue;
-        };
-        let active = active_map
-            .get(&profile.uuid)
-            .map(|connection| connection.state == NM_ACTIVE_CONNECTION_STATE_ACTIVATED)
-            .unwrap_or(false);
-        out.push(VpnConnection {
-            name: profile.id,
-            uuid: profile.uuid,
-            kind,
-            active,
-        });
+    parse_power_save_output(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow!("Unexpected iw output for {}", iface))
+}
+
+pub async fn set_wifi_power_save(iface: &str, enabled: bool) -> Result<()> {
+    let state = if enabled { "on" } else { "off" };
+    let output =
+        command_output(Command::new("iw").args(["dev", iface, "set", "power_save", state])).await?;
+    if output.status.success() {
+        return Ok(());
     }
 
-    out.sort_by(|a, b| {
-        if a.active && !b.active {
-            CmpOrdering::Less
-        } else if !a.active && b.active {
-            CmpOrdering::Greater
-        } else {
-            a.name.to_lowercase().cmp(&b.name.to_lowercase())
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.contains("Operation not permitted") || stderr.contains("(-1)") {
+        Err(anyhow!("Changing power save needs root privileges"))
+    } else if stderr.is_empty() {
+        Err(anyhow!("iw command failed"))
+    } else {
+        Err(anyhow!(stderr))
+    }
+}
+
+// * "Power save: on" / "Power save: off"
+fn parse_power_save_output(output: &str) -> Option<bool> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if !key.trim().eq_ignore_ascii_case("power save") {
+            return None;
         }
-    });
+        match value.trim().to_ascii_lowercase().as_str() {
+            "on" => Some(true),
+            "off" => Some(false),
+            _ => None,
+        }
+    })
+}
 
-    Ok(out)
+pub async fn has_ethernet_device() -> Result<bool> {
+    let devices = NetworkManager::get_devices().await?;
+    Ok(devices
+        .into_iter()
+        .any(|d| d.device_type == DeviceType::Ethernet))
 }
 
-pub async fn activate_vpn_connection(uuid: &str) -> Result<()> {
-    // * Use activate_vpn_connection_by_uuid — VPNs need "/" as device path,
-    // * not an actual ethernet/wifi device (causes UnknownDevice error otherwise)
-    dbus_client()
-        .await?
-        .activate_vpn_connection_by_uuid(uuid)
-        .await?;
-    Ok(())
+pub async fn scan_networks() -> Result<Vec<WifiNetwork>> {
+    with_timeout(scan_timeout(), "Scan", backend().scan_networks())
+        .await
+        .map(remember_scan)
 }
 
-pub async fn deactivate_vpn_connection(uuid: &str) -> Result<()> {
-    dbus_client()
-        .await?
-        .deactivate_connection_by_uuid(uuid)
+pub async fn scan_networks_quick() -> Result<Vec<WifiNetwork>> {
+    with_timeout(scan_timeout(), "Scan", backend().scan_networks_quick())
         .await
+        .map(remember_scan)
 }
 
-pub async fn delete_vpn_connection(uuid: &str) -> Result<()> {
-    dbus_client().await?.delete_connection_by_uuid(uuid).await
+pub async fn cached_networks() -> Result<Vec<WifiNetwork>> {
+    with_timeout(
+        scan_timeout(),
+        "Listing networks",
+        backend().cached_networks(),
+    )
+    .await
+    .map(remember_scan)
 }
 
-pub async fn get_wireguard_connection_config(uuid: &str) -> Result<WireGuardConnectionConfig> {
-    let client = dbus_client().await?;
-    let profile = client
-        .find_connection_by_uuid(uuid)
-        .await?
-        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
-    let kind = vpn_kind_for_profile(&profile)
-        .ok_or_else(|| anyhow!("Connection {} is not a supported VPN", uuid))?;
-    if kind != VpnKind::WireGuard {
-        return Err(anyhow!("Connection {} is not a WireGuard VPN", uuid));
+// * Single scans miss networks now and then; keep a network this long before dropping it.
+const SCAN_CACHE_TTL: Duration = Duration::from_secs(30);
+
+struct CachedNetwork {
+    seen_at: Instant,
+    network: WifiNetwork,
+}
+
+#[derive(Default)]
+struct ScanCache {
+    updated_at: Option<Instant>,
+    entries: Vec<CachedNetwork>,
+}
+
+fn scan_cache() -> &'static Mutex<ScanCache> {
+    static CACHE: OnceLock<Mutex<ScanCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(ScanCache::default()))
+}
+
+// * What the last scan found, so a page can render it while its own rescan runs.
+pub struct LastScan {
+    pub networks: Vec<WifiNetwork>,
+    pub age: Duration,
+}
+
+impl LastScan {
+    pub fn age_label(&self) -> String {
+        match self.age.as_secs() {
+            s if s < 5 => "updated just now".to_string(),
+            s if s < 60 => format!("updated {}s ago", s),
+            s => format!("updated {}m ago", s / 60),
+        }
     }
+}
 
-    let addresses = collect_address_strings(&profile.settings);
-    let dns_servers = collect_dns_strings(&profile.settings);
-    let interface_name = profile
-        .interface_name
-        .clone()
-        .unwrap_or_else(|| "wg0".to_string());
+pub fn last_scan() -> Option<LastScan> {
+    let cache = scan_cache().lock().ok()?;
+    let updated_at = cache.updated_at?;
+    Some(LastScan {
+        networks: cache.entries.iter().map(|c| c.network.clone()).collect(),
+        age: updated_at.elapsed(),
+    })
+}
 
-    let mut config = WireGuardConnectionConfig {
-        name: profile.id.clone(),
-        interface_name,
-        addresses,
-        dns_servers,
-        private_key: Zeroizing::new(
-            profile
-                .settings
-                .get("wireguard")
-                .and_then(|section| section.get("private-key"))
-                .and_then(value_string)
-                .unwrap_or_default(),
-        ),
-        mtu: profile
-            .settings
-            .get("wireguard")
-            .and_then(|section| section.get("mtu"))
-            .and_then(value_u32),
-        ..WireGuardConnectionConfig::default()
+pub fn clear_scan_cache() {
+    if let Ok(mut cache) = scan_cache().lock() {
+        *cache = ScanCache::default();
+    }
+}
+
+fn remember_scan(networks: Vec<WifiNetwork>) -> Vec<WifiNetwork> {
+    let Ok(mut cache) = scan_cache().lock() else {
+        return networks;
     };
+    let now = Instant::now();
+    let previous = std::mem::take(&mut cache.entries);
+    cache.entries = merge_scan(previous, networks, now);
+    cache.updated_at = Some(now);
+    cache.entries.iter().map(|c| c.network.clone()).collect()
+}
 
-    for (section_name, section) in &profile.settings {
-        let Some(public_key) = section_name.strip_prefix("wireguard-peer.") else {
-            continue;
-        };
-        config.public_key = public_key.to_string();
-        config.endpoint = section
-            .get("endpoint")
-            .and_then(value_string)
-            .unwrap_or_default();
-        config.preshared_key = section.get("preshared-key").and_then(value_string).map(Zeroizing::new);
-        config.persistent_keepalive = section.get("persistent-keepalive").and_then(value_u32);
-        config.allowed_ips = section
-            .get("allowed-ips")
-            .and_then(value_string_list)
-            .unwrap_or_else(|| vec!["0.0.0.0/0".to_string(), "::/0".to_string()]);
-        break;
+// * Fresh results win; recently seen networks missing from this scan stay until the TTL runs out.
+fn merge_scan(
+    previous: Vec<CachedNetwork>,
+    fresh: Vec<WifiNetwork>,
+    now: Instant,
+) -> Vec<CachedNetwork> {
+    let key = |n: &WifiNetwork| (n.ssid.clone(), n.band.clone(), n.security_type.clone());
+    let fresh_keys: HashSet<_> = fresh.iter().map(key).collect();
+
+    let mut merged: Vec<CachedNetwork> = fresh
+        .into_iter()
+        .map(|network| CachedNetwork {
+            seen_at: now,
+            network,
+        })
+        .collect();
+    merged.extend(
+        previous
+            .into_iter()
+            .filter(|cached| {
+                !fresh_keys.contains(&key(&cached.network))
+                    && now.duration_since(cached.seen_at) < SCAN_CACHE_TTL
+            })
+            .map(|mut cached| {
+                cached.network.connected = false;
+                cached
+            }),
+    );
+    merged.sort_by(|a, b| compare_wifi_networks(&a.network, &b.network));
+    merged
+}
+
+async fn scan_networks_via_dbus() -> Result<Vec<WifiNetwork>> {
+    let client = dbus_client().await?;
+
+    // * Merge cached access points with a fresh scan because NM scan completion is asynchronous.
+    let mut aps = client.list_access_points().await.unwrap_or_default();
+    let _ = client.request_wifi_scan().await;
+    sleep(Duration::from_millis(1200)).await;
+    if let Ok(scanned) = client.list_access_points().await {
+        aps.extend(scanned);
     }
 
-    Ok(config)
+    Ok(networks_from_access_points(aps))
 }
 
-pub async fn get_openvpn_connection_config(uuid: &str) -> Result<OpenVpnConnectionConfig> {
+// * Access points NM already knows about; requests a rescan but doesn't wait for it.
+async fn scan_networks_quick_via_dbus() -> Result<Vec<WifiNetwork>> {
     let client = dbus_client().await?;
-    let profile = client
-        .find_connection_by_uuid(uuid)
-        .await?
-        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
-    let kind = vpn_kind_for_profile(&profile)
-        .ok_or_else(|| anyhow!("Connection {} is not a supported VPN", uuid))?;
-    if kind != VpnKind::OpenVpn {
-        return Err(anyhow!("Connection {} is not an OpenVPN connection", uuid));
+    let aps = client.list_access_points().await?;
+    let _ = client.request_wifi_scan().await;
+    Ok(networks_from_access_points(aps))
+}
+
+// * No rescan at all: used when NM itself reports access point changes.
+async fn cached_networks_via_dbus() -> Result<Vec<WifiNetwork>> {
+    let aps = dbus_client().await?.list_access_points().await?;
+    Ok(networks_from_access_points(aps))
+}
+
+fn networks_from_access_points(aps: Vec<DbusAccessPoint>) -> Vec<WifiNetwork> {
+    let mut networks_by_key: HashMap<(String, String, String), WifiNetwork> = HashMap::new();
+
+    for ap in aps {
+        let normalized_freq = normalize_frequency_mhz(ap.frequency);
+        let band = band_from_frequency(normalized_freq);
+        let network = WifiNetwork {
+            ssid: ap.ssid.clone(),
+            signal: ap.strength,
+            secured: is_ap_secured(&ap),
+            connected: ap.active,
+            band: band.to_string(),
+            channel: channel_from_frequency(normalized_freq),
+            freq_mhz: normalized_freq,
+            security_type: ap_security_type(&ap),
+            device: Some(ap.device.to_string()),
+        };
+
+        // * Keep distinct entries for SSID + band + security because one SSID may expose variants.
+        let key = (
+            network.ssid.clone(),
+            network.band.clone(),
+            network.security_type.clone(),
+        );
+        match networks_by_key.get_mut(&key) {
+            None => {
+                networks_by_key.insert(key, network);
+            }
+            Some(existing) => {
+                if (network.connected && !existing.connected)
+                    || (network.connected == existing.connected && network.signal > existing.signal)
+                {
+                    *existing = network;
+                }
+            }
+        }
     }
 
-    let vpn_section = profile
-        .settings
-        .get("vpn")
-        .ok_or_else(|| anyhow!("OpenVPN settings are missing"))?;
-    let vpn_data = vpn_section
-        .get("data")
-        .and_then(value_string_map)
-        .unwrap_or_default();
+    let mut networks: Vec<WifiNetwork> = networks_by_key.into_values().collect();
+    networks.sort_by(compare_wifi_networks);
+    networks
+}
 
-    Ok(OpenVpnConnectionConfig {
-        name: profile.id,
-        remote: vpn_data.get("remote").cloned().unwrap_or_default(),
-        username: vpn_section
-            .get("user-name")
-            .and_then(value_string)
-            .or_else(|| vpn_data.get("username").cloned()),
+// * Every BSSID currently advertising `ssid`, the one we're associated with first.
+pub async fn list_access_points_for_ssid(ssid: &str) -> Result<Vec<AccessPoint>> {
+    let aps = with_timeout(operation_timeout(), "Listing access points", async {
+        dbus_client().await?.list_access_points().await
     })
+    .await?;
+    Ok(access_points_for_ssid(aps, ssid))
 }
 
-pub async fn create_openvpn_connection(config: &OpenVpnConnectionConfig) -> Result<()> {
+fn access_points_for_ssid(aps: Vec<DbusAccessPoint>, ssid: &str) -> Vec<AccessPoint> {
+    let mut by_bssid: HashMap<String, AccessPoint> = HashMap::new();
+
+    for ap in aps.into_iter().filter(|ap| ap.ssid == ssid) {
+        let freq_mhz = normalize_frequency_mhz(ap.frequency);
+        let entry = AccessPoint {
+            bssid: ap.bssid.to_uppercase(),
+            signal: ap.strength,
+            band: band_from_frequency(freq_mhz).to_string(),
+            channel: channel_from_frequency(freq_mhz),
+            freq_mhz,
+            active: ap.active,
+            supports_wps: ap.flags & NM_802_11_AP_FLAGS_WPS_PBC != 0,
+        };
+        // * Two Wi-Fi adapters see the same AP; keep whichever one is associated or hears it best.
+        let keep_existing = by_bssid.get(&entry.bssid).is_some_and(|existing| {
+            (existing.active, existing.signal) >= (entry.active, entry.signal)
+        });
+        if !keep_existing {
+            by_bssid.insert(entry.bssid.clone(), entry);
+        }
+    }
+
+    let mut access_points: Vec<AccessPoint> = by_bssid.into_values().collect();
+    access_points.sort_by(|a, b| {
+        b.active
+            .cmp(&a.active)
+            .then_with(|| b.signal.cmp(&a.signal))
+            .then_with(|| a.bssid.cmp(&b.bssid))
+    });
+    access_points
+}
+
+const NETWORK_INFO_RETRY_ATTEMPTS: u32 = 5;
+const NETWORK_INFO_RETRY_DELAY: Duration = Duration::from_millis(800);
+
+// * Right after connecting DHCP may still be running; poll until an IP shows up.
+pub async fn get_network_info_with_retry(
+    ssid: &str,
+    device_path: Option<&str>,
+) -> Result<NetworkInfo> {
+    retry_until(
+        NETWORK_INFO_RETRY_ATTEMPTS,
+        NETWORK_INFO_RETRY_DELAY,
+        || get_network_info_on(ssid, device_path),
+        |info| info.ip_address.is_some(),
+    )
+    .await
+}
+
+// * Returns the first result accepted by `done`, otherwise the last one fetched.
+async fn retry_until<T, F, Fut>(
+    attempts: u32,
+    delay: Duration,
+    mut fetch: F,
+    done: impl Fn(&T) -> bool,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        let result = fetch().await;
+        let finished = matches!(&result, Ok(value) if done(value));
+        if finished || attempt >= attempts {
+            return result;
+        }
+        attempt += 1;
+        sleep(delay).await;
+    }
+}
+
+pub async fn get_network_info(ssid: &str) -> Result<NetworkInfo> {
+    with_timeout(
+        operation_timeout(),
+        "Reading connection details",
+        backend().network_info(ssid),
+    )
+    .await
+}
+
+// * `device_path` comes from WifiNetwork::device or Connection::device; it wins over the name.
+pub async fn get_network_info_on(name: &str, device_path: Option<&str>) -> Result<NetworkInfo> {
+    match device_path {
+        Some(device_path) => {
+            with_timeout(
+                operation_timeout(),
+                "Reading connection details",
+                get_device_network_info(device_path),
+            )
+            .await
+        }
+        None => get_network_info(name).await,
+    }
+}
+
+// * First IPv4 gateway among activated devices; None when offline or on a point-to-point link.
+pub async fn default_gateway() -> Result<Option<String>> {
     let client = dbus_client().await?;
-    client
-        .add_connection(&build_openvpn_settings(config)?)
-        .await?;
-    Ok(())
+    for device in client.list_devices().await? {
+        if device.state != NM_DEVICE_STATE_ACTIVATED {
+            continue;
+        }
+        if let Ok(Some(gateway)) = client
+            .get_ip4_info(&device.path)
+            .await
+            .map(|info| info.gateway)
+        {
+            return Ok(Some(gateway));
+        }
+    }
+    Ok(None)
 }
 
-pub async fn update_openvpn_connection(uuid: &str, config: &OpenVpnConnectionConfig) -> Result<()> {
+// * Interface, IPv4 address and prefix length of the primary connection.
+pub async fn primary_ipv4_subnet() -> Result<Option<(String, std::net::Ipv4Addr, u32)>> {
     let client = dbus_client().await?;
-    let profile = client
-        .find_connection_by_uuid(uuid)
+    let Some(iface) = client.get_primary_connected_device().await? else {
+        return Ok(None);
+    };
+    let Some(device) = client
+        .list_devices()
         .await?
-        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
-    let mut settings = clone_settings_map(&profile.settings)?;
+        .into_iter()
+        .find(|device| device.interface == iface)
+    else {
+        return Ok(None);
+    };
+    let info = client.get_ip4_info(&device.path).await?;
+    let address = info
+        .addresses
+        .first()
+        .and_then(|address| address.parse().ok());
+    Ok(address.map(|address| (iface, address, info.prefix.unwrap_or(24))))
+}
 
-    let connection = settings.entry("connection".to_string()).or_default();
-    connection.insert("id".to_string(), owned_string(&config.name));
-    connection.insert("type".to_string(), owned_string("vpn"));
-    connection.insert(
-        "uuid".to_string(),
-        profile
-            .settings
-            .get("connection")
-            .and_then(|section| section.get("uuid"))
-            .and_then(|value| value.try_clone().ok())
-            .unwrap_or_else(|| owned_string(&profile.uuid)),
-    );
+pub async fn get_device_network_info(device_path: &str) -> Result<NetworkInfo> {
+    let client = dbus_client().await?;
+    let (profile, active, device, ip4_info) =
+        client.get_network_info_by_device(device_path).await?;
+    Ok(network_info_from_parts(profile, active, device, ip4_info))
+}
 
-    let vpn = settings.entry("vpn".to_string()).or_default();
-    vpn.insert(
-        "service-type".to_string(),
-        owned_string("org.freedesktop.NetworkManager.openvpn"),
-    );
-    if let Some(username) = config
-        .username
-        .as_deref()
-        .filter(|value| !value.trim().is_empty())
-    {
-        vpn.insert("user-name".to_string(), owned_string(username.trim()));
-    } else {
-        vpn.remove("user-name");
-    }
-    vpn.insert(
-        "data".to_string(),
-        owned_string_map(HashMap::from([
-            ("connection-type".to_string(), "password".to_string()),
-            ("remote".to_string(), config.remote.trim().to_string()),
-        ]))?,
-    );
+async fn get_network_info_via_dbus(ssid: &str) -> Result<NetworkInfo> {
+    let client = dbus_client().await?;
+    let (profile, active, device, ip4_info) = client.get_network_info_by_id(ssid).await?;
+    Ok(network_info_from_parts(profile, active, device, ip4_info))
+}
 
-    let ipv4 = settings.entry("ipv4".to_string()).or_default();
-    ipv4.insert("method".to_string(), owned_string("auto"));
-    let ipv6 = settings.entry("ipv6".to_string()).or_default();
-    ipv6.insert("method".to_string(), owned_string("ignore"));
+fn network_info_from_parts(
+    profile: Option<DbusConnectionProfile>,
+    active: Option<DbusActiveConnection>,
+    device: Option<DbusDevice>,
+    ip4_info: DbusIp4Info,
+) -> NetworkInfo {
+    let mut info = NetworkInfo::default();
 
-    // * Check if this VPN is currently active before updating — we'll reconnect after
-    let was_active = client
-        .list_active_connections()
-        .await
-        .unwrap_or_default()
-        .iter()
-        .any(|c| c.uuid == uuid);
+    if let Some(p) = profile.as_ref() {
+        info.connection_type = Some(p.conn_type.clone());
+        info.uuid = Some(p.uuid.clone());
+        info.interface = p.interface_name.clone();
 
-    // ! Deactivate before updating — NM rejects settings changes on live VPN connections
-    if was_active {
-        log::info!("Deactivating VPN {} before settings update", uuid);
-        client.deactivate_connection_by_uuid(uuid).await?;
-    }
+        if let Some(wifi) = p.settings.get("802-11-wireless") {
+            if let Some(value) = wifi.get("mac-address") {
+                info.mac_address = value
+                    .try_clone()
+                    .ok()
+                    .and_then(|v| String::try_from(v).ok());
+            }
+            if info.mac_address.is_none() {
+                if let Some(value) = wifi.get("seen-bssids") {
+                    info.mac_address = value
+                        .try_clone()
+                        .ok()
+                        .and_then(|v| String::try_from(v).ok());
+                }
+            }
+        }
+    }
+
+    if let Some(active) = active.as_ref() {
+        info.state = Some(active_connection_state_label(active.state).to_string());
+        if info.connection_type.is_none() {
+            info.connection_type = Some(active.conn_type.clone());
+        }
+    }
+
+    if let Some(device) = device.as_ref() {
+        info.interface = Some(device.interface.clone());
+        if info.mac_address.is_none() {
+            info.mac_address = Some(device.interface.clone());
+        }
+    }
+
+    if let Some(ip) = ip4_info.addresses.first() {
+        info.ip_address = Some(ip.clone());
+    }
+    info.gateway = ip4_info.gateway;
+    info.dns = ip4_info.dns;
+    info.dhcp_lease_time_seconds = ip4_info.dhcp_lease_time_seconds;
+
+    info
+}
+
+pub async fn get_active_wifi_ssid() -> Result<Option<String>> {
+    backend().active_wifi_ssid().await
+}
+
+pub async fn get_active_wired_connection() -> Result<Option<String>> {
+    dbus_client().await?.get_active_wired_connection().await
+}
+
+fn vpn_kind_for_profile(profile: &DbusConnectionProfile) -> Option<VpnKind> {
+    if profile.conn_type == "wireguard" {
+        return Some(VpnKind::WireGuard);
+    }
+
+    if profile.conn_type == "vpn" {
+        let service_type = profile
+            .settings
+            .get("vpn")
+            .and_then(|vpn| vpn.get("service-type"))
+            .and_then(value_string);
+        if matches!(
+            service_type.as_deref(),
+            Some("org.freedesktop.NetworkManager.openvpn")
+        ) {
+            return Some(VpnKind::OpenVpn);
+        }
+    }
+
+    None
+}
+
+pub async fn list_supported_vpn_connections() -> Result<Vec<VpnConnection>> {
+    let client = dbus_client().await?;
+    let profiles = client.list_connections().await?;
+    let active = client.list_active_connections().await?;
+
+    let mut active_map = HashMap::new();
+    for connection in active {
+        active_map.insert(connection.uuid.clone(), connection);
+    }
+
+    let mut out = Vec::new();
+    for profile in profiles {
+        let Some(kind) = vpn_kind_for_profile(&profile) else {
+            continue;
+        };
+        let active = active_map
+            .get(&profile.uuid)
+            .map(|connection| connection.state == NM_ACTIVE_CONNECTION_STATE_ACTIVATED)
+            .unwrap_or(false);
+        out.push(VpnConnection {
+            name: profile.id,
+            uuid: profile.uuid,
+            kind,
+            active,
+        });
+    }
+
+    out.sort_by(|a, b| {
+        if a.active && !b.active {
+            CmpOrdering::Less
+        } else if !a.active && b.active {
+            CmpOrdering::Greater
+        } else {
+            a.name.to_lowercase().cmp(&b.name.to_lowercase())
+        }
+    });
+
+    Ok(out)
+}
+
+pub async fn activate_vpn_connection(uuid: &str) -> Result<()> {
+    // * Use activate_vpn_connection_by_uuid — VPNs need "/" as device path,
+    // * not an actual ethernet/wifi device (causes UnknownDevice error otherwise)
+    dbus_client()
+        .await?
+        .activate_vpn_connection_by_uuid(uuid)
+        .await?;
+    Ok(())
+}
+
+pub async fn deactivate_vpn_connection(uuid: &str) -> Result<()> {
+    dbus_client()
+        .await?
+        .deactivate_connection_by_uuid(uuid)
+        .await
+}
+
+pub async fn delete_vpn_connection(uuid: &str) -> Result<()> {
+    dbus_client().await?.delete_connection_by_uuid(uuid).await
+}
+
+pub async fn get_wireguard_connection_config(uuid: &str) -> Result<WireGuardConnectionConfig> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+    let kind = vpn_kind_for_profile(&profile)
+        .ok_or_else(|| anyhow!("Connection {} is not a supported VPN", uuid))?;
+    if kind != VpnKind::WireGuard {
+        return Err(anyhow!("Connection {} is not a WireGuard VPN", uuid));
+    }
+
+    let secrets = client
+        .get_connection_secrets(&profile.path, "wireguard")
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("WireGuard secrets for {} unavailable: {}", uuid, e);
+            SettingsMap::new()
+        });
+    let addresses = collect_address_strings(&profile.settings);
+    let dns_servers = collect_dns_strings(&profile.settings);
+    let interface_name = profile
+        .interface_name
+        .clone()
+        .unwrap_or_else(|| "wg0".to_string());
+
+    let mut config = WireGuardConnectionConfig {
+        name: profile.id.clone(),
+        interface_name,
+        addresses,
+        dns_servers,
+        private_key: Zeroizing::new(
+            [&secrets, &profile.settings]
+                .iter()
+                .find_map(|settings| {
+                    settings
+                        .get("wireguard")
+                        .and_then(|section| section.get("private-key"))
+                        .and_then(value_string)
+                })
+                .unwrap_or_default(),
+        ),
+        mtu: profile
+            .settings
+            .get("wireguard")
+            .and_then(|section| section.get("mtu"))
+            .and_then(value_u32),
+        ..WireGuardConnectionConfig::default()
+    };
+
+    let peers = |settings: &SettingsMap| {
+        settings
+            .get("wireguard")
+            .and_then(|section| section.get("peers"))
+            .and_then(|value| {
+                Vec::<HashMap<String, OwnedValue>>::try_from(value.try_clone().ok()?).ok()
+            })
+            .unwrap_or_default()
+    };
+    let secret_peers = peers(&secrets);
+    let stored_peers: Vec<WireGuardPeer> = peers(&profile.settings)
+        .iter()
+        .map(|peer| {
+            let public_key = peer
+                .get("public-key")
+                .and_then(value_string)
+                .unwrap_or_default();
+            let preshared_key = secret_peers
+                .iter()
+                .find(|secret| {
+                    secret.get("public-key").and_then(value_string).as_deref()
+                        == Some(public_key.as_str())
+                })
+                .and_then(|secret| secret.get("preshared-key"))
+                .and_then(value_string)
+                .map(Zeroizing::new);
+            WireGuardPeer {
+                endpoint: peer
+                    .get("endpoint")
+                    .and_then(value_string)
+                    .unwrap_or_default(),
+                preshared_key,
+                persistent_keepalive: peer.get("persistent-keepalive").and_then(value_u32),
+                allowed_ips: peer
+                    .get("allowed-ips")
+                    .and_then(value_string_list)
+                    .unwrap_or_else(|| vec!["0.0.0.0/0".to_string(), "::/0".to_string()]),
+                public_key,
+            }
+        })
+        .collect();
+    if !stored_peers.is_empty() {
+        config.set_peers(stored_peers);
+        return Ok(config);
+    }
+
+    for (section_name, section) in &profile.settings {
+        let Some(public_key) = section_name.strip_prefix("wireguard-peer.") else {
+            continue;
+        };
+        config.public_key = public_key.to_string();
+        config.endpoint = section
+            .get("endpoint")
+            .and_then(value_string)
+            .unwrap_or_default();
+        config.preshared_key = section
+            .get("preshared-key")
+            .and_then(value_string)
+            .map(Zeroizing::new);
+        config.persistent_keepalive = section.get("persistent-keepalive").and_then(value_u32);
+        config.allowed_ips = section
+            .get("allowed-ips")
+            .and_then(value_string_list)
+            .unwrap_or_else(|| vec!["0.0.0.0/0".to_string(), "::/0".to_string()]);
+        break;
+    }
+
+    Ok(config)
+}
+
+// * Interface and configured endpoint come from the profile, the live numbers from the kernel.
+pub async fn get_wireguard_stats(uuid: &str) -> Result<TunnelStats> {
+    let profile = dbus_client()
+        .await?
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+    let interface = profile
+        .interface_name
+        .clone()
+        .ok_or_else(|| anyhow!("WireGuard VPN {} has no interface name", profile.id))?;
+    let endpoint = wireguard_peer_endpoint(&profile.settings);
+    Ok(wireguard::read_tunnel_stats(&interface, endpoint.as_deref()).await)
+}
+
+// * NM exposes peers as an array of dicts over D-Bus; keyfile-style sections are the fallback.
+fn wireguard_peer_endpoint(settings: &SettingsMap) -> Option<String> {
+    let from_array = settings
+        .get("wireguard")
+        .and_then(|section| section.get("peers"))
+        .and_then(|value| {
+            Vec::<HashMap<String, OwnedValue>>::try_from(value.try_clone().ok()?).ok()
+        })
+        .and_then(|peers| {
+            peers
+                .iter()
+                .find_map(|peer| peer.get("endpoint").and_then(value_string))
+        });
+    from_array.or_else(|| {
+        settings
+            .iter()
+            .filter(|(name, _)| name.starts_with("wireguard-peer."))
+            .find_map(|(_, section)| section.get("endpoint").and_then(value_string))
+    })
+}
+
+pub async fn get_openvpn_connection_config(uuid: &str) -> Result<OpenVpnConnectionConfig> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+    let kind = vpn_kind_for_profile(&profile)
+        .ok_or_else(|| anyhow!("Connection {} is not a supported VPN", uuid))?;
+    if kind != VpnKind::OpenVpn {
+        return Err(anyhow!("Connection {} is not an OpenVPN connection", uuid));
+    }
+
+    let vpn_section = profile
+        .settings
+        .get("vpn")
+        .ok_or_else(|| anyhow!("OpenVPN settings are missing"))?;
+    let vpn_data = vpn_section
+        .get("data")
+        .and_then(value_string_map)
+        .unwrap_or_default();
+
+    let ipv4_flag = |key: &str| {
+        profile
+            .settings
+            .get("ipv4")
+            .and_then(|section| section.get(key))
+            .and_then(value_bool)
+            .unwrap_or(false)
+    };
+
+    Ok(OpenVpnConnectionConfig {
+        name: profile.id.clone(),
+        remote: vpn_data.get("remote").cloned().unwrap_or_default(),
+        username: vpn_section
+            .get("user-name")
+            .and_then(value_string)
+            .or_else(|| vpn_data.get("username").cloned()),
+        autoconnect: profile.autoconnect.unwrap_or(false),
+        never_default: ipv4_flag("never-default"),
+        ignore_auto_routes: ipv4_flag("ignore-auto-routes"),
+    })
+}
+
+pub async fn create_openvpn_connection(config: &OpenVpnConnectionConfig) -> Result<()> {
+    let client = dbus_client().await?;
+    client
+        .add_connection(&build_openvpn_settings(config)?)
+        .await?;
+    Ok(())
+}
+
+pub async fn update_openvpn_connection(uuid: &str, config: &OpenVpnConnectionConfig) -> Result<()> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+    let mut settings = clone_settings_map(&profile.settings)?;
+
+    let connection = settings.entry("connection".to_string()).or_default();
+    connection.insert("id".to_string(), owned_string(&config.name));
+    connection.insert("type".to_string(), owned_string("vpn"));
+    connection.insert(
+        "uuid".to_string(),
+        profile
+            .settings
+            .get("connection")
+            .and_then(|section| section.get("uuid"))
+            .and_then(|value| value.try_clone().ok())
+            .unwrap_or_else(|| owned_string(&profile.uuid)),
+    );
+
+    let vpn = settings.entry("vpn".to_string()).or_default();
+    vpn.insert(
+        "service-type".to_string(),
+        owned_string("org.freedesktop.NetworkManager.openvpn"),
+    );
+    if let Some(username) = config
+        .username
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        vpn.insert("user-name".to_string(), owned_string(username.trim()));
+    } else {
+        vpn.remove("user-name");
+    }
+    // * Imported profiles carry certificates, ciphers and more in here; only touch the remote
+    let mut vpn_data = vpn
+        .get("data")
+        .and_then(value_string_map)
+        .unwrap_or_default();
+    vpn_data
+        .entry("connection-type".to_string())
+        .or_insert_with(|| "password".to_string());
+    vpn_data.insert("remote".to_string(), config.remote.trim().to_string());
+    vpn.insert("data".to_string(), owned_string_map(vpn_data)?);
+
+    settings
+        .entry("connection".to_string())
+        .or_default()
+        .insert("autoconnect".to_string(), config.autoconnect.into());
+    apply_vpn_routing(&mut settings, config);
+
+    // * Check if this VPN is currently active before updating — we'll reconnect after
+    let was_active = client
+        .list_active_connections()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .any(|c| c.uuid == uuid);
+
+    // ! Deactivate before updating — NM rejects settings changes on live VPN connections
+    if was_active {
+        log::info!("Deactivating VPN {} before settings update", uuid);
+        client.deactivate_connection_by_uuid(uuid).await?;
+    }
+
+    client
+        .update_connection_settings(&profile.path, &settings)
+        .await?;
+
+    // * Reconnect using VPN-safe activation (device path = "/")
+    if was_active {
+        log::info!("Reconnecting VPN {} after settings update", uuid);
+        client.activate_vpn_connection_path(&profile.path).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn create_wireguard_connection(config: &WireGuardConnectionConfig) -> Result<String> {
+    import_wireguard_config(None, config, false).await
+}
+
+pub async fn update_wireguard_connection(
+    uuid: &str,
+    config: &WireGuardConnectionConfig,
+) -> Result<String> {
+    import_wireguard_config(Some(uuid), config, true).await
+}
+
+pub async fn import_vpn_connection(path: &Path) -> Result<()> {
+    match detect_vpn_file_type(path).await? {
+        VpnKind::WireGuard => {
+            let content = fs::read_to_string(path).await?;
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("wireguard");
+            let config = parse_wireguard_config_text(name, &content)?;
+            create_wireguard_connection(&config).await.map(|_| ())
+        }
+        // ? .ovpn parsing lives in the NM OpenVPN plugin, which is only reachable through nmcli
+        VpnKind::OpenVpn => {
+            run_nmcli_command(&[
+                "connection",
+                "import",
+                "type",
+                "openvpn",
+                "file",
+                path.to_string_lossy().as_ref(),
+            ])
+            .await
+        }
+    }
+}
+
+pub async fn rename_connection(old_name: &str, new_name: &str) -> Result<()> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(anyhow!("Connection name can't be empty"));
+    }
+    if new_name == old_name {
+        return Ok(());
+    }
+    // * Profiles are looked up by name throughout, so names have to stay unique
+    if dbus_client()
+        .await?
+        .find_connection_by_id(new_name)
+        .await?
+        .is_some()
+    {
+        return Err(anyhow!("A connection named {} already exists", new_name));
+    }
+    edit_connection_by_id(old_name, |settings| {
+        settings
+            .entry("connection".to_string())
+            .or_default()
+            .insert("id".to_string(), owned_string(new_name));
+        Ok(())
+    })
+    .await
+}
+
+const SECRET_SETTINGS: [&str; 2] = ["802-11-wireless-security", "802-1x"];
+
+// * Copies settings and secrets under a new UUID and a free "<name> (copy)" name; returns that name.
+pub async fn duplicate_connection(name: &str) -> Result<String> {
+    let client = dbus_client().await?;
+    let profiles = client.list_connections().await?;
+    let profile = profiles
+        .iter()
+        .find(|p| p.id == name)
+        .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", name)))?;
+
+    let mut settings = clone_settings_map(&profile.settings)?;
+    for section in SECRET_SETTINGS {
+        if !settings.contains_key(section) {
+            continue;
+        }
+        match client.get_connection_secrets(&profile.path, section).await {
+            Ok(secrets) => {
+                for (secret_section, values) in secrets {
+                    settings.entry(secret_section).or_default().extend(values);
+                }
+            }
+            Err(e) => log::warn!("Copying {} without {} secrets: {}", name, section, e),
+        }
+    }
+
+    let taken: HashSet<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+    let copy_name = duplicate_name(name, &taken);
+    let connection = settings.entry("connection".to_string()).or_default();
+    connection.insert("id".to_string(), owned_string(&copy_name));
+    connection.insert(
+        "uuid".to_string(),
+        owned_string(&Uuid::new_v4().to_string()),
+    );
+    connection.remove("timestamp");
+    // * The copy is a variant to pick by hand; it shouldn't race the original on autoconnect
+    connection.insert("autoconnect".to_string(), false.into());
+
+    client.add_connection(&settings).await?;
+    Ok(copy_name)
+}
+
+fn duplicate_name(name: &str, taken: &HashSet<&str>) -> String {
+    let first = format!("{} (copy)", name);
+    if !taken.contains(first.as_str()) {
+        return first;
+    }
+    (2..)
+        .map(|n| format!("{} (copy {})", name, n))
+        .find(|candidate| !taken.contains(candidate.as_str()))
+        .unwrap_or(first)
+}
+
+pub async fn rename_connection_uuid(uuid: &str, name: &str) -> Result<()> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("Connection {} not found", uuid))?;
+    let mut settings = clone_settings_map(&profile.settings)?;
+    let connection = settings.entry("connection".to_string()).or_default();
+    connection.insert("id".to_string(), owned_string(name));
+    client
+        .update_connection_settings(&profile.path, &settings)
+        .await
+}
+
+pub async fn get_dhcp_client_id(name: &str) -> Result<Option<String>> {
+    let profile = dbus_client()
+        .await?
+        .find_connection_by_id(name)
+        .await?
+        .ok_or_else(|| anyhow!("Connection {} not found", name))?;
+    Ok(profile
+        .settings
+        .get("ipv4")
+        .and_then(|ipv4| ipv4.get("dhcp-client-id"))
+        .and_then(value_string)
+        .filter(|value| !value.is_empty()))
+}
+
+// * None clears ipv4.dhcp-client-id so NetworkManager falls back to its global default.
+pub async fn set_dhcp_client_id(name: &str, client_id: Option<&str>) -> Result<()> {
+    let client_id = client_id.map(str::trim).filter(|value| !value.is_empty());
+    if let Some(value) = client_id {
+        validate_dhcp_client_id(value)?;
+    }
+
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_id(name)
+        .await?
+        .ok_or_else(|| anyhow!("Connection {} not found", name))?;
+    let mut settings = clone_settings_map(&profile.settings)?;
+    let ipv4 = settings.entry("ipv4".to_string()).or_default();
+    match client_id {
+        Some(value) => {
+            ipv4.insert("dhcp-client-id".to_string(), owned_string(value));
+        }
+        None => {
+            ipv4.remove("dhcp-client-id");
+        }
+    }
+    client
+        .update_connection_settings(&profile.path, &settings)
+        .await
+}
+
+async fn connection_settings_by_id(name: &str) -> Result<SettingsMap> {
+    dbus_client()
+        .await?
+        .find_connection_by_id(name)
+        .await?
+        .map(|profile| profile.settings)
+        .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", name)).into())
+}
+
+// * Read-modify-write of a whole profile; `edit` sees a copy of the current settings.
+async fn edit_connection_by_id(
+    name: &str,
+    edit: impl FnOnce(&mut SettingsMap) -> Result<()>,
+) -> Result<()> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_id(name)
+        .await?
+        .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", name)))?;
+    let mut settings = clone_settings_map(&profile.settings)?;
+    edit(&mut settings)?;
+    client
+        .update_connection_settings(&profile.path, &settings)
+        .await
+}
+
+pub async fn get_metered(name: &str) -> Result<bool> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("connection")
+        .and_then(|connection| connection.get("metered"))
+        .and_then(|value| i32::try_from(value).ok())
+        == Some(NM_METERED_YES))
+}
+
+// * Off goes back to "unknown" so NM can still guess, e.g. from an Android hotspot's DHCP hint.
+pub async fn set_metered(name: &str, metered: bool) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let value = if metered {
+            NM_METERED_YES
+        } else {
+            NM_METERED_UNKNOWN
+        };
+        settings
+            .entry("connection".to_string())
+            .or_default()
+            .insert("metered".to_string(), value.into());
+        Ok(())
+    })
+    .await
+}
+
+// * Zones firewalld ships that fit a client connection, most open first.
+pub const FIREWALL_ZONES: [&str; 4] = ["trusted", "home", "work", "public"];
+const FIREWALLD_SERVICE: &str = "org.fedoraproject.FirewallD1";
+
+// * None means the profile leaves the choice to firewalld's default zone.
+pub async fn get_connection_zone(name: &str) -> Result<Option<String>> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("connection")
+        .and_then(|connection| connection.get("zone"))
+        .and_then(value_string)
+        .filter(|zone| !zone.is_empty()))
+}
+
+pub async fn set_connection_zone(name: &str, zone: Option<&str>) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let connection = settings.entry("connection".to_string()).or_default();
+        match zone {
+            Some(zone) => {
+                connection.insert("zone".to_string(), owned_string(zone));
+            }
+            None => {
+                connection.remove("zone");
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+// * connection.zone is only acted on when firewalld is on the system bus.
+pub async fn firewalld_running() -> bool {
+    let Ok(conn) = zbus::Connection::system().await else {
+        return false;
+    };
+    let Ok(proxy) = zbus::fdo::DBusProxy::new(&conn).await else {
+        return false;
+    };
+    let Ok(name) = zbus::names::BusName::try_from(FIREWALLD_SERVICE) else {
+        return false;
+    };
+    proxy.name_has_owner(name).await.unwrap_or(false)
+}
+
+// * Maps to connection.dns-over-tls; only systemd-resolved honours it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DnsOverTls {
+    #[default]
+    Default,
+    Off,
+    Opportunistic,
+    Required,
+}
+
+impl DnsOverTls {
+    fn from_nm(value: i32) -> Self {
+        match value {
+            0 => Self::Off,
+            1 => Self::Opportunistic,
+            2 => Self::Required,
+            _ => Self::Default,
+        }
+    }
+
+    fn as_nm(self) -> i32 {
+        match self {
+            Self::Default => -1,
+            Self::Off => 0,
+            Self::Opportunistic => 1,
+            Self::Required => 2,
+        }
+    }
+}
+
+pub async fn get_dns_over_tls(name: &str) -> Result<DnsOverTls> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("connection")
+        .and_then(|connection| connection.get("dns-over-tls"))
+        .and_then(|value| i32::try_from(value).ok())
+        .map(DnsOverTls::from_nm)
+        .unwrap_or_default())
+}
+
+pub async fn set_dns_over_tls(name: &str, mode: DnsOverTls) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        settings
+            .entry("connection".to_string())
+            .or_default()
+            .insert("dns-over-tls".to_string(), mode.as_nm().into());
+        Ok(())
+    })
+    .await
+}
+
+pub async fn get_wake_on_lan(name: &str) -> Result<bool> {
+    let settings = connection_settings_by_id(name).await?;
+    let flags = settings
+        .get("802-3-ethernet")
+        .and_then(|ethernet| ethernet.get("wake-on-lan"))
+        .and_then(value_u32)
+        .unwrap_or(NM_WAKE_ON_LAN_DEFAULT);
+    Ok(flags & NM_WAKE_ON_LAN_MAGIC != 0)
+}
+
+// * Only the magic-packet bit is toggled; other wake sources the profile already has are kept.
+pub async fn set_wake_on_lan(name: &str, enabled: bool) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let ethernet = settings.entry("802-3-ethernet".to_string()).or_default();
+        let current = ethernet
+            .get("wake-on-lan")
+            .and_then(value_u32)
+            .unwrap_or(NM_WAKE_ON_LAN_DEFAULT);
+        ethernet.insert(
+            "wake-on-lan".to_string(),
+            wake_on_lan_flags(current, enabled).into(),
+        );
+        Ok(())
+    })
+    .await
+}
+
+// ? DEFAULT and IGNORE can't be combined with explicit wake sources
+fn wake_on_lan_flags(current: u32, magic: bool) -> u32 {
+    let explicit = current & !(NM_WAKE_ON_LAN_DEFAULT | NM_WAKE_ON_LAN_IGNORE);
+    if magic {
+        explicit | NM_WAKE_ON_LAN_MAGIC
+    } else {
+        explicit & !NM_WAKE_ON_LAN_MAGIC
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WifiBand {
+    #[default]
+    Any,
+    Band24,
+    Band5,
+}
+
+impl WifiBand {
+    fn from_nm(band: &str) -> Self {
+        match band {
+            "bg" => Self::Band24,
+            "a" => Self::Band5,
+            _ => Self::Any,
+        }
+    }
+
+    fn as_nm(self) -> Option<&'static str> {
+        match self {
+            Self::Any => None,
+            Self::Band24 => Some("bg"),
+            Self::Band5 => Some("a"),
+        }
+    }
+}
+
+pub async fn get_wifi_band(name: &str) -> Result<WifiBand> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("802-11-wireless")
+        .and_then(|wifi| wifi.get("band"))
+        .and_then(value_string)
+        .map(|band| WifiBand::from_nm(&band))
+        .unwrap_or_default())
+}
+
+pub async fn set_wifi_band(name: &str, band: WifiBand) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let wifi = settings.entry("802-11-wireless".to_string()).or_default();
+        // ! A pinned channel must belong to the band, so drop it rather than fail activation
+        wifi.remove("channel");
+        match band.as_nm() {
+            Some(value) => {
+                wifi.insert("band".to_string(), owned_string(value));
+            }
+            None => {
+                wifi.remove("band");
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+pub async fn get_wifi_bssid(name: &str) -> Result<Option<String>> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("802-11-wireless")
+        .and_then(|wifi| wifi.get("bssid"))
+        .and_then(|value| Vec::<u8>::try_from(value.try_clone().ok()?).ok())
+        .filter(|bytes| bytes.len() == 6)
+        .map(|bytes| format_mac_bytes(&bytes)))
+}
+
+// * Pins the profile to one access point; roaming to other BSSIDs of the SSID stops.
+pub async fn set_wifi_bssid(name: &str, bssid: Option<&str>) -> Result<()> {
+    let bssid = bssid.map(mac_address_bytes).transpose()?;
+    edit_connection_by_id(name, |settings| {
+        let wifi = settings.entry("802-11-wireless".to_string()).or_default();
+        match bssid {
+            Some(bytes) => {
+                wifi.insert(
+                    "bssid".to_string(),
+                    OwnedValue::try_from(zvariant::Value::from(bytes))?,
+                );
+            }
+            None => {
+                wifi.remove("bssid");
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+// * NM accepts -999..=999; higher wins when several saved networks are in range.
+pub const AUTOCONNECT_PRIORITY_RANGE: std::ops::RangeInclusive<i32> = -999..=999;
+
+pub async fn get_autoconnect_priority(name: &str) -> Result<i32> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(settings
+        .get("connection")
+        .and_then(|connection| connection.get("autoconnect-priority"))
+        .and_then(|value| i32::try_from(value).ok())
+        .unwrap_or(0))
+}
+
+pub async fn set_autoconnect_priority(name: &str, priority: i32) -> Result<()> {
+    if !AUTOCONNECT_PRIORITY_RANGE.contains(&priority) {
+        return Err(anyhow!(
+            "Priority must be between {} and {}",
+            AUTOCONNECT_PRIORITY_RANGE.start(),
+            AUTOCONNECT_PRIORITY_RANGE.end()
+        ));
+    }
+    edit_connection_by_id(name, |settings| {
+        settings
+            .entry("connection".to_string())
+            .or_default()
+            .insert("autoconnect-priority".to_string(), priority.into());
+        Ok(())
+    })
+    .await
+}
+
+// * Secrets aren't part of GetSettings, so the returned config never carries passwords.
+pub async fn get_wired_8021x(name: &str) -> Result<Option<EapConfig>> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(eap_config_from_settings(&settings))
+}
+
+// * None drops the 802-1x section; NM only keeps secrets that are sent again, so passwords are required.
+pub async fn set_wired_8021x(name: &str, config: Option<&EapConfig>) -> Result<()> {
+    if let Some(config) = config {
+        validate_eap_config(config)?;
+    }
+    edit_connection_by_id(name, |settings| {
+        match config {
+            Some(config) => {
+                settings.insert("802-1x".to_string(), eap_8021x_section(config)?);
+            }
+            None => {
+                settings.remove("802-1x");
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+// * Works for Wi-Fi and Ethernet profiles; the section follows the profile's connection type.
+pub async fn get_mac_policy(name: &str) -> Result<MacAddressPolicy> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(mac_policy_from_settings(
+        &settings,
+        hardware_section(&settings)?,
+    ))
+}
+
+pub async fn set_mac_policy(name: &str, policy: &MacAddressPolicy) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let section = hardware_section(settings)?;
+        apply_mac_policy(settings, section, policy)
+    })
+    .await
+}
+
+// * 576 is the smallest MTU IPv4 hosts must accept; 9000 covers jumbo frames. 0 lets the driver pick.
+pub const MTU_RANGE: std::ops::RangeInclusive<u32> = 576..=9000;
+
+pub async fn get_mtu(name: &str) -> Result<u32> {
+    let settings = connection_settings_by_id(name).await?;
+    let section = hardware_section(&settings)?;
+    Ok(settings
+        .get(section)
+        .and_then(|values| values.get("mtu"))
+        .and_then(value_u32)
+        .unwrap_or(0))
+}
+
+pub async fn set_mtu(name: &str, mtu: u32) -> Result<()> {
+    if mtu != 0 && !MTU_RANGE.contains(&mtu) {
+        return Err(anyhow!(
+            "MTU must be between {} and {}",
+            MTU_RANGE.start(),
+            MTU_RANGE.end()
+        ));
+    }
+    edit_connection_by_id(name, |settings| {
+        let section = hardware_section(settings)?;
+        settings
+            .entry(section.to_string())
+            .or_default()
+            .insert("mtu".to_string(), mtu.into());
+        Ok(())
+    })
+    .await
+}
+
+fn hardware_section(settings: &SettingsMap) -> Result<&'static str> {
+    let conn_type = settings
+        .get("connection")
+        .and_then(|connection| connection.get("type"))
+        .and_then(value_string)
+        .unwrap_or_default();
+    match conn_type.as_str() {
+        "802-11-wireless" => Ok("802-11-wireless"),
+        "802-3-ethernet" => Ok("802-3-ethernet"),
+        other => Err(anyhow!("MAC address can't be set on {} connections", other)),
+    }
+}
+
+// * D-Bus exposes the string form as assigned-mac-address; cloned-mac-address is the raw-bytes form.
+fn mac_policy_from_settings(settings: &SettingsMap, section: &str) -> MacAddressPolicy {
+    let Some(values) = settings.get(section) else {
+        return MacAddressPolicy::Default;
+    };
+    if let Some(assigned) = values.get("assigned-mac-address").and_then(value_string) {
+        return MacAddressPolicy::from_nm(&assigned);
+    }
+    if let Some(bytes) = values
+        .get("cloned-mac-address")
+        .and_then(|value| Vec::<u8>::try_from(value.try_clone().ok()?).ok())
+        .filter(|bytes| bytes.len() == 6)
+    {
+        return MacAddressPolicy::Fixed(format_mac_bytes(&bytes));
+    }
+    // ? Older profiles only carry the deprecated wifi.mac-address-randomization (1 = never, 2 = always)
+    match values.get("mac-address-randomization").and_then(value_u32) {
+        Some(1) => MacAddressPolicy::Permanent,
+        Some(2) => MacAddressPolicy::Random,
+        _ => MacAddressPolicy::Default,
+    }
+}
+
+fn apply_mac_policy(
+    settings: &mut SettingsMap,
+    section: &str,
+    policy: &MacAddressPolicy,
+) -> Result<()> {
+    if let MacAddressPolicy::Fixed(mac) = policy {
+        validate_mac_address(mac)?;
+    }
+    let values = settings.entry(section.to_string()).or_default();
+    values.remove("cloned-mac-address");
+    values.remove("mac-address-randomization");
+    match policy.as_nm() {
+        Some(value) => {
+            values.insert("assigned-mac-address".to_string(), owned_string(value));
+        }
+        None => {
+            values.remove("assigned-mac-address");
+        }
+    }
+    Ok(())
+}
+
+fn format_mac_bytes(bytes: &[u8]) -> String {
+    let octets: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    octets.join(":")
+}
+
+fn mac_address_bytes(mac: &str) -> Result<Vec<u8>> {
+    validate_mac_address(mac)?;
+    mac.split(':')
+        .map(|octet| u8::from_str_radix(octet, 16).map_err(|e| anyhow!(e)))
+        .collect()
+}
+
+pub fn validate_mac_address(mac: &str) -> Result<()> {
+    let octets: Vec<&str> = mac.split(':').collect();
+    let valid = octets.len() == 6
+        && octets
+            .iter()
+            .all(|octet| octet.len() == 2 && octet.chars().all(|c| c.is_ascii_hexdigit()));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid MAC address: {}", mac))
+    }
+}
+
+pub async fn get_ipv4_config(name: &str) -> Result<Ipv4Config> {
+    Ok(ipv4_config_from_settings(
+        &connection_settings_by_id(name).await?,
+    ))
+}
+
+// * Only the first static address is managed here; extra addresses on the profile are dropped on save.
+pub async fn set_ipv4_config(name: &str, config: &Ipv4Config) -> Result<()> {
+    validate_ipv4_config(config)?;
+
+    edit_connection_by_id(name, |settings| {
+        let ipv4 = settings.entry("ipv4".to_string()).or_default();
+        ipv4.insert("method".to_string(), owned_string(config.method.as_nm()));
+        // ? NM still returns the deprecated "addresses" array; leaving it would fight with address-data
+        ipv4.remove("addresses");
+        ipv4.remove("address-data");
+        ipv4.remove("gateway");
+        if config.method == Ipv4Method::Manual {
+            if let Some(address) = config.address {
+                let mut entry = HashMap::new();
+                entry.insert("address".to_string(), owned_string(&address.to_string()));
+                entry.insert("prefix".to_string(), config.prefix.into());
+                ipv4.insert("address-data".to_string(), owned_dict_array(vec![entry])?);
+            }
+            if let Some(gateway) = config.gateway {
+                ipv4.insert("gateway".to_string(), owned_string(&gateway.to_string()));
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+// * Parses the editor fields; empty address/gateway text means "not set".
+pub fn parse_ipv4_config(
+    method: Ipv4Method,
+    address: &str,
+    prefix: u32,
+    gateway: &str,
+) -> Result<Ipv4Config> {
+    let parse = |label: &str, raw: &str| -> Result<Option<std::net::Ipv4Addr>> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        raw.parse()
+            .map(Some)
+            .map_err(|_| anyhow!("Invalid {}: {}", label, raw))
+    };
+
+    let config = Ipv4Config {
+        method,
+        address: parse("IPv4 address", address)?,
+        prefix,
+        gateway: parse("gateway", gateway)?,
+    };
+    validate_ipv4_config(&config)?;
+    Ok(config)
+}
+
+fn validate_ipv4_config(config: &Ipv4Config) -> Result<()> {
+    if config.method != Ipv4Method::Manual {
+        return Ok(());
+    }
+    let Some(address) = config.address else {
+        return Err(anyhow!("A static IPv4 address is required for manual mode"));
+    };
+    if !(1..=32).contains(&config.prefix) {
+        return Err(anyhow!("Prefix length must be between 1 and 32"));
+    }
+    if let Some(gateway) = config.gateway {
+        let mask = u32::MAX << (32 - config.prefix);
+        if gateway == address {
+            return Err(anyhow!("Gateway cannot be the same as the address"));
+        }
+        if u32::from(gateway) & mask != u32::from(address) & mask {
+            return Err(anyhow!(
+                "Gateway {} is outside {}/{}",
+                gateway,
+                address,
+                config.prefix
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn ipv4_config_from_settings(settings: &SettingsMap) -> Ipv4Config {
+    let Some(ipv4) = settings.get("ipv4") else {
+        return Ipv4Config::default();
+    };
+    let first_address = ipv4
+        .get("address-data")
+        .and_then(|value| {
+            Vec::<HashMap<String, OwnedValue>>::try_from(value.try_clone().ok()?).ok()
+        })
+        .and_then(|data| data.into_iter().next());
+
+    Ipv4Config {
+        method: Ipv4Method::from_nm(
+            &ipv4
+                .get("method")
+                .and_then(value_string)
+                .unwrap_or_default(),
+        ),
+        address: first_address
+            .as_ref()
+            .and_then(|entry| entry.get("address"))
+            .and_then(value_string)
+            .and_then(|raw| raw.parse().ok()),
+        prefix: first_address
+            .as_ref()
+            .and_then(|entry| entry.get("prefix"))
+            .and_then(value_u32)
+            .unwrap_or(24),
+        gateway: ipv4
+            .get("gateway")
+            .and_then(value_string)
+            .and_then(|raw| raw.parse().ok()),
+    }
+}
+
+pub async fn list_static_routes(name: &str) -> Result<Vec<StaticRoute>> {
+    let settings = connection_settings_by_id(name).await?;
+    Ok(["ipv4", "ipv6"]
+        .into_iter()
+        .flat_map(|section| routes_from_settings(&settings, section))
+        .collect())
+}
+
+pub async fn add_static_route(name: &str, route: &StaticRoute) -> Result<()> {
+    update_static_routes(name, route.section(), |routes| {
+        if routes.iter().any(|existing| existing.same_target(route)) {
+            return Err(anyhow!("Route {} already exists", route.label()));
+        }
+        routes.push(route.clone());
+        Ok(())
+    })
+    .await
+}
 
-    client
-        .update_connection_settings(&profile.path, &settings)
-        .await?;
+pub async fn delete_static_route(name: &str, route: &StaticRoute) -> Result<()> {
+    update_static_routes(name, route.section(), |routes| {
+        let before = routes.len();
+        routes.retain(|existing| !existing.same_target(route));
+        if routes.len() == before {
+            return Err(NmError::NotFound(format!("Route {} not found", route.label())).into());
+        }
+        Ok(())
+    })
+    .await
+}
 
-    // * Reconnect using VPN-safe activation (device path = "/")
-    if was_active {
-        log::info!("Reconnecting VPN {} after settings update", uuid);
-        client.activate_vpn_connection_path(&profile.path).await?;
-    }
+async fn update_static_routes(
+    name: &str,
+    section: &str,
+    edit: impl FnOnce(&mut Vec<StaticRoute>) -> Result<()>,
+) -> Result<()> {
+    edit_connection_by_id(name, |settings| {
+        let mut routes = routes_from_settings(settings, section);
+        edit(&mut routes)?;
+
+        let route_data = routes
+            .iter()
+            .map(|route| {
+                let mut entry = HashMap::new();
+                entry.insert(
+                    "dest".to_string(),
+                    owned_string(&route.destination.to_string()),
+                );
+                entry.insert("prefix".to_string(), route.prefix.into());
+                if let Some(hop) = route.next_hop {
+                    entry.insert("next-hop".to_string(), owned_string(&hop.to_string()));
+                }
+                if let Some(metric) = route.metric {
+                    entry.insert("metric".to_string(), metric.into());
+                }
+                entry
+            })
+            .collect();
 
-    Ok(())
+        let ip = settings.entry(section.to_string()).or_default();
+        // ? Same as addresses: the deprecated "routes" array would otherwise be merged back in
+        ip.remove("routes");
+        ip.insert("route-data".to_string(), owned_dict_array(route_data)?);
+        Ok(())
+    })
+    .await
 }
 
-pub async fn create_wireguard_connection(config: &WireGuardConnectionConfig) -> Result<String> {
-    import_wireguard_config(None, config, false).await
-}
+// * Destination in CIDR form ("10.8.0.0/16"); a bare address is a host route.
+pub fn parse_static_route(destination: &str, next_hop: &str, metric: &str) -> Result<StaticRoute> {
+    let destination = destination.trim();
+    let (address, prefix) = match destination.split_once('/') {
+        Some((address, prefix)) => (address, Some(prefix)),
+        None => (destination, None),
+    };
+    let destination_ip: std::net::IpAddr = address
+        .parse()
+        .map_err(|_| anyhow!("Invalid route destination: {}", destination))?;
+    let max_prefix = if destination_ip.is_ipv4() { 32 } else { 128 };
+    let prefix = match prefix {
+        Some(raw) => raw
+            .parse::<u32>()
+            .ok()
+            .filter(|prefix| *prefix <= max_prefix)
+            .ok_or_else(|| anyhow!("Invalid prefix length: {}", raw))?,
+        None => max_prefix,
+    };
 
-pub async fn update_wireguard_connection(
-    uuid: &str,
-    config: &WireGuardConnectionConfig,
-) -> Result<String> {
-    import_wireguard_config(Some(uuid), config, true).await
+    let next_hop = match next_hop.trim() {
+        "" => None,
+        raw => {
+            let hop: std::net::IpAddr = raw
+                .parse()
+                .map_err(|_| anyhow!("Invalid gateway: {}", raw))?;
+            if hop.is_ipv4() != destination_ip.is_ipv4() {
+                return Err(anyhow!(
+                    "Gateway {} is not in the same address family as {}",
+                    hop,
+                    destination
+                ));
+            }
+            Some(hop)
+        }
+    };
+
+    let metric = match metric.trim() {
+        "" => None,
+        raw => Some(
+            raw.parse::<u32>()
+                .map_err(|_| anyhow!("Invalid metric: {}", raw))?,
+        ),
+    };
+
+    Ok(StaticRoute {
+        destination: destination_ip,
+        prefix,
+        next_hop,
+        metric,
+    })
 }
 
-pub async fn import_vpn_connection(path: &Path) -> Result<()> {
-    let kind = detect_vpn_file_type(path).await?;
-    let vpn_type = match kind {
-        VpnKind::WireGuard => "wireguard",
-        VpnKind::OpenVpn => "openvpn",
+fn routes_from_settings(settings: &SettingsMap, section: &str) -> Vec<StaticRoute> {
+    let Some(data) = settings
+        .get(section)
+        .and_then(|ip| ip.get("route-data"))
+        .and_then(|value| {
+            Vec::<HashMap<String, OwnedValue>>::try_from(value.try_clone().ok()?).ok()
+        })
+    else {
+        return Vec::new();
     };
-    run_nmcli_command(&[
-        "connection",
-        "import",
-        "type",
-        vpn_type,
-        "file",
-        path.to_string_lossy().as_ref(),
-    ])
-    .await
+
+    data.iter()
+        .filter_map(|entry| {
+            let destination = entry.get("dest").and_then(value_string)?.parse().ok()?;
+            Some(StaticRoute {
+                destination,
+                prefix: entry.get("prefix").and_then(value_u32)?,
+                next_hop: entry
+                    .get("next-hop")
+                    .and_then(value_string)
+                    .and_then(|raw| raw.parse().ok()),
+                metric: entry.get("metric").and_then(value_u32),
+            })
+        })
+        .collect()
 }
 
-pub async fn rename_connection_uuid(uuid: &str, name: &str) -> Result<()> {
-    let client = dbus_client().await?;
-    let profile = client
-        .find_connection_by_uuid(uuid)
-        .await?
-        .ok_or_else(|| anyhow!("Connection {} not found", uuid))?;
-    let mut settings = clone_settings_map(&profile.settings)?;
-    let connection = settings.entry("connection".to_string()).or_default();
-    connection.insert("id".to_string(), owned_string(name));
-    client
-        .update_connection_settings(&profile.path, &settings)
-        .await
+// * Accepts NM keywords (mac, perm-mac, duid, ...), colon-separated hex, or a plain text ID.
+pub fn validate_dhcp_client_id(value: &str) -> Result<()> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(anyhow!("DHCP client ID cannot be empty"));
+    }
+    if value.len() > 255 {
+        return Err(anyhow!("DHCP client ID must be at most 255 characters"));
+    }
+    if !value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        return Err(anyhow!("DHCP client ID contains invalid characters"));
+    }
+
+    let looks_hex = value.contains(':') && value.chars().all(|c| c.is_ascii_hexdigit() || c == ':');
+    if looks_hex
+        && !value
+            .split(':')
+            .all(|octet| !octet.is_empty() && octet.len() <= 2)
+    {
+        return Err(anyhow!("Invalid hex DHCP client ID: {}", value));
+    }
+
+    Ok(())
 }
 
 pub async fn get_active_connection_name() -> Result<Option<String>> {
@@ -832,13 +3113,32 @@ pub async fn get_primary_connected_device() -> Result<Option<String>> {
 
 pub async fn get_internet_connectivity() -> Result<InternetConnectivity> {
     let state = dbus_client().await?.get_connectivity_state().await?;
-    Ok(match state {
+    Ok(connectivity_from_state(state))
+}
+
+pub async fn check_connectivity() -> Result<InternetConnectivity> {
+    let state = dbus_client().await?.check_connectivity().await?;
+    Ok(connectivity_from_state(state))
+}
+
+// * The property stays "unknown" until NM's first check; ask for one instead of showing nothing.
+pub async fn current_connectivity() -> InternetConnectivity {
+    match get_internet_connectivity().await {
+        Ok(InternetConnectivity::Unknown) | Err(_) => check_connectivity()
+            .await
+            .unwrap_or(InternetConnectivity::Unknown),
+        Ok(connectivity) => connectivity,
+    }
+}
+
+fn connectivity_from_state(state: u32) -> InternetConnectivity {
+    match state {
         NM_CONNECTIVITY_NONE => InternetConnectivity::NoInternet,
         NM_CONNECTIVITY_PORTAL => InternetConnectivity::Portal,
         NM_CONNECTIVITY_LIMITED => InternetConnectivity::Limited,
         NM_CONNECTIVITY_FULL => InternetConnectivity::Full,
         _ => InternetConnectivity::Unknown,
-    })
+    }
 }
 
 pub async fn set_custom_ipv4_dns_for_connection(
@@ -856,21 +3156,57 @@ pub async fn reapply_connection(connection: &str) -> Result<()> {
     dbus_client().await?.reapply_connection(connection).await
 }
 
+// * Snapshot taken before a risky change; dropping it without confirming leaves NM to roll back.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: OwnedObjectPath,
+}
+
+pub async fn create_checkpoint(rollback_timeout: Duration) -> Result<Checkpoint> {
+    let secs = u32::try_from(rollback_timeout.as_secs()).unwrap_or(u32::MAX);
+    let path = dbus_client().await?.checkpoint_create(secs).await?;
+    Ok(Checkpoint { path })
+}
+
+pub async fn confirm_checkpoint(checkpoint: Checkpoint) -> Result<()> {
+    dbus_client()
+        .await?
+        .checkpoint_destroy(&checkpoint.path)
+        .await
+}
+
+pub async fn rollback_checkpoint(checkpoint: Checkpoint) -> Result<()> {
+    dbus_client()
+        .await?
+        .checkpoint_rollback(&checkpoint.path)
+        .await
+}
+
 pub async fn is_network_saved(ssid: &str) -> Result<bool> {
-    let client = dbus_client().await?;
-    let conn = client.find_connection_by_id(ssid).await?;
-    Ok(conn
-        .map(|c| c.conn_type == "802-11-wireless")
-        .unwrap_or(false))
+    backend().is_saved(ssid).await
 }
 
-pub async fn get_autoconnect_for_ssid(ssid: &str) -> Result<bool> {
+pub async fn save_wifi_connection(
+    ssid: &str,
+    psk: Option<&str>,
+    hidden: bool,
+    priority: Option<i32>,
+) -> Result<bool> {
     dbus_client()
         .await?
-        .get_connection_autoconnect_by_id(ssid)
+        .save_wifi_connection(ssid, psk, hidden, priority)
         .await
 }
 
+// * SSIDs with a saved Wi-Fi profile, whatever the profiles are named.
+pub async fn saved_wifi_ssids() -> Result<HashSet<String>> {
+    backend().saved_wifi_ssids().await
+}
+
+pub async fn get_autoconnect_for_ssid(ssid: &str) -> Result<bool> {
+    backend().autoconnect(ssid).await
+}
+
 pub async fn get_autoconnect_for_connection(name: &str) -> Result<bool> {
     dbus_client()
         .await?
@@ -879,10 +3215,7 @@ pub async fn get_autoconnect_for_connection(name: &str) -> Result<bool> {
 }
 
 pub async fn set_autoconnect_for_ssid(ssid: &str, enabled: bool) -> Result<()> {
-    dbus_client()
-        .await?
-        .set_connection_autoconnect_by_id(ssid, enabled)
-        .await
+    backend().set_autoconnect(ssid, enabled).await
 }
 
 pub async fn set_autoconnect_for_connection(name: &str, enabled: bool) -> Result<()> {
@@ -906,8 +3239,22 @@ pub async fn set_connection_zone_for_connection_uuid(uuid: &str, zone: &str) ->
         .await
 }
 
-pub async fn connect_open_network(ssid: &str) -> Result<ConnectStatus> {
-    connect_wifi_network(ssid, None, None, false).await
+pub async fn set_connection_zone_for_connection(name: &str, zone: &str) -> Result<()> {
+    dbus_client()
+        .await?
+        .set_connection_zone_by_id(name, zone)
+        .await
+}
+
+pub async fn connect_open_network(
+    ssid: &str,
+    security_type: Option<&str>,
+) -> Result<ConnectStatus> {
+    connect_with_timeout(
+        ssid,
+        backend().connect_wifi(ssid, None, security_type, false),
+    )
+    .await
 }
 
 pub async fn connect_secured_network(
@@ -915,7 +3262,11 @@ pub async fn connect_secured_network(
     password: &str,
     security_type: Option<&str>,
 ) -> Result<ConnectStatus> {
-    connect_wifi_network(ssid, Some(password), security_type, false).await
+    connect_with_timeout(
+        ssid,
+        backend().connect_wifi(ssid, Some(password), security_type, false),
+    )
+    .await
 }
 
 pub async fn connect_hidden_network(
@@ -923,24 +3274,82 @@ pub async fn connect_hidden_network(
     password: Option<&str>,
     security_type: Option<&str>,
 ) -> Result<ConnectStatus> {
-    connect_wifi_network(ssid, password, security_type, true).await
+    connect_with_timeout(
+        ssid,
+        backend().connect_wifi(ssid, password, security_type, true),
+    )
+    .await
+}
+
+// * Press the router's WPS button within about two minutes; NM then saves the PSK it hands out.
+pub async fn connect_wps_pbc(ssid: &str) -> Result<ConnectStatus> {
+    with_timeout(
+        WPS_TIMEOUT,
+        &format!("Waiting for WPS on {}", ssid),
+        async {
+            dbus_client()
+                .await?
+                .add_and_activate_wps_connection(ssid)
+                .await?;
+            Ok(ConnectStatus::Connected)
+        },
+    )
+    .await
+}
+
+pub async fn connect_wifi_enterprise(
+    ssid: &str,
+    config: &EapConfig,
+    hidden: bool,
+) -> Result<ConnectStatus> {
+    let settings = build_enterprise_wifi_settings(ssid, config, hidden)?;
+    connect_with_timeout(ssid, async {
+        dbus_client()
+            .await?
+            .add_and_activate_enterprise_connection(ssid, settings, hidden)
+            .await?;
+        Ok(ConnectStatus::Connected)
+    })
+    .await
+}
+
+pub fn validate_eap_config(config: &EapConfig) -> Result<()> {
+    if config.identity.trim().is_empty() {
+        return Err(anyhow!("Identity is required"));
+    }
+    if config.method.uses_password() {
+        if config.password.as_ref().is_none_or(|p| p.is_empty()) {
+            return Err(anyhow!("Password is required"));
+        }
+    } else if config.client_cert.is_none() || config.private_key.is_none() {
+        return Err(anyhow!("TLS needs a client certificate and a private key"));
+    }
+    Ok(())
 }
 
 pub async fn activate_saved_connection(ssid: &str) -> Result<ConnectStatus> {
-    dbus_client()
-        .await?
-        .activate_connection_by_id(ssid, None)
-        .await?;
-    Ok(ConnectStatus::Connected)
+    connect_with_timeout(ssid, backend().activate_saved(ssid)).await
 }
 
-pub fn is_network_not_found_error(message: &str) -> bool {
-    let msg = message.to_lowercase();
-    msg.contains("not found") || msg.contains("unknown connection")
+async fn connect_with_timeout(
+    ssid: &str,
+    future: impl Future<Output = Result<ConnectStatus>>,
+) -> Result<ConnectStatus> {
+    with_timeout(
+        operation_timeout(),
+        &format!("Connecting to {}", ssid),
+        future,
+    )
+    .await
 }
 
 pub async fn disconnect_network(ssid: &str) -> Result<()> {
-    dbus_client().await?.disconnect_connection_by_id(ssid).await
+    with_timeout(
+        operation_timeout(),
+        &format!("Disconnecting {}", ssid),
+        backend().disconnect(ssid),
+    )
+    .await
 }
 
 async fn map_saved_connections(profiles: Vec<DbusConnectionProfile>) -> Vec<SavedConnection> {
@@ -948,20 +3357,84 @@ async fn map_saved_connections(profiles: Vec<DbusConnectionProfile>) -> Vec<Save
         .into_iter()
         .filter(|c| c.conn_type == "802-11-wireless" && c.id != "Hotspot")
         .map(|c| SavedConnection {
+            security_type: saved_security_type(&c.settings),
             uuid: c.uuid,
             ssid: c.id,
+            last_used: c.timestamp,
+            autoconnect: c.autoconnect.unwrap_or(true),
+            interface_name: c.interface_name,
         })
         .collect()
 }
 
-#[allow(dead_code)]
+// * Same labels as scanned networks so saved-only rows read like the rest of the list.
+fn saved_security_type(settings: &SettingsMap) -> String {
+    let Some(security) = settings.get("802-11-wireless-security") else {
+        return "Open".to_string();
+    };
+    let key_mgmt = security.get("key-mgmt").and_then(value_string);
+    match key_mgmt.as_deref() {
+        Some("sae") => "WPA3",
+        Some("wpa-eap") | Some("wpa-eap-suite-b-192") => "WPA2 Enterprise",
+        Some("none") | Some("ieee8021x") => "WEP",
+        Some("owe") => "Enhanced Open",
+        _ => "WPA2",
+    }
+    .to_string()
+}
+
 pub async fn get_saved_connections() -> Result<Vec<SavedConnection>> {
+    with_timeout(
+        operation_timeout(),
+        "Listing saved networks",
+        backend().saved_connections(),
+    )
+    .await
+}
+
+// * Saved auto-connecting profiles in the order NetworkManager tries them.
+pub async fn get_autoconnect_preview() -> Result<Vec<AutoconnectCandidate>> {
     let profiles = dbus_client().await?.list_connections().await?;
-    Ok(map_saved_connections(profiles).await)
+    let candidates = profiles
+        .into_iter()
+        .filter(|p| p.autoconnect.unwrap_or(true))
+        .filter(|p| {
+            matches!(
+                p.conn_type.as_str(),
+                "802-11-wireless" | "wifi" | "802-3-ethernet" | "ethernet"
+            )
+        })
+        .map(|p| AutoconnectCandidate {
+            name: p.id,
+            uuid: p.uuid,
+            conn_type: p.conn_type,
+            priority: p.autoconnect_priority,
+            last_connected: p.timestamp,
+        })
+        .collect();
+    Ok(order_autoconnect_candidates(candidates))
+}
+
+// * NetworkManager prefers higher autoconnect-priority, then the most recently used profile.
+pub fn order_autoconnect_candidates(
+    mut candidates: Vec<AutoconnectCandidate>,
+) -> Vec<AutoconnectCandidate> {
+    candidates.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| b.last_connected.cmp(&a.last_connected))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    candidates
 }
 
 pub async fn delete_connection_by_ssid(ssid: &str) -> Result<()> {
-    dbus_client().await?.delete_connection_by_id(ssid).await
+    with_timeout(
+        operation_timeout(),
+        &format!("Forgetting {}", ssid),
+        backend().forget(ssid),
+    )
+    .await
 }
 
 #[allow(dead_code)]
@@ -969,6 +3442,76 @@ pub async fn delete_connection(uuid: &str) -> Result<()> {
     dbus_client().await?.delete_connection_by_uuid(uuid).await
 }
 
+pub const VLAN_ID_RANGE: std::ops::RangeInclusive<u32> = 1..=4094;
+// * IFNAMSIZ minus the trailing NUL
+const MAX_INTERFACE_NAME_LEN: usize = 15;
+
+// * Creates a tagged sub-interface on `parent` (e.g. eth0.10); returns the new connection's name.
+pub async fn create_vlan(parent: &str, id: u32) -> Result<String> {
+    let settings = build_vlan_settings(parent, id)?;
+    let name = settings
+        .get("connection")
+        .and_then(|connection| connection.get("id"))
+        .and_then(value_string)
+        .unwrap_or_default();
+    dbus_client().await?.add_connection(&settings).await?;
+    Ok(name)
+}
+
+fn vlan_interface_name(parent: &str, id: u32) -> String {
+    let name = format!("{}.{}", parent, id);
+    if name.len() <= MAX_INTERFACE_NAME_LEN {
+        name
+    } else {
+        format!("vlan{}", id)
+    }
+}
+
+fn build_vlan_settings(parent: &str, id: u32) -> Result<SettingsMap> {
+    let parent = parent.trim();
+    if parent.is_empty() {
+        return Err(anyhow!("Choose a parent interface for the VLAN"));
+    }
+    if !VLAN_ID_RANGE.contains(&id) {
+        return Err(anyhow!(
+            "VLAN ID must be between {} and {}",
+            VLAN_ID_RANGE.start(),
+            VLAN_ID_RANGE.end()
+        ));
+    }
+
+    let interface_name = vlan_interface_name(parent, id);
+    let mut settings = SettingsMap::new();
+
+    let mut connection = HashMap::new();
+    connection.insert(
+        "id".to_string(),
+        owned_string(&format!("VLAN {} on {}", id, parent)),
+    );
+    connection.insert("type".to_string(), owned_string("vlan"));
+    connection.insert(
+        "uuid".to_string(),
+        owned_string(&Uuid::new_v4().to_string()),
+    );
+    connection.insert("interface-name".to_string(), owned_string(&interface_name));
+    settings.insert("connection".to_string(), connection);
+
+    let mut vlan = HashMap::new();
+    vlan.insert("parent".to_string(), owned_string(parent));
+    vlan.insert("id".to_string(), id.into());
+    settings.insert("vlan".to_string(), vlan);
+
+    let mut ipv4 = HashMap::new();
+    ipv4.insert("method".to_string(), owned_string("auto"));
+    settings.insert("ipv4".to_string(), ipv4);
+
+    let mut ipv6 = HashMap::new();
+    ipv6.insert("method".to_string(), owned_string("auto"));
+    settings.insert("ipv6".to_string(), ipv6);
+
+    Ok(settings)
+}
+
 pub fn is_vpn_plugin_missing_error(message: &str) -> bool {
     let lower = message.to_lowercase();
     lower.contains("vpn plugin")
@@ -1007,7 +3550,7 @@ fn active_connection_state_label(state: u32) -> &'static str {
     }
 }
 
-fn band_from_frequency(freq: u32) -> &'static str {
+pub(crate) fn band_from_frequency(freq: u32) -> &'static str {
     if (2400..=2500).contains(&freq) {
         "2.4 GHz"
     } else if (4900..=5900).contains(&freq) {
@@ -1019,7 +3562,7 @@ fn band_from_frequency(freq: u32) -> &'static str {
     }
 }
 
-fn channel_from_frequency(freq: u32) -> u32 {
+pub(crate) fn channel_from_frequency(freq: u32) -> u32 {
     if (2412..=2472).contains(&freq) {
         ((freq - 2407) / 5).max(1)
     } else if freq == 2484 {
@@ -1045,19 +3588,49 @@ fn normalize_frequency_mhz(freq: u32) -> u32 {
     }
 }
 
+// * Whether joining needs a secret; Enhanced Open is encrypted but joins like an open network.
 fn is_ap_secured(ap: &DbusAccessPoint) -> bool {
+    if is_owe_only(ap.rsn_flags) && ap.wpa_flags == 0 {
+        return false;
+    }
     ap.flags != 0 || ap.wpa_flags != 0 || ap.rsn_flags != 0
 }
 
+fn is_owe_only(rsn_flags: u32) -> bool {
+    let owe = NM_802_11_AP_SEC_KEY_MGMT_OWE | NM_802_11_AP_SEC_KEY_MGMT_OWE_TM;
+    let secret = NM_802_11_AP_SEC_KEY_MGMT_PSK
+        | NM_802_11_AP_SEC_KEY_MGMT_SAE
+        | NM_802_11_AP_SEC_KEY_MGMT_802_1X;
+    rsn_flags & owe != 0 && rsn_flags & secret == 0
+}
+
 fn ap_security_type(ap: &DbusAccessPoint) -> String {
+    // * 802.1X key management without a PSK alternative means the AP only takes EAP logins
+    let enterprise_only = |flags: u32| {
+        flags & NM_802_11_AP_SEC_KEY_MGMT_802_1X != 0 && flags & NM_802_11_AP_SEC_KEY_MGMT_PSK == 0
+    };
+
     if ap.rsn_flags != 0 {
-        if ap.rsn_flags & 0x0000_0400 != 0 {
+        if is_owe_only(ap.rsn_flags) && ap.wpa_flags == 0 {
+            return "Enhanced Open".to_string();
+        }
+        if ap.rsn_flags & NM_802_11_AP_SEC_KEY_MGMT_SAE != 0 {
+            // * Transition mode advertises PSK next to SAE so WPA2-only clients can still join
+            if ap.rsn_flags & NM_802_11_AP_SEC_KEY_MGMT_PSK != 0 {
+                return "WPA2/WPA3".to_string();
+            }
             return "WPA3".to_string();
         }
+        if enterprise_only(ap.rsn_flags) {
+            return "WPA2 Enterprise".to_string();
+        }
         return "WPA2".to_string();
     }
 
     if ap.wpa_flags != 0 {
+        if enterprise_only(ap.wpa_flags) {
+            return "WPA Enterprise".to_string();
+        }
         return "WPA".to_string();
     }
 
@@ -1068,13 +3641,21 @@ fn ap_security_type(ap: &DbusAccessPoint) -> String {
     "Open".to_string()
 }
 
+pub fn is_enterprise_security(security_type: &str) -> bool {
+    security_type.to_lowercase().contains("enterprise")
+}
+
 fn key_mgmt_from_security_type(security_type: Option<&str>) -> &'static str {
     let Some(sec) = security_type else {
         return "wpa-psk";
     };
 
     let sec = sec.to_lowercase();
-    if sec.contains("wpa3") {
+    if sec.contains("wpa2/wpa3") {
+        // * NM adds SAE on its own for transition networks when the driver supports it;
+        // * pinning "sae" would lock out adapters that only speak WPA2.
+        "wpa-psk"
+    } else if sec.contains("wpa3") {
         "sae"
     } else if sec.contains("wep") {
         "none"
@@ -1083,13 +3664,27 @@ fn key_mgmt_from_security_type(security_type: Option<&str>) -> &'static str {
     }
 }
 
+// * Key management for joins without a password: OWE where NM can do it, plain open otherwise.
+fn open_key_mgmt(security_type: Option<&str>) -> Option<&'static str> {
+    let enhanced_open = security_type.is_some_and(|sec| sec.eq_ignore_ascii_case("enhanced open"));
+    (enhanced_open && capabilities().supports(NmFeature::Owe)).then_some("owe")
+}
+
 async fn connect_wifi_network(
     ssid: &str,
     password: Option<&str>,
     security_type: Option<&str>,
     hidden: bool,
 ) -> Result<ConnectStatus> {
-    let key_mgmt = password.map(|_| key_mgmt_from_security_type(security_type));
+    let key_mgmt = match password {
+        Some(_) => Some(key_mgmt_from_security_type(security_type)),
+        None => open_key_mgmt(security_type),
+    };
+    if key_mgmt == Some("sae") {
+        if let Some(reason) = capabilities().unsupported_reason(NmFeature::Sae) {
+            return Err(anyhow!(reason));
+        }
+    }
 
     dbus_client()
         .await?
@@ -1099,7 +3694,7 @@ async fn connect_wifi_network(
     Ok(ConnectStatus::Connected)
 }
 
-fn compare_wifi_networks(a: &WifiNetwork, b: &WifiNetwork) -> CmpOrdering {
+pub(crate) fn compare_wifi_networks(a: &WifiNetwork, b: &WifiNetwork) -> CmpOrdering {
     match (a.connected, b.connected) {
         (true, false) => return CmpOrdering::Less,
         (false, true) => return CmpOrdering::Greater,
@@ -1175,11 +3770,15 @@ fn owned_string(value: &str) -> OwnedValue {
     OwnedValue::from(Str::from(value))
 }
 
-fn owned_string_map(map: HashMap<String, String>) -> Result<OwnedValue> {
+pub(crate) fn owned_string_map(map: HashMap<String, String>) -> Result<OwnedValue> {
     Ok(OwnedValue::from(map))
 }
 
-fn value_string(value: &OwnedValue) -> Option<String> {
+fn owned_dict_array(items: Vec<HashMap<String, OwnedValue>>) -> Result<OwnedValue> {
+    Ok(OwnedValue::try_from(zvariant::Value::from(items))?)
+}
+
+pub(crate) fn value_string(value: &OwnedValue) -> Option<String> {
     value
         .try_clone()
         .ok()
@@ -1195,6 +3794,10 @@ fn value_u32(value: &OwnedValue) -> Option<u32> {
     u32::try_from(value).ok()
 }
 
+fn value_bool(value: &OwnedValue) -> Option<bool> {
+    bool::try_from(value).ok()
+}
+
 fn value_string_list(value: &OwnedValue) -> Option<Vec<String>> {
     Vec::<String>::try_from(value.try_clone().ok()?).ok()
 }
@@ -1241,23 +3844,155 @@ fn collect_address_strings(settings: &SettingsMap) -> Vec<String> {
             }
         }
     }
-    out
+    out
+}
+
+fn collect_dns_strings(settings: &SettingsMap) -> Vec<String> {
+    let mut out = Vec::new();
+    for section_name in ["ipv4", "ipv6"] {
+        let Some(section) = settings.get(section_name) else {
+            continue;
+        };
+        let Some(value) = section.get("dns-data") else {
+            continue;
+        };
+        if let Some(list) = value_string_list(value) {
+            out.extend(list);
+        }
+    }
+    out
+}
+
+fn build_enterprise_wifi_settings(
+    ssid: &str,
+    config: &EapConfig,
+    hidden: bool,
+) -> Result<SettingsMap> {
+    validate_eap_config(config)?;
+    let mut settings = SettingsMap::new();
+
+    let mut connection = HashMap::new();
+    connection.insert("id".to_string(), owned_string(ssid));
+    connection.insert("type".to_string(), owned_string("802-11-wireless"));
+    connection.insert("autoconnect".to_string(), true.into());
+    settings.insert("connection".to_string(), connection);
+
+    let mut wifi = HashMap::new();
+    wifi.insert(
+        "ssid".to_string(),
+        OwnedValue::try_from(zvariant::Value::from(ssid.as_bytes().to_vec()))?,
+    );
+    wifi.insert("mode".to_string(), owned_string("infrastructure"));
+    if hidden {
+        wifi.insert("hidden".to_string(), true.into());
+    }
+    settings.insert("802-11-wireless".to_string(), wifi);
+
+    let mut security = HashMap::new();
+    security.insert("key-mgmt".to_string(), owned_string("wpa-eap"));
+    settings.insert("802-11-wireless-security".to_string(), security);
+
+    settings.insert("802-1x".to_string(), eap_8021x_section(config)?);
+    Ok(settings)
+}
+
+// * Shared by Wi-Fi and wired profiles; certificates are passed by path as NM's file:// blobs.
+fn eap_8021x_section(config: &EapConfig) -> Result<HashMap<String, OwnedValue>> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut section = HashMap::new();
+    section.insert(
+        "eap".to_string(),
+        OwnedValue::try_from(zvariant::Value::from(vec![config.method.as_nm()]))?,
+    );
+    section.insert("identity".to_string(), owned_string(config.identity.trim()));
+    if let Some(anonymous) = non_empty(&config.anonymous_identity) {
+        section.insert("anonymous-identity".to_string(), owned_string(&anonymous));
+    }
+    if let Some(domain) = non_empty(&config.domain_suffix_match) {
+        section.insert("domain-suffix-match".to_string(), owned_string(&domain));
+    }
+
+    match &config.ca_cert {
+        Some(path) => {
+            section.insert("ca-cert".to_string(), cert_path_value(path)?);
+        }
+        None => {
+            section.insert("system-ca-certs".to_string(), true.into());
+        }
+    }
+
+    if config.method.uses_password() {
+        section.insert(
+            "phase2-auth".to_string(),
+            owned_string(config.phase2_auth.as_nm()),
+        );
+        if let Some(password) = &config.password {
+            section.insert("password".to_string(), owned_string(password));
+        }
+    } else {
+        if let Some(path) = &config.client_cert {
+            section.insert("client-cert".to_string(), cert_path_value(path)?);
+        }
+        if let Some(path) = &config.private_key {
+            section.insert("private-key".to_string(), cert_path_value(path)?);
+        }
+        if let Some(password) = config
+            .private_key_password
+            .as_ref()
+            .filter(|p| !p.is_empty())
+        {
+            section.insert("private-key-password".to_string(), owned_string(password));
+        }
+    }
+    Ok(section)
 }
 
-fn collect_dns_strings(settings: &SettingsMap) -> Vec<String> {
-    let mut out = Vec::new();
-    for section_name in ["ipv4", "ipv6"] {
-        let Some(section) = settings.get(section_name) else {
-            continue;
-        };
-        let Some(value) = section.get("dns-data") else {
-            continue;
-        };
-        if let Some(list) = value_string_list(value) {
-            out.extend(list);
-        }
-    }
-    out
+fn eap_config_from_settings(settings: &SettingsMap) -> Option<EapConfig> {
+    let section = settings.get("802-1x")?;
+    let text = |key: &str| {
+        section
+            .get(key)
+            .and_then(value_string)
+            .filter(|value| !value.is_empty())
+    };
+    let cert_path = |key: &str| {
+        let value = text(key)?;
+        let path = value.strip_prefix("file://")?.trim_end_matches('\0');
+        Some(PathBuf::from(path))
+    };
+
+    let method = section
+        .get("eap")
+        .and_then(value_string_list)
+        .and_then(|methods| methods.iter().find_map(|method| EapMethod::from_nm(method)))
+        .unwrap_or_default();
+    Some(EapConfig {
+        method,
+        identity: text("identity").unwrap_or_default(),
+        anonymous_identity: text("anonymous-identity"),
+        phase2_auth: text("phase2-auth")
+            .map(|auth| Phase2Auth::from_nm(&auth))
+            .unwrap_or_default(),
+        ca_cert: cert_path("ca-cert"),
+        domain_suffix_match: text("domain-suffix-match"),
+        client_cert: cert_path("client-cert"),
+        private_key: cert_path("private-key"),
+        ..EapConfig::default()
+    })
+}
+
+// * NM's path scheme for certificate properties: "file://" + absolute path + NUL.
+fn cert_path_value(path: &Path) -> Result<OwnedValue> {
+    let mut bytes = format!("file://{}", path.display()).into_bytes();
+    bytes.push(0);
+    Ok(OwnedValue::try_from(zvariant::Value::from(bytes))?)
 }
 
 fn build_openvpn_settings(config: &OpenVpnConnectionConfig) -> Result<SettingsMap> {
@@ -1270,7 +4005,7 @@ fn build_openvpn_settings(config: &OpenVpnConnectionConfig) -> Result<SettingsMa
         "uuid".to_string(),
         owned_string(&Uuid::new_v4().to_string()),
     );
-    connection.insert("autoconnect".to_string(), false.into());
+    connection.insert("autoconnect".to_string(), config.autoconnect.into());
     settings.insert("connection".to_string(), connection);
 
     let mut vpn = HashMap::new();
@@ -1290,6 +4025,8 @@ fn build_openvpn_settings(config: &OpenVpnConnectionConfig) -> Result<SettingsMa
         owned_string_map(HashMap::from([
             ("connection-type".to_string(), "password".to_string()),
             ("remote".to_string(), config.remote.trim().to_string()),
+            // * "Not saved": NM asks the secret agent for the password on every connect
+            ("password-flags".to_string(), "2".to_string()),
         ]))?,
     );
     settings.insert("vpn".to_string(), vpn);
@@ -1302,9 +4039,21 @@ fn build_openvpn_settings(config: &OpenVpnConnectionConfig) -> Result<SettingsMa
     ipv6.insert("method".to_string(), owned_string("ignore"));
     settings.insert("ipv6".to_string(), ipv6);
 
+    apply_vpn_routing(&mut settings, config);
     Ok(settings)
 }
 
+fn apply_vpn_routing(settings: &mut SettingsMap, config: &OpenVpnConnectionConfig) {
+    for family in ["ipv4", "ipv6"] {
+        let section = settings.entry(family.to_string()).or_default();
+        section.insert("never-default".to_string(), config.never_default.into());
+        section.insert(
+            "ignore-auto-routes".to_string(),
+            config.ignore_auto_routes.into(),
+        );
+    }
+}
+
 async fn detect_vpn_file_type(path: &Path) -> Result<VpnKind> {
     let content = fs::read_to_string(path).await.unwrap_or_default();
     let lower = content.to_lowercase();
@@ -1330,101 +4079,246 @@ async fn import_wireguard_config(
     reconnect_if_active: bool,
 ) -> Result<String> {
     let client = dbus_client().await?;
-    let before = list_supported_vpn_connections().await?;
-    let was_active = existing_uuid
-        .and_then(|uuid| before.iter().find(|vpn| vpn.uuid == uuid))
-        .map(|vpn| vpn.active)
-        .unwrap_or(false);
 
-    if let Some(uuid) = existing_uuid {
-        client.deactivate_connection_by_uuid(uuid).await.ok();
-        client.delete_connection_by_uuid(uuid).await?;
-    }
-
-    let temp_path = build_temp_wireguard_path(&config.name);
-    let contents = build_wireguard_config_text(config);
-    fs::write(&temp_path, contents).await?;
-    let import_result = run_nmcli_command(&[
-        "connection",
-        "import",
-        "type",
-        "wireguard",
-        "file",
-        temp_path.to_string_lossy().as_ref(),
-    ])
-    .await;
-    let _ = fs::remove_file(&temp_path).await;
-    import_result?;
+    let Some(uuid) = existing_uuid else {
+        let uuid = Uuid::new_v4().to_string();
+        let settings = build_wireguard_settings(config, &uuid)?;
+        client.add_connection(&settings).await?;
+        return Ok(uuid);
+    };
 
-    let after = list_supported_vpn_connections().await?;
-    let imported = after
+    let profile = client
+        .find_connection_by_uuid(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+    let was_active = client
+        .list_active_connections()
+        .await?
         .iter()
-        .find(|vpn| {
-            !before.iter().any(|existing| existing.uuid == vpn.uuid)
-                && vpn.kind == VpnKind::WireGuard
-        })
-        .cloned()
-        .or_else(|| {
-            after.into_iter().find(|vpn| {
-                vpn.kind == VpnKind::WireGuard && vpn.name == sanitized_wireguard_name(&config.name)
-            })
-        })
-        .ok_or_else(|| anyhow!("WireGuard connection was imported but could not be located"))?;
+        .any(|active| active.uuid == uuid);
 
-    rename_connection_uuid(&imported.uuid, config.name.trim()).await?;
+    let settings = build_wireguard_settings(config, uuid)?;
+    client
+        .update_connection_settings(&profile.path, &settings)
+        .await?;
 
     if reconnect_if_active && was_active {
-        activate_vpn_connection(&imported.uuid).await.ok();
+        log::info!("Reconnecting VPN {} after settings update", uuid);
+        client.deactivate_connection_by_uuid(uuid).await.ok();
+        activate_vpn_connection(uuid).await.ok();
     }
 
-    Ok(imported.uuid)
+    Ok(uuid.to_string())
 }
 
-fn build_wireguard_config_text(config: &WireGuardConnectionConfig) -> String {
-    let mut lines = vec![
-        "[Interface]".to_string(),
-        format!("PrivateKey = {}", config.private_key.trim()),
-    ];
+fn build_wireguard_settings(config: &WireGuardConnectionConfig, uuid: &str) -> Result<SettingsMap> {
+    let mut settings = SettingsMap::new();
+
+    let interface_name = if config.interface_name.trim().is_empty() {
+        sanitized_wireguard_name(&config.name)
+            .chars()
+            .take(15)
+            .collect::<String>()
+    } else {
+        config.interface_name.trim().to_string()
+    };
+
+    let mut connection = HashMap::new();
+    connection.insert("id".to_string(), owned_string(config.name.trim()));
+    connection.insert("type".to_string(), owned_string("wireguard"));
+    connection.insert("uuid".to_string(), owned_string(uuid));
+    connection.insert("interface-name".to_string(), owned_string(&interface_name));
+    connection.insert("autoconnect".to_string(), false.into());
+    settings.insert("connection".to_string(), connection);
+
+    let peers = std::iter::once(config.first_peer())
+        .chain(config.extra_peers.iter().cloned())
+        .map(|peer| wireguard_peer_settings(&peer))
+        .collect::<Result<Vec<_>>>()?;
 
-    if !config.addresses.is_empty() {
-        lines.push(format!("Address = {}", config.addresses.join(", ")));
+    let mut wireguard = HashMap::new();
+    wireguard.insert(
+        "private-key".to_string(),
+        owned_string(config.private_key.trim()),
+    );
+    wireguard.insert("private-key-flags".to_string(), 0u32.into());
+    if let Some(mtu) = config.mtu.filter(|value| *value > 0) {
+        wireguard.insert("mtu".to_string(), mtu.into());
     }
-    if !config.dns_servers.is_empty() {
-        lines.push(format!("DNS = {}", config.dns_servers.join(", ")));
+    wireguard.insert("peers".to_string(), owned_dict_array(peers)?);
+    settings.insert("wireguard".to_string(), wireguard);
+
+    let (v4_addresses, v6_addresses): (Vec<&String>, Vec<&String>) = config
+        .addresses
+        .iter()
+        .partition(|address| !address.contains(':'));
+
+    for (section_name, addresses, empty_method) in [
+        ("ipv4", v4_addresses, "disabled"),
+        ("ipv6", v6_addresses, "ignore"),
+    ] {
+        let mut section = HashMap::new();
+        let address_data = addresses
+            .iter()
+            .map(|address| address_data_entry(address))
+            .collect::<Result<Vec<_>>>()?;
+        if address_data.is_empty() {
+            section.insert("method".to_string(), owned_string(empty_method));
+        } else {
+            section.insert("method".to_string(), owned_string("manual"));
+            section.insert("address-data".to_string(), owned_dict_array(address_data)?);
+        }
+        settings.insert(section_name.to_string(), section);
     }
-    if let Some(mtu) = config.mtu.filter(|value| *value > 0) {
-        lines.push(format!("MTU = {}", mtu));
+
+    let dns_v4: Vec<u32> = config
+        .dns_servers
+        .iter()
+        .filter_map(|raw| raw.trim().parse::<std::net::Ipv4Addr>().ok())
+        // ? NM wants the address in network byte order inside a host integer
+        .map(|addr| u32::from_ne_bytes(addr.octets()))
+        .collect();
+    if !dns_v4.is_empty() {
+        if let Some(ipv4) = settings.get_mut("ipv4") {
+            ipv4.insert(
+                "dns".to_string(),
+                OwnedValue::try_from(zvariant::Value::from(dns_v4))?,
+            );
+        }
+    }
+    // ? ipv6.dns is a list of raw 16-byte addresses
+    let dns_v6: Vec<Vec<u8>> = config
+        .dns_servers
+        .iter()
+        .filter_map(|raw| raw.trim().parse::<std::net::Ipv6Addr>().ok())
+        .map(|addr| addr.octets().to_vec())
+        .collect();
+    if !dns_v6.is_empty() {
+        if let Some(ipv6) = settings.get_mut("ipv6") {
+            ipv6.insert(
+                "dns".to_string(),
+                OwnedValue::try_from(zvariant::Value::from(dns_v6))?,
+            );
+        }
     }
 
-    lines.push(String::new());
-    lines.push("[Peer]".to_string());
-    lines.push(format!("PublicKey = {}", config.public_key.trim()));
-    if let Some(preshared_key) = config
+    Ok(settings)
+}
+
+fn wireguard_peer_settings(peer: &WireGuardPeer) -> Result<HashMap<String, OwnedValue>> {
+    let mut settings: HashMap<String, OwnedValue> = HashMap::new();
+    settings.insert(
+        "public-key".to_string(),
+        owned_string(peer.public_key.trim()),
+    );
+    if !peer.endpoint.trim().is_empty() {
+        settings.insert("endpoint".to_string(), owned_string(peer.endpoint.trim()));
+    }
+    settings.insert(
+        "allowed-ips".to_string(),
+        OwnedValue::try_from(zvariant::Value::from(peer.allowed_ips.clone()))?,
+    );
+    if let Some(preshared_key) = peer
         .preshared_key
         .as_ref()
-        .map(|k| k.as_str())
-        .filter(|value| !value.trim().is_empty())
+        .map(|k| k.trim())
+        .filter(|k| !k.is_empty())
     {
-        lines.push(format!("PresharedKey = {}", preshared_key.trim()));
-    }
-    lines.push(format!("Endpoint = {}", config.endpoint.trim()));
-    if !config.allowed_ips.is_empty() {
-        lines.push(format!("AllowedIPs = {}", config.allowed_ips.join(", ")));
+        settings.insert("preshared-key".to_string(), owned_string(preshared_key));
+        settings.insert("preshared-key-flags".to_string(), 0u32.into());
     }
-    if let Some(keepalive) = config.persistent_keepalive.filter(|value| *value > 0) {
-        lines.push(format!("PersistentKeepalive = {}", keepalive));
+    if let Some(keepalive) = peer.persistent_keepalive.filter(|value| *value > 0) {
+        settings.insert("persistent-keepalive".to_string(), keepalive.into());
     }
+    Ok(settings)
+}
 
-    lines.join("
-")
+fn address_data_entry(address: &str) -> Result<HashMap<String, OwnedValue>> {
+    let (ip, prefix) = match address.trim().split_once('/') {
+        Some((ip, prefix)) => (
+            ip,
+            prefix
+                .parse::<u32>()
+                .map_err(|_| anyhow!("Invalid address prefix in {}", address))?,
+        ),
+        None if address.contains(':') => (address.trim(), 128),
+        None => (address.trim(), 32),
+    };
+    ip.parse::<std::net::IpAddr>()
+        .map_err(|_| anyhow!("Invalid address {}", address))?;
+
+    let mut entry = HashMap::new();
+    entry.insert("address".to_string(), owned_string(ip));
+    entry.insert("prefix".to_string(), prefix.into());
+    Ok(entry)
 }
 
-fn build_temp_wireguard_path(name: &str) -> std::path::PathBuf {
-    let mut sanitized = sanitized_wireguard_name(name);
-    if sanitized.is_empty() {
-        sanitized = format!("adw-network-{}", Uuid::new_v4());
+// * Reads a wg-quick style file; every [Peer] is kept, the editor shows the first.
+fn parse_wireguard_config_text(name: &str, content: &str) -> Result<WireGuardConnectionConfig> {
+    let mut config = WireGuardConnectionConfig {
+        name: name.to_string(),
+        ..WireGuardConnectionConfig::default()
+    };
+    let mut section = String::new();
+    let mut peers: Vec<WireGuardPeer> = Vec::new();
+
+    let split_list = |value: &str| -> Vec<String> {
+        value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    };
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].trim().to_lowercase();
+            if section == "peer" {
+                peers.push(WireGuardPeer::default());
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_lowercase(), value.trim());
+
+        match (section.as_str(), key.as_str()) {
+            ("interface", "privatekey") => config.private_key = Zeroizing::new(value.to_string()),
+            ("interface", "address") => config.addresses.extend(split_list(value)),
+            ("interface", "dns") => config.dns_servers.extend(split_list(value)),
+            ("interface", "mtu") => config.mtu = value.parse().ok(),
+            ("peer", _) => {
+                let Some(peer) = peers.last_mut() else {
+                    continue;
+                };
+                match key.as_str() {
+                    "publickey" => peer.public_key = value.to_string(),
+                    "presharedkey" => peer.preshared_key = Some(Zeroizing::new(value.to_string())),
+                    "endpoint" => peer.endpoint = value.to_string(),
+                    "allowedips" => peer.allowed_ips.extend(split_list(value)),
+                    "persistentkeepalive" => peer.persistent_keepalive = value.parse().ok(),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if config.private_key.is_empty()
+        || peers.is_empty()
+        || peers.iter().any(|peer| peer.public_key.is_empty())
+    {
+        return Err(anyhow!(
+            "WireGuard file needs an [Interface] PrivateKey and a PublicKey for every [Peer]"
+        ));
     }
-    std::env::temp_dir().join(format!("{sanitized}.conf"))
+    config.set_peers(peers);
+    Ok(config)
 }
 
 fn sanitized_wireguard_name(name: &str) -> String {
@@ -1443,16 +4337,50 @@ fn sanitized_wireguard_name(name: &str) -> String {
 }
 
 async fn run_nmcli_command(args: &[&str]) -> Result<()> {
-    let output = Command::new("nmcli").args(args).output().await?;
-    if output.status.success() {
-        return Ok(());
+    let output = command_output(Command::new("nmcli").args(args)).await?;
+    match nmcli_failure(&output) {
+        None => Ok(()),
+        Some(NmError::PermissionDenied(stderr)) => {
+            log::info!(
+                "nmcli was denied by polkit, retrying through pkexec: {}",
+                stderr
+            );
+            run_nmcli_with_pkexec(args, stderr).await
+        }
+        Some(error) => Err(error.into()),
     }
+}
 
+fn nmcli_failure(output: &Output) -> Option<NmError> {
+    if output.status.success() {
+        return None;
+    }
     let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-    if stderr.is_empty() {
-        Err(anyhow!("nmcli command failed"))
+    Some(if stderr.is_empty() {
+        NmError::Other("nmcli command failed".to_string())
     } else {
-        Err(anyhow!(stderr))
+        NmError::from_command_stderr(&stderr)
+    })
+}
+
+// * pkexec asks the session's polkit agent for an admin password; without it the denial stands.
+async fn run_nmcli_with_pkexec(args: &[&str], denial: String) -> Result<()> {
+    let output = match command_output(Command::new("pkexec").arg("nmcli").args(args)).await {
+        Ok(output) => output,
+        Err(e) => {
+            log::debug!("pkexec fallback unavailable: {}", e);
+            return Err(NmError::PermissionDenied(denial).into());
+        }
+    };
+    match output.status.code() {
+        // * The user dismissed the authentication prompt
+        Some(126) => Err(NmError::Cancelled.into()),
+        // * Not authorized, or no polkit agent to ask
+        Some(127) => Err(NmError::PermissionDenied(denial).into()),
+        _ => match nmcli_failure(&output) {
+            None => Ok(()),
+            Some(error) => Err(error.into()),
+        },
     }
 }
 
@@ -1497,35 +4425,115 @@ pub async fn is_hotspot_active() -> Result<bool> {
     Ok(get_active_hotspot_connection().await?.is_some())
 }
 
+// * Skips this app's own "Hotspot" profile, which only exists while the hotspot runs.
+pub async fn list_saved_hotspot_profiles() -> Result<Vec<SavedHotspotProfile>> {
+    let client = dbus_client().await?;
+    let mut profiles = Vec::new();
+    for profile in client.list_connections().await? {
+        if profile.id == "Hotspot" || !connection_profile_is_hotspot(&profile) {
+            continue;
+        }
+        let Some(wireless) = profile.settings.get("802-11-wireless") else {
+            continue;
+        };
+        let Some(ssid) = wireless.get("ssid").and_then(value_string) else {
+            continue;
+        };
+        let band = match wireless
+            .get("band")
+            .and_then(owned_value_to_string)
+            .as_deref()
+        {
+            Some("a") => "5 GHz",
+            Some("bg") => "2.4 GHz",
+            _ => "Auto",
+        };
+        let hidden = wireless.get("hidden").and_then(value_bool).unwrap_or(false);
+        // * Secrets are only handed out when polkit allows it; import without one otherwise
+        let password = client
+            .get_connection_secrets(&profile.path, "802-11-wireless-security")
+            .await
+            .ok()
+            .and_then(|secrets| {
+                secrets
+                    .get("802-11-wireless-security")
+                    .and_then(|section| section.get("psk"))
+                    .and_then(value_string)
+            })
+            .filter(|password| !password.is_empty());
+        profiles.push(SavedHotspotProfile {
+            id: profile.id,
+            ssid,
+            password,
+            band: band.to_string(),
+            hidden,
+        });
+    }
+    Ok(profiles)
+}
+
+// * Reads the saved PSK over D-Bus; NM only hands it out when polkit allows this user.
+pub async fn get_saved_wifi_password(ssid: &str) -> Result<Option<String>> {
+    let client = dbus_client().await?;
+    let profile = client
+        .find_connection_by_id(ssid)
+        .await?
+        .ok_or_else(|| anyhow!("Network {} is not saved", ssid))?;
+    let secrets = client
+        .get_connection_secrets(&profile.path, "802-11-wireless-security")
+        .await?;
+    Ok(secrets
+        .get("802-11-wireless-security")
+        .and_then(|section| section.get("psk").or_else(|| section.get("wep-key0")))
+        .and_then(value_string)
+        .filter(|password| !password.is_empty()))
+}
+
 // * Retrieves the saved wifi password for an SSID using nmcli with sudo.
 // * Requires the user's sudo password — we pass it via stdin with -S flag.
 pub async fn get_wifi_password_with_sudo(ssid: &str, sudo_password: &str) -> Result<String> {
     use tokio::io::AsyncWriteExt;
     use tokio::process::Command;
 
+    // * -g prints escaped terse values; `id` stops SSIDs like "uuid" or "path" being read as keywords.
     let mut child = Command::new("sudo")
         .args([
             "-S",
+            "env",
+            "LC_ALL=C",
             "nmcli",
             "--show-secrets",
+            "-g",
+            NMCLI_SECRET_FIELDS,
             "connection",
             "show",
+            "id",
             ssid,
         ])
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
         .spawn()?;
 
     // Feed sudo password on stdin
     if let Some(mut stdin) = child.stdin.take() {
         stdin
-            .write_all(format!("{}
-", sudo_password).as_bytes())
+            .write_all(
+                format!(
+                    "{}
+",
+                    sudo_password
+                )
+                .as_bytes(),
+            )
             .await?;
     }
 
-    let output = child.wait_with_output().await?;
+    let output = with_timeout(operation_timeout(), "sudo nmcli", async {
+        Ok(child.wait_with_output().await?)
+    })
+    .await?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1536,19 +4544,630 @@ pub async fn get_wifi_password_with_sudo(ssid: &str, sudo_password: &str) -> Res
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        // nmcli output: "802-11-wireless-security.psk:             <password>"
-        if line.contains("802-11-wireless-security.psk:") {
-            let password = line
-                .splitn(2, ':')
-                .nth(1)
-                .map(|s| s.trim().to_string())
-                .unwrap_or_default();
-            if !password.is_empty() && password != "--" {
-                return Ok(password);
-            }
+    password_from_nmcli_secrets(&stdout)
+        .ok_or_else(|| anyhow!("Password not found for SSID: {}", ssid))
+}
+
+const NMCLI_SECRET_FIELDS: &str = "802-11-wireless-security.psk,802-11-wireless-security.wep-key0";
+
+// * One line of `-g psk,wep-key0`; passwords keep their spaces, only the escaping is undone.
+fn password_from_nmcli_secrets(output: &str) -> Option<String> {
+    let line = output.lines().find(|line| !line.is_empty())?;
+    split_nmcli_terse(line)
+        .into_iter()
+        .find(|value| !value.is_empty() && value != "--")
+}
+
+// * nmcli terse/get-values output separates fields with ':' and escapes ':' and '\' inside values.
+fn split_nmcli_terse(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) => current.push(escaped),
+                None => current.push('\\'),
+            },
+            ':' => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gates_features_on_the_detected_version() {
+        assert_eq!(
+            NmVersion::parse("1.42.2-1.fc38"),
+            Some(NmVersion::new(1, 42, 2))
+        );
+        assert_eq!(NmVersion::parse("1.14"), Some(NmVersion::new(1, 14, 0)));
+        assert_eq!(NmVersion::parse("unknown"), None);
+
+        let old = NmCapabilities {
+            version: Some(NmVersion::new(1, 14, 0)),
+        };
+        assert!(!old.supports(NmFeature::Sae));
+        assert_eq!(
+            old.unsupported_reason(NmFeature::Sae).as_deref(),
+            Some("WPA3 Personal needs NetworkManager 1.16 or newer (this system has 1.14.0)")
+        );
+
+        let current = NmCapabilities {
+            version: Some(NmVersion::new(1, 46, 0)),
+        };
+        assert!(current.supports(NmFeature::Band6Ghz));
+        assert!(NmCapabilities::default().supports(NmFeature::Wpa3Hotspot));
+        assert_eq!(
+            NmCapabilities::default().unsupported_reason(NmFeature::Owe),
+            None
+        );
+    }
+
+    #[test]
+    fn cancelled_and_timed_out_operations_are_typed() {
+        tokio_test::block_on(async {
+            let (future, handle) = cancellable(async {
+                sleep(Duration::from_secs(5)).await;
+                Ok(())
+            });
+            handle.cancel();
+            let err = future.await.unwrap_err();
+            assert_eq!(NmError::classify(&err), NmError::Cancelled);
+
+            let err = with_timeout(Duration::from_millis(10), "Scan", async {
+                sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+            assert!(matches!(NmError::classify(&err), NmError::Timeout(_)));
+        });
+    }
+
+    #[test]
+    fn recognizes_owe_and_wpa3_transition() {
+        let with_rsn = |rsn_flags: u32| DbusAccessPoint {
+            rsn_flags,
+            ..dbus_ap("Cafe", "aa:bb:cc:dd:ee:ff", 2437, 60, false)
+        };
+
+        let transition = with_rsn(0x188 | NM_802_11_AP_SEC_KEY_MGMT_SAE);
+        assert_eq!(ap_security_type(&transition), "WPA2/WPA3");
+        assert_eq!(key_mgmt_from_security_type(Some("WPA2/WPA3")), "wpa-psk");
+        assert_eq!(
+            ap_security_type(&with_rsn(0x08 | NM_802_11_AP_SEC_KEY_MGMT_SAE)),
+            "WPA3"
+        );
+        assert_eq!(key_mgmt_from_security_type(Some("WPA3")), "sae");
+
+        let owe = with_rsn(0x08 | NM_802_11_AP_SEC_KEY_MGMT_OWE);
+        assert_eq!(ap_security_type(&owe), "Enhanced Open");
+        assert!(!is_ap_secured(&owe));
+        assert!(!is_ap_secured(&with_rsn(NM_802_11_AP_SEC_KEY_MGMT_OWE_TM)));
+        assert_eq!(open_key_mgmt(Some("Enhanced Open")), Some("owe"));
+        assert_eq!(open_key_mgmt(Some("Open")), None);
+    }
+
+    fn dbus_ap(
+        ssid: &str,
+        bssid: &str,
+        frequency: u32,
+        strength: u8,
+        active: bool,
+    ) -> DbusAccessPoint {
+        DbusAccessPoint {
+            path: OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/AccessPoint/1")
+                .unwrap(),
+            device: OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/Devices/2").unwrap(),
+            ssid: ssid.to_string(),
+            bssid: bssid.to_string(),
+            frequency,
+            strength,
+            flags: 0,
+            wpa_flags: 0,
+            rsn_flags: 0,
+            active,
+        }
+    }
+
+    #[test]
+    fn lists_each_bssid_of_an_ssid_active_first() {
+        let aps = vec![
+            dbus_ap("Mesh", "aa:aa:aa:aa:aa:01", 2437, 80, false),
+            dbus_ap("Mesh", "aa:aa:aa:aa:aa:02", 5180, 55, true),
+            dbus_ap("Mesh", "AA:AA:AA:AA:AA:01", 2437, 60, false),
+            dbus_ap("Other", "bb:bb:bb:bb:bb:01", 2412, 90, false),
+        ];
+
+        let listed = access_points_for_ssid(aps, "Mesh");
+
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].bssid, "AA:AA:AA:AA:AA:02");
+        assert_eq!((listed[0].channel, listed[0].band.as_str()), (36, "5 GHz"));
+        assert!(listed[0].active);
+        assert_eq!(listed[1].signal, 80);
+    }
+
+    #[test]
+    fn validates_static_ipv4_fields() {
+        let config =
+            parse_ipv4_config(Ipv4Method::Manual, "192.168.1.50", 24, "192.168.1.1").unwrap();
+        assert_eq!(
+            config.address,
+            Some(std::net::Ipv4Addr::new(192, 168, 1, 50))
+        );
+
+        assert!(parse_ipv4_config(Ipv4Method::Manual, "", 24, "").is_err());
+        assert!(parse_ipv4_config(Ipv4Method::Manual, "192.168.1.50", 24, "10.0.0.1").is_err());
+        assert!(parse_ipv4_config(Ipv4Method::Manual, "192.168.1.500", 24, "").is_err());
+        // * Leftover fields are ignored once the method no longer needs them
+        assert!(parse_ipv4_config(Ipv4Method::Auto, "", 0, "").is_ok());
+    }
+
+    #[test]
+    fn reads_ipv4_config_from_profile_settings() {
+        let mut entry = HashMap::new();
+        entry.insert("address".to_string(), owned_string("10.0.0.7"));
+        entry.insert("prefix".to_string(), OwnedValue::from(16u32));
+        let mut ipv4 = HashMap::new();
+        ipv4.insert("method".to_string(), owned_string("manual"));
+        ipv4.insert(
+            "address-data".to_string(),
+            owned_dict_array(vec![entry]).unwrap(),
+        );
+        ipv4.insert("gateway".to_string(), owned_string("10.0.0.1"));
+        let settings = SettingsMap::from([("ipv4".to_string(), ipv4)]);
+
+        let config = ipv4_config_from_settings(&settings);
+        assert_eq!(config.method, Ipv4Method::Manual);
+        assert_eq!(config.prefix, 16);
+        assert_eq!(config.gateway, Some(std::net::Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(
+            ipv4_config_from_settings(&SettingsMap::new()),
+            Ipv4Config::default()
+        );
+    }
+
+    #[test]
+    fn parses_and_round_trips_static_routes() {
+        let route = parse_static_route("10.8.0.0/16", "192.168.1.254", "50").unwrap();
+        assert_eq!(route.label(), "10.8.0.0/16");
+        assert_eq!(route.details(), "via 192.168.1.254 · metric 50");
+        assert_eq!(parse_static_route("fd00::1", "", "").unwrap().prefix, 128);
+        assert!(parse_static_route("10.0.0.0/33", "", "").is_err());
+        assert!(parse_static_route("10.0.0.0/8", "fe80::1", "").is_err());
+
+        let mut entry = HashMap::new();
+        entry.insert("dest".to_string(), owned_string("10.8.0.0"));
+        entry.insert("prefix".to_string(), OwnedValue::from(16u32));
+        entry.insert("next-hop".to_string(), owned_string("192.168.1.254"));
+        entry.insert("metric".to_string(), OwnedValue::from(50u32));
+        let ipv4 = HashMap::from([(
+            "route-data".to_string(),
+            owned_dict_array(vec![entry]).unwrap(),
+        )]);
+        let settings = SettingsMap::from([("ipv4".to_string(), ipv4)]);
+        assert_eq!(routes_from_settings(&settings, "ipv4"), vec![route]);
+        assert!(routes_from_settings(&settings, "ipv6").is_empty());
+    }
+
+    #[test]
+    fn maps_mac_policies_to_profile_settings() {
+        let mut settings = SettingsMap::from([(
+            "802-11-wireless".to_string(),
+            HashMap::from([(
+                "mac-address-randomization".to_string(),
+                OwnedValue::from(2u32),
+            )]),
+        )]);
+        assert_eq!(
+            mac_policy_from_settings(&settings, "802-11-wireless"),
+            MacAddressPolicy::Random
+        );
+
+        apply_mac_policy(&mut settings, "802-11-wireless", &MacAddressPolicy::Stable).unwrap();
+        assert_eq!(
+            mac_policy_from_settings(&settings, "802-11-wireless"),
+            MacAddressPolicy::Stable
+        );
+        assert!(!settings["802-11-wireless"].contains_key("mac-address-randomization"));
+
+        apply_mac_policy(&mut settings, "802-11-wireless", &MacAddressPolicy::Default).unwrap();
+        assert_eq!(
+            mac_policy_from_settings(&settings, "802-11-wireless"),
+            MacAddressPolicy::Default
+        );
+        let fixed = MacAddressPolicy::Fixed("02:00:5E:10:00:0Z".to_string());
+        assert!(apply_mac_policy(&mut settings, "802-11-wireless", &fixed).is_err());
+
+        let wired = SettingsMap::from([(
+            "connection".to_string(),
+            HashMap::from([("type".to_string(), owned_string("802-3-ethernet"))]),
+        )]);
+        assert_eq!(hardware_section(&wired).unwrap(), "802-3-ethernet");
+        assert!(hardware_section(&SettingsMap::new()).is_err());
+    }
+
+    #[test]
+    fn builds_enterprise_settings_per_eap_method() {
+        let mut config = EapConfig {
+            identity: "student@uni.example".to_string(),
+            anonymous_identity: Some("  ".to_string()),
+            ..EapConfig::default()
+        };
+        assert!(build_enterprise_wifi_settings("eduroam", &config, false).is_err());
+
+        config.password = Some(Zeroizing::new("hunter2".to_string()));
+        let settings = build_enterprise_wifi_settings("eduroam", &config, false).unwrap();
+        let eap = &settings["802-1x"];
+        assert_eq!(
+            eap.get("eap").and_then(value_string_list),
+            Some(vec!["peap".to_string()])
+        );
+        assert_eq!(
+            eap.get("phase2-auth").and_then(value_string),
+            Some("mschapv2".to_string())
+        );
+        assert!(!eap.contains_key("anonymous-identity"));
+        assert!(eap.contains_key("system-ca-certs"));
+        assert_eq!(
+            settings["802-11-wireless-security"]
+                .get("key-mgmt")
+                .and_then(value_string),
+            Some("wpa-eap".to_string())
+        );
+
+        config.method = EapMethod::Tls;
+        config.ca_cert = Some(PathBuf::from("/etc/ssl/uni-ca.pem"));
+        assert!(validate_eap_config(&config).is_err());
+        config.client_cert = Some(PathBuf::from("/home/me/cert.pem"));
+        config.private_key = Some(PathBuf::from("/home/me/key.pem"));
+        let settings = build_enterprise_wifi_settings("eduroam", &config, true).unwrap();
+        let eap = &settings["802-1x"];
+        assert!(!eap.contains_key("phase2-auth"));
+        assert!(!eap.contains_key("password"));
+        let ca_cert = Vec::<u8>::try_from(eap["ca-cert"].try_clone().unwrap()).unwrap();
+        assert_eq!(ca_cert, b"file:///etc/ssl/uni-ca.pem\0".to_vec());
+        assert!(is_enterprise_security("WPA2 Enterprise"));
+
+        let read_back = eap_config_from_settings(&settings).unwrap();
+        assert_eq!(read_back.method, EapMethod::Tls);
+        assert_eq!(read_back.identity, "student@uni.example");
+        assert_eq!(read_back.ca_cert, config.ca_cert);
+        assert_eq!(read_back.private_key, config.private_key);
+        assert!(read_back.private_key_password.is_none());
+        assert!(eap_config_from_settings(&SettingsMap::new()).is_none());
+    }
+
+    #[test]
+    fn picks_a_free_duplicate_name() {
+        let taken = HashSet::from(["Office", "Office (copy)", "Office (copy 2)"]);
+        assert_eq!(duplicate_name("Office", &taken), "Office (copy 3)");
+        assert_eq!(duplicate_name("Home", &taken), "Home (copy)");
+    }
+
+    #[test]
+    fn recognizes_phone_tethering_drivers() {
+        assert!(is_usb_tether_driver("rndis_host"));
+        assert!(is_usb_tether_driver("ipheth"));
+        assert!(!is_usb_tether_driver("r8169"));
+    }
+
+    #[test]
+    fn round_trips_mac_address_bytes() {
+        let bytes = mac_address_bytes("aa:0B:cc:1d:EE:ff").unwrap();
+        assert_eq!(bytes, vec![0xAA, 0x0B, 0xCC, 0x1D, 0xEE, 0xFF]);
+        assert_eq!(format_mac_bytes(&bytes), "AA:0B:CC:1D:EE:FF");
+        assert!(mac_address_bytes("AA:BB:CC").is_err());
+        assert_eq!(WifiBand::from_nm("a").as_nm(), Some("a"));
+        assert_eq!(WifiBand::from_nm(""), WifiBand::Any);
+    }
+
+    #[test]
+    fn merges_scans_and_expires_missing_networks() {
+        let network = |ssid: &str, connected: bool| WifiNetwork {
+            ssid: ssid.to_string(),
+            signal: 50,
+            secured: true,
+            connected,
+            band: "2.4 GHz".to_string(),
+            channel: 6,
+            freq_mhz: 2437,
+            security_type: "WPA2".to_string(),
+            device: None,
+        };
+        let start = Instant::now();
+        let first = merge_scan(
+            Vec::new(),
+            vec![network("Home", true), network("Cafe", false)],
+            start,
+        );
+
+        let later = start + Duration::from_secs(10);
+        let second = merge_scan(first, vec![network("Office", false)], later);
+        let ssids: Vec<&str> = second.iter().map(|c| c.network.ssid.as_str()).collect();
+        assert_eq!(ssids, vec!["Cafe", "Home", "Office"]);
+        assert!(second.iter().all(|c| !c.network.connected));
+
+        let expired = merge_scan(second, Vec::new(), start + SCAN_CACHE_TTL);
+        let ssids: Vec<&str> = expired.iter().map(|c| c.network.ssid.as_str()).collect();
+        assert_eq!(ssids, vec!["Office"]);
+    }
+
+    #[test]
+    fn builds_vlan_profiles_with_valid_interface_names() {
+        let settings = build_vlan_settings("enp3s0", 10).unwrap();
+        let connection = &settings["connection"];
+        assert_eq!(
+            value_string(&connection["interface-name"]).as_deref(),
+            Some("enp3s0.10")
+        );
+        assert_eq!(value_u32(&settings["vlan"]["id"]), Some(10));
+
+        assert_eq!(vlan_interface_name("enx0123456789ab", 200), "vlan200");
+        assert!(build_vlan_settings("enp3s0", 0).is_err());
+        assert!(build_vlan_settings("enp3s0", 4095).is_err());
+        assert!(build_vlan_settings(" ", 10).is_err());
+    }
+
+    #[test]
+    fn toggles_only_the_magic_packet_wake_flag() {
+        assert_eq!(
+            wake_on_lan_flags(NM_WAKE_ON_LAN_DEFAULT, true),
+            NM_WAKE_ON_LAN_MAGIC
+        );
+        assert_eq!(wake_on_lan_flags(0x40 | 0x2, false), 0x2);
+        assert_eq!(wake_on_lan_flags(NM_WAKE_ON_LAN_IGNORE, false), 0);
+    }
+
+    #[test]
+    fn splits_escaped_terse_fields() {
+        assert_eq!(
+            split_nmcli_terse(r"Caf\\é\:5G 🚀:yes:WPA2"),
+            vec!["Caf\\é:5G 🚀", "yes", "WPA2"]
+        );
+        assert_eq!(split_nmcli_terse(r"a\:b::"), vec!["a:b", "", ""]);
+        assert_eq!(split_nmcli_terse(r"trailing\\"), vec![r"trailing\"]);
+    }
+
+    #[test]
+    fn reads_password_from_get_values_output() {
+        assert_eq!(
+            password_from_nmcli_secrets(" pass\\:word :\n"),
+            Some(" pass:word ".to_string())
+        );
+        assert_eq!(
+            password_from_nmcli_secrets(":wepkey\n"),
+            Some("wepkey".to_string())
+        );
+        assert_eq!(password_from_nmcli_secrets(":\n"), None);
+    }
+
+    fn candidate(name: &str, priority: i32, last_connected: u64) -> AutoconnectCandidate {
+        AutoconnectCandidate {
+            name: name.to_string(),
+            uuid: String::new(),
+            conn_type: "802-11-wireless".to_string(),
+            priority,
+            last_connected,
         }
     }
 
-    Err(anyhow!("Password not found for SSID: {}", ssid))
+    #[test]
+    fn retry_stops_once_the_result_is_complete() {
+        let calls = std::cell::Cell::new(0);
+        let result = tokio_test::block_on(retry_until(
+            5,
+            Duration::ZERO,
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move { Ok::<_, anyhow::Error>(n) }
+            },
+            |n| *n >= 3,
+        ));
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn retry_returns_last_result_when_attempts_run_out() {
+        let calls = std::cell::Cell::new(0);
+        let result = tokio_test::block_on(retry_until(
+            2,
+            Duration::ZERO,
+            || {
+                calls.set(calls.get() + 1);
+                async { Err::<u32, _>(anyhow!("no device")) }
+            },
+            |_| true,
+        ));
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn parses_iw_power_save_output() {
+        assert_eq!(parse_power_save_output("Power save: on\n"), Some(true));
+        assert_eq!(parse_power_save_output("Power save: off"), Some(false));
+        assert_eq!(
+            parse_power_save_output("command failed: No such device"),
+            None
+        );
+    }
+
+    #[test]
+    fn validates_dhcp_client_ids() {
+        assert!(validate_dhcp_client_id("mac").is_ok());
+        assert!(validate_dhcp_client_id("01:aa:bb:cc:dd:ee:ff").is_ok());
+        assert!(validate_dhcp_client_id("laptop-01").is_ok());
+        assert!(validate_dhcp_client_id("  ").is_err());
+        assert!(validate_dhcp_client_id("01::bb").is_err());
+        assert!(validate_dhcp_client_id("01:abc:ff").is_err());
+        assert!(validate_dhcp_client_id("id\twith-tab").is_err());
+    }
+
+    #[test]
+    fn maps_saved_wifi_profiles_with_metadata() {
+        let profile = |id: &str, conn_type: &str, key_mgmt: Option<&str>| DbusConnectionProfile {
+            path: OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/Settings/1").unwrap(),
+            id: id.to_string(),
+            uuid: format!("uuid-{}", id),
+            conn_type: conn_type.to_string(),
+            interface_name: None,
+            autoconnect: None,
+            autoconnect_priority: 0,
+            timestamp: 1_700_000_000,
+            zone: None,
+            settings: key_mgmt
+                .map(|key_mgmt| {
+                    SettingsMap::from([(
+                        "802-11-wireless-security".to_string(),
+                        HashMap::from([("key-mgmt".to_string(), owned_string(key_mgmt))]),
+                    )])
+                })
+                .unwrap_or_default(),
+        };
+        let mut bound = profile("Office", "802-11-wireless", Some("wpa-eap"));
+        bound.interface_name = Some("wlan1".to_string());
+        bound.autoconnect = Some(false);
+
+        let saved = tokio_test::block_on(map_saved_connections(vec![
+            profile("Home", "802-11-wireless", Some("sae")),
+            profile("Cafe", "802-11-wireless", None),
+            profile("Wired", "802-3-ethernet", None),
+            bound,
+        ]));
+
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved[0].security_type, "WPA3");
+        assert!(saved[0].autoconnect);
+        assert_eq!(saved[0].last_used, 1_700_000_000);
+        assert_eq!(saved[1].security_type, "Open");
+        assert_eq!(saved[2].security_type, "WPA2 Enterprise");
+        assert_eq!(saved[2].interface_name.as_deref(), Some("wlan1"));
+        assert!(!saved[2].autoconnect);
+    }
+
+    #[test]
+    fn orders_autoconnect_candidates_by_priority_first() {
+        let ordered = order_autoconnect_candidates(vec![
+            candidate("Cafe", 0, 1_700_000_900),
+            candidate("Home", 10, 1_600_000_000),
+            candidate("Office", -5, 1_700_001_000),
+        ]);
+        let names: Vec<&str> = ordered.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Home", "Cafe", "Office"]);
+    }
+
+    #[test]
+    fn breaks_priority_ties_by_most_recent_use_then_name() {
+        let ordered = order_autoconnect_candidates(vec![
+            candidate("never-b", 0, 0),
+            candidate("older", 0, 1_600_000_000),
+            candidate("Never-a", 0, 0),
+            candidate("newer", 0, 1_700_000_000),
+        ]);
+        let names: Vec<&str> = ordered.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["newer", "older", "Never-a", "never-b"]);
+    }
+
+    #[test]
+    fn parses_wg_quick_files() {
+        let config = parse_wireguard_config_text(
+            "office",
+            "[Interface]\nPrivateKey = cHJpdmF0ZQ==\nAddress = 10.0.0.2/24, fd00::2/64\nDNS = 1.1.1.1\n\n\
+             [Peer]\nPublicKey = cHVibGlj\nEndpoint = vpn.example.com:51820 # main\n\
+             AllowedIPs = 0.0.0.0/0, ::/0\nPersistentKeepalive = 25\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.addresses, vec!["10.0.0.2/24", "fd00::2/64"]);
+        assert_eq!(config.endpoint, "vpn.example.com:51820");
+        assert_eq!(config.persistent_keepalive, Some(25));
+        assert!(parse_wireguard_config_text("x", "[Interface]\nAddress = 10.0.0.2/24").is_err());
+    }
+
+    #[test]
+    fn imports_every_wireguard_peer() {
+        let config = parse_wireguard_config_text(
+            "mesh",
+            "[Interface]\nPrivateKey = cHJpdmF0ZQ==\nAddress = 10.0.0.2/24\n\n\
+             [Peer]\nPublicKey = cGVlcjE=\nEndpoint = a.example.com:51820\nAllowedIPs = 10.0.0.1/32\n\n\
+             [Peer]\nPublicKey = cGVlcjI=\nPresharedKey = c2hhcmVk\nAllowedIPs = 10.0.1.0/24\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.public_key, "cGVlcjE=");
+        assert_eq!(config.extra_peers.len(), 1);
+        assert_eq!(config.extra_peers[0].allowed_ips, vec!["10.0.1.0/24"]);
+
+        let settings = build_wireguard_settings(&config, "uuid-2").unwrap();
+        let peers = Vec::<HashMap<String, OwnedValue>>::try_from(
+            settings["wireguard"]["peers"].try_clone().unwrap(),
+        )
+        .unwrap();
+        let keys: Vec<String> = peers
+            .iter()
+            .filter_map(|peer| peer.get("public-key").and_then(value_string))
+            .collect();
+        assert_eq!(keys, vec!["cGVlcjE=", "cGVlcjI="]);
+        assert!(peers[1].contains_key("preshared-key"));
+        assert!(!peers[0].contains_key("preshared-key"));
+        assert!(parse_wireguard_config_text(
+            "x",
+            "[Interface]\nPrivateKey = a\n[Peer]\nEndpoint = b:1\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn builds_wireguard_settings_for_dbus() {
+        let config = WireGuardConnectionConfig {
+            name: "Office VPN".to_string(),
+            addresses: vec!["10.0.0.2/24".to_string(), "fd00::2/64".to_string()],
+            dns_servers: vec!["1.1.1.1".to_string(), "2606:4700:4700::1111".to_string()],
+            private_key: Zeroizing::new("cHJpdmF0ZQ==".to_string()),
+            public_key: "cHVibGlj".to_string(),
+            allowed_ips: vec!["0.0.0.0/0".to_string()],
+            ..WireGuardConnectionConfig::default()
+        };
+
+        let settings = build_wireguard_settings(&config, "uuid-1").unwrap();
+
+        assert_eq!(
+            settings["connection"]
+                .get("interface-name")
+                .and_then(value_string),
+            Some("Office-VPN".to_string())
+        );
+        assert_eq!(
+            settings["ipv6"].get("method").and_then(value_string),
+            Some("manual".to_string())
+        );
+        let peers = Vec::<HashMap<String, OwnedValue>>::try_from(
+            settings["wireguard"]["peers"].try_clone().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(peers.len(), 1);
+        let dns_v6 =
+            Vec::<Vec<u8>>::try_from(settings["ipv6"]["dns"].try_clone().unwrap()).unwrap();
+        assert_eq!(
+            dns_v6,
+            vec!["2606:4700:4700::1111"
+                .parse::<std::net::Ipv6Addr>()
+                .unwrap()
+                .octets()
+                .to_vec()]
+        );
+        assert!(settings["ipv4"].contains_key("dns"));
+        assert!(address_data_entry("not-an-ip/24").is_err());
+    }
 }
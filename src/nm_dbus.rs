@@ -6,11 +6,13 @@ use std::collections::{HashMap, HashSet};
 use std::future::poll_fn;
 use std::net::Ipv4Addr;
 use tokio::sync::watch;
-use tokio::time::{sleep, Duration};
-use zbus::{Connection, Message, Proxy};
+use tokio::time::{sleep, Duration, Instant};
+use zbus::proxy::MethodFlags;
+use zbus::{Connection, MatchRule, Message, MessageStream, Proxy};
 use zvariant::{Array, OwnedObjectPath, OwnedValue, Str};
 
-use crate::config::HotspotConfig;
+use crate::config::{HotspotConfig, HotspotMode, HotspotSecurity};
+use crate::nm_error::NmError;
 
 const NM_SERVICE: &str = "org.freedesktop.NetworkManager";
 const NM_PATH: &str = "/org/freedesktop/NetworkManager";
@@ -25,6 +27,11 @@ const NM_ACTIVE_CONN_IFACE: &str = "org.freedesktop.NetworkManager.Connection.Ac
 const NM_IP4_CONFIG_IFACE: &str = "org.freedesktop.NetworkManager.IP4Config";
 const NM_DHCP4_CONFIG_IFACE: &str = "org.freedesktop.NetworkManager.DHCP4Config";
 
+// * Profiles behind a bridged hotspot; the AP and the wired uplink are both ports of the bridge
+pub const HOTSPOT_BRIDGE_ID: &str = "Hotspot Bridge";
+pub const HOTSPOT_UPLINK_ID: &str = "Hotspot Uplink";
+pub const HOTSPOT_BRIDGE_INTERFACE: &str = "adw-hs-br0";
+
 pub const NM_DEVICE_TYPE_ETHERNET: u32 = 1;
 pub const NM_DEVICE_TYPE_WIFI: u32 = 2;
 pub const NM_DEVICE_TYPE_UNUSED1: u32 = 3;
@@ -75,6 +82,12 @@ pub const NM_DEVICE_STATE_ACTIVATED: u32 = 100;
 pub const NM_DEVICE_STATE_DEACTIVATING: u32 = 110;
 pub const NM_DEVICE_STATE_FAILED: u32 = 120;
 
+pub const NM_DEVICE_STATE_REASON_NO_SECRETS: u32 = 7;
+pub const NM_DEVICE_STATE_REASON_SUPPLICANT_DISCONNECT: u32 = 8;
+pub const NM_DEVICE_STATE_REASON_SUPPLICANT_FAILED: u32 = 10;
+pub const NM_DEVICE_STATE_REASON_SUPPLICANT_TIMEOUT: u32 = 11;
+pub const NM_DEVICE_STATE_REASON_SSID_NOT_FOUND: u32 = 53;
+
 pub const NM_ACTIVE_CONNECTION_STATE_UNKNOWN: u32 = 0;
 pub const NM_ACTIVE_CONNECTION_STATE_ACTIVATING: u32 = 1;
 pub const NM_ACTIVE_CONNECTION_STATE_ACTIVATED: u32 = 2;
@@ -87,6 +100,27 @@ pub const NM_CONNECTIVITY_PORTAL: u32 = 2;
 pub const NM_CONNECTIVITY_LIMITED: u32 = 3;
 pub const NM_CONNECTIVITY_FULL: u32 = 4;
 
+pub const NM_802_11_AP_FLAGS_WPS_PBC: u32 = 0x4;
+pub const NM_802_11_AP_SEC_KEY_MGMT_PSK: u32 = 0x100;
+pub const NM_802_11_AP_SEC_KEY_MGMT_802_1X: u32 = 0x200;
+pub const NM_802_11_AP_SEC_KEY_MGMT_SAE: u32 = 0x400;
+pub const NM_802_11_AP_SEC_KEY_MGMT_OWE: u32 = 0x800;
+pub const NM_802_11_AP_SEC_KEY_MGMT_OWE_TM: u32 = 0x1000;
+const NM_WPS_METHOD_PBC: u32 = 0x4;
+// * About two minutes at the 350 ms poll interval, the length of a WPS walk time.
+const WPS_ACTIVATION_ATTEMPTS: u32 = 350;
+// * RADIUS round-trips make EAP slower than PSK; about 30 seconds.
+const EAP_ACTIVATION_ATTEMPTS: u32 = 90;
+
+const NM_ROLLBACK_RESULT_OK: u32 = 0;
+
+pub const NM_WAKE_ON_LAN_DEFAULT: u32 = 0x1;
+pub const NM_WAKE_ON_LAN_MAGIC: u32 = 0x40;
+pub const NM_WAKE_ON_LAN_IGNORE: u32 = 0x8000;
+
+pub const NM_METERED_UNKNOWN: i32 = 0;
+pub const NM_METERED_YES: i32 = 1;
+
 pub type SettingsMap = HashMap<String, HashMap<String, OwnedValue>>;
 
 fn hotspot_band_for_nm(band: &str) -> Option<String> {
@@ -103,6 +137,9 @@ fn hotspot_band_for_nm(band: &str) -> Option<String> {
     if trimmed == "5 GHz" {
         return Some("a".to_string());
     }
+    if trimmed == "6 GHz" {
+        return Some("6GHz".to_string());
+    }
     if trimmed == "2.4 GHz (Wider Range)" {
         return Some("bg".to_string());
     }
@@ -114,6 +151,16 @@ fn hotspot_band_for_nm(band: &str) -> Option<String> {
     Some(trimmed.to_string())
 }
 
+// * Key management and PMF (1 disable, 2 optional, 3 required) for the AP profile.
+// ? With PMF optional NM offers SAE next to PSK when the driver can do it, which is the mixed mode
+fn hotspot_security_for_nm(security: HotspotSecurity) -> (&'static str, i32) {
+    match security {
+        HotspotSecurity::Wpa2 => ("wpa-psk", 1),
+        HotspotSecurity::Wpa3 => ("sae", 3),
+        HotspotSecurity::Wpa2Wpa3 => ("wpa-psk", 2),
+    }
+}
+
 fn hotspot_channel_for_nm(channel: &str) -> Option<u32> {
     let trimmed = channel.trim();
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("auto") {
@@ -144,6 +191,8 @@ pub struct DbusConnectionProfile {
     pub conn_type: String,
     pub interface_name: Option<String>,
     pub autoconnect: Option<bool>,
+    pub autoconnect_priority: i32,
+    pub timestamp: u64,
     pub zone: Option<String>,
     pub settings: SettingsMap,
 }
@@ -164,6 +213,7 @@ pub struct DbusAccessPoint {
     pub path: OwnedObjectPath,
     pub device: OwnedObjectPath,
     pub ssid: String,
+    pub bssid: String,
     pub frequency: u32,
     pub strength: u8,
     pub flags: u32,
@@ -175,11 +225,70 @@ pub struct DbusAccessPoint {
 #[derive(Debug, Clone, Default)]
 pub struct DbusIp4Info {
     pub addresses: Vec<String>,
+    // * Prefix length of the first address
+    pub prefix: Option<u32>,
     pub gateway: Option<String>,
     pub dns: Vec<String>,
     pub dhcp_lease_time_seconds: Option<u32>,
 }
 
+// * What one poll of an ActiveConnection object says about a Wi-Fi activation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationProgress {
+    Activated,
+    Pending,
+    Failed,
+}
+
+// * `state` is None when the object can't be read: NM drops it as soon as a failed activation is torn down.
+pub fn activation_progress(state: Option<u32>, id: &str, ssid: &str) -> ActivationProgress {
+    match state {
+        None
+        | Some(NM_ACTIVE_CONNECTION_STATE_DEACTIVATING | NM_ACTIVE_CONNECTION_STATE_DEACTIVATED) => {
+            ActivationProgress::Failed
+        }
+        Some(NM_ACTIVE_CONNECTION_STATE_ACTIVATED) if id.is_empty() || id == ssid => {
+            ActivationProgress::Activated
+        }
+        Some(_) => ActivationProgress::Pending,
+    }
+}
+
+// * Classifies a failed activation from the Wi-Fi device's StateReason.
+pub fn activation_failure(reason: Option<u32>, ssid: &str, hidden: bool) -> NmError {
+    let message = if hidden {
+        format!(
+            "Hidden Wi-Fi network {} could not be found or activated",
+            ssid
+        )
+    } else {
+        format!("Wi-Fi network {} could not be activated", ssid)
+    };
+    match reason {
+        Some(
+            NM_DEVICE_STATE_REASON_NO_SECRETS
+            | NM_DEVICE_STATE_REASON_SUPPLICANT_DISCONNECT
+            | NM_DEVICE_STATE_REASON_SUPPLICANT_FAILED,
+        ) => NmError::AuthFailed(message),
+        Some(NM_DEVICE_STATE_REASON_SUPPLICANT_TIMEOUT) => NmError::Timeout(message),
+        Some(NM_DEVICE_STATE_REASON_SSID_NOT_FOUND) => NmError::NotFound(message),
+        _ => NmError::Other(message),
+    }
+}
+
+// * Calls that change settings or state; the flag lets NM ask the session's polkit agent for
+// * approval instead of refusing users who aren't authorized up front.
+async fn call_authorized<B, R>(proxy: &Proxy<'_>, method: &'static str, body: &B) -> zbus::Result<R>
+where
+    B: serde::Serialize + zvariant::DynamicType,
+    R: for<'d> zvariant::DynamicDeserialize<'d>,
+{
+    proxy
+        .call_with_flags(method, MethodFlags::AllowInteractiveAuth.into(), body)
+        .await?
+        .ok_or_else(|| zbus::Error::Failure(format!("{} sent no reply", method)))
+}
+
 #[derive(Clone)]
 pub struct NmDbusClient {
     conn: Connection,
@@ -280,6 +389,20 @@ impl NmDbusClient {
             .and_then(Self::value_bool)
     }
 
+    fn get_setting_i32(settings: &SettingsMap, section: &str, key: &str) -> Option<i32> {
+        settings
+            .get(section)
+            .and_then(|s| s.get(key))
+            .and_then(|v| i32::try_from(v).ok())
+    }
+
+    fn get_setting_u64(settings: &SettingsMap, section: &str, key: &str) -> Option<u64> {
+        settings
+            .get(section)
+            .and_then(|s| s.get(key))
+            .and_then(|v| u64::try_from(v).ok())
+    }
+
     fn resolve_parent_interface(
         parent_ref: &str,
         by_uuid: &HashMap<String, &DbusConnectionProfile>,
@@ -338,6 +461,23 @@ impl NmDbusClient {
         Ok(())
     }
 
+    pub async fn is_wwan_enabled(&self) -> Result<bool> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        Ok(nm.get_property("WwanEnabled").await?)
+    }
+
+    pub async fn set_wwan_enabled(&self, enabled: bool) -> Result<()> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        nm.set_property("WwanEnabled", &enabled).await?;
+        Ok(())
+    }
+
+    pub async fn get_version(&self) -> Result<String> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let version: String = nm.get_property("Version").await?;
+        Ok(version)
+    }
+
     pub async fn is_wifi_present(&self) -> Result<bool> {
         let nm = self.proxy(NM_PATH, NM_IFACE).await?;
         let enabled_hw: bool = nm.get_property("WirelessHardwareEnabled").await?;
@@ -346,7 +486,17 @@ impl NmDbusClient {
 
     pub async fn get_connectivity_state(&self) -> Result<u32> {
         let nm = self.proxy(NM_PATH, NM_IFACE).await?;
-        let state: u32 = nm.get_property("Connectivity").await.unwrap_or(NM_CONNECTIVITY_UNKNOWN);
+        let state: u32 = nm
+            .get_property("Connectivity")
+            .await
+            .unwrap_or(NM_CONNECTIVITY_UNKNOWN);
+        Ok(state)
+    }
+
+    // * Asks NM to re-run its connectivity probe now instead of on the next interval.
+    pub async fn check_connectivity(&self) -> Result<u32> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let state: u32 = nm.call("CheckConnectivity", &()).await?;
         Ok(state)
     }
 
@@ -419,7 +569,7 @@ impl NmDbusClient {
                 .proxy(device.path.as_str(), NM_WIFI_DEVICE_IFACE)
                 .await?;
             let opts: HashMap<String, OwnedValue> = HashMap::new();
-            let _: () = wifi.call("RequestScan", &(opts)).await?;
+            let _: () = call_authorized(&wifi, "RequestScan", &(opts)).await?;
         }
         Ok(())
     }
@@ -442,6 +592,7 @@ impl NmDbusClient {
                     continue;
                 }
 
+                let bssid: String = ap.get_property("HwAddress").await.unwrap_or_default();
                 let frequency: u32 = ap.get_property("Frequency").await.unwrap_or(0);
                 let strength: u8 = ap.get_property("Strength").await.unwrap_or(0);
                 let flags: u32 = ap.get_property("Flags").await.unwrap_or(0);
@@ -452,6 +603,7 @@ impl NmDbusClient {
                     path: ap_path.clone(),
                     device: device.path.clone(),
                     ssid,
+                    bssid,
                     frequency,
                     strength,
                     flags,
@@ -477,13 +629,24 @@ impl NmDbusClient {
         Ok(settings)
     }
 
+    // * Secrets are only returned to the profile owner or a polkit-authorized caller.
+    pub async fn get_connection_secrets(
+        &self,
+        path: &OwnedObjectPath,
+        setting: &str,
+    ) -> Result<SettingsMap> {
+        let conn = self.proxy(path.as_str(), NM_SETTINGS_CONN_IFACE).await?;
+        let secrets: SettingsMap = call_authorized(&conn, "GetSecrets", &(setting)).await?;
+        Ok(secrets)
+    }
+
     pub async fn update_connection_settings(
         &self,
         path: &OwnedObjectPath,
         settings: &SettingsMap,
     ) -> Result<()> {
         let conn = self.proxy(path.as_str(), NM_SETTINGS_CONN_IFACE).await?;
-        let _: () = conn.call("Update", &(settings)).await?;
+        let _: () = call_authorized(&conn, "Update", &(settings)).await?;
         Ok(())
     }
 
@@ -509,6 +672,10 @@ impl NmDbusClient {
             let interface_name =
                 Self::get_setting_string(&settings, "connection", "interface-name");
             let autoconnect = Self::get_setting_bool(&settings, "connection", "autoconnect");
+            let autoconnect_priority =
+                Self::get_setting_i32(&settings, "connection", "autoconnect-priority").unwrap_or(0);
+            let timestamp =
+                Self::get_setting_u64(&settings, "connection", "timestamp").unwrap_or(0);
             let zone = Self::get_setting_string(&settings, "connection", "zone");
 
             out.push(DbusConnectionProfile {
@@ -518,6 +685,8 @@ impl NmDbusClient {
                 conn_type,
                 interface_name,
                 autoconnect,
+                autoconnect_priority,
+                timestamp,
                 zone,
                 settings,
             });
@@ -582,7 +751,7 @@ impl NmDbusClient {
             devices
                 .into_iter()
                 .find(|d| d.interface == iface)
-                .ok_or_else(|| anyhow!("Network device {} not found", iface))?
+                .ok_or_else(|| NmError::NotFound(format!("Network device {} not found", iface)))?
         } else {
             // ! Filter loopback and virtual devices — NM sometimes returns lo first,
             // ! which causes "profile is not compatible with device" errors.
@@ -594,20 +763,19 @@ impl NmDbusClient {
             devices
                 .into_iter()
                 .find(|d| {
-                    d.state != NM_DEVICE_STATE_UNMANAGED
-                        && physical_types.contains(&d.device_type)
+                    d.state != NM_DEVICE_STATE_UNMANAGED && physical_types.contains(&d.device_type)
                 })
                 .ok_or_else(|| anyhow!("No managed physical NetworkManager device found"))?
         };
 
         let nm = self.proxy(NM_PATH, NM_IFACE).await?;
         let root = Self::root_path()?;
-        let active_path: OwnedObjectPath = nm
-            .call(
-                "ActivateConnection",
-                &(connection_path.clone(), device.path.clone(), root),
-            )
-            .await?;
+        let active_path: OwnedObjectPath = call_authorized(
+            &nm,
+            "ActivateConnection",
+            &(connection_path.clone(), device.path.clone(), root),
+        )
+        .await?;
         Ok(active_path)
     }
 
@@ -623,12 +791,12 @@ impl NmDbusClient {
         // * "/" signals NM to activate the VPN without binding to a specific device
         let no_device = OwnedObjectPath::try_from("/")
             .map_err(|e| anyhow!("Failed to build VPN device path: {}", e))?;
-        let active_path: OwnedObjectPath = nm
-            .call(
-                "ActivateConnection",
-                &(connection_path.clone(), no_device, root),
-            )
-            .await?;
+        let active_path: OwnedObjectPath = call_authorized(
+            &nm,
+            "ActivateConnection",
+            &(connection_path.clone(), no_device, root),
+        )
+        .await?;
         Ok(active_path)
     }
 
@@ -640,7 +808,7 @@ impl NmDbusClient {
         let connection = self
             .find_connection_by_id(id)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", id))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
         self.activate_connection_path(&connection.path, iface).await
     }
 
@@ -661,7 +829,9 @@ impl NmDbusClient {
         &self,
         active_path: &OwnedObjectPath,
     ) -> Result<()> {
-        let active = self.proxy(active_path.as_str(), NM_ACTIVE_CONN_IFACE).await?;
+        let active = self
+            .proxy(active_path.as_str(), NM_ACTIVE_CONN_IFACE)
+            .await?;
         let stream = active.receive_signal("StateChanged").await?;
 
         let current_state: u32 = active.get_property("State").await?;
@@ -699,61 +869,78 @@ impl NmDbusClient {
         let connection = self
             .find_connection_by_uuid(uuid)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", uuid))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", uuid)))?;
         self.activate_connection_path(&connection.path, iface).await
     }
 
     // * VPN-specific activation — always uses "/" as device path
-    pub async fn activate_vpn_connection_by_uuid(
-        &self,
-        uuid: &str,
-    ) -> Result<OwnedObjectPath> {
+    pub async fn activate_vpn_connection_by_uuid(&self, uuid: &str) -> Result<OwnedObjectPath> {
         let connection = self
             .find_connection_by_uuid(uuid)
             .await?
-            .ok_or_else(|| anyhow!("VPN connection {} not found", uuid))?;
+            .ok_or_else(|| NmError::NotFound(format!("VPN connection {} not found", uuid)))?;
         self.activate_vpn_connection_path(&connection.path).await
     }
 
     async fn wait_for_wifi_activation(
         &self,
         active_path: &OwnedObjectPath,
+        device_path: &OwnedObjectPath,
         ssid: &str,
         hidden: bool,
     ) -> Result<()> {
         let max_attempts = if hidden { 30 } else { 20 };
+        self.wait_for_wifi_activation_within(active_path, device_path, ssid, hidden, max_attempts)
+            .await
+    }
 
+    // * Polls every 350 ms; WPS needs far longer than a PSK handshake while the user walks to the router.
+    async fn wait_for_wifi_activation_within(
+        &self,
+        active_path: &OwnedObjectPath,
+        device_path: &OwnedObjectPath,
+        ssid: &str,
+        hidden: bool,
+        max_attempts: u32,
+    ) -> Result<()> {
         for _ in 0..max_attempts {
             if self.get_active_wifi_ssid().await?.as_deref() == Some(ssid) {
                 return Ok(());
             }
 
-            if let Ok(active) = self.proxy(active_path.as_str(), NM_ACTIVE_CONN_IFACE).await {
-                let state: u32 = active.get_property("State").await.unwrap_or(0);
-                let id: String = active.get_property("Id").await.unwrap_or_default();
-                if state == NM_ACTIVE_CONNECTION_STATE_ACTIVATED && (id.is_empty() || id == ssid) {
-                    return Ok(());
-                }
-                if matches!(
-                    state,
-                    NM_ACTIVE_CONNECTION_STATE_DEACTIVATING
-                        | NM_ACTIVE_CONNECTION_STATE_DEACTIVATED
-                ) {
-                    break;
+            let (state, id) = match self.proxy(active_path.as_str(), NM_ACTIVE_CONN_IFACE).await {
+                Ok(active) => (
+                    active.get_property::<u32>("State").await.ok(),
+                    active
+                        .get_property::<String>("Id")
+                        .await
+                        .unwrap_or_default(),
+                ),
+                Err(_) => (None, String::new()),
+            };
+            match activation_progress(state, &id, ssid) {
+                ActivationProgress::Activated => return Ok(()),
+                ActivationProgress::Failed => {
+                    let reason = self.device_state_reason(device_path).await;
+                    return Err(activation_failure(reason, ssid, hidden).into());
                 }
+                ActivationProgress::Pending => {}
             }
 
             sleep(Duration::from_millis(350)).await;
         }
 
-        if hidden {
-            Err(anyhow!(
-                "Hidden Wi-Fi network {} could not be found or activated",
-                ssid
-            ))
-        } else {
-            Err(anyhow!("Wi-Fi network {} could not be activated", ssid))
-        }
+        Err(NmError::Timeout(format!("Timed out activating Wi-Fi network {}", ssid)).into())
+    }
+
+    // * Why the device last changed state; NM keeps it after the failed activation is torn down.
+    async fn device_state_reason(&self, device_path: &OwnedObjectPath) -> Option<u32> {
+        let device = self
+            .proxy(device_path.as_str(), NM_DEVICE_IFACE)
+            .await
+            .ok()?;
+        let (_state, reason): (u32, u32) = device.get_property("StateReason").await.ok()?;
+        Some(reason)
     }
 
     pub async fn deactivate_connection_by_id(&self, id: &str) -> Result<()> {
@@ -762,7 +949,7 @@ impl NmDbusClient {
 
         for conn in active {
             if conn.id == id {
-                let _: () = nm.call("DeactivateConnection", &(conn.path)).await?;
+                let _: () = call_authorized(&nm, "DeactivateConnection", &(conn.path)).await?;
             }
         }
 
@@ -775,7 +962,7 @@ impl NmDbusClient {
 
         for conn in active {
             if conn.uuid == uuid {
-                let _: () = nm.call("DeactivateConnection", &(conn.path)).await?;
+                let _: () = call_authorized(&nm, "DeactivateConnection", &(conn.path)).await?;
             }
         }
 
@@ -787,7 +974,7 @@ impl NmDbusClient {
             let settings_conn = self
                 .proxy(conn.path.as_str(), NM_SETTINGS_CONN_IFACE)
                 .await?;
-            let _: () = settings_conn.call("Delete", &()).await?;
+            let _: () = call_authorized(&settings_conn, "Delete", &()).await?;
         }
         Ok(())
     }
@@ -797,14 +984,15 @@ impl NmDbusClient {
             let settings_conn = self
                 .proxy(conn.path.as_str(), NM_SETTINGS_CONN_IFACE)
                 .await?;
-            let _: () = settings_conn.call("Delete", &()).await?;
+            let _: () = call_authorized(&settings_conn, "Delete", &()).await?;
         }
         Ok(())
     }
 
     pub async fn add_connection(&self, settings: &SettingsMap) -> Result<OwnedObjectPath> {
         let settings_proxy = self.proxy(NM_SETTINGS_PATH, NM_SETTINGS_IFACE).await?;
-        let path: OwnedObjectPath = settings_proxy.call("AddConnection", &(settings)).await?;
+        let path: OwnedObjectPath =
+            call_authorized(&settings_proxy, "AddConnection", &(settings)).await?;
         Ok(path)
     }
 
@@ -855,6 +1043,11 @@ impl NmDbusClient {
         connection.insert("type".to_string(), Self::ov_str("802-11-wireless"));
         connection.insert("autoconnect".to_string(), false.into());
         connection.insert("interface-name".to_string(), Self::ov_str(iface));
+        let bridged = config.mode == HotspotMode::Bridged;
+        if bridged {
+            connection.insert("master".to_string(), Self::ov_str(HOTSPOT_BRIDGE_INTERFACE));
+            connection.insert("slave-type".to_string(), Self::ov_str("bridge"));
+        }
         settings.insert("connection".to_string(), connection);
 
         let mut wifi = HashMap::new();
@@ -869,19 +1062,53 @@ impl NmDbusClient {
             // * Persist numeric custom channel values when the UI provides one.
             wifi.insert("channel".to_string(), channel.into());
         }
+        // * Stops the driver bridging frames between clients; the nft rule covers routed traffic
+        if config.isolate_clients
+            && crate::nm::capabilities().supports(crate::nm::NmFeature::ApIsolation)
+        {
+            wifi.insert("ap-isolation".to_string(), 1i32.into());
+        }
         settings.insert("802-11-wireless".to_string(), wifi);
 
-        let mut ipv4 = HashMap::new();
-        ipv4.insert("method".to_string(), Self::ov_str("shared"));
-        settings.insert("ipv4".to_string(), ipv4);
+        // * Bridge ports carry no IP setup; bridged clients get addresses from the LAN's DHCP
+        if !bridged {
+            let mut ipv4 = HashMap::new();
+            ipv4.insert("method".to_string(), Self::ov_str("shared"));
+            if let Some(subnet) = config.subnet()? {
+                let mut address = HashMap::new();
+                address.insert(
+                    "address".to_string(),
+                    Self::ov_str(&subnet.gateway.to_string()),
+                );
+                address.insert("prefix".to_string(), u32::from(subnet.prefix).into());
+                ipv4.insert(
+                    "address-data".to_string(),
+                    OwnedValue::try_from(zvariant::Value::from(vec![address]))?,
+                );
+                if let Some((start, end)) = subnet.dhcp_range {
+                    // * NM hands this to the dnsmasq it runs for shared connections
+                    if crate::nm::capabilities().supports(crate::nm::NmFeature::SharedDhcpRange) {
+                        ipv4.insert(
+                            "shared-dhcp-range".to_string(),
+                            Self::ov_str(&format!("{},{}", start, end)),
+                        );
+                    } else {
+                        log::warn!("NetworkManager is too old for a custom hotspot DHCP range");
+                    }
+                }
+            }
+            settings.insert("ipv4".to_string(), ipv4);
 
-        let mut ipv6 = HashMap::new();
-        ipv6.insert("method".to_string(), Self::ov_str("disabled"));
-        settings.insert("ipv6".to_string(), ipv6);
+            let mut ipv6 = HashMap::new();
+            ipv6.insert("method".to_string(), Self::ov_str("disabled"));
+            settings.insert("ipv6".to_string(), ipv6);
+        }
 
         if !config.password.is_empty() {
+            let (key_mgmt, pmf) = hotspot_security_for_nm(config.security);
             let mut sec = HashMap::new();
-            sec.insert("key-mgmt".to_string(), Self::ov_str("wpa-psk"));
+            sec.insert("key-mgmt".to_string(), Self::ov_str(key_mgmt));
+            sec.insert("pmf".to_string(), pmf.into());
             sec.insert("psk".to_string(), Self::ov_str(&config.password));
             settings.insert("802-11-wireless-security".to_string(), sec);
         }
@@ -893,11 +1120,156 @@ impl NmDbusClient {
         }
 
         let settings_proxy = self.proxy(NM_SETTINGS_PATH, NM_SETTINGS_IFACE).await?;
-        let _: OwnedObjectPath = settings_proxy.call("AddConnection", &(settings)).await?;
+        let _: OwnedObjectPath =
+            call_authorized(&settings_proxy, "AddConnection", &(settings)).await?;
+
+        Ok(())
+    }
+
+    // * Creates or refreshes the bridge a bridged hotspot joins, with `uplink` as its wired port.
+    pub async fn upsert_hotspot_bridge(&self, uplink: &str) -> Result<()> {
+        let mut bridge_settings: SettingsMap = HashMap::new();
+        let mut connection = HashMap::new();
+        connection.insert("id".to_string(), Self::ov_str(HOTSPOT_BRIDGE_ID));
+        connection.insert("type".to_string(), Self::ov_str("bridge"));
+        connection.insert("autoconnect".to_string(), false.into());
+        connection.insert(
+            "interface-name".to_string(),
+            Self::ov_str(HOTSPOT_BRIDGE_INTERFACE),
+        );
+        bridge_settings.insert("connection".to_string(), connection);
+        let mut bridge = HashMap::new();
+        // * STP would hold the ports in listening state for ~30s on every start
+        bridge.insert("stp".to_string(), false.into());
+        bridge_settings.insert("bridge".to_string(), bridge);
+        // * The host keeps its LAN address, now on the bridge instead of the wired port
+        for family in ["ipv4", "ipv6"] {
+            let mut ip = HashMap::new();
+            ip.insert("method".to_string(), Self::ov_str("auto"));
+            bridge_settings.insert(family.to_string(), ip);
+        }
+        self.upsert_connection_by_id(HOTSPOT_BRIDGE_ID, &bridge_settings)
+            .await?;
+
+        let mut uplink_settings: SettingsMap = HashMap::new();
+        let mut connection = HashMap::new();
+        connection.insert("id".to_string(), Self::ov_str(HOTSPOT_UPLINK_ID));
+        connection.insert("type".to_string(), Self::ov_str("802-3-ethernet"));
+        connection.insert("autoconnect".to_string(), false.into());
+        connection.insert("interface-name".to_string(), Self::ov_str(uplink));
+        connection.insert("master".to_string(), Self::ov_str(HOTSPOT_BRIDGE_INTERFACE));
+        connection.insert("slave-type".to_string(), Self::ov_str("bridge"));
+        uplink_settings.insert("connection".to_string(), connection);
+        uplink_settings.insert("802-3-ethernet".to_string(), HashMap::new());
+        self.upsert_connection_by_id(HOTSPOT_UPLINK_ID, &uplink_settings)
+            .await
+    }
+
+    // * Tears the bridge down and hands the wired port back to its usual profile.
+    pub async fn remove_hotspot_bridge(&self) -> Result<()> {
+        let Some(uplink) = self.find_connection_by_id(HOTSPOT_UPLINK_ID).await? else {
+            return Ok(());
+        };
+        self.deactivate_connection_by_id(HOTSPOT_BRIDGE_ID).await?;
+        self.delete_connection_by_id(HOTSPOT_UPLINK_ID).await?;
+        self.delete_connection_by_id(HOTSPOT_BRIDGE_ID).await?;
+        if let Some(iface) = uplink.interface_name.as_deref() {
+            if let Err(e) = self.connect_device(iface).await {
+                log::warn!("Failed to reconnect {} after bridged hotspot: {}", iface, e);
+            }
+        }
+        Ok(())
+    }
 
+    async fn upsert_connection_by_id(&self, id: &str, settings: &SettingsMap) -> Result<()> {
+        if let Some(existing) = self.find_connection_by_id(id).await? {
+            return self
+                .update_connection_settings(&existing.path, settings)
+                .await;
+        }
+        self.add_connection(settings).await?;
         Ok(())
     }
 
+    // * Saves a Wi-Fi profile without activating it; returns true when an existing one was replaced.
+    pub async fn save_wifi_connection(
+        &self,
+        ssid: &str,
+        psk: Option<&str>,
+        hidden: bool,
+        priority: Option<i32>,
+    ) -> Result<bool> {
+        // * Overwriting keeps the profile's uuid, name and every key the import doesn't set
+        let existing = self.find_wifi_connection_by_ssid(ssid).await?;
+        let mut settings = match &existing {
+            Some(existing) => Self::clone_settings_map(&existing.settings)?,
+            None => {
+                let mut settings: SettingsMap = HashMap::new();
+                let connection = Self::connection_section_mut(&mut settings, "connection");
+                connection.insert("id".to_string(), Self::ov_str(ssid));
+                connection.insert("type".to_string(), Self::ov_str("802-11-wireless"));
+                connection.insert("autoconnect".to_string(), true.into());
+                settings
+            }
+        };
+
+        if let Some(priority) = priority {
+            Self::connection_section_mut(&mut settings, "connection")
+                .insert("autoconnect-priority".to_string(), priority.into());
+        }
+
+        let wifi = Self::connection_section_mut(&mut settings, "802-11-wireless");
+        wifi.insert("ssid".to_string(), Self::ov_bytes(ssid.as_bytes())?);
+        wifi.insert("mode".to_string(), Self::ov_str("infrastructure"));
+        if hidden {
+            wifi.insert("hidden".to_string(), true.into());
+        }
+
+        match psk {
+            Some(psk) => {
+                // ? NM accepts either an 8-63 character passphrase or the 64-hex derived key here
+                let sec = Self::connection_section_mut(&mut settings, "802-11-wireless-security");
+                sec.insert("key-mgmt".to_string(), Self::ov_str("wpa-psk"));
+                sec.insert("psk".to_string(), Self::ov_str(psk));
+            }
+            None => {
+                settings.remove("802-11-wireless-security");
+            }
+        }
+
+        if let Some(existing) = existing {
+            self.update_connection_settings(&existing.path, &settings)
+                .await?;
+            return Ok(true);
+        }
+
+        self.add_connection(&settings).await?;
+        Ok(false)
+    }
+
+    // * Matches on the SSID bytes; the profile id is only a label and may have been renamed.
+    pub async fn find_wifi_connection_by_ssid(
+        &self,
+        ssid: &str,
+    ) -> Result<Option<DbusConnectionProfile>> {
+        Ok(self
+            .list_connections()
+            .await?
+            .into_iter()
+            .find(|c| Self::wifi_profile_ssid(c).as_deref() == Some(ssid)))
+    }
+
+    pub fn wifi_profile_ssid(profile: &DbusConnectionProfile) -> Option<String> {
+        // * Hotspot profiles are access points, not networks to join
+        if profile.conn_type != "802-11-wireless"
+            || Self::get_setting_string(&profile.settings, "802-11-wireless", "mode").as_deref()
+                == Some("ap")
+        {
+            return None;
+        }
+        Self::get_setting_string(&profile.settings, "802-11-wireless", "ssid")
+    }
+
     pub async fn add_and_activate_wifi_connection(
         &self,
         ssid: &str,
@@ -910,7 +1282,7 @@ impl NmDbusClient {
             .await?
             .into_iter()
             .next()
-            .ok_or_else(|| anyhow!("No Wi-Fi device available"))?;
+            .ok_or_else(|| NmError::NotFound("No Wi-Fi device available".to_string()))?;
         let existing_connection = self.find_connection_by_id(ssid).await?;
 
         let mut settings: SettingsMap = HashMap::new();
@@ -941,21 +1313,26 @@ impl NmDbusClient {
                 sec.insert("psk".to_string(), Self::ov_str(password));
             }
             settings.insert("802-11-wireless-security".to_string(), sec);
+        } else if key_mgmt == Some("owe") {
+            // * Enhanced Open: encrypted like WPA, but there is no secret to send.
+            let mut sec = HashMap::new();
+            sec.insert("key-mgmt".to_string(), Self::ov_str("owe"));
+            settings.insert("802-11-wireless-security".to_string(), sec);
         }
 
         let nm = self.proxy(NM_PATH, NM_IFACE).await?;
         let root = Self::root_path()?;
 
-        let add_result: Result<(OwnedObjectPath, OwnedObjectPath), zbus::Error> = nm
-            .call(
-                "AddAndActivateConnection",
-                &(settings, device.path.clone(), root.clone()),
-            )
-            .await;
+        let add_result: Result<(OwnedObjectPath, OwnedObjectPath), zbus::Error> = call_authorized(
+            &nm,
+            "AddAndActivateConnection",
+            &(settings, device.path.clone(), root.clone()),
+        )
+        .await;
 
         if let Ok((_, active_path)) = add_result {
             if let Err(e) = self
-                .wait_for_wifi_activation(&active_path, ssid, hidden)
+                .wait_for_wifi_activation(&active_path, &device.path, ssid, hidden)
                 .await
             {
                 if existing_connection.is_none() {
@@ -972,7 +1349,7 @@ impl NmDbusClient {
             let active_path = self
                 .activate_connection_path(&existing.path, Some(&device.interface))
                 .await?;
-            self.wait_for_wifi_activation(&active_path, ssid, hidden)
+            self.wait_for_wifi_activation(&active_path, &device.path, ssid, hidden)
                 .await?;
             return Ok(());
         }
@@ -980,10 +1357,138 @@ impl NmDbusClient {
         Err(anyhow!("Failed to activate Wi-Fi connection {}", ssid))
     }
 
+    // * No PSK in the profile: NM starts WPS push-button on the AP and stores the key it receives.
+    pub async fn add_and_activate_wps_connection(&self, ssid: &str) -> Result<()> {
+        let mut settings: SettingsMap = HashMap::new();
+
+        let mut connection = HashMap::new();
+        connection.insert("id".to_string(), Self::ov_str(ssid));
+        connection.insert("type".to_string(), Self::ov_str("802-11-wireless"));
+        connection.insert("autoconnect".to_string(), true.into());
+        settings.insert("connection".to_string(), connection);
+
+        let mut wifi = HashMap::new();
+        wifi.insert("ssid".to_string(), Self::ov_bytes(ssid.as_bytes())?);
+        wifi.insert("mode".to_string(), Self::ov_str("infrastructure"));
+        settings.insert("802-11-wireless".to_string(), wifi);
+
+        let mut sec = HashMap::new();
+        sec.insert("key-mgmt".to_string(), Self::ov_str("wpa-psk"));
+        sec.insert("wps-method".to_string(), NM_WPS_METHOD_PBC.into());
+        settings.insert("802-11-wireless-security".to_string(), sec);
+
+        self.add_and_activate_wifi_settings(ssid, settings, false, WPS_ACTIVATION_ATTEMPTS)
+            .await
+    }
+
+    pub async fn add_and_activate_enterprise_connection(
+        &self,
+        ssid: &str,
+        settings: SettingsMap,
+        hidden: bool,
+    ) -> Result<()> {
+        self.add_and_activate_wifi_settings(ssid, settings, hidden, EAP_ACTIVATION_ATTEMPTS)
+            .await
+    }
+
+    // * Activates a profile built by the caller; a new profile is removed again if activation fails.
+    async fn add_and_activate_wifi_settings(
+        &self,
+        ssid: &str,
+        settings: SettingsMap,
+        hidden: bool,
+        max_attempts: u32,
+    ) -> Result<()> {
+        let device = self
+            .get_wifi_devices()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| NmError::NotFound("No Wi-Fi device available".to_string()))?;
+        let existing_connection = self.find_connection_by_id(ssid).await?;
+
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let root = Self::root_path()?;
+        let (_, active_path): (OwnedObjectPath, OwnedObjectPath) = call_authorized(
+            &nm,
+            "AddAndActivateConnection",
+            &(settings, device.path.clone(), root),
+        )
+        .await?;
+
+        if let Err(e) = self
+            .wait_for_wifi_activation_within(&active_path, &device.path, ssid, hidden, max_attempts)
+            .await
+        {
+            if existing_connection.is_none() {
+                let _ = self.delete_connection_by_id(ssid).await;
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
     pub async fn disconnect_connection_by_id(&self, id: &str) -> Result<()> {
         self.deactivate_connection_by_id(id).await
     }
 
+    // * Like `nmcli device connect`: NM picks the best saved profile, or creates a default one.
+    pub async fn connect_device(&self, iface: &str) -> Result<OwnedObjectPath> {
+        let device = self
+            .list_devices()
+            .await?
+            .into_iter()
+            .find(|d| d.interface == iface)
+            .ok_or_else(|| NmError::NotFound(format!("Network device {} not found", iface)))?;
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let root = Self::root_path()?;
+
+        let activate_result: Result<OwnedObjectPath, zbus::Error> = call_authorized(
+            &nm,
+            "ActivateConnection",
+            &(root.clone(), device.path.clone(), root.clone()),
+        )
+        .await;
+        match activate_result {
+            Ok(active_path) => Ok(active_path),
+            Err(e) => {
+                log::debug!("No saved profile for {} ({}), creating one", iface, e);
+                let (_, active_path): (OwnedObjectPath, OwnedObjectPath) = call_authorized(
+                    &nm,
+                    "AddAndActivateConnection",
+                    &(SettingsMap::new(), device.path, root),
+                )
+                .await?;
+                Ok(active_path)
+            }
+        }
+    }
+
+    // * An unmanaged device is left alone by NM until it is handed back.
+    pub async fn set_device_managed(&self, iface: &str, managed: bool) -> Result<()> {
+        let device = self
+            .list_devices()
+            .await?
+            .into_iter()
+            .find(|d| d.interface == iface)
+            .ok_or_else(|| NmError::NotFound(format!("Network device {} not found", iface)))?;
+        let proxy = self.proxy(device.path.as_str(), NM_DEVICE_IFACE).await?;
+        proxy.set_property("Managed", &managed).await?;
+        Ok(())
+    }
+
+    pub async fn disconnect_device(&self, iface: &str) -> Result<()> {
+        let device = self
+            .list_devices()
+            .await?
+            .into_iter()
+            .find(|d| d.interface == iface)
+            .ok_or_else(|| NmError::NotFound(format!("Network device {} not found", iface)))?;
+        let proxy = self.proxy(device.path.as_str(), NM_DEVICE_IFACE).await?;
+        let _: () = call_authorized(&proxy, "Disconnect", &()).await?;
+        Ok(())
+    }
+
     pub async fn set_connection_autoconnect_by_uuid(
         &self,
         uuid: &str,
@@ -992,7 +1497,7 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_uuid(uuid)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", uuid))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", uuid)))?;
 
         let mut settings = Self::clone_settings_map(&conn.settings)?;
         Self::connection_section_mut(&mut settings, "connection")
@@ -1004,7 +1509,7 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_id(id)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", id))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
 
         let mut settings = Self::clone_settings_map(&conn.settings)?;
         Self::connection_section_mut(&mut settings, "connection")
@@ -1016,7 +1521,7 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_id(id)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", id))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
         Ok(conn.autoconnect.unwrap_or(false))
     }
 
@@ -1024,7 +1529,19 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_uuid(uuid)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", uuid))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", uuid)))?;
+
+        let mut settings = Self::clone_settings_map(&conn.settings)?;
+        Self::connection_section_mut(&mut settings, "connection")
+            .insert("zone".to_string(), Self::ov_str(zone));
+        self.update_connection_settings(&conn.path, &settings).await
+    }
+
+    pub async fn set_connection_zone_by_id(&self, id: &str, zone: &str) -> Result<()> {
+        let conn = self
+            .find_connection_by_id(id)
+            .await?
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
 
         let mut settings = Self::clone_settings_map(&conn.settings)?;
         Self::connection_section_mut(&mut settings, "connection")
@@ -1041,7 +1558,7 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_id(id)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", id))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
 
         let mut settings = Self::clone_settings_map(&conn.settings)?;
         let ipv4 = Self::connection_section_mut(&mut settings, "ipv4");
@@ -1072,7 +1589,7 @@ impl NmDbusClient {
         let conn = self
             .find_connection_by_id(id)
             .await?
-            .ok_or_else(|| anyhow!("Connection {} not found", id))?;
+            .ok_or_else(|| NmError::NotFound(format!("Connection {} not found", id)))?;
 
         let iface = conn.interface_name.clone();
         self.deactivate_connection_by_uuid(&conn.uuid).await?;
@@ -1081,6 +1598,37 @@ impl NmDbusClient {
         Ok(())
     }
 
+    // * An empty device list snapshots every device; NM rolls back by itself once the timeout passes.
+    pub async fn checkpoint_create(&self, rollback_timeout_secs: u32) -> Result<OwnedObjectPath> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let devices: Vec<OwnedObjectPath> = Vec::new();
+        let checkpoint: OwnedObjectPath = call_authorized(
+            &nm,
+            "CheckpointCreate",
+            &(devices, rollback_timeout_secs, 0u32),
+        )
+        .await?;
+        Ok(checkpoint)
+    }
+
+    pub async fn checkpoint_destroy(&self, checkpoint: &OwnedObjectPath) -> Result<()> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let _: () = call_authorized(&nm, "CheckpointDestroy", &(checkpoint,)).await?;
+        Ok(())
+    }
+
+    pub async fn checkpoint_rollback(&self, checkpoint: &OwnedObjectPath) -> Result<()> {
+        let nm = self.proxy(NM_PATH, NM_IFACE).await?;
+        let results: HashMap<String, u32> =
+            call_authorized(&nm, "CheckpointRollback", &(checkpoint,)).await?;
+        for (device, result) in results {
+            if result != NM_ROLLBACK_RESULT_OK {
+                log::warn!("Rollback of {} finished with result {}", device, result);
+            }
+        }
+        Ok(())
+    }
+
     pub async fn get_ip4_info(&self, device_path: &OwnedObjectPath) -> Result<DbusIp4Info> {
         let dev = self.proxy(device_path.as_str(), NM_DEVICE_IFACE).await?;
         let ip4_path: OwnedObjectPath = dev.get_property("Ip4Config").await?;
@@ -1099,6 +1647,9 @@ impl NmDbusClient {
         for item in address_data {
             if let Some(value) = item.get("address").and_then(Self::value_string) {
                 if !value.is_empty() {
+                    if out.addresses.is_empty() {
+                        out.prefix = item.get("prefix").and_then(|p| u32::try_from(p).ok());
+                    }
                     out.addresses.push(value);
                 }
             }
@@ -1148,15 +1699,7 @@ impl NmDbusClient {
 
     pub async fn get_active_wired_connection(&self) -> Result<Option<String>> {
         let active = self.list_active_connections().await?;
-        for conn in active {
-            if (conn.conn_type == "802-3-ethernet" || conn.conn_type == "ethernet")
-                && conn.state == NM_ACTIVE_CONNECTION_STATE_ACTIVATED
-                && !conn.id.is_empty()
-            {
-                return Ok(Some(conn.id));
-            }
-        }
-        Ok(None)
+        Ok(select_active_wired_connection(&active))
     }
 
     pub async fn get_active_connection_name(&self) -> Result<Option<String>> {
@@ -1229,8 +1772,14 @@ impl NmDbusClient {
         Option<DbusDevice>,
         DbusIp4Info,
     )> {
-        let profile = self.find_connection_by_id(id).await?;
-        let active = self.list_active_connections().await?.into_iter().find(|c| {
+        let started = Instant::now();
+        // * Independent lookups; the details dialog waits on all of them
+        let (profile, active, devices) = tokio::try_join!(
+            self.find_connection_by_id(id),
+            self.list_active_connections(),
+            self.list_devices(),
+        )?;
+        let active = active.into_iter().find(|c| {
             c.id == id
                 || c.uuid
                     == profile
@@ -1239,18 +1788,10 @@ impl NmDbusClient {
                         .unwrap_or_default()
         });
 
-        let device = if let Some(active) = &active {
-            if let Some(path) = active.devices.first() {
-                self.list_devices()
-                    .await?
-                    .into_iter()
-                    .find(|d| d.path == *path)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let device = active
+            .as_ref()
+            .and_then(|active| active.devices.first())
+            .and_then(|path| devices.into_iter().find(|d| d.path == *path));
 
         let ip4_info = if let Some(device) = &device {
             self.get_ip4_info(&device.path).await.unwrap_or_default()
@@ -1258,9 +1799,51 @@ impl NmDbusClient {
             DbusIp4Info::default()
         };
 
+        log::debug!(
+            "Network info for {}: {} ms",
+            id,
+            started.elapsed().as_millis()
+        );
         Ok((profile, active, device, ip4_info))
     }
 
+    // * Keyed by the adapter rather than the profile name, so two adapters on one SSID stay apart.
+    pub async fn get_network_info_by_device(
+        &self,
+        device_path: &str,
+    ) -> Result<(
+        Option<DbusConnectionProfile>,
+        Option<DbusActiveConnection>,
+        Option<DbusDevice>,
+        DbusIp4Info,
+    )> {
+        let started = Instant::now();
+        let (devices, active) =
+            tokio::try_join!(self.list_devices(), self.list_active_connections())?;
+        let device = devices
+            .into_iter()
+            .find(|d| d.path.as_str() == device_path)
+            .ok_or_else(|| {
+                NmError::NotFound(format!("Network device {} not found", device_path))
+            })?;
+        let active = device
+            .active_connection
+            .as_ref()
+            .and_then(|path| active.into_iter().find(|c| c.path == *path));
+        let profile = match &active {
+            Some(active) => self.find_connection_by_uuid(&active.uuid).await?,
+            None => None,
+        };
+        let ip4_info = self.get_ip4_info(&device.path).await.unwrap_or_default();
+
+        log::debug!(
+            "Network info for {}: {} ms",
+            device.interface,
+            started.elapsed().as_millis()
+        );
+        Ok((profile, active, Some(device), ip4_info))
+    }
+
     pub async fn set_ethernet_enabled(&self, enabled: bool) -> Result<()> {
         let devices = self.get_ethernet_devices().await?;
         let nm = self.proxy(NM_PATH, NM_IFACE).await?;
@@ -1292,15 +1875,13 @@ impl NmDbusClient {
 
                 if let Some(conn) = candidate {
                     used_connection_paths.insert(conn.path.to_string());
-                    let _: OwnedObjectPath = nm
-                        .call(
-                            "ActivateConnection",
-                            &(conn.path.clone(), dev.path.clone(), root.clone()),
-                        )
-                        .await
-                        .with_context(|| {
-                            format!("Failed to activate ethernet on {}", dev.interface)
-                        })?;
+                    let _: OwnedObjectPath = call_authorized(
+                        &nm,
+                        "ActivateConnection",
+                        &(conn.path.clone(), dev.path.clone(), root.clone()),
+                    )
+                    .await
+                    .with_context(|| format!("Failed to activate ethernet on {}", dev.interface))?;
                 }
             } else if let Some(active_path) = dev.active_connection {
                 if dev.interface.contains('.') || dev.interface.contains(':') {
@@ -1317,7 +1898,8 @@ impl NmDbusClient {
                     .map(|conn| conn.conn_type == "802-3-ethernet")
                     .unwrap_or(false);
                 if should_deactivate {
-                    let _: () = nm.call("DeactivateConnection", &(active_path)).await?;
+                    let _: () =
+                        call_authorized(&nm, "DeactivateConnection", &(active_path)).await?;
                 }
             }
         }
@@ -1340,11 +1922,12 @@ impl NmDbusClient {
     ) -> Result<()> {
         let nm = self.nm_proxy.clone();
         tokio::spawn(async move {
-            let Ok(stream) = nm.receive_signal("StateChanged").await else { return };
+            let Ok(stream) = nm.receive_signal("StateChanged").await else {
+                return;
+            };
             tokio::pin!(stream);
             loop {
-                let msg: Option<Message> =
-                    poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+                let msg: Option<Message> = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
                 let Some(msg) = msg else { break };
                 if let Ok(state) = msg.body().deserialize::<u32>() {
                     log::debug!("NM StateChanged: {}", state);
@@ -1355,11 +1938,12 @@ impl NmDbusClient {
 
         let nm = self.nm_proxy.clone();
         tokio::spawn(async move {
-            let Ok(stream) = nm.receive_signal("DeviceAdded").await else { return };
+            let Ok(stream) = nm.receive_signal("DeviceAdded").await else {
+                return;
+            };
             tokio::pin!(stream);
             loop {
-                let msg: Option<Message> =
-                    poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+                let msg: Option<Message> = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
                 let Some(msg) = msg else { break };
                 if let Ok(path) = msg.body().deserialize::<OwnedObjectPath>() {
                     log::debug!("NM DeviceAdded: {}", path);
@@ -1370,11 +1954,12 @@ impl NmDbusClient {
 
         let nm = self.nm_proxy.clone();
         tokio::spawn(async move {
-            let Ok(stream) = nm.receive_signal("DeviceRemoved").await else { return };
+            let Ok(stream) = nm.receive_signal("DeviceRemoved").await else {
+                return;
+            };
             tokio::pin!(stream);
             loop {
-                let msg: Option<Message> =
-                    poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+                let msg: Option<Message> = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
                 let Some(msg) = msg else { break };
                 if let Ok(path) = msg.body().deserialize::<OwnedObjectPath>() {
                     log::debug!("NM DeviceRemoved: {}", path);
@@ -1385,4 +1970,150 @@ impl NmDbusClient {
 
         Ok(())
     }
+
+    // * One match rule covers every Wi-Fi device, including adapters plugged in later.
+    pub async fn spawn_access_point_listener(&self, ap_tx: watch::Sender<()>) -> Result<()> {
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(NM_SERVICE)?
+            .interface(NM_WIFI_DEVICE_IFACE)?
+            .build();
+        let stream = MessageStream::for_match_rule(rule, &self.conn, None).await?;
+        tokio::spawn(async move {
+            tokio::pin!(stream);
+            loop {
+                let msg = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+                let Some(msg) = msg else { break };
+                let Ok(msg) = msg else { continue };
+                let header = msg.header();
+                let member = header.member().map(|member| member.as_str());
+                if matches!(member, Some("AccessPointAdded" | "AccessPointRemoved")) {
+                    log::debug!("NM {:?}", member);
+                    let _ = ap_tx.send(());
+                }
+            }
+        });
+        Ok(())
+    }
+
+    // * Also forwards the Connectivity property so the header reacts as soon as NM's check finishes.
+    pub async fn spawn_active_connection_listener(
+        &self,
+        active_tx: watch::Sender<()>,
+        connectivity_tx: watch::Sender<u32>,
+    ) -> Result<()> {
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(NM_SERVICE)?
+            .path(NM_PATH)?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .build();
+        let stream = MessageStream::for_match_rule(rule, &self.conn, None).await?;
+        tokio::spawn(async move {
+            tokio::pin!(stream);
+            loop {
+                let msg = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+                let Some(msg) = msg else { break };
+                let Ok(msg) = msg else { continue };
+                let Ok((iface, changed, _)) =
+                    msg.body()
+                        .deserialize::<(String, HashMap<String, OwnedValue>, Vec<String>)>()
+                else {
+                    continue;
+                };
+                if iface != NM_IFACE {
+                    continue;
+                }
+                if changed.contains_key("ActiveConnections")
+                    || changed.contains_key("PrimaryConnection")
+                {
+                    log::debug!("NM active connections changed");
+                    let _ = active_tx.send(());
+                }
+                if let Some(state) = changed
+                    .get("Connectivity")
+                    .and_then(|value| u32::try_from(value).ok())
+                {
+                    log::debug!("NM connectivity changed: {}", state);
+                    let _ = connectivity_tx.send(state);
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+// * Pure selection so the header status logic can be tested without a bus.
+// * Names come straight from D-Bus, so colons/backslashes are kept verbatim.
+pub fn select_active_wired_connection(active: &[DbusActiveConnection]) -> Option<String> {
+    active
+        .iter()
+        .filter(|conn| conn.conn_type == "802-3-ethernet" || conn.conn_type == "ethernet")
+        .filter(|conn| conn.state == NM_ACTIVE_CONNECTION_STATE_ACTIVATED)
+        .find(|conn| !conn.id.trim().is_empty())
+        .map(|conn| conn.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_connection(id: &str, conn_type: &str, state: u32) -> DbusActiveConnection {
+        DbusActiveConnection {
+            path: OwnedObjectPath::try_from("/org/freedesktop/NetworkManager/ActiveConnection/1")
+                .unwrap(),
+            id: id.to_string(),
+            uuid: String::new(),
+            conn_type: conn_type.to_string(),
+            state,
+            devices: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_wired_connection_names_with_colons_and_backslashes() {
+        let active = vec![active_connection(
+            r"Office: Dock\Port 2",
+            "802-3-ethernet",
+            NM_ACTIVE_CONNECTION_STATE_ACTIVATED,
+        )];
+
+        assert_eq!(
+            select_active_wired_connection(&active).as_deref(),
+            Some(r"Office: Dock\Port 2")
+        );
+    }
+
+    #[test]
+    fn ignores_wired_connections_that_are_not_fully_activated() {
+        let active = vec![
+            active_connection(
+                "Dock",
+                "802-3-ethernet",
+                NM_ACTIVE_CONNECTION_STATE_ACTIVATING,
+            ),
+            active_connection("Desk", "ethernet", NM_ACTIVE_CONNECTION_STATE_DEACTIVATING),
+            active_connection(
+                "Home",
+                "802-11-wireless",
+                NM_ACTIVE_CONNECTION_STATE_ACTIVATED,
+            ),
+        ];
+
+        assert_eq!(select_active_wired_connection(&active), None);
+    }
+
+    #[test]
+    fn skips_wired_connections_with_empty_names() {
+        let active = vec![
+            active_connection("  ", "802-3-ethernet", NM_ACTIVE_CONNECTION_STATE_ACTIVATED),
+            active_connection("Wired", "ethernet", NM_ACTIVE_CONNECTION_STATE_ACTIVATED),
+        ];
+
+        assert_eq!(
+            select_active_wired_connection(&active).as_deref(),
+            Some("Wired")
+        );
+    }
 }
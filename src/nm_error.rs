@@ -0,0 +1,197 @@
+// * ./src/nm_error.rs
+
+use std::fmt;
+use zbus::DBusError;
+
+// * What nmcli prints when polkit refuses the action, e.g. "Not authorized to control networking."
+const POLKIT_DENIAL_MARKERS: &[&str] = &["not authorized", "insufficient privileges"];
+
+// * Failure categories the pages act on; the text is kept for logs and the "Other" fallback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NmError {
+    AuthFailed(String),
+    NotFound(String),
+    Timeout(String),
+    PermissionDenied(String),
+    RadioOff,
+    // * The user aborted the operation from the UI
+    Cancelled,
+    // * NetworkManager isn't running or the system bus can't be reached
+    Unavailable(String),
+    Other(String),
+}
+
+impl NmError {
+    // * Works on any error from nm/nm_dbus: typed errors first, then D-Bus error names, never message text.
+    pub fn classify(error: &anyhow::Error) -> NmError {
+        for cause in error.chain() {
+            if let Some(nm_error) = cause.downcast_ref::<NmError>() {
+                return nm_error.clone();
+            }
+            if let Some(kind) = cause
+                .downcast_ref::<zbus::Error>()
+                .and_then(from_zbus_error)
+            {
+                return kind;
+            }
+        }
+        NmError::Other(error.to_string())
+    }
+
+    pub fn is_not_found(error: &anyhow::Error) -> bool {
+        matches!(Self::classify(error), NmError::NotFound(_))
+    }
+
+    // * NetworkManager itself is unreachable; pages show one shared toast instead of the raw error.
+    pub fn is_unavailable(error: &anyhow::Error) -> bool {
+        matches!(Self::classify(error), NmError::Unavailable(_))
+    }
+
+    pub fn is_permission_denied(error: &anyhow::Error) -> bool {
+        matches!(Self::classify(error), NmError::PermissionDenied(_))
+    }
+
+    // * For command-line tools, whose stderr is all there is; polkit denials get their own kind.
+    pub fn from_command_stderr(stderr: &str) -> NmError {
+        let lower = stderr.to_ascii_lowercase();
+        if POLKIT_DENIAL_MARKERS
+            .iter()
+            .any(|marker| lower.contains(marker))
+        {
+            NmError::PermissionDenied(stderr.to_string())
+        } else {
+            NmError::Other(stderr.to_string())
+        }
+    }
+
+    pub fn user_message(&self) -> String {
+        match self {
+            NmError::AuthFailed(_) => "Wrong password or missing credentials".to_string(),
+            NmError::NotFound(_) => "Network or connection not found".to_string(),
+            NmError::Timeout(_) => "NetworkManager did not respond in time".to_string(),
+            NmError::PermissionDenied(_) => "Not allowed by system policy".to_string(),
+            NmError::RadioOff => "Wi-Fi is turned off".to_string(),
+            NmError::Cancelled => "Cancelled".to_string(),
+            NmError::Unavailable(_) => "NetworkManager is not available".to_string(),
+            NmError::Other(message) => message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for NmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NmError::RadioOff => write!(f, "Wi-Fi is disabled"),
+            NmError::Cancelled => write!(f, "Operation cancelled"),
+            NmError::AuthFailed(message)
+            | NmError::NotFound(message)
+            | NmError::Timeout(message)
+            | NmError::PermissionDenied(message)
+            | NmError::Unavailable(message)
+            | NmError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for NmError {}
+
+fn from_zbus_error(error: &zbus::Error) -> Option<NmError> {
+    match error {
+        zbus::Error::MethodError(name, detail, _) => {
+            from_dbus_error_name(name.as_str(), detail.as_deref().unwrap_or(name.as_str()))
+        }
+        zbus::Error::FDO(fdo) => from_dbus_error_name(fdo.name().as_str(), &fdo.to_string()),
+        _ => None,
+    }
+}
+
+fn from_dbus_error_name(name: &str, detail: &str) -> Option<NmError> {
+    let detail = detail.to_string();
+    let kind = match name {
+        "org.freedesktop.NetworkManager.UnknownConnection"
+        | "org.freedesktop.NetworkManager.UnknownDevice"
+        | "org.freedesktop.DBus.Error.UnknownObject" => NmError::NotFound(detail),
+        "org.freedesktop.NetworkManager.PermissionDenied"
+        | "org.freedesktop.NetworkManager.Settings.PermissionDenied"
+        | "org.freedesktop.NetworkManager.Settings.Connection.PermissionDenied"
+        | "org.freedesktop.NetworkManager.Device.PermissionDenied"
+        | "org.freedesktop.DBus.Error.AccessDenied"
+        | "org.freedesktop.DBus.Error.AuthFailed"
+        | "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired" => {
+            NmError::PermissionDenied(detail)
+        }
+        "org.freedesktop.NetworkManager.AgentManager.NoSecrets"
+        | "org.freedesktop.NetworkManager.SecretAgent.NoSecrets"
+        | "org.freedesktop.NetworkManager.SecretAgent.UserCanceled" => NmError::AuthFailed(detail),
+        "org.freedesktop.DBus.Error.NoReply"
+        | "org.freedesktop.DBus.Error.Timeout"
+        | "org.freedesktop.DBus.Error.TimedOut" => NmError::Timeout(detail),
+        "org.freedesktop.DBus.Error.ServiceUnknown"
+        | "org.freedesktop.DBus.Error.NameHasNoOwner"
+        | "org.freedesktop.DBus.Error.Disconnected" => NmError::Unavailable(detail),
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn finds_typed_errors_through_context() {
+        let error = anyhow::Error::new(NmError::NotFound("Connection Cafe not found".to_string()))
+            .context("Failed to activate Cafe");
+        assert!(NmError::is_not_found(&error));
+
+        let untyped = anyhow!("Connection Cafe not found");
+        assert_eq!(
+            NmError::classify(&untyped),
+            NmError::Other("Connection Cafe not found".to_string())
+        );
+    }
+
+    #[test]
+    fn maps_dbus_error_names() {
+        let error: anyhow::Error = zbus::Error::FDO(Box::new(zbus::fdo::Error::AccessDenied(
+            "denied".to_string(),
+        )))
+        .into();
+        let error = error.context("set Wi-Fi");
+        assert!(matches!(
+            NmError::classify(&error),
+            NmError::PermissionDenied(_)
+        ));
+        assert!(!NmError::is_unavailable(&error));
+        assert!(NmError::is_unavailable(
+            &anyhow::Error::from(NmError::Unavailable("no bus".to_string())).context("scan")
+        ));
+
+        assert_eq!(
+            from_dbus_error_name("org.freedesktop.DBus.Error.NoReply", "no reply"),
+            Some(NmError::Timeout("no reply".to_string()))
+        );
+        assert_eq!(from_dbus_error_name("org.example.Custom", "boom"), None);
+    }
+
+    #[test]
+    fn recognizes_polkit_denials_in_stderr() {
+        assert!(matches!(
+            NmError::from_command_stderr(
+                "Error: Connection activation failed: Not authorized to control networking."
+            ),
+            NmError::PermissionDenied(_)
+        ));
+        assert!(matches!(
+            NmError::from_command_stderr(
+                "Error: Failed to add 'vpn' connection: Insufficient privileges"
+            ),
+            NmError::PermissionDenied(_)
+        ));
+        assert_eq!(
+            NmError::from_command_stderr("Error: unknown connection 'vpn'."),
+            NmError::Other("Error: unknown connection 'vpn'.".to_string())
+        );
+    }
+}
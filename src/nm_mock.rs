@@ -0,0 +1,484 @@
+// * ./src/nm_mock.rs
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+use crate::nm::{
+    compare_wifi_networks, ConnectStatus, Device, DeviceType, NetworkBackend, NetworkInfo, NmError,
+    SavedConnection, WifiNetwork,
+};
+use crate::nm_dbus::{
+    activation_failure, NM_ACTIVE_CONNECTION_STATE_ACTIVATED, NM_DEVICE_STATE_REASON_NO_SECRETS,
+    NM_DEVICE_STATE_REASON_SSID_NOT_FOUND, NM_DEVICE_STATE_REASON_SUPPLICANT_DISCONNECT,
+};
+
+// * Set to "mock" to run the UI against `MockBackend::demo()` instead of NetworkManager.
+pub const BACKEND_ENV: &str = "ADW_NETWORK_BACKEND";
+
+const MOCK_WIFI_IFACE: &str = "wlan0";
+const MOCK_ETHERNET_IFACE: &str = "eth0";
+
+struct MockNetwork {
+    network: WifiNetwork,
+    // * None for open networks; connecting with anything else fails like a bad PSK would.
+    password: Option<String>,
+}
+
+struct MockProfile {
+    saved: SavedConnection,
+    password: Option<String>,
+}
+
+#[derive(Default)]
+struct MockState {
+    wifi_enabled: bool,
+    networks: Vec<MockNetwork>,
+    profiles: Vec<MockProfile>,
+    active: Option<String>,
+    // * The Wi-Fi device's StateReason after the last failed activation, as NM reports it
+    device_reason: Option<u32>,
+    scans: usize,
+}
+
+// * In-memory stand-in for NetworkManager so pages can be exercised without hardware or D-Bus.
+pub struct MockBackend {
+    state: Mutex<MockState>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MockState {
+                wifi_enabled: true,
+                ..MockState::default()
+            }),
+        }
+    }
+
+    // * A handful of neighbours covering open, secured and 5 GHz rows.
+    pub fn demo() -> Self {
+        Self::new()
+            .with_network("Home", 82, Some("correct horse"))
+            .with_network("Cafe Guest", 64, None)
+            .with_network_on("Office 5G", 48, Some("battery staple"), 5180)
+            .with_network("Neighbour", 21, Some("not-yours"))
+            .with_saved("Home", Some("correct horse"))
+    }
+
+    pub fn with_network(self, ssid: &str, signal: u8, password: Option<&str>) -> Self {
+        self.with_network_on(ssid, signal, password, 2437)
+    }
+
+    pub fn with_network_on(
+        self,
+        ssid: &str,
+        signal: u8,
+        password: Option<&str>,
+        freq_mhz: u32,
+    ) -> Self {
+        let (band, channel) = if freq_mhz >= 5000 {
+            ("5 GHz", (freq_mhz - 5000) / 5)
+        } else {
+            ("2.4 GHz", (freq_mhz - 2407) / 5)
+        };
+        self.lock().networks.push(MockNetwork {
+            network: WifiNetwork {
+                ssid: ssid.to_string(),
+                signal,
+                secured: password.is_some(),
+                connected: false,
+                band: band.to_string(),
+                channel,
+                freq_mhz,
+                security_type: if password.is_some() { "WPA2" } else { "Open" }.to_string(),
+                device: None,
+            },
+            password: password.map(str::to_string),
+        });
+        self
+    }
+
+    pub fn with_saved(self, ssid: &str, password: Option<&str>) -> Self {
+        self.lock().save(ssid, password);
+        self
+    }
+
+    pub fn remove_network(&self, ssid: &str) {
+        let mut state = self.lock();
+        state.networks.retain(|n| n.network.ssid != ssid);
+        if state.active.as_deref() == Some(ssid) {
+            state.active = None;
+        }
+    }
+
+    pub fn active_ssid(&self) -> Option<String> {
+        self.lock().active.clone()
+    }
+
+    // * State of the ActiveConnection object; None once NM would have removed it
+    pub fn active_connection_state(&self) -> Option<u32> {
+        self.lock()
+            .active
+            .as_ref()
+            .map(|_| NM_ACTIVE_CONNECTION_STATE_ACTIVATED)
+    }
+
+    pub fn device_state_reason(&self) -> Option<u32> {
+        self.lock().device_reason
+    }
+
+    pub fn scan_count(&self) -> usize {
+        self.lock().scans
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl MockState {
+    fn visible_networks(&self) -> Vec<WifiNetwork> {
+        if !self.wifi_enabled {
+            return Vec::new();
+        }
+        let mut networks: Vec<WifiNetwork> = self
+            .networks
+            .iter()
+            .map(|n| WifiNetwork {
+                connected: self.active.as_deref() == Some(n.network.ssid.as_str()),
+                ..n.network.clone()
+            })
+            .collect();
+        networks.sort_by(compare_wifi_networks);
+        networks
+    }
+
+    fn in_range(&self, ssid: &str) -> Option<&MockNetwork> {
+        self.networks.iter().find(|n| n.network.ssid == ssid)
+    }
+
+    fn profile(&self, ssid: &str) -> Option<&MockProfile> {
+        self.profiles.iter().find(|p| p.saved.ssid == ssid)
+    }
+
+    fn profile_mut(&mut self, ssid: &str) -> Result<&mut MockProfile> {
+        self.profiles
+            .iter_mut()
+            .find(|p| p.saved.ssid == ssid)
+            .ok_or_else(|| NmError::NotFound(format!("Connection '{}' not found", ssid)).into())
+    }
+
+    fn save(&mut self, ssid: &str, password: Option<&str>) {
+        let password = password.map(str::to_string);
+        match self.profiles.iter_mut().find(|p| p.saved.ssid == ssid) {
+            Some(profile) => profile.password = password,
+            None => self.profiles.push(MockProfile {
+                saved: SavedConnection {
+                    uuid: Uuid::new_v4().to_string(),
+                    ssid: ssid.to_string(),
+                    last_used: 0,
+                    security_type: if password.is_some() { "WPA2" } else { "Open" }.to_string(),
+                    autoconnect: true,
+                    interface_name: None,
+                },
+                password,
+            }),
+        }
+    }
+
+    fn require_radio(&self) -> Result<()> {
+        if self.wifi_enabled {
+            Ok(())
+        } else {
+            Err(NmError::RadioOff.into())
+        }
+    }
+
+    // * Fails the way NM does: the active connection goes away and the device keeps the reason.
+    fn join(&mut self, ssid: &str, password: Option<&str>) -> Result<()> {
+        let reason = match self.in_range(ssid).map(|n| n.password.as_deref()) {
+            None => Some(NM_DEVICE_STATE_REASON_SSID_NOT_FOUND),
+            Some(None) => None,
+            Some(Some(_)) if password.is_none() => Some(NM_DEVICE_STATE_REASON_NO_SECRETS),
+            Some(Some(expected)) if Some(expected) != password => {
+                Some(NM_DEVICE_STATE_REASON_SUPPLICANT_DISCONNECT)
+            }
+            Some(Some(_)) => None,
+        };
+        if let Some(reason) = reason {
+            self.active = None;
+            self.device_reason = Some(reason);
+            return Err(activation_failure(Some(reason), ssid, false).into());
+        }
+        self.device_reason = None;
+        self.active = Some(ssid.to_string());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NetworkBackend for MockBackend {
+    async fn is_wifi_enabled(&self) -> Result<bool> {
+        Ok(self.lock().wifi_enabled)
+    }
+
+    async fn set_wifi_enabled(&self, enabled: bool) -> Result<()> {
+        let mut state = self.lock();
+        state.wifi_enabled = enabled;
+        if !enabled {
+            state.active = None;
+        }
+        Ok(())
+    }
+
+    async fn devices(&self) -> Result<Vec<Device>> {
+        let state = self.lock();
+        let wifi_state = match (state.wifi_enabled, state.active.is_some()) {
+            (false, _) => "unavailable",
+            (true, true) => "activated",
+            (true, false) => "disconnected",
+        };
+        Ok(vec![
+            Device {
+                name: MOCK_WIFI_IFACE.to_string(),
+                device_type: DeviceType::Wifi,
+                state: wifi_state.to_string(),
+                connection: state.active.clone(),
+            },
+            Device {
+                name: MOCK_ETHERNET_IFACE.to_string(),
+                device_type: DeviceType::Ethernet,
+                state: "unavailable".to_string(),
+                connection: None,
+            },
+        ])
+    }
+
+    async fn scan_networks(&self) -> Result<Vec<WifiNetwork>> {
+        let mut state = self.lock();
+        state.require_radio()?;
+        state.scans += 1;
+        Ok(state.visible_networks())
+    }
+
+    async fn scan_networks_quick(&self) -> Result<Vec<WifiNetwork>> {
+        self.scan_networks().await
+    }
+
+    async fn cached_networks(&self) -> Result<Vec<WifiNetwork>> {
+        Ok(self.lock().visible_networks())
+    }
+
+    async fn connect_wifi(
+        &self,
+        ssid: &str,
+        password: Option<&str>,
+        _security_type: Option<&str>,
+        _hidden: bool,
+    ) -> Result<ConnectStatus> {
+        let mut state = self.lock();
+        state.require_radio()?;
+        state.join(ssid, password)?;
+        state.save(ssid, password);
+        Ok(ConnectStatus::Connected)
+    }
+
+    async fn activate_saved(&self, ssid: &str) -> Result<ConnectStatus> {
+        let mut state = self.lock();
+        state.require_radio()?;
+        let password = state
+            .profile(ssid)
+            .ok_or_else(|| NmError::NotFound(format!("Connection '{}' not found", ssid)))?
+            .password
+            .clone();
+        state.join(ssid, password.as_deref())?;
+        Ok(ConnectStatus::Connected)
+    }
+
+    async fn disconnect(&self, ssid: &str) -> Result<()> {
+        let mut state = self.lock();
+        if state.active.as_deref() != Some(ssid) {
+            return Err(NmError::NotFound(format!("Connection '{}' is not active", ssid)).into());
+        }
+        state.active = None;
+        Ok(())
+    }
+
+    async fn saved_connections(&self) -> Result<Vec<SavedConnection>> {
+        Ok(self
+            .lock()
+            .profiles
+            .iter()
+            .map(|p| p.saved.clone())
+            .collect())
+    }
+
+    async fn forget(&self, ssid: &str) -> Result<()> {
+        let mut state = self.lock();
+        let before = state.profiles.len();
+        state.profiles.retain(|p| p.saved.ssid != ssid);
+        if state.profiles.len() == before {
+            return Err(NmError::NotFound(format!("Connection '{}' not found", ssid)).into());
+        }
+        if state.active.as_deref() == Some(ssid) {
+            state.active = None;
+        }
+        Ok(())
+    }
+
+    async fn network_info(&self, ssid: &str) -> Result<NetworkInfo> {
+        let state = self.lock();
+        let profile = state
+            .profile(ssid)
+            .ok_or_else(|| NmError::NotFound(format!("Connection '{}' not found", ssid)))?;
+
+        let mut info = NetworkInfo {
+            connection_type: Some("802-11-wireless".to_string()),
+            uuid: Some(profile.saved.uuid.clone()),
+            ..NetworkInfo::default()
+        };
+        if state.active.as_deref() == Some(ssid) {
+            info.state = Some("activated".to_string());
+            info.interface = Some(MOCK_WIFI_IFACE.to_string());
+            info.mac_address = Some("02:00:00:00:00:01".to_string());
+            info.ip_address = Some("192.168.1.23/24".to_string());
+            info.gateway = Some("192.168.1.1".to_string());
+            info.dns = vec!["192.168.1.1".to_string()];
+            info.dhcp_lease_time_seconds = Some(86400);
+        }
+        Ok(info)
+    }
+
+    async fn saved_wifi_ssids(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .lock()
+            .profiles
+            .iter()
+            .map(|p| p.saved.ssid.clone())
+            .collect())
+    }
+
+    async fn is_saved(&self, ssid: &str) -> Result<bool> {
+        Ok(self.lock().profile(ssid).is_some())
+    }
+
+    async fn active_wifi_ssid(&self) -> Result<Option<String>> {
+        Ok(self.lock().active.clone())
+    }
+
+    async fn autoconnect(&self, ssid: &str) -> Result<bool> {
+        Ok(self.lock().profile_mut(ssid)?.saved.autoconnect)
+    }
+
+    async fn set_autoconnect(&self, ssid: &str, enabled: bool) -> Result<()> {
+        self.lock().profile_mut(ssid)?.saved.autoconnect = enabled;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nm_dbus::{activation_progress, ActivationProgress};
+
+    #[test]
+    fn connects_saves_and_forgets() {
+        let backend = MockBackend::new()
+            .with_network("Home", 80, Some("secret123"))
+            .with_network("Cafe", 40, None);
+
+        tokio_test::block_on(async {
+            let err = backend
+                .connect_wifi("Home", Some("wrong-pass"), None, false)
+                .await
+                .unwrap_err();
+            assert!(matches!(NmError::classify(&err), NmError::AuthFailed(_)));
+            backend
+                .connect_wifi("Home", Some("secret123"), Some("WPA2"), false)
+                .await
+                .unwrap();
+
+            let networks = backend.scan_networks().await.unwrap();
+            assert_eq!(networks[0].ssid, "Home");
+            assert!(networks[0].connected);
+            assert_eq!(backend.saved_connections().await.unwrap().len(), 1);
+
+            let info = backend.network_info("Home").await.unwrap();
+            assert!(info.ip_address.is_some());
+
+            backend.forget("Home").await.unwrap();
+            assert_eq!(backend.active_ssid(), None);
+            let err = backend.activate_saved("Home").await.unwrap_err();
+            assert!(NmError::is_not_found(&err));
+        });
+    }
+
+    #[test]
+    fn vanished_active_connection_reports_the_device_reason() {
+        let backend = MockBackend::new().with_network("Home", 80, Some("secret123"));
+
+        tokio_test::block_on(async {
+            let err = backend
+                .connect_wifi("Home", Some("wrong-pass"), None, false)
+                .await
+                .unwrap_err();
+            assert!(matches!(NmError::classify(&err), NmError::AuthFailed(_)));
+
+            // * What the D-Bus wait loop sees on its next poll
+            let progress = activation_progress(backend.active_connection_state(), "", "Home");
+            assert_eq!(progress, ActivationProgress::Failed);
+            let failure = activation_failure(backend.device_state_reason(), "Home", false);
+            assert_eq!(
+                failure.user_message(),
+                "Wrong password or missing credentials"
+            );
+
+            let err = backend
+                .connect_wifi("Nowhere", None, None, false)
+                .await
+                .unwrap_err();
+            assert!(NmError::is_not_found(&err));
+
+            backend
+                .connect_wifi("Home", Some("secret123"), None, false)
+                .await
+                .unwrap();
+            assert_eq!(
+                activation_progress(backend.active_connection_state(), "Home", "Home"),
+                ActivationProgress::Activated
+            );
+            assert_eq!(
+                activation_progress(Some(1), "Home", "Home"),
+                ActivationProgress::Pending
+            );
+        });
+    }
+
+    #[test]
+    fn radio_off_hides_networks_and_drops_the_connection() {
+        let backend = MockBackend::demo();
+
+        tokio_test::block_on(async {
+            backend.activate_saved("Home").await.unwrap();
+            backend.set_wifi_enabled(false).await.unwrap();
+
+            assert!(backend.cached_networks().await.unwrap().is_empty());
+            let err = backend.scan_networks().await.unwrap_err();
+            assert_eq!(NmError::classify(&err), NmError::RadioOff);
+            let devices = backend.devices().await.unwrap();
+            assert_eq!(devices[0].state, "unavailable");
+            assert_eq!(devices[0].connection, None);
+        });
+    }
+}
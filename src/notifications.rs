@@ -0,0 +1,254 @@
+// * ./src/notifications.rs
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::config::{self, AppSettings};
+use crate::event_log::{EventKind, NetworkEvent};
+use crate::hotspot;
+use crate::polling::PollScheduler;
+
+const HOTSPOT_CLIENT_POLL: Duration = Duration::from_secs(10);
+// * App actions behind the new-client notification's buttons, registered by the main window
+pub const BLOCK_CLIENT_ACTION: &str = "hotspot-block-client";
+pub const SHOW_DEVICES_ACTION: &str = "show-devices";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Connected,
+    Disconnected,
+    HotspotClientJoined,
+    HotspotAutoOff,
+    HotspotPasswordRotated,
+    CaptivePortal,
+}
+
+impl NotificationKind {
+    fn enabled_in(self, settings: &AppSettings) -> bool {
+        match self {
+            Self::Connected => settings.notify_on_connect,
+            Self::Disconnected => settings.notify_on_disconnect,
+            Self::HotspotClientJoined => settings.notify_on_hotspot_client,
+            // * The user set the timer, so always say when it turned the hotspot off
+            Self::HotspotAutoOff => true,
+            Self::HotspotPasswordRotated => true,
+            Self::CaptivePortal => settings.notify_on_captive_portal,
+        }
+    }
+
+    // * Connects and drops share an id so a flapping link shows one notification, not a stack.
+    fn id(self) -> &'static str {
+        match self {
+            Self::Connected | Self::Disconnected => "connection",
+            Self::HotspotClientJoined => "hotspot-client",
+            Self::HotspotAutoOff => "hotspot-auto-off",
+            Self::HotspotPasswordRotated => "hotspot-password",
+            Self::CaptivePortal => "captive-portal",
+        }
+    }
+
+    fn icon_name(self) -> &'static str {
+        match self {
+            Self::Connected => "network-wireless-signal-excellent-symbolic",
+            Self::Disconnected => "network-wireless-offline-symbolic",
+            Self::HotspotClientJoined | Self::HotspotAutoOff | Self::HotspotPasswordRotated => {
+                "network-wireless-hotspot-symbolic"
+            }
+            Self::CaptivePortal => "web-browser-symbolic",
+        }
+    }
+}
+
+// * Title and body for a recorded event; roams and hotspot toggles aren't worth a notification.
+pub fn event_message(event: &NetworkEvent) -> Option<(NotificationKind, String, String)> {
+    match event.kind {
+        EventKind::WifiConnected => Some((
+            NotificationKind::Connected,
+            "Connected".to_string(),
+            format!("Connected to {}", event.network),
+        )),
+        EventKind::WiredConnected => Some((
+            NotificationKind::Connected,
+            "Cable Connected".to_string(),
+            format!("Connected to {}", event.network),
+        )),
+        EventKind::WifiDisconnected => Some((
+            NotificationKind::Disconnected,
+            "Disconnected".to_string(),
+            format!("Lost connection to {}", event.network),
+        )),
+        EventKind::WiredDisconnected => Some((
+            NotificationKind::Disconnected,
+            "Cable Disconnected".to_string(),
+            format!("{} is no longer connected", event.network),
+        )),
+        EventKind::Roamed | EventKind::HotspotStarted | EventKind::HotspotStopped => None,
+    }
+}
+
+pub fn notify_event(event: &NetworkEvent) {
+    if let Some((kind, title, body)) = event_message(event) {
+        send(kind, &title, &body);
+    }
+}
+
+pub fn notify_captive_portal(ssid: Option<&str>) {
+    send(
+        NotificationKind::CaptivePortal,
+        "Sign-In Required",
+        &format!(
+            "{} requires you to sign in before you can use the internet",
+            ssid.unwrap_or("This network")
+        ),
+    );
+}
+
+pub fn send(kind: NotificationKind, title: &str, body: &str) {
+    let notification = gio::Notification::new(title);
+    notification.set_body(Some(body));
+    deliver(kind, &notification);
+}
+
+fn deliver(kind: NotificationKind, notification: &gio::Notification) {
+    let settings = config::load_app_settings_sync(&config::app_settings_path()).unwrap_or_default();
+    if !kind.enabled_in(&settings) {
+        return;
+    }
+    let Some(app) = gio::Application::default() else {
+        return;
+    };
+    // * The window already shows the change while it has focus
+    let focused = app
+        .downcast_ref::<gtk4::Application>()
+        .and_then(|app| app.active_window())
+        .is_some_and(|window| window.is_visible() && window.is_active());
+    if focused {
+        return;
+    }
+
+    notification.set_icon(&gio::ThemedIcon::new(kind.icon_name()));
+    app.send_notification(Some(kind.id()), notification);
+}
+
+pub fn client_joined_message(name: &str, vendor: Option<&str>, ip: &str) -> String {
+    match vendor {
+        Some(vendor) => format!("{} ({}) joined at {}", name, vendor, ip),
+        None => format!("{} joined at {}", name, ip),
+    }
+}
+
+type JoinCallback = Rc<dyn Fn(&str, &str)>;
+
+// * Polls the hotspot's client list and announces MACs that weren't there on the previous poll.
+// * `on_join` gets each newcomer's MAC and message, for a toast while the window is in front.
+pub fn watch_hotspot_clients(scheduler: &PollScheduler, on_join: impl Fn(&str, &str) + 'static) {
+    // * None until the first poll of a running hotspot, so clients already connected stay quiet
+    let known: Rc<RefCell<Option<HashSet<String>>>> = Rc::new(RefCell::new(None));
+    let in_flight = Rc::new(Cell::new(false));
+    let on_join: JoinCallback = Rc::new(on_join);
+
+    scheduler.subscribe(
+        "hotspot-client-alerts",
+        HOTSPOT_CLIENT_POLL,
+        HOTSPOT_CLIENT_POLL,
+        move || {
+            if in_flight.replace(true) {
+                return;
+            }
+            let known = known.clone();
+            let in_flight = in_flight.clone();
+            let on_join = on_join.clone();
+            glib::spawn_future_local(async move {
+                poll_hotspot_clients(&known, on_join.as_ref()).await;
+                in_flight.set(false);
+            });
+        },
+    );
+}
+
+async fn poll_hotspot_clients(
+    known: &RefCell<Option<HashSet<String>>>,
+    on_join: &dyn Fn(&str, &str),
+) {
+    if !hotspot::is_hotspot_active().await.unwrap_or(false) {
+        known.replace(None);
+        return;
+    }
+    let clients = match hotspot::list_connected_clients().await {
+        Ok(clients) => clients,
+        Err(e) => {
+            log::debug!("Failed to list hotspot clients: {}", e);
+            return;
+        }
+    };
+
+    let current: HashSet<String> = clients.iter().map(|client| client.mac.clone()).collect();
+    let previous = known.replace(Some(current));
+    let Some(previous) = previous else {
+        return;
+    };
+    let settings = config::load_app_settings_sync(&config::app_settings_path()).unwrap_or_default();
+    if !NotificationKind::HotspotClientJoined.enabled_in(&settings) {
+        return;
+    }
+    for client in clients
+        .iter()
+        .filter(|client| !previous.contains(&client.mac))
+    {
+        let name = client.hostname.as_deref().unwrap_or(&client.mac);
+        let vendor = crate::oui::vendor_from_mac(&client.mac);
+        let message = client_joined_message(name, vendor.as_deref(), &client.ip);
+
+        let notification = gio::Notification::new("New Device on Hotspot");
+        notification.set_body(Some(&message));
+        notification.add_button_with_target_value(
+            "Block",
+            &format!("app.{}", BLOCK_CLIENT_ACTION),
+            Some(&client.mac.to_variant()),
+        );
+        notification.add_button("View Devices", &format!("app.{}", SHOW_DEVICES_ACTION));
+        deliver(NotificationKind::HotspotClientJoined, &notification);
+        on_join(&client.mac, &message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_connects_and_drops_become_notifications() {
+        let event = |kind| NetworkEvent {
+            timestamp: 0,
+            kind,
+            network: "Home".to_string(),
+            detail: None,
+        };
+
+        let (kind, _, body) = event_message(&event(EventKind::WifiDisconnected)).unwrap();
+        assert_eq!(kind, NotificationKind::Disconnected);
+        assert_eq!(body, "Lost connection to Home");
+        assert_eq!(
+            event_message(&event(EventKind::WiredConnected)).map(|(kind, _, _)| kind),
+            Some(NotificationKind::Connected)
+        );
+        assert_eq!(event_message(&event(EventKind::Roamed)), None);
+        assert_eq!(event_message(&event(EventKind::HotspotStarted)), None);
+    }
+
+    #[test]
+    fn names_the_vendor_of_a_joining_client_when_known() {
+        assert_eq!(
+            client_joined_message("pixel-7", Some("Google"), "10.42.0.23"),
+            "pixel-7 (Google) joined at 10.42.0.23"
+        );
+        assert_eq!(
+            client_joined_message("AA:BB:CC:DD:EE:FF", None, "10.42.0.24"),
+            "AA:BB:CC:DD:EE:FF joined at 10.42.0.24"
+        );
+    }
+}
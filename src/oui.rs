@@ -0,0 +1,169 @@
+// * ./src/oui.rs
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{OnceLock, RwLock};
+use tokio::process::Command;
+
+// * Shipped so vendor names work on distros without hwdata or ieee-data
+const BUNDLED_OUI: &str = include_str!("../data/oui-compact.txt");
+
+const IEEE_OUI_URL: &str = "https://standards-oui.ieee.org/oui/oui.txt";
+
+const DOWNLOAD_TIMEOUT_SECS: u64 = 60;
+
+// ? The full registry has well over 30k entries; far fewer means we fetched an error page
+const MIN_DOWNLOADED_ENTRIES: usize = 10_000;
+
+const SYSTEM_OUI_PATHS: &[&str] = &[
+    "/usr/share/hwdata/oui.txt",
+    "/usr/share/misc/oui.txt",
+    "/usr/share/ieee-data/oui.txt",
+    "/var/lib/ieee-data/oui.txt",
+];
+
+pub fn downloaded_oui_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".local/share/adw-network/oui.txt"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-oui.txt"))
+}
+
+pub fn vendor_from_mac(mac: &str) -> Option<String> {
+    let oui = normalize_oui(mac)?;
+    oui_map().read().ok()?.get(&oui).cloned()
+}
+
+fn oui_map() -> &'static RwLock<HashMap<String, String>> {
+    static OUI_MAP: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    OUI_MAP.get_or_init(|| RwLock::new(load_oui_map()))
+}
+
+// * Later sources win: bundled subset, then the distro's copy, then a downloaded update.
+fn load_oui_map() -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    parse_oui_content(BUNDLED_OUI, &mut map);
+
+    if let Some(content) = SYSTEM_OUI_PATHS
+        .iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())
+    {
+        parse_oui_content(&content, &mut map);
+    }
+    if let Ok(content) = std::fs::read_to_string(downloaded_oui_path()) {
+        parse_oui_content(&content, &mut map);
+    }
+
+    map
+}
+
+// * Downloads the IEEE registry into the data dir and reloads lookups. Returns the entry count.
+pub async fn update_vendor_database() -> Result<usize> {
+    if !crate::hotspot::command_available("curl").await {
+        return Err(anyhow!("Updating the vendor database needs curl installed"));
+    }
+    let output = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--max-time",
+            &DOWNLOAD_TIMEOUT_SECS.to_string(),
+            "--",
+            IEEE_OUI_URL,
+        ])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Download failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let content = String::from_utf8_lossy(&output.stdout);
+    let mut downloaded = HashMap::new();
+    parse_oui_content(&content, &mut downloaded);
+    if downloaded.len() < MIN_DOWNLOADED_ENTRIES {
+        return Err(anyhow!(
+            "The download doesn't look like the IEEE vendor list"
+        ));
+    }
+
+    let path = downloaded_oui_path();
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let partial = path.with_extension("txt.part");
+    tokio::fs::write(&partial, content.as_bytes()).await?;
+    tokio::fs::rename(&partial, &path).await?;
+
+    let count = downloaded.len();
+    let map = load_oui_map();
+    if let Ok(mut current) = oui_map().write() {
+        *current = map;
+    }
+    log::info!("Updated the vendor database with {} entries", count);
+    Ok(count)
+}
+
+fn parse_oui_content(content: &str, map: &mut HashMap<String, String>) {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (prefix, vendor) = if let Some((left, right)) = line.split_once("(hex)") {
+            (left, right)
+        } else if let Some((left, right)) = line.split_once("(base 16)") {
+            (left, right)
+        } else {
+            continue;
+        };
+
+        let oui = match normalize_oui(prefix) {
+            Some(oui) => oui,
+            None => continue,
+        };
+        let vendor = vendor.trim();
+        if !vendor.is_empty() {
+            map.insert(oui, vendor.to_string());
+        }
+    }
+}
+
+fn normalize_oui(prefix: &str) -> Option<String> {
+    let hex: String = prefix.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if hex.len() < 6 {
+        return None;
+    }
+    Some(hex[..6].to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_list_parses_in_ieee_layout() {
+        let mut map = HashMap::new();
+        parse_oui_content(BUNDLED_OUI, &mut map);
+        assert!(map.len() > 900);
+        assert_eq!(
+            map.get("B827EB").map(String::as_str),
+            Some("Raspberry Pi Foundation")
+        );
+
+        parse_oui_content(
+            "B8-27-EB   (hex)\t\tRaspberry Pi Foundation\nB827EB     (base 16)\t\tRaspberry Pi Foundation\n\t\t\t\tCambridge\n",
+            &mut map,
+        );
+        assert!(map.values().all(|vendor| vendor != "Cambridge"));
+    }
+}
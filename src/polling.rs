@@ -0,0 +1,215 @@
+// * ./src/polling.rs
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gtk4::glib;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+use crate::nm::{self, NmEvent};
+
+// * Cheap heartbeat; it only compares deadlines, the subscribers do the real work.
+const HEARTBEAT_SECS: u32 = 1;
+// * NM emits signals in bursts (a scan adds dozens of APs); one refresh per burst is enough.
+const EVENT_COALESCE: Duration = Duration::from_millis(400);
+
+const ALL_EVENTS: [NmEvent; 5] = [
+    NmEvent::StateChanged,
+    NmEvent::DevicesChanged,
+    NmEvent::AccessPointsChanged,
+    NmEvent::ActiveConnectionsChanged,
+    NmEvent::ConnectivityChanged,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    min: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min,
+            max: max.max(min),
+            current: min,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.current = self.min;
+    }
+
+    // * Returns the delay before the next poll and doubles it for the one after.
+    // ? Without NM signals a transition can't be noticed, so `capped` keeps polls close to the minimum
+    pub fn advance(&mut self, capped: bool) -> Duration {
+        let limit = if capped {
+            (self.min * 2).min(self.max)
+        } else {
+            self.max
+        };
+        let delay = self.current.min(limit);
+        self.current = (self.current * 2).min(limit);
+        delay
+    }
+}
+
+struct Subscription {
+    name: &'static str,
+    backoff: Backoff,
+    due: Instant,
+    callback: Rc<dyn Fn()>,
+}
+
+// * One timer for every periodic refresh: fast right after a state change, backing off while stable.
+#[derive(Clone, Default)]
+pub struct PollScheduler {
+    subscriptions: Rc<RefCell<Vec<Subscription>>>,
+}
+
+impl PollScheduler {
+    pub fn start() -> Self {
+        let scheduler = Self::default();
+        let scheduler_for_tick = scheduler.clone();
+        glib::timeout_add_seconds_local(HEARTBEAT_SECS, move || {
+            scheduler_for_tick.tick();
+            glib::ControlFlow::Continue
+        });
+        scheduler
+    }
+
+    // * The callback runs once the first delay elapses; callers refresh immediately themselves if needed.
+    pub fn subscribe(
+        &self,
+        name: &'static str,
+        min: Duration,
+        max: Duration,
+        callback: impl Fn() + 'static,
+    ) {
+        let mut subscriptions = self.subscriptions.borrow_mut();
+        subscriptions.push(Subscription {
+            name,
+            backoff: Backoff::new(min, max),
+            due: Instant::now() + min,
+            callback: Rc::new(callback),
+        });
+    }
+
+    // * Connect/disconnect and other transitions: poll everything soon and start backing off again.
+    pub fn nudge(&self) {
+        let Ok(mut subscriptions) = self.subscriptions.try_borrow_mut() else {
+            log::warn!("Borrow conflict in poll scheduler");
+            return;
+        };
+        let now = Instant::now();
+        for subscription in subscriptions.iter_mut() {
+            subscription.backoff.reset();
+            subscription.due = now;
+        }
+    }
+
+    fn tick(&self) {
+        if nm::signal_happened() {
+            nm::signal_ack();
+            log::debug!("NM state changed; polling at the fast rate");
+            self.nudge();
+        }
+
+        let capped = nm::signal_polling_fallback_active();
+        let now = Instant::now();
+        let due: Vec<Rc<dyn Fn()>> = {
+            let Ok(mut subscriptions) = self.subscriptions.try_borrow_mut() else {
+                return;
+            };
+            subscriptions
+                .iter_mut()
+                .filter(|subscription| subscription.due <= now)
+                .map(|subscription| {
+                    let delay = subscription.backoff.advance(capped);
+                    subscription.due = now + delay;
+                    log::trace!(
+                        "Polling {} (next in {}s)",
+                        subscription.name,
+                        delay.as_secs()
+                    );
+                    Rc::clone(&subscription.callback)
+                })
+                .collect()
+        };
+
+        // * Run outside the borrow so callbacks may nudge or subscribe
+        for callback in due {
+            callback();
+        }
+    }
+}
+
+// * Delivers NM signal bursts on the GTK main loop as one de-duplicated batch.
+pub fn forward_nm_events(callback: impl Fn(&[NmEvent]) + 'static) {
+    let mut receiver = nm::subscribe_events();
+    glib::spawn_future_local(async move {
+        loop {
+            let mut batch = Vec::new();
+            match receiver.recv().await {
+                Ok(event) => batch.push(event),
+                Err(RecvError::Lagged(_)) => batch.extend(ALL_EVENTS),
+                Err(RecvError::Closed) => return,
+            }
+
+            glib::timeout_future(EVENT_COALESCE).await;
+            loop {
+                match receiver.try_recv() {
+                    Ok(event) => batch.push(event),
+                    Err(TryRecvError::Lagged(_)) => batch.extend(ALL_EVENTS),
+                    Err(_) => break,
+                }
+            }
+
+            callback(&coalesce_events(batch));
+        }
+    });
+}
+
+fn coalesce_events(batch: Vec<NmEvent>) -> Vec<NmEvent> {
+    ALL_EVENTS
+        .into_iter()
+        .filter(|event| batch.contains(event))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_until_the_maximum_and_resets() {
+        let mut backoff = Backoff::new(Duration::from_secs(2), Duration::from_secs(10));
+        let delays: Vec<u64> = (0..5).map(|_| backoff.advance(false).as_secs()).collect();
+        assert_eq!(delays, vec![2, 4, 8, 10, 10]);
+
+        backoff.reset();
+        assert_eq!(backoff.advance(false), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn coalesces_bursts_into_one_of_each() {
+        let batch = vec![
+            NmEvent::AccessPointsChanged,
+            NmEvent::StateChanged,
+            NmEvent::AccessPointsChanged,
+        ];
+        assert_eq!(
+            coalesce_events(batch),
+            vec![NmEvent::StateChanged, NmEvent::AccessPointsChanged]
+        );
+    }
+
+    #[test]
+    fn stays_near_the_minimum_without_signals() {
+        let mut backoff = Backoff::new(Duration::from_secs(3), Duration::from_secs(60));
+        let delays: Vec<u64> = (0..4).map(|_| backoff.advance(true).as_secs()).collect();
+        assert_eq!(delays, vec![3, 6, 6, 6]);
+    }
+}
@@ -0,0 +1,148 @@
+// * ./src/public_ip.rs
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::net::IpAddr;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::time::{timeout, Duration};
+
+use crate::captive_portal;
+
+const LOOKUP_TIMEOUT_SECS: u64 = 8;
+const REVERSE_LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicIpInfo {
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    pub reverse_dns: Option<String>,
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct LookupResponse {
+    ip: String,
+    hostname: Option<String>,
+    provider: Option<String>,
+}
+
+// * Asks the endpoint once over IPv4 and once over IPv6; either may be missing.
+pub async fn lookup(url: &str) -> Result<PublicIpInfo> {
+    captive_portal::validate_form_url(url)?;
+    let (v4, v6) = tokio::join!(fetch(url, "-4"), fetch(url, "-6"));
+    let (v4, v6) = match (v4, v6) {
+        (Err(e), Err(_)) => return Err(e),
+        (v4, v6) => (v4.ok(), v6.ok()),
+    };
+
+    let primary = v4.as_ref().or(v6.as_ref());
+    let mut reverse_dns = primary.and_then(|response| response.hostname.clone());
+    if reverse_dns.is_none() {
+        if let Some(ip) = primary.and_then(|response| response.ip.parse::<IpAddr>().ok()) {
+            reverse_dns = reverse_lookup(ip).await;
+        }
+    }
+
+    Ok(PublicIpInfo {
+        provider: primary.and_then(|response| response.provider.clone()),
+        ipv4: v4.map(|response| response.ip),
+        ipv6: v6.map(|response| response.ip),
+        reverse_dns,
+    })
+}
+
+async fn fetch(url: &str, family: &str) -> Result<LookupResponse> {
+    let output = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--max-time",
+            &LOOKUP_TIMEOUT_SECS.to_string(),
+            "--header",
+            "Accept: application/json",
+            family,
+            "--",
+            url,
+        ])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run curl: {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Lookup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_lookup_response(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow!("The lookup service returned no address"))
+}
+
+// * Accepts ipinfo.io and ip-api.com style JSON, or a bare address like api.ipify.org returns.
+fn parse_lookup_response(body: &str) -> Option<LookupResponse> {
+    let body = body.trim();
+    if let Ok(ip) = body.parse::<IpAddr>() {
+        return Some(LookupResponse {
+            ip: ip.to_string(),
+            ..Default::default()
+        });
+    }
+
+    let json: Value = serde_json::from_str(body).ok()?;
+    let field = |keys: &[&str]| {
+        keys.iter()
+            .filter_map(|key| json.get(*key).and_then(Value::as_str))
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let ip = field(&["ip", "query", "address"])?;
+    ip.parse::<IpAddr>().ok()?;
+    Some(LookupResponse {
+        ip,
+        hostname: field(&["hostname", "reverse"]),
+        provider: field(&["org", "isp", "as"]),
+    })
+}
+
+async fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    let lookup = tokio::task::spawn_blocking(move || dns_lookup::lookup_addr(&ip).ok());
+    let name = timeout(REVERSE_LOOKUP_TIMEOUT, lookup).await.ok()?.ok()??;
+    (name != ip.to_string()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_lookup_formats() {
+        let ipinfo =
+            r#"{"ip":"203.0.113.7","hostname":"host.example.net","org":"AS64500 Example ISP"}"#;
+        assert_eq!(
+            parse_lookup_response(ipinfo),
+            Some(LookupResponse {
+                ip: "203.0.113.7".to_string(),
+                hostname: Some("host.example.net".to_string()),
+                provider: Some("AS64500 Example ISP".to_string()),
+            })
+        );
+
+        let ip_api = r#"{"query":"2001:db8::1","isp":"Example ISP","reverse":""}"#;
+        let parsed = parse_lookup_response(ip_api).unwrap();
+        assert_eq!(parsed.ip, "2001:db8::1");
+        assert_eq!(parsed.hostname, None);
+        assert_eq!(parsed.provider.as_deref(), Some("Example ISP"));
+
+        assert_eq!(
+            parse_lookup_response("198.51.100.2\n").map(|r| r.ip),
+            Some("198.51.100.2".to_string())
+        );
+        assert_eq!(parse_lookup_response("<html>blocked</html>"), None);
+    }
+}
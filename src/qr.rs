@@ -1,6 +1,7 @@
 // * ./src/qr.rs
 
 use anyhow::Result;
+use qrcode::render::unicode;
 use qrcode::QrCode;
 
 pub fn generate_bytes_for_pixbuf(data: &str) -> Result<(Vec<u8>, i32, i32)> {
@@ -26,3 +27,13 @@ pub fn generate_bytes_for_pixbuf(data: &str) -> Result<(Vec<u8>, i32, i32)> {
 
     Ok((rgb_bytes, img_size, img_size))
 }
+
+// * Half blocks keep the code square; colors are swapped so it scans on a dark terminal.
+pub fn render_terminal(data: &str) -> Result<String> {
+    let code = QrCode::new(data)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
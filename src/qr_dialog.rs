@@ -35,17 +35,7 @@ pub async fn show_qr_dialog(
     size: i32,
     toast_overlay: &adw::ToastOverlay,
 ) {
-    let ssid_escaped = escape_wifi_field(ssid);
-    let password_escaped = escape_wifi_field(password);
-    let auth = wifi_auth_type(password, security_type);
-    let wifi_string = if password.is_empty() {
-        format!("WIFI:T:{};S:{};;", auth, ssid_escaped)
-    } else {
-        format!(
-            "WIFI:T:{};S:{};P:{};;",
-            auth, ssid_escaped, password_escaped
-        )
-    };
+    let wifi_string = wifi_qr_string(ssid, password, security_type);
 
     let qr_result = qr::generate_bytes_for_pixbuf(&wifi_string[..]);
 
@@ -155,6 +145,21 @@ pub async fn show_qr_dialog(
     }
 }
 
+// * The WIFI: payload phone cameras understand; also printed by the `qr` CLI command.
+pub fn wifi_qr_string(ssid: &str, password: &str, security_type: Option<&str>) -> String {
+    let ssid_escaped = escape_wifi_field(ssid);
+    let password_escaped = escape_wifi_field(password);
+    let auth = wifi_auth_type(password, security_type);
+    if password.is_empty() {
+        format!("WIFI:T:{};S:{};;", auth, ssid_escaped)
+    } else {
+        format!(
+            "WIFI:T:{};S:{};P:{};;",
+            auth, ssid_escaped, password_escaped
+        )
+    }
+}
+
 fn escape_wifi_field(input: &str) -> String {
     let mut out = String::with_capacity(input.len());
     for ch in input.chars() {
@@ -177,6 +182,9 @@ fn wifi_auth_type<'a>(password: &str, security_type: Option<&'a str>) -> &'a str
     let sec = security_type.unwrap_or_default().to_ascii_lowercase();
     if sec.contains("wep") {
         "WEP"
+    } else if sec.contains("wpa2/wpa3") {
+        // * Transition networks accept WPA2, which every scanner app can join
+        "WPA"
     } else if sec.contains("wpa3") || sec.contains("sae") {
         "SAE"
     } else {
@@ -198,5 +206,6 @@ mod tests {
     fn maps_wpa3_to_sae() {
         assert_eq!(wifi_auth_type("12345678", Some("WPA3")), "SAE");
         assert_eq!(wifi_auth_type("12345678", Some("sae")), "SAE");
+        assert_eq!(wifi_auth_type("12345678", Some("WPA2/WPA3")), "WPA");
     }
 }
@@ -0,0 +1,173 @@
+// * ./src/quick_connect.rs
+
+use anyhow::Result;
+use gtk4::gio;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::nm::{self, WifiNetwork};
+
+pub const CONNECT_SAVED_ACTION: &str = "connect-saved";
+
+// * Menus open often; a short-lived cache keeps them instant without going stale.
+const CACHE_TTL: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickConnectEntry {
+    pub ssid: String,
+    pub signal: u8,
+    pub connected: bool,
+}
+
+impl QuickConnectEntry {
+    pub fn menu_label(&self) -> String {
+        if self.connected {
+            format!("{} (connected)", self.ssid)
+        } else {
+            format!("{} ({}%)", self.ssid, self.signal)
+        }
+    }
+}
+
+type Cache = Mutex<Option<(Instant, Vec<QuickConnectEntry>)>>;
+
+fn cache() -> &'static Cache {
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+pub async fn in_range_saved_networks() -> Result<Vec<QuickConnectEntry>> {
+    if let Ok(guard) = cache().lock() {
+        if let Some((fetched_at, entries)) = guard.as_ref() {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return Ok(entries.clone());
+            }
+        }
+    }
+
+    let networks = nm::scan_networks_quick().await?;
+    let saved: HashSet<String> = nm::get_saved_connections()
+        .await?
+        .into_iter()
+        .map(|c| c.ssid)
+        .collect();
+    let entries = select_saved_in_range(&networks, &saved);
+
+    if let Ok(mut guard) = cache().lock() {
+        *guard = Some((Instant::now(), entries.clone()));
+    }
+    Ok(entries)
+}
+
+pub fn invalidate_cache() {
+    if let Ok(mut guard) = cache().lock() {
+        *guard = None;
+    }
+}
+
+// * One entry per SSID with its strongest signal; connected first, then by signal.
+fn select_saved_in_range(
+    networks: &[WifiNetwork],
+    saved: &HashSet<String>,
+) -> Vec<QuickConnectEntry> {
+    let mut by_ssid: HashMap<&str, QuickConnectEntry> = HashMap::new();
+    for network in networks
+        .iter()
+        .filter(|n| !n.ssid.is_empty() && saved.contains(&n.ssid))
+    {
+        let entry = by_ssid
+            .entry(network.ssid.as_str())
+            .or_insert_with(|| QuickConnectEntry {
+                ssid: network.ssid.clone(),
+                signal: network.signal,
+                connected: network.connected,
+            });
+        entry.signal = entry.signal.max(network.signal);
+        entry.connected |= network.connected;
+    }
+
+    let mut entries: Vec<QuickConnectEntry> = by_ssid.into_values().collect();
+    entries.sort_by(|a, b| {
+        b.connected
+            .cmp(&a.connected)
+            .then(b.signal.cmp(&a.signal))
+            .then_with(|| a.ssid.to_lowercase().cmp(&b.ssid.to_lowercase()))
+    });
+    entries
+}
+
+// * Fills a menu with `app.connect-saved` items; shared by the header button and the tray.
+pub fn fill_menu(menu: &gio::Menu, entries: &[QuickConnectEntry]) {
+    menu.remove_all();
+    if entries.is_empty() {
+        menu.append(Some("No saved networks in range"), None);
+        return;
+    }
+
+    for entry in entries {
+        let item = gio::MenuItem::new(Some(&entry.menu_label()), None);
+        if !entry.connected {
+            item.set_action_and_target_value(
+                Some(&format!("app.{}", CONNECT_SAVED_ACTION)),
+                Some(&gtk4::glib::Variant::from(entry.ssid.as_str())),
+            );
+        }
+        menu.append_item(&item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(ssid: &str, signal: u8, connected: bool) -> WifiNetwork {
+        WifiNetwork {
+            ssid: ssid.to_string(),
+            signal,
+            secured: true,
+            connected,
+            band: "2.4 GHz".to_string(),
+            channel: 6,
+            freq_mhz: 2437,
+            security_type: "WPA2".to_string(),
+            device: None,
+        }
+    }
+
+    #[test]
+    fn keeps_only_saved_networks_once_each() {
+        let networks = vec![
+            network("Home", 40, false),
+            network("Home", 70, false),
+            network("Neighbour", 90, false),
+            network("Office", 55, false),
+        ];
+        let saved: HashSet<String> = ["Home", "Office", "Away"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let entries = select_saved_in_range(&networks, &saved);
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| (e.ssid.as_str(), e.signal))
+                .collect::<Vec<_>>(),
+            vec![("Home", 70), ("Office", 55)]
+        );
+    }
+
+    #[test]
+    fn lists_the_connected_network_first() {
+        let networks = vec![network("Cafe", 95, false), network("Home", 30, true)];
+        let saved: HashSet<String> = ["Cafe", "Home"].iter().map(|s| s.to_string()).collect();
+
+        let entries = select_saved_in_range(&networks, &saved);
+
+        assert_eq!(entries[0].ssid, "Home");
+        assert_eq!(entries[0].menu_label(), "Home (connected)");
+        assert_eq!(entries[1].menu_label(), "Cafe (95%)");
+    }
+}
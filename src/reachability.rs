@@ -0,0 +1,244 @@
+// * ./src/reachability.rs
+
+use anyhow::{anyhow, Result};
+use std::time::Instant;
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tokio::time::{timeout, Duration};
+
+const PROBE_TIMEOUT_SECS: u64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReachabilityTarget {
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl ReachabilityTarget {
+    pub fn label(&self) -> String {
+        match self.port {
+            Some(port) if self.host.contains(':') => format!("[{}]:{}", self.host, port),
+            Some(port) => format!("{}:{}", self.host, port),
+            None => self.host.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReachabilityResult {
+    pub reachable: bool,
+    pub latency_ms: Option<f64>,
+    pub method: &'static str,
+    pub detail: Option<String>,
+}
+
+impl ReachabilityResult {
+    pub fn summary(&self) -> String {
+        match (self.reachable, self.latency_ms) {
+            (true, Some(ms)) => format!("Reachable via {} in {:.1} ms", self.method, ms),
+            (true, None) => format!("Reachable via {}", self.method),
+            (false, _) => match self.detail.as_deref() {
+                Some(detail) => format!("Unreachable via {}: {}", self.method, detail),
+                None => format!("Unreachable via {}", self.method),
+            },
+        }
+    }
+}
+
+// * Accepts "host", "host:port", "1.2.3.4", "1.2.3.4:port", "fe80::1" and "[fe80::1]:port"
+pub fn parse_target(input: &str) -> Result<ReachabilityTarget> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(anyhow!("Enter a host name or IP address"));
+    }
+
+    if let Some(rest) = input.strip_prefix('[') {
+        let (host, tail) = rest
+            .split_once(']')
+            .ok_or_else(|| anyhow!("Missing closing bracket in IPv6 address"))?;
+        if host.parse::<std::net::Ipv6Addr>().is_err() {
+            return Err(anyhow!("Invalid IPv6 address: {}", host));
+        }
+        let port = match tail {
+            "" => None,
+            _ => {
+                Some(parse_port(tail.strip_prefix(':').ok_or_else(|| {
+                    anyhow!("Unexpected text after IPv6 address")
+                })?)?)
+            }
+        };
+        return Ok(ReachabilityTarget {
+            host: host.to_string(),
+            port,
+        });
+    }
+
+    if input.parse::<std::net::Ipv6Addr>().is_ok() {
+        return Ok(ReachabilityTarget {
+            host: input.to_string(),
+            port: None,
+        });
+    }
+
+    let (host, port) = match input.rsplit_once(':') {
+        Some((host, port)) => (host, Some(parse_port(port)?)),
+        None => (input, None),
+    };
+
+    if host.parse::<std::net::Ipv4Addr>().is_err() && !is_valid_hostname(host) {
+        return Err(anyhow!("Invalid host name: {}", host));
+    }
+
+    Ok(ReachabilityTarget {
+        host: host.to_string(),
+        port,
+    })
+}
+
+pub async fn check_reachability(target: &ReachabilityTarget) -> ReachabilityResult {
+    match target.port {
+        Some(port) => tcp_probe(&target.host, port).await,
+        None => ping_probe(&target.host).await,
+    }
+}
+
+async fn tcp_probe(host: &str, port: u16) -> ReachabilityResult {
+    let started = Instant::now();
+    let outcome = timeout(
+        Duration::from_secs(PROBE_TIMEOUT_SECS),
+        TcpStream::connect((host, port)),
+    )
+    .await;
+
+    let (reachable, detail) = match outcome {
+        Ok(Ok(_stream)) => (true, None),
+        Ok(Err(e)) => (false, Some(e.to_string())),
+        Err(_) => (false, Some("timed out".to_string())),
+    };
+
+    ReachabilityResult {
+        reachable,
+        latency_ms: reachable.then(|| started.elapsed().as_secs_f64() * 1000.0),
+        method: "TCP",
+        detail,
+    }
+}
+
+async fn ping_probe(host: &str) -> ReachabilityResult {
+    let wait = PROBE_TIMEOUT_SECS.to_string();
+    let output = Command::new("ping")
+        .args(["-c", "1", "-W", wait.as_str(), "--", host])
+        .output();
+
+    // * ping has its own -W deadline, the outer timeout also covers slow DNS resolution
+    let output = match timeout(Duration::from_secs(PROBE_TIMEOUT_SECS + 2), output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            return ReachabilityResult {
+                reachable: false,
+                latency_ms: None,
+                method: "ping",
+                detail: Some(format!("failed to run ping: {}", e)),
+            }
+        }
+        Err(_) => {
+            return ReachabilityResult {
+                reachable: false,
+                latency_ms: None,
+                method: "ping",
+                detail: Some("timed out".to_string()),
+            }
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        return ReachabilityResult {
+            reachable: true,
+            latency_ms: parse_ping_latency_ms(&stdout),
+            method: "ping",
+            detail: None,
+        };
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    ReachabilityResult {
+        reachable: false,
+        latency_ms: None,
+        method: "ping",
+        detail: Some(if stderr.is_empty() {
+            "no reply".to_string()
+        } else {
+            stderr
+        }),
+    }
+}
+
+fn parse_port(value: &str) -> Result<u16> {
+    match value.trim().parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(anyhow!("Invalid port: {}", value)),
+    }
+}
+
+fn is_valid_hostname(host: &str) -> bool {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+fn parse_ping_latency_ms(output: &str) -> Option<f64> {
+    output.lines().find_map(|line| {
+        let (_, rest) = line.split_once("time=")?;
+        rest.split_whitespace().next()?.parse::<f64>().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hosts_with_and_without_ports() {
+        assert_eq!(
+            parse_target(" router.lan ").unwrap(),
+            ReachabilityTarget {
+                host: "router.lan".to_string(),
+                port: None
+            }
+        );
+        assert_eq!(parse_target("10.0.0.5:22").unwrap().port, Some(22));
+        assert_eq!(parse_target("fe80::1").unwrap().host, "fe80::1");
+
+        let bracketed = parse_target("[fe80::1]:8080").unwrap();
+        assert_eq!(bracketed.host, "fe80::1");
+        assert_eq!(bracketed.port, Some(8080));
+        assert_eq!(bracketed.label(), "[fe80::1]:8080");
+    }
+
+    #[test]
+    fn rejects_invalid_targets() {
+        assert!(parse_target("").is_err());
+        assert!(parse_target("host:0").is_err());
+        assert!(parse_target("host:70000").is_err());
+        assert!(parse_target("-bad.example").is_err());
+        assert!(parse_target("bad host").is_err());
+        assert!(parse_target("[fe80::1").is_err());
+        assert!(parse_target("[nothex]:80").is_err());
+    }
+
+    #[test]
+    fn extracts_latency_from_ping_output() {
+        let output = "PING 10.0.0.1 (10.0.0.1) 56(84) bytes of data.\n\
+64 bytes from 10.0.0.1: icmp_seq=1 ttl=64 time=2.47 ms\n";
+        assert_eq!(parse_ping_latency_ms(output), Some(2.47));
+        assert_eq!(parse_ping_latency_ms("no reply"), None);
+    }
+}
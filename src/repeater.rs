@@ -0,0 +1,209 @@
+// * ./src/repeater.rs
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::config::HotspotConfig;
+
+// * AP side of a single-adapter repeater, added next to the station interface
+pub const REPEATER_AP_INTERFACE: &str = "adw-hs-ap0";
+
+// * What `iw dev <iface> link` reports about the network being extended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StationLink {
+    pub ssid: String,
+    pub frequency: u32,
+    pub signal_dbm: i32,
+    pub rx_bitrate_mbit: Option<f32>,
+    pub tx_bitrate_mbit: Option<f32>,
+}
+
+// * Where the repeater ended up: the AP interface to host on, the station joined upstream,
+// * and the config adjusted for a shared radio.
+pub struct RepeaterSetup {
+    pub ap_interface: String,
+    pub station: String,
+    pub config: HotspotConfig,
+}
+
+fn parse_bitrate(value: &str) -> Option<f32> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+fn parse_station_link(link: &str) -> Option<StationLink> {
+    if !link.trim_start().starts_with("Connected to") {
+        return None;
+    }
+    let field = |name: &str| {
+        link.lines()
+            .find_map(|line| line.trim().strip_prefix(name))
+            .map(str::trim)
+    };
+    Some(StationLink {
+        ssid: field("SSID:")?.to_string(),
+        frequency: field("freq:")?.parse::<f32>().ok()? as u32,
+        signal_dbm: field("signal:")?.split_whitespace().next()?.parse().ok()?,
+        rx_bitrate_mbit: field("rx bitrate:").and_then(parse_bitrate),
+        tx_bitrate_mbit: field("tx bitrate:").and_then(parse_bitrate),
+    })
+}
+
+pub async fn station_link(iface: &str) -> Option<StationLink> {
+    let output = crate::nm::command_output(Command::new("iw").args(["dev", iface, "link"]))
+        .await
+        .ok()?;
+    parse_station_link(&String::from_utf8_lossy(&output.stdout))
+}
+
+async fn run_iw(args: &[&str]) -> Result<()> {
+    let output = crate::nm::command_output(Command::new("iw").args(args)).await?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        Err(anyhow!("iw command failed"))
+    } else {
+        Err(anyhow!(stderr))
+    }
+}
+
+// * Joins the network through its saved profile, so NM already has the password.
+async fn join_upstream(station: &str, ssid: &str) -> Result<StationLink> {
+    if let Some(link) = station_link(station).await.filter(|link| link.ssid == ssid) {
+        return Ok(link);
+    }
+    let saved = crate::nm::get_saved_connections()
+        .await?
+        .into_iter()
+        .find(|connection| connection.ssid == ssid)
+        .ok_or_else(|| {
+            anyhow!(
+                "Connect to {} once from the Wi-Fi page so the repeater can join it",
+                ssid
+            )
+        })?;
+    let client = crate::nm::dbus_client().await?;
+    let timeout = crate::nm::operation_timeout();
+    let active_path = crate::nm::with_timeout(
+        timeout,
+        &format!("Joining {}", ssid),
+        client.activate_connection_by_uuid(&saved.uuid, Some(station)),
+    )
+    .await?;
+    crate::nm::with_timeout(
+        timeout,
+        &format!("Joining {}", ssid),
+        client.wait_for_active_connection_activated(&active_path),
+    )
+    .await?;
+    station_link(station)
+        .await
+        .ok_or_else(|| anyhow!("{} joined {} but reports no link", station, ssid))
+}
+
+// * A second adapter keeps the station separate; otherwise the hotspot adapter does both jobs
+// * through a virtual AP interface, which has to stay on the station's channel.
+pub async fn prepare(config: &HotspotConfig, iface: &str) -> Result<RepeaterSetup> {
+    let ssid = config
+        .repeater_ssid
+        .as_deref()
+        .ok_or_else(|| anyhow!("Repeater mode needs a network to extend"))?;
+    let station = crate::hotspot::get_wifi_devices()
+        .await?
+        .into_iter()
+        .find(|device| device != iface)
+        .unwrap_or_else(|| iface.to_string());
+    let link = join_upstream(&station, ssid).await?;
+
+    if station != iface {
+        info!("Repeating {} from {} on {}", ssid, station, iface);
+        return Ok(RepeaterSetup {
+            ap_interface: iface.to_string(),
+            station,
+            config: config.clone(),
+        });
+    }
+
+    if crate::hotspot::ap_sta_concurrency(&station).await
+        == Some(crate::hotspot::ApStaConcurrency::Unsupported)
+    {
+        return Err(anyhow!(
+            "{} can't host a hotspot while connected to {}; a repeater needs a second Wi-Fi adapter",
+            station,
+            ssid
+        ));
+    }
+    if !std::path::Path::new("/sys/class/net")
+        .join(REPEATER_AP_INTERFACE)
+        .exists()
+    {
+        run_iw(&[
+            "dev",
+            &station,
+            "interface",
+            "add",
+            REPEATER_AP_INTERFACE,
+            "type",
+            "__ap",
+        ])
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "{} can't host a hotspot while connected ({}); try a second Wi-Fi adapter",
+                station,
+                e
+            )
+        })?;
+        // * NM picks the new interface up asynchronously
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    info!(
+        "Repeating {} on {} alongside {} at {} MHz",
+        ssid, REPEATER_AP_INTERFACE, station, link.frequency
+    );
+    Ok(RepeaterSetup {
+        ap_interface: REPEATER_AP_INTERFACE.to_string(),
+        station,
+        config: HotspotConfig {
+            band: crate::nm::band_from_frequency(link.frequency).to_string(),
+            channel: crate::nm::channel_from_frequency(link.frequency).to_string(),
+            ..config.clone()
+        },
+    })
+}
+
+pub async fn remove_virtual_interface() {
+    if !std::path::Path::new("/sys/class/net")
+        .join(REPEATER_AP_INTERFACE)
+        .exists()
+    {
+        return;
+    }
+    if let Err(e) = run_iw(&["dev", REPEATER_AP_INTERFACE, "del"]).await {
+        warn!("Failed to remove {}: {}", REPEATER_AP_INTERFACE, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_upstream_link() {
+        let link = "Connected to aa:bb:cc:dd:ee:ff (on wlan0)\n\tSSID: Cafe\n\tfreq: 5180.0\n\tRX: 1024 bytes (8 packets)\n\tsignal: -54 dBm\n\trx bitrate: 433.3 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 1\n\ttx bitrate: 390.0 MBit/s\n";
+        assert_eq!(
+            parse_station_link(link),
+            Some(StationLink {
+                ssid: "Cafe".to_string(),
+                frequency: 5180,
+                signal_dbm: -54,
+                rx_bitrate_mbit: Some(433.3),
+                tx_bitrate_mbit: Some(390.0),
+            })
+        );
+        assert_eq!(parse_station_link("Not connected.\n"), None);
+    }
+}
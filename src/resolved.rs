@@ -0,0 +1,99 @@
+// * ./src/resolved.rs
+
+use anyhow::{Context, Result};
+use zbus::{Connection, Proxy};
+use zvariant::OwnedObjectPath;
+
+const RESOLVED_SERVICE: &str = "org.freedesktop.resolve1";
+const RESOLVED_PATH: &str = "/org/freedesktop/resolve1";
+const RESOLVED_MANAGER_IFACE: &str = "org.freedesktop.resolve1.Manager";
+const RESOLVED_LINK_IFACE: &str = "org.freedesktop.resolve1.Link";
+
+// * What systemd-resolved actually does for a link, which can differ from what the profile asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnsEncryption {
+    // * resolved isn't running, so NM's DNS-over-TLS setting has no effect
+    Unavailable,
+    Encrypted,
+    Opportunistic,
+    Plaintext,
+}
+
+impl DnsEncryption {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unavailable => "Not available — systemd-resolved is not running",
+            Self::Encrypted => "Encrypted with DNS-over-TLS",
+            Self::Opportunistic => "Encrypted when the server supports it",
+            Self::Plaintext => "Not encrypted",
+        }
+    }
+}
+
+pub async fn dns_encryption_for_interface(interface: &str) -> Result<DnsEncryption> {
+    let Ok(conn) = Connection::system().await else {
+        return Ok(DnsEncryption::Unavailable);
+    };
+    let manager = Proxy::new(
+        &conn,
+        RESOLVED_SERVICE,
+        RESOLVED_PATH,
+        RESOLVED_MANAGER_IFACE,
+    )
+    .await?;
+    let global: String = match manager.get_property("DNSOverTLS").await {
+        Ok(mode) => mode,
+        Err(e) => {
+            log::debug!("systemd-resolved not reachable: {}", e);
+            return Ok(DnsEncryption::Unavailable);
+        }
+    };
+
+    let ifindex = interface_index(interface).await?;
+    let link_path: OwnedObjectPath = manager
+        .call("GetLink", &(ifindex,))
+        .await
+        .with_context(|| format!("systemd-resolved does not manage {}", interface))?;
+    let link = Proxy::new(&conn, RESOLVED_SERVICE, link_path, RESOLVED_LINK_IFACE).await?;
+    let link_mode: String = link.get_property("DNSOverTLS").await.unwrap_or_default();
+
+    Ok(effective_encryption(&link_mode, &global))
+}
+
+async fn interface_index(interface: &str) -> Result<i32> {
+    let path = format!("/sys/class/net/{}/ifindex", interface);
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("Interface {} not found", interface))?;
+    Ok(text.trim().parse()?)
+}
+
+// * An empty per-link mode falls back to the global resolved.conf setting.
+fn effective_encryption(link_mode: &str, global_mode: &str) -> DnsEncryption {
+    let mode = if link_mode.is_empty() {
+        global_mode
+    } else {
+        link_mode
+    };
+    match mode {
+        "yes" => DnsEncryption::Encrypted,
+        "opportunistic" => DnsEncryption::Opportunistic,
+        _ => DnsEncryption::Plaintext,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_mode_overrides_the_global_default() {
+        assert_eq!(effective_encryption("", "yes"), DnsEncryption::Encrypted);
+        assert_eq!(effective_encryption("no", "yes"), DnsEncryption::Plaintext);
+        assert_eq!(
+            effective_encryption("opportunistic", "no"),
+            DnsEncryption::Opportunistic
+        );
+        assert_eq!(effective_encryption("", ""), DnsEncryption::Plaintext);
+    }
+}
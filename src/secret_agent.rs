@@ -0,0 +1,270 @@
+// * ./src/secret_agent.rs
+
+use anyhow::Result;
+use futures::future::{AbortHandle, Abortable};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::{mpsc, oneshot};
+use zbus::{interface, Connection, Proxy};
+use zeroize::Zeroizing;
+use zvariant::{OwnedObjectPath, OwnedValue, Str};
+
+use crate::nm::{owned_string_map, value_string};
+use crate::nm_dbus::SettingsMap;
+
+const NM_SERVICE: &str = "org.freedesktop.NetworkManager";
+const AGENT_MANAGER_PATH: &str = "/org/freedesktop/NetworkManager/AgentManager";
+const AGENT_MANAGER_IFACE: &str = "org.freedesktop.NetworkManager.AgentManager";
+// * NM always calls agents at this fixed path on the connection that registered them.
+const AGENT_PATH: &str = "/org/freedesktop/NetworkManager/SecretAgent";
+
+const WIFI_SECURITY_SETTING: &str = "802-11-wireless-security";
+const VPN_SETTING: &str = "vpn";
+// * Hints with this prefix carry a message from the VPN plugin, not a secret name.
+const VPN_MESSAGE_HINT_PREFIX: &str = "x-vpn-message:";
+const GET_SECRETS_FLAG_ALLOW_INTERACTION: u32 = 0x1;
+const GET_SECRETS_FLAG_REQUEST_NEW: u32 = 0x2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretKind {
+    Wifi,
+    Vpn,
+}
+
+// * One password prompt; dropping `reply` without sending counts as the user cancelling.
+pub struct SecretRequest {
+    pub kind: SecretKind,
+    pub connection_id: String,
+    // * NM already tried a stored secret and it was rejected
+    pub retry: bool,
+    pub reply: oneshot::Sender<Option<Zeroizing<String>>>,
+}
+
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.freedesktop.NetworkManager.SecretAgent")]
+enum AgentError {
+    #[zbus(error)]
+    ZBus(zbus::Error),
+    NoSecrets(String),
+    UserCanceled(String),
+}
+
+struct SecretAgent {
+    requests: mpsc::UnboundedSender<SecretRequest>,
+    pending: Mutex<HashMap<String, AbortHandle>>,
+}
+
+impl SecretAgent {
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, AbortHandle>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[interface(name = "org.freedesktop.NetworkManager.SecretAgent")]
+impl SecretAgent {
+    async fn get_secrets(
+        &self,
+        connection: SettingsMap,
+        connection_path: OwnedObjectPath,
+        setting_name: String,
+        hints: Vec<String>,
+        flags: u32,
+    ) -> Result<SettingsMap, AgentError> {
+        let kind = match setting_name.as_str() {
+            WIFI_SECURITY_SETTING => SecretKind::Wifi,
+            VPN_SETTING => SecretKind::Vpn,
+            _ => {
+                return Err(AgentError::NoSecrets(format!(
+                    "No secrets handled for {}",
+                    setting_name
+                )))
+            }
+        };
+        if flags & GET_SECRETS_FLAG_ALLOW_INTERACTION == 0 {
+            return Err(AgentError::NoSecrets(
+                "Secrets are only kept by NetworkManager".to_string(),
+            ));
+        }
+
+        let (reply, answer) = oneshot::channel();
+        self.requests
+            .send(SecretRequest {
+                kind,
+                connection_id: connection_id(&connection),
+                retry: flags & GET_SECRETS_FLAG_REQUEST_NEW != 0,
+                reply,
+            })
+            .map_err(|_| AgentError::NoSecrets("No window to ask in".to_string()))?;
+
+        let key = pending_key(&connection_path, &setting_name);
+        let (abort, registration) = AbortHandle::new_pair();
+        self.pending().insert(key.clone(), abort);
+        let answer = Abortable::new(answer, registration).await;
+        self.pending().remove(&key);
+
+        match answer {
+            Ok(Ok(Some(secret))) => match kind {
+                SecretKind::Wifi => Ok(secrets_reply(secret_key(&connection), &secret)),
+                SecretKind::Vpn => vpn_secrets_reply(vpn_secret_key(&hints), &secret)
+                    .map_err(|e| AgentError::NoSecrets(e.to_string())),
+            },
+            _ => Err(AgentError::UserCanceled(
+                "The password prompt was dismissed".to_string(),
+            )),
+        }
+    }
+
+    // * Aborting drops the reply receiver, which closes the open prompt.
+    async fn cancel_get_secrets(&self, connection_path: OwnedObjectPath, setting_name: String) {
+        if let Some(abort) = self
+            .pending()
+            .remove(&pending_key(&connection_path, &setting_name))
+        {
+            abort.abort();
+        }
+    }
+
+    // ? Nothing is persisted here: agent-owned secrets are asked for again on the next connect
+    async fn save_secrets(&self, _connection: SettingsMap, _connection_path: OwnedObjectPath) {}
+
+    async fn delete_secrets(&self, _connection: SettingsMap, _connection_path: OwnedObjectPath) {}
+}
+
+fn requests_cell() -> &'static Mutex<Option<mpsc::UnboundedReceiver<SecretRequest>>> {
+    static REQUESTS: OnceLock<Mutex<Option<mpsc::UnboundedReceiver<SecretRequest>>>> =
+        OnceLock::new();
+    REQUESTS.get_or_init(|| Mutex::new(None))
+}
+
+// * Exports the agent and registers it with NM; the connection is kept for the app's lifetime.
+pub async fn register(identifier: &str) -> Result<()> {
+    static CONNECTION: OnceLock<Connection> = OnceLock::new();
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let conn = Connection::system().await?;
+    conn.object_server()
+        .at(
+            AGENT_PATH,
+            SecretAgent {
+                requests: sender,
+                pending: Mutex::new(HashMap::new()),
+            },
+        )
+        .await?;
+
+    let manager = Proxy::new(&conn, NM_SERVICE, AGENT_MANAGER_PATH, AGENT_MANAGER_IFACE).await?;
+    manager.call::<_, _, ()>("Register", &(identifier,)).await?;
+
+    *requests_cell()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(receiver);
+    let _ = CONNECTION.set(conn);
+    log::info!("Registered as NetworkManager secret agent");
+    Ok(())
+}
+
+// * Handed to the window once; None when registration failed or it was already taken.
+pub fn take_requests() -> Option<mpsc::UnboundedReceiver<SecretRequest>> {
+    requests_cell()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+}
+
+fn pending_key(connection_path: &OwnedObjectPath, setting_name: &str) -> String {
+    format!("{}/{}", connection_path.as_str(), setting_name)
+}
+
+fn connection_id(connection: &SettingsMap) -> String {
+    connection
+        .get("connection")
+        .and_then(|section| section.get("id"))
+        .and_then(value_string)
+        .unwrap_or_else(|| "this network".to_string())
+}
+
+// * WEP profiles keep their key under a different name than WPA/SAE ones.
+fn secret_key(connection: &SettingsMap) -> &'static str {
+    let key_mgmt = connection
+        .get(WIFI_SECURITY_SETTING)
+        .and_then(|section| section.get("key-mgmt"))
+        .and_then(value_string);
+    if key_mgmt.as_deref() == Some("none") {
+        "wep-key0"
+    } else {
+        "psk"
+    }
+}
+
+fn secrets_reply(key: &str, secret: &str) -> SettingsMap {
+    let mut section = HashMap::new();
+    section.insert(key.to_string(), OwnedValue::from(Str::from(secret)));
+    HashMap::from([(WIFI_SECURITY_SETTING.to_string(), section)])
+}
+
+// * NM names the missing VPN secret in the hints; OpenVPN asks for "password" or "cert-pass".
+fn vpn_secret_key(hints: &[String]) -> &str {
+    hints
+        .iter()
+        .map(String::as_str)
+        .find(|hint| !hint.starts_with(VPN_MESSAGE_HINT_PREFIX))
+        .unwrap_or("password")
+}
+
+// * VPN secrets travel as an a{ss} under vpn.secrets rather than as plain keys.
+fn vpn_secrets_reply(key: &str, secret: &str) -> Result<SettingsMap> {
+    let secrets = HashMap::from([(key.to_string(), secret.to_string())]);
+    let mut section = HashMap::new();
+    section.insert("secrets".to_string(), owned_string_map(secrets)?);
+    Ok(HashMap::from([(VPN_SETTING.to_string(), section)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(entries: &[(&str, &str)]) -> HashMap<String, OwnedValue> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), OwnedValue::from(Str::from(*value))))
+            .collect()
+    }
+
+    #[test]
+    fn answers_with_the_key_the_profile_uses() {
+        let wep: SettingsMap = HashMap::from([
+            ("connection".to_string(), section(&[("id", "Attic")])),
+            (
+                WIFI_SECURITY_SETTING.to_string(),
+                section(&[("key-mgmt", "none")]),
+            ),
+        ]);
+        assert_eq!(connection_id(&wep), "Attic");
+        assert_eq!(secret_key(&wep), "wep-key0");
+        assert_eq!(secret_key(&HashMap::new()), "psk");
+
+        let reply = secrets_reply("psk", "correct horse");
+        let psk = reply[WIFI_SECURITY_SETTING]
+            .get("psk")
+            .and_then(value_string);
+        assert_eq!(psk.as_deref(), Some("correct horse"));
+    }
+
+    #[test]
+    fn answers_vpn_prompts_under_vpn_secrets() {
+        let hints = vec![
+            "x-vpn-message:Enter your token".to_string(),
+            "cert-pass".to_string(),
+        ];
+        assert_eq!(vpn_secret_key(&hints), "cert-pass");
+        assert_eq!(vpn_secret_key(&[]), "password");
+
+        let reply = vpn_secrets_reply("password", "hunter2").unwrap();
+        let secrets =
+            HashMap::<String, String>::try_from(reply[VPN_SETTING]["secrets"].try_clone().unwrap())
+                .unwrap();
+        assert_eq!(secrets.get("password").map(String::as_str), Some("hunter2"));
+    }
+}
@@ -5,26 +5,19 @@ use keyring::Error as KeyringError;
 
 const KEYRING_SERVICE: &str = "adw-network";
 const KEYRING_USERNAME: &str = "hotspot-password";
+// * Separate entry so switching storage backends never clears the backup.
+const KEYRING_BACKUP_USERNAME: &str = "hotspot-password-backup";
 
 pub fn store_hotspot_password(password: &str) -> Result<()> {
     if password.is_empty() {
         return delete_hotspot_password();
     }
 
-    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USERNAME)?;
-    entry
-        .set_password(password)
-        .map_err(|e| anyhow!("Keyring save failed: {}", e))?;
-    Ok(())
+    store_entry(KEYRING_USERNAME, password)
 }
 
 pub fn load_hotspot_password() -> Result<Option<String>> {
-    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USERNAME)?;
-    match entry.get_password() {
-        Ok(password) => Ok(Some(password)),
-        Err(KeyringError::NoEntry) => Ok(None),
-        Err(e) => Err(anyhow!("Keyring read failed: {}", e)),
-    }
+    load_entry(KEYRING_USERNAME)
 }
 
 pub fn delete_hotspot_password() -> Result<()> {
@@ -36,3 +29,33 @@ pub fn delete_hotspot_password() -> Result<()> {
     }
     Ok(())
 }
+
+// * Last password a hotspot successfully started with, whatever the storage backend.
+pub fn store_hotspot_password_backup(password: &str) -> Result<()> {
+    if password.is_empty() {
+        return Ok(());
+    }
+
+    store_entry(KEYRING_BACKUP_USERNAME, password)
+}
+
+pub fn load_hotspot_password_backup() -> Result<Option<String>> {
+    load_entry(KEYRING_BACKUP_USERNAME)
+}
+
+fn store_entry(username: &str, password: &str) -> Result<()> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, username)?;
+    entry
+        .set_password(password)
+        .map_err(|e| anyhow!("Keyring save failed: {}", e))?;
+    Ok(())
+}
+
+fn load_entry(username: &str) -> Result<Option<String>> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, username)?;
+    match entry.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(KeyringError::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow!("Keyring read failed: {}", e)),
+    }
+}
@@ -1,7 +1,7 @@
 // * ./src/state.rs
 
 use crate::config::AppSettings;
-use crate::nm::{Connection, WifiNetwork};
+use crate::nm::{Connection, SavedConnection, WifiNetwork};
 use crate::profiles::NetworkProfile;
 use gtk4::glib;
 use std::collections::HashSet;
@@ -53,10 +53,9 @@ struct WifiSharedState {
     busy_count: AtomicU32,
     search_text: RwLock<String>,
     all_networks: RwLock<Vec<WifiNetwork>>,
-    saved_ssids: RwLock<HashSet<String>>,
+    saved_connections: RwLock<Vec<SavedConnection>>,
     filter_state: RwLock<WifiFilterState>,
     connected_network: RwLock<Option<WifiNetwork>>,
-    refresh_source: RwLock<Option<glib::SourceId>>,
     search_debounce_source: RwLock<Option<glib::SourceId>>,
     enabled: AtomicBool,
     scan_complete: AtomicBool,
@@ -70,8 +69,6 @@ struct HotspotSharedState {
     wifi_present: AtomicBool,
     wifi_enabled: AtomicBool,
     operation_in_progress: AtomicBool,
-    status_refresh_source: RwLock<Option<glib::SourceId>>,
-    interface_refresh_source: RwLock<Option<glib::SourceId>>,
 }
 
 #[derive(Debug, Default)]
@@ -79,7 +76,6 @@ struct HotspotSharedState {
 struct DevicesSharedState {
     auto_refresh_active: AtomicBool,
     refresh_in_flight: AtomicBool,
-    refresh_source: RwLock<Option<glib::SourceId>>,
 }
 
 #[derive(Debug, Default)]
@@ -231,9 +227,7 @@ impl AppState {
     }
 
     pub fn wifi_scan_complete_with_no_networks(&self) -> bool {
-        self.wifi_scan_complete()
-            && self.wifi_enabled()
-            && self.wifi_all_networks().is_empty()
+        self.wifi_scan_complete() && self.wifi_enabled() && self.wifi_all_networks().is_empty()
     }
 
     pub fn wifi_search_text(&self) -> String {
@@ -257,15 +251,25 @@ impl AppState {
     }
 
     pub fn wifi_saved_ssids(&self) -> HashSet<String> {
-        Self::read_guard(&self.wifi.saved_ssids).clone()
+        Self::read_guard(&self.wifi.saved_connections)
+            .iter()
+            .map(|saved| saved.ssid.clone())
+            .collect()
     }
 
-    pub fn set_wifi_saved_ssids(&self, value: HashSet<String>) {
-        *Self::write_guard(&self.wifi.saved_ssids) = value;
+    pub fn wifi_saved_connection(&self, ssid: &str) -> Option<SavedConnection> {
+        Self::read_guard(&self.wifi.saved_connections)
+            .iter()
+            .find(|saved| saved.ssid == ssid)
+            .cloned()
     }
 
-    pub fn clear_wifi_saved_ssids(&self) {
-        Self::write_guard(&self.wifi.saved_ssids).clear();
+    pub fn set_wifi_saved_connections(&self, value: Vec<SavedConnection>) {
+        *Self::write_guard(&self.wifi.saved_connections) = value;
+    }
+
+    pub fn clear_wifi_saved_connections(&self) {
+        Self::write_guard(&self.wifi.saved_connections).clear();
     }
 
     pub fn wifi_filter_state(&self) -> WifiFilterState {
@@ -284,18 +288,6 @@ impl AppState {
         *Self::write_guard(&self.wifi.connected_network) = value;
     }
 
-    pub fn take_wifi_refresh_source(&self) -> Option<glib::SourceId> {
-        Self::write_guard(&self.wifi.refresh_source).take()
-    }
-
-    pub fn set_wifi_refresh_source(&self, source: Option<glib::SourceId>) {
-        *Self::write_guard(&self.wifi.refresh_source) = source;
-    }
-
-    pub fn wifi_has_refresh_source(&self) -> bool {
-        Self::read_guard(&self.wifi.refresh_source).is_some()
-    }
-
     pub fn take_wifi_search_debounce_source(&self) -> Option<glib::SourceId> {
         Self::write_guard(&self.wifi.search_debounce_source).take()
     }
@@ -350,30 +342,6 @@ impl AppState {
             .store(value, Ordering::Relaxed);
     }
 
-    pub fn take_hotspot_status_refresh_source(&self) -> Option<glib::SourceId> {
-        Self::write_guard(&self.hotspot.status_refresh_source).take()
-    }
-
-    pub fn set_hotspot_status_refresh_source(&self, source: Option<glib::SourceId>) {
-        *Self::write_guard(&self.hotspot.status_refresh_source) = source;
-    }
-
-    pub fn hotspot_has_status_refresh_source(&self) -> bool {
-        Self::read_guard(&self.hotspot.status_refresh_source).is_some()
-    }
-
-    pub fn take_hotspot_interface_refresh_source(&self) -> Option<glib::SourceId> {
-        Self::write_guard(&self.hotspot.interface_refresh_source).take()
-    }
-
-    pub fn set_hotspot_interface_refresh_source(&self, source: Option<glib::SourceId>) {
-        *Self::write_guard(&self.hotspot.interface_refresh_source) = source;
-    }
-
-    pub fn hotspot_has_interface_refresh_source(&self) -> bool {
-        Self::read_guard(&self.hotspot.interface_refresh_source).is_some()
-    }
-
     pub fn devices_auto_refresh_active(&self) -> bool {
         self.devices.auto_refresh_active.load(Ordering::Relaxed)
     }
@@ -394,18 +362,6 @@ impl AppState {
             .store(value, Ordering::Relaxed);
     }
 
-    pub fn take_devices_refresh_source(&self) -> Option<glib::SourceId> {
-        Self::write_guard(&self.devices.refresh_source).take()
-    }
-
-    pub fn set_devices_refresh_source(&self, source: Option<glib::SourceId>) {
-        *Self::write_guard(&self.devices.refresh_source) = source;
-    }
-
-    pub fn devices_has_refresh_source(&self) -> bool {
-        Self::read_guard(&self.devices.refresh_source).is_some()
-    }
-
     pub fn ethernet_connections(&self) -> Vec<Connection> {
         Self::read_guard(&self.ethernet.connections).clone()
     }
@@ -0,0 +1,211 @@
+// * ./src/tailscale.rs
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::process::Command;
+
+use crate::nm::command_output;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitNode {
+    // * What `tailscale set --exit-node` accepts; the first Tailscale IP of the peer
+    pub address: String,
+    pub name: String,
+    pub online: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TailscaleStatus {
+    // * "Running", "Stopped", "NeedsLogin", ...
+    pub backend_state: String,
+    pub node_name: String,
+    pub addresses: Vec<String>,
+    pub exit_node: Option<String>,
+    pub exit_nodes: Vec<ExitNode>,
+}
+
+impl TailscaleStatus {
+    pub fn is_up(&self) -> bool {
+        self.backend_state == "Running"
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StatusJson {
+    #[serde(default)]
+    backend_state: String,
+    #[serde(rename = "Self")]
+    self_node: Option<PeerJson>,
+    #[serde(default)]
+    peer: HashMap<String, PeerJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PeerJson {
+    #[serde(default)]
+    host_name: String,
+    #[serde(rename = "DNSName", default)]
+    dns_name: String,
+    #[serde(rename = "TailscaleIPs", default)]
+    tailscale_ips: Vec<String>,
+    #[serde(default)]
+    online: bool,
+    #[serde(default)]
+    exit_node: bool,
+    #[serde(default)]
+    exit_node_option: bool,
+}
+
+impl PeerJson {
+    // * MagicDNS names are fully qualified; the first label is what the admin console shows
+    fn display_name(&self) -> String {
+        self.dns_name
+            .split('.')
+            .next()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.host_name)
+            .to_string()
+    }
+}
+
+// * None when the CLI is missing or tailscaled isn't running, so the card stays hidden.
+pub async fn status() -> Option<TailscaleStatus> {
+    let output = command_output(Command::new("tailscale").args(["status", "--json"]))
+        .await
+        .ok()?;
+    if !output.status.success() {
+        log::debug!(
+            "tailscale status failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    match parse_status(&String::from_utf8_lossy(&output.stdout)) {
+        Ok(status) => Some(status),
+        Err(e) => {
+            log::warn!("Failed to parse tailscale status: {}", e);
+            None
+        }
+    }
+}
+
+fn parse_status(json: &str) -> Result<TailscaleStatus> {
+    let parsed: StatusJson = serde_json::from_str(json)?;
+
+    let mut exit_nodes: Vec<ExitNode> = parsed
+        .peer
+        .values()
+        .filter(|peer| peer.exit_node_option)
+        .filter_map(|peer| {
+            Some(ExitNode {
+                address: peer.tailscale_ips.first()?.clone(),
+                name: peer.display_name(),
+                online: peer.online,
+            })
+        })
+        .collect();
+    exit_nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let exit_node = parsed
+        .peer
+        .values()
+        .find(|peer| peer.exit_node)
+        .map(PeerJson::display_name);
+
+    let (node_name, addresses) = parsed
+        .self_node
+        .map(|node| (node.display_name(), node.tailscale_ips))
+        .unwrap_or_default();
+
+    Ok(TailscaleStatus {
+        backend_state: parsed.backend_state,
+        node_name,
+        addresses,
+        exit_node,
+        exit_nodes,
+    })
+}
+
+pub async fn set_up(up: bool) -> Result<()> {
+    run_tailscale(&[if up { "up" } else { "down" }]).await
+}
+
+// * An empty address routes traffic directly again.
+pub async fn set_exit_node(address: Option<&str>) -> Result<()> {
+    let arg = format!("--exit-node={}", address.unwrap_or(""));
+    run_tailscale(&["set", &arg]).await
+}
+
+async fn run_tailscale(args: &[&str]) -> Result<()> {
+    let output = command_output(Command::new("tailscale").args(args)).await?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    // ? Changing prefs needs `tailscale set --operator=$USER` once, or root
+    if stderr.contains("Access denied") || stderr.contains("access denied") {
+        return Err(anyhow!(
+            "Tailscale refused the change. Run \"sudo tailscale set --operator=$USER\" once to allow it"
+        ));
+    }
+    Err(anyhow!("tailscale {} failed: {}", args.join(" "), stderr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_node_and_exit_nodes() {
+        let json = r#"{
+            "BackendState": "Running",
+            "Self": {
+                "HostName": "laptop",
+                "DNSName": "laptop.tail1234.ts.net.",
+                "TailscaleIPs": ["100.64.0.2", "fd7a:115c:a1e0::2"],
+                "Online": true
+            },
+            "Peer": {
+                "nodekey:a": {
+                    "HostName": "Home Server",
+                    "DNSName": "homeserver.tail1234.ts.net.",
+                    "TailscaleIPs": ["100.64.0.5"],
+                    "Online": true,
+                    "ExitNode": true,
+                    "ExitNodeOption": true
+                },
+                "nodekey:b": {
+                    "HostName": "phone",
+                    "DNSName": "phone.tail1234.ts.net.",
+                    "TailscaleIPs": ["100.64.0.9"],
+                    "Online": false
+                }
+            }
+        }"#;
+        let status = parse_status(json).unwrap();
+
+        assert!(status.is_up());
+        assert_eq!(status.node_name, "laptop");
+        assert_eq!(status.addresses[0], "100.64.0.2");
+        assert_eq!(status.exit_node.as_deref(), Some("homeserver"));
+        assert_eq!(
+            status.exit_nodes,
+            vec![ExitNode {
+                address: "100.64.0.5".to_string(),
+                name: "homeserver".to_string(),
+                online: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn stopped_daemon_has_no_self_node() {
+        let status = parse_status(r#"{"BackendState": "Stopped", "Self": null}"#).unwrap();
+        assert!(!status.is_up());
+        assert!(status.node_name.is_empty());
+        assert!(status.exit_nodes.is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+// * ./src/traffic.rs
+
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib;
+use tokio::sync::mpsc;
+
+use crate::nm;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrafficEvent {
+    // * Sent before any sample from the new interface; subscribers drop per-interface state.
+    InterfaceChanged {
+        previous: Option<String>,
+        current: Option<String>,
+    },
+    Sample(TrafficSample),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrafficSample {
+    pub interface: String,
+    pub down_bytes_per_sec: u64,
+    pub up_bytes_per_sec: u64,
+    pub session_rx_bytes: u64,
+    pub session_tx_bytes: u64,
+}
+
+// * Single source of truth for which interface the speed meter, graph and session usage follow.
+#[derive(Debug, Default)]
+pub struct InterfaceTracker {
+    current: Option<String>,
+    last_counters: Option<(u64, u64)>,
+    session_rx: u64,
+    session_tx: u64,
+}
+
+impl InterfaceTracker {
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    pub fn observe(
+        &mut self,
+        iface: Option<&str>,
+        counters: Option<(u64, u64)>,
+    ) -> Vec<TrafficEvent> {
+        let mut events = Vec::new();
+
+        if self.current.as_deref() != iface {
+            events.push(TrafficEvent::InterfaceChanged {
+                previous: self.current.take(),
+                current: iface.map(str::to_string),
+            });
+            self.current = iface.map(str::to_string);
+            self.reset_counters();
+        }
+
+        let Some(iface) = iface else {
+            return events;
+        };
+        // ? None: the interface vanished from sysfs between the NM query and the read.
+        // ? Report it as idle so the meter and graph drop to zero instead of freezing.
+        // * Counters going backwards mean the device was recreated under the same name
+        let (down, up) = match (counters, self.last_counters) {
+            (Some((rx, tx)), Some((prev_rx, prev_tx))) if rx >= prev_rx && tx >= prev_tx => {
+                (rx - prev_rx, tx - prev_tx)
+            }
+            _ => (0, 0),
+        };
+        self.last_counters = counters;
+        self.session_rx = self.session_rx.saturating_add(down);
+        self.session_tx = self.session_tx.saturating_add(up);
+
+        events.push(TrafficEvent::Sample(TrafficSample {
+            interface: iface.to_string(),
+            down_bytes_per_sec: down,
+            up_bytes_per_sec: up,
+            session_rx_bytes: self.session_rx,
+            session_tx_bytes: self.session_tx,
+        }));
+        events
+    }
+
+    fn reset_counters(&mut self) {
+        self.last_counters = None;
+        self.session_rx = 0;
+        self.session_tx = 0;
+    }
+}
+
+type Subscriber = Box<dyn Fn(&TrafficEvent)>;
+
+// * UI-side fan-out of tracker events; callbacks run on the GTK main loop.
+#[derive(Clone, Default)]
+pub struct TrafficMonitor {
+    subscribers: Rc<RefCell<Vec<Subscriber>>>,
+}
+
+impl TrafficMonitor {
+    pub fn start() -> Self {
+        let monitor = Self::default();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            let mut tracker = InterfaceTracker::default();
+            loop {
+                interval.tick().await;
+                let iface = nm::get_primary_connected_device().await.ok().flatten();
+                let counters = iface.as_deref().and_then(read_interface_bytes);
+                for event in tracker.observe(iface.as_deref(), counters) {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        let subscribers = Rc::clone(&monitor.subscribers);
+        glib::spawn_future_local(async move {
+            while let Some(event) = receiver.recv().await {
+                if let TrafficEvent::InterfaceChanged { previous, current } = &event {
+                    log::info!("Tracked interface changed: {:?} -> {:?}", previous, current);
+                }
+                for subscriber in subscribers.borrow().iter() {
+                    subscriber(&event);
+                }
+            }
+        });
+
+        monitor
+    }
+
+    pub fn subscribe(&self, callback: impl Fn(&TrafficEvent) + 'static) {
+        self.subscribers.borrow_mut().push(Box::new(callback));
+    }
+}
+
+pub fn read_interface_bytes(iface: &str) -> Option<(u64, u64)> {
+    let rx_path = format!("/sys/class/net/{}/statistics/rx_bytes", iface);
+    let tx_path = format!("/sys/class/net/{}/statistics/tx_bytes", iface);
+    let rx = fs::read_to_string(rx_path)
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let tx = fs::read_to_string(tx_path)
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some((rx, tx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(events: &[TrafficEvent]) -> &TrafficSample {
+        match events.last() {
+            Some(TrafficEvent::Sample(sample)) => sample,
+            other => panic!("expected a sample, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accumulates_session_usage_on_one_interface() {
+        let mut tracker = InterfaceTracker::default();
+        let first = tracker.observe(Some("wlan0"), Some((1_000, 500)));
+        assert!(matches!(first[0], TrafficEvent::InterfaceChanged { .. }));
+        assert_eq!(sample(&first).down_bytes_per_sec, 0);
+
+        let second = tracker.observe(Some("wlan0"), Some((4_000, 700)));
+        assert_eq!(second.len(), 1);
+        assert_eq!(sample(&second).down_bytes_per_sec, 3_000);
+        assert_eq!(sample(&second).up_bytes_per_sec, 200);
+        assert_eq!(sample(&second).session_rx_bytes, 3_000);
+    }
+
+    #[test]
+    fn resets_everything_when_the_interface_changes() {
+        let mut tracker = InterfaceTracker::default();
+        tracker.observe(Some("wlan0"), Some((1_000, 100)));
+        tracker.observe(Some("wlan0"), Some((9_000, 900)));
+
+        let events = tracker.observe(Some("wlan1"), Some((50, 5)));
+        assert_eq!(
+            events[0],
+            TrafficEvent::InterfaceChanged {
+                previous: Some("wlan0".to_string()),
+                current: Some("wlan1".to_string()),
+            }
+        );
+        assert_eq!(sample(&events).down_bytes_per_sec, 0);
+        assert_eq!(sample(&events).session_rx_bytes, 0);
+        assert_eq!(tracker.current(), Some("wlan1"));
+
+        let gone = tracker.observe(None, None);
+        assert_eq!(gone.len(), 1);
+        assert_eq!(tracker.current(), None);
+    }
+
+    #[test]
+    fn treats_counter_rollback_as_a_fresh_baseline() {
+        let mut tracker = InterfaceTracker::default();
+        tracker.observe(Some("eth0"), Some((10_000, 10_000)));
+        let events = tracker.observe(Some("eth0"), Some((20, 20)));
+        assert_eq!(sample(&events).down_bytes_per_sec, 0);
+
+        let next = tracker.observe(Some("eth0"), Some((120, 20)));
+        assert_eq!(sample(&next).down_bytes_per_sec, 100);
+    }
+
+    #[test]
+    fn unreadable_counters_report_an_idle_sample() {
+        let mut tracker = InterfaceTracker::default();
+        tracker.observe(Some("wlan0"), Some((1_000, 100)));
+        tracker.observe(Some("wlan0"), Some((3_000, 300)));
+
+        let missing = tracker.observe(Some("wlan0"), None);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(sample(&missing).down_bytes_per_sec, 0);
+        assert_eq!(sample(&missing).up_bytes_per_sec, 0);
+        assert_eq!(sample(&missing).session_rx_bytes, 2_000);
+
+        let back = tracker.observe(Some("wlan0"), Some((3_500, 300)));
+        assert_eq!(sample(&back).down_bytes_per_sec, 0);
+    }
+}
@@ -0,0 +1,647 @@
+// * ./src/tray.rs
+
+use anyhow::{anyhow, Result};
+use gtk4::glib;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+use zbus::{interface, Connection, Proxy};
+use zvariant::{OwnedObjectPath, OwnedValue, Str, Value};
+
+use crate::hotspot;
+use crate::nm;
+use crate::quick_connect::{self, QuickConnectEntry};
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+const WATCHER_SERVICE: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+const WATCHER_IFACE: &str = "org.kde.StatusNotifierWatcher";
+const APP_TITLE: &str = "Adwaita Network";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayAction {
+    ShowWindow,
+    SetWifiEnabled(bool),
+    SetHotspotActive(bool),
+    ConnectSaved(String),
+    Quit,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrayStatus {
+    pub wifi_enabled: bool,
+    pub hotspot_active: bool,
+    pub ssid: Option<String>,
+    pub saved_networks: Vec<QuickConnectEntry>,
+}
+
+impl TrayStatus {
+    pub async fn current() -> Self {
+        let hotspot_active = hotspot::is_hotspot_active().await.unwrap_or(false);
+        let wifi_enabled = nm::is_wifi_enabled().await.unwrap_or(false);
+        let ssid = if hotspot_active {
+            None
+        } else {
+            nm::get_active_wifi_ssid().await.ok().flatten()
+        };
+        let saved_networks = if wifi_enabled && !hotspot_active {
+            quick_connect::in_range_saved_networks()
+                .await
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Self {
+            wifi_enabled,
+            hotspot_active,
+            ssid,
+            saved_networks,
+        }
+    }
+
+    fn summary(&self) -> String {
+        if self.hotspot_active {
+            "Hotspot active".to_string()
+        } else if let Some(ssid) = &self.ssid {
+            format!("Connected to {}", ssid)
+        } else if self.wifi_enabled {
+            "Not connected".to_string()
+        } else {
+            "Wi-Fi off".to_string()
+        }
+    }
+
+    fn icon_name(&self) -> &'static str {
+        if self.hotspot_active {
+            "network-wireless-hotspot-symbolic"
+        } else if self.ssid.is_some() {
+            "network-wireless-signal-excellent-symbolic"
+        } else if self.wifi_enabled {
+            "network-wireless-offline-symbolic"
+        } else {
+            "network-wireless-disabled-symbolic"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    Label(String),
+    Separator,
+    Toggle {
+        label: &'static str,
+        active: bool,
+        action: TrayAction,
+    },
+    Item {
+        label: String,
+        action: TrayAction,
+    },
+}
+
+// * Flat menu under the root (id 0); MenuIds hands out the other ids.
+pub fn menu_entries(status: &TrayStatus) -> Vec<MenuEntry> {
+    let mut entries = vec![
+        MenuEntry::Label(status.summary()),
+        MenuEntry::Separator,
+        MenuEntry::Toggle {
+            label: "Wi-Fi",
+            active: status.wifi_enabled,
+            action: TrayAction::SetWifiEnabled(!status.wifi_enabled),
+        },
+        MenuEntry::Toggle {
+            label: "Hotspot",
+            active: status.hotspot_active,
+            action: TrayAction::SetHotspotActive(!status.hotspot_active),
+        },
+    ];
+
+    if !status.saved_networks.is_empty() {
+        entries.push(MenuEntry::Separator);
+        for network in &status.saved_networks {
+            entries.push(if network.connected {
+                MenuEntry::Label(network.menu_label())
+            } else {
+                MenuEntry::Item {
+                    label: network.menu_label(),
+                    action: TrayAction::ConnectSaved(network.ssid.clone()),
+                }
+            });
+        }
+    }
+
+    entries.push(MenuEntry::Separator);
+    entries.push(MenuEntry::Item {
+        label: format!("Open {}", APP_TITLE),
+        action: TrayAction::ShowWindow,
+    });
+    entries.push(MenuEntry::Item {
+        label: "Quit".to_string(),
+        action: TrayAction::Quit,
+    });
+    entries
+}
+
+fn string_value(value: &str) -> OwnedValue {
+    OwnedValue::from(Str::from(value))
+}
+
+fn entry_properties(entry: &MenuEntry) -> HashMap<String, OwnedValue> {
+    let mut properties = HashMap::new();
+    match entry {
+        MenuEntry::Label(label) => {
+            properties.insert("label".to_string(), string_value(label));
+            properties.insert("enabled".to_string(), OwnedValue::from(false));
+        }
+        MenuEntry::Separator => {
+            properties.insert("type".to_string(), string_value("separator"));
+        }
+        MenuEntry::Toggle { label, active, .. } => {
+            properties.insert("label".to_string(), string_value(label));
+            properties.insert("toggle-type".to_string(), string_value("checkmark"));
+            properties.insert(
+                "toggle-state".to_string(),
+                OwnedValue::from(i32::from(*active)),
+            );
+        }
+        MenuEntry::Item { label, .. } => {
+            properties.insert("label".to_string(), string_value(label));
+        }
+    }
+    properties
+}
+
+// * Clickable entries keep their D-Bus id across refreshes, so a click that races a rebuild
+// * reaches the entry the user saw or nothing, never whatever took its place.
+#[derive(Debug, Default)]
+struct MenuIds {
+    ids: HashMap<String, i32>,
+}
+
+impl MenuIds {
+    fn key(entry: &MenuEntry, index: usize) -> String {
+        match entry {
+            // * Not clickable, so a position is good enough
+            MenuEntry::Label(_) | MenuEntry::Separator => format!("#{}", index),
+            MenuEntry::Toggle { label, .. } => format!("toggle:{}", label),
+            MenuEntry::Item { action, .. } => format!("{:?}", action),
+        }
+    }
+
+    fn assign(&mut self, entries: Vec<MenuEntry>) -> Vec<(i32, MenuEntry)> {
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let next = self.ids.len() as i32 + 1;
+                let id = *self.ids.entry(Self::key(&entry, index)).or_insert(next);
+                (id, entry)
+            })
+            .collect()
+    }
+}
+
+type MenuLayout = (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>);
+// * (icon name, icon pixmaps, title, description)
+type ToolTip = (String, Vec<(i32, i32, Vec<u8>)>, String, String);
+
+struct StatusNotifierItem {
+    status: Arc<Mutex<TrayStatus>>,
+    actions: mpsc::UnboundedSender<TrayAction>,
+}
+
+#[interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    #[zbus(property)]
+    fn category(&self) -> &str {
+        "Hardware"
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> &str {
+        "adw-network"
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> &str {
+        APP_TITLE
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "Active"
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        lock(&self.status).icon_name().to_string()
+    }
+
+    #[zbus(property)]
+    fn tool_tip(&self) -> ToolTip {
+        (
+            String::new(),
+            Vec::new(),
+            APP_TITLE.to_string(),
+            lock(&self.status).summary(),
+        )
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).expect("static menu path is valid")
+    }
+
+    fn activate(&self, _x: i32, _y: i32) {
+        let _ = self.actions.send(TrayAction::ShowWindow);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        let _ = self.actions.send(TrayAction::ShowWindow);
+    }
+
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[zbus(signal)]
+    async fn new_icon(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn new_tool_tip(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+}
+
+struct DbusMenu {
+    status: Arc<Mutex<TrayStatus>>,
+    actions: mpsc::UnboundedSender<TrayAction>,
+    revision: AtomicU32,
+    ids: Mutex<MenuIds>,
+}
+
+impl DbusMenu {
+    fn entries(&self) -> Vec<(i32, MenuEntry)> {
+        let entries = menu_entries(&lock(&self.status));
+        self.ids
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .assign(entries)
+    }
+
+    fn entry(&self, id: i32) -> Option<MenuEntry> {
+        self.entries()
+            .into_iter()
+            .find(|(entry_id, _)| *entry_id == id)
+            .map(|(_, entry)| entry)
+    }
+
+    fn clicked(&self, id: i32) {
+        let action = self.entry(id).and_then(|entry| match entry {
+            MenuEntry::Toggle { action, .. } | MenuEntry::Item { action, .. } => Some(action),
+            _ => None,
+        });
+        if let Some(action) = action {
+            let _ = self.actions.send(action);
+        }
+    }
+}
+
+// * com.canonical.dbusmenu, the menu protocol StatusNotifier hosts expect.
+#[interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> zbus::fdo::Result<(u32, MenuLayout)> {
+        let revision = self.revision.load(Ordering::Relaxed);
+        if parent_id != 0 {
+            let entry = self
+                .entry(parent_id)
+                .ok_or_else(|| zbus::fdo::Error::InvalidArgs("Unknown menu item".to_string()))?;
+            return Ok((revision, (parent_id, entry_properties(&entry), Vec::new())));
+        }
+
+        let entries = self.entries();
+        let mut children = Vec::with_capacity(entries.len());
+        for (id, entry) in &entries {
+            let child: MenuLayout = (*id, entry_properties(entry), Vec::new());
+            children.push(
+                OwnedValue::try_from(Value::from(child))
+                    .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?,
+            );
+        }
+        let mut root = HashMap::new();
+        root.insert("children-display".to_string(), string_value("submenu"));
+        Ok((revision, (0, root, children)))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        let entries = self.entries();
+        ids.into_iter()
+            .filter_map(|id| {
+                let (_, entry) = entries.iter().find(|(entry_id, _)| *entry_id == id)?;
+                Some((id, entry_properties(entry)))
+            })
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> zbus::fdo::Result<OwnedValue> {
+        self.entry(id)
+            .and_then(|entry| entry_properties(&entry).remove(&name))
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs("Unknown menu property".to_string()))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id == "clicked" {
+            self.clicked(id);
+        }
+    }
+
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        for (id, event_id, _, _) in events {
+            if event_id == "clicked" {
+                self.clicked(id);
+            }
+        }
+        Vec::new()
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> &str {
+        "ltr"
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "normal"
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(signal)]
+    async fn layout_updated(
+        emitter: &SignalEmitter<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+}
+
+fn lock(status: &Mutex<TrayStatus>) -> std::sync::MutexGuard<'_, TrayStatus> {
+    status
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// * A registered StatusNotifierItem; dropping every clone releases the bus name and the icon goes away.
+#[derive(Clone)]
+struct Tray {
+    conn: Connection,
+    status: Arc<Mutex<TrayStatus>>,
+}
+
+impl Tray {
+    async fn start() -> Result<(Self, mpsc::UnboundedReceiver<TrayAction>)> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let status = Arc::new(Mutex::new(TrayStatus::default()));
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+
+        let conn = zbus::connection::Builder::session()?
+            .name(name.as_str())?
+            .serve_at(
+                ITEM_PATH,
+                StatusNotifierItem {
+                    status: status.clone(),
+                    actions: sender.clone(),
+                },
+            )?
+            .serve_at(
+                MENU_PATH,
+                DbusMenu {
+                    status: status.clone(),
+                    actions: sender,
+                    revision: AtomicU32::new(1),
+                    ids: Mutex::new(MenuIds::default()),
+                },
+            )?
+            .build()
+            .await?;
+
+        let watcher = Proxy::new(&conn, WATCHER_SERVICE, WATCHER_PATH, WATCHER_IFACE).await?;
+        watcher
+            .call::<_, _, ()>("RegisterStatusNotifierItem", &(name.as_str(),))
+            .await
+            .map_err(|e| anyhow!("No system tray is available: {}", e))?;
+
+        Ok((Self { conn, status }, receiver))
+    }
+
+    async fn update(&self, status: TrayStatus) -> Result<()> {
+        {
+            let mut current = lock(&self.status);
+            if *current == status {
+                return Ok(());
+            }
+            *current = status;
+        }
+
+        let server = self.conn.object_server();
+        let item = server.interface::<_, StatusNotifierItem>(ITEM_PATH).await?;
+        StatusNotifierItem::new_icon(item.signal_emitter()).await?;
+        StatusNotifierItem::new_tool_tip(item.signal_emitter()).await?;
+
+        let menu = server.interface::<_, DbusMenu>(MENU_PATH).await?;
+        let revision = menu.get().await.revision.fetch_add(1, Ordering::Relaxed) + 1;
+        DbusMenu::layout_updated(menu.signal_emitter(), revision, 0).await?;
+        Ok(())
+    }
+}
+
+// * GTK-side owner of the tray icon; tray clicks are handed back to the window as actions.
+#[derive(Clone)]
+pub struct TrayIcon {
+    tray: Rc<RefCell<Option<Tray>>>,
+    // * Bumped on every enable/disable so a slow start can't revive a disabled icon
+    generation: Rc<Cell<u32>>,
+    on_action: Rc<dyn Fn(TrayAction)>,
+}
+
+impl TrayIcon {
+    pub fn new(on_action: impl Fn(TrayAction) + 'static) -> Self {
+        Self {
+            tray: Rc::new(RefCell::new(None)),
+            generation: Rc::new(Cell::new(0)),
+            on_action: Rc::new(on_action),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.tray.borrow().is_some()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        let generation = self.generation.get().wrapping_add(1);
+        self.generation.set(generation);
+        if !enabled {
+            self.tray.borrow_mut().take();
+            return;
+        }
+        if self.is_enabled() {
+            return;
+        }
+
+        let icon = self.clone();
+        glib::spawn_future_local(async move {
+            let (tray, mut actions) = match Tray::start().await {
+                Ok(started) => started,
+                Err(e) => {
+                    log::warn!("Failed to show tray icon: {}", e);
+                    return;
+                }
+            };
+            if icon.generation.get() != generation {
+                return;
+            }
+            *icon.tray.borrow_mut() = Some(tray);
+            icon.refresh();
+
+            let on_action = icon.on_action.clone();
+            glib::spawn_future_local(async move {
+                while let Some(action) = actions.recv().await {
+                    on_action(action);
+                }
+            });
+        });
+    }
+
+    pub fn refresh(&self) {
+        let Some(tray) = self.tray.borrow().clone() else {
+            return;
+        };
+        glib::spawn_future_local(async move {
+            let status = TrayStatus::current().await;
+            if let Err(e) = tray.update(status).await {
+                log::warn!("Failed to update tray icon: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn menu_offers_toggles_and_saved_networks() {
+        let status = TrayStatus {
+            wifi_enabled: true,
+            hotspot_active: false,
+            ssid: Some("Home".to_string()),
+            saved_networks: vec![
+                QuickConnectEntry {
+                    ssid: "Home".to_string(),
+                    signal: 80,
+                    connected: true,
+                },
+                QuickConnectEntry {
+                    ssid: "Office".to_string(),
+                    signal: 40,
+                    connected: false,
+                },
+            ],
+        };
+        let entries = menu_entries(&status);
+
+        assert_eq!(
+            entries[0],
+            MenuEntry::Label("Connected to Home".to_string())
+        );
+        assert_eq!(
+            entries[2],
+            MenuEntry::Toggle {
+                label: "Wi-Fi",
+                active: true,
+                action: TrayAction::SetWifiEnabled(false),
+            }
+        );
+        assert!(entries.contains(&MenuEntry::Label("Home (connected)".to_string())));
+        assert!(entries.contains(&MenuEntry::Item {
+            label: "Office (40%)".to_string(),
+            action: TrayAction::ConnectSaved("Office".to_string()),
+        }));
+        assert_eq!(
+            entries.last(),
+            Some(&MenuEntry::Item {
+                label: "Quit".to_string(),
+                action: TrayAction::Quit,
+            })
+        );
+    }
+
+    #[test]
+    fn menu_ids_survive_a_rebuild() {
+        let network = |ssid: &str| QuickConnectEntry {
+            ssid: ssid.to_string(),
+            signal: 50,
+            connected: false,
+        };
+        let mut status = TrayStatus {
+            wifi_enabled: true,
+            saved_networks: vec![network("Cafe"), network("Office")],
+            ..Default::default()
+        };
+        let mut ids = MenuIds::default();
+        let id_of = |entries: &[(i32, MenuEntry)], action: &TrayAction| {
+            entries.iter().find_map(|(id, entry)| match entry {
+                MenuEntry::Item { action: a, .. } if a == action => Some(*id),
+                _ => None,
+            })
+        };
+
+        let before = ids.assign(menu_entries(&status));
+        let cafe = TrayAction::ConnectSaved("Cafe".to_string());
+        let office = TrayAction::ConnectSaved("Office".to_string());
+        let cafe_id = id_of(&before, &cafe).unwrap();
+        let office_id = id_of(&before, &office).unwrap();
+
+        // * Cafe drops out, so Office moves up into its slot
+        status.saved_networks.remove(0);
+        let after = ids.assign(menu_entries(&status));
+        assert_eq!(id_of(&after, &office), Some(office_id));
+        assert!(after.iter().all(|(id, _)| *id != cafe_id));
+        assert_eq!(
+            id_of(&after, &TrayAction::Quit),
+            id_of(&before, &TrayAction::Quit)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// * ./src/ui/bluetooth_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::bluetooth::{self, PanDevice};
+use crate::nm;
+use crate::ui::{common, icon_name};
+
+// * Tethering over Bluetooth PAN to paired phones; hidden when BlueZ isn't running.
+#[derive(Clone)]
+pub struct BluetoothPage {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    empty_row: adw::ActionRow,
+    device_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+}
+
+impl BluetoothPage {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let widget = adw::PreferencesGroup::new();
+        widget.set_title("Bluetooth Tethering");
+        widget.set_description(Some(
+            "Share a paired phone's mobile data over Bluetooth, which uses less battery than a Wi-Fi hotspot.",
+        ));
+        widget.set_visible(false);
+
+        let empty_row = adw::ActionRow::builder()
+            .title("No phones offer tethering")
+            .subtitle("Pair a phone and turn on Bluetooth tethering in its settings")
+            .build();
+        widget.add(&empty_row);
+
+        Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            empty_row,
+            device_rows: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    pub async fn refresh(&self) {
+        let devices = match bluetooth::list_pan_devices().await {
+            Ok(devices) => devices,
+            Err(e) => {
+                log::debug!("Bluetooth tethering unavailable: {}", e);
+                self.widget.set_visible(false);
+                return;
+            }
+        };
+        let active = nm::active_bluetooth_pan_addresses()
+            .await
+            .unwrap_or_default();
+
+        for row in self.device_rows.borrow_mut().drain(..) {
+            self.widget.remove(&row);
+        }
+        self.empty_row.set_visible(devices.is_empty());
+        for device in devices {
+            let connected = active
+                .iter()
+                .any(|address| address.eq_ignore_ascii_case(&device.address));
+            let row = self.create_device_row(&device, connected);
+            self.widget.add(&row);
+            self.device_rows.borrow_mut().push(row);
+        }
+        self.widget.set_visible(true);
+    }
+
+    fn create_device_row(&self, device: &PanDevice, connected: bool) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(&device.name)
+            .subtitle(if connected {
+                "Connected"
+            } else {
+                "Not connected"
+            })
+            .build();
+        let icon = gtk4::Image::from_icon_name(icon_name(
+            "phone-symbolic",
+            &["bluetooth-active-symbolic", "bluetooth-symbolic"][..],
+        ));
+        row.add_prefix(&icon);
+
+        let button = gtk4::Button::builder()
+            .label(if connected { "Disconnect" } else { "Connect" })
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        row.add_suffix(&button);
+        row.set_activatable_widget(Some(&button));
+
+        let page = self.clone();
+        let device = device.clone();
+        button.connect_clicked(move |button| {
+            button.set_sensitive(false);
+            let page = page.clone();
+            let device = device.clone();
+            glib::spawn_future_local(async move {
+                page.toggle(&device, connected).await;
+            });
+        });
+        row
+    }
+
+    async fn toggle(&self, device: &PanDevice, connected: bool) {
+        let result = if connected {
+            nm::disconnect_bluetooth_pan(&device.address).await
+        } else {
+            nm::connect_bluetooth_pan(&device.address, &device.name).await
+        };
+        match result {
+            Ok(()) => common::show_toast(
+                &self.toast_overlay,
+                &if connected {
+                    format!("Stopped tethering to {}", device.name)
+                } else {
+                    format!("Tethering to {}", device.name)
+                },
+            ),
+            Err(e) => {
+                log::error!("Bluetooth tethering with {} failed: {}", device.name, e);
+                common::show_toast(
+                    &self.toast_overlay,
+                    &format!("Bluetooth tethering failed: {}", e),
+                );
+            }
+        }
+        self.refresh().await;
+    }
+}
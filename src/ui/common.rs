@@ -4,12 +4,38 @@ use gtk4::prelude::*;
 use libadwaita::{self as adw, prelude::*};
 use std::time::Duration;
 
+use crate::nm;
+
 pub fn show_toast(overlay: &adw::ToastOverlay, message: &str) {
     let toast = adw::Toast::new(message);
     toast.set_timeout(5);
     overlay.add_toast(toast);
 }
 
+// * Toasts "<prefix>: <reason>", except polkit denials, which get a dialog saying why and what to do.
+pub fn show_error(overlay: &adw::ToastOverlay, prefix: &str, error: &anyhow::Error) {
+    let kind = nm::NmError::classify(error);
+    if !matches!(kind, nm::NmError::PermissionDenied(_)) {
+        show_toast(overlay, &format!("{}: {}", prefix, kind.user_message()));
+        return;
+    }
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Permission Required")
+        .body(format!(
+            "{}. The system's security policy (polkit) doesn't allow your account to change this \
+             network setting, and no administrator approved it.\n\nAsk an administrator to add \
+             you to the group allowed to manage networking (often \"netdev\" or \"wheel\"), or \
+             try again and enter an administrator password when prompted.",
+            prefix
+        ))
+        .default_response("ok")
+        .close_response("ok")
+        .build();
+    dialog.add_response("ok", "OK");
+    dialog.present(overlay.root().and_downcast_ref::<gtk4::Window>());
+}
+
 pub fn set_busy(
     spinner: &gtk4::Spinner,
     status_label: &gtk4::Label,
@@ -97,3 +123,20 @@ pub fn make_dialog_responsive(
         glib::ControlFlow::Continue
     });
 }
+
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+    let value = bytes as f64;
+    if value >= GIB {
+        format!("{:.2} GiB", value / GIB)
+    } else if value >= MIB {
+        format!("{:.1} MiB", value / MIB)
+    } else if value >= KIB {
+        format!("{:.0} KiB", value / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
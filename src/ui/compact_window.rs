@@ -0,0 +1,211 @@
+// * ./src/ui/compact_window.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::hotspot;
+use crate::nm;
+use crate::polling;
+use crate::ui::common;
+
+pub const COMPACT_ACTION: &str = "compact";
+// * Lets build_ui tell this window apart from the main one
+pub const WINDOW_NAME: &str = "compact-window";
+
+// * Small always-fresh window with the everyday toggles; closes when it loses focus.
+#[derive(Clone)]
+struct CompactWindow {
+    window: adw::ApplicationWindow,
+    toast_overlay: adw::ToastOverlay,
+    wifi_row: adw::SwitchRow,
+    network_row: adw::ActionRow,
+    hotspot_row: adw::SwitchRow,
+    // * Set while rows are synced from NM so their notify handlers don't act on it
+    updating: Rc<Cell<bool>>,
+}
+
+impl CompactWindow {
+    fn new(app: &adw::Application) -> Self {
+        let window = adw::ApplicationWindow::builder()
+            .application(app)
+            .title("Network")
+            .default_width(340)
+            .resizable(false)
+            .build();
+        window.set_widget_name(WINDOW_NAME);
+
+        let group = adw::PreferencesGroup::new();
+        let wifi_row = adw::SwitchRow::builder().title("Wi-Fi").build();
+        let network_row = adw::ActionRow::builder()
+            .title("Network")
+            .subtitle("Not connected")
+            .build();
+        network_row.add_css_class("property");
+        let hotspot_row = adw::SwitchRow::builder().title("Hotspot").build();
+        group.add(&wifi_row);
+        group.add(&network_row);
+        group.add(&hotspot_row);
+
+        let more_button = gtk4::Button::builder()
+            .label("More…")
+            .halign(gtk4::Align::Center)
+            .css_classes(vec!["pill".to_string()])
+            .build();
+
+        let content = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+        content.append(&group);
+        content.append(&more_button);
+
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&content));
+        let toolbar_view = adw::ToolbarView::new();
+        toolbar_view.add_top_bar(&adw::HeaderBar::new());
+        toolbar_view.set_content(Some(&toast_overlay));
+        window.set_content(Some(&toolbar_view));
+
+        let compact = Self {
+            window: window.clone(),
+            toast_overlay,
+            wifi_row: wifi_row.clone(),
+            network_row,
+            hotspot_row: hotspot_row.clone(),
+            updating: Rc::new(Cell::new(false)),
+        };
+
+        let compact_ref = compact.clone();
+        wifi_row.connect_active_notify(move |row| {
+            if compact_ref.updating.get() {
+                return;
+            }
+            let enabled = row.is_active();
+            let compact = compact_ref.clone();
+            glib::spawn_future_local(async move {
+                if let Err(e) = nm::set_wifi_enabled(enabled).await {
+                    common::show_toast(
+                        &compact.toast_overlay,
+                        &format!("Failed to switch Wi-Fi: {}", e),
+                    );
+                }
+                compact.refresh();
+            });
+        });
+
+        let compact_ref = compact.clone();
+        hotspot_row.connect_active_notify(move |row| {
+            if compact_ref.updating.get() {
+                return;
+            }
+            let active = row.is_active();
+            row.set_sensitive(false);
+            let compact = compact_ref.clone();
+            glib::spawn_future_local(async move {
+                let result = if active {
+                    hotspot::start_saved_hotspot(None).await.map(|_| ())
+                } else {
+                    hotspot::stop_hotspot().await
+                };
+                if let Err(e) = result {
+                    log::error!("Compact hotspot toggle failed: {}", e);
+                    common::show_toast(&compact.toast_overlay, &e.to_string());
+                }
+                compact.hotspot_row.set_sensitive(true);
+                compact.refresh();
+            });
+        });
+
+        let app_weak = app.downgrade();
+        let window_for_more = window.clone();
+        more_button.connect_clicked(move |_| {
+            if let Some(app) = app_weak.upgrade() {
+                app.activate();
+            }
+            window_for_more.close();
+        });
+
+        let key_controller = gtk4::EventControllerKey::new();
+        let window_for_key = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gtk4::gdk::Key::Escape {
+                window_for_key.close();
+                return glib::Propagation::Stop;
+            }
+            glib::Propagation::Proceed
+        });
+        window.add_controller(key_controller);
+
+        window.connect_is_active_notify(|window| {
+            if !window.is_active() {
+                window.close();
+            }
+        });
+
+        compact
+    }
+
+    fn refresh(&self) {
+        let compact = self.clone();
+        glib::spawn_future_local(async move {
+            let wifi_enabled = nm::is_wifi_enabled().await.unwrap_or(false);
+            let hotspot_active = hotspot::is_hotspot_active().await.unwrap_or(false);
+            let ssid = nm::get_active_wifi_ssid().await.ok().flatten();
+
+            compact.updating.set(true);
+            compact.wifi_row.set_active(wifi_enabled);
+            compact.hotspot_row.set_active(hotspot_active);
+            compact.updating.set(false);
+            compact.network_row.set_subtitle(&if hotspot_active {
+                "Sharing a hotspot".to_string()
+            } else if let Some(ssid) = ssid {
+                ssid
+            } else if wifi_enabled {
+                "Not connected".to_string()
+            } else {
+                "Wi-Fi is off".to_string()
+            });
+        });
+    }
+}
+
+// * Owns the compact window while it is open and keeps it in sync with NM signals.
+#[derive(Clone, Default)]
+pub struct CompactLauncher {
+    current: Rc<RefCell<Option<CompactWindow>>>,
+}
+
+impl CompactLauncher {
+    pub fn new() -> Self {
+        let launcher = Self::default();
+        let current = launcher.current.clone();
+        polling::forward_nm_events(move |_| {
+            if let Some(compact) = current.borrow().as_ref() {
+                compact.refresh();
+            }
+        });
+        launcher
+    }
+
+    pub fn present(&self, app: &adw::Application) {
+        if let Some(compact) = self.current.borrow().as_ref() {
+            compact.window.present();
+            return;
+        }
+
+        crate::window::AdwNetworkWindow::load_saved_theme();
+        let compact = CompactWindow::new(app);
+        let current = self.current.clone();
+        compact.window.connect_close_request(move |_| {
+            current.borrow_mut().take();
+            glib::Propagation::Proceed
+        });
+        compact.refresh();
+        compact.window.present();
+        *self.current.borrow_mut() = Some(compact);
+    }
+}
@@ -0,0 +1,1174 @@
+// * ./src/ui/connection_editor.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::nm;
+use crate::ui::common;
+
+// * Per-connection properties that don't fit the Wi-Fi/Ethernet details dialogs.
+pub async fn show_advanced_editor(parent: &gtk4::Widget, connection_name: &str, active: bool) {
+    let dialog = adw::PreferencesDialog::builder()
+        .title("Advanced Settings")
+        .build();
+    let parent_window = parent
+        .root()
+        .and_then(|root| root.downcast::<gtk4::Window>().ok());
+    common::make_dialog_responsive(
+        dialog.upcast_ref::<adw::Dialog>(),
+        parent_window.as_ref(),
+        520,
+        560,
+    );
+
+    let page = adw::PreferencesPage::new();
+    page.set_title(connection_name);
+
+    let dhcp_group = adw::PreferencesGroup::builder()
+        .title("DHCP")
+        .description(
+            "Some networks hand out leases by client identifier. Leave empty to use the default.",
+        )
+        .build();
+
+    let client_id_entry = adw::EntryRow::builder().title("DHCP client ID").build();
+    match nm::get_dhcp_client_id(connection_name).await {
+        Ok(Some(value)) => client_id_entry.set_text(&value),
+        Ok(None) => {}
+        Err(e) => log::warn!(
+            "Failed to read DHCP client ID for {}: {}",
+            connection_name,
+            e
+        ),
+    }
+
+    let apply_button = gtk4::Button::builder()
+        .label("Apply")
+        .valign(gtk4::Align::Center)
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    let apply_row = adw::ActionRow::builder()
+        .title("Save client ID")
+        .subtitle(if active {
+            "The active connection is reapplied"
+        } else {
+            "Takes effect on next connect"
+        })
+        .build();
+    apply_row.add_suffix(&apply_button);
+    apply_row.set_activatable_widget(Some(&apply_button));
+
+    let dialog_for_apply = dialog.clone();
+    let entry_for_apply = client_id_entry.clone();
+    let name_for_apply = connection_name.to_string();
+    apply_button.connect_clicked(move |button| {
+        let text = entry_for_apply.text().trim().to_string();
+        if !text.is_empty() {
+            if let Err(e) = nm::validate_dhcp_client_id(&text) {
+                dialog_for_apply.add_toast(adw::Toast::new(&e.to_string()));
+                return;
+            }
+        }
+
+        let dialog = dialog_for_apply.clone();
+        let name = name_for_apply.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let client_id = (!text.is_empty()).then_some(text.as_str());
+            let result = match nm::set_dhcp_client_id(&name, client_id).await {
+                Ok(()) if active => nm::reapply_connection(&name).await,
+                other => other,
+            };
+            button.set_sensitive(true);
+            match result {
+                Ok(()) => dialog.add_toast(adw::Toast::new("DHCP client ID saved")),
+                Err(e) => {
+                    log::error!("Failed to set DHCP client ID for {}: {}", name, e);
+                    dialog.add_toast(adw::Toast::new(&format!(
+                        "Failed to set DHCP client ID: {}",
+                        e
+                    )));
+                }
+            }
+        });
+    });
+
+    dhcp_group.add(&client_id_entry);
+    dhcp_group.add(&apply_row);
+    page.add(&dhcp_group);
+    dialog.add(&page);
+
+    dialog.present(Some(parent));
+}
+
+pub fn advanced_settings_row() -> adw::ActionRow {
+    let row = adw::ActionRow::builder()
+        .title("Advanced settings")
+        .subtitle("DHCP and other per-connection options")
+        .activatable(true)
+        .build();
+    row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+    row
+}
+
+const CUSTOM_MAC_LABEL: &str = "Custom address";
+
+// * MAC policy picker plus a cloned-address entry, for networks that allow-list devices by MAC.
+pub async fn add_mac_address_rows(
+    group: &adw::PreferencesGroup,
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) {
+    // * Preserve profiles get their own entry so opening the dialog changes nothing.
+    let mut mac_policies = vec![
+        (nm::MacAddressPolicy::Default, "System default"),
+        (nm::MacAddressPolicy::Permanent, "Hardware address"),
+        (nm::MacAddressPolicy::Random, "Random each time"),
+        (nm::MacAddressPolicy::Stable, "Stable per network"),
+    ];
+    let current = nm::get_mac_policy(connection_name)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to read MAC address policy: {}", e);
+            nm::MacAddressPolicy::Default
+        });
+    if current == nm::MacAddressPolicy::Preserve {
+        mac_policies.push((nm::MacAddressPolicy::Preserve, "Keep current"));
+    }
+
+    let mut labels: Vec<&str> = mac_policies.iter().map(|(_, label)| *label).collect();
+    labels.push(CUSTOM_MAC_LABEL);
+    let custom_index = mac_policies.len() as u32;
+    let selected = match &current {
+        nm::MacAddressPolicy::Fixed(_) => custom_index,
+        _ => mac_policies
+            .iter()
+            .position(|(policy, _)| *policy == current)
+            .unwrap_or(0) as u32,
+    };
+    let mac_row = adw::ComboRow::builder()
+        .title("MAC address")
+        .subtitle("A random address makes this device harder to track")
+        .model(&gtk4::StringList::new(&labels))
+        .selected(selected)
+        .build();
+
+    let custom_entry = adw::EntryRow::builder()
+        .title("Cloned MAC address")
+        .show_apply_button(true)
+        .visible(selected == custom_index)
+        .build();
+    if let nm::MacAddressPolicy::Fixed(mac) = &current {
+        custom_entry.set_text(mac);
+    }
+
+    let save = {
+        let overlay = toast_overlay.clone();
+        let name = connection_name.to_string();
+        move |policy: nm::MacAddressPolicy| {
+            let overlay = overlay.clone();
+            let name = name.clone();
+            glib::spawn_future_local(async move {
+                match nm::set_mac_policy(&name, &policy).await {
+                    Ok(()) if active => {
+                        common::show_toast(&overlay, "MAC address setting applies on next connect")
+                    }
+                    Ok(()) => {}
+                    Err(e) => {
+                        log::error!("Failed to set MAC address policy for {}: {}", name, e);
+                        common::show_toast(
+                            &overlay,
+                            &format!("Failed to update MAC address: {}", e),
+                        );
+                    }
+                }
+            });
+        }
+    };
+
+    let entry_for_select = custom_entry.clone();
+    let save_for_select = save.clone();
+    mac_row.connect_selected_notify(move |row| {
+        let custom = row.selected() == custom_index;
+        entry_for_select.set_visible(custom);
+        if custom {
+            entry_for_select.grab_focus();
+            return;
+        }
+        if let Some((policy, _)) = mac_policies.get(row.selected() as usize) {
+            save_for_select(policy.clone());
+        }
+    });
+
+    let overlay_for_apply = toast_overlay.clone();
+    custom_entry.connect_apply(move |entry| {
+        let mac = entry.text().trim().to_uppercase();
+        if let Err(e) = nm::validate_mac_address(&mac) {
+            common::show_toast(&overlay_for_apply, &e.to_string());
+            return;
+        }
+        entry.set_text(&mac);
+        save(nm::MacAddressPolicy::Fixed(mac));
+    });
+
+    group.add(&mac_row);
+    group.add(&custom_entry);
+}
+
+// * 0 keeps the driver default; PPPoE and VPN users often need 1492 or lower.
+pub async fn add_mtu_row(
+    group: &adw::PreferencesGroup,
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) {
+    let mtu_row = adw::SpinRow::with_range(0.0, f64::from(*nm::MTU_RANGE.end()), 1.0);
+    mtu_row.set_title("MTU");
+    mtu_row.set_subtitle("0 uses the default; lower it for PPPoE or VPN links");
+    match nm::get_mtu(connection_name).await {
+        Ok(mtu) => mtu_row.set_value(f64::from(mtu)),
+        Err(e) => log::warn!("Failed to read MTU for {}: {}", connection_name, e),
+    }
+
+    // * Spin clicks arrive one by one; only the last value within the delay is written.
+    let generation = Rc::new(Cell::new(0u32));
+    let overlay = toast_overlay.clone();
+    let name = connection_name.to_string();
+    mtu_row.connect_value_notify(move |row| {
+        let current = generation.get().wrapping_add(1);
+        generation.set(current);
+        let generation = Rc::clone(&generation);
+        let overlay = overlay.clone();
+        let name = name.clone();
+        let row = row.clone();
+
+        glib::spawn_future_local(async move {
+            glib::timeout_future(Duration::from_millis(600)).await;
+            if generation.get() != current {
+                return;
+            }
+            // ? Values below the minimum snap up rather than failing on every spin click
+            let mut mtu = row.value() as u32;
+            if mtu != 0 && mtu < *nm::MTU_RANGE.start() {
+                mtu = *nm::MTU_RANGE.start();
+                row.set_value(f64::from(mtu));
+                return;
+            }
+            match nm::set_mtu(&name, mtu).await {
+                Ok(()) if active => common::show_toast(&overlay, "MTU applies on next connect"),
+                Ok(()) => {}
+                Err(e) => {
+                    log::error!("Failed to set MTU for {}: {}", name, e);
+                    common::show_toast(&overlay, &format!("Failed to update MTU: {}", e));
+                }
+            }
+        });
+    });
+
+    group.add(&mtu_row);
+}
+
+// * Hidden unless firewalld is running; NM ignores the zone otherwise.
+pub async fn add_firewall_zone_row(
+    group: &adw::PreferencesGroup,
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) {
+    if !nm::firewalld_running().await {
+        return;
+    }
+
+    let current = match nm::get_connection_zone(connection_name).await {
+        Ok(zone) => zone,
+        Err(e) => {
+            log::warn!(
+                "Failed to read firewall zone for {}: {}",
+                connection_name,
+                e
+            );
+            None
+        }
+    };
+    // * Index 0 is firewalld's default zone; a custom zone set elsewhere is kept as an extra entry
+    let mut zones: Vec<Option<String>> = vec![None];
+    zones.extend(nm::FIREWALL_ZONES.iter().map(|zone| Some(zone.to_string())));
+    if current.is_some() && !zones.contains(&current) {
+        zones.push(current.clone());
+    }
+    let labels: Vec<String> = zones
+        .iter()
+        .map(|zone| match zone {
+            Some(zone) => capitalize(zone),
+            None => "Default".to_string(),
+        })
+        .collect();
+    let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    let zone_row = adw::ComboRow::builder()
+        .title("Firewall Zone")
+        .subtitle("Trusted allows everything; Public blocks incoming connections")
+        .model(&gtk4::StringList::new(&label_refs))
+        .build();
+    zone_row.set_selected(zones.iter().position(|zone| *zone == current).unwrap_or(0) as u32);
+
+    let overlay = toast_overlay.clone();
+    let name = connection_name.to_string();
+    zone_row.connect_selected_notify(move |row| {
+        let Some(zone) = zones.get(row.selected() as usize).cloned() else {
+            return;
+        };
+        let overlay = overlay.clone();
+        let name = name.clone();
+        glib::spawn_future_local(async move {
+            match nm::set_connection_zone(&name, zone.as_deref()).await {
+                Ok(()) if active => {
+                    common::show_toast(&overlay, "Firewall zone applies on next connect")
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    log::error!("Failed to set firewall zone for {}: {}", name, e);
+                    common::show_toast(&overlay, &format!("Failed to update firewall zone: {}", e));
+                }
+            }
+        });
+    });
+
+    group.add(&zone_row);
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub async fn add_wake_on_lan_row(
+    group: &adw::PreferencesGroup,
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) {
+    let wol_row = adw::SwitchRow::builder()
+        .title("Wake on LAN")
+        .subtitle("Power this computer on with a magic packet from the network")
+        .build();
+    match nm::get_wake_on_lan(connection_name).await {
+        Ok(enabled) => wol_row.set_active(enabled),
+        Err(e) => log::warn!("Failed to read Wake-on-LAN for {}: {}", connection_name, e),
+    }
+
+    let overlay = toast_overlay.clone();
+    let name = connection_name.to_string();
+    wol_row.connect_active_notify(move |row| {
+        let overlay = overlay.clone();
+        let name = name.clone();
+        let enabled = row.is_active();
+        glib::spawn_future_local(async move {
+            match nm::set_wake_on_lan(&name, enabled).await {
+                Ok(()) if active => {
+                    common::show_toast(&overlay, "Wake on LAN applies on next connect")
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    log::error!("Failed to set Wake-on-LAN for {}: {}", name, e);
+                    common::show_toast(&overlay, &format!("Failed to update Wake on LAN: {}", e));
+                }
+            }
+        });
+    });
+
+    group.add(&wol_row);
+}
+
+const ROLLBACK_COUNTDOWN_SECS: u32 = 20;
+// * NM's own timer outlives the dialog, so a hung or closed app still gets its network back.
+const CHECKPOINT_TIMEOUT: Duration = Duration::from_secs(ROLLBACK_COUNTDOWN_SECS as u64 + 15);
+
+// * Changes that can cut connectivity: on an active connection they're applied inside an NM
+// * checkpoint and reverted unless the user confirms in time, like a display resolution prompt.
+// ? Ok(false) means the change was rolled back
+pub async fn apply_with_rollback(
+    parent: &impl IsA<gtk4::Widget>,
+    connection_name: &str,
+    active: bool,
+    change: impl Future<Output = anyhow::Result<()>>,
+) -> anyhow::Result<bool> {
+    if !active {
+        change.await?;
+        return Ok(true);
+    }
+
+    let checkpoint = match nm::create_checkpoint(CHECKPOINT_TIMEOUT).await {
+        Ok(checkpoint) => Some(checkpoint),
+        Err(e) => {
+            log::warn!(
+                "Applying {} without a rollback checkpoint: {}",
+                connection_name,
+                e
+            );
+            None
+        }
+    };
+
+    let result = match change.await {
+        Ok(()) => nm::reapply_connection(connection_name).await,
+        Err(e) => Err(e),
+    };
+    let Some(checkpoint) = checkpoint else {
+        return result.map(|()| true);
+    };
+    if let Err(e) = result {
+        if let Err(rollback_error) = nm::rollback_checkpoint(checkpoint).await {
+            log::warn!("Rollback after failed change failed: {}", rollback_error);
+        }
+        return Err(e);
+    }
+
+    if confirm_kept_settings(parent.upcast_ref()).await {
+        nm::confirm_checkpoint(checkpoint).await?;
+        Ok(true)
+    } else {
+        nm::rollback_checkpoint(checkpoint).await?;
+        Ok(false)
+    }
+}
+
+fn rollback_countdown_text(seconds: u32) -> String {
+    format!(
+        "The previous settings are restored in {} seconds unless you keep these",
+        seconds
+    )
+}
+
+async fn confirm_kept_settings(parent: &gtk4::Widget) -> bool {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Keep These Network Settings?")
+        .body(rollback_countdown_text(ROLLBACK_COUNTDOWN_SECS))
+        .default_response("keep")
+        .close_response("revert")
+        .build();
+    dialog.add_responses(&[("revert", "Revert"), ("keep", "Keep Changes")][..]);
+    dialog.set_response_appearance("keep", adw::ResponseAppearance::Suggested);
+
+    let remaining = Rc::new(Cell::new(ROLLBACK_COUNTDOWN_SECS));
+    let answered = Rc::new(Cell::new(false));
+    let dialog_for_tick = dialog.clone();
+    let answered_for_tick = Rc::clone(&answered);
+    glib::timeout_add_seconds_local(1, move || {
+        if answered_for_tick.get() {
+            return glib::ControlFlow::Break;
+        }
+        let left = remaining.get().saturating_sub(1);
+        remaining.set(left);
+        if left == 0 {
+            dialog_for_tick.close();
+            return glib::ControlFlow::Break;
+        }
+        dialog_for_tick.set_body(&rollback_countdown_text(left));
+        glib::ControlFlow::Continue
+    });
+
+    let response = if let Some(parent) = parent.root().and_downcast_ref::<gtk4::Window>() {
+        dialog.choose_future(Some(parent)).await
+    } else {
+        dialog.choose_future(None::<&gtk4::Window>).await
+    };
+    answered.set(true);
+    response.as_str() == "keep"
+}
+
+const IPV4_METHODS: [nm::Ipv4Method; 3] = [
+    nm::Ipv4Method::Auto,
+    nm::Ipv4Method::Manual,
+    nm::Ipv4Method::LinkLocal,
+];
+
+// * Shared by the Wi-Fi and Ethernet details dialogs; address rows only show in manual mode.
+pub async fn ipv4_settings_group(
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::builder().title("IPv4").build();
+
+    let current = match nm::get_ipv4_config(connection_name).await {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!(
+                "Failed to read IPv4 settings for {}: {}",
+                connection_name,
+                e
+            );
+            nm::Ipv4Config::default()
+        }
+    };
+    if let nm::Ipv4Method::Other(method) = &current.method {
+        group.set_description(Some(&format!(
+            "This profile uses the \"{}\" method; applying replaces it",
+            method
+        )));
+    }
+
+    let method_model =
+        gtk4::StringList::new(&["Automatic (DHCP)", "Manual", "Link-local only"][..]);
+    let method_row = adw::ComboRow::builder()
+        .title("Method")
+        .model(&method_model)
+        .selected(
+            IPV4_METHODS
+                .iter()
+                .position(|method| *method == current.method)
+                .unwrap_or(0) as u32,
+        )
+        .build();
+
+    let address_entry = adw::EntryRow::builder().title("Address").build();
+    if let Some(address) = current.address {
+        address_entry.set_text(&address.to_string());
+    }
+    let prefix_row = adw::SpinRow::with_range(1.0, 32.0, 1.0);
+    prefix_row.set_title("Prefix length");
+    prefix_row.set_value(f64::from(current.prefix));
+    let gateway_entry = adw::EntryRow::builder().title("Gateway").build();
+    if let Some(gateway) = current.gateway {
+        gateway_entry.set_text(&gateway.to_string());
+    }
+
+    let sync_visibility = {
+        let address_entry = address_entry.clone();
+        let prefix_row = prefix_row.clone();
+        let gateway_entry = gateway_entry.clone();
+        move |row: &adw::ComboRow| {
+            let manual = IPV4_METHODS.get(row.selected() as usize) == Some(&nm::Ipv4Method::Manual);
+            address_entry.set_visible(manual);
+            prefix_row.set_visible(manual);
+            gateway_entry.set_visible(manual);
+        }
+    };
+    sync_visibility(&method_row);
+    method_row.connect_selected_notify(sync_visibility);
+
+    let apply_button = gtk4::Button::builder()
+        .label("Apply")
+        .valign(gtk4::Align::Center)
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    let apply_row = adw::ActionRow::builder()
+        .title("Save IPv4 settings")
+        .subtitle(if active {
+            "The active connection is reapplied"
+        } else {
+            "Takes effect on next connect"
+        })
+        .build();
+    apply_row.add_suffix(&apply_button);
+    apply_row.set_activatable_widget(Some(&apply_button));
+
+    let overlay_for_apply = toast_overlay.clone();
+    let name_for_apply = connection_name.to_string();
+    let method_for_apply = method_row.clone();
+    let address_for_apply = address_entry.clone();
+    let prefix_for_apply = prefix_row.clone();
+    let gateway_for_apply = gateway_entry.clone();
+    apply_button.connect_clicked(move |button| {
+        let method = IPV4_METHODS
+            .get(method_for_apply.selected() as usize)
+            .cloned()
+            .unwrap_or(nm::Ipv4Method::Auto);
+        let config = match nm::parse_ipv4_config(
+            method,
+            &address_for_apply.text(),
+            prefix_for_apply.value() as u32,
+            &gateway_for_apply.text(),
+        ) {
+            Ok(config) => config,
+            Err(e) => {
+                common::show_toast(&overlay_for_apply, &e.to_string());
+                return;
+            }
+        };
+
+        let overlay = overlay_for_apply.clone();
+        let name = name_for_apply.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let result =
+                apply_with_rollback(&overlay, &name, active, nm::set_ipv4_config(&name, &config))
+                    .await;
+            button.set_sensitive(true);
+            match result {
+                Ok(true) => common::show_toast(&overlay, "IPv4 settings saved"),
+                Ok(false) => common::show_toast(&overlay, "IPv4 settings reverted"),
+                Err(e) => {
+                    log::error!("Failed to set IPv4 settings for {}: {}", name, e);
+                    common::show_toast(&overlay, &format!("Failed to set IPv4 settings: {}", e));
+                }
+            }
+        });
+    });
+
+    group.add(&method_row);
+    group.add(&address_entry);
+    group.add(&prefix_row);
+    group.add(&gateway_entry);
+    group.add(&apply_row);
+    group
+}
+
+const EAP_METHODS: [nm::EapMethod; 3] =
+    [nm::EapMethod::Peap, nm::EapMethod::Ttls, nm::EapMethod::Tls];
+const PHASE2_AUTHS: [nm::Phase2Auth; 4] = [
+    nm::Phase2Auth::Mschapv2,
+    nm::Phase2Auth::Mschap,
+    nm::Phase2Auth::Pap,
+    nm::Phase2Auth::Gtc,
+];
+
+// * A file path picked through the portal; the subtitle shows the file name or the empty hint.
+#[derive(Clone)]
+struct CertificateRow {
+    row: adw::ActionRow,
+    path: Rc<RefCell<Option<PathBuf>>>,
+}
+
+impl CertificateRow {
+    fn new(title: &str, empty_hint: &'static str, path: Option<PathBuf>) -> Self {
+        let row = adw::ActionRow::builder().title(title).build();
+        let choose_button = gtk4::Button::builder()
+            .label("Choose…")
+            .valign(gtk4::Align::Center)
+            .build();
+        let clear_button = gtk4::Button::builder()
+            .icon_name("edit-clear-symbolic")
+            .tooltip_text("Clear")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        row.add_suffix(&clear_button);
+        row.add_suffix(&choose_button);
+
+        let certificate = Self {
+            row,
+            path: Rc::new(RefCell::new(path)),
+        };
+        certificate.sync(empty_hint);
+
+        let certificate_for_choose = certificate.clone();
+        let title_for_choose = title.to_string();
+        choose_button.connect_clicked(move |button| {
+            let certificate = certificate_for_choose.clone();
+            let title = title_for_choose.clone();
+            let parent = button.root().and_downcast::<gtk4::Window>();
+            glib::spawn_future_local(async move {
+                if let Some(path) = choose_certificate_file(&title, parent.as_ref()).await {
+                    *certificate.path.borrow_mut() = Some(path);
+                    certificate.sync(empty_hint);
+                }
+            });
+        });
+
+        let certificate_for_clear = certificate.clone();
+        clear_button.connect_clicked(move |_| {
+            *certificate_for_clear.path.borrow_mut() = None;
+            certificate_for_clear.sync(empty_hint);
+        });
+
+        certificate
+    }
+
+    fn sync(&self, empty_hint: &str) {
+        match self.path.borrow().as_ref() {
+            Some(path) => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                self.row.set_subtitle(&name);
+                self.row.set_tooltip_text(Some(&path.display().to_string()));
+            }
+            None => {
+                self.row.set_subtitle(empty_hint);
+                self.row.set_tooltip_text(None);
+            }
+        }
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.path.borrow().clone()
+    }
+}
+
+#[allow(deprecated)]
+async fn choose_certificate_file(title: &str, parent: Option<&gtk4::Window>) -> Option<PathBuf> {
+    let chooser = gtk4::FileChooserNative::builder()
+        .title(title)
+        .action(gtk4::FileChooserAction::Open)
+        .accept_label("Select")
+        .cancel_label("Cancel")
+        .build();
+    if let Some(parent) = parent {
+        chooser.set_transient_for(Some(parent));
+    }
+
+    let response = chooser.run_future().await;
+    let path = if response == gtk4::ResponseType::Accept {
+        chooser.file().and_then(|file| file.path())
+    } else {
+        None
+    };
+    chooser.destroy();
+    path
+}
+
+// * 802.1X credentials shared by the enterprise Wi-Fi dialog and wired connections.
+#[derive(Clone)]
+pub struct EapForm {
+    pub group: adw::PreferencesGroup,
+    method_row: adw::ComboRow,
+    identity_entry: adw::EntryRow,
+    anonymous_identity_entry: adw::EntryRow,
+    domain_entry: adw::EntryRow,
+    ca_cert: CertificateRow,
+    phase2_row: adw::ComboRow,
+    password_entry: adw::PasswordEntryRow,
+    client_cert: CertificateRow,
+    private_key: CertificateRow,
+    private_key_password_entry: adw::PasswordEntryRow,
+}
+
+impl EapForm {
+    pub fn new(current: &nm::EapConfig) -> Self {
+        let group = adw::PreferencesGroup::builder()
+            .title("802.1X Authentication")
+            .build();
+
+        let method_model = gtk4::StringList::new(&["PEAP", "TTLS", "TLS"][..]);
+        let method_row = adw::ComboRow::builder()
+            .title("Authentication")
+            .model(&method_model)
+            .selected(
+                EAP_METHODS
+                    .iter()
+                    .position(|method| *method == current.method)
+                    .unwrap_or(0) as u32,
+            )
+            .build();
+
+        let identity_entry = adw::EntryRow::builder().title("Identity").build();
+        identity_entry.set_text(&current.identity);
+        let anonymous_identity_entry = adw::EntryRow::builder().title("Anonymous identity").build();
+        if let Some(anonymous) = &current.anonymous_identity {
+            anonymous_identity_entry.set_text(anonymous);
+        }
+        let domain_entry = adw::EntryRow::builder().title("Domain").build();
+        if let Some(domain) = &current.domain_suffix_match {
+            domain_entry.set_text(domain);
+        }
+
+        let ca_cert = CertificateRow::new(
+            "CA certificate",
+            "None — system certificates are trusted",
+            current.ca_cert.clone(),
+        );
+
+        let phase2_model = gtk4::StringList::new(&["MSCHAPv2", "MSCHAP", "PAP", "GTC"][..]);
+        let phase2_row = adw::ComboRow::builder()
+            .title("Inner authentication")
+            .model(&phase2_model)
+            .selected(
+                PHASE2_AUTHS
+                    .iter()
+                    .position(|auth| *auth == current.phase2_auth)
+                    .unwrap_or(0) as u32,
+            )
+            .build();
+        let password_entry = adw::PasswordEntryRow::builder().title("Password").build();
+        if let Some(password) = &current.password {
+            password_entry.set_text(password);
+        }
+
+        let client_cert =
+            CertificateRow::new("User certificate", "Required", current.client_cert.clone());
+        let private_key =
+            CertificateRow::new("Private key", "Required", current.private_key.clone());
+        let private_key_password_entry = adw::PasswordEntryRow::builder()
+            .title("Private key password")
+            .build();
+
+        let form = Self {
+            group,
+            method_row,
+            identity_entry,
+            anonymous_identity_entry,
+            domain_entry,
+            ca_cert,
+            phase2_row,
+            password_entry,
+            client_cert,
+            private_key,
+            private_key_password_entry,
+        };
+
+        form.group.add(&form.method_row);
+        form.group.add(&form.identity_entry);
+        form.group.add(&form.anonymous_identity_entry);
+        form.group.add(&form.domain_entry);
+        form.group.add(&form.ca_cert.row);
+        form.group.add(&form.phase2_row);
+        form.group.add(&form.password_entry);
+        form.group.add(&form.client_cert.row);
+        form.group.add(&form.private_key.row);
+        form.group.add(&form.private_key_password_entry);
+
+        form.sync_visibility();
+        let form_for_method = form.clone();
+        form.method_row
+            .connect_selected_notify(move |_| form_for_method.sync_visibility());
+        form
+    }
+
+    fn method(&self) -> nm::EapMethod {
+        EAP_METHODS
+            .get(self.method_row.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn sync_visibility(&self) {
+        let tunnelled = self.method().uses_password();
+        self.anonymous_identity_entry.set_visible(tunnelled);
+        self.phase2_row.set_visible(tunnelled);
+        self.password_entry.set_visible(tunnelled);
+        self.client_cert.row.set_visible(!tunnelled);
+        self.private_key.row.set_visible(!tunnelled);
+        self.private_key_password_entry.set_visible(!tunnelled);
+    }
+
+    pub fn config(&self) -> nm::EapConfig {
+        let optional = |text: glib::GString| {
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_string())
+        };
+        let secret = |text: glib::GString| {
+            (!text.is_empty()).then(|| zeroize::Zeroizing::new(text.to_string()))
+        };
+
+        nm::EapConfig {
+            method: self.method(),
+            identity: self.identity_entry.text().trim().to_string(),
+            anonymous_identity: optional(self.anonymous_identity_entry.text()),
+            password: secret(self.password_entry.text()),
+            phase2_auth: PHASE2_AUTHS
+                .get(self.phase2_row.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            ca_cert: self.ca_cert.path(),
+            domain_suffix_match: optional(self.domain_entry.text()),
+            client_cert: self.client_cert.path(),
+            private_key: self.private_key.path(),
+            private_key_password: secret(self.private_key_password_entry.text()),
+        }
+    }
+}
+
+// * Wired profiles only; the form stays hidden until 802.1X is switched on.
+pub async fn wired_8021x_section(
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) -> gtk4::Box {
+    let current = match nm::get_wired_8021x(connection_name).await {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!(
+                "Failed to read 802.1X settings for {}: {}",
+                connection_name,
+                e
+            );
+            None
+        }
+    };
+
+    let toggle_group = adw::PreferencesGroup::builder()
+        .title("802.1X Security")
+        .description("Required by some corporate and campus LANs")
+        .build();
+    let enabled_row = adw::SwitchRow::builder()
+        .title("Authenticate with 802.1X")
+        .active(current.is_some())
+        .build();
+    toggle_group.add(&enabled_row);
+
+    let form = EapForm::new(&current.clone().unwrap_or_default());
+    form.group.set_title("");
+    if current.is_some() {
+        form.group
+            .set_description(Some("Enter the passwords again to save changes"));
+    }
+    form.group.set_visible(enabled_row.is_active());
+    let form_for_toggle = form.clone();
+    enabled_row.connect_active_notify(move |row| {
+        form_for_toggle.group.set_visible(row.is_active());
+    });
+
+    let apply_group = adw::PreferencesGroup::new();
+    let apply_button = gtk4::Button::builder()
+        .label("Apply")
+        .valign(gtk4::Align::Center)
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    let apply_row = adw::ActionRow::builder()
+        .title("Save 802.1X settings")
+        .subtitle(if active {
+            "The active connection is reapplied"
+        } else {
+            "Takes effect on next connect"
+        })
+        .build();
+    apply_row.add_suffix(&apply_button);
+    apply_row.set_activatable_widget(Some(&apply_button));
+    apply_group.add(&apply_row);
+
+    let overlay_for_apply = toast_overlay.clone();
+    let name_for_apply = connection_name.to_string();
+    let enabled_for_apply = enabled_row.clone();
+    let form_for_apply = form.clone();
+    apply_button.connect_clicked(move |button| {
+        let config = enabled_for_apply
+            .is_active()
+            .then(|| form_for_apply.config());
+        if let Some(config) = &config {
+            if let Err(e) = nm::validate_eap_config(config) {
+                common::show_toast(&overlay_for_apply, &e.to_string());
+                return;
+            }
+        }
+
+        let overlay = overlay_for_apply.clone();
+        let name = name_for_apply.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let result = match nm::set_wired_8021x(&name, config.as_ref()).await {
+                Ok(()) if active => nm::reapply_connection(&name).await,
+                other => other,
+            };
+            button.set_sensitive(true);
+            match result {
+                Ok(()) => common::show_toast(&overlay, "802.1X settings saved"),
+                Err(e) => {
+                    log::error!("Failed to set 802.1X settings for {}: {}", name, e);
+                    common::show_toast(&overlay, &format!("Failed to set 802.1X settings: {}", e));
+                }
+            }
+        });
+    });
+
+    let section = gtk4::Box::new(gtk4::Orientation::Vertical, 16);
+    section.append(&toggle_group);
+    section.append(&form.group);
+    section.append(&apply_group);
+    section
+}
+
+// * Keeps the route rows so the list can be rebuilt after every add/delete.
+#[derive(Clone)]
+struct RoutesEditor {
+    group: adw::PreferencesGroup,
+    route_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+    connection_name: String,
+    active: bool,
+    toast_overlay: adw::ToastOverlay,
+}
+
+impl RoutesEditor {
+    async fn reload(&self) {
+        let routes = match nm::list_static_routes(&self.connection_name).await {
+            Ok(routes) => routes,
+            Err(e) => {
+                log::warn!("Failed to read routes for {}: {}", self.connection_name, e);
+                Vec::new()
+            }
+        };
+
+        let Ok(mut route_rows) = self.route_rows.try_borrow_mut() else {
+            log::warn!("Borrow conflict while rebuilding the routes list");
+            return;
+        };
+        for row in route_rows.drain(..) {
+            self.group.remove(&row);
+        }
+
+        if routes.is_empty() {
+            let empty_row = adw::ActionRow::builder()
+                .title("No static routes")
+                .subtitle("Traffic follows the routes from DHCP or the VPN")
+                .build();
+            self.group.add(&empty_row);
+            route_rows.push(empty_row);
+            return;
+        }
+
+        for route in routes {
+            let row = adw::ActionRow::builder()
+                .title(route.label())
+                .subtitle(route.details())
+                .build();
+            let delete_button = gtk4::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text("Delete route")
+                .valign(gtk4::Align::Center)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            row.add_suffix(&delete_button);
+
+            let editor = self.clone();
+            delete_button.connect_clicked(move |button| {
+                let editor = editor.clone();
+                let route = route.clone();
+                let button = button.clone();
+                button.set_sensitive(false);
+                glib::spawn_future_local(async move {
+                    let change = nm::delete_static_route(&editor.connection_name, &route);
+                    if !editor.apply(change, "delete route", "Route deleted").await {
+                        button.set_sensitive(true);
+                    }
+                });
+            });
+
+            self.group.add(&row);
+            route_rows.push(row);
+        }
+    }
+
+    // * True when the change stuck; a failed or reverted change leaves the inputs for another try.
+    async fn apply(
+        &self,
+        change: impl Future<Output = anyhow::Result<()>>,
+        what: &str,
+        message: &str,
+    ) -> bool {
+        match apply_with_rollback(
+            &self.toast_overlay,
+            &self.connection_name,
+            self.active,
+            change,
+        )
+        .await
+        {
+            Ok(kept) => {
+                common::show_toast(
+                    &self.toast_overlay,
+                    if kept {
+                        message
+                    } else {
+                        "Route change reverted"
+                    },
+                );
+                self.reload().await;
+                kept
+            }
+            Err(e) => {
+                self.failed(what, e);
+                false
+            }
+        }
+    }
+
+    fn failed(&self, what: &str, e: anyhow::Error) {
+        log::error!("Failed to {} on {}: {}", what, self.connection_name, e);
+        common::show_toast(&self.toast_overlay, &format!("Failed to {}: {}", what, e));
+    }
+}
+
+// * Static ipv4/ipv6 routes for split tunnels and lab networks.
+pub async fn routes_group(
+    connection_name: &str,
+    active: bool,
+    toast_overlay: &adw::ToastOverlay,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::builder()
+        .title("Routes")
+        .description(if active {
+            "Changes reapply the active connection"
+        } else {
+            "Changes take effect on next connect"
+        })
+        .build();
+
+    let add_row = adw::ExpanderRow::builder().title("Add route").build();
+    let destination_entry = adw::EntryRow::builder()
+        .title("Destination (e.g. 10.8.0.0/16)")
+        .build();
+    let gateway_entry = adw::EntryRow::builder().title("Gateway (optional)").build();
+    let metric_entry = adw::EntryRow::builder().title("Metric (optional)").build();
+    let add_button = gtk4::Button::builder()
+        .label("Add")
+        .valign(gtk4::Align::Center)
+        .css_classes(vec!["suggested-action".to_string()])
+        .build();
+    let add_button_row = adw::ActionRow::builder().title("Save route").build();
+    add_button_row.add_suffix(&add_button);
+    add_button_row.set_activatable_widget(Some(&add_button));
+    add_row.add_row(&destination_entry);
+    add_row.add_row(&gateway_entry);
+    add_row.add_row(&metric_entry);
+    add_row.add_row(&add_button_row);
+    group.add(&add_row);
+
+    let editor = RoutesEditor {
+        group: group.clone(),
+        route_rows: Rc::new(RefCell::new(Vec::new())),
+        connection_name: connection_name.to_string(),
+        active,
+        toast_overlay: toast_overlay.clone(),
+    };
+
+    let editor_for_add = editor.clone();
+    add_button.connect_clicked(move |button| {
+        let route = match nm::parse_static_route(
+            &destination_entry.text(),
+            &gateway_entry.text(),
+            &metric_entry.text(),
+        ) {
+            Ok(route) => route,
+            Err(e) => {
+                common::show_toast(&editor_for_add.toast_overlay, &e.to_string());
+                return;
+            }
+        };
+
+        let editor = editor_for_add.clone();
+        let button = button.clone();
+        let entries = [
+            destination_entry.clone(),
+            gateway_entry.clone(),
+            metric_entry.clone(),
+        ];
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let change = nm::add_static_route(&editor.connection_name, &route);
+            if editor.apply(change, "add route", "Route added").await {
+                for entry in &entries {
+                    entry.set_text("");
+                }
+            }
+            button.set_sensitive(true);
+        });
+    });
+
+    editor.reload().await;
+    group
+}
@@ -4,13 +4,19 @@ use chrono::Utc;
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::rc::Rc;
 
-use crate::config::{self, HotspotClientRule};
+use crate::config::{self, HotspotClientRule, HotspotMacFilterMode};
 use crate::hotspot;
-use crate::modem_manager;
+use crate::oui::{self, vendor_from_mac};
+use crate::polling::PollScheduler;
 use crate::state::{AppState, PageKind};
+use crate::ui::bluetooth_page::BluetoothPage;
+use crate::ui::lan_scan_page::LanScanPage;
+use crate::ui::modem_page::ModemPage;
+use crate::ui::wake_page::WakePage;
 use crate::ui::{common, icon_name};
 use anyhow::Result;
 
@@ -18,14 +24,16 @@ use anyhow::Result;
 pub struct DevicesPage {
     pub widget: gtk4::Box,
     toast_overlay: adw::ToastOverlay,
-    mobile_group: adw::PreferencesGroup,
-    mobile_status_row: adw::ActionRow,
-    mobile_signal_row: adw::ActionRow,
-    mobile_network_row: adw::ActionRow,
-    mobile_sim_row: adw::ActionRow,
-    mobile_apn_entry: adw::EntryRow,
-    mobile_connect_button: gtk4::Button,
-    mobile_radio_button: gtk4::Button,
+    modem_page: ModemPage,
+    bluetooth_page: BluetoothPage,
+    access_mode_combo: adw::ComboRow,
+    access_list_row: adw::ActionRow,
+    access_list_button: gtk4::Button,
+    // * Set while the access rows are synced from disk so their handlers don't save it back
+    syncing_access_control: Rc<Cell<bool>>,
+    blocked_group: adw::PreferencesGroup,
+    // * Rows in the Blocked Devices group, rebuilt on every refresh
+    blocked_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
     list_box: gtk4::ListBox,
     empty_state: adw::StatusPage,
     client_count_label: gtk4::Label,
@@ -85,6 +93,35 @@ impl DevicesPage {
             ])
             .build();
 
+        let copy_list_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "edit-copy-symbolic",
+                &["edit-copy", "edit-paste-symbolic"][..],
+            ))
+            .tooltip_text("Copy device list")
+            .css_classes(vec![
+                "flat".to_string(),
+                "circular".to_string(),
+                "touch-target".to_string(),
+            ])
+            .build();
+
+        let vendor_update_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "software-update-available-symbolic",
+                &[
+                    "system-software-update-symbolic",
+                    "folder-download-symbolic",
+                ][..],
+            ))
+            .tooltip_text("Update vendor database")
+            .css_classes(vec![
+                "flat".to_string(),
+                "circular".to_string(),
+                "touch-target".to_string(),
+            ])
+            .build();
+
         let spinner = gtk4::Spinner::new();
         spinner.add_css_class("big-spinner");
         spinner.set_size_request(22, 22);
@@ -104,6 +141,8 @@ impl DevicesPage {
 
         header.append(&title);
         header.append(&spinner);
+        header.append(&vendor_update_button);
+        header.append(&copy_list_button);
         header.append(&refresh_button);
         widget.append(&header);
         // * Add a dedicated client-count confidence line under the Devices header.
@@ -124,50 +163,45 @@ impl DevicesPage {
         content.set_margin_end(16);
         content.set_margin_bottom(16);
 
-        let mobile_group = adw::PreferencesGroup::new();
-        mobile_group.set_title("Mobile Data");
-        mobile_group.set_description(Some(
-            "Manage cellular modem status, APN, and connection state from ModemManager.",
-        ));
+        let modem_page = ModemPage::new(&toast_overlay);
+        content.append(&modem_page.widget);
+        let bluetooth_page = BluetoothPage::new(&toast_overlay);
+        content.append(&bluetooth_page.widget);
 
-        let mobile_status_row = adw::ActionRow::builder()
-            .title("Status")
-            .subtitle("Checking for modems...")
+        let access_group = adw::PreferencesGroup::builder()
+            .title("Hotspot Access Control")
+            .description("Decide which devices may use the hotspot by MAC address")
+            .margin_bottom(12)
             .build();
-        let mobile_actions = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
-        let mobile_connect_button = gtk4::Button::builder()
-            .label("Connect")
-            .css_classes(vec!["flat".to_string()])
+        let access_mode_model = gtk4::StringList::new(&["Off", "Allow List", "Deny List"]);
+        let access_mode_combo = adw::ComboRow::builder()
+            .title("Mode")
+            .subtitle("Every device may connect")
+            .model(&access_mode_model)
             .build();
-        let mobile_radio_button = gtk4::Button::builder()
-            .label("Turn radio on")
+        let access_list_button = gtk4::Button::builder()
+            .label("Edit")
+            .valign(gtk4::Align::Center)
             .css_classes(vec!["flat".to_string()])
             .build();
-        mobile_actions.append(&mobile_connect_button);
-        mobile_actions.append(&mobile_radio_button);
-        mobile_status_row.add_suffix(&mobile_actions);
-
-        let mobile_signal_row = adw::ActionRow::builder()
-            .title("Signal")
-            .subtitle("Unavailable")
-            .build();
-        let mobile_network_row = adw::ActionRow::builder()
-            .title("Network")
-            .subtitle("Unavailable")
+        let access_list_row = adw::ActionRow::builder()
+            .title("Listed Devices")
+            .subtitle("No devices listed")
             .build();
-        let mobile_sim_row = adw::ActionRow::builder()
-            .title("SIM / PIN")
-            .subtitle("Unavailable")
+        access_list_row.add_suffix(&access_list_button);
+        access_list_row.set_activatable_widget(Some(&access_list_button));
+        access_group.add(&access_mode_combo);
+        access_group.add(&access_list_row);
+        content.append(&access_group);
+
+        // * Lists blocked devices even when they're not connected, so they can be let back in
+        let blocked_group = adw::PreferencesGroup::builder()
+            .title("Blocked Devices")
+            .description("These devices can't use the hotspot, even with the password")
+            .margin_bottom(12)
+            .visible(false)
             .build();
-        let mobile_apn_entry = adw::EntryRow::builder().title("APN").build();
-        mobile_apn_entry.set_text("");
-
-        mobile_group.add(&mobile_status_row);
-        mobile_group.add(&mobile_signal_row);
-        mobile_group.add(&mobile_network_row);
-        mobile_group.add(&mobile_sim_row);
-        mobile_group.add(&mobile_apn_entry);
-        content.append(&mobile_group);
+        content.append(&blocked_group);
 
         let list_box = gtk4::ListBox::builder()
             .css_classes(vec!["boxed-list".to_string()])
@@ -194,6 +228,11 @@ impl DevicesPage {
         content.append(&list_box);
         content.append(&empty_state);
 
+        let wake_page = WakePage::new(&toast_overlay);
+        let lan_scan_page = LanScanPage::new(&toast_overlay, &wake_page);
+        content.append(&lan_scan_page.widget);
+        content.append(&wake_page.widget);
+
         clamp.set_child(Some(&content));
         scrolled.set_child(Some(&clamp));
         toast_overlay.set_child(Some(&scrolled));
@@ -202,14 +241,14 @@ impl DevicesPage {
         let page = Self {
             widget,
             toast_overlay,
-            mobile_group: mobile_group.clone(),
-            mobile_status_row: mobile_status_row.clone(),
-            mobile_signal_row: mobile_signal_row.clone(),
-            mobile_network_row: mobile_network_row.clone(),
-            mobile_sim_row: mobile_sim_row.clone(),
-            mobile_apn_entry: mobile_apn_entry.clone(),
-            mobile_connect_button: mobile_connect_button.clone(),
-            mobile_radio_button: mobile_radio_button.clone(),
+            modem_page,
+            bluetooth_page,
+            access_mode_combo: access_mode_combo.clone(),
+            access_list_row,
+            access_list_button: access_list_button.clone(),
+            syncing_access_control: Rc::new(Cell::new(false)),
+            blocked_group,
+            blocked_rows: Rc::new(RefCell::new(Vec::new())),
             list_box,
             empty_state,
             client_count_label: client_count_label.clone(),
@@ -235,18 +274,39 @@ impl DevicesPage {
         });
 
         let page_ref = page.clone();
-        mobile_connect_button.connect_clicked(move |_| {
+        copy_list_button.connect_clicked(move |_| {
             let page = page_ref.clone();
             glib::spawn_future_local(async move {
-                page.toggle_mobile_connection().await;
+                page.copy_device_list().await;
             });
         });
 
         let page_ref = page.clone();
-        mobile_radio_button.connect_clicked(move |_| {
+        vendor_update_button.connect_clicked(move |button| {
             let page = page_ref.clone();
+            let button = button.clone();
             glib::spawn_future_local(async move {
-                page.toggle_mobile_radio().await;
+                page.update_vendor_database(&button).await;
+            });
+        });
+
+        let page_ref = page.clone();
+        access_mode_combo.connect_selected_notify(move |combo| {
+            if page_ref.syncing_access_control.get() {
+                return;
+            }
+            let mode = mac_filter_mode_from_selection(combo.selected());
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.set_access_mode(mode).await;
+            });
+        });
+
+        let page_ref = page.clone();
+        access_list_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.edit_access_list().await;
             });
         });
 
@@ -258,45 +318,33 @@ impl DevicesPage {
     pub fn set_page_visible(&self, visible: bool) {
         self.app_state.set_page_visible(PageKind::Devices, visible);
         if visible {
-            self.start_auto_refresh();
             let page = self.clone();
             glib::spawn_future_local(async move {
                 page.refresh_devices(false).await;
             });
-        } else {
-            self.stop_auto_refresh();
         }
     }
 
-    fn start_auto_refresh(&self) {
-        if self.app_state.devices_has_refresh_source() {
-            return;
-        }
-
+    pub fn attach_poll_scheduler(&self, scheduler: &PollScheduler) {
         let page_ref = self.clone();
-        let source = glib::timeout_add_seconds_local(8, move || {
-            if !page_ref.app_state.is_page_visible(PageKind::Devices) {
-                return glib::ControlFlow::Continue;
-            }
-            if page_ref.app_state.devices_refresh_in_flight() {
-                return glib::ControlFlow::Continue;
-            }
-
-            let page = page_ref.clone();
-            glib::spawn_future_local(async move {
-                page.refresh_devices(false).await;
-            });
-
-            glib::ControlFlow::Continue
-        });
-
-        self.app_state.set_devices_refresh_source(Some(source));
-    }
+        scheduler.subscribe(
+            "devices",
+            std::time::Duration::from_secs(4),
+            std::time::Duration::from_secs(30),
+            move || {
+                if !page_ref.app_state.is_page_visible(PageKind::Devices) {
+                    return;
+                }
+                if page_ref.app_state.devices_refresh_in_flight() {
+                    return;
+                }
 
-    fn stop_auto_refresh(&self) {
-        if let Some(id) = self.app_state.take_devices_refresh_source() {
-            id.remove();
-        }
+                let page = page_ref.clone();
+                glib::spawn_future_local(async move {
+                    page.refresh_devices(false).await;
+                });
+            },
+        );
     }
 
     pub async fn refresh_devices(&self, show_feedback: bool) {
@@ -318,7 +366,9 @@ impl DevicesPage {
             self.list_box.add_css_class("list-loading");
         }
 
-        self.refresh_mobile_data().await;
+        self.modem_page.refresh().await;
+        self.bluetooth_page.refresh().await;
+        self.refresh_access_control().await;
 
         match self.get_connected_devices().await {
             Ok(devices) => {
@@ -358,178 +408,6 @@ impl DevicesPage {
         self.app_state.set_devices_refresh_in_flight(false);
     }
 
-    async fn refresh_mobile_data(&self) {
-        match modem_manager::get_mobile_data_status().await {
-            Ok(status) => self.apply_mobile_data_status(status),
-            Err(e) => {
-                log::error!("Failed to refresh mobile data status: {}", e);
-                self.mobile_group
-                    .set_description(Some("Could not read ModemManager status."));
-                self.mobile_status_row.set_subtitle("Status unavailable");
-                self.mobile_signal_row.set_subtitle("Unavailable");
-                self.mobile_network_row.set_subtitle("Unavailable");
-                self.mobile_sim_row.set_subtitle("Unavailable");
-                self.mobile_apn_entry.set_sensitive(false);
-                self.mobile_connect_button.set_sensitive(false);
-                self.mobile_radio_button.set_sensitive(false);
-            }
-        }
-    }
-
-    fn apply_mobile_data_status(&self, status: modem_manager::MobileDataStatus) {
-        if !status.service_available {
-            self.mobile_group.set_description(Some(
-                "ModemManager is not available on this system, so mobile data controls are disabled.",
-            ));
-            self.mobile_status_row.set_subtitle(&status.state_label);
-            self.mobile_signal_row.set_subtitle("Unavailable");
-            self.mobile_network_row.set_subtitle("Unavailable");
-            self.mobile_sim_row.set_subtitle("Unavailable");
-            self.mobile_apn_entry.set_text("");
-            self.mobile_apn_entry.set_sensitive(false);
-            self.mobile_connect_button.set_label("Connect");
-            self.mobile_connect_button.set_sensitive(false);
-            self.mobile_radio_button.set_label("Turn radio on");
-            self.mobile_radio_button.set_sensitive(false);
-            return;
-        }
-
-        if !status.modem_present {
-            self.mobile_group.set_description(Some(
-                "No mobile broadband modem was detected. If you attach one, it will appear here automatically.",
-            ));
-            self.mobile_status_row.set_subtitle(&status.state_label);
-            self.mobile_signal_row.set_subtitle("Unavailable");
-            self.mobile_network_row.set_subtitle("Unavailable");
-            self.mobile_sim_row.set_subtitle("Unavailable");
-            self.mobile_apn_entry.set_text("");
-            self.mobile_apn_entry.set_sensitive(false);
-            self.mobile_connect_button.set_label("Connect");
-            self.mobile_connect_button.set_sensitive(false);
-            self.mobile_radio_button.set_label("Turn radio on");
-            self.mobile_radio_button.set_sensitive(false);
-            return;
-        }
-
-        self.mobile_group.set_description(Some(
-            "Cellular modem controls are available through ModemManager.",
-        ));
-
-        let status_subtitle = match status.device_name.as_deref() {
-            Some(device) => format!("{} • {}", status.state_label, device),
-            None => status.state_label.clone(),
-        };
-        self.mobile_status_row.set_subtitle(&status_subtitle);
-
-        let signal_text = match status.signal_quality_percent {
-            Some(signal) => format!("{}%", signal),
-            None => "Unavailable".to_string(),
-        };
-        self.mobile_signal_row.set_subtitle(&signal_text);
-
-        let network_text = format!(
-            "{} • {}",
-            status
-                .operator_name
-                .as_deref()
-                .filter(|value| !value.is_empty())
-                .unwrap_or("Operator unavailable"),
-            status
-                .network_generation
-                .as_deref()
-                .filter(|value| !value.is_empty())
-                .unwrap_or("Unavailable")
-        );
-        self.mobile_network_row.set_subtitle(&network_text);
-        self.mobile_sim_row
-            .set_subtitle(&format!("{} • {}", status.sim_status, status.pin_status));
-        self.mobile_apn_entry
-            .set_text(status.apn.as_deref().unwrap_or(""));
-        self.mobile_apn_entry.set_sensitive(status.radio_enabled);
-        self.mobile_connect_button.set_label(if status.connected {
-            "Disconnect"
-        } else {
-            "Connect"
-        });
-        self.mobile_connect_button.set_sensitive(
-            status.radio_enabled && !matches!(status.state_label.as_str(), "Locked"),
-        );
-        self.mobile_radio_button.set_label(if status.radio_enabled {
-            "Turn radio off"
-        } else {
-            "Turn radio on"
-        });
-        self.mobile_radio_button.set_sensitive(true);
-    }
-
-    async fn toggle_mobile_connection(&self) {
-        let status = match modem_manager::get_mobile_data_status().await {
-            Ok(status) => status,
-            Err(e) => {
-                self.show_toast(&format!("Failed to read mobile data status: {}", e));
-                return;
-            }
-        };
-
-        if !status.modem_present {
-            self.show_toast("No mobile modem detected");
-            return;
-        }
-
-        let apn = self.mobile_apn_entry.text().trim().to_string();
-        let result = if status.connected {
-            modem_manager::disconnect_mobile_data().await
-        } else {
-            modem_manager::connect_mobile_data((!apn.is_empty()).then_some(apn.as_str())).await
-        };
-
-        match result {
-            Ok(()) => {
-                self.show_toast(if status.connected {
-                    "Mobile data disconnected"
-                } else {
-                    "Mobile data connected"
-                });
-                self.refresh_mobile_data().await;
-            }
-            Err(e) => {
-                log::error!("Failed to toggle mobile data connection: {}", e);
-                self.show_toast(&format!("Failed to update mobile data: {}", e));
-            }
-        }
-    }
-
-    async fn toggle_mobile_radio(&self) {
-        let status = match modem_manager::get_mobile_data_status().await {
-            Ok(status) => status,
-            Err(e) => {
-                self.show_toast(&format!("Failed to read mobile data status: {}", e));
-                return;
-            }
-        };
-
-        if !status.modem_present {
-            self.show_toast("No mobile modem detected");
-            return;
-        }
-
-        let target = !status.radio_enabled;
-        match modem_manager::set_radio_enabled(target).await {
-            Ok(()) => {
-                self.show_toast(if target {
-                    "Mobile radio enabled"
-                } else {
-                    "Mobile radio disabled"
-                });
-                self.refresh_mobile_data().await;
-            }
-            Err(e) => {
-                log::error!("Failed to toggle mobile radio: {}", e);
-                self.show_toast(&format!("Failed to update radio state: {}", e));
-            }
-        }
-    }
-
     fn update_empty_state_message(&self, hotspot_active: bool) {
         if hotspot_active {
             self.empty_state
@@ -572,6 +450,29 @@ impl DevicesPage {
             .collect())
     }
 
+    async fn copy_device_list(&self) {
+        let devices = match self.get_connected_devices().await {
+            Ok(devices) => devices,
+            Err(e) => {
+                log::error!("Failed to list connected devices: {}", e);
+                self.show_toast(&format!("Failed to list devices: {}", e));
+                return;
+            }
+        };
+
+        if devices.is_empty() {
+            self.show_toast("No connected devices to copy");
+            return;
+        }
+
+        let rows: Vec<Vec<String>> = devices.iter().map(device_table_row).collect();
+        copy_to_clipboard(&format_text_table(
+            &["Hostname", "IP", "MAC", "Vendor", "Kind"],
+            &rows,
+        ));
+        self.show_toast(&format!("Copied {} device(s) to clipboard", devices.len()));
+    }
+
     fn update_list(&self, devices: Vec<ConnectedDevice>) {
         while let Some(child) = self.list_box.first_child() {
             self.list_box.remove(&child);
@@ -591,6 +492,12 @@ impl DevicesPage {
                 config::HotspotConfig::default()
             }
         };
+        let access_label = match hotspot_config.mac_filter_mode {
+            HotspotMacFilterMode::Disabled => None,
+            HotspotMacFilterMode::Allowlist => Some("On allow list"),
+            HotspotMacFilterMode::Blocklist => Some("On deny list"),
+        };
+        let listed_macs = hotspot_config.mac_filter_list.clone();
         let rule_map: HashMap<String, HotspotClientRule> = hotspot_config
             .client_rules
             .into_iter()
@@ -618,7 +525,15 @@ impl DevicesPage {
                     subtitle_parts.push(lease_info);
                 }
             }
+            if let Some(label) = access_label {
+                if listed_macs.contains(&device.mac) {
+                    subtitle_parts.push(label.to_string());
+                }
+            }
             if let Some(rule) = rule_map.get(&device.mac) {
+                if let Some(limit) = speed_limit_summary(rule) {
+                    subtitle_parts.push(limit);
+                }
                 if let Some(summary) = rule_summary(rule) {
                     subtitle_parts.push(summary);
                 }
@@ -678,8 +593,11 @@ impl DevicesPage {
         let device_mac = device.mac.clone();
         let page = self.clone();
         let device_mac_for_status = device.mac.clone();
-        let currently_blocked = config::load_config_sync(&config::hotspot_config_path())
-            .ok()
+        let saved_config = config::load_config_sync(&config::hotspot_config_path()).ok();
+        let currently_listed = saved_config
+            .as_ref()
+            .is_some_and(|config| config.is_mac_filter_listed(&device.mac));
+        let currently_blocked = saved_config
             .and_then(|config| {
                 config
                     .client_rules
@@ -744,6 +662,28 @@ impl DevicesPage {
             });
             menu_box.append(&manage_btn);
 
+            let limit_btn = gtk4::Button::builder()
+                .label("Limit speed…")
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            let popover_limit = popover.clone();
+            let page_limit = page.clone();
+            let limit_device = ConnectedDevice {
+                ip: device_ip.clone(),
+                mac: device_mac.clone(),
+                hostname: Some(device_name.clone()),
+                lease_expiry: None,
+            };
+            limit_btn.connect_clicked(move |_| {
+                popover_limit.popdown();
+                let page = page_limit.clone();
+                let device = limit_device.clone();
+                glib::spawn_future_local(async move {
+                    page.limit_device_speed(device).await;
+                });
+            });
+            menu_box.append(&limit_btn);
+
             let copy_ip_btn = gtk4::Button::builder()
                 .label("Copy IP")
                 .css_classes(vec!["flat".to_string()])
@@ -809,6 +749,28 @@ impl DevicesPage {
             });
 
             menu_box.append(&block_btn);
+
+            let access_btn = gtk4::Button::builder()
+                .label(if currently_listed {
+                    "Remove from access list"
+                } else {
+                    "Add to access list"
+                })
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            let popover_access = popover.clone();
+            let page_access = page.clone();
+            let device_mac_for_access = device_mac.clone();
+            access_btn.connect_clicked(move |_| {
+                popover_access.popdown();
+                let page = page_access.clone();
+                let mac = device_mac_for_access.clone();
+                glib::spawn_future_local(async move {
+                    page.set_device_listed(&mac, !currently_listed).await;
+                });
+            });
+            menu_box.append(&access_btn);
+
             popover.set_child(Some(&menu_box));
             popover.set_parent(&row_for_menu);
             popover.popup();
@@ -817,6 +779,179 @@ impl DevicesPage {
         row.add_controller(gesture);
     }
 
+    async fn update_vendor_database(&self, button: &gtk4::Button) {
+        common::set_busy(
+            &self.spinner,
+            &self.operation_status_label,
+            Some(button),
+            true,
+            Some("Downloading vendor database..."),
+        );
+        let result = oui::update_vendor_database().await;
+        common::set_busy(
+            &self.spinner,
+            &self.operation_status_label,
+            Some(button),
+            false,
+            None,
+        );
+        match result {
+            Ok(count) => {
+                self.show_toast(&format!("Vendor database updated ({} vendors)", count));
+                self.refresh_devices(false).await;
+            }
+            Err(e) => self.show_toast(&format!("Failed to update vendor database: {}", e)),
+        }
+    }
+
+    async fn refresh_access_control(&self) {
+        let hotspot_config = config::load_config(&config::hotspot_config_path())
+            .await
+            .unwrap_or_default();
+        self.syncing_access_control.set(true);
+        self.access_mode_combo
+            .set_selected(selection_from_mac_filter_mode(
+                &hotspot_config.mac_filter_mode,
+            ));
+        self.syncing_access_control.set(false);
+        self.access_mode_combo
+            .set_subtitle(access_mode_subtitle(&hotspot_config.mac_filter_mode));
+        self.access_list_row
+            .set_subtitle(&access_list_summary(&hotspot_config.mac_filter_list));
+        self.update_blocked_rows(&hotspot_config.client_rules);
+
+        let nft_available = hotspot::advanced_support().await.nft_available;
+        self.access_mode_combo.set_sensitive(nft_available);
+        self.access_list_button.set_sensitive(nft_available);
+        if !nft_available {
+            self.access_mode_combo
+                .set_subtitle("Requires nftables (`nft`) to be installed");
+        }
+    }
+
+    fn update_blocked_rows(&self, rules: &[HotspotClientRule]) {
+        for row in self.blocked_rows.borrow_mut().drain(..) {
+            self.blocked_group.remove(&row);
+        }
+        let blocked: Vec<&HotspotClientRule> = rules.iter().filter(|rule| rule.blocked).collect();
+        for rule in &blocked {
+            let title = rule
+                .display_name
+                .clone()
+                .unwrap_or_else(|| rule.mac_address.clone());
+            let subtitle = match vendor_from_mac(&rule.mac_address) {
+                Some(vendor) => format!("{} • {}", rule.mac_address, vendor),
+                None => rule.mac_address.clone(),
+            };
+            let row = adw::ActionRow::builder()
+                .title(title.as_str())
+                .subtitle(subtitle)
+                .build();
+            let unblock_button = gtk4::Button::builder()
+                .label("Unblock")
+                .valign(gtk4::Align::Center)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            row.add_suffix(&unblock_button);
+            row.set_activatable_widget(Some(&unblock_button));
+
+            let page = self.clone();
+            let mac = rule.mac_address.clone();
+            unblock_button.connect_clicked(move |button| {
+                button.set_sensitive(false);
+                let page = page.clone();
+                let mac = mac.clone();
+                let title = title.clone();
+                glib::spawn_future_local(async move {
+                    match page.set_device_blocked(&mac, false).await {
+                        Ok(()) => page.show_toast(&format!("{} is no longer blocked", title)),
+                        Err(e) => page.show_toast(&format!("Failed to unblock device: {}", e)),
+                    }
+                    page.refresh_devices(false).await;
+                });
+            });
+
+            self.blocked_group.add(&row);
+            self.blocked_rows.borrow_mut().push(row);
+        }
+        self.blocked_group.set_visible(!blocked.is_empty());
+    }
+
+    async fn update_access_control(
+        &self,
+        apply: impl FnOnce(&mut config::HotspotConfig),
+    ) -> anyhow::Result<()> {
+        let mut hotspot_config = match config::load_config(&config::hotspot_config_path()).await {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("Hotspot config load failed: {}", e);
+                config::HotspotConfig::default()
+            }
+        };
+        apply(&mut hotspot_config);
+        hotspot_config.normalize();
+        config::save_config(&config::hotspot_config_path(), &hotspot_config).await?;
+        hotspot::sync_runtime_rules_from_disk().await.ok();
+        Ok(())
+    }
+
+    async fn set_access_mode(&self, mode: HotspotMacFilterMode) {
+        let warn_empty_allowlist = mode == HotspotMacFilterMode::Allowlist;
+        let mut list_empty = false;
+        let result = self
+            .update_access_control(|hotspot_config| {
+                list_empty = hotspot_config.mac_filter_list.is_empty();
+                hotspot_config.mac_filter_mode = mode;
+            })
+            .await;
+        match result {
+            Err(e) => self.show_toast(&format!("Failed to update access control: {}", e)),
+            Ok(()) if warn_empty_allowlist && list_empty => {
+                self.show_toast("The allow list is empty, so no device can use the hotspot")
+            }
+            Ok(()) => {}
+        }
+        self.refresh_devices(false).await;
+    }
+
+    async fn set_device_listed(&self, mac_address: &str, listed: bool) {
+        let Some(mac) = config::normalize_mac_address(mac_address) else {
+            self.show_toast("Invalid MAC address");
+            return;
+        };
+        let result = self
+            .update_access_control(|hotspot_config| {
+                hotspot_config.mac_filter_list.retain(|entry| *entry != mac);
+                if listed {
+                    hotspot_config.mac_filter_list.push(mac.clone());
+                }
+            })
+            .await;
+        match result {
+            Ok(()) if listed => self.show_toast("Added to the access list"),
+            Ok(()) => self.show_toast("Removed from the access list"),
+            Err(e) => self.show_toast(&format!("Failed to update access control: {}", e)),
+        }
+        self.refresh_devices(false).await;
+    }
+
+    async fn edit_access_list(&self) {
+        let initial = config::load_config(&config::hotspot_config_path())
+            .await
+            .map(|hotspot_config| hotspot_config.mac_filter_list)
+            .unwrap_or_default();
+        let Some(edited) = edit_mac_list(self.widget.upcast_ref(), initial).await else {
+            return;
+        };
+        if let Err(e) = self
+            .update_access_control(|hotspot_config| hotspot_config.mac_filter_list = edited)
+            .await
+        {
+            self.show_toast(&format!("Failed to update access control: {}", e));
+        }
+        self.refresh_devices(false).await;
+    }
+
     async fn show_device_details_dialog(&self, device: ConnectedDevice) {
         let title = device
             .hostname
@@ -887,6 +1022,76 @@ impl DevicesPage {
         }
     }
 
+    async fn limit_device_speed(&self, device: ConnectedDevice) {
+        let existing_rule = config::load_config(&config::hotspot_config_path())
+            .await
+            .ok()
+            .and_then(|config| {
+                config
+                    .client_rules
+                    .into_iter()
+                    .find(|rule| rule.mac_address == device.mac)
+            });
+        let mut rule = existing_rule.unwrap_or_else(|| HotspotClientRule {
+            mac_address: device.mac.clone(),
+            ..HotspotClientRule::default()
+        });
+
+        let download_spin = mbit_spin_button(rule.download_limit_kbps);
+        let download_row = adw::ActionRow::builder()
+            .title("Download")
+            .subtitle("Mbit/s, 0 for no limit")
+            .build();
+        download_row.add_suffix(&download_spin);
+        let upload_spin = mbit_spin_button(rule.upload_limit_kbps);
+        let upload_row = adw::ActionRow::builder()
+            .title("Upload")
+            .subtitle("Mbit/s, 0 for no limit")
+            .build();
+        upload_row.add_suffix(&upload_spin);
+
+        let group = adw::PreferencesGroup::new();
+        group.add(&download_row);
+        group.add(&upload_row);
+
+        let title = device
+            .hostname
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or(device.ip.as_str())
+            .to_string();
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("Limit Speed for {}", title))
+            .body("The limit follows this device's MAC address even if its IP changes.")
+            .extra_child(&group)
+            .default_response("save")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        if response.as_str() != "save" {
+            return;
+        }
+
+        rule.download_limit_kbps = mbit_spin_to_kbps(&download_spin);
+        rule.upload_limit_kbps = mbit_spin_to_kbps(&upload_spin);
+        let message = speed_limit_summary(&rule)
+            .map(|summary| format!("{}: {}", title, summary))
+            .unwrap_or_else(|| format!("Speed limit removed for {}", title));
+        if let Err(e) = self.save_device_rule(&device.mac, rule).await {
+            self.show_toast(&format!("Failed to save speed limit: {}", e));
+            return;
+        }
+        self.show_toast(&message);
+        self.refresh_devices(false).await;
+    }
+
     async fn save_device_rule(
         &self,
         mac_address: &str,
@@ -921,7 +1126,11 @@ impl DevicesPage {
         Ok(())
     }
 
-    async fn set_device_blocked(&self, mac_address: &str, blocked: bool) -> anyhow::Result<()> {
+    pub(crate) async fn set_device_blocked(
+        &self,
+        mac_address: &str,
+        blocked: bool,
+    ) -> anyhow::Result<()> {
         let normalized_mac = config::normalize_mac_address(mac_address)
             .ok_or_else(|| anyhow::anyhow!("Invalid MAC address"))?;
         let mut hotspot_config = match config::load_config(&config::hotspot_config_path()).await {
@@ -1199,6 +1408,151 @@ impl DevicesPage {
     }
 }
 
+fn mac_filter_mode_from_selection(selected: u32) -> HotspotMacFilterMode {
+    match selected {
+        1 => HotspotMacFilterMode::Allowlist,
+        2 => HotspotMacFilterMode::Blocklist,
+        _ => HotspotMacFilterMode::Disabled,
+    }
+}
+
+fn selection_from_mac_filter_mode(mode: &HotspotMacFilterMode) -> u32 {
+    match mode {
+        HotspotMacFilterMode::Disabled => 0,
+        HotspotMacFilterMode::Allowlist => 1,
+        HotspotMacFilterMode::Blocklist => 2,
+    }
+}
+
+fn access_mode_subtitle(mode: &HotspotMacFilterMode) -> &'static str {
+    match mode {
+        HotspotMacFilterMode::Disabled => "Every device may connect",
+        HotspotMacFilterMode::Allowlist => "Only listed devices may use the hotspot",
+        HotspotMacFilterMode::Blocklist => "Listed devices are kept off the hotspot",
+    }
+}
+
+fn access_list_summary(macs: &[String]) -> String {
+    match macs.len() {
+        0 => "No devices listed".to_string(),
+        1 => "1 device listed".to_string(),
+        count => format!("{} devices listed", count),
+    }
+}
+
+fn repopulate_mac_rows(list_box: &gtk4::ListBox, macs: &Rc<RefCell<Vec<String>>>) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+
+    let snapshot = macs.borrow().clone();
+    for (index, mac) in snapshot.into_iter().enumerate() {
+        let row = adw::ActionRow::builder().title(&mac).build();
+        if let Some(vendor) = vendor_from_mac(&mac) {
+            row.set_subtitle(&vendor);
+        }
+        let delete_btn = gtk4::Button::builder()
+            .label("Delete")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string(), "destructive-action".to_string()])
+            .build();
+        row.add_suffix(&delete_btn);
+
+        let list_box_for_delete = list_box.clone();
+        let macs_for_delete = macs.clone();
+        delete_btn.connect_clicked(move |_| {
+            if let Ok(mut macs) = macs_for_delete.try_borrow_mut() {
+                if index < macs.len() {
+                    macs.remove(index);
+                }
+            }
+            repopulate_mac_rows(&list_box_for_delete, &macs_for_delete);
+        });
+
+        list_box.append(&row);
+    }
+}
+
+// * Returns the edited list, or None when the dialog was cancelled.
+async fn edit_mac_list(parent: &gtk4::Widget, initial: Vec<String>) -> Option<Vec<String>> {
+    let macs = Rc::new(RefCell::new(initial));
+
+    let mac_entry = adw::EntryRow::builder().title("MAC Address").build();
+    let error_label = gtk4::Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .css_classes(vec!["error".to_string()])
+        .visible(false)
+        .build();
+    let add_button = gtk4::Button::builder()
+        .label("Add device")
+        .halign(gtk4::Align::End)
+        .css_classes(vec!["flat".to_string()])
+        .build();
+
+    let form = adw::PreferencesGroup::new();
+    form.add(&mac_entry);
+
+    let list_box = gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(vec!["boxed-list".to_string()])
+        .build();
+    repopulate_mac_rows(&list_box, &macs);
+
+    let list_box_for_add = list_box.clone();
+    let macs_for_add = macs.clone();
+    let error_label_for_add = error_label.clone();
+    let mac_for_add = mac_entry.clone();
+    add_button.connect_clicked(move |_| {
+        let Some(mac) = config::normalize_mac_address(&mac_for_add.text()) else {
+            error_label_for_add.set_text("Enter a MAC address like AA:BB:CC:DD:EE:FF");
+            error_label_for_add.set_visible(true);
+            return;
+        };
+        error_label_for_add.set_visible(false);
+        if let Ok(mut macs) = macs_for_add.try_borrow_mut() {
+            if !macs.contains(&mac) {
+                macs.push(mac);
+                macs.sort();
+            }
+        }
+        mac_for_add.set_text("");
+        repopulate_mac_rows(&list_box_for_add, &macs_for_add);
+    });
+
+    let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+    body.set_margin_top(12);
+    body.set_margin_bottom(12);
+    body.set_margin_start(12);
+    body.set_margin_end(12);
+    body.append(&form);
+    body.append(&error_label);
+    body.append(&add_button);
+    body.append(&list_box);
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Access List")
+        .body("In Allow List mode only these devices may use the hotspot; in Deny List mode they are kept off it.")
+        .extra_child(&body)
+        .default_response("save")
+        .close_response("cancel")
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+    let response = if let Some(parent) = parent.root().and_downcast_ref::<gtk4::Window>() {
+        dialog.choose_future(Some(parent)).await
+    } else {
+        dialog.choose_future(None::<&gtk4::Window>).await
+    };
+
+    if response.as_str() != "save" {
+        return None;
+    }
+    let edited = macs.borrow().clone();
+    Some(edited)
+}
+
 fn spin_value_to_option(spin: &gtk4::SpinButton) -> Option<u32> {
     let value = spin.value_as_int();
     if value <= 0 {
@@ -1208,16 +1562,49 @@ fn spin_value_to_option(spin: &gtk4::SpinButton) -> Option<u32> {
     }
 }
 
-fn rule_summary(rule: &HotspotClientRule) -> Option<String> {
-    let mut parts = Vec::new();
-    if rule.blocked {
-        parts.push("blocked".to_string());
+fn mbit_spin_button(kbps: Option<u32>) -> gtk4::SpinButton {
+    let spin = gtk4::SpinButton::builder()
+        .adjustment(&gtk4::Adjustment::new(0.0, 0.0, 1000.0, 0.5, 10.0, 0.0))
+        .numeric(true)
+        .digits(1)
+        .valign(gtk4::Align::Center)
+        .build();
+    spin.set_value(kbps.unwrap_or_default() as f64 / 1000.0);
+    spin
+}
+
+fn mbit_spin_to_kbps(spin: &gtk4::SpinButton) -> Option<u32> {
+    let kbps = (spin.value() * 1000.0).round();
+    (kbps > 0.0).then_some(kbps as u32)
+}
+
+fn format_rate(kbps: u32) -> String {
+    if kbps >= 1000 && kbps.is_multiple_of(100) {
+        format!("{} Mbit/s", kbps as f64 / 1000.0)
+    } else {
+        format!("{} kbit/s", kbps)
     }
+}
+
+fn speed_limit_summary(rule: &HotspotClientRule) -> Option<String> {
+    let mut parts = Vec::new();
     if let Some(limit) = rule.download_limit_kbps {
-        parts.push(format!("down {} kbit/s", limit));
+        parts.push(format!("↓ {}", format_rate(limit)));
     }
     if let Some(limit) = rule.upload_limit_kbps {
-        parts.push(format!("up {} kbit/s", limit));
+        parts.push(format!("↑ {}", format_rate(limit)));
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(format!("Limited to {}", parts.join(" ")))
+    }
+}
+
+fn rule_summary(rule: &HotspotClientRule) -> Option<String> {
+    let mut parts = Vec::new();
+    if rule.blocked {
+        parts.push("blocked".to_string());
     }
     if let Some(limit) = rule.time_limit_minutes {
         parts.push(format!("{} min quota", limit));
@@ -1254,6 +1641,60 @@ fn copy_to_clipboard(value: &str) {
     }
 }
 
+fn device_table_row(device: &ConnectedDevice) -> Vec<String> {
+    let hostname = device
+        .hostname
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != device.ip)
+        .unwrap_or("-");
+    vec![
+        hostname.to_string(),
+        device.ip.clone(),
+        device.mac.clone(),
+        vendor_from_mac(&device.mac).unwrap_or_else(|| "-".to_string()),
+        device_kind_label(device_kind_for(device)).to_string(),
+    ]
+}
+
+fn format_text_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (idx, cell) in row.iter().enumerate() {
+            if let Some(width) = widths.get_mut(idx) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+    }
+
+    let format_line = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let mut lines = vec![format_line(&header), format_line(&separator)];
+    lines.extend(rows.iter().map(|row| format_line(row)));
+    lines.join("\n")
+}
+
+fn device_kind_label(kind: DeviceKind) -> &'static str {
+    match kind {
+        DeviceKind::Phone => "Phone",
+        DeviceKind::Computer => "Computer",
+        DeviceKind::Tv => "TV",
+        DeviceKind::Iot => "IoT",
+        DeviceKind::Unknown => "Unknown",
+    }
+}
+
 fn device_icon_name(device: &ConnectedDevice) -> &'static str {
     match device_kind_for(device) {
         DeviceKind::Phone => icon_name(
@@ -1419,79 +1860,6 @@ fn device_kind_from_vendor(vendor: &str) -> Option<DeviceKind> {
     None
 }
 
-fn vendor_from_mac(mac: &str) -> Option<String> {
-    let oui = normalize_mac_prefix(mac)?;
-    oui_map().get(&oui).cloned()
-}
-
-fn oui_map() -> &'static HashMap<String, String> {
-    static OUI_MAP: OnceLock<HashMap<String, String>> = OnceLock::new();
-    OUI_MAP.get_or_init(load_oui_map)
-}
-
-fn load_oui_map() -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    let paths = [
-        "/usr/share/hwdata/oui.txt",
-        "/usr/share/misc/oui.txt",
-        "/usr/share/ieee-data/oui.txt",
-        "/var/lib/ieee-data/oui.txt",
-    ];
-
-    for path in &paths {
-        if let Ok(content) = std::fs::read_to_string(path) {
-            parse_oui_content(&content, &mut map);
-            if !map.is_empty() {
-                break;
-            }
-        }
-    }
-
-    map
-}
-
-fn parse_oui_content(content: &str, map: &mut HashMap<String, String>) {
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        let (prefix, vendor) = if let Some((left, right)) = line.split_once("(hex)") {
-            (left, right)
-        } else if let Some((left, right)) = line.split_once("(base 16)") {
-            (left, right)
-        } else {
-            continue;
-        };
-
-        let oui = match normalize_oui(prefix) {
-            Some(oui) => oui,
-            std::prelude::v1::None => continue,
-        };
-        let vendor = vendor.trim();
-        if !vendor.is_empty() {
-            map.insert(oui, vendor.to_string());
-        }
-    }
-}
-
-fn normalize_oui(prefix: &str) -> Option<String> {
-    let hex: String = prefix.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-    if hex.len() < 6 {
-        return None;
-    }
-    Some(hex[..6].to_uppercase())
-}
-
-fn normalize_mac_prefix(mac: &str) -> Option<String> {
-    let hex: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-    if hex.len() < 6 {
-        return None;
-    }
-    Some(hex[..6].to_uppercase())
-}
-
 fn is_locally_administered(mac: &str) -> bool {
     let hex: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).collect();
     if hex.len() < 2 {
@@ -1522,3 +1890,36 @@ fn format_lease_remaining(expiry: i64) -> Option<String> {
     let hours_rem = hours % 24;
     Some(format!("Lease expires in {}d {}h", days, hours_rem))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_device_table_with_aligned_columns() {
+        let rows = vec![
+            vec!["laptop".to_string(), "10.42.0.12".to_string()],
+            vec!["-".to_string(), "10.42.0.7".to_string()],
+        ];
+
+        assert_eq!(
+            format_text_table(&["Hostname", "IP"], &rows),
+            "Hostname  IP\n--------  ----------\nlaptop    10.42.0.12\n-         10.42.0.7"
+        );
+    }
+
+    #[test]
+    fn summarizes_speed_limits_in_the_most_readable_unit() {
+        let rule = HotspotClientRule {
+            mac_address: "AA:BB:CC:DD:EE:FF".to_string(),
+            download_limit_kbps: Some(2500),
+            upload_limit_kbps: Some(768),
+            ..HotspotClientRule::default()
+        };
+        assert_eq!(
+            speed_limit_summary(&rule).as_deref(),
+            Some("Limited to ↓ 2.5 Mbit/s ↑ 768 kbit/s")
+        );
+        assert!(speed_limit_summary(&HotspotClientRule::default()).is_none());
+    }
+}
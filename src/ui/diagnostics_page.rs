@@ -0,0 +1,260 @@
+// * ./src/ui/diagnostics_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use tokio::sync::mpsc;
+
+use crate::diagnostics::{self, Tool};
+use crate::nm;
+use crate::ui::public_ip_card::PublicIpCard;
+use crate::ui::{common, icon_name};
+
+const PRESETS: [&str; 3] = ["Router", "Cloudflare (1.1.1.1)", "example.com"];
+
+// * Troubleshooting tools: a one-click path check plus live ping, traceroute and DNS lookups.
+#[derive(Clone)]
+pub struct DiagnosticsPage {
+    pub widget: adw::ToastOverlay,
+    check_row: adw::ActionRow,
+    check_button: gtk4::Button,
+    target_entry: adw::EntryRow,
+    tool_buttons: gtk4::Box,
+    stop_button: gtk4::Button,
+    output_view: gtk4::TextView,
+    output_end: gtk4::TextMark,
+    task: Rc<RefCell<Option<tokio::task::AbortHandle>>>,
+    // * Bumped on every run so output from a stopped run can't leak into the next one
+    run_id: Rc<Cell<u32>>,
+}
+
+impl DiagnosticsPage {
+    pub fn new() -> Self {
+        let widget = adw::ToastOverlay::new();
+        let page = adw::PreferencesPage::new();
+
+        let check_group = adw::PreferencesGroup::builder()
+            .title("Connection Check")
+            .description("Tests the router, the internet and DNS in turn to find where the connection breaks.")
+            .build();
+        let check_row = adw::ActionRow::builder()
+            .title("Not checked yet")
+            .title_lines(0)
+            .subtitle_lines(0)
+            .build();
+        check_row.add_prefix(&gtk4::Image::from_icon_name(icon_name(
+            "network-workgroup-symbolic",
+            &["network-wired-symbolic"][..],
+        )));
+        let check_button = gtk4::Button::builder()
+            .label("Check")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["suggested-action".to_string()])
+            .build();
+        check_row.add_suffix(&check_button);
+        check_group.add(&check_row);
+
+        let tools_group = adw::PreferencesGroup::builder().title("Tools").build();
+        let preset_row = adw::ComboRow::builder()
+            .title("Preset")
+            .model(&gtk4::StringList::new(&PRESETS[..]))
+            .build();
+        let target_entry = adw::EntryRow::builder()
+            .title("Host or Address")
+            .activates_default(true)
+            .build();
+        target_entry.set_text(PRESETS[2]);
+        preset_row.set_selected(2);
+        tools_group.add(&preset_row);
+        tools_group.add(&target_entry);
+
+        let tool_buttons = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        tool_buttons.set_margin_top(12);
+        tool_buttons.set_halign(gtk4::Align::Center);
+        let stop_button = gtk4::Button::builder()
+            .label("Stop")
+            .css_classes(vec!["destructive-action".to_string()])
+            .sensitive(false)
+            .build();
+
+        let output_view = gtk4::TextView::builder()
+            .editable(false)
+            .cursor_visible(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .top_margin(8)
+            .bottom_margin(8)
+            .left_margin(8)
+            .right_margin(8)
+            .build();
+        let buffer = output_view.buffer();
+        let output_end = buffer.create_mark(None, &buffer.end_iter(), false);
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .min_content_height(260)
+            .child(&output_view)
+            .build();
+        scrolled.add_css_class("card");
+        scrolled.set_margin_top(12);
+
+        let output_group = adw::PreferencesGroup::new();
+        output_group.add(&scrolled);
+
+        page.add(&check_group);
+        page.add(&PublicIpCard::new(&widget).widget);
+        page.add(&tools_group);
+        tools_group.add(&tool_buttons);
+        page.add(&output_group);
+        widget.set_child(Some(&page));
+
+        let diagnostics = Self {
+            widget,
+            check_row,
+            check_button: check_button.clone(),
+            target_entry: target_entry.clone(),
+            tool_buttons: tool_buttons.clone(),
+            stop_button: stop_button.clone(),
+            output_view,
+            output_end,
+            task: Rc::new(RefCell::new(None)),
+            run_id: Rc::new(Cell::new(0)),
+        };
+
+        for tool in [Tool::Ping, Tool::Traceroute, Tool::DnsLookup] {
+            let button = gtk4::Button::with_label(tool.label());
+            let page = diagnostics.clone();
+            button.connect_clicked(move |_| page.run(tool));
+            tool_buttons.append(&button);
+        }
+        tool_buttons.append(&stop_button);
+
+        let page_ref = diagnostics.clone();
+        stop_button.connect_clicked(move |_| page_ref.stop());
+
+        let page_ref = diagnostics.clone();
+        target_entry.connect_entry_activated(move |_| page_ref.run(Tool::Ping));
+
+        let page_ref = diagnostics.clone();
+        preset_row.connect_selected_notify(move |row| {
+            let page = page_ref.clone();
+            match row.selected() {
+                0 => {
+                    glib::spawn_future_local(async move {
+                        match nm::default_gateway().await {
+                            Ok(Some(gateway)) => page.target_entry.set_text(&gateway),
+                            Ok(None) => page.show_toast("Not connected to a router"),
+                            Err(e) => page.show_toast(&format!("Failed to find the router: {}", e)),
+                        }
+                    });
+                }
+                1 => page.target_entry.set_text("1.1.1.1"),
+                _ => page.target_entry.set_text(PRESETS[2]),
+            }
+        });
+
+        let page_ref = diagnostics.clone();
+        check_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.check_connection().await;
+            });
+        });
+
+        diagnostics
+    }
+
+    async fn check_connection(&self) {
+        self.check_button.set_sensitive(false);
+        self.check_row.set_title("Checking...");
+        self.check_row.set_subtitle("");
+        let check = diagnostics::check_path().await;
+        let hop = |ok: Option<bool>| match ok {
+            Some(true) => "OK",
+            Some(false) => "No answer",
+            None => "Skipped",
+        };
+        self.check_row.set_title(check.summary());
+        self.check_row.set_subtitle(&format!(
+            "Router: {} • Internet: {} • DNS: {}",
+            hop(check.gateway),
+            hop(Some(check.internet)),
+            hop(Some(check.dns))
+        ));
+        self.check_button.set_sensitive(true);
+    }
+
+    fn run(&self, tool: Tool) {
+        let target = self.target_entry.text().trim().to_string();
+        if target.is_empty() {
+            self.show_toast("Enter a host or address first");
+            return;
+        }
+        self.stop();
+        let run_id = self.run_id.get().wrapping_add(1);
+        self.run_id.set(run_id);
+        self.output_view
+            .buffer()
+            .set_text(&format!("$ {} {}\n", tool.label(), target));
+        self.set_running(true);
+
+        let (sender, mut lines) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(async move { diagnostics::stream(tool, &target, sender).await });
+        *self.task.borrow_mut() = Some(handle.abort_handle());
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            while let Some(line) = lines.recv().await {
+                if page.run_id.get() != run_id {
+                    return;
+                }
+                page.append_line(&line);
+            }
+            let result = handle.await;
+            if page.run_id.get() != run_id {
+                return;
+            }
+            match result {
+                Ok(Ok(true)) => page.append_line("\nFinished."),
+                Ok(Ok(false)) => page.append_line("\nFinished with errors."),
+                Ok(Err(e)) => page.append_line(&format!("\n{}", e)),
+                Err(_) => page.append_line("\nStopped."),
+            }
+            page.task.borrow_mut().take();
+            page.set_running(false);
+        });
+    }
+
+    pub fn stop(&self) {
+        if let Some(task) = self.task.borrow_mut().take() {
+            task.abort();
+        }
+    }
+
+    fn set_running(&self, running: bool) {
+        let mut child = self.tool_buttons.first_child();
+        while let Some(widget) = child {
+            widget.set_sensitive(
+                running == (widget == *self.stop_button.upcast_ref::<gtk4::Widget>()),
+            );
+            child = widget.next_sibling();
+        }
+    }
+
+    fn append_line(&self, line: &str) {
+        let buffer = self.output_view.buffer();
+        buffer.insert(&mut buffer.end_iter(), &format!("{}\n", line));
+        self.output_view.scroll_mark_onscreen(&self.output_end);
+    }
+
+    fn show_toast(&self, message: &str) {
+        common::show_toast(&self.widget, message);
+    }
+}
+
+impl Default for DiagnosticsPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
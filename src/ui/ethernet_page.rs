@@ -10,14 +10,17 @@ use libadwaita::{self as adw, prelude::*};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::ethtool::{self, LinkMode};
 use crate::nm::{self, Connection, DeviceType, NetworkManager};
-use crate::ui::{common, icon_name};
+use crate::traffic;
+use crate::ui::{common, confirm_dialog, connection_editor, icon_name};
 
 pub struct EthernetPage {
     pub widget: gtk4::Box,
     toast_overlay: adw::ToastOverlay,
     ethernet_switch: adw::SwitchRow,
     refresh_button: gtk4::Button,
+    new_vlan_button: gtk4::Button,
     spinner: gtk4::Spinner,
     operation_status_label: gtk4::Label,
     connected_card: gtk4::Box,
@@ -28,6 +31,9 @@ pub struct EthernetPage {
     connections: Rc<RefCell<Vec<Connection>>>,
     connected_connection: Rc<RefCell<Option<Connection>>>,
     ethernet_devices: Rc<RefCell<Vec<String>>>,
+    usb_tether_group: adw::PreferencesGroup,
+    usb_tether_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+    usb_tether_ifaces: Rc<RefCell<Vec<String>>>,
 }
 
 impl Clone for EthernetPage {
@@ -37,6 +43,7 @@ impl Clone for EthernetPage {
             toast_overlay: self.toast_overlay.clone(),
             ethernet_switch: self.ethernet_switch.clone(),
             refresh_button: self.refresh_button.clone(),
+            new_vlan_button: self.new_vlan_button.clone(),
             spinner: self.spinner.clone(),
             operation_status_label: self.operation_status_label.clone(),
             connected_card: self.connected_card.clone(),
@@ -47,6 +54,9 @@ impl Clone for EthernetPage {
             connections: self.connections.clone(),
             connected_connection: self.connected_connection.clone(),
             ethernet_devices: self.ethernet_devices.clone(),
+            usb_tether_group: self.usb_tether_group.clone(),
+            usb_tether_rows: self.usb_tether_rows.clone(),
+            usb_tether_ifaces: self.usb_tether_ifaces.clone(),
         }
     }
 }
@@ -111,8 +121,19 @@ impl EthernetPage {
             ])
             .build();
 
+        let new_vlan_button = gtk4::Button::builder()
+            .icon_name(icon_name("list-add-symbolic", &["list-add"][..]))
+            .tooltip_text("New VLAN…")
+            .css_classes(vec![
+                "flat".to_string(),
+                "circular".to_string(),
+                "touch-target".to_string(),
+            ])
+            .build();
+
         header_box.append(&title);
         header_box.append(&spinner);
+        header_box.append(&new_vlan_button);
         header_box.append(&refresh_button);
         content.append(&header_box);
         content.append(&operation_status_label);
@@ -158,6 +179,14 @@ impl EthernetPage {
         content.append(&list);
         content.append(&empty_state);
 
+        let usb_tether_group = adw::PreferencesGroup::builder()
+            .title("USB Tethering")
+            .description("A phone sharing its connection over a USB cable")
+            .margin_top(16)
+            .visible(false)
+            .build();
+        content.append(&usb_tether_group);
+
         clamp.set_child(Some(&content));
         scrolled.set_child(Some(&clamp));
         toast_overlay.set_child(Some(&scrolled));
@@ -172,6 +201,7 @@ impl EthernetPage {
             toast_overlay,
             ethernet_switch: ethernet_switch.clone(),
             refresh_button: refresh_button.clone(),
+            new_vlan_button: new_vlan_button.clone(),
             spinner: spinner.clone(),
             operation_status_label: operation_status_label.clone(),
             connected_card: connected_card.clone(),
@@ -182,6 +212,9 @@ impl EthernetPage {
             connections: connections.clone(),
             connected_connection: connected_connection.clone(),
             ethernet_devices: ethernet_devices.clone(),
+            usb_tether_group,
+            usb_tether_rows: Rc::new(RefCell::new(Vec::new())),
+            usb_tether_ifaces: Rc::new(RefCell::new(Vec::new())),
         };
 
         // Connected card context menu
@@ -234,7 +267,7 @@ impl EthernetPage {
                     Err(e) => {
                         log::error!("Failed to toggle ethernet: {}", e);
                         // * Use operation-specific ethernet toggle failure messaging.
-                        page.show_toast(&format!("Failed to change Ethernet state: {}", e));
+                        page.show_error("Failed to change Ethernet state", &e);
                     }
                 }
             });
@@ -249,10 +282,19 @@ impl EthernetPage {
             });
         });
 
+        // New VLAN handler
+        let page_ref = page.clone();
+        new_vlan_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.show_new_vlan_dialog().await;
+            });
+        });
+
         page
     }
 
-    async fn refresh_connections(&self) {
+    pub async fn refresh_connections(&self) {
         if !self.ethernet_switch.is_active() {
             self.set_operation_state(false, "");
             self.show_disabled_state();
@@ -261,18 +303,27 @@ impl EthernetPage {
 
         self.set_operation_state(true, "Refreshing...");
 
+        let tethers = nm::usb_tethers().await.unwrap_or_else(|e| {
+            log::warn!("Failed to detect USB tethering: {}", e);
+            Vec::new()
+        });
+        *self.usb_tether_ifaces.borrow_mut() = tethers.iter().map(|t| t.iface.clone()).collect();
+        self.populate_usb_tethers(&tethers);
+
         match NetworkManager::get_devices().await {
             Ok(devices) => {
+                let tether_ifaces = self.usb_tether_ifaces.borrow().clone();
                 let ethernet = devices
                     .into_iter()
                     .filter(|d| d.device_type == DeviceType::Ethernet)
+                    .filter(|d| !tether_ifaces.contains(&d.name))
                     .map(|d| d.name)
                     .collect::<Vec<_>>();
                 *self.ethernet_devices.borrow_mut() = ethernet;
             }
             Err(e) => {
                 log::warn!("Failed to get devices: {}", e);
-                if nm::is_nmcli_retrieval_error(&e.to_string()) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 }
                 self.ethernet_devices.borrow_mut().clear();
@@ -281,9 +332,18 @@ impl EthernetPage {
 
         match NetworkManager::get_connections().await {
             Ok(connections) => {
+                // * Tethering profiles are shown in their own group below
+                let tether_ifaces = self.usb_tether_ifaces.borrow().clone();
                 let mut wired: Vec<Connection> = connections
                     .into_iter()
-                    .filter(|conn| conn.is_ethernet())
+                    .filter(|conn| conn.is_ethernet() || conn.is_vlan())
+                    .filter(|conn| {
+                        !(conn.active
+                            && conn
+                                .device
+                                .as_ref()
+                                .is_some_and(|device| tether_ifaces.contains(device)))
+                    })
                     .collect();
                 wired.sort_by(|a, b| {
                     if a.active && !b.active {
@@ -299,10 +359,10 @@ impl EthernetPage {
             }
             Err(e) => {
                 log::error!("Failed to get connections: {}", e);
-                if nm::is_nmcli_retrieval_error(&e.to_string()) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 } else {
-                    self.show_toast(&format!("Failed to refresh Ethernet connections: {}", e));
+                    self.show_error("Failed to refresh Ethernet connections", &e);
                 }
                 self.populate_connections(Vec::new());
             }
@@ -323,6 +383,7 @@ impl EthernetPage {
 
     fn update_enabled_state(&self, enabled: bool) {
         self.refresh_button.set_sensitive(enabled);
+        self.new_vlan_button.set_sensitive(enabled);
         self.list.set_sensitive(enabled);
         if !enabled {
             self.show_disabled_state();
@@ -374,6 +435,103 @@ impl EthernetPage {
         }
     }
 
+    fn populate_usb_tethers(&self, tethers: &[nm::UsbTether]) {
+        for row in self.usb_tether_rows.borrow_mut().drain(..) {
+            self.usb_tether_group.remove(&row);
+        }
+        for tether in tethers {
+            let row = self.create_usb_tether_row(tether);
+            self.usb_tether_group.add(&row);
+            self.usb_tether_rows.borrow_mut().push(row);
+        }
+        self.usb_tether_group.set_visible(!tethers.is_empty());
+    }
+
+    fn create_usb_tether_row(&self, tether: &nm::UsbTether) -> adw::ActionRow {
+        let mut subtitle = format!(
+            "{} • {}",
+            if tether.connected {
+                "Connected"
+            } else {
+                "Not connected"
+            },
+            tether.iface
+        );
+        // * Counters start when the phone is plugged in, so this is usage for the cable session
+        if let Some((rx, tx)) = traffic::read_interface_bytes(&tether.iface) {
+            subtitle.push_str(&format!(
+                " • ↓ {} • ↑ {}",
+                common::format_bytes(rx),
+                common::format_bytes(tx)
+            ));
+        }
+        let row = adw::ActionRow::builder()
+            .title(if tether.driver == "ipheth" {
+                "iPhone"
+            } else {
+                "Phone"
+            })
+            .subtitle(subtitle)
+            .build();
+        let icon = gtk4::Image::from_icon_name(icon_name(
+            "phone-symbolic",
+            &["network-wired-symbolic", "network-wired"][..],
+        ));
+        icon.set_pixel_size(24);
+        row.add_prefix(&icon);
+
+        let action_button = gtk4::Button::builder()
+            .label(if tether.connected {
+                "Disconnect"
+            } else {
+                "Connect"
+            })
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        row.add_suffix(&action_button);
+        row.set_activatable_widget(Some(&action_button));
+
+        let page = self.clone();
+        let tether = tether.clone();
+        action_button.connect_clicked(move |_| {
+            let page = page.clone();
+            let tether = tether.clone();
+            glib::spawn_future_local(async move {
+                page.toggle_usb_tether(&tether).await;
+            });
+        });
+        row
+    }
+
+    async fn toggle_usb_tether(&self, tether: &nm::UsbTether) {
+        self.set_operation_state(
+            true,
+            if tether.connected {
+                "Disconnecting..."
+            } else {
+                "Connecting..."
+            },
+        );
+        let result = if tether.connected {
+            nm::disconnect_usb_tether(&tether.iface).await
+        } else {
+            nm::connect_usb_tether(&tether.iface).await
+        };
+        match result {
+            Ok(()) => self.show_toast(if tether.connected {
+                "USB tethering disconnected"
+            } else {
+                "Connected through USB tethering"
+            }),
+            Err(e) => {
+                log::error!("USB tethering on {} failed: {}", tether.iface, e);
+                self.show_error("USB tethering failed", &e);
+            }
+        }
+        self.refresh_connections().await;
+    }
+
     fn clear_connections(&self) {
         while let Some(child) = self.list.first_child() {
             self.list.remove(&child);
@@ -407,7 +565,11 @@ impl EthernetPage {
         } else {
             "Not connected".to_string()
         };
-        row.set_subtitle(&subtitle);
+        if connection.is_vlan() {
+            row.set_subtitle(&format!("VLAN • {}", subtitle));
+        } else {
+            row.set_subtitle(&subtitle);
+        }
 
         let icon = gtk4::Image::new();
         icon.set_icon_name(Some(icon_name(
@@ -552,6 +714,68 @@ impl EthernetPage {
 
         menu_box.append(&details_btn);
 
+        let rename_btn = gtk4::Button::builder()
+            .label("Rename…")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let page_rename = self.clone();
+        let conn_rename = connection.clone();
+        let popover_rename = popover.clone();
+        rename_btn.connect_clicked(move |_| {
+            let page = page_rename.clone();
+            let connection = conn_rename.clone();
+            popover_rename.popdown();
+
+            glib::spawn_future_local(async move {
+                page.rename_connection(&connection).await;
+            });
+        });
+
+        menu_box.append(&rename_btn);
+
+        let duplicate_btn = gtk4::Button::builder()
+            .label("Duplicate")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let page_duplicate = self.clone();
+        let conn_duplicate = connection.clone();
+        let popover_duplicate = popover.clone();
+        duplicate_btn.connect_clicked(move |_| {
+            let page = page_duplicate.clone();
+            let connection = conn_duplicate.clone();
+            popover_duplicate.popdown();
+
+            glib::spawn_future_local(async move {
+                page.duplicate_connection(&connection).await;
+            });
+        });
+
+        menu_box.append(&duplicate_btn);
+
+        if connection.is_vlan() {
+            let delete_btn = gtk4::Button::builder()
+                .label("Delete VLAN")
+                .css_classes(vec!["flat".to_string()])
+                .build();
+
+            let page_delete = self.clone();
+            let conn_delete = connection.clone();
+            let popover_delete = popover.clone();
+            delete_btn.connect_clicked(move |_| {
+                let page = page_delete.clone();
+                let connection = conn_delete.clone();
+                popover_delete.popdown();
+
+                glib::spawn_future_local(async move {
+                    page.delete_vlan(&connection).await;
+                });
+            });
+
+            menu_box.append(&delete_btn);
+        }
+
         let auto_row = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
         auto_row.set_margin_top(6);
         auto_row.set_margin_bottom(6);
@@ -588,7 +812,7 @@ impl EthernetPage {
                 glib::spawn_future_local(async move {
                     if let Err(e) = conn.set_autoconnect(enabled).await {
                         log::error!("Failed to set autoconnect: {}", e);
-                        page.show_toast(&format!("Failed to update auto-connect: {}", e));
+                        page.show_error("Failed to update auto-connect", &e);
                     }
                 });
             });
@@ -610,7 +834,7 @@ impl EthernetPage {
             }
             Err(e) => {
                 log::error!("Connection failed: {}", e);
-                self.show_toast(&format!("Failed to connect: {}", e));
+                self.show_error("Failed to connect", &e);
                 self.set_operation_state(false, "");
             }
         }
@@ -625,7 +849,7 @@ impl EthernetPage {
             }
             Err(e) => {
                 log::error!("Disconnect failed: {}", e);
-                self.show_toast(&format!("Failed to disconnect: {}", e));
+                self.show_error("Failed to disconnect", &e);
                 self.set_operation_state(false, "");
             }
         }
@@ -635,8 +859,221 @@ impl EthernetPage {
         common::show_toast(&self.toast_overlay, message);
     }
 
+    fn show_error(&self, prefix: &str, error: &anyhow::Error) {
+        common::show_error(&self.toast_overlay, prefix, error);
+    }
+
+    async fn show_new_vlan_dialog(&self) {
+        let parents = self.ethernet_devices.borrow().clone();
+        if parents.is_empty() {
+            self.show_toast("No wired interface to add a VLAN to");
+            return;
+        }
+
+        let parent_refs: Vec<&str> = parents.iter().map(String::as_str).collect();
+        let parent_row = adw::ComboRow::builder()
+            .title("Parent interface")
+            .model(&gtk4::StringList::new(&parent_refs))
+            .build();
+        let id_row = adw::SpinRow::builder()
+            .title("VLAN ID")
+            .subtitle("The tag configured on the switch port")
+            .adjustment(&gtk4::Adjustment::new(
+                *nm::VLAN_ID_RANGE.start() as f64,
+                *nm::VLAN_ID_RANGE.start() as f64,
+                *nm::VLAN_ID_RANGE.end() as f64,
+                1.0,
+                10.0,
+                0.0,
+            ))
+            .build();
+
+        let group = adw::PreferencesGroup::new();
+        group.add(&parent_row);
+        group.add(&id_row);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("New VLAN")
+            .body("Traffic on the parent interface tagged with this ID gets its own connection.")
+            .extra_child(&group)
+            .default_response("create")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("create", "Create")]);
+        dialog.set_response_appearance("create", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        if response.as_str() != "create" {
+            return;
+        }
+
+        let Some(parent) = parents.get(parent_row.selected() as usize) else {
+            return;
+        };
+        match nm::create_vlan(parent, id_row.value() as u32).await {
+            Ok(name) => {
+                self.show_toast(&format!("Created {}", name));
+                self.refresh_connections().await;
+            }
+            Err(e) => {
+                log::error!("Failed to create VLAN on {}: {}", parent, e);
+                self.show_error("Failed to create VLAN", &e);
+            }
+        }
+    }
+
+    async fn rename_connection(&self, connection: &Connection) {
+        let name_entry = adw::EntryRow::builder().title("Name").build();
+        name_entry.set_text(&connection.name);
+        let group = adw::PreferencesGroup::new();
+        group.add(&name_entry);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Rename Connection")
+            .extra_child(&group)
+            .default_response("rename")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("rename", "Rename")]);
+        dialog.set_response_appearance("rename", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        if response.as_str() != "rename" {
+            return;
+        }
+
+        let new_name = name_entry.text().trim().to_string();
+        match nm::rename_connection(&connection.name, &new_name).await {
+            Ok(()) => {
+                self.show_toast(&format!("Renamed to {}", new_name));
+                self.refresh_connections().await;
+            }
+            Err(e) => {
+                log::error!("Failed to rename {}: {}", connection.name, e);
+                self.show_error("Failed to rename connection", &e);
+            }
+        }
+    }
+
+    async fn duplicate_connection(&self, connection: &Connection) {
+        match nm::duplicate_connection(&connection.name).await {
+            Ok(name) => {
+                self.show_toast(&format!("Created {}", name));
+                self.refresh_connections().await;
+            }
+            Err(e) => {
+                log::error!("Failed to duplicate {}: {}", connection.name, e);
+                self.show_error("Failed to duplicate connection", &e);
+            }
+        }
+    }
+
+    async fn delete_vlan(&self, connection: &Connection) {
+        let confirmed = confirm_dialog(
+            &self.widget,
+            "Delete VLAN?",
+            &format!("The {} connection will be removed.", connection.name),
+            "Delete",
+            true,
+        )
+        .await;
+        if !confirmed {
+            return;
+        }
+
+        match nm::delete_connection(&connection.uuid).await {
+            Ok(()) => {
+                self.show_toast(&format!("Deleted {}", connection.name));
+                self.refresh_connections().await;
+            }
+            Err(e) => {
+                log::error!("Failed to delete VLAN {}: {}", connection.name, e);
+                self.show_error("Failed to delete VLAN", &e);
+            }
+        }
+    }
+
+    // * Live NIC settings from ethtool; forcing a mode is for switches that negotiate badly.
+    async fn build_link_group(&self, iface: &str) -> Option<adw::PreferencesGroup> {
+        let settings = match ethtool::read_link_settings(iface).await {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::debug!("Link settings unavailable for {}: {}", iface, e);
+                return None;
+            }
+        };
+
+        let group = adw::PreferencesGroup::builder()
+            .title("Link")
+            .description("Changes apply immediately and last until the cable is replugged")
+            .build();
+        let status_row = adw::ActionRow::builder()
+            .title("Current link")
+            .subtitle(settings.summary())
+            .build();
+
+        let modes: Vec<LinkMode> = settings.supported.clone();
+        let mut labels = vec!["Automatic".to_string()];
+        labels.extend(modes.iter().map(LinkMode::label));
+        let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let forced = match (settings.autoneg, settings.speed_mbps, settings.duplex) {
+            (false, Some(speed_mbps), Some(duplex)) => modes
+                .iter()
+                .position(|mode| *mode == LinkMode { speed_mbps, duplex })
+                .map(|index| index as u32 + 1),
+            _ => None,
+        };
+        let mode_row = adw::ComboRow::builder()
+            .title("Speed and duplex")
+            .model(&gtk4::StringList::new(&label_refs))
+            .selected(forced.unwrap_or(0))
+            .sensitive(!modes.is_empty())
+            .build();
+
+        let page = self.clone();
+        let iface_for_mode = iface.to_string();
+        let status_for_mode = status_row.clone();
+        mode_row.connect_selected_notify(move |row| {
+            let mode = (row.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| modes.get(index).copied());
+            let page = page.clone();
+            let iface = iface_for_mode.clone();
+            let status_row = status_for_mode.clone();
+
+            glib::spawn_future_local(async move {
+                let result = match mode {
+                    Some(mode) => ethtool::force_link_mode(&iface, mode).await,
+                    None => ethtool::enable_autonegotiation(&iface).await,
+                };
+                if let Err(e) = result {
+                    log::error!("Failed to change link settings on {}: {}", iface, e);
+                    page.show_error("Failed to change link settings", &e);
+                }
+                // * Renegotiation takes a moment; show what the NIC settled on
+                glib::timeout_future_seconds(3).await;
+                if let Ok(settings) = ethtool::read_link_settings(&iface).await {
+                    status_row.set_subtitle(&settings.summary());
+                }
+            });
+        });
+
+        group.add(&status_row);
+        group.add(&mode_row);
+        Some(group)
+    }
+
     async fn show_connection_details_dialog(&self, connection: &Connection) {
-        let info = nm::get_network_info(&connection.name).await.ok();
+        let device = connection.device.as_deref().filter(|_| connection.active);
+        let info = nm::get_network_info_on(&connection.name, device).await.ok();
 
         let dialog = adw::Dialog::builder()
             .title("Connection Details")
@@ -718,7 +1155,12 @@ impl EthernetPage {
         items.push((
             "network-wired-symbolic",
             "Type".to_string(),
-            "Ethernet".to_string(),
+            if connection.is_vlan() {
+                "VLAN"
+            } else {
+                "Ethernet"
+            }
+            .to_string(),
         ));
         if let Some(dev) = connection.device.as_ref() {
             items.push(("computer-symbolic", "Device".to_string(), dev.to_string()));
@@ -865,6 +1307,90 @@ impl EthernetPage {
 
         info_box.append(&details_card);
 
+        info_box.append(
+            &connection_editor::ipv4_settings_group(
+                &connection.name,
+                connection.active,
+                &self.toast_overlay,
+            )
+            .await,
+        );
+        info_box.append(
+            &connection_editor::routes_group(
+                &connection.name,
+                connection.active,
+                &self.toast_overlay,
+            )
+            .await,
+        );
+
+        // * VLANs ride on the parent NIC; link, 802.1X and hardware settings belong to it
+        if !connection.is_vlan() {
+            // * `connection.device` is the NM object path; ethtool needs the interface name
+            if let Some(iface) = info.as_ref().and_then(|i| i.interface.as_deref()) {
+                if let Some(link_group) = self.build_link_group(iface).await {
+                    info_box.append(&link_group);
+                }
+            }
+            info_box.append(
+                &connection_editor::wired_8021x_section(
+                    &connection.name,
+                    connection.active,
+                    &self.toast_overlay,
+                )
+                .await,
+            );
+        }
+
+        let advanced_group = adw::PreferencesGroup::new();
+        if !connection.is_vlan() {
+            connection_editor::add_mac_address_rows(
+                &advanced_group,
+                &connection.name,
+                connection.active,
+                &self.toast_overlay,
+            )
+            .await;
+            connection_editor::add_mtu_row(
+                &advanced_group,
+                &connection.name,
+                connection.active,
+                &self.toast_overlay,
+            )
+            .await;
+            connection_editor::add_wake_on_lan_row(
+                &advanced_group,
+                &connection.name,
+                connection.active,
+                &self.toast_overlay,
+            )
+            .await;
+        }
+        connection_editor::add_firewall_zone_row(
+            &advanced_group,
+            &connection.name,
+            connection.active,
+            &self.toast_overlay,
+        )
+        .await;
+        let advanced_row = connection_editor::advanced_settings_row();
+        let name_for_advanced = connection.name.clone();
+        let active_for_advanced = connection.active;
+        advanced_row.connect_activated(move |row| {
+            let row = row.clone();
+            let name = name_for_advanced.clone();
+            glib::spawn_future_local(async move {
+                connection_editor::show_advanced_editor(
+                    row.upcast_ref(),
+                    &name,
+                    active_for_advanced,
+                )
+                .await;
+            });
+        });
+        advanced_group.add(&advanced_row);
+        info_box.append(&advanced_group);
+
         scrolled.set_child(Some(&info_box));
         main_box.append(&scrolled);
         dialog.set_child(Some(&main_box));
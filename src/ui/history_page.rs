@@ -0,0 +1,178 @@
+// * ./src/ui/history_page.rs
+
+use chrono::{Duration as DateDuration, Local};
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::event_log::{EventCategory, EventKind, EventRecorder, NetworkEvent};
+use crate::ui::icon_name;
+
+// * Recorded connects, drops, roams and hotspot changes, newest first and grouped by day.
+#[derive(Clone)]
+pub struct HistoryPage {
+    pub widget: adw::PreferencesPage,
+    recorder: EventRecorder,
+    category: Rc<Cell<Option<EventCategory>>>,
+    search_entry: gtk4::SearchEntry,
+    day_groups: Rc<RefCell<Vec<adw::PreferencesGroup>>>,
+}
+
+impl HistoryPage {
+    pub fn new(recorder: &EventRecorder) -> Self {
+        let widget = adw::PreferencesPage::new();
+
+        let filter_group = adw::PreferencesGroup::builder()
+            .title("Network History")
+            .description("Events are recorded while the app is running and kept for 30 days.")
+            .build();
+        let category_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        category_box.add_css_class("linked");
+        category_box.set_valign(gtk4::Align::Center);
+        filter_group.set_header_suffix(Some(&category_box));
+
+        let search_entry = gtk4::SearchEntry::builder()
+            .placeholder_text("Filter by network")
+            .hexpand(true)
+            .build();
+        filter_group.add(&search_entry);
+        widget.add(&filter_group);
+
+        let page = Self {
+            widget,
+            recorder: recorder.clone(),
+            category: Rc::new(Cell::new(None)),
+            search_entry: search_entry.clone(),
+            day_groups: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        let mut first_button: Option<gtk4::ToggleButton> = None;
+        for (label, category) in [
+            ("All", None),
+            ("Wi-Fi", Some(EventCategory::Wifi)),
+            ("Wired", Some(EventCategory::Wired)),
+            ("Hotspot", Some(EventCategory::Hotspot)),
+        ] {
+            let button = gtk4::ToggleButton::with_label(label);
+            button.set_group(first_button.as_ref());
+            let page_ref = page.clone();
+            button.connect_toggled(move |button| {
+                if button.is_active() {
+                    page_ref.category.set(category);
+                    page_ref.refresh();
+                }
+            });
+            category_box.append(&button);
+            first_button.get_or_insert(button);
+        }
+        if let Some(button) = first_button {
+            button.set_active(true);
+        }
+
+        let page_ref = page.clone();
+        search_entry.connect_search_changed(move |_| page_ref.refresh());
+
+        page.refresh();
+        page
+    }
+
+    pub fn refresh(&self) {
+        for group in self.day_groups.borrow_mut().drain(..) {
+            self.widget.remove(&group);
+        }
+
+        let query = self.search_entry.text().trim().to_lowercase();
+        let category = self.category.get();
+        let history = self.recorder.history();
+        let events: Vec<&NetworkEvent> = history
+            .events
+            .iter()
+            .rev()
+            .filter(|event| category.is_none_or(|category| event.kind.category() == category))
+            .filter(|event| query.is_empty() || event.network.to_lowercase().contains(&query))
+            .collect();
+
+        if events.is_empty() {
+            let group = adw::PreferencesGroup::new();
+            let status = adw::StatusPage::builder()
+                .icon_name(icon_name(
+                    "document-open-recent-symbolic",
+                    &["preferences-system-time-symbolic"][..],
+                ))
+                .title("No Events")
+                .description(if history.events.is_empty() {
+                    "Connection changes will appear here"
+                } else {
+                    "Nothing matches the current filter"
+                })
+                .build();
+            status.add_css_class("compact");
+            group.add(&status);
+            self.widget.add(&group);
+            self.day_groups.borrow_mut().push(group);
+            return;
+        }
+
+        let today = Local::now().date_naive();
+        let mut current: Option<(String, adw::PreferencesGroup)> = None;
+        for event in events {
+            let Some(time) = event.local_time() else {
+                continue;
+            };
+            let date = time.date_naive();
+            let title = if date == today {
+                "Today".to_string()
+            } else if date == today - DateDuration::days(1) {
+                "Yesterday".to_string()
+            } else {
+                date.format("%A, %B %-d").to_string()
+            };
+            if current.as_ref().map(|(day, _)| day) != Some(&title) {
+                let group = adw::PreferencesGroup::builder().title(&title).build();
+                self.widget.add(&group);
+                self.day_groups.borrow_mut().push(group.clone());
+                current = Some((title, group));
+            }
+            if let Some((_, group)) = &current {
+                group.add(&event_row(event, &time.format("%H:%M:%S").to_string()));
+            }
+        }
+    }
+}
+
+fn event_row(event: &NetworkEvent, time: &str) -> adw::ActionRow {
+    let subtitle = match &event.detail {
+        Some(detail) => format!("{} • {}", time, detail),
+        None => time.to_string(),
+    };
+    let row = adw::ActionRow::builder()
+        .title(format!("{} — {}", event.kind.label(), event.network))
+        .subtitle(subtitle)
+        .build();
+
+    let icon = gtk4::Image::from_icon_name(match event.kind {
+        EventKind::WifiConnected | EventKind::Roamed => icon_name(
+            "network-wireless-signal-excellent-symbolic",
+            &["network-wireless-symbolic"][..],
+        ),
+        EventKind::WifiDisconnected => icon_name(
+            "network-wireless-offline-symbolic",
+            &["network-wireless-symbolic"][..],
+        ),
+        EventKind::WiredConnected => icon_name("network-wired-symbolic", &["network-wired"][..]),
+        EventKind::WiredDisconnected => icon_name(
+            "network-wired-disconnected-symbolic",
+            &["network-wired-symbolic"][..],
+        ),
+        EventKind::HotspotStarted | EventKind::HotspotStopped => icon_name(
+            "network-wireless-hotspot-symbolic",
+            &["network-wireless-symbolic"][..],
+        ),
+    });
+    if event.kind.is_drop() {
+        icon.add_css_class("error");
+    }
+    row.add_prefix(&icon);
+    row
+}
@@ -4,11 +4,44 @@ const BAND_24_GHZ_INDEX: u32 = 0;
 const BAND_5_GHZ_INDEX: u32 = 1;
 const BAND_AUTO_INDEX: u32 = 2;
 const BAND_CUSTOM_INDEX: u32 = 3;
+// * Only in the combo on Wi-Fi 6E adapters, where it sits after 5 GHz; the band helpers
+// * below take these indices, the combo position goes through band_index_from_position.
+const BAND_6_GHZ_INDEX: u32 = 4;
+
+pub(super) fn band_labels(six_ghz: bool) -> &'static [&'static str] {
+    if six_ghz {
+        &["2.4 GHz", "5 GHz", "6 GHz", "Auto", "Custom"]
+    } else {
+        &["2.4 GHz", "5 GHz", "Auto", "Custom"]
+    }
+}
+
+pub(super) fn band_index_from_position(position: u32, six_ghz: bool) -> u32 {
+    match position {
+        2 if six_ghz => BAND_6_GHZ_INDEX,
+        position if six_ghz && position > 2 => position - 1,
+        position => position,
+    }
+}
+
+pub(super) fn band_position_from_index(index: u32, six_ghz: bool) -> u32 {
+    match index {
+        BAND_6_GHZ_INDEX if six_ghz => 2,
+        BAND_6_GHZ_INDEX => BAND_AUTO_INDEX,
+        index if six_ghz && index >= BAND_AUTO_INDEX => index + 1,
+        index => index,
+    }
+}
+
+pub(super) fn is_6ghz_band_selected(selected: u32) -> bool {
+    selected == BAND_6_GHZ_INDEX
+}
 
 pub(super) fn band_from_selected(selected: u32, custom_band: &str) -> String {
     match selected {
         BAND_24_GHZ_INDEX => "2.4 GHz".to_string(),
         BAND_5_GHZ_INDEX => "5 GHz".to_string(),
+        BAND_6_GHZ_INDEX => "6 GHz".to_string(),
         BAND_AUTO_INDEX => "Auto".to_string(),
         _ => custom_band.trim().to_string(),
     }
@@ -22,6 +55,9 @@ pub(super) fn band_to_selection(band: &str) -> (u32, String) {
     if trimmed == "5 GHz" || trimmed == "5 GHz (Faster Speed)" {
         return (BAND_5_GHZ_INDEX, String::new());
     }
+    if trimmed == "6 GHz" {
+        return (BAND_6_GHZ_INDEX, String::new());
+    }
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("auto") {
         return (BAND_AUTO_INDEX, String::new());
     }
@@ -33,3 +69,151 @@ pub(super) fn band_to_selection(band: &str) -> (u32, String) {
 pub(super) fn is_custom_band_selected(selected: u32) -> bool {
     selected == BAND_CUSTOM_INDEX
 }
+
+const CHANNELS_24_GHZ: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+const CHANNELS_5_GHZ: &[u32] = &[
+    36, 40, 44, 48, 52, 56, 60, 64, 100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144,
+    149, 153, 157, 161, 165,
+];
+// * Preferred scanning channels: 6 GHz clients only look for access points on these
+const CHANNELS_6_GHZ: &[u32] = &[
+    5, 21, 37, 53, 69, 85, 101, 117, 133, 149, 165, 181, 197, 213, 229,
+];
+// * Auto-pick sticks to the non-overlapping 2.4 GHz channels and skips DFS on 5 GHz,
+// * where many drivers refuse to start an access point.
+const PREFERRED_24_GHZ: &[u32] = &[1, 6, 11];
+const PREFERRED_5_GHZ: &[u32] = &[36, 40, 44, 48, 149, 153, 157, 161, 165];
+
+// * Channels the picker offers for a predefined band; Auto and Custom have no fixed list.
+pub(super) fn channels_for_band(selected: u32) -> &'static [u32] {
+    match selected {
+        BAND_24_GHZ_INDEX => CHANNELS_24_GHZ,
+        BAND_5_GHZ_INDEX => CHANNELS_5_GHZ,
+        BAND_6_GHZ_INDEX => CHANNELS_6_GHZ,
+        _ => &[],
+    }
+}
+
+// * Scores each candidate by the signal of networks on or overlapping it; 2.4 GHz channels
+// * bleed into their four neighbours on each side.
+pub(super) fn least_congested_channel(selected: u32, seen: &[(u32, u8)]) -> Option<u32> {
+    let (candidates, overlap) = match selected {
+        BAND_24_GHZ_INDEX => (PREFERRED_24_GHZ, 4),
+        BAND_5_GHZ_INDEX => (PREFERRED_5_GHZ, 0),
+        BAND_6_GHZ_INDEX => (CHANNELS_6_GHZ, 0),
+        _ => return None,
+    };
+    candidates.iter().copied().min_by_key(|candidate| {
+        seen.iter()
+            .filter_map(|(channel, signal)| {
+                let distance = channel.abs_diff(*candidate);
+                (distance <= overlap).then(|| u32::from(*signal) * (overlap + 1 - distance))
+            })
+            .sum::<u32>()
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SwitchToggleAction {
+    Start,
+    Stop,
+    // * Programmatic sync or no-op toggle: nothing to do.
+    Ignore,
+    // * A start/stop is already running; snap the switch back to the real state afterwards.
+    Resync,
+}
+
+pub(super) fn switch_toggle_action(
+    requested: bool,
+    is_active: bool,
+    operation_in_progress: bool,
+) -> SwitchToggleAction {
+    if operation_in_progress {
+        return SwitchToggleAction::Resync;
+    }
+    match (requested, is_active) {
+        (true, false) => SwitchToggleAction::Start,
+        (false, true) => SwitchToggleAction::Stop,
+        _ => SwitchToggleAction::Ignore,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_toggle_maps_to_exactly_one_start_or_stop() {
+        assert_eq!(
+            switch_toggle_action(true, false, false),
+            SwitchToggleAction::Start
+        );
+        assert_eq!(
+            switch_toggle_action(false, true, false),
+            SwitchToggleAction::Stop
+        );
+    }
+
+    #[test]
+    fn ignores_programmatic_syncs_to_the_current_state() {
+        assert_eq!(
+            switch_toggle_action(true, true, false),
+            SwitchToggleAction::Ignore
+        );
+        assert_eq!(
+            switch_toggle_action(false, false, false),
+            SwitchToggleAction::Ignore
+        );
+    }
+
+    #[test]
+    fn picks_the_quietest_channel_for_the_band() {
+        assert_eq!(channels_for_band(BAND_24_GHZ_INDEX).len(), 13);
+        assert!(channels_for_band(BAND_AUTO_INDEX).is_empty());
+
+        // * Channel 1 only overlaps the weak network on 2
+        let seen = [(6, 80), (2, 20), (11, 60)];
+        assert_eq!(least_congested_channel(BAND_24_GHZ_INDEX, &seen), Some(1));
+        assert_eq!(
+            least_congested_channel(BAND_5_GHZ_INDEX, &[(36, 70), (149, 40)]),
+            Some(40)
+        );
+        assert_eq!(least_congested_channel(BAND_AUTO_INDEX, &seen), None);
+    }
+
+    #[test]
+    fn maps_band_positions_around_the_6ghz_entry() {
+        let (index, _) = band_to_selection("6 GHz");
+        assert_eq!(band_position_from_index(index, true), 2);
+        assert_eq!(band_index_from_position(2, true), index);
+        for index in [
+            BAND_24_GHZ_INDEX,
+            BAND_5_GHZ_INDEX,
+            BAND_AUTO_INDEX,
+            BAND_CUSTOM_INDEX,
+        ] {
+            for six_ghz in [false, true] {
+                let position = band_position_from_index(index, six_ghz);
+                assert_eq!(band_index_from_position(position, six_ghz), index);
+                assert_eq!(
+                    band_labels(six_ghz)[position as usize],
+                    band_labels(false)[index as usize]
+                );
+            }
+        }
+        // * Never points past the shorter list
+        assert_eq!(band_position_from_index(index, false), BAND_AUTO_INDEX);
+    }
+
+    #[test]
+    fn toggles_during_an_operation_do_not_start_another() {
+        assert_eq!(
+            switch_toggle_action(true, false, true),
+            SwitchToggleAction::Resync
+        );
+        assert_eq!(
+            switch_toggle_action(false, false, true),
+            SwitchToggleAction::Resync
+        );
+    }
+}
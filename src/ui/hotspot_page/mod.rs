@@ -5,10 +5,13 @@ use gtk4::prelude::*;
 use libadwaita::{self as adw, prelude::*};
 
 use crate::config::{
-    self, HotspotClientRule, HotspotConfig, HotspotMacFilterMode, HotspotPasswordStorage,
+    self, HotspotClientRule, HotspotConfig, HotspotDhcpReservation, HotspotMacFilterMode,
+    HotspotMode, HotspotPasswordStorage, HotspotScheduleRule, HotspotSecurity,
 };
 use crate::hotspot;
 use crate::nm;
+use crate::notifications::{self, NotificationKind};
+use crate::polling::PollScheduler;
 use crate::qr_dialog;
 use crate::secrets;
 use crate::state::{AppState, PageKind};
@@ -20,11 +23,23 @@ use std::rc::Rc;
 
 mod actions;
 mod password;
-use actions::{band_from_selected, band_to_selection, is_custom_band_selected};
-use password::update_strength_indicator;
+mod reservations;
+mod schedule;
+mod sessions;
+use actions::{
+    band_from_selected, band_index_from_position, band_labels, band_position_from_index,
+    band_to_selection, channels_for_band, is_6ghz_band_selected, is_custom_band_selected,
+    least_congested_channel, switch_toggle_action, SwitchToggleAction,
+};
+use password::{
+    rotation_due, rotation_hours_from_selection, rotation_labels, selection_from_rotation_hours,
+    update_strength_indicator,
+};
 
 const MIN_PASSWORD_LEN: usize = 8;
 const MAX_PASSWORD_LEN: usize = 63;
+const AUTOMATIC_UPSTREAM: &str = "Automatic (default route)";
+const SECURITY_SUBTITLE: &str = "WPA3 is more secure; older devices may only join WPA2";
 
 const QR_CODE_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" height="24px" viewBox="0 -960 960 960" width="24px" fill="#000000">
   <path d="M120-520v-320h320v320H120Zm80-80h160v-160H200v160Zm-80 480v-320h320v320H120Zm80-80h160v-160H200v160Zm320-320v-320h320v320H520Zm80-80h160v-160H600v160Zm160 480v-80h80v80h-80ZM520-360v-80h80v80h-80Zm80 80v-80h80v80h-80Zm-80 80v-80h80v80h-80Zm80 80v-80h80v80h-80Zm80-80v-80h80v80h-80Zm0-160v-80h80v80h-80Zm80 80v-80h80v80h-80Z"/>
@@ -38,29 +53,48 @@ pub struct HotspotPage {
     hotspot_switch: adw::SwitchRow,
     ssid_entry: adw::EntryRow,
     password_entry: adw::PasswordEntryRow,
+    security_combo: adw::ComboRow,
     band_combo: adw::ComboRow,
     // * Keep a handle to the custom band input row for free-text hotspot band values.
     custom_band_entry: adw::EntryRow,
     // * Keep a handle to the custom channel input row for free-text hotspot channels.
     channel_entry: adw::EntryRow,
+    // * Channel picker for the predefined bands; Auto band leaves the channel to NM.
+    channel_combo: adw::ComboRow,
     hidden_switch: adw::SwitchRow,
+    isolate_switch: adw::SwitchRow,
+    idle_timeout_spin: gtk4::SpinButton,
+    schedule_row: adw::ActionRow,
+    start_on_launch_switch: adw::SwitchRow,
     interface_combo: adw::ComboRow,
+    upstream_combo: adw::ComboRow,
     config_group: adw::PreferencesGroup,
+    addressing_group: adw::PreferencesGroup,
+    mode_combo: adw::ComboRow,
+    repeater_ssid_entry: adw::EntryRow,
+    ipv4_address_entry: adw::EntryRow,
+    dhcp_start_entry: adw::EntryRow,
+    dhcp_end_entry: adw::EntryRow,
+    reservations_row: adw::ActionRow,
     advanced_group: adw::PreferencesGroup,
     upload_limit_spin: gtk4::SpinButton,
     download_limit_spin: gtk4::SpinButton,
     device_limit_spin: gtk4::SpinButton,
-    mac_filter_combo: adw::ComboRow,
     client_rules_row: adw::ActionRow,
     client_rules_button: gtk4::Button,
     advanced_support_row: adw::ActionRow,
     qr_button: gtk4::Button,
     guest_password_row: adw::ActionRow,
+    rotation_combo: adw::ComboRow,
     guest_password_label: gtk4::Label,
     status_label: gtk4::Label,
     operation_spinner: gtk4::Spinner,
     status_subtitle: gtk4::Label,
     status_meta: gtk4::Label,
+    session_label: gtk4::Label,
+    repeater_label: gtk4::Label,
+    history_group: adw::PreferencesGroup,
+    session_rows: Rc<RefCell<sessions::SessionRows>>,
     status_icon: gtk4::Image,
     reveal_switch: adw::SwitchRow,
     revealed_password_row: adw::ActionRow,
@@ -68,6 +102,10 @@ pub struct HotspotPage {
     strength_label: gtk4::Label,
     strength_bar: gtk4::ProgressBar,
     devices: Rc<RefCell<Vec<String>>>,
+    // * Interface names behind upstream_combo entries after "Automatic"
+    upstream_choices: Rc<RefCell<Vec<String>>>,
+    // * Whether band_combo currently lists 6 GHz
+    band_6ghz_shown: Rc<Cell<bool>>,
     is_active: Rc<Cell<bool>>,
     wifi_present: Rc<Cell<bool>>,
     wifi_enabled: Rc<Cell<bool>>,
@@ -75,10 +113,10 @@ pub struct HotspotPage {
     operation_in_progress: Rc<Cell<bool>>,
     config_dirty: Rc<Cell<bool>>,
     client_rules: Rc<RefCell<Vec<HotspotClientRule>>>,
+    dhcp_reservations: Rc<RefCell<Vec<HotspotDhcpReservation>>>,
+    schedule: Rc<RefCell<Vec<HotspotScheduleRule>>>,
     temporary_password: Rc<RefCell<Option<String>>>,
     config_update_source: Rc<RefCell<Option<glib::SourceId>>>,
-    status_refresh_source: Rc<RefCell<Option<glib::SourceId>>>,
-    interface_refresh_source: Rc<RefCell<Option<glib::SourceId>>>,
     suppress_config_updates: Rc<Cell<u32>>,
 }
 
@@ -91,27 +129,45 @@ impl Clone for HotspotPage {
             hotspot_switch: self.hotspot_switch.clone(),
             ssid_entry: self.ssid_entry.clone(),
             password_entry: self.password_entry.clone(),
+            security_combo: self.security_combo.clone(),
             band_combo: self.band_combo.clone(),
             custom_band_entry: self.custom_band_entry.clone(),
             channel_entry: self.channel_entry.clone(),
+            channel_combo: self.channel_combo.clone(),
             hidden_switch: self.hidden_switch.clone(),
+            isolate_switch: self.isolate_switch.clone(),
+            idle_timeout_spin: self.idle_timeout_spin.clone(),
+            schedule_row: self.schedule_row.clone(),
+            start_on_launch_switch: self.start_on_launch_switch.clone(),
             interface_combo: self.interface_combo.clone(),
+            upstream_combo: self.upstream_combo.clone(),
             config_group: self.config_group.clone(),
+            addressing_group: self.addressing_group.clone(),
+            mode_combo: self.mode_combo.clone(),
+            repeater_ssid_entry: self.repeater_ssid_entry.clone(),
+            ipv4_address_entry: self.ipv4_address_entry.clone(),
+            dhcp_start_entry: self.dhcp_start_entry.clone(),
+            dhcp_end_entry: self.dhcp_end_entry.clone(),
+            reservations_row: self.reservations_row.clone(),
             advanced_group: self.advanced_group.clone(),
             upload_limit_spin: self.upload_limit_spin.clone(),
             download_limit_spin: self.download_limit_spin.clone(),
             device_limit_spin: self.device_limit_spin.clone(),
-            mac_filter_combo: self.mac_filter_combo.clone(),
             client_rules_row: self.client_rules_row.clone(),
             client_rules_button: self.client_rules_button.clone(),
             advanced_support_row: self.advanced_support_row.clone(),
             qr_button: self.qr_button.clone(),
             guest_password_row: self.guest_password_row.clone(),
+            rotation_combo: self.rotation_combo.clone(),
             guest_password_label: self.guest_password_label.clone(),
             status_label: self.status_label.clone(),
             operation_spinner: self.operation_spinner.clone(),
             status_subtitle: self.status_subtitle.clone(),
             status_meta: self.status_meta.clone(),
+            session_label: self.session_label.clone(),
+            repeater_label: self.repeater_label.clone(),
+            history_group: self.history_group.clone(),
+            session_rows: self.session_rows.clone(),
             status_icon: self.status_icon.clone(),
             reveal_switch: self.reveal_switch.clone(),
             revealed_password_row: self.revealed_password_row.clone(),
@@ -119,6 +175,8 @@ impl Clone for HotspotPage {
             strength_label: self.strength_label.clone(),
             strength_bar: self.strength_bar.clone(),
             devices: self.devices.clone(),
+            upstream_choices: self.upstream_choices.clone(),
+            band_6ghz_shown: self.band_6ghz_shown.clone(),
             is_active: self.is_active.clone(),
             wifi_present: self.wifi_present.clone(),
             wifi_enabled: self.wifi_enabled.clone(),
@@ -126,10 +184,10 @@ impl Clone for HotspotPage {
             operation_in_progress: self.operation_in_progress.clone(),
             config_dirty: self.config_dirty.clone(),
             client_rules: self.client_rules.clone(),
+            dhcp_reservations: self.dhcp_reservations.clone(),
+            schedule: self.schedule.clone(),
             temporary_password: self.temporary_password.clone(),
             config_update_source: self.config_update_source.clone(),
-            status_refresh_source: self.status_refresh_source.clone(),
-            interface_refresh_source: self.interface_refresh_source.clone(),
             suppress_config_updates: self.suppress_config_updates.clone(),
         }
     }
@@ -190,11 +248,23 @@ impl HotspotPage {
         status_meta.set_wrap(true);
         status_meta.set_visible(false);
 
+        let session_label = gtk4::Label::new(None);
+        session_label.set_opacity(0.6);
+        session_label.set_wrap(true);
+        session_label.set_visible(false);
+
+        let repeater_label = gtk4::Label::new(None);
+        repeater_label.set_opacity(0.6);
+        repeater_label.set_wrap(true);
+        repeater_label.set_visible(false);
+
         status_box.append(&status_icon);
         status_box.append(&status_label);
         status_box.append(&operation_spinner);
         status_box.append(&status_subtitle);
         status_box.append(&status_meta);
+        status_box.append(&session_label);
+        status_box.append(&repeater_label);
         content.append(&status_box);
 
         // Action buttons (placed near status for quick access)
@@ -387,9 +457,16 @@ impl HotspotPage {
         strength_box.append(&strength_label);
         strength_row.add_suffix(&strength_box);
 
+        let security_model = gtk4::StringList::new(&["WPA2", "WPA3", "WPA2/WPA3"][..]);
+        let security_combo = adw::ComboRow::builder()
+            .title("Security")
+            .subtitle(SECURITY_SUBTITLE)
+            .model(&security_model)
+            .build();
+
         // Advanced settings
         // * Keep predefined hotspot bands while still supporting a custom path.
-        let band_model = gtk4::StringList::new(&["2.4 GHz", "5 GHz", "Auto", "Custom"][..]);
+        let band_model = gtk4::StringList::new(band_labels(false));
         let band_combo = adw::ComboRow::builder()
             .title("Frequency Band")
             .model(&band_model)
@@ -404,11 +481,70 @@ impl HotspotPage {
         channel_entry.set_text("Auto");
         channel_entry.set_visible(false);
 
+        let channel_combo = adw::ComboRow::builder()
+            .title("Channel")
+            .model(&gtk4::StringList::new(&["Auto"][..]))
+            .build();
+        channel_combo.set_visible(false);
+        let quietest_channel_button = gtk4::Button::builder()
+            .label("Least Busy")
+            .valign(gtk4::Align::Center)
+            .tooltip_text("Pick the least congested channel from the last scan")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        channel_combo.add_suffix(&quietest_channel_button);
+
         let hidden_switch = adw::SwitchRow::builder()
             .title("Hidden Network")
             .subtitle("Network won't be visible in WiFi lists")
             .build();
 
+        let isolate_switch = adw::SwitchRow::builder()
+            .title("Isolate Clients")
+            .subtitle("Devices can reach the internet but not each other")
+            .build();
+        if let Some(reason) = nm::capabilities().unsupported_reason(nm::NmFeature::ApIsolation) {
+            isolate_switch.set_subtitle(&format!(
+                "{}; only traffic routed through this computer is blocked",
+                reason
+            ));
+        }
+
+        let rotation_combo = adw::ComboRow::builder()
+            .title("Change Password Automatically")
+            .subtitle("For shared hotspots; devices need the new password to rejoin")
+            .model(&gtk4::StringList::new(&rotation_labels()))
+            .build();
+
+        let idle_timeout_row = adw::ActionRow::builder()
+            .title("Turn Off When Idle")
+            .subtitle("Minutes with no connected devices, 0 to keep it on")
+            .build();
+        let idle_timeout_spin = gtk4::SpinButton::builder()
+            .adjustment(&gtk4::Adjustment::new(0.0, 0.0, 1440.0, 5.0, 30.0, 0.0))
+            .numeric(true)
+            .digits(0)
+            .valign(gtk4::Align::Center)
+            .build();
+        idle_timeout_row.add_suffix(&idle_timeout_spin);
+
+        let schedule_button = gtk4::Button::builder()
+            .label("Edit")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let schedule_row = adw::ActionRow::builder()
+            .title("Schedule")
+            .subtitle(schedule::schedule_summary(&[]))
+            .build();
+        schedule_row.add_suffix(&schedule_button);
+        schedule_row.set_activatable_widget(Some(&schedule_button));
+
+        let start_on_launch_switch = adw::SwitchRow::builder()
+            .title("Start With the App")
+            .subtitle("Turn the hotspot back on whenever Adwaita Network starts")
+            .build();
+
         let interface_model = gtk4::StringList::new(&[][..]);
         let interface_combo = adw::ComboRow::builder()
             .title("Network Interface")
@@ -416,6 +552,13 @@ impl HotspotPage {
             .selected(0)
             .build();
 
+        let upstream_combo = adw::ComboRow::builder()
+            .title("Internet Source")
+            .subtitle("Connection whose internet the hotspot shares")
+            .model(&gtk4::StringList::new(&[AUTOMATIC_UPSTREAM]))
+            .selected(0)
+            .build();
+
         config_group.add(&ssid_entry);
         config_group.add(&password_entry);
         config_group.add(&length_row);
@@ -423,14 +566,72 @@ impl HotspotPage {
         config_group.add(&reveal_switch);
         config_group.add(&revealed_password_row);
         config_group.add(&guest_password_row);
+        config_group.add(&rotation_combo);
+        config_group.add(&security_combo);
         config_group.add(&band_combo);
         config_group.add(&custom_band_entry);
         config_group.add(&channel_entry);
+        config_group.add(&channel_combo);
         config_group.add(&hidden_switch);
+        config_group.add(&isolate_switch);
+        config_group.add(&idle_timeout_row);
+        config_group.add(&schedule_row);
+        config_group.add(&start_on_launch_switch);
         config_group.add(&interface_combo);
+        config_group.add(&upstream_combo);
 
         content.append(&config_group);
 
+        // * Moves the hotspot off 10.42.x when that collides with the upstream network
+        let addressing_group = adw::PreferencesGroup::builder()
+            .title("Addressing")
+            .description("Leave empty to let NetworkManager choose the subnet")
+            .margin_top(12)
+            .build();
+        let mode_combo = adw::ComboRow::builder()
+            .title("Network Mode")
+            .model(&gtk4::StringList::new(&[
+                "Shared (NAT)",
+                "Bridged to LAN",
+                "Wi-Fi Repeater",
+            ]))
+            .build();
+        let repeater_ssid_entry = adw::EntryRow::builder()
+            .title("Network to Extend")
+            .visible(false)
+            .build();
+        let ipv4_address_entry = adw::EntryRow::builder()
+            .title("Hotspot Address (e.g. 192.168.12.1/24)")
+            .build();
+        let dhcp_start_entry = adw::EntryRow::builder().title("DHCP Range Start").build();
+        let dhcp_end_entry = adw::EntryRow::builder().title("DHCP Range End").build();
+        if let Some(reason) = nm::capabilities().unsupported_reason(nm::NmFeature::SharedDhcpRange)
+        {
+            dhcp_start_entry.set_sensitive(false);
+            dhcp_start_entry.set_tooltip_text(Some(&reason));
+            dhcp_end_entry.set_sensitive(false);
+            dhcp_end_entry.set_tooltip_text(Some(&reason));
+        }
+        addressing_group.add(&mode_combo);
+        addressing_group.add(&repeater_ssid_entry);
+        addressing_group.add(&ipv4_address_entry);
+        addressing_group.add(&dhcp_start_entry);
+        addressing_group.add(&dhcp_end_entry);
+
+        let reservations_button = gtk4::Button::builder()
+            .label("Edit")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let reservations_row = adw::ActionRow::builder()
+            .title("Reserved Addresses")
+            .subtitle(reservations::reservations_summary(&[]))
+            .build();
+        reservations_row.add_suffix(&reservations_button);
+        reservations_row.set_activatable_widget(Some(&reservations_button));
+        addressing_group.add(&reservations_row);
+        content.append(&addressing_group);
+
         let advanced_group = adw::PreferencesGroup::builder()
             .title("Advanced Hotspot Controls")
             .margin_top(12)
@@ -438,27 +639,25 @@ impl HotspotPage {
 
         let download_limit_row = adw::ActionRow::builder()
             .title("Download limit")
-            .subtitle("kbit/s, optional")
+            .subtitle("Mbit/s shared by all devices, optional")
             .build();
-        let download_limit_adjustment =
-            gtk4::Adjustment::new(0.0, 0.0, 1_000_000.0, 100.0, 1000.0, 0.0);
+        let download_limit_adjustment = gtk4::Adjustment::new(0.0, 0.0, 1000.0, 0.5, 10.0, 0.0);
         let download_limit_spin = gtk4::SpinButton::builder()
             .adjustment(&download_limit_adjustment)
             .numeric(true)
-            .digits(0)
+            .digits(1)
             .build();
         download_limit_row.add_suffix(&download_limit_spin);
 
         let upload_limit_row = adw::ActionRow::builder()
             .title("Upload limit")
-            .subtitle("kbit/s, optional")
+            .subtitle("Mbit/s shared by all devices, optional")
             .build();
-        let upload_limit_adjustment =
-            gtk4::Adjustment::new(0.0, 0.0, 1_000_000.0, 100.0, 1000.0, 0.0);
+        let upload_limit_adjustment = gtk4::Adjustment::new(0.0, 0.0, 1000.0, 0.5, 10.0, 0.0);
         let upload_limit_spin = gtk4::SpinButton::builder()
             .adjustment(&upload_limit_adjustment)
             .numeric(true)
-            .digits(0)
+            .digits(1)
             .build();
         upload_limit_row.add_suffix(&upload_limit_spin);
 
@@ -474,13 +673,6 @@ impl HotspotPage {
             .build();
         device_limit_row.add_suffix(&device_limit_spin);
 
-        let mac_filter_model = gtk4::StringList::new(&["Disabled", "Allowlist", "Blocklist"]);
-        let mac_filter_combo = adw::ComboRow::builder()
-            .title("MAC filtering")
-            .subtitle("Allow or block devices by MAC address")
-            .model(&mac_filter_model)
-            .build();
-
         let client_rules_button = gtk4::Button::builder()
             .label("Edit rules")
             .css_classes(vec!["flat".to_string()])
@@ -500,11 +692,17 @@ impl HotspotPage {
         advanced_group.add(&download_limit_row);
         advanced_group.add(&upload_limit_row);
         advanced_group.add(&device_limit_row);
-        advanced_group.add(&mac_filter_combo);
         advanced_group.add(&client_rules_row);
         advanced_group.add(&advanced_support_row);
         content.append(&advanced_group);
 
+        let history_group = adw::PreferencesGroup::builder()
+            .title("Recent Sessions")
+            .margin_top(12)
+            .visible(false)
+            .build();
+        content.append(&history_group);
+
         clamp.set_child(Some(&content));
         scrolled.set_child(Some(&clamp));
         toast_overlay.set_child(Some(&scrolled));
@@ -519,8 +717,6 @@ impl HotspotPage {
         let client_rules = Rc::new(RefCell::new(Vec::new()));
         let temporary_password = Rc::new(RefCell::new(hotspot::load_temporary_password()));
         let config_update_source = Rc::new(RefCell::new(None));
-        let status_refresh_source = Rc::new(RefCell::new(None));
-        let interface_refresh_source = Rc::new(RefCell::new(None));
         let suppress_config_updates = Rc::new(Cell::new(0));
         let password_adjusting = Rc::new(Cell::new(false));
         let reveal_timeout_generation = Rc::new(Cell::new(0u64));
@@ -533,27 +729,45 @@ impl HotspotPage {
             hotspot_switch: hotspot_switch.clone(),
             ssid_entry: ssid_entry.clone(),
             password_entry: password_entry.clone(),
+            security_combo: security_combo.clone(),
             band_combo: band_combo.clone(),
             custom_band_entry: custom_band_entry.clone(),
             channel_entry: channel_entry.clone(),
+            channel_combo: channel_combo.clone(),
             hidden_switch: hidden_switch.clone(),
+            isolate_switch: isolate_switch.clone(),
+            idle_timeout_spin: idle_timeout_spin.clone(),
+            schedule_row: schedule_row.clone(),
+            start_on_launch_switch: start_on_launch_switch.clone(),
             interface_combo: interface_combo.clone(),
+            upstream_combo: upstream_combo.clone(),
             config_group: config_group.clone(),
+            addressing_group: addressing_group.clone(),
+            mode_combo: mode_combo.clone(),
+            repeater_ssid_entry: repeater_ssid_entry.clone(),
+            ipv4_address_entry: ipv4_address_entry.clone(),
+            dhcp_start_entry: dhcp_start_entry.clone(),
+            dhcp_end_entry: dhcp_end_entry.clone(),
+            reservations_row: reservations_row.clone(),
             advanced_group: advanced_group.clone(),
             upload_limit_spin: upload_limit_spin.clone(),
             download_limit_spin: download_limit_spin.clone(),
             device_limit_spin: device_limit_spin.clone(),
-            mac_filter_combo: mac_filter_combo.clone(),
             client_rules_row: client_rules_row.clone(),
             client_rules_button: client_rules_button.clone(),
             advanced_support_row: advanced_support_row.clone(),
             qr_button: qr_button.clone(),
             guest_password_row: guest_password_row.clone(),
+            rotation_combo: rotation_combo.clone(),
             guest_password_label: guest_password_label.clone(),
             status_label: status_label.clone(),
             operation_spinner: operation_spinner.clone(),
             status_subtitle: status_subtitle.clone(),
             status_meta: status_meta.clone(),
+            session_label: session_label.clone(),
+            repeater_label: repeater_label.clone(),
+            history_group: history_group.clone(),
+            session_rows: Rc::new(RefCell::new(sessions::SessionRows::default())),
             status_icon: status_icon.clone(),
             reveal_switch: reveal_switch.clone(),
             revealed_password_row: revealed_password_row.clone(),
@@ -561,6 +775,8 @@ impl HotspotPage {
             strength_label: strength_label.clone(),
             strength_bar: strength_bar.clone(),
             devices,
+            upstream_choices: Rc::new(RefCell::new(Vec::new())),
+            band_6ghz_shown: Rc::new(Cell::new(false)),
             is_active,
             wifi_present,
             wifi_enabled,
@@ -568,10 +784,10 @@ impl HotspotPage {
             operation_in_progress,
             config_dirty,
             client_rules,
+            dhcp_reservations: Rc::new(RefCell::new(Vec::new())),
+            schedule: Rc::new(RefCell::new(Vec::new())),
             temporary_password,
             config_update_source,
-            status_refresh_source,
-            interface_refresh_source,
             suppress_config_updates,
         };
 
@@ -580,6 +796,7 @@ impl HotspotPage {
         page.set_config_dirty(false);
         // * Keep the custom band and channel rows aligned with the current selection on load.
         page.update_custom_band_channel_visibility();
+        page.update_mode_rows();
 
         // Load configuration and check status
         let page_ref = page.clone();
@@ -813,9 +1030,23 @@ impl HotspotPage {
         band_combo.connect_selected_notify(move |_| {
             // * Toggle the free-text band and channel rows when Custom is selected.
             page_ref.update_custom_band_channel_visibility();
+            page_ref.update_channel_options(&page_ref.selected_channel());
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        channel_combo.connect_selected_notify(move |_| {
             page_ref.schedule_configuration_update();
         });
 
+        let page_ref = page.clone();
+        quietest_channel_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.pick_least_congested_channel().await;
+            });
+        });
+
         let page_ref = page.clone();
         custom_band_entry.connect_changed(move |_| {
             page_ref.schedule_configuration_update();
@@ -831,6 +1062,38 @@ impl HotspotPage {
             page_ref.schedule_configuration_update();
         });
 
+        let page_ref = page.clone();
+        isolate_switch.connect_active_notify(move |_| {
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        idle_timeout_spin.connect_value_changed(move |_| {
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        start_on_launch_switch.connect_active_notify(move |_| {
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        security_combo.connect_selected_notify(move |_| {
+            page_ref.schedule_configuration_update();
+        });
+
+        for entry in [
+            &repeater_ssid_entry,
+            &ipv4_address_entry,
+            &dhcp_start_entry,
+            &dhcp_end_entry,
+        ] {
+            let page_ref = page.clone();
+            entry.connect_changed(move |_| {
+                page_ref.schedule_configuration_update();
+            });
+        }
+
         let page_ref = page.clone();
         download_limit_spin.connect_value_changed(move |_| {
             page_ref.schedule_configuration_update();
@@ -842,8 +1105,19 @@ impl HotspotPage {
         });
 
         let page_ref = page.clone();
-        mac_filter_combo.connect_selected_notify(move |_| {
-            page_ref.schedule_configuration_update();
+        schedule_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.edit_schedule().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        reservations_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.edit_dhcp_reservations().await;
+            });
         });
 
         let page_ref = page.clone();
@@ -857,6 +1131,31 @@ impl HotspotPage {
         let page_ref = page.clone();
         interface_combo.connect_selected_notify(move |_| {
             page_ref.schedule_configuration_update();
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.refresh_security_support().await;
+                page.refresh_band_support().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        rotation_combo.connect_selected_notify(move |_| {
+            // * Loading saved settings isn't a change; picking another interval or Never is
+            if page_ref.suppress_config_updates.get() == 0 {
+                hotspot::reset_password_rotation();
+            }
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        mode_combo.connect_selected_notify(move |_| {
+            page_ref.update_mode_rows();
+            page_ref.schedule_configuration_update();
+        });
+
+        let page_ref = page.clone();
+        upstream_combo.connect_selected_notify(move |_| {
+            page_ref.schedule_configuration_update();
         });
 
         let page_ref = page.clone();
@@ -867,24 +1166,30 @@ impl HotspotPage {
             });
         });
 
-        // Hotspot switch handler - kept for compatibility
+        // * Single switch handler: one user toggle runs at most one start or stop.
         let page_ref = page.clone();
         hotspot_switch.connect_active_notify(move |switch| {
             let page = page_ref.clone();
-            let active = switch.is_active();
-
-            // Prevent loops
-            if active == page.is_active.get() {
-                return;
-            }
-
-            glib::spawn_future_local(async move {
-                if active {
-                    page.start_hotspot().await;
-                } else {
-                    page.stop_hotspot().await;
+            match switch_toggle_action(
+                switch.is_active(),
+                page.is_active.get(),
+                page.operation_in_progress.get(),
+            ) {
+                SwitchToggleAction::Start => {
+                    glib::spawn_future_local(async move {
+                        page.start_hotspot().await;
+                    });
                 }
-            });
+                SwitchToggleAction::Stop => {
+                    glib::spawn_future_local(async move {
+                        page.stop_hotspot().await;
+                    });
+                }
+                SwitchToggleAction::Resync => {
+                    log::debug!("Hotspot toggle ignored while an operation is in progress");
+                }
+                SwitchToggleAction::Ignore => {}
+            }
         });
 
         // QR button handler
@@ -903,56 +1208,107 @@ impl HotspotPage {
     pub fn set_page_visible(&self, visible: bool) {
         self.app_state.set_page_visible(PageKind::Hotspot, visible);
         if visible {
-            self.start_auto_refresh();
             let page = self.clone();
             glib::spawn_future_local(async move {
                 page.load_interfaces().await;
                 page.refresh_status().await;
             });
-        } else {
-            self.stop_auto_refresh();
         }
     }
 
-    fn start_auto_refresh(&self) {
-        if self.status_refresh_source.borrow().is_none() {
-            let page_ref = self.clone();
-            let source = glib::timeout_add_seconds_local(5, move || {
+    // * Used by the tray; goes through the switch so validation and errors match a manual toggle.
+    pub fn set_hotspot_active(&self, active: bool) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            page.load_interfaces().await;
+            page.hotspot_switch.set_active(active);
+        });
+    }
+
+    pub fn attach_poll_scheduler(&self, scheduler: &PollScheduler) {
+        let page_ref = self.clone();
+        scheduler.subscribe(
+            "hotspot",
+            std::time::Duration::from_secs(3),
+            std::time::Duration::from_secs(15),
+            move || {
                 if !page_ref.app_state.is_page_visible(PageKind::Hotspot) {
-                    return glib::ControlFlow::Continue;
+                    return;
                 }
                 if page_ref.is_active.get() {
                     page_ref.refresh_status_details();
                 }
-                glib::ControlFlow::Continue
-            });
-            *self.status_refresh_source.borrow_mut() = Some(source);
-        }
-
-        if self.interface_refresh_source.borrow().is_none() {
-            let page_ref = self.clone();
-            let source = glib::timeout_add_seconds_local(3, move || {
-                if !page_ref.app_state.is_page_visible(PageKind::Hotspot) {
-                    return glib::ControlFlow::Continue;
-                }
                 let page = page_ref.clone();
                 glib::spawn_future_local(async move {
                     page.load_interfaces().await;
                     page.refresh_status().await;
                 });
-                glib::ControlFlow::Continue
-            });
-            *self.interface_refresh_source.borrow_mut() = Some(source);
-        }
+            },
+        );
+
+        // * Runs whether or not the page is shown; the password has to change on time
+        let page_ref = self.clone();
+        scheduler.subscribe(
+            "hotspot-password-rotation",
+            std::time::Duration::from_secs(60),
+            std::time::Duration::from_secs(60),
+            move || {
+                let page = page_ref.clone();
+                glib::spawn_future_local(async move {
+                    page.rotate_password_if_due().await;
+                });
+            },
+        );
     }
 
-    fn stop_auto_refresh(&self) {
-        if let Some(source) = self.status_refresh_source.borrow_mut().take() {
-            source.remove();
+    // * Swaps in a generated password once the interval passes, saves it through the chosen
+    // * storage and restarts a running hotspot. Waits while the user has unsaved edits.
+    async fn rotate_password_if_due(&self) {
+        let Some(hours) = rotation_hours_from_selection(self.rotation_combo.selected()) else {
+            return;
+        };
+        if self.operation_in_progress.get() || self.config_dirty.get() {
+            return;
+        }
+        let now = chrono::Utc::now().timestamp();
+        let Some(rotated_at) = hotspot::password_rotated_at() else {
+            hotspot::record_password_rotation(now);
+            return;
+        };
+        if !rotation_due(rotated_at, now, hours) {
+            return;
+        }
+
+        let len = self
+            .password_entry
+            .text()
+            .chars()
+            .count()
+            .max(MIN_PASSWORD_LEN);
+        // * No symbols: guests type this one in by hand
+        let password = password::generate_password(len, false);
+        self.with_suppressed_config_updates(|| {
+            self.password_entry.set_text(&password);
+            self.revealed_password_label.set_text(&password);
+            update_strength_indicator(&password, &self.strength_label, &self.strength_bar);
+        });
+        let config = self.build_hotspot_config(password);
+        let storage = self.load_password_storage();
+        if !self.persist_configuration(&config, &storage, true) {
+            return;
         }
-        if let Some(source) = self.interface_refresh_source.borrow_mut().take() {
-            source.remove();
+        hotspot::record_password_rotation(now);
+        log::info!("Rotated the hotspot password");
+        if self.is_active.get() {
+            self.restart_hotspot_with_config(&config).await;
         }
+        self.set_config_dirty(false);
+        notifications::send(
+            NotificationKind::HotspotPasswordRotated,
+            "Hotspot Password Changed",
+            "Share the new password or QR code with your guests",
+        );
+        self.show_toast("Hotspot password changed");
     }
 
     fn set_operation_state(&self, active: bool, status: &str) {
@@ -982,11 +1338,176 @@ impl HotspotPage {
 
     fn update_custom_band_channel_visibility(&self) {
         // * Expose editable band and channel controls only when Custom band is chosen.
-        let custom_selected = is_custom_band_selected(self.band_combo.selected());
+        let custom_selected = is_custom_band_selected(self.selected_band());
         self.custom_band_entry.set_visible(custom_selected);
         self.channel_entry.set_visible(custom_selected);
     }
 
+    // * Bridged clients are addressed by the LAN's router, so the subnet rows don't apply.
+    // * A repeater's internet source is the network it extends.
+    fn update_mode_rows(&self) {
+        let mode = mode_from_selection(self.mode_combo.selected());
+        let bridged = mode == HotspotMode::Bridged;
+        self.mode_combo.set_subtitle(match mode {
+            HotspotMode::Nat => "Clients get their own subnet behind this computer",
+            HotspotMode::Bridged => "Clients join the wired LAN picked as Internet Source",
+            HotspotMode::Repeater => {
+                "Joins a Wi-Fi network and shares it; one adapter stays on that network's channel"
+            }
+        });
+        self.repeater_ssid_entry
+            .set_visible(mode == HotspotMode::Repeater);
+        self.upstream_combo
+            .set_visible(mode != HotspotMode::Repeater);
+        self.ipv4_address_entry.set_visible(!bridged);
+        self.dhcp_start_entry.set_visible(!bridged);
+        self.dhcp_end_entry.set_visible(!bridged);
+        self.reservations_row.set_visible(!bridged);
+    }
+
+    // * Rebuilds the channel list for the selected band, keeping `preferred` when it's on it.
+    fn update_channel_options(&self, preferred: &str) {
+        let channels = channels_for_band(self.selected_band());
+        let mut labels = vec!["Auto".to_string()];
+        labels.extend(channels.iter().map(u32::to_string));
+        let model = gtk4::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        self.with_suppressed_config_updates(|| {
+            self.channel_combo.set_model(Some(&model));
+            self.channel_combo.set_selected(0);
+        });
+        self.channel_combo.set_visible(!channels.is_empty());
+        if let Ok(channel) = preferred.trim().parse::<u32>() {
+            self.with_suppressed_config_updates(|| {
+                self.select_channel(channel);
+            });
+        }
+    }
+
+    fn selected_band(&self) -> u32 {
+        band_index_from_position(self.band_combo.selected(), self.band_6ghz_shown.get())
+    }
+
+    fn set_selected_band(&self, band: u32) {
+        if is_6ghz_band_selected(band) && !self.band_6ghz_shown.get() {
+            self.set_band_model(true);
+        }
+        self.band_combo
+            .set_selected(band_position_from_index(band, self.band_6ghz_shown.get()));
+    }
+
+    fn set_band_model(&self, six_ghz: bool) {
+        self.band_6ghz_shown.set(six_ghz);
+        self.band_combo
+            .set_model(Some(&gtk4::StringList::new(band_labels(six_ghz))));
+    }
+
+    // * 6 GHz is listed only when NM and the adapter can both host it. A saved 6 GHz band
+    // * stays listed with a warning rather than quietly turning into another band.
+    async fn refresh_band_support(&self) {
+        if self.devices.borrow().is_empty() {
+            return;
+        }
+        let iface = self.current_interface_name();
+        let supported = nm::capabilities().supports(nm::NmFeature::Band6Ghz)
+            && hotspot::supports_6ghz(&iface).await;
+        let band = self.selected_band();
+        let six_ghz_selected = is_6ghz_band_selected(band);
+        let show = supported || six_ghz_selected;
+        if show != self.band_6ghz_shown.get() {
+            let channel = self.selected_channel();
+            self.with_suppressed_config_updates(|| {
+                self.set_band_model(show);
+                self.set_selected_band(band);
+            });
+            self.update_channel_options(&channel);
+        }
+        if six_ghz_selected && !supported {
+            self.band_combo
+                .set_subtitle(&format!("{} can't host a 6 GHz hotspot", iface));
+        } else {
+            self.band_combo.set_subtitle("");
+        }
+    }
+
+    fn select_channel(&self, channel: u32) -> bool {
+        let channels = channels_for_band(self.selected_band());
+        match channels.iter().position(|candidate| *candidate == channel) {
+            Some(index) => {
+                self.channel_combo.set_selected(index as u32 + 1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn selected_upstream(&self) -> Option<String> {
+        (self.upstream_combo.selected() as usize)
+            .checked_sub(1)
+            .and_then(|index| self.upstream_choices.borrow().get(index).cloned())
+    }
+
+    async fn load_upstream_choices(&self, selected: Option<String>) {
+        let hotspot_iface = self
+            .devices
+            .borrow()
+            .get(self.interface_combo.selected() as usize)
+            .cloned()
+            .unwrap_or_default();
+        let upstreams = hotspot::list_upstream_interfaces(&hotspot_iface).await;
+
+        let mut labels = vec![AUTOMATIC_UPSTREAM.to_string()];
+        let mut choices = Vec::new();
+        for upstream in &upstreams {
+            labels.push(format!("{} ({})", upstream.name, upstream.kind.label()));
+            choices.push(upstream.name.clone());
+        }
+        // * Keep a saved source selectable while it is disconnected
+        if let Some(saved) = selected.as_ref().filter(|saved| !choices.contains(saved)) {
+            labels.push(format!("{} (not connected)", saved));
+            choices.push(saved.clone());
+        }
+        let selected_idx = selected
+            .and_then(|saved| choices.iter().position(|name| *name == saved))
+            .map_or(0, |index| index + 1);
+
+        *self.upstream_choices.borrow_mut() = choices;
+        let model = gtk4::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        self.with_suppressed_config_updates(|| {
+            self.upstream_combo.set_model(Some(&model));
+            self.upstream_combo.set_selected(selected_idx as u32);
+        });
+    }
+
+    fn selected_channel(&self) -> String {
+        let channels = channels_for_band(self.selected_band());
+        (self.channel_combo.selected() as usize)
+            .checked_sub(1)
+            .and_then(|index| channels.get(index))
+            .map(u32::to_string)
+            .unwrap_or_else(|| "Auto".to_string())
+    }
+
+    async fn pick_least_congested_channel(&self) {
+        // * 6 GHz reuses 2.4 GHz channel numbers, so only compare networks on the same band
+        let band = band_from_selected(self.selected_band(), "");
+        let seen = match nm::cached_networks().await {
+            Ok(networks) => networks
+                .iter()
+                .filter(|network| network.band == band)
+                .map(|network| (network.channel, network.signal))
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                self.show_toast(&format!("No scan results to compare channels: {}", e));
+                return;
+            }
+        };
+        if let Some(channel) = least_congested_channel(self.selected_band(), &seen) {
+            if self.select_channel(channel) {
+                self.show_toast(&format!("Channel {} is the least busy nearby", channel));
+            }
+        }
+    }
+
     fn current_temporary_password(&self) -> Option<String> {
         self.temporary_password
             .borrow()
@@ -1141,10 +1662,10 @@ impl HotspotPage {
         let stop_result = hotspot::stop_hotspot().await;
         if let Err(e) = stop_result {
             log::error!("Failed to stop hotspot during update: {}", e);
-            if nm::is_nmcli_retrieval_error(&e.to_string()) {
+            if nm::NmError::is_unavailable(&e) {
                 self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
             } else {
-                self.show_toast(&format!("Failed to update hotspot: {}", e));
+                self.show_error("Failed to update hotspot", &e);
             }
             self.operation_in_progress.set(false);
             self.set_operation_state(false, "");
@@ -1154,6 +1675,7 @@ impl HotspotPage {
 
         match hotspot::create_hotspot_on(config, &interface).await {
             Ok(_) => {
+                hotspot::backup_password_to_keyring(&config.password).await;
                 self.is_active.set(true);
                 self.hotspot_switch.set_active(true);
                 self.set_config_dirty(false);
@@ -1162,15 +1684,12 @@ impl HotspotPage {
             Err(e) => {
                 log::error!("Failed to restart hotspot: {}", e);
                 let error_text = e.to_string();
-                if nm::is_nmcli_retrieval_error(&error_text) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 } else if hotspot::is_hotspot_mode_not_supported_error(&error_text) {
                     self.show_toast(hotspot::HOTSPOT_UNSUPPORTED_TOAST);
                 } else {
-                    self.show_toast(&format!(
-                        "Failed to restart hotspot on {}: {}",
-                        interface, error_text
-                    ));
+                    self.show_error(&format!("Failed to restart hotspot on {}", interface), &e);
                 }
                 self.is_active.set(false);
                 self.hotspot_switch.set_active(false);
@@ -1184,8 +1703,8 @@ impl HotspotPage {
 
     fn build_hotspot_config(&self, password: String) -> HotspotConfig {
         let selected_band =
-            band_from_selected(self.band_combo.selected(), &self.custom_band_entry.text());
-        let channel = if is_custom_band_selected(self.band_combo.selected()) {
+            band_from_selected(self.selected_band(), &self.custom_band_entry.text());
+        let channel = if is_custom_band_selected(self.selected_band()) {
             let trimmed = self.channel_entry.text().trim().to_string();
             if trimmed.is_empty() {
                 "Auto".to_string()
@@ -1193,8 +1712,9 @@ impl HotspotPage {
                 trimmed
             }
         } else {
-            "Auto".to_string()
+            self.selected_channel()
         };
+        let (mac_filter_mode, mac_filter_list) = saved_mac_filter();
 
         HotspotConfig {
             ssid: self.ssid_entry.text().to_string(),
@@ -1202,17 +1722,36 @@ impl HotspotPage {
             band: selected_band,
             channel,
             hidden: self.hidden_switch.is_active(),
-            upload_limit_kbps: spin_value_to_option(&self.upload_limit_spin),
-            download_limit_kbps: spin_value_to_option(&self.download_limit_spin),
+            isolate_clients: self.isolate_switch.is_active(),
+            security: security_from_selection(self.security_combo.selected()),
+            ipv4_address: entry_text_to_option(&self.ipv4_address_entry),
+            dhcp_range_start: entry_text_to_option(&self.dhcp_start_entry),
+            dhcp_range_end: entry_text_to_option(&self.dhcp_end_entry),
+            dhcp_reservations: self.dhcp_reservations.borrow().clone(),
+            upload_limit_kbps: mbit_spin_to_kbps(&self.upload_limit_spin),
+            download_limit_kbps: mbit_spin_to_kbps(&self.download_limit_spin),
             max_connected_devices: spin_value_to_option(&self.device_limit_spin),
-            mac_filter_mode: mac_filter_mode_from_selection(self.mac_filter_combo.selected()),
+            idle_timeout_minutes: spin_value_to_option(&self.idle_timeout_spin),
+            password_rotation_hours: rotation_hours_from_selection(self.rotation_combo.selected()),
+            schedule: self.schedule.borrow().clone(),
+            interface: self
+                .devices
+                .borrow()
+                .get(self.interface_combo.selected() as usize)
+                .cloned(),
+            start_on_launch: self.start_on_launch_switch.is_active(),
+            upstream_interface: self.selected_upstream(),
+            mode: mode_from_selection(self.mode_combo.selected()),
+            repeater_ssid: entry_text_to_option(&self.repeater_ssid_entry),
+            mac_filter_mode,
+            mac_filter_list,
             client_rules: self.client_rules.borrow().clone(),
         }
     }
 
     fn validate_channel_on_apply(&self, config: &HotspotConfig) -> Result<(), String> {
         // * Reject obviously invalid custom channels before applying hotspot settings.
-        if !is_custom_band_selected(self.band_combo.selected()) {
+        if !is_custom_band_selected(self.selected_band()) {
             return Ok(());
         }
 
@@ -1253,24 +1792,50 @@ impl HotspotPage {
                     update_strength_indicator(&password, &self.strength_label, &self.strength_bar);
                     // * Restore custom band text when the stored value is outside the predefined list.
                     let (band_index, custom_band) = band_to_selection(&config.band);
-                    self.band_combo.set_selected(band_index);
+                    self.set_selected_band(band_index);
                     self.custom_band_entry.set_text(&custom_band);
                     self.channel_entry.set_text(&config.channel);
                     self.update_custom_band_channel_visibility();
+                    self.update_channel_options(&config.channel);
                     self.hidden_switch.set_active(config.hidden);
+                    self.isolate_switch.set_active(config.isolate_clients);
+                    self.security_combo
+                        .set_selected(selection_from_security(config.security));
+                    self.mode_combo
+                        .set_selected(selection_from_mode(config.mode));
+                    self.ipv4_address_entry
+                        .set_text(config.ipv4_address.as_deref().unwrap_or_default());
+                    self.dhcp_start_entry
+                        .set_text(config.dhcp_range_start.as_deref().unwrap_or_default());
+                    self.dhcp_end_entry
+                        .set_text(config.dhcp_range_end.as_deref().unwrap_or_default());
+                    self.repeater_ssid_entry
+                        .set_text(config.repeater_ssid.as_deref().unwrap_or_default());
                     self.download_limit_spin
-                        .set_value(config.download_limit_kbps.unwrap_or_default() as f64);
+                        .set_value(kbps_to_mbit(config.download_limit_kbps));
                     self.upload_limit_spin
-                        .set_value(config.upload_limit_kbps.unwrap_or_default() as f64);
+                        .set_value(kbps_to_mbit(config.upload_limit_kbps));
                     self.device_limit_spin
                         .set_value(config.max_connected_devices.unwrap_or_default() as f64);
-                    self.mac_filter_combo
-                        .set_selected(selection_from_mac_filter_mode(&config.mac_filter_mode));
+                    self.idle_timeout_spin
+                        .set_value(config.idle_timeout_minutes.unwrap_or_default() as f64);
+                    self.rotation_combo
+                        .set_selected(selection_from_rotation_hours(
+                            config.password_rotation_hours,
+                        ));
+                    self.start_on_launch_switch
+                        .set_active(config.start_on_launch);
                 });
                 *self.temporary_password.borrow_mut() = hotspot::load_temporary_password();
                 self.update_guest_password_ui();
                 *self.client_rules.borrow_mut() = config.client_rules.clone();
                 self.update_client_rules_summary();
+                *self.dhcp_reservations.borrow_mut() = config.dhcp_reservations.clone();
+                self.update_reservations_summary();
+                *self.schedule.borrow_mut() = config.schedule.clone();
+                self.update_schedule_summary();
+                self.load_upstream_choices(config.upstream_interface.clone())
+                    .await;
                 self.set_config_dirty(false);
             }
             Err(_) => {
@@ -1281,24 +1846,47 @@ impl HotspotPage {
                     self.password_entry.set_text(&password);
                     self.revealed_password_label.set_text(&password);
                     update_strength_indicator(&password, &self.strength_label, &self.strength_bar);
-                    self.band_combo.set_selected(2);
+                    self.set_selected_band(band_to_selection("Auto").0);
                     self.custom_band_entry.set_text("");
                     self.channel_entry.set_text(&config.channel);
                     self.update_custom_band_channel_visibility();
+                    self.update_channel_options(&config.channel);
                     self.hidden_switch.set_active(false);
+                    self.isolate_switch.set_active(false);
+                    self.security_combo
+                        .set_selected(selection_from_security(config.security));
+                    self.mode_combo
+                        .set_selected(selection_from_mode(config.mode));
+                    self.ipv4_address_entry
+                        .set_text(config.ipv4_address.as_deref().unwrap_or_default());
+                    self.dhcp_start_entry
+                        .set_text(config.dhcp_range_start.as_deref().unwrap_or_default());
+                    self.dhcp_end_entry
+                        .set_text(config.dhcp_range_end.as_deref().unwrap_or_default());
+                    self.repeater_ssid_entry
+                        .set_text(config.repeater_ssid.as_deref().unwrap_or_default());
                     self.download_limit_spin.set_value(0.0);
                     self.upload_limit_spin.set_value(0.0);
                     self.device_limit_spin.set_value(0.0);
-                    self.mac_filter_combo.set_selected(0);
+                    self.idle_timeout_spin.set_value(0.0);
+                    self.rotation_combo.set_selected(0);
+                    self.start_on_launch_switch.set_active(false);
                 });
                 *self.temporary_password.borrow_mut() = hotspot::load_temporary_password();
                 self.update_guest_password_ui();
                 self.client_rules.borrow_mut().clear();
                 self.update_client_rules_summary();
+                self.dhcp_reservations.borrow_mut().clear();
+                self.update_reservations_summary();
+                self.schedule.borrow_mut().clear();
+                self.update_schedule_summary();
+                self.load_upstream_choices(None).await;
                 self.set_config_dirty(false);
             }
         }
         self.refresh_advanced_support().await;
+        self.refresh_security_support().await;
+        self.refresh_band_support().await;
     }
 
     async fn start_hotspot(&self) {
@@ -1379,11 +1967,29 @@ impl HotspotPage {
         }
 
         let interface = self.current_interface_name();
+        let proceed = match hotspot::concurrency_guidance(&config, &interface).await {
+            Some(hotspot::ConcurrencyGuidance::DropsWifi(message)) => {
+                self.confirm_wifi_disconnect(&message).await
+            }
+            Some(hotspot::ConcurrencyGuidance::SharesChannel(message)) => {
+                self.show_toast(&message);
+                true
+            }
+            None => true,
+        };
+        if !proceed {
+            self.hotspot_switch.set_active(false);
+            self.operation_in_progress.set(false);
+            self.set_operation_state(false, "");
+            self.update_ui();
+            return;
+        }
         hotspot::store_temporary_password(temporary_password.as_deref());
 
         match hotspot::create_hotspot_on(&config, &interface).await {
             Ok(_) => {
                 let _ = self.persist_configuration(&config, &storage, true);
+                hotspot::backup_password_to_keyring(&config.password).await;
                 self.is_active.set(true);
                 self.set_config_dirty(false);
                 self.show_toast("Hotspot started successfully");
@@ -1391,17 +1997,14 @@ impl HotspotPage {
             Err(e) => {
                 log::error!("Failed to start hotspot: {}", e);
                 let error_text = e.to_string();
-                if nm::is_nmcli_retrieval_error(&error_text) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 } else if hotspot::is_hotspot_mode_not_supported_error(&error_text) {
                     // * Show a specific unsupported-adapter message for hotspot mode.
                     self.show_toast(hotspot::HOTSPOT_UNSUPPORTED_TOAST);
                 } else {
                     // * Keep hotspot start failures contextual instead of generic.
-                    self.show_toast(&format!(
-                        "Failed to start hotspot on {}: {}",
-                        interface, error_text
-                    ));
+                    self.show_error(&format!("Failed to start hotspot on {}", interface), &e);
                 }
                 self.is_active.set(false);
                 self.hotspot_switch.set_active(false);
@@ -1410,6 +2013,7 @@ impl HotspotPage {
         }
 
         self.operation_in_progress.set(false);
+        self.hotspot_switch.set_active(self.is_active.get());
         self.set_operation_state(false, "");
         self.update_ui();
     }
@@ -1429,30 +2033,36 @@ impl HotspotPage {
             }
             Err(e) => {
                 log::error!("Failed to stop hotspot: {}", e);
-                if nm::is_nmcli_retrieval_error(&e.to_string()) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 } else {
-                    self.show_toast(&format!("Failed to stop hotspot: {}", e));
+                    self.show_error("Failed to stop hotspot", &e);
                 }
 
                 // Even if stop failed, try to recover state
                 if let Ok(active) = hotspot::is_hotspot_active().await {
                     self.is_active.set(active);
-                    self.hotspot_switch.set_active(active);
                 }
             }
         }
 
         self.operation_in_progress.set(false);
+        self.hotspot_switch.set_active(self.is_active.get());
         self.set_operation_state(false, "");
         self.update_ui();
     }
 
     async fn refresh_status(&self) {
+        // * Don't fight an in-flight start/stop; it resyncs the switch when it finishes.
+        if self.operation_in_progress.get() {
+            return;
+        }
+
         if !self.wifi_present.get() {
             self.is_active.set(false);
             self.hotspot_switch.set_active(false);
             self.update_ui();
+            self.refresh_sessions();
             return;
         }
 
@@ -1464,7 +2074,7 @@ impl HotspotPage {
             }
             Err(e) => {
                 log::error!("Failed to check hotspot status: {}", e);
-                if nm::is_nmcli_retrieval_error(&e.to_string()) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 }
                 self.is_active.set(false);
@@ -1472,6 +2082,35 @@ impl HotspotPage {
                 self.update_ui();
             }
         }
+        self.refresh_sessions();
+        self.refresh_repeater_link().await;
+    }
+
+    // * Passthrough stats for the network a running repeater extends
+    async fn refresh_repeater_link(&self) {
+        let link = if self.is_active.get() {
+            hotspot::repeater_link().await
+        } else {
+            None
+        };
+        if let Some((station, link)) = &link {
+            self.repeater_label
+                .set_text(&repeater_link_summary(station, link));
+        }
+        self.repeater_label.set_visible(link.is_some());
+    }
+
+    fn refresh_sessions(&self) {
+        let (current, past) = hotspot::session_history();
+        let current = current.filter(|_| self.is_active.get());
+        if let Some(session) = &current {
+            self.session_label
+                .set_text(&sessions::current_session_summary(session));
+        }
+        self.session_label.set_visible(current.is_some());
+        self.session_rows
+            .borrow_mut()
+            .update(&self.history_group, &past);
     }
 
     async fn load_interfaces(&self) {
@@ -1480,7 +2119,12 @@ impl HotspotPage {
         let previous_iface = {
             let selected_idx = self.interface_combo.selected() as usize;
             self.devices.borrow().get(selected_idx).cloned()
-        };
+        }
+        .or_else(|| {
+            config::load_config_sync(&config::hotspot_config_path())
+                .ok()
+                .and_then(|saved| saved.interface)
+        });
 
         match hotspot::get_wifi_devices().await {
             Ok(mut ifaces) if !ifaces.is_empty() => {
@@ -1497,6 +2141,9 @@ impl HotspotPage {
                     self.interface_combo.set_selected(selected_idx as u32);
                 });
                 self.set_wifi_state(present, enabled);
+                self.refresh_security_support().await;
+                self.refresh_band_support().await;
+                self.load_upstream_choices(self.selected_upstream()).await;
                 log::info!(
                     "Loaded {} WiFi interfaces, selected: {}",
                     ifaces.len(),
@@ -1529,6 +2176,7 @@ impl HotspotPage {
         let controls_enabled = present && enabled && !self.operation_in_progress.get();
         self.hotspot_switch.set_sensitive(controls_enabled);
         self.config_group.set_sensitive(controls_enabled);
+        self.addressing_group.set_sensitive(controls_enabled);
         self.advanced_group.set_sensitive(controls_enabled);
         self.interface_combo
             .set_sensitive(present && !self.operation_in_progress.get());
@@ -1662,6 +2310,10 @@ impl HotspotPage {
         common::show_toast(&self.toast_overlay, message);
     }
 
+    fn show_error(&self, prefix: &str, error: &anyhow::Error) {
+        common::show_error(&self.toast_overlay, prefix, error);
+    }
+
     async fn confirm_plain_json_usage(&self) -> bool {
         let dialog = adw::AlertDialog::builder()
             .heading("Confirm insecure password storage")
@@ -1681,6 +2333,25 @@ impl HotspotPage {
         response == "continue"
     }
 
+    async fn confirm_wifi_disconnect(&self, message: &str) -> bool {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Leave Wi-Fi?")
+            .body(message)
+            .default_response("cancel")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("start", "Start Hotspot")][..]);
+        dialog.set_response_appearance("start", adw::ResponseAppearance::Destructive);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast::<gtk4::Window>() {
+            dialog.choose_future(Some(&parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        response == "start"
+    }
+
     fn load_password_storage(&self) -> HotspotPasswordStorage {
         config::load_app_settings_sync(&config::app_settings_path())
             .map(|s| s.hotspot_password_storage)
@@ -1697,7 +2368,7 @@ impl HotspotPage {
             return entry_password;
         }
 
-        match storage {
+        let resolved = match storage {
             HotspotPasswordStorage::PlainJson => {
                 config.map(|c| c.password.clone()).unwrap_or_default()
             }
@@ -1708,6 +2379,22 @@ impl HotspotPage {
             HotspotPasswordStorage::NetworkManager => {
                 config.map(|c| c.password.clone()).unwrap_or_default()
             }
+        };
+        if !resolved.is_empty() {
+            return resolved;
+        }
+
+        // * Password stranded by a storage backend switch; recover the last one that started
+        match secrets::load_hotspot_password_backup() {
+            Ok(Some(password)) => {
+                log::info!("Recovered hotspot password from keyring backup");
+                password
+            }
+            Ok(None) => String::new(),
+            Err(e) => {
+                log::warn!("Failed to read hotspot password backup: {}", e);
+                String::new()
+            }
         }
     }
 
@@ -1769,11 +2456,30 @@ impl HotspotPage {
         self.download_limit_spin.set_sensitive(support.tc_available);
         self.upload_limit_spin.set_sensitive(support.tc_available);
         self.device_limit_spin.set_sensitive(support.nft_available);
-        self.mac_filter_combo.set_sensitive(support.nft_available);
         self.client_rules_button
             .set_sensitive(support.tc_available || support.nft_available);
     }
 
+    // * Old drivers can't do SAE; WPA3-only would fail to start, so say so before the user tries.
+    async fn refresh_security_support(&self) {
+        if self.devices.borrow().is_empty() {
+            return;
+        }
+        if let Some(reason) = nm::capabilities().unsupported_reason(nm::NmFeature::Wpa3Hotspot) {
+            self.security_combo.set_subtitle(&reason);
+            return;
+        }
+        let iface = self.current_interface_name();
+        if hotspot::supports_wpa3(&iface).await {
+            self.security_combo.set_subtitle(SECURITY_SUBTITLE);
+        } else {
+            self.security_combo.set_subtitle(&format!(
+                "{} does not support WPA3; WPA2/WPA3 falls back to WPA2",
+                iface
+            ));
+        }
+    }
+
     fn update_client_rules_summary(&self) {
         let rules = self.client_rules.borrow();
         let subtitle = if rules.is_empty() {
@@ -1784,6 +2490,39 @@ impl HotspotPage {
         self.client_rules_row.set_subtitle(&subtitle);
     }
 
+    fn update_reservations_summary(&self) {
+        self.reservations_row
+            .set_subtitle(&reservations::reservations_summary(
+                &self.dhcp_reservations.borrow(),
+            ));
+    }
+
+    fn update_schedule_summary(&self) {
+        self.schedule_row
+            .set_subtitle(&schedule::schedule_summary(&self.schedule.borrow()));
+    }
+
+    async fn edit_schedule(&self) {
+        let initial = self.schedule.borrow().clone();
+        let Some(edited) = schedule::edit_schedule(self.widget.upcast_ref(), initial).await else {
+            return;
+        };
+        *self.schedule.borrow_mut() = edited;
+        self.update_schedule_summary();
+        self.schedule_configuration_update();
+    }
+
+    async fn edit_dhcp_reservations(&self) {
+        let initial = self.dhcp_reservations.borrow().clone();
+        let Some(edited) = reservations::edit_reservations(self.widget.upcast_ref(), initial).await
+        else {
+            return;
+        };
+        *self.dhcp_reservations.borrow_mut() = edited;
+        self.update_reservations_summary();
+        self.schedule_configuration_update();
+    }
+
     async fn edit_client_rules(&self) {
         let initial_rules = self.client_rules.borrow().clone();
         let rules_state = Rc::new(RefCell::new(initial_rules.clone()));
@@ -1865,19 +2604,69 @@ fn spin_value_to_option(spin: &gtk4::SpinButton) -> Option<u32> {
     }
 }
 
-fn mac_filter_mode_from_selection(selected: u32) -> HotspotMacFilterMode {
+// * The total caps are entered in Mbit/s but stored in kbit/s like the per-device ones
+fn mbit_spin_to_kbps(spin: &gtk4::SpinButton) -> Option<u32> {
+    let kbps = (spin.value() * 1000.0).round();
+    (kbps > 0.0).then_some(kbps as u32)
+}
+
+fn kbps_to_mbit(kbps: Option<u32>) -> f64 {
+    kbps.unwrap_or_default() as f64 / 1000.0
+}
+
+fn entry_text_to_option(entry: &adw::EntryRow) -> Option<String> {
+    let text = entry.text();
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+// * The access list is edited on the Devices page; keep whatever is saved there
+fn saved_mac_filter() -> (HotspotMacFilterMode, Vec<String>) {
+    config::load_config_sync(&config::hotspot_config_path())
+        .map(|saved| (saved.mac_filter_mode, saved.mac_filter_list))
+        .unwrap_or_default()
+}
+
+fn security_from_selection(selected: u32) -> HotspotSecurity {
+    match selected {
+        1 => HotspotSecurity::Wpa3,
+        2 => HotspotSecurity::Wpa2Wpa3,
+        _ => HotspotSecurity::Wpa2,
+    }
+}
+
+fn repeater_link_summary(station: &str, link: &crate::repeater::StationLink) -> String {
+    let mut summary = format!(
+        "Extending {} via {} • {} dBm",
+        link.ssid, station, link.signal_dbm
+    );
+    if let Some(rx) = link.rx_bitrate_mbit {
+        summary.push_str(&format!(" • {:.0} Mbit/s", rx));
+    }
+    summary
+}
+
+fn mode_from_selection(selected: u32) -> HotspotMode {
     match selected {
-        1 => HotspotMacFilterMode::Allowlist,
-        2 => HotspotMacFilterMode::Blocklist,
-        _ => HotspotMacFilterMode::Disabled,
+        1 => HotspotMode::Bridged,
+        2 => HotspotMode::Repeater,
+        _ => HotspotMode::Nat,
     }
 }
 
-fn selection_from_mac_filter_mode(mode: &HotspotMacFilterMode) -> u32 {
+fn selection_from_mode(mode: HotspotMode) -> u32 {
     match mode {
-        HotspotMacFilterMode::Disabled => 0,
-        HotspotMacFilterMode::Allowlist => 1,
-        HotspotMacFilterMode::Blocklist => 2,
+        HotspotMode::Nat => 0,
+        HotspotMode::Bridged => 1,
+        HotspotMode::Repeater => 2,
+    }
+}
+
+fn selection_from_security(security: HotspotSecurity) -> u32 {
+    match security {
+        HotspotSecurity::Wpa2 => 0,
+        HotspotSecurity::Wpa3 => 1,
+        HotspotSecurity::Wpa2Wpa3 => 2,
     }
 }
 
@@ -112,9 +112,43 @@ pub(super) fn generate_password(len: usize, include_symbols: bool) -> String {
     out.into_iter().map(char::from).collect()
 }
 
+// * Offered intervals for automatic password changes, in hours
+const ROTATION_CHOICES: &[(&str, Option<u32>)] = &[
+    ("Never", None),
+    ("Every 6 hours", Some(6)),
+    ("Every 12 hours", Some(12)),
+    ("Every day", Some(24)),
+    ("Every week", Some(168)),
+];
+
+pub(super) fn rotation_labels() -> Vec<&'static str> {
+    ROTATION_CHOICES.iter().map(|(label, _)| *label).collect()
+}
+
+pub(super) fn rotation_hours_from_selection(selected: u32) -> Option<u32> {
+    ROTATION_CHOICES
+        .get(selected as usize)
+        .and_then(|(_, hours)| *hours)
+}
+
+// * Hand-edited intervals snap to the next longer choice, so they never rotate more often
+pub(super) fn selection_from_rotation_hours(hours: Option<u32>) -> u32 {
+    let Some(hours) = hours else {
+        return 0;
+    };
+    ROTATION_CHOICES
+        .iter()
+        .position(|(_, choice)| choice.is_some_and(|choice| choice >= hours))
+        .unwrap_or(ROTATION_CHOICES.len() - 1) as u32
+}
+
+pub(super) fn rotation_due(rotated_at: i64, now: i64, hours: u32) -> bool {
+    now.saturating_sub(rotated_at) >= i64::from(hours) * 3600
+}
+
 #[cfg(test)]
 mod tests {
-    use super::generate_password;
+    use super::*;
 
     #[test]
     fn generated_password_respects_bounds() {
@@ -134,4 +168,21 @@ mod tests {
         assert!(value.chars().any(|c| c.is_ascii_digit()));
         assert!(value.chars().any(|c| !c.is_ascii_alphanumeric()));
     }
+
+    #[test]
+    fn rotation_intervals_round_trip_and_fall_due() {
+        assert_eq!(rotation_hours_from_selection(0), None);
+        assert_eq!(
+            rotation_hours_from_selection(selection_from_rotation_hours(Some(24))),
+            Some(24)
+        );
+        assert_eq!(
+            rotation_hours_from_selection(selection_from_rotation_hours(Some(8))),
+            Some(12)
+        );
+        assert_eq!(selection_from_rotation_hours(Some(1000)), 4);
+
+        assert!(!rotation_due(1_000, 1_000 + 6 * 3600 - 1, 6));
+        assert!(rotation_due(1_000, 1_000 + 6 * 3600, 6));
+    }
 }
@@ -0,0 +1,158 @@
+// * ./src/ui/hotspot_page/reservations.rs
+
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::HotspotDhcpReservation;
+
+pub(super) fn reservations_summary(reservations: &[HotspotDhcpReservation]) -> String {
+    if reservations.is_empty() {
+        "No reserved addresses".to_string()
+    } else {
+        format!("{} reserved address(es)", reservations.len())
+    }
+}
+
+fn repopulate_rows(
+    list_box: &gtk4::ListBox,
+    reservations: &Rc<RefCell<Vec<HotspotDhcpReservation>>>,
+) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+
+    let snapshot = reservations.borrow().clone();
+    for (index, reservation) in snapshot.into_iter().enumerate() {
+        let row = adw::ActionRow::builder()
+            .title(
+                reservation
+                    .name
+                    .as_deref()
+                    .unwrap_or(&reservation.mac_address),
+            )
+            .subtitle(format!(
+                "{} • {}",
+                reservation.mac_address, reservation.ip_address
+            ))
+            .build();
+        let delete_btn = gtk4::Button::builder()
+            .label("Delete")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string(), "destructive-action".to_string()])
+            .build();
+        row.add_suffix(&delete_btn);
+
+        let list_box_for_delete = list_box.clone();
+        let reservations_for_delete = reservations.clone();
+        delete_btn.connect_clicked(move |_| {
+            if let Ok(mut reservations) = reservations_for_delete.try_borrow_mut() {
+                if index < reservations.len() {
+                    reservations.remove(index);
+                }
+            }
+            repopulate_rows(&list_box_for_delete, &reservations_for_delete);
+        });
+
+        list_box.append(&row);
+    }
+}
+
+// * Returns the edited list, or None when the dialog was cancelled.
+pub(super) async fn edit_reservations(
+    parent: &gtk4::Widget,
+    initial: Vec<HotspotDhcpReservation>,
+) -> Option<Vec<HotspotDhcpReservation>> {
+    let reservations = Rc::new(RefCell::new(initial));
+
+    let mac_entry = adw::EntryRow::builder().title("MAC Address").build();
+    let ip_entry = adw::EntryRow::builder().title("IP Address").build();
+    let name_entry = adw::EntryRow::builder().title("Name (optional)").build();
+    let error_label = gtk4::Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .css_classes(vec!["error".to_string()])
+        .visible(false)
+        .build();
+    let add_button = gtk4::Button::builder()
+        .label("Add reservation")
+        .halign(gtk4::Align::End)
+        .css_classes(vec!["flat".to_string()])
+        .build();
+
+    let form = adw::PreferencesGroup::new();
+    form.add(&mac_entry);
+    form.add(&ip_entry);
+    form.add(&name_entry);
+
+    let list_box = gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(vec!["boxed-list".to_string()])
+        .build();
+    repopulate_rows(&list_box, &reservations);
+
+    let list_box_for_add = list_box.clone();
+    let reservations_for_add = reservations.clone();
+    let error_label_for_add = error_label.clone();
+    let (mac_for_add, ip_for_add, name_for_add) =
+        (mac_entry.clone(), ip_entry.clone(), name_entry.clone());
+    add_button.connect_clicked(move |_| {
+        let parsed = HotspotDhcpReservation::parse(
+            &mac_for_add.text(),
+            &ip_for_add.text(),
+            &name_for_add.text(),
+        );
+        let reservation = match parsed {
+            Ok(reservation) => reservation,
+            Err(e) => {
+                error_label_for_add.set_text(&e.to_string());
+                error_label_for_add.set_visible(true);
+                return;
+            }
+        };
+        error_label_for_add.set_visible(false);
+        if let Ok(mut reservations) = reservations_for_add.try_borrow_mut() {
+            // * Re-adding a MAC replaces its old reservation
+            reservations.retain(|existing| existing.mac_address != reservation.mac_address);
+            reservations.push(reservation);
+            reservations.sort_by(|a, b| a.mac_address.cmp(&b.mac_address));
+        }
+        mac_for_add.set_text("");
+        ip_for_add.set_text("");
+        name_for_add.set_text("");
+        repopulate_rows(&list_box_for_add, &reservations_for_add);
+    });
+
+    let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+    body.set_margin_top(12);
+    body.set_margin_bottom(12);
+    body.set_margin_start(12);
+    body.set_margin_end(12);
+    body.append(&form);
+    body.append(&error_label);
+    body.append(&add_button);
+    body.append(&list_box);
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Reserved Addresses")
+        .body("Devices listed here always get the same address from the hotspot. Changes apply the next time the hotspot starts.")
+        .extra_child(&body)
+        .default_response("save")
+        .close_response("cancel")
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+    let response = if let Some(parent) = parent.root().and_downcast_ref::<gtk4::Window>() {
+        dialog.choose_future(Some(parent)).await
+    } else {
+        dialog.choose_future(None::<&gtk4::Window>).await
+    };
+
+    if response.as_str() != "save" {
+        return None;
+    }
+    let edited = reservations.borrow().clone();
+    Some(edited)
+}
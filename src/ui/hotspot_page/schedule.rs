@@ -0,0 +1,162 @@
+// * ./src/ui/hotspot_page/schedule.rs
+
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::HotspotScheduleRule;
+
+const DAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+pub(super) fn schedule_summary(rules: &[HotspotScheduleRule]) -> String {
+    match rules {
+        [] => "Not scheduled".to_string(),
+        [rule] => rule.describe(),
+        rules => format!("{} time slots", rules.len()),
+    }
+}
+
+fn repopulate_rows(list_box: &gtk4::ListBox, rules: &Rc<RefCell<Vec<HotspotScheduleRule>>>) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+
+    let snapshot = rules.borrow().clone();
+    for (index, rule) in snapshot.into_iter().enumerate() {
+        let row = adw::ActionRow::builder().title(rule.describe()).build();
+        let delete_btn = gtk4::Button::builder()
+            .label("Delete")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string(), "destructive-action".to_string()])
+            .build();
+        row.add_suffix(&delete_btn);
+
+        let list_box_for_delete = list_box.clone();
+        let rules_for_delete = rules.clone();
+        delete_btn.connect_clicked(move |_| {
+            if let Ok(mut rules) = rules_for_delete.try_borrow_mut() {
+                if index < rules.len() {
+                    rules.remove(index);
+                }
+            }
+            repopulate_rows(&list_box_for_delete, &rules_for_delete);
+        });
+
+        list_box.append(&row);
+    }
+}
+
+// * Returns the edited schedule, or None when the dialog was cancelled.
+pub(super) async fn edit_schedule(
+    parent: &gtk4::Widget,
+    initial: Vec<HotspotScheduleRule>,
+) -> Option<Vec<HotspotScheduleRule>> {
+    let rules = Rc::new(RefCell::new(initial));
+
+    let day_box = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Horizontal)
+        .halign(gtk4::Align::Center)
+        .css_classes(vec!["linked".to_string()])
+        .build();
+    let day_buttons: Vec<gtk4::ToggleButton> = DAY_LABELS
+        .iter()
+        .enumerate()
+        .map(|(day, label)| {
+            let button = gtk4::ToggleButton::builder()
+                .label(*label)
+                .active(day < 5)
+                .build();
+            day_box.append(&button);
+            button
+        })
+        .collect();
+
+    let start_entry = adw::EntryRow::builder().title("Start (HH:MM)").build();
+    start_entry.set_text("09:00");
+    let end_entry = adw::EntryRow::builder().title("End (HH:MM)").build();
+    end_entry.set_text("17:00");
+    let error_label = gtk4::Label::builder()
+        .xalign(0.0)
+        .wrap(true)
+        .css_classes(vec!["error".to_string()])
+        .visible(false)
+        .build();
+    let add_button = gtk4::Button::builder()
+        .label("Add time slot")
+        .halign(gtk4::Align::End)
+        .css_classes(vec!["flat".to_string()])
+        .build();
+
+    let form = adw::PreferencesGroup::new();
+    form.add(&start_entry);
+    form.add(&end_entry);
+
+    let list_box = gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(vec!["boxed-list".to_string()])
+        .build();
+    repopulate_rows(&list_box, &rules);
+
+    let list_box_for_add = list_box.clone();
+    let rules_for_add = rules.clone();
+    let error_label_for_add = error_label.clone();
+    let (start_for_add, end_for_add) = (start_entry.clone(), end_entry.clone());
+    add_button.connect_clicked(move |_| {
+        let days: Vec<u8> = day_buttons
+            .iter()
+            .enumerate()
+            .filter(|(_, button)| button.is_active())
+            .map(|(day, _)| day as u8)
+            .collect();
+        let parsed = HotspotScheduleRule::parse(&days, &start_for_add.text(), &end_for_add.text());
+        let rule = match parsed {
+            Ok(rule) => rule,
+            Err(e) => {
+                error_label_for_add.set_text(&e.to_string());
+                error_label_for_add.set_visible(true);
+                return;
+            }
+        };
+        error_label_for_add.set_visible(false);
+        if let Ok(mut rules) = rules_for_add.try_borrow_mut() {
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+        repopulate_rows(&list_box_for_add, &rules_for_add);
+    });
+
+    let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+    body.set_margin_top(12);
+    body.set_margin_bottom(12);
+    body.set_margin_start(12);
+    body.set_margin_end(12);
+    body.append(&day_box);
+    body.append(&form);
+    body.append(&error_label);
+    body.append(&add_button);
+    body.append(&list_box);
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Hotspot Schedule")
+        .body("The hotspot starts when a time slot begins and stops when it ends. An end before the start runs past midnight.")
+        .extra_child(&body)
+        .default_response("save")
+        .close_response("cancel")
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+    let response = if let Some(parent) = parent.root().and_downcast_ref::<gtk4::Window>() {
+        dialog.choose_future(Some(parent)).await
+    } else {
+        dialog.choose_future(None::<&gtk4::Window>).await
+    };
+
+    if response.as_str() != "save" {
+        return None;
+    }
+    let edited = rules.borrow().clone();
+    Some(edited)
+}
@@ -0,0 +1,80 @@
+// * ./src/ui/hotspot_page/sessions.rs
+
+use libadwaita::{self as adw, prelude::*};
+
+use crate::hotspot_runtime::HotspotSession;
+use crate::ui::common;
+
+// * How many past sessions the Recent Sessions group shows
+const VISIBLE_SESSIONS: usize = 5;
+
+fn format_uptime(seconds: u64) -> String {
+    match seconds {
+        0..=59 => "Under a minute".to_string(),
+        secs if secs < 3600 => format!("{} min", secs / 60),
+        secs => format!("{} h {} min", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn peak_label(peak_clients: u32) -> String {
+    match peak_clients {
+        1 => "peak 1 device".to_string(),
+        peak => format!("peak {} devices", peak),
+    }
+}
+
+// * Shown under the status header while the hotspot runs
+pub(super) fn current_session_summary(session: &HotspotSession) -> String {
+    format!(
+        "Up {} • {} • {} served",
+        format_uptime(session.uptime_seconds()),
+        peak_label(session.peak_clients),
+        common::format_bytes(session.bytes_served)
+    )
+}
+
+fn session_title(session: &HotspotSession) -> String {
+    chrono::DateTime::from_timestamp(session.started_at, 0)
+        .map(|started| {
+            started
+                .with_timezone(&chrono::Local)
+                .format("%b %-d, %H:%M")
+                .to_string()
+        })
+        .map(|started| format!("{} • {}", started, session.ssid))
+        .unwrap_or_else(|| session.ssid.clone())
+}
+
+// * Rows currently in the Recent Sessions group, rebuilt only when the history changes.
+#[derive(Default)]
+pub(super) struct SessionRows {
+    rows: Vec<adw::ActionRow>,
+    shown: Vec<HotspotSession>,
+}
+
+impl SessionRows {
+    pub(super) fn update(&mut self, group: &adw::PreferencesGroup, sessions: &[HotspotSession]) {
+        let sessions = &sessions[..sessions.len().min(VISIBLE_SESSIONS)];
+        if self.shown == sessions {
+            return;
+        }
+        for row in self.rows.drain(..) {
+            group.remove(&row);
+        }
+        for session in sessions {
+            let row = adw::ActionRow::builder()
+                .title(session_title(session))
+                .subtitle(format!(
+                    "{} • {} • {}",
+                    format_uptime(session.uptime_seconds()),
+                    peak_label(session.peak_clients),
+                    common::format_bytes(session.bytes_served)
+                ))
+                .build();
+            group.add(&row);
+            self.rows.push(row);
+        }
+        self.shown = sessions.to_vec();
+        group.set_visible(!sessions.is_empty());
+    }
+}
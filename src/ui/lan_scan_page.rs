@@ -0,0 +1,168 @@
+// * ./src/ui/lan_scan_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::lan_scan::{self, LanDevice};
+use crate::oui::vendor_from_mac;
+use crate::ui::wake_page::WakePage;
+use crate::ui::{common, icon_name};
+
+// * Devices on whatever network we're connected to; scanning is manual since it probes every host.
+#[derive(Clone)]
+pub struct LanScanPage {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    wake_page: WakePage,
+    scan_button: gtk4::Button,
+    spinner: gtk4::Spinner,
+    status_row: adw::ActionRow,
+    device_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+}
+
+impl LanScanPage {
+    pub fn new(toast_overlay: &adw::ToastOverlay, wake_page: &WakePage) -> Self {
+        let widget = adw::PreferencesGroup::new();
+        widget.set_title("Devices on This Network");
+        widget.set_description(Some(
+            "Find computers, phones and printers on the network you're connected to.",
+        ));
+        widget.set_margin_top(24);
+
+        let header_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        let spinner = gtk4::Spinner::new();
+        spinner.set_visible(false);
+        let scan_button = gtk4::Button::builder()
+            .label("Scan")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        header_box.append(&spinner);
+        header_box.append(&scan_button);
+        widget.set_header_suffix(Some(&header_box));
+
+        let status_row = adw::ActionRow::builder()
+            .title("Not scanned yet")
+            .subtitle("Scanning briefly contacts every address on the local subnet")
+            .build();
+        widget.add(&status_row);
+
+        let page = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            wake_page: wake_page.clone(),
+            scan_button: scan_button.clone(),
+            spinner,
+            status_row,
+            device_rows: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        let page_ref = page.clone();
+        scan_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.scan().await;
+            });
+        });
+
+        page
+    }
+
+    async fn scan(&self) {
+        self.scan_button.set_sensitive(false);
+        self.spinner.set_visible(true);
+        self.spinner.start();
+        self.status_row.set_title("Scanning...");
+        self.status_row.set_subtitle("");
+
+        match lan_scan::scan().await {
+            Ok(result) => {
+                self.status_row.set_title(&match result.devices.len() {
+                    0 => "No other devices found".to_string(),
+                    1 => "1 device found".to_string(),
+                    count => format!("{} devices found", count),
+                });
+                self.status_row.set_subtitle(&format!(
+                    "On {}{}",
+                    result.interface,
+                    result
+                        .own_address
+                        .map(|address| format!(" • this computer is {}", address))
+                        .unwrap_or_default()
+                ));
+                self.show_devices(&result.devices);
+            }
+            Err(e) => {
+                log::warn!("LAN scan failed: {}", e);
+                self.status_row.set_title("Scan failed");
+                self.status_row.set_subtitle(&e.to_string());
+                self.show_devices(&[]);
+                common::show_toast(&self.toast_overlay, &format!("Scan failed: {}", e));
+            }
+        }
+
+        self.spinner.stop();
+        self.spinner.set_visible(false);
+        self.scan_button.set_label("Scan Again");
+        self.scan_button.set_sensitive(true);
+    }
+
+    fn show_devices(&self, devices: &[LanDevice]) {
+        for row in self.device_rows.borrow_mut().drain(..) {
+            self.widget.remove(&row);
+        }
+        for device in devices {
+            let row = self.create_device_row(device);
+            self.widget.add(&row);
+            self.device_rows.borrow_mut().push(row);
+        }
+    }
+
+    fn create_device_row(&self, device: &LanDevice) -> adw::ActionRow {
+        let ip = device.ip.to_string();
+        let mut details = vec![device.mac.clone()];
+        if let Some(vendor) = vendor_from_mac(&device.mac) {
+            details.push(vendor);
+        }
+        let (title, subtitle) = match device.hostname.as_deref() {
+            Some(hostname) => (
+                hostname.to_string(),
+                format!("{} • {}", ip, details.join(" • ")),
+            ),
+            None => (ip, details.join(" • ")),
+        };
+        let row = adw::ActionRow::builder()
+            .title(&title)
+            .subtitle(&subtitle)
+            .subtitle_selectable(true)
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(icon_name(
+            "network-workgroup-symbolic",
+            &["computer-symbolic", "computer"][..],
+        )));
+
+        let wake_button = gtk4::Button::builder()
+            .icon_name(icon_name("starred-symbolic", &["non-starred-symbolic"][..]))
+            .tooltip_text("Add to Wake on LAN")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        row.add_suffix(&wake_button);
+
+        let wake_page = self.wake_page.clone();
+        let name = device.hostname.clone().unwrap_or_default();
+        let mac = device.mac.clone();
+        wake_button.connect_clicked(move |_| {
+            let wake_page = wake_page.clone();
+            let name = name.clone();
+            let mac = mac.clone();
+            glib::spawn_future_local(async move {
+                wake_page.show_add_dialog(&name, &mac).await;
+            });
+        });
+        row
+    }
+}
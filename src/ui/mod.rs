@@ -1,12 +1,32 @@
 // * ./src/ui/mod.rs
 
+pub mod bluetooth_page;
 pub mod common;
+pub mod compact_window;
+pub mod connection_editor;
 pub mod devices_page;
+pub mod diagnostics_page;
 pub mod ethernet_page;
+pub mod history_page;
 pub mod hotspot_page;
+pub mod lan_scan_page;
+pub mod modem_page;
 pub mod profiles_page;
+pub mod public_ip_card;
+pub mod secret_prompt;
+pub mod setup_wizard;
+pub mod speed_graph;
+pub mod tailscale_card;
+pub mod usage_page;
+pub mod vpn_page;
+pub mod wake_page;
 pub mod wifi_page;
 
+use libadwaita::{self as adw, prelude::*};
+
+const CONFIRM_RESPONSE: &str = "confirm";
+const CANCEL_RESPONSE: &str = "cancel";
+
 pub fn icon_name<'a>(primary: &'a str, fallbacks: &'a [&'a str]) -> &'a str {
     let Some(display) = gtk4::gdk::Display::default() else {
         return primary;
@@ -31,3 +51,86 @@ pub fn icon_name<'a>(primary: &'a str, fallbacks: &'a [&'a str]) -> &'a str {
 
     primary
 }
+
+// * Wording and styling of a cancel/confirm dialog, kept apart from the widget for tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmSpec<'a> {
+    pub heading: &'a str,
+    pub body: &'a str,
+    pub confirm_label: &'a str,
+    pub destructive: bool,
+}
+
+impl ConfirmSpec<'_> {
+    fn responses(&self) -> [(&str, &str); 2] {
+        [
+            (CANCEL_RESPONSE, "Cancel"),
+            (CONFIRM_RESPONSE, self.confirm_label),
+        ]
+    }
+
+    fn confirm_appearance(&self) -> adw::ResponseAppearance {
+        if self.destructive {
+            adw::ResponseAppearance::Destructive
+        } else {
+            adw::ResponseAppearance::Suggested
+        }
+    }
+
+    fn is_confirmed(response: &str) -> bool {
+        response == CONFIRM_RESPONSE
+    }
+}
+
+pub async fn confirm_dialog(
+    parent: &impl IsA<gtk4::Widget>,
+    heading: &str,
+    body: &str,
+    confirm_label: &str,
+    destructive: bool,
+) -> bool {
+    let spec = ConfirmSpec {
+        heading,
+        body,
+        confirm_label,
+        destructive,
+    };
+    let dialog = adw::AlertDialog::builder()
+        .heading(spec.heading)
+        .body(spec.body)
+        .default_response(CONFIRM_RESPONSE)
+        .close_response(CANCEL_RESPONSE)
+        .build();
+    dialog.add_responses(&spec.responses()[..]);
+    dialog.set_response_appearance(CONFIRM_RESPONSE, spec.confirm_appearance());
+
+    let response = dialog.choose_future(Some(parent)).await;
+    ConfirmSpec::is_confirmed(response.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_spec_maps_responses() {
+        let spec = ConfirmSpec {
+            heading: "Forget Network?",
+            body: "",
+            confirm_label: "Forget",
+            destructive: true,
+        };
+        assert_eq!(
+            spec.responses(),
+            [(CANCEL_RESPONSE, "Cancel"), (CONFIRM_RESPONSE, "Forget")]
+        );
+        assert_eq!(
+            spec.confirm_appearance(),
+            adw::ResponseAppearance::Destructive
+        );
+        assert!(ConfirmSpec::is_confirmed(CONFIRM_RESPONSE));
+        assert!(!ConfirmSpec::is_confirmed(CANCEL_RESPONSE));
+        // * Escape / closing the dialog reports the close response
+        assert!(!ConfirmSpec::is_confirmed(""));
+    }
+}
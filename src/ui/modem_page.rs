@@ -0,0 +1,381 @@
+// * ./src/ui/modem_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use zeroize::Zeroizing;
+
+use crate::modem_manager::{self, MobileDataStatus};
+use crate::nm;
+use crate::ui::common;
+
+// * Cellular modem status and controls from ModemManager: signal, operator, APN, PIN and data.
+#[derive(Clone)]
+pub struct ModemPage {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    wwan_switch: adw::SwitchRow,
+    status_row: adw::ActionRow,
+    signal_row: adw::ActionRow,
+    network_row: adw::ActionRow,
+    sim_row: adw::ActionRow,
+    unlock_button: gtk4::Button,
+    apn_entry: adw::EntryRow,
+    connect_button: gtk4::Button,
+    radio_button: gtk4::Button,
+}
+
+impl ModemPage {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let widget = adw::PreferencesGroup::new();
+        widget.set_title("Mobile Data");
+        widget.set_description(Some(
+            "Manage cellular modem status, APN, and connection state from ModemManager.",
+        ));
+
+        let wwan_switch = adw::SwitchRow::builder()
+            .title("Use Mobile Broadband")
+            .subtitle("Turns every cellular radio on or off")
+            .visible(false)
+            .build();
+
+        let status_row = adw::ActionRow::builder()
+            .title("Status")
+            .subtitle("Checking for modems...")
+            .build();
+        let actions = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        let connect_button = gtk4::Button::builder()
+            .label("Connect")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let radio_button = gtk4::Button::builder()
+            .label("Turn radio on")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        actions.append(&connect_button);
+        actions.append(&radio_button);
+        status_row.add_suffix(&actions);
+
+        let signal_row = adw::ActionRow::builder()
+            .title("Signal")
+            .subtitle("Unavailable")
+            .build();
+        let network_row = adw::ActionRow::builder()
+            .title("Network")
+            .subtitle("Unavailable")
+            .build();
+        let sim_row = adw::ActionRow::builder()
+            .title("SIM / PIN")
+            .subtitle("Unavailable")
+            .build();
+        let unlock_button = gtk4::Button::builder()
+            .label("Unlock")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .visible(false)
+            .build();
+        sim_row.add_suffix(&unlock_button);
+        let apn_entry = adw::EntryRow::builder().title("APN").build();
+        apn_entry.set_text("");
+
+        widget.add(&wwan_switch);
+        widget.add(&status_row);
+        widget.add(&signal_row);
+        widget.add(&network_row);
+        widget.add(&sim_row);
+        widget.add(&apn_entry);
+
+        let page = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            wwan_switch: wwan_switch.clone(),
+            status_row,
+            signal_row,
+            network_row,
+            sim_row,
+            unlock_button: unlock_button.clone(),
+            apn_entry,
+            connect_button: connect_button.clone(),
+            radio_button: radio_button.clone(),
+        };
+
+        let page_ref = page.clone();
+        connect_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.toggle_connection().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        radio_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.toggle_radio().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        unlock_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.unlock_sim().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        wwan_switch.connect_active_notify(move |switch| {
+            let enabled = switch.is_active();
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                match nm::set_wwan_enabled(enabled).await {
+                    Ok(()) => page.refresh().await,
+                    Err(e) => {
+                        log::error!("Failed to toggle mobile broadband: {}", e);
+                        page.show_toast(&format!("Failed to toggle mobile broadband: {}", e));
+                    }
+                }
+            });
+        });
+
+        page
+    }
+
+    pub async fn refresh(&self) {
+        self.refresh_wwan_switch().await;
+        match modem_manager::get_mobile_data_status().await {
+            Ok(status) => self.apply_status(status),
+            Err(e) => {
+                log::error!("Failed to refresh mobile data status: {}", e);
+                self.widget
+                    .set_description(Some("Could not read ModemManager status."));
+                self.status_row.set_subtitle("Status unavailable");
+                self.signal_row.set_subtitle("Unavailable");
+                self.network_row.set_subtitle("Unavailable");
+                self.sim_row.set_subtitle("Unavailable");
+                self.unlock_button.set_visible(false);
+                self.apn_entry.set_sensitive(false);
+                self.connect_button.set_sensitive(false);
+                self.radio_button.set_sensitive(false);
+            }
+        }
+    }
+
+    // * Hidden without a modem; the switch only moves when the state was changed elsewhere.
+    async fn refresh_wwan_switch(&self) {
+        let has_modem = nm::has_modem_device().await.unwrap_or(false);
+        self.wwan_switch.set_visible(has_modem);
+        if !has_modem {
+            return;
+        }
+        match nm::is_wwan_enabled().await {
+            Ok(enabled) if enabled != self.wwan_switch.is_active() => {
+                self.wwan_switch.set_active(enabled)
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to read mobile broadband state: {}", e),
+        }
+    }
+
+    fn apply_status(&self, status: MobileDataStatus) {
+        if !status.service_available || !status.modem_present {
+            self.widget.set_description(Some(if !status.service_available {
+                "ModemManager is not available on this system, so mobile data controls are disabled."
+            } else {
+                "No mobile broadband modem was detected. If you attach one, it will appear here automatically."
+            }));
+            self.status_row.set_subtitle(&status.state_label);
+            self.signal_row.set_subtitle("Unavailable");
+            self.network_row.set_subtitle("Unavailable");
+            self.sim_row.set_subtitle("Unavailable");
+            self.unlock_button.set_visible(false);
+            self.apn_entry.set_text("");
+            self.apn_entry.set_sensitive(false);
+            self.connect_button.set_label("Connect");
+            self.connect_button.set_sensitive(false);
+            self.radio_button.set_label("Turn radio on");
+            self.radio_button.set_sensitive(false);
+            return;
+        }
+
+        self.widget.set_description(Some(
+            "Cellular modem controls are available through ModemManager.",
+        ));
+
+        let status_subtitle = match status.device_name.as_deref() {
+            Some(device) => format!("{} • {}", status.state_label, device),
+            None => status.state_label.clone(),
+        };
+        self.status_row.set_subtitle(&status_subtitle);
+
+        let signal_text = match status.signal_quality_percent {
+            Some(signal) => format!("{}%", signal),
+            None => "Unavailable".to_string(),
+        };
+        self.signal_row.set_subtitle(&signal_text);
+
+        let network_text = format!(
+            "{} • {}",
+            status
+                .operator_name
+                .as_deref()
+                .filter(|value| !value.is_empty())
+                .unwrap_or("Operator unavailable"),
+            status
+                .network_generation
+                .as_deref()
+                .filter(|value| !value.is_empty())
+                .unwrap_or("Unavailable")
+        );
+        self.network_row.set_subtitle(&network_text);
+        self.sim_row
+            .set_subtitle(&format!("{} • {}", status.sim_status, status.pin_status));
+        self.unlock_button.set_visible(status.pin_required);
+        // * Don't overwrite an APN the user is still typing
+        if !self.apn_entry.has_focus() {
+            self.apn_entry.set_text(status.apn.as_deref().unwrap_or(""));
+        }
+        self.apn_entry.set_sensitive(status.radio_enabled);
+        self.connect_button.set_label(if status.connected {
+            "Disconnect"
+        } else {
+            "Connect"
+        });
+        self.connect_button.set_sensitive(
+            status.radio_enabled && !matches!(status.state_label.as_str(), "Locked"),
+        );
+        self.radio_button.set_label(if status.radio_enabled {
+            "Turn radio off"
+        } else {
+            "Turn radio on"
+        });
+        self.radio_button.set_sensitive(true);
+    }
+
+    async fn toggle_connection(&self) {
+        let status = match modem_manager::get_mobile_data_status().await {
+            Ok(status) => status,
+            Err(e) => {
+                self.show_toast(&format!("Failed to read mobile data status: {}", e));
+                return;
+            }
+        };
+
+        if !status.modem_present {
+            self.show_toast("No mobile modem detected");
+            return;
+        }
+
+        let apn = self.apn_entry.text().trim().to_string();
+        let result = if status.connected {
+            modem_manager::disconnect_mobile_data().await
+        } else {
+            modem_manager::connect_mobile_data((!apn.is_empty()).then_some(apn.as_str())).await
+        };
+
+        match result {
+            Ok(()) => {
+                self.show_toast(if status.connected {
+                    "Mobile data disconnected"
+                } else {
+                    "Mobile data connected"
+                });
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to toggle mobile data connection: {}", e);
+                self.show_toast(&format!("Failed to update mobile data: {}", e));
+            }
+        }
+    }
+
+    async fn toggle_radio(&self) {
+        let status = match modem_manager::get_mobile_data_status().await {
+            Ok(status) => status,
+            Err(e) => {
+                self.show_toast(&format!("Failed to read mobile data status: {}", e));
+                return;
+            }
+        };
+
+        if !status.modem_present {
+            self.show_toast("No mobile modem detected");
+            return;
+        }
+
+        let target = !status.radio_enabled;
+        match modem_manager::set_radio_enabled(target).await {
+            Ok(()) => {
+                self.show_toast(if target {
+                    "Mobile radio enabled"
+                } else {
+                    "Mobile radio disabled"
+                });
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to toggle mobile radio: {}", e);
+                self.show_toast(&format!("Failed to update radio state: {}", e));
+            }
+        }
+    }
+
+    async fn unlock_sim(&self) {
+        let pin_entry = adw::PasswordEntryRow::builder()
+            .title("PIN")
+            .activates_default(true)
+            .build();
+        pin_entry.set_input_purpose(gtk4::InputPurpose::Pin);
+
+        let content_box = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        content_box.set_margin_top(12);
+        content_box.set_margin_bottom(12);
+        content_box.set_margin_start(12);
+        content_box.set_margin_end(12);
+        content_box.append(&pin_entry);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Unlock SIM")
+            .body("Enter the SIM PIN. Too many wrong attempts will lock the SIM until the PUK is entered.")
+            .extra_child(&content_box)
+            .default_response("unlock")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("unlock", "Unlock")][..]);
+        dialog.set_response_appearance("unlock", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("unlock", false);
+
+        let dialog_for_entry = dialog.clone();
+        pin_entry.connect_changed(move |entry| {
+            let pin = entry.text();
+            let valid = (4..=8).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit());
+            dialog_for_entry.set_response_enabled("unlock", valid);
+        });
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        if response.as_str() != "unlock" {
+            return;
+        }
+
+        let pin = Zeroizing::new(pin_entry.text().to_string());
+        match modem_manager::send_pin(&pin).await {
+            Ok(()) => {
+                self.show_toast("SIM unlocked");
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to unlock SIM: {}", e);
+                self.show_toast(&format!("Failed to unlock SIM: {}", e));
+                self.refresh().await;
+            }
+        }
+    }
+
+    fn show_toast(&self, message: &str) {
+        common::show_toast(&self.toast_overlay, message);
+    }
+}
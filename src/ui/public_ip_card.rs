@@ -0,0 +1,167 @@
+// * ./src/ui/public_ip_card.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+
+use crate::config::{self, AppSettings};
+use crate::public_ip::{self, PublicIpInfo};
+use crate::ui::{common, icon_name};
+
+// * Opt-in: nothing is sent to the lookup service until the switch is turned on.
+#[derive(Clone)]
+pub struct PublicIpCard {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    enabled_row: adw::SwitchRow,
+    refresh_button: gtk4::Button,
+    ipv4_row: adw::ActionRow,
+    ipv6_row: adw::ActionRow,
+    reverse_dns_row: adw::ActionRow,
+    provider_row: adw::ActionRow,
+    endpoint_row: adw::EntryRow,
+}
+
+impl PublicIpCard {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let settings =
+            config::load_app_settings_sync(&config::app_settings_path()).unwrap_or_default();
+
+        let widget = adw::PreferencesGroup::builder()
+            .title("External Connectivity")
+            .build();
+        let refresh_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "view-refresh-symbolic",
+                &["view-refresh", "reload-symbolic"][..],
+            ))
+            .tooltip_text("Look up again")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        widget.set_header_suffix(Some(&refresh_button));
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title("Show Public Address")
+            .subtitle("Asks the lookup service below for your public IP and provider")
+            .active(settings.show_public_ip)
+            .build();
+        let property_row = |title: &str| {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle_selectable(true)
+                .build();
+            row.add_css_class("property");
+            row
+        };
+        let ipv4_row = property_row("IPv4");
+        let ipv6_row = property_row("IPv6");
+        let reverse_dns_row = property_row("Reverse DNS");
+        let provider_row = property_row("Provider");
+        let endpoint_row = adw::EntryRow::builder()
+            .title("Lookup Service")
+            .show_apply_button(true)
+            .build();
+        endpoint_row.set_text(&settings.public_ip_lookup_url);
+
+        widget.add(&enabled_row);
+        widget.add(&ipv4_row);
+        widget.add(&ipv6_row);
+        widget.add(&reverse_dns_row);
+        widget.add(&provider_row);
+        widget.add(&endpoint_row);
+
+        let card = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            enabled_row: enabled_row.clone(),
+            refresh_button: refresh_button.clone(),
+            ipv4_row,
+            ipv6_row,
+            reverse_dns_row,
+            provider_row,
+            endpoint_row: endpoint_row.clone(),
+        };
+        card.set_enabled(settings.show_public_ip);
+
+        let card_ref = card.clone();
+        enabled_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            card_ref.save_setting(|settings| settings.show_public_ip = enabled);
+            card_ref.set_enabled(enabled);
+        });
+
+        let card_ref = card.clone();
+        refresh_button.connect_clicked(move |_| card_ref.refresh());
+
+        let card_ref = card.clone();
+        endpoint_row.connect_apply(move |row| {
+            let url = row.text().trim().to_string();
+            card_ref.save_setting(|settings| settings.public_ip_lookup_url = url);
+            card_ref.refresh();
+        });
+
+        card
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.refresh_button.set_visible(enabled);
+        for row in [
+            &self.ipv4_row,
+            &self.ipv6_row,
+            &self.reverse_dns_row,
+            &self.provider_row,
+        ] {
+            row.set_visible(enabled);
+        }
+        if enabled {
+            self.refresh();
+        }
+    }
+
+    pub fn refresh(&self) {
+        if !self.enabled_row.is_active() {
+            return;
+        }
+        for row in [
+            &self.ipv4_row,
+            &self.ipv6_row,
+            &self.reverse_dns_row,
+            &self.provider_row,
+        ] {
+            row.set_subtitle("Looking up...");
+        }
+        self.refresh_button.set_sensitive(false);
+
+        let card = self.clone();
+        let url = self.endpoint_row.text().trim().to_string();
+        glib::spawn_future_local(async move {
+            match public_ip::lookup(&url).await {
+                Ok(info) => card.show_info(&info),
+                Err(e) => {
+                    log::warn!("Public IP lookup failed: {}", e);
+                    card.show_info(&PublicIpInfo::default());
+                    common::show_toast(&card.toast_overlay, &format!("Lookup failed: {}", e));
+                }
+            }
+            card.refresh_button.set_sensitive(true);
+        });
+    }
+
+    fn show_info(&self, info: &PublicIpInfo) {
+        let text = |value: &Option<String>| value.clone().unwrap_or_else(|| "—".to_string());
+        self.ipv4_row.set_subtitle(&text(&info.ipv4));
+        self.ipv6_row.set_subtitle(&text(&info.ipv6));
+        self.reverse_dns_row.set_subtitle(&text(&info.reverse_dns));
+        self.provider_row.set_subtitle(&text(&info.provider));
+    }
+
+    fn save_setting(&self, update: impl FnOnce(&mut AppSettings)) {
+        let path = config::app_settings_path();
+        let mut settings = config::load_app_settings_sync(&path).unwrap_or_default();
+        update(&mut settings);
+        if let Err(e) = config::save_app_settings_sync(&path, &settings) {
+            log::warn!("Failed to save app settings: {}", e);
+        }
+    }
+}
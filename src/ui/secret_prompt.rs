@@ -0,0 +1,83 @@
+// * ./src/ui/secret_prompt.rs
+
+use futures::future::{select, Either};
+use gtk4::glib;
+use libadwaita::{self as adw, prelude::*};
+use zeroize::Zeroizing;
+
+use crate::secret_agent::{self, SecretKind, SecretRequest};
+
+// * Asks for passwords NM requests through the secret agent, one prompt at a time.
+pub fn forward_secret_requests(window: &adw::ApplicationWindow) {
+    let Some(mut requests) = secret_agent::take_requests() else {
+        return;
+    };
+    let window = window.clone();
+    glib::spawn_future_local(async move {
+        while let Some(request) = requests.recv().await {
+            prompt_for_secret(&window, request).await;
+        }
+    });
+}
+
+async fn prompt_for_secret(window: &adw::ApplicationWindow, mut request: SecretRequest) {
+    let password_entry = adw::PasswordEntryRow::builder()
+        .title("Password")
+        .activates_default(true)
+        .build();
+
+    let content_box = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.append(&password_entry);
+
+    let target = match request.kind {
+        SecretKind::Wifi => request.connection_id.clone(),
+        SecretKind::Vpn => format!("VPN {}", request.connection_id),
+    };
+    let body = if request.retry {
+        format!(
+            "The password for {} was not accepted. Enter it again",
+            target
+        )
+    } else {
+        format!("Enter the password for {}", target)
+    };
+    let dialog = adw::AlertDialog::builder()
+        .heading("Password Required")
+        .body(body)
+        .extra_child(&content_box)
+        .default_response("connect")
+        .close_response("cancel")
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("connect", "Connect")][..]);
+    dialog.set_response_appearance("connect", adw::ResponseAppearance::Suggested);
+    dialog.set_response_enabled("connect", false);
+
+    let dialog_for_entry = dialog.clone();
+    password_entry.connect_changed(move |entry| {
+        dialog_for_entry.set_response_enabled("connect", !entry.text().is_empty());
+    });
+
+    // * NM may cancel the request (timeout, another agent answered) while the prompt is open
+    let response = match select(
+        Box::pin(dialog.clone().choose_future(Some(window))),
+        Box::pin(request.reply.closed()),
+    )
+    .await
+    {
+        Either::Left((response, _)) => Some(response),
+        Either::Right(_) => None,
+    };
+    let Some(response) = response else {
+        dialog.force_close();
+        return;
+    };
+
+    let password = (response.as_str() == "connect")
+        .then(|| Zeroizing::new(password_entry.text().to_string()))
+        .filter(|password| !password.is_empty());
+    let _ = request.reply.send(password);
+}
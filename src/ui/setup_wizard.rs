@@ -0,0 +1,470 @@
+// * ./src/ui/setup_wizard.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::config::{self, AppSettings, HotspotConfig, HotspotPasswordStorage};
+use crate::nm::{self, SavedHotspotProfile};
+use crate::secrets;
+use crate::ui::icon_name;
+
+// * (label, view stack name); the first entry leaves the choice to the module layout
+const START_PAGES: [(&str, Option<&str>); 6] = [
+    ("Automatic", None),
+    ("Wi-Fi", Some("wifi")),
+    ("Ethernet", Some("ethernet")),
+    ("Hotspot", Some("hotspot")),
+    ("Devices", Some("devices")),
+    ("Profiles", Some("profiles")),
+];
+
+// * No settings file yet means the app has never been set up on this account.
+pub fn is_first_run() -> bool {
+    !config::app_settings_path().exists()
+}
+
+// * Makes sure the chosen start page is actually in the top navigation. Starts from the
+// * automatic layout (Ethernet or Wi-Fi, plus Profiles) and adds the page to it.
+fn show_start_page_module(
+    settings: &mut AppSettings,
+    page: &str,
+    has_wifi: bool,
+    has_ethernet: bool,
+) {
+    let automatic = match page {
+        "ethernet" | "profiles" => has_ethernet || page == "profiles",
+        "wifi" => !has_ethernet && has_wifi,
+        _ => false,
+    };
+    if automatic {
+        return;
+    }
+
+    settings.module_layout_customized = true;
+    settings.show_ethernet_module = has_ethernet || page == "ethernet";
+    settings.show_wifi_module = (!has_ethernet && has_wifi) || page == "wifi";
+    settings.show_hotspot_module = page == "hotspot";
+    settings.show_devices_module = page == "devices";
+    settings.show_profiles_module = true;
+}
+
+#[derive(Clone)]
+struct SetupWizard {
+    window: adw::ApplicationWindow,
+    carousel: adw::Carousel,
+    back_button: gtk4::Button,
+    next_button: gtk4::Button,
+    adapters_group: adw::PreferencesGroup,
+    adapters_placeholder: adw::ActionRow,
+    storage_row: adw::ComboRow,
+    start_page_row: adw::ComboRow,
+    auto_scan_row: adw::SwitchRow,
+    import_group: adw::PreferencesGroup,
+    import_placeholder: adw::ActionRow,
+    import_choice: Rc<RefCell<Option<SavedHotspotProfile>>>,
+    has_wifi: Rc<Cell<bool>>,
+    has_ethernet: Rc<Cell<bool>>,
+}
+
+// * Shows the wizard and calls `on_done` once settings are saved, whether finished or skipped.
+pub fn present(app: &adw::Application, on_done: impl Fn() + 'static) {
+    let wizard = SetupWizard::new(app);
+    let on_done: Rc<dyn Fn()> = Rc::new(on_done);
+    let finished = Rc::new(Cell::new(false));
+
+    let wizard_ref = wizard.clone();
+    let on_done_ref = on_done.clone();
+    let finished_ref = finished.clone();
+    wizard.next_button.connect_clicked(move |_| {
+        let position = wizard_ref.carousel.position().round() as u32;
+        if position + 1 < wizard_ref.carousel.n_pages() {
+            wizard_ref
+                .carousel
+                .scroll_to(&wizard_ref.carousel.nth_page(position + 1), true);
+            return;
+        }
+        wizard_ref.save();
+        finished_ref.set(true);
+        // * Build the main window first so the app never runs out of windows in between
+        on_done_ref();
+        wizard_ref.window.close();
+    });
+
+    // * Closing early keeps the defaults but still counts as set up, so the wizard isn't nagging
+    wizard.window.connect_close_request(move |_| {
+        if !finished.replace(true) {
+            save_defaults();
+            on_done();
+        }
+        glib::Propagation::Proceed
+    });
+
+    wizard.detect();
+    wizard.window.present();
+}
+
+fn save_defaults() {
+    if let Err(e) =
+        config::save_app_settings_sync(&config::app_settings_path(), &AppSettings::default())
+    {
+        log::warn!("Failed to save default app settings: {}", e);
+    }
+}
+
+fn wizard_page(
+    icon: &str,
+    title: &str,
+    description: &str,
+) -> (adw::PreferencesPage, adw::PreferencesGroup) {
+    let page = adw::PreferencesPage::new();
+    page.set_hexpand(true);
+    let header_group = adw::PreferencesGroup::new();
+    let status = adw::StatusPage::builder()
+        .icon_name(icon)
+        .title(title)
+        .description(description)
+        .build();
+    status.add_css_class("compact");
+    header_group.add(&status);
+    page.add(&header_group);
+
+    let group = adw::PreferencesGroup::new();
+    page.add(&group);
+    (page, group)
+}
+
+impl SetupWizard {
+    fn new(app: &adw::Application) -> Self {
+        let window = adw::ApplicationWindow::builder()
+            .application(app)
+            .title("Welcome")
+            .default_width(520)
+            .default_height(620)
+            .build();
+
+        let carousel = adw::Carousel::builder()
+            .allow_scroll_wheel(false)
+            .vexpand(true)
+            .build();
+
+        let (adapters_page, adapters_group) = wizard_page(
+            icon_name(
+                "network-wireless-symbolic",
+                &["network-wireless", "network-workgroup-symbolic"][..],
+            ),
+            "Welcome to Adwaita Network",
+            "A few questions before the first start. Everything can be changed later in Settings.",
+        );
+        adapters_group.set_title("Detected Adapters");
+        let adapters_placeholder = adw::ActionRow::builder()
+            .title("Looking for network hardware...")
+            .build();
+        adapters_group.add(&adapters_placeholder);
+
+        let (storage_page, storage_group) = wizard_page(
+            icon_name("dialog-password-symbolic", &["channel-secure-symbolic"][..]),
+            "Hotspot Password",
+            "Choose where the hotspot password is kept between sessions.",
+        );
+        let storage_row = adw::ComboRow::builder()
+            .title("Store Password In")
+            .model(&gtk4::StringList::new(
+                &["Keyring (recommended)", "NetworkManager"][..],
+            ))
+            .build();
+        storage_group.set_description(Some(
+            "The keyring encrypts the password with your login. NetworkManager keeps it in the hotspot profile, readable by administrators.",
+        ));
+        storage_group.add(&storage_row);
+
+        let (behavior_page, behavior_group) = wizard_page(
+            icon_name(
+                "preferences-system-symbolic",
+                &["emblem-system-symbolic"][..],
+            ),
+            "Start-Up",
+            "Pick what the window shows first and whether it scans on its own.",
+        );
+        let start_page_labels: Vec<&str> = START_PAGES.iter().map(|(label, _)| *label).collect();
+        let start_page_row = adw::ComboRow::builder()
+            .title("Start Page")
+            .model(&gtk4::StringList::new(&start_page_labels[..]))
+            .build();
+        let defaults = AppSettings::default();
+        let auto_scan_row = adw::SwitchRow::builder()
+            .title("Scan Automatically")
+            .subtitle("Refresh the Wi-Fi list in the background")
+            .active(defaults.auto_scan)
+            .build();
+        behavior_group.add(&start_page_row);
+        behavior_group.add(&auto_scan_row);
+
+        let (import_page, import_group) = wizard_page(
+            icon_name(
+                "network-wireless-hotspot-symbolic",
+                &["network-wireless-symbolic"][..],
+            ),
+            "Existing Hotspot",
+            "Hotspots saved by other tools can be reused as this app's hotspot settings.",
+        );
+        let import_placeholder = adw::ActionRow::builder()
+            .title("Looking for saved hotspots...")
+            .build();
+        import_group.add(&import_placeholder);
+
+        carousel.append(&adapters_page);
+        carousel.append(&storage_page);
+        carousel.append(&behavior_page);
+        carousel.append(&import_page);
+
+        let back_button = gtk4::Button::with_label("Back");
+        let next_button = gtk4::Button::builder()
+            .label("Next")
+            .css_classes(vec!["suggested-action".to_string()])
+            .build();
+        let dots = adw::CarouselIndicatorDots::builder()
+            .carousel(&carousel)
+            .hexpand(true)
+            .build();
+        let bottom_bar = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+        bottom_bar.set_margin_top(12);
+        bottom_bar.set_margin_bottom(12);
+        bottom_bar.set_margin_start(12);
+        bottom_bar.set_margin_end(12);
+        bottom_bar.append(&back_button);
+        bottom_bar.append(&dots);
+        bottom_bar.append(&next_button);
+
+        let toolbar_view = adw::ToolbarView::new();
+        toolbar_view.add_top_bar(&adw::HeaderBar::new());
+        toolbar_view.set_content(Some(&carousel));
+        toolbar_view.add_bottom_bar(&bottom_bar);
+        window.set_content(Some(&toolbar_view));
+
+        let wizard = Self {
+            window,
+            carousel: carousel.clone(),
+            back_button: back_button.clone(),
+            next_button,
+            adapters_group,
+            adapters_placeholder,
+            storage_row,
+            start_page_row,
+            auto_scan_row,
+            import_group,
+            import_placeholder,
+            import_choice: Rc::new(RefCell::new(None)),
+            has_wifi: Rc::new(Cell::new(false)),
+            has_ethernet: Rc::new(Cell::new(false)),
+        };
+
+        let wizard_ref = wizard.clone();
+        back_button.connect_clicked(move |_| {
+            let position = wizard_ref.carousel.position().round() as u32;
+            if position > 0 {
+                wizard_ref
+                    .carousel
+                    .scroll_to(&wizard_ref.carousel.nth_page(position - 1), true);
+            }
+        });
+        let wizard_ref = wizard.clone();
+        carousel.connect_position_notify(move |_| wizard_ref.update_buttons());
+        wizard.update_buttons();
+
+        wizard
+    }
+
+    fn update_buttons(&self) {
+        let position = self.carousel.position().round() as u32;
+        self.back_button.set_sensitive(position > 0);
+        self.next_button
+            .set_label(if position + 1 >= self.carousel.n_pages() {
+                "Finish"
+            } else {
+                "Next"
+            });
+    }
+
+    fn detect(&self) {
+        let wizard = self.clone();
+        glib::spawn_future_local(async move {
+            let devices = nm::NetworkManager::get_devices().await.unwrap_or_else(|e| {
+                log::warn!("Failed to list network devices: {}", e);
+                Vec::new()
+            });
+            wizard.show_adapters(&devices);
+
+            let profiles = nm::list_saved_hotspot_profiles().await.unwrap_or_else(|e| {
+                log::warn!("Failed to list saved hotspot profiles: {}", e);
+                Vec::new()
+            });
+            wizard.show_import_choices(profiles);
+        });
+    }
+
+    fn show_adapters(&self, devices: &[nm::Device]) {
+        self.adapters_group.remove(&self.adapters_placeholder);
+
+        let mut found = false;
+        for device in devices {
+            let (kind, icon) = match &device.device_type {
+                nm::DeviceType::Wifi => {
+                    self.has_wifi.set(true);
+                    ("Wi-Fi", "network-wireless-symbolic")
+                }
+                nm::DeviceType::Ethernet => {
+                    self.has_ethernet.set(true);
+                    ("Ethernet", "network-wired-symbolic")
+                }
+                nm::DeviceType::Modem => ("Mobile broadband", "network-cellular-symbolic"),
+                nm::DeviceType::Loopback | nm::DeviceType::Other(_) => continue,
+            };
+            let row = adw::ActionRow::builder()
+                .title(&device.name)
+                .subtitle(kind)
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            self.adapters_group.add(&row);
+            found = true;
+        }
+        if !found {
+            self.adapters_group.add(
+                &adw::ActionRow::builder()
+                    .title("No Wi-Fi or Ethernet adapters found")
+                    .subtitle("Network features will appear once one is connected")
+                    .build(),
+            );
+        }
+        if self.has_wifi.get() {
+            self.adapters_group.set_description(Some(
+                "This computer can join Wi-Fi networks and share a hotspot.",
+            ));
+        }
+    }
+
+    fn show_import_choices(&self, profiles: Vec<SavedHotspotProfile>) {
+        self.import_group.remove(&self.import_placeholder);
+
+        let skip_check = gtk4::CheckButton::new();
+        skip_check.set_active(true);
+        let skip_row = adw::ActionRow::builder()
+            .title("Don't Import")
+            .subtitle(if profiles.is_empty() {
+                "No saved hotspots were found"
+            } else {
+                "Start with fresh hotspot settings"
+            })
+            .activatable_widget(&skip_check)
+            .build();
+        skip_row.add_prefix(&skip_check);
+        self.import_group.add(&skip_row);
+        let choice = self.import_choice.clone();
+        skip_check.connect_toggled(move |check| {
+            if check.is_active() {
+                choice.borrow_mut().take();
+            }
+        });
+
+        for profile in profiles {
+            let check = gtk4::CheckButton::new();
+            check.set_group(Some(&skip_check));
+            let subtitle = match profile.password {
+                Some(_) => format!("{} • {}", profile.ssid, profile.band),
+                None => format!(
+                    "{} • {} • password not readable",
+                    profile.ssid, profile.band
+                ),
+            };
+            let row = adw::ActionRow::builder()
+                .title(&profile.id)
+                .subtitle(subtitle)
+                .activatable_widget(&check)
+                .build();
+            row.add_prefix(&check);
+            self.import_group.add(&row);
+
+            let choice = self.import_choice.clone();
+            check.connect_toggled(move |check| {
+                if check.is_active() {
+                    *choice.borrow_mut() = Some(profile.clone());
+                }
+            });
+        }
+    }
+
+    fn save(&self) {
+        let mut settings = AppSettings {
+            hotspot_password_storage: if self.storage_row.selected() == 1 {
+                HotspotPasswordStorage::NetworkManager
+            } else {
+                HotspotPasswordStorage::Keyring
+            },
+            auto_scan: self.auto_scan_row.is_active(),
+            ..AppSettings::default()
+        };
+        if let Some((_, Some(page))) = START_PAGES.get(self.start_page_row.selected() as usize) {
+            settings.start_page = Some(page.to_string());
+            show_start_page_module(
+                &mut settings,
+                page,
+                self.has_wifi.get(),
+                self.has_ethernet.get(),
+            );
+        }
+        if let Err(e) = config::save_app_settings_sync(&config::app_settings_path(), &settings) {
+            log::warn!("Failed to save app settings: {}", e);
+        }
+
+        if let Some(profile) = self.import_choice.borrow().as_ref() {
+            import_hotspot(profile, &settings.hotspot_password_storage);
+        }
+    }
+}
+
+fn import_hotspot(profile: &SavedHotspotProfile, storage: &HotspotPasswordStorage) {
+    let password = profile.password.clone().unwrap_or_default();
+    let config = HotspotConfig {
+        ssid: profile.ssid.clone(),
+        band: profile.band.clone(),
+        hidden: profile.hidden,
+        ..HotspotConfig::default()
+    };
+    // * Neither wizard storage choice keeps the password in hotspot.json; with NetworkManager
+    // * storage the hotspot page recovers it from the keyring backup on first start
+    if !password.is_empty() {
+        if *storage == HotspotPasswordStorage::Keyring {
+            if let Err(e) = secrets::store_hotspot_password(&password) {
+                log::warn!("Failed to store imported hotspot password: {}", e);
+            }
+        }
+        if let Err(e) = secrets::store_hotspot_password_backup(&password) {
+            log::warn!("Failed to back up imported hotspot password: {}", e);
+        }
+    }
+    if let Err(e) = config::save_config_sync(&config::hotspot_config_path(), &config) {
+        log::warn!("Failed to import hotspot {}: {}", profile.id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_page_module_is_added_to_automatic_layout() {
+        let mut settings = AppSettings::default();
+        show_start_page_module(&mut settings, "wifi", true, false);
+        assert!(!settings.module_layout_customized);
+
+        show_start_page_module(&mut settings, "hotspot", true, true);
+        assert!(settings.module_layout_customized);
+        assert!(settings.show_hotspot_module);
+        assert!(settings.show_ethernet_module);
+        assert!(settings.show_profiles_module);
+        assert!(!settings.show_wifi_module);
+        assert!(!settings.show_devices_module);
+    }
+}
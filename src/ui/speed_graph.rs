@@ -0,0 +1,117 @@
+// * ./src/ui/speed_graph.rs
+
+use gtk4::prelude::*;
+use gtk4::{cairo, gdk};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+// * One sample per second from the traffic monitor
+const HISTORY_LEN: usize = 60;
+const GRAPH_HEIGHT: i32 = 56;
+
+// * Scrolling throughput graph: filled download area with the upload line on top.
+#[derive(Clone)]
+pub struct SpeedGraph {
+    pub widget: gtk4::DrawingArea,
+    samples: Rc<RefCell<VecDeque<(u64, u64)>>>,
+}
+
+impl SpeedGraph {
+    pub fn new() -> Self {
+        let widget = gtk4::DrawingArea::builder()
+            .content_height(GRAPH_HEIGHT)
+            .hexpand(true)
+            .build();
+        let samples = Rc::new(RefCell::new(VecDeque::with_capacity(HISTORY_LEN)));
+
+        let samples_ref = samples.clone();
+        widget.set_draw_func(move |area, cr, width, height| {
+            draw(area, cr, width as f64, height as f64, &samples_ref.borrow());
+        });
+
+        Self { widget, samples }
+    }
+
+    pub fn push(&self, down_bytes_per_sec: u64, up_bytes_per_sec: u64) {
+        {
+            let mut samples = self.samples.borrow_mut();
+            if samples.len() == HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back((down_bytes_per_sec, up_bytes_per_sec));
+        }
+        if self.widget.is_mapped() {
+            self.widget.queue_draw();
+        }
+    }
+
+    pub fn clear(&self) {
+        self.samples.borrow_mut().clear();
+        self.widget.queue_draw();
+    }
+}
+
+impl Default for SpeedGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn draw(
+    area: &gtk4::DrawingArea,
+    cr: &cairo::Context,
+    width: f64,
+    height: f64,
+    samples: &VecDeque<(u64, u64)>,
+) {
+    let foreground = area.color();
+    set_source(cr, &foreground, 0.15);
+    cr.set_line_width(1.0);
+    cr.move_to(0.0, height - 0.5);
+    cr.line_to(width, height - 0.5);
+    let _ = cr.stroke();
+
+    if samples.len() < 2 {
+        return;
+    }
+    let max = samples
+        .iter()
+        .map(|(down, up)| *down.max(up))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    // * Newest sample on the right edge; the graph fills in from there
+    let step = width / (HISTORY_LEN - 1) as f64;
+    let x_of = |index: usize| width - (samples.len() - 1 - index) as f64 * step;
+    let y_of = |value: u64| height - (value as f64 / max) * (height - 2.0);
+
+    let accent = adw::StyleManager::default().accent_color_rgba();
+    cr.move_to(x_of(0), height);
+    for (index, (down, _)) in samples.iter().enumerate() {
+        cr.line_to(x_of(index), y_of(*down));
+    }
+    cr.line_to(x_of(samples.len() - 1), height);
+    cr.close_path();
+    set_source(cr, &accent, 0.35);
+    let _ = cr.fill_preserve();
+    set_source(cr, &accent, 1.0);
+    cr.set_line_width(1.5);
+    let _ = cr.stroke();
+
+    for (index, (_, up)) in samples.iter().enumerate() {
+        cr.line_to(x_of(index), y_of(*up));
+    }
+    set_source(cr, &foreground, 0.55);
+    let _ = cr.stroke();
+}
+
+fn set_source(cr: &cairo::Context, color: &gdk::RGBA, alpha: f32) {
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        (color.alpha() * alpha) as f64,
+    );
+}
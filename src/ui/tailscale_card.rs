@@ -0,0 +1,177 @@
+// * ./src/ui/tailscale_card.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::tailscale::{self, ExitNode, TailscaleStatus};
+use crate::ui::common;
+
+// * Shown on the VPN page only while tailscaled is reachable through the `tailscale` CLI.
+pub struct TailscaleCard {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    connected_row: adw::SwitchRow,
+    node_row: adw::ActionRow,
+    address_row: adw::ActionRow,
+    exit_node_row: adw::ComboRow,
+    exit_nodes: Rc<RefCell<Vec<ExitNode>>>,
+    // * Set while rows are filled from a status so the change handlers don't fire commands
+    updating: Rc<Cell<bool>>,
+}
+
+impl Clone for TailscaleCard {
+    fn clone(&self) -> Self {
+        Self {
+            widget: self.widget.clone(),
+            toast_overlay: self.toast_overlay.clone(),
+            connected_row: self.connected_row.clone(),
+            node_row: self.node_row.clone(),
+            address_row: self.address_row.clone(),
+            exit_node_row: self.exit_node_row.clone(),
+            exit_nodes: self.exit_nodes.clone(),
+            updating: self.updating.clone(),
+        }
+    }
+}
+
+impl TailscaleCard {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let widget = adw::PreferencesGroup::builder().title("Tailscale").build();
+        widget.set_visible(false);
+
+        let connected_row = adw::SwitchRow::builder().title("Connected").build();
+        let property_row = |title: &str| {
+            let row = adw::ActionRow::builder().title(title).build();
+            row.add_css_class("property");
+            row
+        };
+        let node_row = property_row("Node");
+        let address_row = property_row("Address");
+        let exit_node_row = adw::ComboRow::builder()
+            .title("Exit Node")
+            .subtitle("Send all traffic through another device in the tailnet")
+            .model(&gtk4::StringList::new(&["None"][..]))
+            .build();
+
+        widget.add(&connected_row);
+        widget.add(&node_row);
+        widget.add(&address_row);
+        widget.add(&exit_node_row);
+
+        let card = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            connected_row: connected_row.clone(),
+            node_row,
+            address_row,
+            exit_node_row: exit_node_row.clone(),
+            exit_nodes: Rc::new(RefCell::new(Vec::new())),
+            updating: Rc::new(Cell::new(false)),
+        };
+
+        let card_ref = card.clone();
+        connected_row.connect_active_notify(move |row| {
+            if card_ref.updating.get() {
+                return;
+            }
+            let card = card_ref.clone();
+            let up = row.is_active();
+            glib::spawn_future_local(async move {
+                if let Err(e) = tailscale::set_up(up).await {
+                    log::error!(
+                        "Failed to switch Tailscale {}: {}",
+                        if up { "up" } else { "down" },
+                        e
+                    );
+                    common::show_toast(&card.toast_overlay, &format!("Tailscale: {}", e));
+                }
+                card.refresh().await;
+            });
+        });
+
+        let card_ref = card.clone();
+        exit_node_row.connect_selected_notify(move |row| {
+            if card_ref.updating.get() {
+                return;
+            }
+            let card = card_ref.clone();
+            // * Index 0 is "None"; the rest follow the exit node list
+            let address = (row.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| card.exit_nodes.borrow().get(index).cloned())
+                .map(|node| node.address);
+            glib::spawn_future_local(async move {
+                if let Err(e) = tailscale::set_exit_node(address.as_deref()).await {
+                    log::error!("Failed to set Tailscale exit node: {}", e);
+                    common::show_toast(&card.toast_overlay, &format!("Tailscale: {}", e));
+                }
+                card.refresh().await;
+            });
+        });
+
+        card
+    }
+
+    pub async fn refresh(&self) {
+        match tailscale::status().await {
+            Some(status) => self.show_status(&status),
+            None => self.widget.set_visible(false),
+        }
+    }
+
+    fn show_status(&self, status: &TailscaleStatus) {
+        self.updating.set(true);
+
+        let up = status.is_up();
+        self.connected_row.set_active(up);
+        self.connected_row
+            .set_subtitle(match status.backend_state.as_str() {
+                "Running" => "",
+                "NeedsLogin" => "Log in with \"tailscale up\" first",
+                "Stopped" => "Disconnected from the tailnet",
+                other => other,
+            });
+
+        self.node_row.set_subtitle(if status.node_name.is_empty() {
+            "Unknown"
+        } else {
+            &status.node_name
+        });
+        self.address_row
+            .set_subtitle(&if status.addresses.is_empty() {
+                "None".to_string()
+            } else {
+                status.addresses.join(", ")
+            });
+        self.node_row.set_visible(up);
+        self.address_row.set_visible(up);
+
+        let mut labels = vec!["None".to_string()];
+        labels.extend(status.exit_nodes.iter().map(|node| {
+            if node.online {
+                node.name.clone()
+            } else {
+                format!("{} (offline)", node.name)
+            }
+        }));
+        let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+        self.exit_node_row
+            .set_model(Some(&gtk4::StringList::new(&label_refs)));
+        let selected = status
+            .exit_node
+            .as_deref()
+            .and_then(|name| status.exit_nodes.iter().position(|node| node.name == name))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        self.exit_node_row.set_selected(selected as u32);
+        self.exit_node_row
+            .set_sensitive(up && !status.exit_nodes.is_empty());
+        *self.exit_nodes.borrow_mut() = status.exit_nodes.clone();
+
+        self.widget.set_visible(true);
+        self.updating.set(false);
+    }
+}
@@ -0,0 +1,232 @@
+// * ./src/ui/usage_page.rs
+
+use gtk4::prelude::*;
+use gtk4::{cairo, gdk};
+use libadwaita::{self as adw, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::ui::common;
+use crate::usage::{self, UsageBucket, UsagePeriod, UsageRecorder};
+
+const CHART_HEIGHT: i32 = 180;
+const LABEL_SPACE: f64 = 18.0;
+
+// * Chart column: label, download bytes, upload bytes
+type Bar = (String, u64, u64);
+
+// * Daily, weekly and monthly data usage from the persisted history.
+#[derive(Clone)]
+pub struct UsagePage {
+    pub widget: adw::PreferencesPage,
+    recorder: UsageRecorder,
+    period: Rc<Cell<UsagePeriod>>,
+    bars: Rc<RefCell<Vec<Bar>>>,
+    chart: gtk4::DrawingArea,
+    download_row: adw::ActionRow,
+    upload_row: adw::ActionRow,
+    networks_group: adw::PreferencesGroup,
+    network_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+}
+
+impl UsagePage {
+    pub fn new(recorder: &UsageRecorder) -> Self {
+        let widget = adw::PreferencesPage::new();
+
+        let chart_group = adw::PreferencesGroup::builder()
+            .title("Data Usage")
+            .description("Download (solid) and upload (light) per period. Only traffic seen while the app was running is counted.")
+            .build();
+        let period_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        period_box.add_css_class("linked");
+        period_box.set_valign(gtk4::Align::Center);
+        chart_group.set_header_suffix(Some(&period_box));
+
+        let chart = gtk4::DrawingArea::builder()
+            .content_height(CHART_HEIGHT)
+            .hexpand(true)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        let chart_frame = gtk4::Frame::new(None);
+        chart_frame.add_css_class("card");
+        chart_frame.set_child(Some(&chart));
+        chart_group.add(&chart_frame);
+
+        let totals_group = adw::PreferencesGroup::builder().title("Total").build();
+        let property_row = |title: &str| {
+            let row = adw::ActionRow::builder().title(title).build();
+            row.add_css_class("property");
+            totals_group.add(&row);
+            row
+        };
+        let download_row = property_row("Downloaded");
+        let upload_row = property_row("Uploaded");
+
+        let networks_group = adw::PreferencesGroup::builder().title("By Network").build();
+
+        widget.add(&chart_group);
+        widget.add(&totals_group);
+        widget.add(&networks_group);
+
+        let page = Self {
+            widget,
+            recorder: recorder.clone(),
+            period: Rc::new(Cell::new(UsagePeriod::Day)),
+            bars: Rc::new(RefCell::new(Vec::new())),
+            chart: chart.clone(),
+            download_row,
+            upload_row,
+            networks_group,
+            network_rows: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        let mut first_button: Option<gtk4::ToggleButton> = None;
+        for (label, period) in [
+            ("Day", UsagePeriod::Day),
+            ("Week", UsagePeriod::Week),
+            ("Month", UsagePeriod::Month),
+        ] {
+            let button = gtk4::ToggleButton::with_label(label);
+            button.set_group(first_button.as_ref());
+            let page_ref = page.clone();
+            button.connect_toggled(move |button| {
+                if button.is_active() {
+                    page_ref.period.set(period);
+                    page_ref.refresh();
+                }
+            });
+            period_box.append(&button);
+            first_button.get_or_insert(button);
+        }
+        if let Some(button) = first_button {
+            button.set_active(true);
+        }
+
+        let bars = page.bars.clone();
+        chart.set_draw_func(move |area, cr, width, height| {
+            draw_chart(area, cr, width as f64, height as f64, &bars.borrow());
+        });
+
+        page.refresh();
+        page
+    }
+
+    pub fn refresh(&self) {
+        let history = self.recorder.history();
+        let buckets = usage::buckets(self.period.get(), usage::today());
+        let totals = history.totals(&buckets);
+        let (rx, tx) = totals
+            .iter()
+            .fold((0, 0), |(rx, tx), (down, up)| (rx + down, tx + up));
+        *self.bars.borrow_mut() = buckets
+            .iter()
+            .zip(&totals)
+            .map(|(bucket, (down, up))| (bucket.label.clone(), *down, *up))
+            .collect();
+        self.chart.queue_draw();
+
+        self.download_row.set_subtitle(&common::format_bytes(rx));
+        self.upload_row.set_subtitle(&common::format_bytes(tx));
+
+        for row in self.network_rows.borrow_mut().drain(..) {
+            self.networks_group.remove(&row);
+        }
+        let (Some(first), Some(last)) = (buckets.first(), buckets.last()) else {
+            return;
+        };
+        let range = UsageBucket {
+            label: String::new(),
+            start: first.start,
+            end: last.end,
+        };
+        let networks = history.totals_by_network(&range);
+        if networks.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title("No usage recorded yet")
+                .build();
+            self.networks_group.add(&row);
+            self.network_rows.borrow_mut().push(row);
+        }
+        for (name, down, up) in networks {
+            let row = adw::ActionRow::builder()
+                .title(&name)
+                .subtitle(format!(
+                    "↓ {} • ↑ {}",
+                    common::format_bytes(down),
+                    common::format_bytes(up)
+                ))
+                .build();
+            self.networks_group.add(&row);
+            self.network_rows.borrow_mut().push(row);
+        }
+    }
+}
+
+fn draw_chart(
+    area: &gtk4::DrawingArea,
+    cr: &cairo::Context,
+    width: f64,
+    height: f64,
+    bars: &[Bar],
+) {
+    if bars.is_empty() {
+        return;
+    }
+    let foreground = area.color();
+    let accent = adw::StyleManager::default().accent_color_rgba();
+    let max = bars
+        .iter()
+        .map(|(_, down, up)| down + up)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let plot_height = (height - LABEL_SPACE).max(1.0);
+    let slot = width / bars.len() as f64;
+    let bar_width = (slot * 0.6).max(2.0);
+
+    cr.set_font_size(10.0);
+    for (index, (label, down, up)) in bars.iter().enumerate() {
+        let x = slot * index as f64 + (slot - bar_width) / 2.0;
+        let down_height = plot_height * *down as f64 / max;
+        let up_height = plot_height * *up as f64 / max;
+
+        set_source(cr, &accent, 1.0);
+        cr.rectangle(x, plot_height - down_height, bar_width, down_height);
+        let _ = cr.fill();
+        set_source(cr, &accent, 0.45);
+        cr.rectangle(
+            x,
+            plot_height - down_height - up_height,
+            bar_width,
+            up_height,
+        );
+        let _ = cr.fill();
+
+        // * Skip labels that would overlap on narrow windows
+        let Ok(extents) = cr.text_extents(label) else {
+            continue;
+        };
+        let step = ((extents.width() + 4.0) / slot).ceil().max(1.0) as usize;
+        if index % step != 0 && index != bars.len() - 1 {
+            continue;
+        }
+        set_source(cr, &foreground, 0.6);
+        cr.move_to(
+            slot * index as f64 + (slot - extents.width()) / 2.0,
+            height - 4.0,
+        );
+        let _ = cr.show_text(label);
+    }
+}
+
+fn set_source(cr: &cairo::Context, color: &gdk::RGBA, alpha: f32) {
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        (color.alpha() * alpha) as f64,
+    );
+}
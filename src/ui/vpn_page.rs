@@ -0,0 +1,902 @@
+// * ./src/ui/vpn_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::nm::{self, OpenVpnConnectionConfig, VpnConnection, VpnKind, WireGuardConnectionConfig};
+use crate::polling::PollScheduler;
+use crate::profiles;
+use crate::ui::tailscale_card::TailscaleCard;
+use crate::ui::{common, icon_name};
+use crate::wireguard::{self, TunnelStats};
+
+// * Detail rows under an active WireGuard tunnel, refreshed in place by the poll scheduler.
+#[derive(Clone)]
+struct TunnelStatsRows {
+    endpoint: adw::ActionRow,
+    handshake: adw::ActionRow,
+    transfer: adw::ActionRow,
+}
+
+impl TunnelStatsRows {
+    fn new() -> Self {
+        let row = |title: &str| {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle("Loading...")
+                .build();
+            row.add_css_class("property");
+            row
+        };
+        Self {
+            endpoint: row("Endpoint"),
+            handshake: row("Latest Handshake"),
+            transfer: row("Transfer"),
+        }
+    }
+
+    fn update(&self, stats: &TunnelStats, now_secs: u64) {
+        self.endpoint
+            .set_subtitle(stats.endpoint.as_deref().unwrap_or("Unknown"));
+        if stats.handshake_known {
+            self.handshake.set_subtitle(&wireguard::format_handshake(
+                stats.latest_handshake,
+                now_secs,
+            ));
+        } else {
+            self.handshake
+                .set_subtitle("Unavailable (reading it needs the wg tool with root access)");
+        }
+        self.transfer.set_subtitle(&format!(
+            "↓ {} • ↑ {}",
+            common::format_bytes(stats.rx_bytes),
+            common::format_bytes(stats.tx_bytes)
+        ));
+    }
+}
+
+// * WireGuard and OpenVPN connections: create, import, toggle and live tunnel stats, plus Tailscale.
+pub struct VpnPage {
+    pub widget: gtk4::Box,
+    toast_overlay: adw::ToastOverlay,
+    vpn_list_box: gtk4::ListBox,
+    vpn_empty_state: adw::StatusPage,
+    new_vpn_button: gtk4::Button,
+    import_vpn_button: gtk4::Button,
+    vpn_connections: Rc<RefCell<Vec<VpnConnection>>>,
+    stats_rows: Rc<RefCell<Vec<(String, TunnelStatsRows)>>>,
+    tailscale_card: TailscaleCard,
+}
+
+impl Clone for VpnPage {
+    fn clone(&self) -> Self {
+        Self {
+            widget: self.widget.clone(),
+            toast_overlay: self.toast_overlay.clone(),
+            vpn_list_box: self.vpn_list_box.clone(),
+            vpn_empty_state: self.vpn_empty_state.clone(),
+            new_vpn_button: self.new_vpn_button.clone(),
+            import_vpn_button: self.import_vpn_button.clone(),
+            vpn_connections: self.vpn_connections.clone(),
+            stats_rows: self.stats_rows.clone(),
+            tailscale_card: self.tailscale_card.clone(),
+        }
+    }
+}
+
+impl VpnPage {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let widget = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+
+        let header = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+        let vpn_title = gtk4::Label::builder()
+            .label("VPN Connections")
+            .halign(gtk4::Align::Start)
+            .hexpand(true)
+            .build();
+        vpn_title.add_css_class("heading");
+
+        let new_vpn_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "network-vpn-symbolic",
+                &[
+                    "network-wireless-encrypted-symbolic",
+                    "network-workgroup-symbolic",
+                ][..],
+            ))
+            .tooltip_text("Create VPN")
+            .css_classes(vec!["flat".to_string(), "circular".to_string()])
+            .build();
+
+        let import_vpn_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "document-open-symbolic",
+                &["folder-open-symbolic", "folder-symbolic"][..],
+            ))
+            .tooltip_text("Import VPN")
+            .css_classes(vec!["flat".to_string(), "circular".to_string()])
+            .build();
+
+        header.append(&vpn_title);
+        header.append(&new_vpn_button);
+        header.append(&import_vpn_button);
+        widget.append(&header);
+
+        let vpn_list_box = gtk4::ListBox::builder()
+            .css_classes(vec!["boxed-list".to_string()])
+            .selection_mode(gtk4::SelectionMode::None)
+            .build();
+        vpn_list_box.set_visible(false);
+
+        let vpn_empty_state = adw::StatusPage::builder()
+            .icon_name(icon_name(
+                "network-vpn-symbolic",
+                &[
+                    "network-wireless-encrypted-symbolic",
+                    "network-workgroup-symbolic",
+                ][..],
+            ))
+            .title("No supported VPN connections")
+            .description("Create a WireGuard VPN or import an OpenVPN/WireGuard profile")
+            .build();
+        vpn_empty_state.set_visible(true);
+
+        widget.append(&vpn_list_box);
+        widget.append(&vpn_empty_state);
+
+        let tailscale_card = TailscaleCard::new(toast_overlay);
+        widget.append(&tailscale_card.widget);
+
+        let page = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            vpn_list_box,
+            vpn_empty_state,
+            new_vpn_button: new_vpn_button.clone(),
+            import_vpn_button: import_vpn_button.clone(),
+            vpn_connections: Rc::new(RefCell::new(Vec::new())),
+            stats_rows: Rc::new(RefCell::new(Vec::new())),
+            tailscale_card,
+        };
+
+        let page_ref = page.clone();
+        new_vpn_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.create_vpn().await;
+            });
+        });
+
+        let page_ref = page.clone();
+        import_vpn_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.import_vpn().await;
+            });
+        });
+
+        page
+    }
+
+    // * Stats only matter while someone is looking at an active tunnel.
+    pub fn attach_poll_scheduler(&self, scheduler: &PollScheduler) {
+        let page_ref = self.clone();
+        scheduler.subscribe(
+            "vpn-stats",
+            Duration::from_secs(5),
+            Duration::from_secs(10),
+            move || {
+                if !page_ref.widget.is_mapped() || page_ref.stats_rows.borrow().is_empty() {
+                    return;
+                }
+                let page = page_ref.clone();
+                glib::spawn_future_local(async move {
+                    page.refresh_stats().await;
+                });
+            },
+        );
+    }
+
+    pub async fn refresh(&self) {
+        self.new_vpn_button.set_sensitive(false);
+        self.import_vpn_button.set_sensitive(false);
+        self.vpn_list_box.add_css_class("list-loading");
+
+        match nm::list_supported_vpn_connections().await {
+            Ok(loaded) => {
+                if let Ok(mut vpn_state) = self.vpn_connections.try_borrow_mut() {
+                    *vpn_state = loaded.clone();
+                    drop(vpn_state);
+                    self.populate_vpns(loaded);
+                } else {
+                    log::error!("Borrow conflict in UI state");
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to load VPN connections: {}", e);
+                self.show_toast(&format!("Failed to load VPN connections: {}", e));
+                self.populate_vpns(Vec::new());
+            }
+        }
+
+        self.vpn_list_box.remove_css_class("list-loading");
+        self.new_vpn_button.set_sensitive(true);
+        self.import_vpn_button.set_sensitive(true);
+        self.refresh_stats().await;
+        self.tailscale_card.refresh().await;
+    }
+
+    fn populate_vpns(&self, vpn_connections: Vec<VpnConnection>) {
+        while let Some(child) = self.vpn_list_box.first_child() {
+            self.vpn_list_box.remove(&child);
+        }
+        self.stats_rows.borrow_mut().clear();
+
+        if vpn_connections.is_empty() {
+            self.vpn_list_box.set_visible(false);
+            self.vpn_empty_state.set_visible(true);
+            return;
+        }
+
+        for vpn in vpn_connections {
+            self.vpn_list_box.append(&self.create_vpn_row(&vpn));
+        }
+
+        self.vpn_empty_state.set_visible(false);
+        self.vpn_list_box.set_visible(true);
+    }
+
+    async fn refresh_stats(&self) {
+        let tracked = self.stats_rows.borrow().clone();
+        if tracked.is_empty() {
+            return;
+        }
+        let now_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        for (uuid, rows) in tracked {
+            match nm::get_wireguard_stats(&uuid).await {
+                Ok(stats) => rows.update(&stats, now_secs),
+                Err(e) => log::debug!("WireGuard stats for {} unavailable: {}", uuid, e),
+            }
+        }
+    }
+
+    fn create_vpn_row(&self, vpn: &VpnConnection) -> gtk4::Widget {
+        let subtitle = if vpn.active {
+            format!("{} • Connected", vpn.kind.label())
+        } else {
+            format!("{} • Disconnected", vpn.kind.label())
+        };
+
+        let icon = gtk4::Image::from_icon_name(vpn_icon_name(vpn.kind));
+        icon.set_pixel_size(22);
+        let (actions, toggle_btn) = self.create_vpn_actions(vpn);
+
+        if vpn.active && vpn.kind == VpnKind::WireGuard {
+            let row = adw::ExpanderRow::builder()
+                .title(&vpn.name)
+                .subtitle(subtitle)
+                .build();
+            row.add_prefix(&icon);
+            row.add_suffix(&actions);
+
+            let stats = TunnelStatsRows::new();
+            row.add_row(&stats.endpoint);
+            row.add_row(&stats.handshake);
+            row.add_row(&stats.transfer);
+            self.stats_rows.borrow_mut().push((vpn.uuid.clone(), stats));
+            return row.upcast();
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(&vpn.name)
+            .subtitle(subtitle)
+            .build();
+        row.add_prefix(&icon);
+        row.add_suffix(&actions);
+        row.set_activatable_widget(Some(&toggle_btn));
+        row.upcast()
+    }
+
+    fn create_vpn_actions(&self, vpn: &VpnConnection) -> (gtk4::Box, gtk4::Button) {
+        let actions = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+
+        let toggle_btn = gtk4::Button::builder()
+            .label(if vpn.active { "Disconnect" } else { "Connect" })
+            .tooltip_text(if vpn.active {
+                "Disconnect VPN"
+            } else {
+                "Connect VPN"
+            })
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let edit_btn = gtk4::Button::builder()
+            .label("Edit")
+            .tooltip_text("Edit VPN")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+
+        let delete_btn = gtk4::Button::builder()
+            .label("Delete")
+            .tooltip_text("Delete VPN")
+            .css_classes(vec!["flat".to_string(), "destructive-action".to_string()])
+            .build();
+
+        actions.append(&toggle_btn);
+        actions.append(&edit_btn);
+        actions.append(&delete_btn);
+
+        let page_toggle = self.clone();
+        let vpn_uuid = vpn.uuid.clone();
+        let vpn_name = vpn.name.clone();
+        let vpn_active = vpn.active;
+        toggle_btn.connect_clicked(move |_| {
+            let page = page_toggle.clone();
+            let vpn_uuid = vpn_uuid.clone();
+            let vpn_name = vpn_name.clone();
+            glib::spawn_future_local(async move {
+                page.toggle_vpn(&vpn_uuid, &vpn_name, vpn_active).await;
+            });
+        });
+
+        let page_edit = self.clone();
+        let vpn_uuid = vpn.uuid.clone();
+        let vpn_kind = vpn.kind;
+        edit_btn.connect_clicked(move |_| {
+            let page = page_edit.clone();
+            let vpn_uuid = vpn_uuid.clone();
+            glib::spawn_future_local(async move {
+                page.edit_vpn(&vpn_uuid, vpn_kind).await;
+            });
+        });
+
+        let page_delete = self.clone();
+        let vpn_uuid = vpn.uuid.clone();
+        let vpn_name = vpn.name.clone();
+        delete_btn.connect_clicked(move |_| {
+            let page = page_delete.clone();
+            let vpn_uuid = vpn_uuid.clone();
+            let vpn_name = vpn_name.clone();
+            glib::spawn_future_local(async move {
+                page.delete_vpn(&vpn_uuid, &vpn_name).await;
+            });
+        });
+
+        (actions, toggle_btn)
+    }
+
+    async fn create_vpn(&self) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Create VPN")
+            .body("Choose how you want to create the VPN connection")
+            .default_response("wireguard")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[
+            ("cancel", "Cancel"),
+            ("wireguard", "WireGuard"),
+            ("openvpn", "OpenVPN"),
+        ]);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        match response.as_str() {
+            "wireguard" => match self.show_wireguard_editor(None).await {
+                Ok(Some(config)) => match nm::create_wireguard_connection(&config).await {
+                    Ok(_) => {
+                        self.show_toast("WireGuard VPN created");
+                        self.refresh().await;
+                    }
+                    Err(e) => {
+                        log::error!("Failed to create WireGuard VPN: {}", e);
+                        self.show_toast(&format!("Failed to create WireGuard VPN: {}", e));
+                    }
+                },
+                Ok(None) => {}
+                Err(e) => {
+                    log::error!("Failed to open WireGuard editor: {}", e);
+                    self.show_toast(&format!("Failed to create WireGuard VPN: {}", e));
+                }
+            },
+            "openvpn" => match self.show_openvpn_editor(None).await {
+                Ok(Some(config)) => match nm::create_openvpn_connection(&config).await {
+                    Ok(_) => {
+                        self.show_toast("OpenVPN profile created");
+                        self.refresh().await;
+                    }
+                    Err(e) => {
+                        log::error!("Failed to create OpenVPN profile: {}", e);
+                        let message = if nm::is_vpn_plugin_missing_error(&e.to_string()) {
+                            "OpenVPN plugin is missing. Install the NetworkManager OpenVPN plugin and try again.".to_string()
+                        } else {
+                            format!("Failed to create OpenVPN profile: {}", e)
+                        };
+                        self.show_toast(&message);
+                    }
+                },
+                Ok(None) => {}
+                Err(e) => {
+                    log::error!("Failed to open OpenVPN editor: {}", e);
+                    self.show_toast(&format!("Failed to create OpenVPN profile: {}", e));
+                }
+            },
+            _ => {}
+        }
+    }
+
+    async fn import_vpn(&self) {
+        match self.choose_import_file().await {
+            Ok(Some(path)) => match nm::import_vpn_connection(&path).await {
+                Ok(_) => {
+                    self.show_toast("VPN imported");
+                    self.refresh().await;
+                }
+                Err(e) => {
+                    log::error!("Failed to import VPN: {}", e);
+                    if nm::NmError::is_permission_denied(&e) {
+                        common::show_error(&self.toast_overlay, "Failed to import VPN", &e);
+                        return;
+                    }
+                    let message = if nm::is_vpn_plugin_missing_error(&e.to_string()) {
+                        "OpenVPN plugin is missing. Install the NetworkManager OpenVPN plugin and try again.".to_string()
+                    } else {
+                        format!("Failed to import VPN: {}", e)
+                    };
+                    self.show_toast(&message);
+                }
+            },
+            Ok(None) => {}
+            Err(e) => {
+                log::error!("Failed to open import dialog: {}", e);
+                self.show_toast(&format!("Failed to import VPN: {}", e));
+            }
+        }
+    }
+
+    async fn toggle_vpn(&self, uuid: &str, name: &str, active: bool) {
+        let result = if active {
+            nm::deactivate_vpn_connection(uuid).await
+        } else {
+            nm::activate_vpn_connection(uuid).await
+        };
+
+        match result {
+            Ok(()) => {
+                let message = if active {
+                    format!("Disconnected VPN: {}", name)
+                } else {
+                    format!("Connected VPN: {}", name)
+                };
+                self.show_toast(&message);
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to toggle VPN {}: {}", name, e);
+                self.show_toast(&format!("Failed to update VPN \"{}\": {}", name, e));
+            }
+        }
+    }
+
+    async fn edit_vpn(&self, uuid: &str, kind: VpnKind) {
+        match kind {
+            VpnKind::WireGuard => match nm::get_wireguard_connection_config(uuid).await {
+                Ok(existing) => match self.show_wireguard_editor(Some(existing)).await {
+                    Ok(Some(updated)) => {
+                        match nm::update_wireguard_connection(uuid, &updated).await {
+                            Ok(new_uuid_text) => {
+                                if new_uuid_text != uuid {
+                                    let old_uuid = Uuid::parse_str(uuid);
+                                    let new_uuid = Uuid::parse_str(&new_uuid_text);
+                                    if let (Ok(old_uuid), Ok(new_uuid)) = (old_uuid, new_uuid) {
+                                        let _ = profiles::replace_connection_uuid_in_store(
+                                            profiles::profiles_path(),
+                                            old_uuid,
+                                            new_uuid,
+                                        )
+                                        .await;
+                                    }
+                                }
+                                self.show_toast("WireGuard VPN updated");
+                                self.refresh().await;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to update WireGuard VPN: {}", e);
+                                self.show_toast(&format!("Failed to update WireGuard VPN: {}", e));
+                            }
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        log::error!("Failed to open WireGuard editor: {}", e);
+                        self.show_toast(&format!("Failed to edit WireGuard VPN: {}", e));
+                    }
+                },
+                Err(e) => {
+                    log::error!("Failed to load WireGuard VPN: {}", e);
+                    self.show_toast(&format!("Failed to load WireGuard VPN: {}", e));
+                }
+            },
+            VpnKind::OpenVpn => match nm::get_openvpn_connection_config(uuid).await {
+                Ok(existing) => match self.show_openvpn_editor(Some(existing)).await {
+                    Ok(Some(updated)) => {
+                        match nm::update_openvpn_connection(uuid, &updated).await {
+                            Ok(()) => {
+                                self.show_toast("OpenVPN profile updated");
+                                self.refresh().await;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to update OpenVPN profile: {}", e);
+                                self.show_toast(&format!(
+                                    "Failed to update OpenVPN profile: {}",
+                                    e
+                                ));
+                            }
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        log::error!("Failed to open OpenVPN editor: {}", e);
+                        self.show_toast(&format!("Failed to edit OpenVPN profile: {}", e));
+                    }
+                },
+                Err(e) => {
+                    log::error!("Failed to load OpenVPN profile: {}", e);
+                    self.show_toast(&format!("Failed to load OpenVPN profile: {}", e));
+                }
+            },
+        }
+    }
+
+    async fn delete_vpn(&self, uuid: &str, name: &str) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Delete VPN?")
+            .body(format!("This will delete VPN \"{}\".", name))
+            .default_response("delete")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("delete", "Delete")]);
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        if response.as_str() != "delete" {
+            return;
+        }
+
+        match nm::delete_vpn_connection(uuid).await {
+            Ok(()) => {
+                self.show_toast(&format!("Deleted VPN: {}", name));
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to delete VPN {}: {}", name, e);
+                self.show_toast(&format!("Failed to delete VPN \"{}\": {}", name, e));
+            }
+        }
+    }
+
+    #[allow(deprecated)]
+    async fn choose_import_file(&self) -> anyhow::Result<Option<PathBuf>> {
+        let chooser = gtk4::FileChooserNative::builder()
+            .title("Import VPN")
+            .action(gtk4::FileChooserAction::Open)
+            .accept_label("Import")
+            .cancel_label("Cancel")
+            .build();
+
+        if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            chooser.set_transient_for(Some(parent));
+        }
+
+        let response = chooser.run_future().await;
+        let path = if response == gtk4::ResponseType::Accept {
+            chooser.file().and_then(|file| file.path())
+        } else {
+            None
+        };
+        chooser.destroy();
+        Ok(path)
+    }
+
+    async fn show_wireguard_editor(
+        &self,
+        existing: Option<WireGuardConnectionConfig>,
+    ) -> anyhow::Result<Option<WireGuardConnectionConfig>> {
+        let name_entry = adw::EntryRow::builder().title("Connection name").build();
+        let interface_entry = adw::EntryRow::builder().title("Interface name").build();
+        let addresses_entry = adw::EntryRow::builder().title("Addresses").build();
+        addresses_entry.set_text("10.0.0.2/32");
+        let dns_entry = adw::EntryRow::builder().title("DNS servers").build();
+        let private_key_entry = adw::PasswordEntryRow::builder()
+            .title("Private key")
+            .build();
+        let public_key_entry = adw::EntryRow::builder().title("Peer public key").build();
+        let endpoint_entry = adw::EntryRow::builder().title("Endpoint").build();
+        let allowed_ips_entry = adw::EntryRow::builder().title("Allowed IPs").build();
+        allowed_ips_entry.set_text("0.0.0.0/0, ::/0");
+        let preshared_key_entry = adw::PasswordEntryRow::builder()
+            .title("Preshared key")
+            .build();
+
+        let keepalive_row = adw::ActionRow::builder()
+            .title("Persistent keepalive")
+            .subtitle("Seconds, optional")
+            .build();
+        let keepalive_adjustment = gtk4::Adjustment::new(25.0, 0.0, 3600.0, 1.0, 10.0, 0.0);
+        let keepalive_spin = gtk4::SpinButton::builder()
+            .adjustment(&keepalive_adjustment)
+            .numeric(true)
+            .digits(0)
+            .build();
+        keepalive_row.add_suffix(&keepalive_spin);
+
+        let mtu_row = adw::ActionRow::builder()
+            .title("MTU")
+            .subtitle("Optional")
+            .build();
+        let mtu_adjustment = gtk4::Adjustment::new(1420.0, 0.0, 9000.0, 1.0, 10.0, 0.0);
+        let mtu_spin = gtk4::SpinButton::builder()
+            .adjustment(&mtu_adjustment)
+            .numeric(true)
+            .digits(0)
+            .build();
+        mtu_row.add_suffix(&mtu_spin);
+
+        if let Some(existing) = existing.as_ref() {
+            name_entry.set_text(&existing.name);
+            interface_entry.set_text(&existing.interface_name);
+            addresses_entry.set_text(&existing.addresses.join(", "));
+            dns_entry.set_text(&existing.dns_servers.join(", "));
+            private_key_entry.set_text(&existing.private_key);
+            public_key_entry.set_text(&existing.public_key);
+            endpoint_entry.set_text(&existing.endpoint);
+            allowed_ips_entry.set_text(&existing.allowed_ips.join(", "));
+            preshared_key_entry.set_text(
+                existing
+                    .preshared_key
+                    .as_ref()
+                    .map(|k| k.as_str())
+                    .unwrap_or(""),
+            );
+            keepalive_spin.set_value(existing.persistent_keepalive.unwrap_or_default() as f64);
+            mtu_spin.set_value(existing.mtu.unwrap_or_default() as f64);
+        } else {
+            interface_entry.set_text("wg0");
+            keepalive_spin.set_value(25.0);
+        }
+
+        let group = adw::PreferencesGroup::new();
+        group.add(&name_entry);
+        group.add(&interface_entry);
+        group.add(&addresses_entry);
+        group.add(&dns_entry);
+        group.add(&private_key_entry);
+        group.add(&public_key_entry);
+        group.add(&endpoint_entry);
+        group.add(&allowed_ips_entry);
+        group.add(&preshared_key_entry);
+        group.add(&keepalive_row);
+        group.add(&mtu_row);
+
+        let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        body.set_margin_top(12);
+        body.set_margin_bottom(12);
+        body.set_margin_start(12);
+        body.set_margin_end(12);
+        body.append(&group);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(if existing.is_some() {
+                "Edit WireGuard VPN"
+            } else {
+                "New WireGuard VPN"
+            })
+            .body("Enter the basic WireGuard interface and peer details")
+            .extra_child(&body)
+            .default_response("save")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        if response.as_str() != "save" {
+            return Ok(None);
+        }
+
+        let name = name_entry.text().trim().to_string();
+        let interface_name = interface_entry.text().trim().to_string();
+        let private_key = private_key_entry.text().trim().to_string();
+        let public_key = public_key_entry.text().trim().to_string();
+        let endpoint = endpoint_entry.text().trim().to_string();
+        if name.is_empty()
+            || interface_name.is_empty()
+            || private_key.is_empty()
+            || public_key.is_empty()
+            || endpoint.is_empty()
+        {
+            self.show_toast("Name, interface, keys, and endpoint are required");
+            return Ok(None);
+        }
+
+        let addresses = split_csv(addresses_entry.text().as_str());
+        let allowed_ips = split_csv(allowed_ips_entry.text().as_str());
+        if addresses.is_empty() || allowed_ips.is_empty() {
+            self.show_toast("Addresses and allowed IPs are required");
+            return Ok(None);
+        }
+
+        let keepalive = spin_value_to_option(&keepalive_spin);
+        let mtu = spin_value_to_option(&mtu_spin);
+
+        Ok(Some(WireGuardConnectionConfig {
+            name,
+            interface_name,
+            addresses,
+            dns_servers: split_csv(dns_entry.text().as_str()),
+            private_key: Zeroizing::new(private_key),
+            public_key,
+            preshared_key: optional_text(preshared_key_entry.text().as_str()).map(Zeroizing::new),
+            endpoint,
+            allowed_ips,
+            mtu,
+            persistent_keepalive: keepalive,
+            extra_peers: existing
+                .map(|existing| existing.extra_peers)
+                .unwrap_or_default(),
+        }))
+    }
+
+    async fn show_openvpn_editor(
+        &self,
+        existing: Option<OpenVpnConnectionConfig>,
+    ) -> anyhow::Result<Option<OpenVpnConnectionConfig>> {
+        let name_entry = adw::EntryRow::builder().title("Connection name").build();
+        let remote_entry = adw::EntryRow::builder().title("Server / remote").build();
+        let username_entry = adw::EntryRow::builder().title("Username").build();
+        let autoconnect_row = adw::SwitchRow::builder()
+            .title("Connect Automatically")
+            .build();
+        let never_default_row = adw::SwitchRow::builder()
+            .title("Use Only for Its Network")
+            .subtitle("Other traffic keeps going through the regular connection")
+            .build();
+        let ignore_routes_row = adw::SwitchRow::builder()
+            .title("Ignore Server Routes")
+            .subtitle("Don't add routes pushed by the VPN server")
+            .build();
+
+        if let Some(existing) = existing.as_ref() {
+            name_entry.set_text(&existing.name);
+            remote_entry.set_text(&existing.remote);
+            username_entry.set_text(existing.username.as_deref().unwrap_or(""));
+            autoconnect_row.set_active(existing.autoconnect);
+            never_default_row.set_active(existing.never_default);
+            ignore_routes_row.set_active(existing.ignore_auto_routes);
+        }
+
+        let group = adw::PreferencesGroup::new();
+        group.add(&name_entry);
+        group.add(&remote_entry);
+        group.add(&username_entry);
+
+        let routing_group = adw::PreferencesGroup::builder().title("Routing").build();
+        routing_group.add(&autoconnect_row);
+        routing_group.add(&never_default_row);
+        routing_group.add(&ignore_routes_row);
+
+        let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        body.set_margin_top(12);
+        body.set_margin_bottom(12);
+        body.set_margin_start(12);
+        body.set_margin_end(12);
+        body.append(&group);
+        body.append(&routing_group);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(if existing.is_some() {
+                "Edit OpenVPN Profile"
+            } else {
+                "New OpenVPN Profile"
+            })
+            .body("This creates a basic OpenVPN profile. Certificates and advanced options can still be imported from a .ovpn file.")
+            .extra_child(&body)
+            .default_response("save")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        if response.as_str() != "save" {
+            return Ok(None);
+        }
+
+        let name = name_entry.text().trim().to_string();
+        let remote = remote_entry.text().trim().to_string();
+        if name.is_empty() || remote.is_empty() {
+            self.show_toast("Connection name and remote server are required");
+            return Ok(None);
+        }
+
+        Ok(Some(OpenVpnConnectionConfig {
+            name,
+            remote,
+            username: optional_text(username_entry.text().as_str()),
+            autoconnect: autoconnect_row.is_active(),
+            never_default: never_default_row.is_active(),
+            ignore_auto_routes: ignore_routes_row.is_active(),
+        }))
+    }
+
+    fn show_toast(&self, message: &str) {
+        common::show_toast(&self.toast_overlay, message);
+    }
+}
+
+fn vpn_icon_name(kind: VpnKind) -> &'static str {
+    match kind {
+        VpnKind::WireGuard | VpnKind::OpenVpn => icon_name(
+            "network-vpn-symbolic",
+            &[
+                "network-wireless-encrypted-symbolic",
+                "network-workgroup-symbolic",
+            ],
+        ),
+    }
+}
+
+fn split_csv(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn optional_text(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn spin_value_to_option(spin: &gtk4::SpinButton) -> Option<u32> {
+    let value = spin.value_as_int();
+    if value <= 0 {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
@@ -0,0 +1,208 @@
+// * ./src/ui/wake_page.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config;
+use crate::ui::{common, icon_name};
+use crate::wake_on_lan::{self, WakeTarget};
+
+// * Favorite machines to wake with a magic packet, filled by hand or from the LAN scan.
+#[derive(Clone)]
+pub struct WakePage {
+    pub widget: adw::PreferencesGroup,
+    toast_overlay: adw::ToastOverlay,
+    empty_row: adw::ActionRow,
+    target_rows: Rc<RefCell<Vec<adw::ActionRow>>>,
+}
+
+impl WakePage {
+    pub fn new(toast_overlay: &adw::ToastOverlay) -> Self {
+        let widget = adw::PreferencesGroup::new();
+        widget.set_title("Wake on LAN");
+        widget.set_description(Some(
+            "Turn on computers on this network that have Wake on LAN enabled.",
+        ));
+        widget.set_margin_top(24);
+
+        let add_button = gtk4::Button::builder()
+            .icon_name(icon_name("list-add-symbolic", &["list-add"][..]))
+            .tooltip_text("Add device")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        widget.set_header_suffix(Some(&add_button));
+
+        let empty_row = adw::ActionRow::builder()
+            .title("No devices yet")
+            .subtitle("Add one by MAC address, or star a device found by a scan")
+            .build();
+        widget.add(&empty_row);
+
+        let page = Self {
+            widget,
+            toast_overlay: toast_overlay.clone(),
+            empty_row,
+            target_rows: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        let page_ref = page.clone();
+        add_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.show_add_dialog("", "").await;
+            });
+        });
+
+        let page_ref = page.clone();
+        glib::spawn_future_local(async move {
+            page_ref.refresh().await;
+        });
+
+        page
+    }
+
+    pub async fn refresh(&self) {
+        let targets = match wake_on_lan::load_wake_targets(&wake_on_lan::wake_targets_path()).await
+        {
+            Ok(targets) => targets,
+            Err(e) => {
+                log::warn!("Failed to load Wake on LAN devices: {}", e);
+                Vec::new()
+            }
+        };
+
+        for row in self.target_rows.borrow_mut().drain(..) {
+            self.widget.remove(&row);
+        }
+        self.empty_row.set_visible(targets.is_empty());
+        for target in targets {
+            let row = self.create_target_row(&target);
+            self.widget.add(&row);
+            self.target_rows.borrow_mut().push(row);
+        }
+    }
+
+    // * Asks for a name before saving; used by the LAN scan with the device's details filled in.
+    pub async fn show_add_dialog(&self, name: &str, mac: &str) {
+        let name_entry = adw::EntryRow::builder().title("Name").build();
+        name_entry.set_text(name);
+        let mac_entry = adw::EntryRow::builder()
+            .title("MAC Address")
+            .activates_default(true)
+            .build();
+        mac_entry.set_text(mac);
+
+        let list = gtk4::ListBox::builder()
+            .css_classes(vec!["boxed-list".to_string()])
+            .selection_mode(gtk4::SelectionMode::None)
+            .build();
+        list.append(&name_entry);
+        list.append(&mac_entry);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Add Device to Wake")
+            .extra_child(&list)
+            .default_response("add")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("add", "Add")][..]);
+        dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("add", config::normalize_mac_address(mac).is_some());
+
+        let dialog_for_entry = dialog.clone();
+        mac_entry.connect_changed(move |entry| {
+            dialog_for_entry.set_response_enabled(
+                "add",
+                config::normalize_mac_address(&entry.text()).is_some(),
+            );
+        });
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        if response.as_str() != "add" {
+            return;
+        }
+
+        match wake_on_lan::store_wake_target(&name_entry.text(), &mac_entry.text()).await {
+            Ok(target) => {
+                self.show_toast(&format!("Added {}", target.name));
+                self.refresh().await;
+            }
+            Err(e) => {
+                log::error!("Failed to save Wake on LAN device: {}", e);
+                self.show_toast(&format!("Failed to add device: {}", e));
+            }
+        }
+    }
+
+    fn create_target_row(&self, target: &WakeTarget) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(&target.name)
+            .subtitle(&target.mac)
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(icon_name(
+            "computer-symbolic",
+            &["computer"][..],
+        )));
+
+        let wake_button = gtk4::Button::builder()
+            .label("Wake")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let remove_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "user-trash-symbolic",
+                &["edit-delete-symbolic"][..],
+            ))
+            .tooltip_text("Remove")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        row.add_suffix(&wake_button);
+        row.add_suffix(&remove_button);
+
+        let page = self.clone();
+        let wake_target = target.clone();
+        wake_button.connect_clicked(move |_| {
+            let page = page.clone();
+            let target = wake_target.clone();
+            glib::spawn_future_local(async move {
+                match wake_on_lan::wake(&target.mac).await {
+                    Ok(()) => page.show_toast(&format!("Wake packet sent to {}", target.name)),
+                    Err(e) => {
+                        log::error!("Failed to wake {}: {}", target.name, e);
+                        page.show_toast(&format!("Failed to wake {}: {}", target.name, e));
+                    }
+                }
+            });
+        });
+
+        let page = self.clone();
+        let mac = target.mac.clone();
+        remove_button.connect_clicked(move |_| {
+            let page = page.clone();
+            let mac = mac.clone();
+            glib::spawn_future_local(async move {
+                if let Err(e) = wake_on_lan::forget_wake_target(&mac).await {
+                    log::error!("Failed to remove Wake on LAN device: {}", e);
+                    page.show_toast(&format!("Failed to remove device: {}", e));
+                }
+                page.refresh().await;
+            });
+        });
+
+        row
+    }
+
+    fn show_toast(&self, message: &str) {
+        common::show_toast(&self.toast_overlay, message);
+    }
+}
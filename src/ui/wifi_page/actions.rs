@@ -1,6 +1,11 @@
 // * ./src/ui/wifi_page/actions.rs
 
+use gtk4::prelude::*;
+use std::future::Future;
+use std::rc::Rc;
+
 use super::WifiPage;
+use crate::nm::{self, NmError, WifiNetwork};
 
 pub(super) struct BusyGuard {
     pub(super) page: WifiPage,
@@ -11,3 +16,142 @@ impl Drop for BusyGuard {
         self.page.set_busy(false, None);
     }
 }
+
+impl WifiPage {
+    // * Connects and scans the user can abort from the header's Cancel button.
+    pub(super) async fn run_cancellable<T>(
+        &self,
+        future: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        let (future, handle) = nm::cancellable(future);
+        let handle = Rc::new(handle);
+        self.pending_operations
+            .borrow_mut()
+            .push(Rc::clone(&handle));
+        self.cancel_button.set_visible(true);
+
+        let result = future.await;
+
+        let mut pending = self.pending_operations.borrow_mut();
+        pending.retain(|other| !Rc::ptr_eq(other, &handle));
+        self.cancel_button.set_visible(!pending.is_empty());
+        result
+    }
+
+    pub(super) fn cancel_pending_operations(&self) {
+        for handle in self.pending_operations.borrow().iter() {
+            handle.cancel();
+        }
+    }
+
+    // ? Errors count as saved so a failed lookup never leads to deleting a real profile.
+    pub(super) async fn has_saved_profile(ssid: &str) -> bool {
+        nm::saved_wifi_ssids()
+            .await
+            .map(|ssids| ssids.contains(ssid))
+            .unwrap_or(true)
+    }
+
+    // * Aborting the future doesn't stop NM, so tear down the half-finished activation too.
+    // * A profile the cancelled connect created is removed again; an existing one is kept.
+    pub(super) async fn abort_activation(&self, ssid: &str, had_profile: bool) {
+        if let Err(e) = nm::disconnect_network(ssid).await {
+            log::debug!("Nothing to abort for {}: {}", ssid, e);
+        }
+        if !had_profile {
+            if let Err(e) = nm::delete_connection_by_ssid(ssid).await {
+                log::debug!("No new profile to remove for {}: {}", ssid, e);
+            }
+        }
+        self.show_toast("Connection cancelled");
+        self.refresh_networks(false).await;
+    }
+}
+
+pub(super) fn is_cancelled(error: &anyhow::Error) -> bool {
+    NmError::classify(error) == NmError::Cancelled
+}
+
+pub(super) enum QuickConnectAction {
+    Connect(WifiNetwork),
+    AlreadyConnected(String),
+    FocusFirst,
+    NoMatch,
+}
+
+// * Enter in the search entry: connect when the filter narrows down to one network.
+pub(super) fn quick_connect_action(
+    filtered: &[WifiNetwork],
+    connected: Option<&WifiNetwork>,
+) -> QuickConnectAction {
+    let is_connected = |net: &WifiNetwork| {
+        connected
+            .map(|c| c.connected && c.ssid == net.ssid)
+            .unwrap_or(false)
+    };
+    let candidates: Vec<&WifiNetwork> = filtered.iter().filter(|n| !is_connected(n)).collect();
+
+    match candidates.as_slice() {
+        [only] => QuickConnectAction::Connect((*only).clone()),
+        [] => match filtered.iter().find(|n| is_connected(n)) {
+            Some(net) => QuickConnectAction::AlreadyConnected(net.ssid.clone()),
+            None => QuickConnectAction::NoMatch,
+        },
+        _ => QuickConnectAction::FocusFirst,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(ssid: &str, connected: bool) -> WifiNetwork {
+        WifiNetwork {
+            ssid: ssid.to_string(),
+            signal: 60,
+            secured: true,
+            connected,
+            band: "5 GHz".to_string(),
+            channel: 36,
+            freq_mhz: 5180,
+            security_type: "WPA2".to_string(),
+            device: None,
+        }
+    }
+
+    #[test]
+    fn connects_to_single_match() {
+        let filtered = vec![network("Cafe", false)];
+        match quick_connect_action(&filtered, None) {
+            QuickConnectAction::Connect(net) => assert_eq!(net.ssid, "Cafe"),
+            _ => panic!("expected a connect action"),
+        }
+    }
+
+    #[test]
+    fn ignores_the_connected_network_when_counting_matches() {
+        let home = network("Home", true);
+        let filtered = vec![home.clone(), network("Home-5G", false)];
+        assert!(matches!(
+            quick_connect_action(&filtered, Some(&home)),
+            QuickConnectAction::Connect(net) if net.ssid == "Home-5G"
+        ));
+        assert!(matches!(
+            quick_connect_action(std::slice::from_ref(&home), Some(&home)),
+            QuickConnectAction::AlreadyConnected(ssid) if ssid == "Home"
+        ));
+    }
+
+    #[test]
+    fn focuses_list_when_several_match() {
+        let filtered = vec![network("Cafe", false), network("Cafe-Guest", false)];
+        assert!(matches!(
+            quick_connect_action(&filtered, None),
+            QuickConnectAction::FocusFirst
+        ));
+        assert!(matches!(
+            quick_connect_action(&[], None),
+            QuickConnectAction::NoMatch
+        ));
+    }
+}
@@ -2,6 +2,8 @@
 
 use std::net::IpAddr;
 
+use crate::nm::SavedConnection;
+
 pub(super) fn get_signal_icon(signal: u8) -> &'static str {
     if signal >= 75 {
         "network-wireless-signal-excellent-symbolic"
@@ -48,6 +50,42 @@ pub(super) fn get_signal_strength_text_plain(signal: u8) -> String {
     format!("{} ({}%)", quality, signal)
 }
 
+// * Saved networks out of range have no signal or channel; show what the profile knows instead.
+pub(super) fn saved_network_subtitle(saved: &SavedConnection, now: i64) -> String {
+    let mut parts = vec!["Saved".to_string(), saved.security_type.clone()];
+    parts.push(match i64::try_from(saved.last_used).unwrap_or(0) {
+        0 => "Never used".to_string(),
+        last_used => format!("Last used {}", describe_age(now.saturating_sub(last_used))),
+    });
+    if !saved.autoconnect {
+        parts.push("Manual connect".to_string());
+    }
+    if let Some(interface) = &saved.interface_name {
+        parts.push(format!("Only on {}", interface));
+    }
+    parts.join(" • ")
+}
+
+fn describe_age(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    let plural = |count: i64, unit: &str| {
+        if count == 1 {
+            format!("1 {} ago", unit)
+        } else {
+            format!("{} {}s ago", count, unit)
+        }
+    };
+    match seconds {
+        s if s < MINUTE => "just now".to_string(),
+        s if s < HOUR => plural(s / MINUTE, "minute"),
+        s if s < DAY => plural(s / HOUR, "hour"),
+        s if s < 30 * DAY => plural(s / DAY, "day"),
+        s => plural(s / (30 * DAY), "month"),
+    }
+}
+
 pub(super) fn invalid_ip_entries(entries: &[String]) -> Vec<String> {
     entries
         .iter()
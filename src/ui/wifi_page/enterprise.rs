@@ -0,0 +1,70 @@
+// * ./src/ui/wifi_page/enterprise.rs
+
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+
+use super::{is_cancelled, WifiPage};
+use crate::nm;
+use crate::ui::connection_editor::EapForm;
+
+impl WifiPage {
+    // * WPA-Enterprise networks need a login instead of a passphrase (eduroam, corporate Wi-Fi).
+    pub(super) async fn show_enterprise_dialog(&self, ssid: &str) {
+        let form = EapForm::new(&nm::EapConfig::default());
+
+        let scroller = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(420)
+            .child(&form.group)
+            .build();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("Connect to {}", ssid))
+            .body("This network requires an enterprise login")
+            .extra_child(&scroller)
+            .default_response("connect")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("connect", "Connect")][..]);
+        dialog.set_response_appearance("connect", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        if response.as_str() != "connect" {
+            return;
+        }
+
+        let config = form.config();
+        if let Err(e) = nm::validate_eap_config(&config) {
+            self.show_toast(&e.to_string());
+            return;
+        }
+
+        self.connect_enterprise_network(ssid, &config).await;
+    }
+
+    async fn connect_enterprise_network(&self, ssid: &str, config: &nm::EapConfig) {
+        let _busy = self.busy_guard("Connecting...");
+        self.show_toast("Connecting...");
+        let had_profile = Self::has_saved_profile(ssid).await;
+
+        match self
+            .run_cancellable(nm::connect_wifi_enterprise(ssid, config, false))
+            .await
+        {
+            Ok(nm::ConnectStatus::Connected) => {
+                self.after_connect(ssid).await;
+            }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, had_profile).await,
+            Err(e) => {
+                log::error!("Enterprise connection failed: {}", e);
+                self.show_error("Failed to connect", &e);
+            }
+        }
+    }
+}
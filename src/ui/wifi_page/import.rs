@@ -0,0 +1,167 @@
+// * ./src/ui/wifi_page/import.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita::{self as adw, prelude::*};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::WifiPage;
+use crate::nm;
+use crate::quick_connect;
+use crate::wpa_import::{self, ImportMode, WpaNetwork};
+
+impl WifiPage {
+    pub(super) async fn import_wpa_supplicant(&self) {
+        let path = match self.choose_wpa_conf_file().await {
+            Some(path) => path,
+            None => return,
+        };
+
+        let networks = match wpa_import::read_conf(&path) {
+            Ok(networks) => networks,
+            Err(e) => {
+                log::error!("Failed to read wpa_supplicant config: {}", e);
+                self.show_toast(&e.to_string());
+                return;
+            }
+        };
+
+        let (selected, mode) = match self.show_wpa_import_preview(networks).await {
+            Some(choice) => choice,
+            None => return,
+        };
+        if selected.is_empty() {
+            self.show_toast("No networks selected");
+            return;
+        }
+
+        let summary = wpa_import::import_networks(&selected, mode).await;
+        for (ssid, error) in &summary.failed {
+            log::warn!("Import of {} failed: {}", ssid, error);
+        }
+        self.show_toast(&summary.message());
+        quick_connect::invalidate_cache();
+        self.refresh_networks(false).await;
+    }
+
+    #[allow(deprecated)]
+    async fn choose_wpa_conf_file(&self) -> Option<PathBuf> {
+        let chooser = gtk4::FileChooserNative::builder()
+            .title("Import from wpa_supplicant.conf")
+            .action(gtk4::FileChooserAction::Open)
+            .accept_label("Open")
+            .cancel_label("Cancel")
+            .build();
+
+        if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            chooser.set_transient_for(Some(parent));
+        }
+        if let Some(folder) = Path::new(wpa_import::DEFAULT_CONF_PATH)
+            .parent()
+            .filter(|dir| dir.is_dir())
+        {
+            let _ = chooser.set_current_folder(Some(&gtk4::gio::File::for_path(folder)));
+        }
+
+        let response = chooser.run_future().await;
+        let path = if response == gtk4::ResponseType::Accept {
+            chooser.file().and_then(|file| file.path())
+        } else {
+            None
+        };
+        chooser.destroy();
+        path
+    }
+
+    async fn show_wpa_import_preview(
+        &self,
+        networks: Vec<WpaNetwork>,
+    ) -> Option<(Vec<WpaNetwork>, ImportMode)> {
+        let saved: HashSet<String> = nm::saved_wifi_ssids().await.unwrap_or_default();
+
+        let group = adw::PreferencesGroup::new();
+        let mut checks = Vec::new();
+        for network in &networks {
+            let subtitle = if saved.contains(&network.ssid) {
+                format!("{} · Already saved", network.security_label())
+            } else {
+                network.security_label()
+            };
+            let check = gtk4::CheckButton::new();
+            check.set_active(network.is_importable());
+            check.set_sensitive(network.is_importable());
+
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&network.ssid))
+                .subtitle(subtitle)
+                .activatable_widget(&check)
+                .build();
+            row.add_prefix(&check);
+            group.add(&row);
+            checks.push(check);
+        }
+
+        let overwrite_row = adw::SwitchRow::builder()
+            .title("Replace saved networks")
+            .subtitle("Otherwise networks that are already saved are skipped")
+            .visible(networks.iter().any(|n| saved.contains(&n.ssid)))
+            .build();
+        let options_group = adw::PreferencesGroup::new();
+        options_group.add(&overwrite_row);
+
+        let list_box = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        list_box.append(&group);
+        list_box.append(&options_group);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(360)
+            .child(&list_box)
+            .build();
+
+        let body = gtk4::Box::new(gtk4::Orientation::Vertical, 12);
+        body.set_margin_top(12);
+        body.set_margin_bottom(12);
+        body.set_margin_start(12);
+        body.set_margin_end(12);
+        body.append(&scrolled);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Import Networks")
+            .body(format!(
+                "Found {} networks. Imported networks are saved but not connected.",
+                networks.len()
+            ))
+            .extra_child(&body)
+            .default_response("import")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("import", "Import")]);
+        dialog.set_response_appearance("import", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+
+        if response.as_str() != "import" {
+            return None;
+        }
+
+        let selected = networks
+            .into_iter()
+            .zip(checks.iter())
+            .filter(|(_, check)| check.is_active())
+            .map(|(network, _)| network)
+            .collect();
+        let mode = if overwrite_row.is_active() {
+            ImportMode::Overwrite
+        } else {
+            ImportMode::Skip
+        };
+        Some((selected, mode))
+    }
+}
@@ -1,36 +1,61 @@
 // * ./src/ui/wifi_page/mod.rs
 
-use std::cell::RefCell;
-use std::rc::Rc;
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita::{self as adw, prelude::*};
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::HashSet;
+use std::rc::Rc;
 
+use crate::captive_portal::{self, CaptivePortalEntry, PortalForm, PortalOutcome};
+use crate::network_trust::{self, NetworkTrustProfile};
 use crate::nm::{self, WifiNetwork};
+use crate::polling::PollScheduler;
 use crate::qr_dialog;
+use crate::reachability;
+use crate::resolved;
 use crate::state::{AppState, PageKind, WifiFilterState};
-use crate::ui::{common, icon_name};
+use crate::traffic::{TrafficEvent, TrafficMonitor};
+use crate::ui::speed_graph::SpeedGraph;
+use crate::ui::{common, confirm_dialog, connection_editor, icon_name};
 
 mod actions;
 mod details;
 mod dialogs;
-use actions::BusyGuard;
-use details::{get_signal_icon, get_signal_strength_text, get_signal_strength_text_plain, invalid_ip_entries};
+mod enterprise;
+mod import;
+mod scan_diff;
+use actions::{is_cancelled, quick_connect_action, BusyGuard, QuickConnectAction};
+use details::{
+    get_signal_icon, get_signal_strength_text, get_signal_strength_text_plain, invalid_ip_entries,
+    saved_network_subtitle,
+};
 use dialogs::parse_entry_list;
+use scan_diff::{diff_scan, ScanSnapshot};
+
+const DNS_OVER_TLS_MODES: [nm::DnsOverTls; 4] = [
+    nm::DnsOverTls::Default,
+    nm::DnsOverTls::Off,
+    nm::DnsOverTls::Opportunistic,
+    nm::DnsOverTls::Required,
+];
+
+const WIFI_BANDS: [nm::WifiBand; 3] =
+    [nm::WifiBand::Any, nm::WifiBand::Band24, nm::WifiBand::Band5];
 
 #[derive(Clone)]
 pub struct WifiPage {
     pub widget: gtk4::Box,
     toast_overlay: adw::ToastOverlay,
     wifi_switch: adw::SwitchRow,
-    #[allow(dead_code)]
     search_entry: gtk4::SearchEntry,
     hidden_network_button: gtk4::Button,
     refresh_button: gtk4::Button,
     spinner: gtk4::Spinner,
     operation_status_label: gtk4::Label,
+    cancel_button: gtk4::Button,
+    pending_operations: Rc<RefCell<Vec<Rc<nm::CancelHandle>>>>,
     connected_card: gtk4::Box,
     connected_ssid: gtk4::Label,
     connected_subtitle: gtk4::Label,
@@ -38,6 +63,8 @@ pub struct WifiPage {
     connected_details_ip: gtk4::Label,
     connected_details_dns: gtk4::Label,
     connected_details_speed: gtk4::Label,
+    connected_details_graph: SpeedGraph,
+    connected_details_graph_caption: gtk4::Label,
     known_header: gtk4::Label,
     known_list: gtk4::ListBox,
     other_header: gtk4::Label,
@@ -49,6 +76,7 @@ pub struct WifiPage {
     filter_24: gtk4::ToggleButton,
     filter_5: gtk4::ToggleButton,
     filter_saved: gtk4::ToggleButton,
+    last_scan: Rc<RefCell<Option<ScanSnapshot>>>,
     app_state: AppState,
 }
 
@@ -115,7 +143,14 @@ impl WifiPage {
         filter_row.append(&filter_5);
         filter_row.append(&filter_saved);
 
-        search_filter_box.append(&filter_row);
+        // * Let the filter toggles scroll sideways instead of forcing a wide window
+        let filter_scroller = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::External)
+            .vscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .child(&filter_row)
+            .build();
+        search_filter_box.append(&filter_scroller);
         content.append(&search_filter_box);
 
         // Networks Header with Refresh Button
@@ -139,12 +174,32 @@ impl WifiPage {
         operation_status_label.set_opacity(0.7);
         operation_status_label.set_visible(false);
 
+        let cancel_button = gtk4::Button::builder()
+            .label("Cancel")
+            .tooltip_text("Stop the pending connection or scan")
+            .css_classes(vec!["flat".to_string(), "touch-target".to_string()])
+            .visible(false)
+            .build();
+
         let hidden_network_button = gtk4::Button::builder()
-            .label("Hidden Network")
-            .tooltip_text("Connect to a hidden Wi-Fi network")
+            .label("Hidden Network…")
+            .tooltip_text("Connect to a network that doesn't broadcast its name")
             .css_classes(vec!["flat".to_string(), "touch-target".to_string()])
             .build();
 
+        let import_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "document-open-symbolic",
+                &["document-open", "folder-open-symbolic"][..],
+            ))
+            .tooltip_text("Import networks from wpa_supplicant.conf")
+            .css_classes(vec![
+                "flat".to_string(),
+                "circular".to_string(),
+                "touch-target".to_string(),
+            ])
+            .build();
+
         let refresh_button = gtk4::Button::builder()
             .icon_name(icon_name(
                 "view-refresh-symbolic",
@@ -160,7 +215,9 @@ impl WifiPage {
 
         header_box.append(&networks_label);
         header_box.append(&spinner);
+        header_box.append(&cancel_button);
         header_box.append(&hidden_network_button);
+        header_box.append(&import_button);
         header_box.append(&refresh_button);
         content.append(&header_box);
         content.append(&operation_status_label);
@@ -220,6 +277,15 @@ impl WifiPage {
         details_box.append(&details_dns);
         details_box.append(&details_speed);
 
+        let details_graph = SpeedGraph::new();
+        details_graph.widget.set_margin_top(6);
+        let details_graph_caption = gtk4::Label::new(Some("Last 60 seconds"));
+        details_graph_caption.set_xalign(0.0);
+        details_graph_caption.add_css_class("caption");
+        details_graph_caption.add_css_class("dim-label");
+        details_box.append(&details_graph.widget);
+        details_box.append(&details_graph_caption);
+
         details_clamp.set_child(Some(&details_box));
         details_revealer.set_child(Some(&details_clamp));
         connected_card.append(&details_revealer);
@@ -311,6 +377,8 @@ impl WifiPage {
             refresh_button: refresh_button.clone(),
             spinner: spinner.clone(),
             operation_status_label: operation_status_label.clone(),
+            cancel_button: cancel_button.clone(),
+            pending_operations: Rc::new(RefCell::new(Vec::new())),
             connected_card: connected_card.clone(),
             connected_ssid: connected_ssid.clone(),
             connected_subtitle: connected_subtitle.clone(),
@@ -318,6 +386,8 @@ impl WifiPage {
             connected_details_ip: details_ip.clone(),
             connected_details_dns: details_dns.clone(),
             connected_details_speed: details_speed.clone(),
+            connected_details_graph: details_graph,
+            connected_details_graph_caption: details_graph_caption,
             known_header: known_header.clone(),
             known_list: known_list.clone(),
             other_header: other_header.clone(),
@@ -329,11 +399,17 @@ impl WifiPage {
             filter_24: filter_24.clone(),
             filter_5: filter_5.clone(),
             filter_saved: filter_saved.clone(),
+            last_scan: Rc::new(RefCell::new(None)),
             app_state: app_state.clone(),
         };
 
         page.apply_expand_details_setting(expand_connected_details);
 
+        let page_ref = page.clone();
+        cancel_button.connect_clicked(move |_| {
+            page_ref.cancel_pending_operations();
+        });
+
         // Connected details toggle
         let page_ref = page.clone();
         details_button.connect_clicked(move |_| {
@@ -469,6 +545,31 @@ impl WifiPage {
                 .set_wifi_search_debounce_source(Some(source));
         });
 
+        // Quick connect: Enter connects when the search matches a single network
+        let page_ref = page.clone();
+        search_entry.connect_activate(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.quick_connect_from_search().await;
+            });
+        });
+
+        let search_shortcuts = gtk4::ShortcutController::new();
+        search_shortcuts.set_scope(gtk4::ShortcutScope::Global);
+        let page_ref = page.clone();
+        search_shortcuts.add_shortcut(gtk4::Shortcut::new(
+            gtk4::ShortcutTrigger::parse_string("<Control>f"),
+            Some(gtk4::CallbackAction::new(move |_, _| {
+                // ? Global scope also fires while another page is visible
+                if !page_ref.widget.is_mapped() {
+                    return glib::Propagation::Proceed;
+                }
+                page_ref.focus_search();
+                glib::Propagation::Stop
+            })),
+        ));
+        page.widget.add_controller(search_shortcuts);
+
         // Refresh button handler
         let page_ref = page.clone();
         refresh_button.connect_clicked(move |_| {
@@ -478,6 +579,14 @@ impl WifiPage {
             });
         });
 
+        let page_ref = page.clone();
+        import_button.connect_clicked(move |_| {
+            let page = page_ref.clone();
+            glib::spawn_future_local(async move {
+                page.import_wpa_supplicant().await;
+            });
+        });
+
         let page_ref = page.clone();
         hidden_network_button.connect_clicked(move |_| {
             let page = page_ref.clone();
@@ -499,42 +608,71 @@ impl WifiPage {
     pub fn set_page_visible(&self, visible: bool) {
         self.app_state.set_page_visible(PageKind::Wifi, visible);
         if visible {
-            self.start_auto_refresh();
+            // * Show the last scan right away; the rescan below merges fresh results in
+            if self.wifi_switch.is_active() && self.app_state.wifi_all_networks().is_empty() {
+                if let Some(scan) = nm::last_scan() {
+                    self.app_state.set_wifi_all_networks(scan.networks);
+                    self.update_filtered_networks();
+                }
+            }
             let page = self.clone();
             glib::spawn_future_local(async move {
                 page.refresh_networks(false).await;
             });
-        } else {
-            self.stop_auto_refresh();
         }
     }
 
-    fn start_auto_refresh(&self) {
-        if self.app_state.wifi_has_refresh_source() {
-            return;
-        }
+    // * Feeds the details graph from the shared sampler, which follows the primary interface.
+    // * Used by the tray; flipping the switch keeps the page's own state in step.
+    pub fn set_wifi_enabled(&self, enabled: bool) {
+        self.wifi_switch.set_active(enabled);
+    }
 
-        let page_ref = self.clone();
-        let source = glib::timeout_add_seconds_local(15, move || {
-            let page = page_ref.clone();
-            if page.wifi_switch.is_active()
-                && page.app_state.auto_scan_enabled()
-                && page.app_state.is_page_visible(PageKind::Wifi)
-            {
-                glib::spawn_future_local(async move {
-                    page.refresh_networks(false).await;
-                });
-            }
-            glib::ControlFlow::Continue
+    // * The quick-connect menu, tray and D-Bus connect through here so they get the same
+    // * follow-up as a click in the list.
+    pub fn connect_saved(&self, ssid: &str) {
+        let page = self.clone();
+        let ssid = ssid.to_string();
+        glib::spawn_future_local(async move {
+            page.connect_saved_network(&ssid).await;
         });
+    }
 
-        self.app_state.set_wifi_refresh_source(Some(source));
+    pub fn attach_traffic_monitor(&self, monitor: &TrafficMonitor) {
+        let page = self.clone();
+        monitor.subscribe(move |event| match event {
+            TrafficEvent::InterfaceChanged { current, .. } => {
+                page.connected_details_graph.clear();
+                page.connected_details_graph_caption
+                    .set_text(&match current {
+                        Some(iface) => format!("Last 60 seconds on {}", iface),
+                        None => "Last 60 seconds".to_string(),
+                    });
+            }
+            TrafficEvent::Sample(sample) => page
+                .connected_details_graph
+                .push(sample.down_bytes_per_sec, sample.up_bytes_per_sec),
+        });
     }
 
-    fn stop_auto_refresh(&self) {
-        if let Some(source) = self.app_state.take_wifi_refresh_source() {
-            source.remove();
-        }
+    pub fn attach_poll_scheduler(&self, scheduler: &PollScheduler) {
+        let page = self.clone();
+        scheduler.subscribe(
+            "wifi-scan",
+            std::time::Duration::from_secs(5),
+            std::time::Duration::from_secs(60),
+            move || {
+                if page.wifi_switch.is_active()
+                    && page.app_state.auto_scan_enabled()
+                    && page.app_state.is_page_visible(PageKind::Wifi)
+                {
+                    let page = page.clone();
+                    glib::spawn_future_local(async move {
+                        page.refresh_networks(false).await;
+                    });
+                }
+            },
+        );
     }
 
     fn set_busy(&self, busy: bool, status_message: Option<&str>) {
@@ -574,7 +712,7 @@ impl WifiPage {
         }
     }
 
-    fn busy_guard(&self, status_message: &'static str) -> BusyGuard {
+    fn busy_guard(&self, status_message: &str) -> BusyGuard {
         self.set_busy(true, Some(status_message));
         BusyGuard { page: self.clone() }
     }
@@ -701,7 +839,11 @@ impl WifiPage {
     }
 
     async fn refresh_networks(&self, show_feedback: bool) {
-        let _busy = self.busy_guard("Refreshing...");
+        let status = match nm::last_scan() {
+            Some(scan) => format!("Refreshing... ({})", scan.age_label()),
+            None => "Refreshing...".to_string(),
+        };
+        let _busy = self.busy_guard(&status);
         if show_feedback {
             self.known_list.add_css_class("list-loading");
             self.other_list.add_css_class("list-loading");
@@ -709,15 +851,19 @@ impl WifiPage {
 
         self.load_saved_connections().await;
 
-        match nm::scan_networks().await {
+        match self.run_cancellable(nm::scan_networks()).await {
             Ok(networks) => {
                 self.app_state.set_wifi_all_networks(networks);
                 self.app_state.set_wifi_scan_complete(true);
                 self.update_filtered_networks();
             }
+            Err(e) if is_cancelled(&e) => {
+                self.app_state.set_wifi_scan_complete(true);
+                self.update_filtered_networks();
+            }
             Err(e) => {
                 log::error!("Failed to scan networks: {}", e);
-                if nm::is_nmcli_retrieval_error(&e.to_string()) {
+                if nm::NmError::is_unavailable(&e) {
                     self.show_toast(nm::NMCLI_RETRIEVAL_TOAST);
                 } else {
                     self.show_toast(&format!("Failed to scan: {}", e));
@@ -735,21 +881,20 @@ impl WifiPage {
 
     async fn load_saved_connections(&self) {
         match nm::get_saved_connections().await {
-            Ok(saved) => {
-                let mut set = HashSet::new();
-                for conn in saved {
-                    set.insert(conn.ssid);
-                }
-                self.app_state.set_wifi_saved_ssids(set);
-            }
+            Ok(saved) => self.app_state.set_wifi_saved_connections(saved),
             Err(e) => {
                 log::warn!("Failed to load saved networks: {}", e);
-                self.app_state.clear_wifi_saved_ssids();
+                self.app_state.clear_wifi_saved_connections();
             }
         }
     }
 
     fn update_filtered_networks(&self) {
+        let (filtered, connected) = self.filtered_networks();
+        self.populate_networks(filtered, connected);
+    }
+
+    fn filtered_networks(&self) -> (Vec<WifiNetwork>, Option<WifiNetwork>) {
         let all_nets = self.app_state.wifi_all_networks();
         let search = self.app_state.wifi_search_text();
         let saved = self.app_state.wifi_saved_ssids();
@@ -789,15 +934,22 @@ impl WifiPage {
                     if !search_match {
                         continue;
                     }
+                    let security_type = self
+                        .app_state
+                        .wifi_saved_connection(&ssid)
+                        .map(|saved| saved.security_type)
+                        .unwrap_or_else(|| "Saved".to_string());
                     list.push(WifiNetwork {
                         ssid,
                         signal: 0,
+                        // * Keeps clicks on the saved-profile path instead of creating a new open profile
                         secured: true,
                         connected: false,
                         band: "Saved".to_string(),
                         channel: 0,
                         freq_mhz: 0,
-                        security_type: "Saved".to_string(),
+                        security_type,
+                        device: None,
                     });
                 }
 
@@ -833,12 +985,68 @@ impl WifiPage {
             }
         };
 
+        (filtered, connected)
+    }
+
+    fn focus_search(&self) {
+        self.search_entry.grab_focus();
+        self.search_entry.select_region(0, -1);
+    }
+
+    async fn quick_connect_from_search(&self) {
+        if self.search_entry.text().trim().is_empty() {
+            return;
+        }
+
+        // * Act on what was typed, not on the last debounced filter
+        if let Some(source) = self.app_state.take_wifi_search_debounce_source() {
+            source.remove();
+        }
+        self.app_state
+            .set_wifi_search_text(self.search_entry.text().to_lowercase());
+        let (filtered, connected) = self.filtered_networks();
+        let action = quick_connect_action(&filtered, connected.as_ref());
         self.populate_networks(filtered, connected);
+
+        match action {
+            QuickConnectAction::Connect(network) => self.handle_network_click(&network).await,
+            QuickConnectAction::AlreadyConnected(ssid) => {
+                self.show_toast(&format!("Already connected to {}", ssid));
+            }
+            QuickConnectAction::FocusFirst => {
+                let first_row = self
+                    .known_list
+                    .row_at_index(0)
+                    .or_else(|| self.other_list.row_at_index(0));
+                if let Some(row) = first_row {
+                    row.grab_focus();
+                }
+            }
+            QuickConnectAction::NoMatch => self.show_toast("No matching networks"),
+        }
     }
 
     fn populate_networks(&self, networks: Vec<WifiNetwork>, connected: Option<WifiNetwork>) {
         self.clear_networks();
 
+        let mut snapshot = ScanSnapshot {
+            filter: self.app_state.wifi_filter_state(),
+            search: self.app_state.wifi_search_text(),
+            networks: networks.clone(),
+        };
+        snapshot.networks.extend(connected.clone());
+        let diff = match self.last_scan.try_borrow_mut() {
+            Ok(mut last_scan) => {
+                let diff = diff_scan(last_scan.as_ref(), &snapshot);
+                *last_scan = Some(snapshot);
+                diff
+            }
+            Err(_) => {
+                log::warn!("Borrow conflict in UI state");
+                Default::default()
+            }
+        };
+
         if let Some(ref network) = connected {
             self.app_state
                 .set_wifi_connected_network(Some(network.clone()));
@@ -884,21 +1092,27 @@ impl WifiPage {
 
         for network in known {
             let row = self.create_network_row(&network);
+            if diff.appeared.contains(&network.ssid) {
+                Self::flash_row(&row, "scan-new");
+            }
             self.known_list.append(&row);
         }
 
         for network in other {
             let row = self.create_network_row(&network);
+            if diff.appeared.contains(&network.ssid) {
+                Self::flash_row(&row, "scan-new");
+            }
             self.other_list.append(&row);
         }
 
         let show_known = self.known_list.first_child().is_some();
         let show_other = self.other_list.first_child().is_some();
+        self.update_section_visibility();
 
-        self.known_header.set_visible(show_known);
-        self.known_list.set_visible(show_known);
-        self.other_header.set_visible(show_other);
-        self.other_list.set_visible(show_other);
+        for network in diff.departed {
+            self.append_departed_row(&network, saved.contains(&network.ssid));
+        }
 
         if !show_known && !show_other && connected.is_none() {
             let wifi_enabled = self.wifi_switch.is_active();
@@ -919,6 +1133,48 @@ impl WifiPage {
         }
     }
 
+    fn flash_row(row: &adw::ActionRow, css_class: &'static str) {
+        row.remove_css_class("fade-in");
+        row.add_css_class(css_class);
+        let row = row.clone();
+        glib::timeout_add_local_once(std::time::Duration::from_millis(1600), move || {
+            row.remove_css_class(css_class);
+        });
+    }
+
+    // * Departed networks linger as a fading, inert row until the animation ends.
+    fn append_departed_row(&self, network: &WifiNetwork, saved: bool) {
+        let list = if saved {
+            &self.known_list
+        } else {
+            &self.other_list
+        };
+        let row = self.create_network_row(network);
+        row.set_sensitive(false);
+        row.set_subtitle("Out of range");
+        Self::flash_row(&row, "scan-departed");
+        list.append(&row);
+        self.update_section_visibility();
+
+        let page = self.clone();
+        glib::timeout_add_local_once(std::time::Duration::from_millis(1500), move || {
+            if let Some(list) = row.parent().and_downcast::<gtk4::ListBox>() {
+                list.remove(&row);
+                page.update_section_visibility();
+            }
+        });
+    }
+
+    fn update_section_visibility(&self) {
+        let show_known = self.known_list.first_child().is_some();
+        let show_other = self.other_list.first_child().is_some();
+
+        self.known_header.set_visible(show_known);
+        self.known_list.set_visible(show_known);
+        self.other_header.set_visible(show_other);
+        self.other_list.set_visible(show_other);
+    }
+
     fn update_connected_card(&self, network: &WifiNetwork) {
         self.connected_ssid.set_text(&network.ssid);
         // * set_text — no markup, use plain text version to avoid Pango "<" parse errors
@@ -941,7 +1197,9 @@ impl WifiPage {
 
         if let Some(net) = network {
             glib::spawn_future_local(async move {
-                let info = nm::get_network_info(&net.ssid).await.ok();
+                let info = nm::get_network_info_with_retry(&net.ssid, net.device.as_deref())
+                    .await
+                    .ok();
 
                 let ip = info
                     .as_ref()
@@ -983,13 +1241,146 @@ impl WifiPage {
         }
     }
 
+    pub fn toggle_connected_details(&self) {
+        let reveal = !self.connected_details_revealer.reveals_child();
+        self.apply_expand_details_setting(reveal);
+    }
+
+    pub fn request_refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            page.refresh_networks(true).await;
+        });
+    }
+
+    // * Re-reads NM's cached access points; never triggers a scan, so AP signals can't loop.
+    pub fn refresh_from_nm_event(&self) {
+        if !self.wifi_switch.is_active() || !self.app_state.is_page_visible(PageKind::Wifi) {
+            return;
+        }
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            page.load_saved_connections().await;
+            match nm::cached_networks().await {
+                Ok(networks) => {
+                    page.app_state.set_wifi_all_networks(networks);
+                    page.update_filtered_networks();
+                }
+                Err(e) => log::debug!("Skipping signal-driven Wi-Fi refresh: {}", e),
+            }
+        });
+    }
+
+    // * For cards that keep hopping to a weak 2.4 GHz radio or the wrong mesh node.
+    async fn add_roaming_lock_rows(&self, group: &adw::PreferencesGroup, network: &WifiNetwork) {
+        let band_row = adw::ComboRow::builder()
+            .title("Band")
+            .model(&gtk4::StringList::new(
+                &["Any band", "2.4 GHz only", "5 GHz only"][..],
+            ))
+            .build();
+        match nm::get_wifi_band(&network.ssid).await {
+            Ok(band) => band_row.set_selected(
+                WIFI_BANDS
+                    .iter()
+                    .position(|candidate| *candidate == band)
+                    .unwrap_or(0) as u32,
+            ),
+            Err(e) => log::warn!("Failed to read band for {}: {}", network.ssid, e),
+        }
+
+        let page_band = self.clone();
+        let ssid_band = network.ssid.clone();
+        let connected_band = network.connected;
+        band_row.connect_selected_notify(move |row| {
+            let band = WIFI_BANDS
+                .get(row.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            let page = page_band.clone();
+            let ssid = ssid_band.clone();
+            glib::spawn_future_local(async move {
+                match nm::set_wifi_band(&ssid, band).await {
+                    Ok(()) if connected_band => page.show_toast("Band applies on next connect"),
+                    Ok(()) => {}
+                    Err(e) => {
+                        log::error!("Failed to set band for {}: {}", ssid, e);
+                        page.show_toast(&format!("Failed to update band: {}", e));
+                    }
+                }
+            });
+        });
+
+        // * Lock to the AP we're associated with now, or keep showing the one already pinned
+        let locked_bssid = nm::get_wifi_bssid(&network.ssid).await.unwrap_or_else(|e| {
+            log::warn!("Failed to read BSSID lock for {}: {}", network.ssid, e);
+            None
+        });
+        let current_bssid = if network.connected {
+            nm::list_access_points_for_ssid(&network.ssid)
+                .await
+                .ok()
+                .and_then(|aps| aps.into_iter().find(|ap| ap.active))
+                .map(|ap| ap.bssid)
+        } else {
+            None
+        };
+        let target_bssid = locked_bssid.clone().or(current_bssid);
+
+        let bssid_row = adw::SwitchRow::builder()
+            .title("Lock to this access point")
+            .subtitle(
+                target_bssid
+                    .as_deref()
+                    .unwrap_or("Connect to pick the access point"),
+            )
+            .active(locked_bssid.is_some())
+            .sensitive(target_bssid.is_some())
+            .build();
+
+        let page_bssid = self.clone();
+        let ssid_bssid = network.ssid.clone();
+        let connected_bssid = network.connected;
+        bssid_row.connect_active_notify(move |row| {
+            let bssid = row.is_active().then(|| target_bssid.clone()).flatten();
+            let page = page_bssid.clone();
+            let ssid = ssid_bssid.clone();
+            glib::spawn_future_local(async move {
+                match nm::set_wifi_bssid(&ssid, bssid.as_deref()).await {
+                    Ok(()) if connected_bssid => {
+                        page.show_toast("Access point lock applies on next connect")
+                    }
+                    Ok(()) => {}
+                    Err(e) => {
+                        log::error!("Failed to set BSSID lock for {}: {}", ssid, e);
+                        page.show_toast(&format!("Failed to update access point lock: {}", e));
+                    }
+                }
+            });
+        });
+
+        group.add(&band_row);
+        group.add(&bssid_row);
+    }
+
+    pub async fn show_connected_network_details(&self) {
+        match self.app_state.wifi_connected_network() {
+            Some(network) => self.show_network_info_dialog(&network).await,
+            None => self.show_toast("Not connected to a Wi-Fi network"),
+        }
+    }
+
     fn create_network_row(&self, network: &WifiNetwork) -> adw::ActionRow {
         let row = adw::ActionRow::new();
         row.set_title(&network.ssid);
 
         // Subtitle with details
         let subtitle = if network.band == "Saved" {
-            "Saved network".to_string()
+            match self.app_state.wifi_saved_connection(&network.ssid) {
+                Some(saved) => saved_network_subtitle(&saved, chrono::Utc::now().timestamp()),
+                None => "Saved network".to_string(),
+            }
         } else {
             let signal_text = get_signal_strength_text(network.signal);
             let channel_text = if network.channel == 0 {
@@ -1134,6 +1525,41 @@ impl WifiPage {
             });
 
             menu_box.append(&connect_btn);
+
+            // Connect with WPS (only when an AP for this SSID advertises push-button)
+            if network.secured {
+                let wps_btn = gtk4::Button::builder()
+                    .label("Connect with WPS")
+                    .css_classes(vec!["flat".to_string()])
+                    .build();
+                wps_btn.set_visible(false);
+
+                let page_wps = self.clone();
+                let ssid_wps = network.ssid.clone();
+                let popover_wps = popover.clone();
+
+                wps_btn.connect_clicked(move |_| {
+                    let page = page_wps.clone();
+                    let ssid = ssid_wps.clone();
+                    popover_wps.popdown();
+
+                    glib::spawn_future_local(async move {
+                        page.connect_wps_network(&ssid).await;
+                    });
+                });
+
+                let wps_btn_state = wps_btn.clone();
+                let ssid_check = network.ssid.clone();
+                glib::spawn_future_local(async move {
+                    let supports_wps = nm::list_access_points_for_ssid(&ssid_check)
+                        .await
+                        .map(|aps| aps.iter().any(|ap| ap.supports_wps))
+                        .unwrap_or(false);
+                    wps_btn_state.set_visible(supports_wps);
+                });
+
+                menu_box.append(&wps_btn);
+            }
         }
 
         // Show QR code (only if a saved password exists)
@@ -1190,6 +1616,36 @@ impl WifiPage {
 
         menu_box.append(&info_btn);
 
+        // Duplicate button (saved networks only)
+        let duplicate_btn = gtk4::Button::builder()
+            .label("Duplicate")
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        duplicate_btn.set_visible(false);
+
+        let page_duplicate = self.clone();
+        let ssid_duplicate = network.ssid.clone();
+        let popover_duplicate = popover.clone();
+
+        duplicate_btn.connect_clicked(move |_| {
+            let page = page_duplicate.clone();
+            let ssid = ssid_duplicate.clone();
+            popover_duplicate.popdown();
+
+            glib::spawn_future_local(async move {
+                page.duplicate_network(&ssid).await;
+            });
+        });
+
+        let duplicate_btn_state = duplicate_btn.clone();
+        let ssid_check = network.ssid.clone();
+        glib::spawn_future_local(async move {
+            let is_saved = nm::is_network_saved(&ssid_check).await.unwrap_or(false);
+            duplicate_btn_state.set_visible(is_saved);
+        });
+
+        menu_box.append(&duplicate_btn);
+
         // Forget button
         let forget_btn = gtk4::Button::builder()
             .css_classes(vec!["flat".to_string(), "destructive-action".to_string()])
@@ -1243,7 +1699,8 @@ impl WifiPage {
 
     async fn handle_network_click(&self, network: &WifiNetwork) {
         if !network.secured {
-            self.connect_open_network(&network.ssid).await;
+            self.connect_open_network(&network.ssid, Some(&network.security_type))
+                .await;
         } else {
             // Check if network is saved
             match nm::is_network_saved(&network.ssid).await {
@@ -1265,6 +1722,10 @@ impl WifiPage {
     }
 
     async fn show_password_dialog(&self, network: &WifiNetwork) {
+        if nm::is_enterprise_security(&network.security_type) {
+            self.show_enterprise_dialog(&network.ssid).await;
+            return;
+        }
         self.show_password_dialog_for_ssid(&network.ssid, Some(&network.security_type))
             .await;
     }
@@ -1305,15 +1766,6 @@ impl WifiPage {
         content_box.append(&security_row);
         content_box.append(&password_entry);
 
-        let password_entry_visibility = password_entry.clone();
-        security_row.connect_selected_notify(move |row| {
-            let password_required = row.selected() != 3;
-            password_entry_visibility.set_visible(password_required);
-            if !password_required {
-                password_entry_visibility.set_text("");
-            }
-        });
-
         let dialog = adw::AlertDialog::builder()
             .heading("Connect to Hidden Network")
             .body("Hidden networks do not appear in the scan list until you connect")
@@ -1324,6 +1776,27 @@ impl WifiPage {
         dialog.add_responses(&[("cancel", "Cancel"), ("connect", "Connect")][..]);
         dialog.set_response_appearance("connect", adw::ResponseAppearance::Suggested);
 
+        // * Older NM rejects key-mgmt "sae" with an opaque error; say why before the user tries.
+        let sae_unsupported = nm::capabilities().unsupported_reason(nm::NmFeature::Sae);
+        let password_entry_visibility = password_entry.clone();
+        let dialog_for_security = dialog.clone();
+        security_row.connect_selected_notify(move |row| {
+            let password_required = row.selected() != 3;
+            password_entry_visibility.set_visible(password_required);
+            if !password_required {
+                password_entry_visibility.set_text("");
+            }
+
+            let blocked = if row.selected() == 1 {
+                sae_unsupported.as_deref()
+            } else {
+                None
+            };
+            row.set_subtitle(blocked.unwrap_or(""));
+            row.set_tooltip_text(blocked);
+            dialog_for_security.set_response_enabled("connect", blocked.is_none());
+        });
+
         let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
             dialog.choose_future(Some(parent)).await
         } else {
@@ -1406,18 +1879,22 @@ impl WifiPage {
             .await;
     }
 
-    async fn connect_open_network(&self, ssid: &str) {
+    async fn connect_open_network(&self, ssid: &str, security_type: Option<&str>) {
         let _busy = self.busy_guard("Connecting...");
         self.show_toast("Connecting...");
+        let had_profile = Self::has_saved_profile(ssid).await;
 
-        match nm::connect_open_network(ssid).await {
+        match self
+            .run_cancellable(nm::connect_open_network(ssid, security_type))
+            .await
+        {
             Ok(nm::ConnectStatus::Connected) => {
-                self.show_toast(&format!("Connected to {}", ssid));
-                self.refresh_networks(false).await;
+                self.after_connect(ssid).await;
             }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, had_profile).await,
             Err(e) => {
                 log::error!("Connection failed: {}", e);
-                self.show_toast(&format!("Failed to connect: {}", e));
+                self.show_error("Failed to connect", &e);
             }
         }
     }
@@ -1430,15 +1907,19 @@ impl WifiPage {
     ) {
         let _busy = self.busy_guard("Connecting...");
         self.show_toast("Connecting...");
+        let had_profile = Self::has_saved_profile(ssid).await;
 
-        match nm::connect_secured_network(ssid, password, security_type).await {
+        match self
+            .run_cancellable(nm::connect_secured_network(ssid, password, security_type))
+            .await
+        {
             Ok(nm::ConnectStatus::Connected) => {
-                self.show_toast(&format!("Connected to {}", ssid));
-                self.refresh_networks(false).await;
+                self.after_connect(ssid).await;
             }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, had_profile).await,
             Err(e) => {
                 log::error!("Connection failed: {}", e);
-                self.show_toast(&format!("Failed to connect: {}", e));
+                self.show_error("Failed to connect", &e);
             }
         }
     }
@@ -1451,15 +1932,36 @@ impl WifiPage {
     ) {
         let _busy = self.busy_guard("Connecting...");
         self.show_toast("Connecting to hidden network...");
+        let had_profile = Self::has_saved_profile(ssid).await;
 
-        match nm::connect_hidden_network(ssid, password, security_type).await {
+        match self
+            .run_cancellable(nm::connect_hidden_network(ssid, password, security_type))
+            .await
+        {
             Ok(nm::ConnectStatus::Connected) => {
-                self.show_toast(&format!("Connected to {}", ssid));
-                self.refresh_networks(false).await;
+                self.after_connect(ssid).await;
             }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, had_profile).await,
             Err(e) => {
                 log::error!("Hidden network connection failed: {}", e);
-                self.show_toast(&format!("Failed to connect: {}", e));
+                self.show_error("Failed to connect", &e);
+            }
+        }
+    }
+
+    async fn connect_wps_network(&self, ssid: &str) {
+        let _busy = self.busy_guard("Waiting for WPS...");
+        self.show_toast("Press the WPS button on your router within two minutes");
+        let had_profile = Self::has_saved_profile(ssid).await;
+
+        match self.run_cancellable(nm::connect_wps_pbc(ssid)).await {
+            Ok(nm::ConnectStatus::Connected) => {
+                self.after_connect(ssid).await;
+            }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, had_profile).await,
+            Err(e) => {
+                log::error!("WPS connection failed: {}", e);
+                self.show_error("WPS connection failed", &e);
             }
         }
     }
@@ -1467,15 +1969,16 @@ impl WifiPage {
     async fn connect_saved_network(&self, ssid: &str) {
         let _busy = self.busy_guard("Connecting...");
         self.show_toast("Connecting...");
-        let activation_result = nm::activate_saved_connection(ssid).await;
+        let activation_result = self
+            .run_cancellable(nm::activate_saved_connection(ssid))
+            .await;
         match activation_result {
             Ok(nm::ConnectStatus::Connected) => {
-                self.show_toast(&format!("Connected to {}", ssid));
-                self.refresh_networks(false).await;
+                self.after_connect(ssid).await;
             }
+            Err(e) if is_cancelled(&e) => self.abort_activation(ssid, true).await,
             Err(e) => {
-                let err_text = e.to_string();
-                if nm::is_network_not_found_error(&err_text) {
+                if nm::NmError::is_not_found(&e) {
                     // Fallback: ask for password explicitly (saved secret reads are disabled).
                     let security_type = self
                         .app_state
@@ -1491,7 +1994,105 @@ impl WifiPage {
                 }
 
                 log::error!("Connection failed: {}", e);
-                self.show_toast(&format!("Failed to connect: {}", e));
+                self.show_error("Failed to connect", &e);
+            }
+        }
+    }
+
+    // * Every successful connect ends here, whichever page or menu started it.
+    async fn after_connect(&self, ssid: &str) {
+        self.show_toast(&format!("Connected to {}", ssid));
+        self.apply_trust_profile(ssid).await;
+        self.watch_captive_portal(ssid);
+        self.refresh_networks(false).await;
+    }
+
+    fn watch_captive_portal(&self, ssid: &str) {
+        let page = self.clone();
+        let ssid = ssid.to_string();
+        glib::spawn_future_local(async move {
+            match captive_portal::handle_portal_after_connect(&ssid).await {
+                Ok(PortalOutcome::NoPortal) => {}
+                Ok(PortalOutcome::FormSubmitted(ssid)) => {
+                    page.show_toast(&format!("Signed in to {} portal", ssid));
+                }
+                Ok(PortalOutcome::ConfirmForm(entry)) => page.confirm_portal_form(entry).await,
+                Ok(PortalOutcome::OpenSignIn(url)) => {
+                    page.show_toast(&format!("{} requires sign-in", ssid));
+                    page.open_sign_in_page(&url).await;
+                }
+                Ok(PortalOutcome::Remembered(ssid)) => {
+                    page.show_toast(&format!("{} requires sign-in", ssid));
+                }
+                Err(e) => log::warn!("Captive portal check failed for {}: {}", ssid, e),
+            }
+        });
+    }
+
+    async fn confirm_portal_form(&self, entry: CaptivePortalEntry) {
+        let Some(form) = entry.form.as_ref() else {
+            return;
+        };
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("Sign in to {}?", entry.ssid))
+            .body(format!(
+                "Send the remembered sign-in form to {}? Only do this on a network you trust; \
+anyone can broadcast the same name.",
+                form.action_url
+            ))
+            .default_response("submit")
+            .close_response("cancel")
+            .build();
+        dialog.add_responses(
+            &[
+                ("cancel", "Not Now"),
+                ("browser", "Open Sign-in Page"),
+                ("submit", "Submit"),
+            ][..],
+        );
+        dialog.set_response_appearance("submit", adw::ResponseAppearance::Suggested);
+
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
+        match response.as_str() {
+            "submit" => match captive_portal::submit_portal_form(form).await {
+                Ok(()) => {
+                    nm::check_connectivity().await.ok();
+                    self.show_toast(&format!("Signed in to {} portal", entry.ssid));
+                }
+                Err(e) => {
+                    log::warn!("Portal form for {} failed: {}", entry.ssid, e);
+                    self.show_toast(&format!("Portal sign-in failed: {}", e));
+                    self.open_sign_in_page(entry.sign_in_url()).await;
+                }
+            },
+            "browser" => self.open_sign_in_page(entry.sign_in_url()).await,
+            _ => {}
+        }
+    }
+
+    async fn open_sign_in_page(&self, url: &str) {
+        let parent = self
+            .widget
+            .root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok());
+        let launcher = gtk4::UriLauncher::new(url);
+        if let Err(e) = launcher.launch_future(parent.as_ref()).await {
+            log::warn!("Failed to open sign-in page {}: {}", url, e);
+            self.show_toast(&format!("Failed to open sign-in page: {}", e));
+        }
+    }
+
+    async fn apply_trust_profile(&self, ssid: &str) {
+        match network_trust::apply_trust_profile_for_ssid(ssid).await {
+            Ok(true) => self.show_toast(&format!("Applied trust profile for {}", ssid)),
+            Ok(false) => {}
+            Err(e) => {
+                log::warn!("Failed to apply trust profile for {}: {}", ssid, e);
+                self.show_toast(&format!("Failed to apply trust profile: {}", e));
             }
         }
     }
@@ -1520,22 +2121,15 @@ impl WifiPage {
     }
 
     async fn forget_network(&self, ssid: &str) {
-        let dialog = adw::AlertDialog::builder()
-            .heading("Forget Network?")
-            .body(format!("This will remove {} from saved networks.", ssid))
-            .default_response("forget")
-            .close_response("cancel")
-            .build();
-        dialog.add_responses(&[("cancel", "Cancel"), ("forget", "Forget")][..]);
-        dialog.set_response_appearance("forget", adw::ResponseAppearance::Destructive);
-
-        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
-            dialog.choose_future(Some(parent)).await
-        } else {
-            dialog.choose_future(None::<&gtk4::Window>).await
-        };
-
-        if response.as_str() != "forget" {
+        let confirmed = confirm_dialog(
+            &self.widget,
+            "Forget Network?",
+            &format!("This will remove {} from saved networks.", ssid),
+            "Forget",
+            true,
+        )
+        .await;
+        if !confirmed {
             return;
         }
 
@@ -1551,6 +2145,21 @@ impl WifiPage {
         }
     }
 
+    // * The copy shows up under the Saved filter; edit its IP or DNS there to make the variant.
+    async fn duplicate_network(&self, ssid: &str) {
+        match nm::duplicate_connection(ssid).await {
+            Ok(name) => {
+                self.show_toast(&format!("Created {}", name));
+                self.load_saved_connections().await;
+                self.update_filtered_networks();
+            }
+            Err(e) => {
+                log::error!("Failed to duplicate {}: {}", ssid, e);
+                self.show_toast(&format!("Failed to duplicate network: {}", e));
+            }
+        }
+    }
+
     async fn show_qr_code(&self, network: &WifiNetwork) {
         let password = if network.secured {
             match self.prompt_sudo_for_wifi_password(&network.ssid).await {
@@ -1579,6 +2188,13 @@ impl WifiPage {
             return None;
         }
 
+        // * Ask NetworkManager first; sudo is only needed when polkit refuses the secrets
+        match nm::get_saved_wifi_password(ssid).await {
+            Ok(Some(password)) => return Some(password),
+            Ok(None) => {}
+            Err(e) => log::info!("Saved password for {} not readable over D-Bus: {}", ssid, e),
+        }
+
         let sudo_entry = adw::PasswordEntryRow::builder()
             .title("sudo password")
             .activates_default(true)
@@ -1601,12 +2217,11 @@ impl WifiPage {
         dialog.add_responses(&[("cancel", "Cancel"), ("generate", "Generate")][..]);
         dialog.set_response_appearance("generate", adw::ResponseAppearance::Suggested);
 
-        let response =
-            if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
-                dialog.choose_future(Some(parent)).await
-            } else {
-                dialog.choose_future(None::<&gtk4::Window>).await
-            };
+        let response = if let Some(parent) = self.widget.root().and_downcast_ref::<gtk4::Window>() {
+            dialog.choose_future(Some(parent)).await
+        } else {
+            dialog.choose_future(None::<&gtk4::Window>).await
+        };
 
         if response.as_str() != "generate" {
             return None;
@@ -1628,7 +2243,8 @@ impl WifiPage {
     }
 
     async fn show_network_info_dialog(&self, network: &WifiNetwork) {
-        let info = nm::get_network_info(&network.ssid).await.ok();
+        let device = network.device.as_deref().filter(|_| network.connected);
+        let info = nm::get_network_info_on(&network.ssid, device).await.ok();
         let is_saved = nm::is_network_saved(&network.ssid).await.unwrap_or(false);
 
         let dialog = adw::Dialog::builder()
@@ -1811,10 +2427,221 @@ impl WifiPage {
                 });
             });
 
+            let priority_row = adw::SpinRow::with_range(
+                f64::from(*nm::AUTOCONNECT_PRIORITY_RANGE.start()),
+                f64::from(*nm::AUTOCONNECT_PRIORITY_RANGE.end()),
+                1.0,
+            );
+            priority_row.set_title("Priority");
+            priority_row.set_subtitle("Higher wins when several saved networks are in range");
+            match nm::get_autoconnect_priority(&network.ssid).await {
+                Ok(priority) => priority_row.set_value(f64::from(priority)),
+                Err(e) => log::warn!("Failed to read autoconnect priority: {}", e),
+            }
+
+            // * Spin clicks arrive one by one; only the last value within the delay is written.
+            let priority_generation = Rc::new(std::cell::Cell::new(0u32));
+            let page_priority = self.clone();
+            let ssid_priority = network.ssid.clone();
+            priority_row.connect_value_notify(move |row| {
+                let generation = priority_generation.get().wrapping_add(1);
+                priority_generation.set(generation);
+                let priority_generation = priority_generation.clone();
+                let page = page_priority.clone();
+                let ssid = ssid_priority.clone();
+                let priority = row.value() as i32;
+
+                glib::spawn_future_local(async move {
+                    glib::timeout_future(std::time::Duration::from_millis(600)).await;
+                    if priority_generation.get() != generation {
+                        return;
+                    }
+                    if let Err(e) = nm::set_autoconnect_priority(&ssid, priority).await {
+                        log::error!("Failed to set autoconnect priority: {}", e);
+                        page.show_toast(&format!("Failed to update priority: {}", e));
+                    }
+                });
+            });
+
+            let metered_row = adw::SwitchRow::builder()
+                .title("Metered connection")
+                .subtitle("Tell apps to limit background data, e.g. on phone tethering")
+                .build();
+            match nm::get_metered(&network.ssid).await {
+                Ok(metered) => metered_row.set_active(metered),
+                Err(e) => log::warn!("Failed to read metered flag: {}", e),
+            }
+
+            let page_metered = self.clone();
+            let ssid_metered = network.ssid.clone();
+            metered_row.connect_active_notify(move |row| {
+                let page = page_metered.clone();
+                let ssid = ssid_metered.clone();
+                let metered = row.is_active();
+
+                glib::spawn_future_local(async move {
+                    if let Err(e) = nm::set_metered(&ssid, metered).await {
+                        log::error!("Failed to set metered flag: {}", e);
+                        page.show_toast(&format!("Failed to update metered setting: {}", e));
+                    }
+                });
+            });
+
+            let advanced_row = connection_editor::advanced_settings_row();
+            let ssid_advanced = network.ssid.clone();
+            let connected_advanced = network.connected;
+            advanced_row.connect_activated(move |row| {
+                let row = row.clone();
+                let ssid = ssid_advanced.clone();
+                glib::spawn_future_local(async move {
+                    connection_editor::show_advanced_editor(
+                        row.upcast_ref(),
+                        &ssid,
+                        connected_advanced,
+                    )
+                    .await;
+                });
+            });
+
             auto_group.add(&auto_row);
+            auto_group.add(&priority_row);
+            auto_group.add(&metered_row);
+            self.add_roaming_lock_rows(&auto_group, network).await;
+            connection_editor::add_mac_address_rows(
+                &auto_group,
+                &network.ssid,
+                network.connected,
+                &self.toast_overlay,
+            )
+            .await;
+            connection_editor::add_mtu_row(
+                &auto_group,
+                &network.ssid,
+                network.connected,
+                &self.toast_overlay,
+            )
+            .await;
+            connection_editor::add_firewall_zone_row(
+                &auto_group,
+                &network.ssid,
+                network.connected,
+                &self.toast_overlay,
+            )
+            .await;
+            auto_group.add(&advanced_row);
             info_box.append(&auto_group);
         }
 
+        // Network trust (zone + DNS remembered per SSID)
+        if is_saved {
+            let trust_group = adw::PreferencesGroup::builder()
+                .title("Network trust")
+                .description("Firewall zone and DNS to apply whenever you connect to this network")
+                .build();
+
+            let existing = network_trust::trust_profile_for_ssid(&network.ssid)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to load trust profiles: {}", e);
+                    None
+                })
+                .unwrap_or_default();
+
+            let apply_row = adw::SwitchRow::builder()
+                .title("Apply on connect")
+                .active(existing.apply_on_connect)
+                .build();
+            let zone_entry = adw::EntryRow::builder().title("Firewall zone").build();
+            zone_entry.set_text(existing.zone.as_deref().unwrap_or_default());
+            let trust_dns_entry = adw::EntryRow::builder().title("DNS servers").build();
+            trust_dns_entry.set_text(&existing.dns_servers.join(", "));
+            let trust_search_entry = adw::EntryRow::builder().title("Search domains").build();
+            trust_search_entry.set_text(&existing.dns_search_domains.join(", "));
+
+            let save_button = gtk4::Button::builder()
+                .label("Save")
+                .valign(gtk4::Align::Center)
+                .build();
+            let save_row = adw::ActionRow::builder()
+                .title("Remember for this network")
+                .subtitle("Leave fields empty to keep the connection's own settings")
+                .build();
+            save_row.add_suffix(&save_button);
+            save_row.set_activatable_widget(Some(&save_button));
+
+            let page_trust = self.clone();
+            let ssid_trust = network.ssid.clone();
+            let apply_row_trust = apply_row.clone();
+            let zone_entry_trust = zone_entry.clone();
+            let dns_entry_trust = trust_dns_entry.clone();
+            let search_entry_trust = trust_search_entry.clone();
+            save_button.connect_clicked(move |_| {
+                let dns_servers = parse_entry_list(&dns_entry_trust.text());
+                let invalid = invalid_ip_entries(&dns_servers);
+                if !invalid.is_empty() {
+                    page_trust.show_toast(&format!("Invalid DNS IP: {}", invalid.join(", ")));
+                    return;
+                }
+
+                let zone = zone_entry_trust.text().trim().to_string();
+                let profile = NetworkTrustProfile {
+                    ssid: ssid_trust.clone(),
+                    zone: (!zone.is_empty()).then_some(zone),
+                    dns_servers,
+                    dns_search_domains: parse_entry_list(&search_entry_trust.text()),
+                    apply_on_connect: apply_row_trust.is_active(),
+                };
+                let page = page_trust.clone();
+
+                glib::spawn_future_local(async move {
+                    match network_trust::store_trust_profile(profile).await {
+                        Ok(()) => page.show_toast("Network trust profile saved"),
+                        Err(e) => {
+                            log::error!("Failed to save trust profile: {}", e);
+                            page.show_toast(&format!("Failed to save trust profile: {}", e));
+                        }
+                    }
+                });
+            });
+
+            trust_group.add(&apply_row);
+            trust_group.add(&zone_entry);
+            trust_group.add(&trust_dns_entry);
+            trust_group.add(&trust_search_entry);
+            trust_group.add(&save_row);
+            info_box.append(&trust_group);
+        }
+
+        // Captive portal (only once a portal has been seen on this network)
+        let portal_entry = captive_portal::portal_entry_for_ssid(&network.ssid)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load captive portal entries: {}", e);
+                None
+            });
+        if let Some(entry) = portal_entry {
+            info_box.append(&self.build_captive_portal_group(entry));
+        }
+
+        if is_saved {
+            info_box.append(
+                &connection_editor::ipv4_settings_group(
+                    &network.ssid,
+                    network.connected,
+                    &self.toast_overlay,
+                )
+                .await,
+            );
+            info_box.append(
+                &connection_editor::routes_group(
+                    &network.ssid,
+                    network.connected,
+                    &self.toast_overlay,
+                )
+                .await,
+            );
+        }
+
         // Custom DNS (active connection only)
         let dns_group = adw::PreferencesGroup::builder().title("Custom DNS").build();
 
@@ -1828,6 +2655,56 @@ impl WifiPage {
 
         let search_entry = adw::EntryRow::builder().title("Search domains").build();
 
+        let secure_dns_model =
+            gtk4::StringList::new(&["System default", "Off", "When supported", "Required"][..]);
+        let secure_dns_row = adw::ComboRow::builder()
+            .title("Secure DNS")
+            .subtitle("DNS-over-TLS through systemd-resolved")
+            .model(&secure_dns_model)
+            .visible(is_saved)
+            .build();
+        if is_saved {
+            match nm::get_dns_over_tls(&network.ssid).await {
+                Ok(mode) => secure_dns_row.set_selected(
+                    DNS_OVER_TLS_MODES
+                        .iter()
+                        .position(|candidate| *candidate == mode)
+                        .unwrap_or(0) as u32,
+                ),
+                Err(e) => log::warn!("Failed to read DNS-over-TLS for {}: {}", network.ssid, e),
+            }
+        }
+
+        // * What resolved really does for the link; the profile setting alone can't tell
+        let encryption_row = adw::ActionRow::builder()
+            .title("DNS encryption")
+            .subtitle("Checking…")
+            .visible(network.connected)
+            .build();
+        let refresh_encryption = {
+            let encryption_row = encryption_row.clone();
+            move || {
+                let encryption_row = encryption_row.clone();
+                glib::spawn_future_local(async move {
+                    let status = match nm::get_active_wifi_interface().await {
+                        Ok(Some(iface)) => resolved::dns_encryption_for_interface(&iface).await,
+                        Ok(None) => Ok(resolved::DnsEncryption::Unavailable),
+                        Err(e) => Err(e),
+                    };
+                    match status {
+                        Ok(status) => encryption_row.set_subtitle(status.label()),
+                        Err(e) => {
+                            log::warn!("Failed to read DNS encryption status: {}", e);
+                            encryption_row.set_subtitle("Unknown");
+                        }
+                    }
+                });
+            }
+        };
+        if network.connected {
+            refresh_encryption();
+        }
+
         let apply_button = gtk4::Button::builder()
             .label("Apply")
             .css_classes(vec!["suggested-action".to_string()])
@@ -1850,6 +2727,7 @@ impl WifiPage {
         let connected_apply = network.connected;
         let dns_entry_apply = dns_entry.clone();
         let search_entry_apply = search_entry.clone();
+        let secure_dns_apply = secure_dns_row.clone();
         apply_button.connect_clicked(move |_| {
             if !connected_apply {
                 page_apply.show_toast("Connect to this network to apply DNS");
@@ -1871,8 +2749,15 @@ impl WifiPage {
             }
 
             let search_domains = parse_entry_list(&search_text);
+            let secure_dns = secure_dns_apply.is_visible().then(|| {
+                DNS_OVER_TLS_MODES
+                    .get(secure_dns_apply.selected() as usize)
+                    .copied()
+                    .unwrap_or_default()
+            });
             let page = page_apply.clone();
             let ssid = ssid_apply.clone();
+            let refresh_encryption = refresh_encryption.clone();
 
             glib::spawn_future_local(async move {
                 match nm::get_active_connection_name().await {
@@ -1881,21 +2766,33 @@ impl WifiPage {
                             page.show_toast("Active connection does not match this network");
                             return;
                         }
-                        if let Err(e) = nm::set_custom_ipv4_dns_for_connection(
+                        let change = async {
+                            nm::set_custom_ipv4_dns_for_connection(
+                                &active,
+                                &dns_servers,
+                                &search_domains,
+                            )
+                            .await?;
+                            match secure_dns {
+                                Some(mode) => nm::set_dns_over_tls(&active, mode).await,
+                                None => Ok(()),
+                            }
+                        };
+                        match connection_editor::apply_with_rollback(
+                            &page.widget,
                             &active,
-                            &dns_servers,
-                            &search_domains,
+                            true,
+                            change,
                         )
                         .await
                         {
-                            page.show_toast(&format!("Failed to set DNS: {}", e));
-                            return;
-                        }
-                        if let Err(e) = nm::reapply_connection(&active).await {
-                            page.show_toast(&format!("Failed to apply connection: {}", e));
-                            return;
+                            Ok(true) => {
+                                page.show_toast("Custom DNS applied");
+                                refresh_encryption();
+                            }
+                            Ok(false) => page.show_toast("DNS change reverted"),
+                            Err(e) => page.show_toast(&format!("Failed to set DNS: {}", e)),
                         }
-                        page.show_toast("Custom DNS applied");
                     }
                     Ok(std::prelude::v1::None) => {
                         page.show_toast("No active connection found");
@@ -1909,9 +2806,123 @@ impl WifiPage {
 
         dns_group.add(&dns_entry);
         dns_group.add(&search_entry);
+        dns_group.add(&secure_dns_row);
+        dns_group.add(&encryption_row);
         dns_group.add(&apply_row);
         info_box.append(&dns_group);
 
+        // Adapter power save (active connection only)
+        if network.connected {
+            if let Ok(Some(iface)) = nm::get_active_wifi_interface().await {
+                let adapter_group = adw::PreferencesGroup::builder()
+                    .title("Adapter")
+                    .description("Power saving can cause latency spikes on some drivers")
+                    .build();
+                let power_save_row = adw::SwitchRow::builder().title("Power saving").build();
+
+                match nm::get_wifi_power_save(&iface).await {
+                    Ok(enabled) => {
+                        power_save_row.set_active(enabled);
+                        power_save_row.set_subtitle(&iface);
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to read power save for {}: {}", iface, e);
+                        power_save_row.set_subtitle(&format!("{} • state unknown", iface));
+                        power_save_row.set_sensitive(false);
+                    }
+                }
+
+                let page_power = self.clone();
+                let reverting = Rc::new(std::cell::Cell::new(false));
+                power_save_row.connect_active_notify(move |row| {
+                    if reverting.get() {
+                        return;
+                    }
+                    let page = page_power.clone();
+                    let iface = iface.clone();
+                    let row = row.clone();
+                    let reverting = reverting.clone();
+                    let enabled = row.is_active();
+                    row.set_sensitive(false);
+
+                    glib::spawn_future_local(async move {
+                        let result = nm::set_wifi_power_save(&iface, enabled).await;
+                        row.set_sensitive(true);
+                        match result {
+                            Ok(()) => page.show_toast(if enabled {
+                                "Power saving enabled"
+                            } else {
+                                "Power saving disabled"
+                            }),
+                            Err(e) => {
+                                log::error!("Failed to set power save on {}: {}", iface, e);
+                                page.show_toast(&format!("Failed to change power saving: {}", e));
+                                reverting.set(true);
+                                row.set_active(!enabled);
+                                reverting.set(false);
+                            }
+                        }
+                    });
+                });
+
+                adapter_group.add(&power_save_row);
+                info_box.append(&adapter_group);
+            }
+        }
+
+        // Reachability test (active connection only)
+        if network.connected {
+            let reach_group = adw::PreferencesGroup::builder()
+                .title("Reachability")
+                .description("Ping a host, or TCP-connect when a port is given")
+                .build();
+
+            let host_entry = adw::EntryRow::builder().title("Host or host:port").build();
+
+            let test_button = gtk4::Button::builder()
+                .label("Test")
+                .valign(gtk4::Align::Center)
+                .build();
+            let result_row = adw::ActionRow::builder()
+                .title("Result")
+                .subtitle("Not tested yet")
+                .build();
+            result_row.add_suffix(&test_button);
+
+            let page_reach = self.clone();
+            let host_entry_reach = host_entry.clone();
+            let result_row_reach = result_row.clone();
+            let test_button_reach = test_button.clone();
+            let run_test = move || {
+                let target = match reachability::parse_target(&host_entry_reach.text()) {
+                    Ok(target) => target,
+                    Err(e) => {
+                        page_reach.show_toast(&e.to_string());
+                        return;
+                    }
+                };
+
+                let result_row = result_row_reach.clone();
+                let test_button = test_button_reach.clone();
+                test_button.set_sensitive(false);
+                result_row.set_subtitle(&format!("Testing {}...", target.label()));
+
+                glib::spawn_future_local(async move {
+                    let result = reachability::check_reachability(&target).await;
+                    result_row.set_subtitle(&format!("{}: {}", target.label(), result.summary()));
+                    test_button.set_sensitive(true);
+                });
+            };
+
+            let run_test_click = run_test.clone();
+            test_button.connect_clicked(move |_| run_test_click());
+            host_entry.connect_entry_activated(move |_| run_test());
+
+            reach_group.add(&host_entry);
+            reach_group.add(&result_row);
+            info_box.append(&reach_group);
+        }
+
         // Info items section
         let info_section = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
 
@@ -1980,6 +2991,15 @@ impl WifiPage {
 
         info_box.append(&info_section);
 
+        // Access points (every BSSID broadcasting this SSID)
+        match nm::list_access_points_for_ssid(&network.ssid).await {
+            Ok(access_points) if !access_points.is_empty() => {
+                info_box.append(&Self::build_access_points_group(&access_points));
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to list access points for {}: {}", network.ssid, e),
+        }
+
         // Network details section
         let details_header = gtk4::Label::new(Some("Network details"));
         details_header.set_xalign(0.0);
@@ -2071,9 +3091,7 @@ impl WifiPage {
         // * Password section — only for saved secured networks.
         // * User enters sudo password → we reveal the wifi password with copy button.
         if is_saved && network.secured {
-            let password_group = adw::PreferencesGroup::builder()
-                .title("Password")
-                .build();
+            let password_group = adw::PreferencesGroup::builder().title("Password").build();
 
             // ! Revealed password is stored only in memory — never written to disk here
             let revealed_password: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
@@ -2174,6 +3192,221 @@ impl WifiPage {
         }
     }
 
+    fn build_access_points_group(access_points: &[nm::AccessPoint]) -> adw::PreferencesGroup {
+        let ap_group = adw::PreferencesGroup::builder()
+            .title("Access Points")
+            .build();
+        if access_points.len() > 1 {
+            ap_group.set_description(Some(&format!(
+                "{} radios broadcast this network",
+                access_points.len()
+            )));
+        }
+
+        for ap in access_points {
+            let row = adw::ActionRow::builder()
+                .title(&ap.bssid)
+                .subtitle(format!(
+                    "Channel {} · {} · {} MHz · {}%",
+                    ap.channel, ap.band, ap.freq_mhz, ap.signal
+                ))
+                .build();
+            row.set_subtitle_selectable(true);
+
+            let signal_icon = gtk4::Image::from_icon_name(icon_name(
+                get_signal_icon(ap.signal),
+                &["network-wireless-symbolic", "network-wireless"][..],
+            ));
+            row.add_prefix(&signal_icon);
+
+            if ap.active {
+                let connected_label = gtk4::Label::new(Some("Connected"));
+                connected_label.add_css_class("dim-label");
+                row.add_suffix(&connected_label);
+            }
+            ap_group.add(&row);
+        }
+
+        ap_group
+    }
+
+    fn build_captive_portal_group(&self, entry: CaptivePortalEntry) -> adw::PreferencesGroup {
+        let portal_group = adw::PreferencesGroup::builder()
+            .title("Captive portal")
+            .description(
+                "A remembered sign-in form is stored unencrypted in your config folder. \
+Only simple form POSTs work; logins with JavaScript, captchas or multi-step auth need the browser.",
+            )
+            .build();
+
+        let auto_open_row = adw::SwitchRow::builder()
+            .title("Open sign-in page on connect")
+            .active(entry.auto_open)
+            .build();
+        let portal_url_entry = adw::EntryRow::builder().title("Sign-in page").build();
+        portal_url_entry.set_text(entry.portal_url.as_deref().unwrap_or_default());
+
+        let form = entry.form.clone().unwrap_or_default();
+        let form_url_entry = adw::EntryRow::builder()
+            .title("Auto-submit form URL (optional)")
+            .build();
+        form_url_entry.set_text(&form.action_url);
+        let auto_submit_row = adw::SwitchRow::builder()
+            .title("Submit form without asking")
+            .subtitle(format!(
+                "Any network named {} would receive these fields",
+                entry.ssid
+            ))
+            .active(form.auto_submit)
+            .build();
+        // * Values may hold commas or spaces, so fields go one per line in a text view
+        let form_fields_view = gtk4::TextView::builder()
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .top_margin(8)
+            .bottom_margin(8)
+            .left_margin(8)
+            .right_margin(8)
+            .build();
+        form_fields_view
+            .buffer()
+            .set_text(&captive_portal::format_form_fields(&form.fields));
+        let form_fields_scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .min_content_height(80)
+            .child(&form_fields_view)
+            .build();
+        form_fields_scrolled.add_css_class("card");
+        let form_fields_label = gtk4::Label::builder()
+            .label("Form fields, one name=value per line")
+            .xalign(0.0)
+            .css_classes(vec!["dim-label".to_string()])
+            .build();
+        let form_fields_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        form_fields_box.append(&form_fields_label);
+        form_fields_box.append(&form_fields_scrolled);
+        let form_fields_row = adw::PreferencesRow::builder()
+            .activatable(false)
+            .child(&form_fields_box)
+            .build();
+
+        let save_button = gtk4::Button::builder()
+            .label("Save")
+            .valign(gtk4::Align::Center)
+            .build();
+        let forget_button = gtk4::Button::builder()
+            .label("Forget")
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["destructive-action".to_string()])
+            .build();
+        let save_row = adw::ActionRow::builder()
+            .title("Remember portal")
+            .subtitle(
+                match entry
+                    .last_seen
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                {
+                    Some(seen) => format!(
+                        "Last seen {}",
+                        seen.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                    ),
+                    None => "Portal detected on this network".to_string(),
+                },
+            )
+            .build();
+        save_row.add_suffix(&forget_button);
+        save_row.add_suffix(&save_button);
+
+        let page_portal = self.clone();
+        let entry_portal = entry.clone();
+        let auto_open_portal = auto_open_row.clone();
+        let portal_url_portal = portal_url_entry.clone();
+        let form_url_portal = form_url_entry.clone();
+        let form_fields_portal = form_fields_view.buffer();
+        let auto_submit_portal = auto_submit_row.clone();
+        save_button.connect_clicked(move |_| {
+            let portal_url = portal_url_portal.text().trim().to_string();
+            let form_url = form_url_portal.text().trim().to_string();
+            for url in [&portal_url, &form_url] {
+                if !url.is_empty() {
+                    if let Err(e) = captive_portal::validate_form_url(url) {
+                        page_portal.show_toast(&e.to_string());
+                        return;
+                    }
+                }
+            }
+            let text = form_fields_portal.text(
+                &form_fields_portal.start_iter(),
+                &form_fields_portal.end_iter(),
+                false,
+            );
+            let fields = match captive_portal::parse_form_fields(&text) {
+                Ok(fields) => fields,
+                Err(e) => {
+                    page_portal.show_toast(&e.to_string());
+                    return;
+                }
+            };
+
+            let updated = CaptivePortalEntry {
+                portal_url: (!portal_url.is_empty()).then_some(portal_url),
+                auto_open: auto_open_portal.is_active(),
+                form: (!form_url.is_empty()).then_some(PortalForm {
+                    action_url: form_url,
+                    fields,
+                    auto_submit: auto_submit_portal.is_active(),
+                }),
+                ..entry_portal.clone()
+            };
+            let page = page_portal.clone();
+            glib::spawn_future_local(async move {
+                match captive_portal::store_portal_entry(updated).await {
+                    Ok(()) => page.show_toast("Captive portal settings saved"),
+                    Err(e) => {
+                        log::error!("Failed to save captive portal entry: {}", e);
+                        page.show_toast(&format!("Failed to save portal settings: {}", e));
+                    }
+                }
+            });
+        });
+
+        let page_forget = self.clone();
+        let ssid_forget = entry.ssid.clone();
+        let group_forget = portal_group.clone();
+        forget_button.connect_clicked(move |_| {
+            let page = page_forget.clone();
+            let ssid = ssid_forget.clone();
+            let group = group_forget.clone();
+            glib::spawn_future_local(async move {
+                match captive_portal::forget_portal_entry(&ssid).await {
+                    Ok(()) => {
+                        group.set_visible(false);
+                        page.show_toast("Captive portal forgotten");
+                    }
+                    Err(e) => {
+                        log::error!("Failed to forget captive portal entry: {}", e);
+                        page.show_toast(&format!("Failed to forget portal: {}", e));
+                    }
+                }
+            });
+        });
+
+        portal_group.add(&auto_open_row);
+        portal_group.add(&portal_url_entry);
+        portal_group.add(&form_url_entry);
+        portal_group.add(&form_fields_row);
+        portal_group.add(&auto_submit_row);
+        portal_group.add(&save_row);
+        portal_group
+    }
+
     fn clear_networks(&self) {
         while let Some(child) = self.known_list.first_child() {
             self.known_list.remove(&child);
@@ -2195,4 +3428,8 @@ impl WifiPage {
     fn show_toast(&self, message: &str) {
         common::show_toast(&self.toast_overlay, message);
     }
+
+    fn show_error(&self, prefix: &str, error: &anyhow::Error) {
+        common::show_error(&self.toast_overlay, prefix, error);
+    }
 }
@@ -0,0 +1,103 @@
+// * ./src/ui/wifi_page/scan_diff.rs
+
+use std::collections::HashSet;
+
+use crate::nm::WifiNetwork;
+use crate::state::WifiFilterState;
+
+// * What the list showed last time, so a filter or search change is not mistaken for a scan change.
+pub(super) struct ScanSnapshot {
+    pub(super) filter: WifiFilterState,
+    pub(super) search: String,
+    pub(super) networks: Vec<WifiNetwork>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ScanDiff {
+    pub(super) appeared: HashSet<String>,
+    pub(super) departed: Vec<WifiNetwork>,
+}
+
+// * Keyed by SSID; the first render after a view change highlights nothing.
+pub(super) fn diff_scan(previous: Option<&ScanSnapshot>, current: &ScanSnapshot) -> ScanDiff {
+    let Some(previous) = previous else {
+        return ScanDiff::default();
+    };
+    if previous.filter != current.filter || previous.search != current.search {
+        return ScanDiff::default();
+    }
+
+    let visible = |networks: &[WifiNetwork]| -> HashSet<String> {
+        networks
+            .iter()
+            .filter(|n| !n.ssid.is_empty() && n.band != "Saved")
+            .map(|n| n.ssid.clone())
+            .collect()
+    };
+    let before = visible(&previous.networks);
+    let after = visible(&current.networks);
+
+    let mut seen = HashSet::new();
+    let departed = previous
+        .networks
+        .iter()
+        .filter(|n| before.contains(&n.ssid) && !after.contains(&n.ssid))
+        .filter(|n| seen.insert(n.ssid.clone()))
+        .cloned()
+        .collect();
+
+    ScanDiff {
+        appeared: after.difference(&before).cloned().collect(),
+        departed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(ssid: &str) -> WifiNetwork {
+        WifiNetwork {
+            ssid: ssid.to_string(),
+            signal: 60,
+            secured: true,
+            connected: false,
+            band: "2.4 GHz".to_string(),
+            channel: 6,
+            freq_mhz: 2437,
+            security_type: "WPA2".to_string(),
+            device: None,
+        }
+    }
+
+    fn snapshot(filter: WifiFilterState, ssids: &[&str]) -> ScanSnapshot {
+        ScanSnapshot {
+            filter,
+            search: String::new(),
+            networks: ssids.iter().map(|s| network(s)).collect(),
+        }
+    }
+
+    #[test]
+    fn reports_new_and_departed_ssids() {
+        let before = snapshot(WifiFilterState::All, &["Home", "Cafe", "Cafe"]);
+        let after = snapshot(WifiFilterState::All, &["Home", "Office"]);
+
+        let diff = diff_scan(Some(&before), &after);
+
+        assert_eq!(diff.appeared, HashSet::from(["Office".to_string()]));
+        assert_eq!(diff.departed.len(), 1);
+        assert_eq!(diff.departed[0].ssid, "Cafe");
+    }
+
+    #[test]
+    fn ignores_first_render_and_view_changes() {
+        let after = snapshot(WifiFilterState::All, &["Home"]);
+        assert!(diff_scan(None, &after).appeared.is_empty());
+
+        let before = snapshot(WifiFilterState::Band5, &[]);
+        let diff = diff_scan(Some(&before), &after);
+        assert!(diff.appeared.is_empty());
+        assert!(diff.departed.is_empty());
+    }
+}
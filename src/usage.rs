@@ -0,0 +1,428 @@
+// * ./src/usage.rs
+
+use anyhow::Result;
+use chrono::{Datelike, Duration as DateDuration, Local, Months, NaiveDate};
+use gtk4::glib;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tokio::fs;
+
+use crate::nm::{self, DeviceType, NetworkManager};
+use crate::traffic::{TrafficEvent, TrafficMonitor};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const FLUSH_INTERVAL_SECS: u32 = 60;
+// * A bit over a year so the month view always has full months to show
+const RETENTION_DAYS: i64 = 400;
+
+// * One row per day, interface and Wi-Fi network; wired links leave `ssid` empty.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageRecord {
+    pub date: String,
+    pub interface: String,
+    #[serde(default)]
+    pub ssid: Option<String>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+impl UsageRecord {
+    pub fn network_label(&self) -> &str {
+        self.ssid.as_deref().unwrap_or(&self.interface)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageHistory {
+    #[serde(default)]
+    pub records: Vec<UsageRecord>,
+}
+
+impl UsageHistory {
+    pub fn add(&mut self, date: NaiveDate, interface: &str, ssid: Option<&str>, rx: u64, tx: u64) {
+        let date = date.format(DATE_FORMAT).to_string();
+        match self.records.iter_mut().find(|record| {
+            record.date == date && record.interface == interface && record.ssid.as_deref() == ssid
+        }) {
+            Some(record) => {
+                record.rx_bytes = record.rx_bytes.saturating_add(rx);
+                record.tx_bytes = record.tx_bytes.saturating_add(tx);
+            }
+            None => self.records.push(UsageRecord {
+                date,
+                interface: interface.to_string(),
+                ssid: ssid.map(str::to_string),
+                rx_bytes: rx,
+                tx_bytes: tx,
+            }),
+        }
+    }
+
+    pub fn prune(&mut self, today: NaiveDate) {
+        // * ISO dates sort the same as strings
+        let oldest = (today - DateDuration::days(RETENTION_DAYS))
+            .format(DATE_FORMAT)
+            .to_string();
+        self.records.retain(|record| record.date >= oldest);
+    }
+
+    fn records_in(&self, bucket: &UsageBucket) -> impl Iterator<Item = &UsageRecord> {
+        let start = bucket.start.format(DATE_FORMAT).to_string();
+        let end = bucket.end.format(DATE_FORMAT).to_string();
+        self.records
+            .iter()
+            .filter(move |record| record.date >= start && record.date <= end)
+    }
+
+    // * (download, upload) per bucket, in bucket order
+    pub fn totals(&self, buckets: &[UsageBucket]) -> Vec<(u64, u64)> {
+        buckets
+            .iter()
+            .map(|bucket| {
+                self.records_in(bucket).fold((0, 0), |(rx, tx), record| {
+                    (rx + record.rx_bytes, tx + record.tx_bytes)
+                })
+            })
+            .collect()
+    }
+
+    // * Networks that carried traffic in the range, biggest first
+    pub fn totals_by_network(&self, range: &UsageBucket) -> Vec<(String, u64, u64)> {
+        let mut networks: Vec<(String, u64, u64)> = Vec::new();
+        for record in self.records_in(range) {
+            let label = record.network_label();
+            match networks.iter_mut().find(|(name, _, _)| name == label) {
+                Some((_, rx, tx)) => {
+                    *rx += record.rx_bytes;
+                    *tx += record.tx_bytes;
+                }
+                None => networks.push((label.to_string(), record.rx_bytes, record.tx_bytes)),
+            }
+        }
+        networks.sort_by_key(|(_, rx, tx)| std::cmp::Reverse(rx + tx));
+        networks
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsagePeriod {
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageBucket {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+// * Chart columns ending with the one that contains `today`, oldest first.
+pub fn buckets(period: UsagePeriod, today: NaiveDate) -> Vec<UsageBucket> {
+    let mut buckets: Vec<UsageBucket> = match period {
+        UsagePeriod::Day => (0..14)
+            .map(|i| {
+                let day = today - DateDuration::days(i);
+                UsageBucket {
+                    label: day.format("%d").to_string(),
+                    start: day,
+                    end: day,
+                }
+            })
+            .collect(),
+        UsagePeriod::Week => {
+            let monday = today - DateDuration::days(today.weekday().num_days_from_monday() as i64);
+            (0..8)
+                .map(|i| {
+                    let start = monday - DateDuration::weeks(i);
+                    UsageBucket {
+                        label: start.format("%b %d").to_string(),
+                        start,
+                        end: start + DateDuration::days(6),
+                    }
+                })
+                .collect()
+        }
+        UsagePeriod::Month => {
+            let first = today.with_day(1).unwrap_or(today);
+            (0..6)
+                .filter_map(|i| {
+                    let start = first.checked_sub_months(Months::new(i))?;
+                    let end = start.checked_add_months(Months::new(1))? - DateDuration::days(1);
+                    Some(UsageBucket {
+                        label: start.format("%b").to_string(),
+                        start,
+                        end,
+                    })
+                })
+                .collect()
+        }
+    };
+    buckets.reverse();
+    buckets
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn usage_history_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".local/share/adw-network/usage.json"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-usage.json"))
+}
+
+pub async fn load_usage_history(path: &Path) -> Result<UsageHistory> {
+    if !path.exists() {
+        return Ok(UsageHistory::default());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+// * Moves a file that no longer parses out of the way so the next save can't overwrite it.
+pub async fn set_aside_unreadable_history(path: &Path) -> Result<PathBuf> {
+    let aside = path.with_extension("json.unreadable");
+    fs::rename(path, &aside).await?;
+    Ok(aside)
+}
+
+pub async fn save_usage_history(path: &Path, history: &UsageHistory) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let json = serde_json::to_string(history)?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+pub fn save_usage_history_sync(path: &Path, history: &UsageHistory) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string(history)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct PendingUsage {
+    interface: Option<String>,
+    ssid: Option<String>,
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+// * Adds the traffic monitor's per-second samples to the history and writes it once a minute.
+#[derive(Clone, Default)]
+pub struct UsageRecorder {
+    history: Rc<RefCell<UsageHistory>>,
+    pending: Rc<RefCell<PendingUsage>>,
+    // * False until the file on disk was read or set aside; saving earlier would clobber it
+    loaded: Rc<Cell<bool>>,
+}
+
+impl UsageRecorder {
+    pub fn start(monitor: &TrafficMonitor) -> Self {
+        let recorder = Self::default();
+
+        let recorder_ref = recorder.clone();
+        glib::spawn_future_local(async move {
+            let path = usage_history_path();
+            match load_usage_history(&path).await {
+                Ok(loaded) => {
+                    // * Keep anything recorded while the file was loading
+                    let mut history = recorder_ref.history.borrow_mut();
+                    let recorded = std::mem::replace(&mut *history, loaded);
+                    history.records.extend(recorded.records);
+                    recorder_ref.loaded.set(true);
+                }
+                Err(e) => {
+                    log::warn!("Failed to load data usage history: {}", e);
+                    match set_aside_unreadable_history(&path).await {
+                        Ok(aside) => {
+                            log::warn!("Moved unreadable usage history to {}", aside.display());
+                            recorder_ref.loaded.set(true);
+                        }
+                        // ? Leave `loaded` unset: this session's usage stays in memory only
+                        Err(e) => log::warn!("Not saving usage history this session: {}", e),
+                    }
+                }
+            }
+        });
+
+        let recorder_ref = recorder.clone();
+        monitor.subscribe(move |event| match event {
+            TrafficEvent::InterfaceChanged { current, .. } => {
+                recorder_ref.flush();
+                {
+                    let mut pending = recorder_ref.pending.borrow_mut();
+                    pending.interface = current.clone();
+                    pending.ssid = None;
+                }
+                if let Some(iface) = current.clone() {
+                    let recorder = recorder_ref.clone();
+                    glib::spawn_future_local(async move {
+                        let ssid = wifi_ssid_for(&iface).await;
+                        let mut pending = recorder.pending.borrow_mut();
+                        if pending.interface.as_deref() == Some(iface.as_str()) {
+                            pending.ssid = ssid;
+                        }
+                    });
+                }
+            }
+            TrafficEvent::Sample(sample) => {
+                let mut pending = recorder_ref.pending.borrow_mut();
+                pending.rx_bytes += sample.down_bytes_per_sec;
+                pending.tx_bytes += sample.up_bytes_per_sec;
+            }
+        });
+
+        let recorder_ref = recorder.clone();
+        glib::timeout_add_seconds_local(FLUSH_INTERVAL_SECS, move || {
+            recorder_ref.flush();
+            glib::ControlFlow::Continue
+        });
+
+        recorder
+    }
+
+    // * Current history including the bytes not written yet
+    pub fn history(&self) -> UsageHistory {
+        self.take_pending();
+        self.history.borrow().clone()
+    }
+
+    pub fn flush(&self) {
+        if !self.take_pending() || !self.loaded.get() {
+            return;
+        }
+        let history = self.history.borrow().clone();
+        glib::spawn_future_local(async move {
+            if let Err(e) = save_usage_history(&usage_history_path(), &history).await {
+                log::warn!("Failed to save data usage history: {}", e);
+            }
+        });
+    }
+
+    // * Blocking save for shutdown, when a spawned write would never run
+    pub fn flush_sync(&self) {
+        if !self.take_pending() || !self.loaded.get() {
+            return;
+        }
+        if let Err(e) = save_usage_history_sync(&usage_history_path(), &self.history.borrow()) {
+            log::warn!("Failed to save data usage history: {}", e);
+        }
+    }
+
+    // * Moves pending bytes into the history; false when there was nothing to move.
+    fn take_pending(&self) -> bool {
+        let mut pending = self.pending.borrow_mut();
+        let Some(interface) = pending.interface.clone() else {
+            return false;
+        };
+        if pending.rx_bytes == 0 && pending.tx_bytes == 0 {
+            return false;
+        }
+        let today = today();
+        let mut history = self.history.borrow_mut();
+        history.add(
+            today,
+            &interface,
+            pending.ssid.as_deref(),
+            pending.rx_bytes,
+            pending.tx_bytes,
+        );
+        history.prune(today);
+        pending.rx_bytes = 0;
+        pending.tx_bytes = 0;
+        true
+    }
+}
+
+async fn wifi_ssid_for(iface: &str) -> Option<String> {
+    let devices = NetworkManager::get_devices().await.ok()?;
+    let device = devices.into_iter().find(|device| device.name == iface)?;
+    if device.device_type != DeviceType::Wifi {
+        return None;
+    }
+    nm::get_active_wifi_ssid().await.ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn adds_to_the_same_day_and_network() {
+        let mut history = UsageHistory::default();
+        history.add(date("2026-03-02"), "wlan0", Some("Home"), 100, 10);
+        history.add(date("2026-03-02"), "wlan0", Some("Home"), 50, 5);
+        history.add(date("2026-03-02"), "wlan0", Some("Cafe"), 7, 1);
+        history.add(date("2026-03-03"), "eth0", None, 1, 1);
+
+        assert_eq!(history.records.len(), 3);
+        assert_eq!(history.records[0].rx_bytes, 150);
+        assert_eq!(history.records[0].tx_bytes, 15);
+
+        let day = &buckets(UsagePeriod::Day, date("2026-03-02"))[13];
+        assert_eq!(
+            history.totals_by_network(day),
+            vec![("Home".to_string(), 150, 15), ("Cafe".to_string(), 7, 1)]
+        );
+    }
+
+    #[test]
+    fn buckets_end_with_the_current_period() {
+        let today = date("2026-03-04"); // Wednesday
+        let weeks = buckets(UsagePeriod::Week, today);
+        assert_eq!(weeks.len(), 8);
+        assert_eq!(weeks[7].start, date("2026-03-02"));
+        assert_eq!(weeks[7].end, date("2026-03-08"));
+
+        let months = buckets(UsagePeriod::Month, today);
+        assert_eq!(months[5].start, date("2026-03-01"));
+        assert_eq!(months[4].end, date("2026-02-28"));
+        assert_eq!(months[0].start, date("2025-10-01"));
+
+        let mut history = UsageHistory::default();
+        history.add(date("2026-02-27"), "eth0", None, 10, 1);
+        history.add(date("2026-03-03"), "eth0", None, 20, 2);
+        assert_eq!(history.totals(&weeks[6..]), vec![(10, 1), (20, 2)]);
+        assert_eq!(history.totals(&months[4..]), vec![(10, 1), (20, 2)]);
+    }
+
+    #[test]
+    fn prune_drops_records_past_retention() {
+        let mut history = UsageHistory::default();
+        history.add(date("2024-01-01"), "eth0", None, 1, 1);
+        history.add(date("2026-03-01"), "eth0", None, 1, 1);
+        history.prune(date("2026-03-04"));
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].date, "2026-03-01");
+    }
+
+    #[test]
+    fn unreadable_history_is_set_aside_before_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        tokio_test::block_on(async {
+            assert!(load_usage_history(&path).await.is_err());
+            let aside = set_aside_unreadable_history(&path).await.unwrap();
+            assert_eq!(std::fs::read_to_string(aside).unwrap(), "{ not json");
+            assert!(load_usage_history(&path).await.unwrap().records.is_empty());
+        });
+    }
+}
@@ -0,0 +1,116 @@
+// * ./src/wake_on_lan.rs
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::net::UdpSocket;
+
+use crate::config;
+
+// * Port 9 (discard) is what most NICs and wake tools expect
+const WAKE_PORT: u16 = 9;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct WakeTarget {
+    pub name: String,
+    pub mac: String,
+}
+
+pub fn wake_targets_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".config/adw-network/wake-targets.json"))
+        .unwrap_or_else(|_| PathBuf::from("/tmp/adw-network-wake-targets.json"))
+}
+
+pub async fn load_wake_targets(path: &Path) -> Result<Vec<WakeTarget>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub async fn save_wake_targets(path: &Path, targets: &[WakeTarget]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let json = serde_json::to_string_pretty(targets)?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+// * Replaces any target with the same MAC so re-adding a machine renames it.
+pub async fn store_wake_target(name: &str, mac: &str) -> Result<WakeTarget> {
+    let mac = config::normalize_mac_address(mac)
+        .ok_or_else(|| anyhow!("\"{}\" is not a valid MAC address", mac.trim()))?;
+    let name = name.trim();
+    let target = WakeTarget {
+        name: if name.is_empty() {
+            mac.clone()
+        } else {
+            name.to_string()
+        },
+        mac,
+    };
+
+    let path = wake_targets_path();
+    let mut targets = load_wake_targets(&path).await?;
+    targets.retain(|t| t.mac != target.mac);
+    targets.push(target.clone());
+    targets.sort_by_key(|t| t.name.to_lowercase());
+    save_wake_targets(&path, &targets).await?;
+    Ok(target)
+}
+
+pub async fn forget_wake_target(mac: &str) -> Result<()> {
+    let path = wake_targets_path();
+    let mut targets = load_wake_targets(&path).await?;
+    targets.retain(|t| t.mac != mac);
+    save_wake_targets(&path, &targets).await
+}
+
+// * Six 0xFF bytes followed by the MAC repeated sixteen times.
+pub fn magic_packet(mac: &str) -> Result<Vec<u8>> {
+    let normalized = config::normalize_mac_address(mac)
+        .ok_or_else(|| anyhow!("\"{}\" is not a valid MAC address", mac.trim()))?;
+    let bytes = normalized
+        .split(':')
+        .map(|octet| u8::from_str_radix(octet, 16).map_err(|e| anyhow!(e)))
+        .collect::<Result<Vec<u8>>>()?;
+
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&bytes);
+    }
+    Ok(packet)
+}
+
+pub async fn wake(mac: &str) -> Result<()> {
+    let packet = magic_packet(mac)?;
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.set_broadcast(true)?;
+    socket
+        .send_to(&packet, (Ipv4Addr::BROADCAST, WAKE_PORT))
+        .await
+        .map_err(|e| anyhow!("Failed to send wake packet: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_magic_packet_from_any_mac_notation() {
+        let packet = magic_packet("aa-bb-cc-dd-ee-01").unwrap();
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert_eq!(&packet[6..12], &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01]);
+        assert_eq!(&packet[96..], &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01]);
+        assert!(magic_packet("not-a-mac").is_err());
+    }
+}
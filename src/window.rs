@@ -4,24 +4,39 @@ use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita::{self as adw, prelude::*};
 use std::cell::{Cell, RefCell};
-use std::fs;
 use std::rc::Rc;
-use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use crate::captive_portal;
 use crate::config;
+use crate::dbus_service::DbusService;
+use crate::event_log::EventRecorder;
 use crate::hotspot;
+use crate::hotspot_automation;
 use crate::nm;
+use crate::notifications;
+use crate::polling::{self, PollScheduler};
+use crate::quick_connect;
 use crate::state::AppState;
+use crate::traffic::{TrafficEvent, TrafficMonitor};
+use crate::tray::{TrayAction, TrayIcon};
 use crate::ui::{
-    common, devices_page::DevicesPage, ethernet_page::EthernetPage, hotspot_page::HotspotPage,
-    icon_name, profiles_page::ProfilesPage, wifi_page::WifiPage,
+    common, confirm_dialog, devices_page::DevicesPage, diagnostics_page::DiagnosticsPage,
+    ethernet_page::EthernetPage, history_page::HistoryPage, hotspot_page::HotspotPage, icon_name,
+    profiles_page::ProfilesPage, secret_prompt, usage_page::UsagePage, wifi_page::WifiPage,
 };
+use crate::usage::UsageRecorder;
+
+const MIN_WINDOW_WIDTH: i32 = 360;
+const MIN_WINDOW_HEIGHT: i32 = 420;
+const COMPACT_WINDOW_WIDTH: f64 = 340.0;
+const COMPACT_WINDOW_HEIGHT: f64 = 400.0;
 
 pub struct AppPrefs {
     pub auto_scan: bool,
     pub expand_connected_details: bool,
     pub icons_only_navigation: bool,
+    pub status_pill_action: config::StatusPillAction,
 }
 
 impl Default for AppPrefs {
@@ -31,6 +46,7 @@ impl Default for AppPrefs {
             expand_connected_details: false,
             // ? Changed from true — first-time users need labels to understand navigation
             icons_only_navigation: false,
+            status_pill_action: config::StatusPillAction::ShowWifiPage,
         }
     }
 }
@@ -268,6 +284,7 @@ struct SettingsWindowContext {
     edit_modules_box: gtk4::Box,
     add_module_btn: gtk4::Button,
     add_module_popover: gtk4::Popover,
+    tray_icon: TrayIcon,
 }
 
 pub struct AdwNetworkWindow {
@@ -288,10 +305,12 @@ impl AdwNetworkWindow {
                 log::warn!("Failed to persist repaired app settings: {}", e);
             }
         }
+        nm::set_operation_timeout(app_settings.operation_timeout_secs);
         let prefs = Rc::new(RefCell::new(AppPrefs {
             auto_scan: app_settings.auto_scan,
             expand_connected_details: app_settings.expand_connected_details,
             icons_only_navigation: app_settings.icons_only_navigation,
+            status_pill_action: app_settings.status_pill_action,
         }));
         let app_state = AppState::new(&app_settings);
 
@@ -301,6 +320,13 @@ impl AdwNetworkWindow {
         let devices_page = DevicesPage::new(app_state.clone());
         let profiles_page = ProfilesPage::new();
 
+        // * Every periodic refresh shares this scheduler instead of running its own timer
+        let poll_scheduler = PollScheduler::start();
+        wifi_page.attach_poll_scheduler(&poll_scheduler);
+        hotspot_page.attach_poll_scheduler(&poll_scheduler);
+        devices_page.attach_poll_scheduler(&poll_scheduler);
+        profiles_page.attach_poll_scheduler(&poll_scheduler);
+
         let view_stack = adw::ViewStack::new();
         // Keep minimum width tied to the visible page, not the widest hidden page.
         view_stack.set_hhomogeneous(false);
@@ -386,7 +412,9 @@ impl AdwNetworkWindow {
 
         let no_network_page_widget = adw::StatusPage::builder()
             .title("No Networks Available")
-            .description("No network hardware detected. Check your hardware or restart NetworkManager.")
+            .description(
+                "No network hardware detected. Check your hardware or restart NetworkManager.",
+            )
             .icon_name(icon_name(
                 "network-offline-symbolic",
                 &["network-offline", "network-wireless-offline-symbolic"][..],
@@ -404,11 +432,8 @@ impl AdwNetworkWindow {
         no_network_page_widget.set_vexpand(true);
         no_network_page_box.append(&no_network_page_widget);
         no_network_page_box.append(&no_network_action_box);
-        let no_network_page = view_stack.add_titled(
-            &no_network_page_box,
-            Some("no-network"),
-            "No Network",
-        );
+        let no_network_page =
+            view_stack.add_titled(&no_network_page_box, Some("no-network"), "No Network");
         no_network_page.set_visible(false);
 
         no_network_action.connect_clicked(move |_| {
@@ -569,6 +594,8 @@ impl AdwNetworkWindow {
         let add_module_popover_for_visibility = add_module_popover.clone();
         let no_network_widget_for_visibility = no_network_page_widget.clone();
         let no_network_action_for_visibility = no_network_action.clone();
+        // * Applied once, after the first availability check has made the pages visible
+        let start_page_for_visibility = Rc::new(RefCell::new(app_settings.start_page.clone()));
         let update_visibility = move || {
             let no_network_page_ref = no_network_page_ref.clone();
             let wifi_page_ref = wifi_page_ref.clone();
@@ -585,6 +612,7 @@ impl AdwNetworkWindow {
             let add_module_popover_for_visibility = add_module_popover_for_visibility.clone();
             let no_network_widget_for_visibility = no_network_widget_for_visibility.clone();
             let no_network_action_for_visibility = no_network_action_for_visibility.clone();
+            let start_page_for_visibility = start_page_for_visibility.clone();
 
             glib::spawn_future_local(async move {
                 let availability = Self::detect_module_availability().await;
@@ -640,6 +668,14 @@ impl AdwNetworkWindow {
                         &view_stack_ref,
                         resolved,
                     );
+                    let start_page = start_page_for_visibility.borrow_mut().take();
+                    if let Some(child) =
+                        start_page.and_then(|name| view_stack_ref.child_by_name(&name))
+                    {
+                        if view_stack_ref.page(&child).is_visible() {
+                            view_stack_ref.set_visible_child(&child);
+                        }
+                    }
                 }
                 Self::render_inline_module_editor(
                     &edit_modules_box_for_visibility,
@@ -742,10 +778,12 @@ impl AdwNetworkWindow {
         });
 
         update_visibility();
-        glib::timeout_add_seconds_local(3, move || {
-            update_visibility();
-            glib::ControlFlow::Continue
-        });
+        poll_scheduler.subscribe(
+            "module-visibility",
+            Duration::from_secs(3),
+            Duration::from_secs(30),
+            update_visibility,
+        );
 
         let current_name = view_stack
             .visible_child_name()
@@ -777,6 +815,49 @@ impl AdwNetworkWindow {
         status_pill.append(&status_label);
         status_pill.set_tooltip_text(Some("Connection status"));
 
+        let status_pill_click = gtk4::GestureClick::new();
+        let prefs_for_pill = prefs.clone();
+        let view_stack_for_pill = view_stack.clone();
+        let wifi_page_for_pill = wifi_page.clone();
+        let ethernet_page_for_pill = ethernet_page.clone();
+        let devices_page_for_pill = devices_page.clone();
+        let profiles_page_for_pill = profiles_page.clone();
+        status_pill_click.connect_released(move |_, _, _, _| {
+            let action = prefs_for_pill.borrow().status_pill_action;
+            match action {
+                config::StatusPillAction::ShowWifiPage => {
+                    let wifi_visible = view_stack_for_pill
+                        .child_by_name("wifi")
+                        .map(|child| view_stack_for_pill.page(&child).is_visible())
+                        .unwrap_or(false);
+                    if wifi_visible {
+                        view_stack_for_pill.set_visible_child_name("wifi");
+                    }
+                }
+                config::StatusPillAction::RefreshAll => {
+                    wifi_page_for_pill.request_refresh();
+                    let ethernet = ethernet_page_for_pill.clone();
+                    let devices = devices_page_for_pill.clone();
+                    let profiles = profiles_page_for_pill.clone();
+                    glib::spawn_future_local(async move {
+                        ethernet.refresh_connections().await;
+                        devices.refresh_devices(false).await;
+                        profiles.refresh_profiles().await;
+                    });
+                }
+                config::StatusPillAction::ToggleDetails => {
+                    wifi_page_for_pill.toggle_connected_details();
+                }
+                config::StatusPillAction::ShowConnectionDetails => {
+                    let wifi = wifi_page_for_pill.clone();
+                    glib::spawn_future_local(async move {
+                        wifi.show_connected_network_details().await;
+                    });
+                }
+            }
+        });
+        status_pill.add_controller(status_pill_click);
+
         let speed_down_label = gtk4::Label::new(Some("↓ 0 KB/s"));
         speed_down_label.add_css_class("status-speed-text");
         let speed_up_label = gtk4::Label::new(Some("↑ 0 KB/s"));
@@ -814,6 +895,9 @@ impl AdwNetworkWindow {
 
         let menu = gio::Menu::new();
         menu.append(Some("Settings"), Some("app.settings"));
+        menu.append(Some("Data Usage"), Some("app.usage"));
+        menu.append(Some("History"), Some("app.history"));
+        menu.append(Some("Troubleshoot"), Some("app.diagnostics"));
         menu.append(Some("About"), Some("app.about"));
         menu_button.set_menu_model(Some(&menu));
 
@@ -823,19 +907,71 @@ impl AdwNetworkWindow {
         // Restore default window close control.
         header.set_decoration_layout(Some(":close"));
 
+        // Saved networks in range, connectable without switching pages.
+        let quick_connect_menu = gio::Menu::new();
+        let quick_connect_button = gtk4::MenuButton::builder()
+            .icon_name("network-wireless-symbolic")
+            .tooltip_text("Quick connect")
+            .menu_model(&quick_connect_menu)
+            .build();
+        quick_connect_button.add_css_class("menu-button");
+        quick_connect_button.add_css_class("header-mini-button");
+        quick_connect_button.set_valign(gtk4::Align::Center);
+        quick_connect_button.connect_active_notify(move |button| {
+            if !button.is_active() {
+                return;
+            }
+            let menu = quick_connect_menu.clone();
+            glib::spawn_future_local(async move {
+                match quick_connect::in_range_saved_networks().await {
+                    Ok(entries) => quick_connect::fill_menu(&menu, &entries),
+                    Err(e) => {
+                        log::warn!("Failed to list saved networks in range: {}", e);
+                        quick_connect::fill_menu(&menu, &[]);
+                    }
+                }
+            });
+        });
+
         // Keep settings near the close button on the right.
         let right_controls = gtk4::Box::new(gtk4::Orientation::Horizontal, 2);
+        right_controls.append(&quick_connect_button);
         right_controls.append(&menu_button);
 
         // Add buttons to the header
         header.pack_end(&right_controls);
 
+        // * Shown while NM reports a captive portal, whichever way the network was joined
+        let portal_banner = adw::Banner::builder()
+            .title("This network requires you to sign in")
+            .button_label("Open Login Page")
+            .build();
+        let portal_url = Rc::new(RefCell::new(captive_portal::DEFAULT_PORTAL_URL.to_string()));
+
         let toolbar_view = adw::ToolbarView::new();
         toolbar_view.add_top_bar(&header);
+        toolbar_view.add_top_bar(&portal_banner);
         toolbar_view.set_content(Some(&view_stack));
         let root_toast_overlay = adw::ToastOverlay::new();
         root_toast_overlay.set_child(Some(&toolbar_view));
 
+        let portal_url_for_banner = portal_url.clone();
+        let toast_overlay_for_banner = root_toast_overlay.clone();
+        portal_banner.connect_button_clicked(move |banner| {
+            let url = portal_url_for_banner.borrow().clone();
+            let parent = banner
+                .root()
+                .and_then(|root| root.downcast::<gtk4::Window>().ok());
+            let overlay = toast_overlay_for_banner.clone();
+            glib::spawn_future_local(async move {
+                let launcher = gtk4::UriLauncher::new(&url);
+                if let Err(e) = launcher.launch_future(parent.as_ref()).await {
+                    log::warn!("Failed to open sign-in page {}: {}", url, e);
+                    common::show_toast(&overlay, &format!("Failed to open sign-in page: {}", e));
+                }
+            });
+        });
+
         // Periodically update the global connection status
         let status_icon_for_updates = status_icon.clone();
         let status_label_for_updates = status_label.clone();
@@ -844,8 +980,26 @@ impl AdwNetworkWindow {
             let status_icon = status_icon_for_updates.clone();
             let status_label = status_label_for_updates.clone();
             let status_pill = status_pill_for_updates.clone();
+            let portal_banner = portal_banner.clone();
+            let portal_url = portal_url.clone();
 
             glib::spawn_future_local(async move {
+                let show_portal_banner =
+                    |connectivity: nm::InternetConnectivity, ssid: Option<String>| {
+                        let portal = connectivity == nm::InternetConnectivity::Portal;
+                        if portal && !portal_banner.is_revealed() {
+                            let portal_banner = portal_banner.clone();
+                            let portal_url = portal_url.clone();
+                            glib::spawn_future_local(async move {
+                                *portal_url.borrow_mut() =
+                                    captive_portal::sign_in_url_for(ssid.as_deref()).await;
+                                portal_banner.set_revealed(true);
+                                notifications::notify_captive_portal(ssid.as_deref());
+                            });
+                        } else if !portal {
+                            portal_banner.set_revealed(false);
+                        }
+                    };
                 status_pill.remove_css_class("status-online");
                 status_pill.remove_css_class("status-offline");
                 status_pill.remove_css_class("status-hotspot");
@@ -866,38 +1020,29 @@ impl AdwNetworkWindow {
                         status_pill.set_tooltip_text(Some("Hotspot active"));
                     }
                     status_pill.add_css_class("status-hotspot");
+                    show_portal_banner(nm::InternetConnectivity::Unknown, None);
                     return;
                 }
 
                 match nm::get_active_wired_connection().await {
                     Ok(Some(conn_name)) => {
-                        let connectivity = nm::get_internet_connectivity()
-                            .await
-                            .unwrap_or(nm::InternetConnectivity::Unknown);
-                        let (suffix, css_class) = match connectivity {
-                            nm::InternetConnectivity::Full => ("", "status-online"),
-                            nm::InternetConnectivity::NoInternet => {
-                                (" • No internet", "status-offline")
-                            }
-                            nm::InternetConnectivity::Portal => {
-                                (" • Login required", "status-offline")
-                            }
-                            nm::InternetConnectivity::Limited => (" • Limited", "status-offline"),
-                            nm::InternetConnectivity::Unknown => {
-                                (" • Checking internet", "status-offline")
-                            }
-                        };
+                        let connectivity = nm::current_connectivity().await;
+                        let css_class = connectivity_css_class(connectivity);
                         status_icon.set_icon_name(Some(icon_name(
                             "network-wired-symbolic",
                             &["network-wired", "network-transmit-receive-symbolic"][..],
                         )));
-                        status_label.set_text(&format!("Connected (Wired){suffix}"));
+                        status_label.set_text(&format!(
+                            "Connected (Wired){}",
+                            connectivity.status_suffix()
+                        ));
                         status_pill.set_tooltip_text(Some(&format!(
                             "Wired connection: {} • {}",
                             conn_name,
                             connectivity.as_label()
                         )));
                         status_pill.add_css_class(css_class);
+                        show_portal_banner(connectivity, None);
                         return;
                     }
                     Ok(std::prelude::v1::None) => {}
@@ -908,33 +1053,20 @@ impl AdwNetworkWindow {
 
                 match nm::get_active_wifi_ssid().await {
                     Ok(Some(ssid)) => {
-                        let connectivity = nm::get_internet_connectivity()
-                            .await
-                            .unwrap_or(nm::InternetConnectivity::Unknown);
-                        let (suffix, css_class) = match connectivity {
-                            nm::InternetConnectivity::Full => ("", "status-online"),
-                            nm::InternetConnectivity::NoInternet => {
-                                (" • No internet", "status-offline")
-                            }
-                            nm::InternetConnectivity::Portal => {
-                                (" • Login required", "status-offline")
-                            }
-                            nm::InternetConnectivity::Limited => (" • Limited", "status-offline"),
-                            nm::InternetConnectivity::Unknown => {
-                                (" • Checking internet", "status-offline")
-                            }
-                        };
+                        let connectivity = nm::current_connectivity().await;
+                        let css_class = connectivity_css_class(connectivity);
                         status_icon.set_icon_name(Some(icon_name(
                             "network-wireless-signal-excellent-symbolic",
                             &["network-wireless-symbolic", "network-wireless"][..],
                         )));
-                        status_label.set_text(&format!("{ssid}{suffix}"));
+                        status_label.set_text(&format!("{}{}", ssid, connectivity.status_suffix()));
                         status_pill.set_tooltip_text(Some(&format!(
                             "Connected to {} • {}",
                             ssid,
                             connectivity.as_label()
                         )));
                         status_pill.add_css_class(css_class);
+                        show_portal_banner(connectivity, Some(ssid));
                     }
                     Ok(std::prelude::v1::None) => {
                         let wifi_enabled = nm::is_wifi_enabled().await.unwrap_or(false);
@@ -954,6 +1086,7 @@ impl AdwNetworkWindow {
                             status_pill.set_tooltip_text(Some("Wi-Fi off"));
                         }
                         status_pill.add_css_class("status-offline");
+                        show_portal_banner(nm::InternetConnectivity::Unknown, None);
                     }
                     Err(e) => {
                         log::warn!("Failed to update connection status: {}", e);
@@ -967,73 +1100,70 @@ impl AdwNetworkWindow {
             glib::ControlFlow::Continue
         };
         update_status();
-        glib::timeout_add_seconds_local(5, update_status);
+        let update_status_for_events = update_status.clone();
+        poll_scheduler.subscribe(
+            "status",
+            Duration::from_secs(2),
+            Duration::from_secs(20),
+            move || {
+                update_status();
+            },
+        );
 
-        let speed_state = Arc::new(Mutex::new((0u64, 0u64)));
-        let speed_state_ui = Arc::clone(&speed_state);
-        let speed_down_label = speed_down_label.clone();
-        let speed_up_label = speed_up_label.clone();
-        glib::timeout_add_seconds_local(1, move || {
-            let (down_bytes, up_bytes) = speed_state_ui.lock().map(|v| *v).unwrap_or((0, 0));
-            speed_down_label.set_text(&format!("↓ {}", format_speed(down_bytes)));
-            speed_up_label.set_text(&format!("↑ {}", format_speed(up_bytes)));
-            glib::ControlFlow::Continue
+        // * NM signals drive the pages directly; the scheduler above is the safety net
+        let wifi_page_for_events = wifi_page.clone();
+        let ethernet_page_for_events = ethernet_page.clone();
+        polling::forward_nm_events(move |events| {
+            let connection_changed = events
+                .iter()
+                .any(|event| *event != nm::NmEvent::AccessPointsChanged);
+            if connection_changed {
+                update_status_for_events();
+                let ethernet_page = ethernet_page_for_events.clone();
+                glib::spawn_future_local(async move {
+                    ethernet_page.refresh_connections().await;
+                });
+            }
+            wifi_page_for_events.refresh_from_nm_event();
         });
 
-        let speed_state_task = Arc::clone(&speed_state);
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(1));
-            let mut last_iface: Option<String> = None;
-            let mut last_rx: Option<u64> = None;
-            let mut last_tx: Option<u64> = None;
-
-            loop {
-                interval.tick().await;
-
-                let iface = match nm::get_primary_connected_device().await {
-                    Ok(Some(dev)) => dev,
-                    _ => {
-                        last_iface = None;
-                        last_rx = None;
-                        last_tx = None;
-                        if let Ok(mut state) = speed_state_task.lock() {
-                            *state = (0, 0);
-                        }
-                        continue;
-                    }
-                };
-
-                if last_iface.as_deref() != Some(&iface) {
-                    last_iface = Some(iface.clone());
-                    last_rx = None;
-                    last_tx = None;
-                }
-
-                let Some((rx, tx)) = read_interface_bytes(&iface) else {
-                    last_rx = None;
-                    last_tx = None;
-                    if let Ok(mut state) = speed_state_task.lock() {
-                        *state = (0, 0);
-                    }
-                    continue;
-                };
-
-                let down = if let Some(prev_rx) = last_rx {
-                    rx.saturating_sub(prev_rx)
-                } else {
-                    0
-                };
-                let up = if let Some(prev_tx) = last_tx {
-                    tx.saturating_sub(prev_tx)
-                } else {
-                    0
-                };
-
-                last_rx = Some(rx);
-                last_tx = Some(tx);
-                if let Ok(mut state) = speed_state_task.lock() {
-                    *state = (down, up);
-                }
+        let traffic_monitor = TrafficMonitor::start();
+        let usage_recorder = UsageRecorder::start(&traffic_monitor);
+        let event_recorder = EventRecorder::start();
+        event_recorder.connect_event(notifications::notify_event);
+        let toast_overlay_for_clients = root_toast_overlay.clone();
+        notifications::watch_hotspot_clients(&poll_scheduler, move |mac, message| {
+            let toast = adw::Toast::builder()
+                .title(message)
+                .button_label("Block")
+                .action_name(format!("app.{}", notifications::BLOCK_CLIENT_ACTION))
+                .action_target(&mac.to_variant())
+                .timeout(5)
+                .build();
+            toast_overlay_for_clients.add_toast(toast);
+        });
+        hotspot_automation::watch_idle_hotspot(&poll_scheduler);
+        hotspot_automation::watch_hotspot_schedule(&poll_scheduler);
+        wifi_page.attach_traffic_monitor(&traffic_monitor);
+        let speed_down_label = speed_down_label.clone();
+        let speed_up_label = speed_up_label.clone();
+        let speed_box_for_traffic = speed_box.clone();
+        traffic_monitor.subscribe(move |event| match event {
+            TrafficEvent::InterfaceChanged { current, .. } => {
+                speed_down_label.set_text(&format!("↓ {}", format_speed(0)));
+                speed_up_label.set_text(&format!("↑ {}", format_speed(0)));
+                speed_box_for_traffic.set_tooltip_text(current.as_deref());
+            }
+            TrafficEvent::Sample(sample) => {
+                speed_down_label
+                    .set_text(&format!("↓ {}", format_speed(sample.down_bytes_per_sec)));
+                speed_up_label.set_text(&format!("↑ {}", format_speed(sample.up_bytes_per_sec)));
+                speed_box_for_traffic.set_tooltip_text(Some(&format!(
+                    "{} this session: ↓ {} • ↑ {}",
+                    sample.interface,
+                    common::format_bytes(sample.session_rx_bytes),
+                    common::format_bytes(sample.session_tx_bytes)
+                )));
             }
         });
 
@@ -1045,6 +1175,8 @@ impl AdwNetworkWindow {
             .default_width(700)
             .default_height(520)
             .build();
+        Self::apply_minimum_window_size(&window, app_settings.enforce_minimum_window_size);
+        secret_prompt::forward_secret_requests(&window);
 
         const SIGNAL_FALLBACK_TOAST: &str =
             "Network change notifications unavailable; using periodic refresh";
@@ -1094,6 +1226,46 @@ impl AdwNetworkWindow {
         });
         window.add_breakpoint(breakpoint);
 
+        // * Compact fallback for very small windows (tiling WMs, when the minimum is disabled)
+        let compact_condition = adw::BreakpointCondition::new_or(
+            adw::BreakpointCondition::new_length(
+                adw::BreakpointConditionLengthType::MaxWidth,
+                COMPACT_WINDOW_WIDTH,
+                adw::LengthUnit::Px,
+            ),
+            adw::BreakpointCondition::new_length(
+                adw::BreakpointConditionLengthType::MaxHeight,
+                COMPACT_WINDOW_HEIGHT,
+                adw::LengthUnit::Px,
+            ),
+        );
+        let compact_breakpoint = adw::Breakpoint::new(compact_condition);
+        let view_switcher_for_bp = view_switcher.clone();
+        let speed_box_for_bp = speed_box.clone();
+        let status_label_for_bp = status_label.clone();
+        let window_for_bp = window.downgrade();
+        compact_breakpoint.connect_apply(move |_| {
+            view_switcher_for_bp.set_policy(adw::ViewSwitcherPolicy::Narrow);
+            speed_box_for_bp.set_visible(false);
+            status_label_for_bp.set_visible(false);
+            if let Some(window) = window_for_bp.upgrade() {
+                window.add_css_class("compact");
+            }
+        });
+        let view_switcher_for_bp = view_switcher.clone();
+        let speed_box_for_bp = speed_box.clone();
+        let status_label_for_bp = status_label.clone();
+        let window_for_bp = window.downgrade();
+        compact_breakpoint.connect_unapply(move |_| {
+            view_switcher_for_bp.set_policy(adw::ViewSwitcherPolicy::Wide);
+            speed_box_for_bp.set_visible(true);
+            status_label_for_bp.set_visible(true);
+            if let Some(window) = window_for_bp.upgrade() {
+                window.remove_css_class("compact");
+            }
+        });
+        window.add_breakpoint(compact_breakpoint);
+
         let about_action = gio::SimpleAction::new("about", None);
         let window_weak = window.downgrade();
         about_action.connect_activate(move |_, _| {
@@ -1103,6 +1275,142 @@ impl AdwNetworkWindow {
         });
         app.add_action(&about_action);
 
+        let diagnostics_action = gio::SimpleAction::new("diagnostics", None);
+        let window_weak = window.downgrade();
+        diagnostics_action.connect_activate(move |_, _| {
+            if let Some(window) = window_weak.upgrade() {
+                Self::show_diagnostics_dialog(&window);
+            }
+        });
+        app.add_action(&diagnostics_action);
+
+        let usage_action = gio::SimpleAction::new("usage", None);
+        let window_weak = window.downgrade();
+        let recorder_for_usage = usage_recorder.clone();
+        usage_action.connect_activate(move |_, _| {
+            if let Some(window) = window_weak.upgrade() {
+                Self::show_usage_dialog(&window, &recorder_for_usage);
+            }
+        });
+        app.add_action(&usage_action);
+
+        let history_action = gio::SimpleAction::new("history", None);
+        let window_weak = window.downgrade();
+        history_action.connect_activate(move |_, _| {
+            if let Some(window) = window_weak.upgrade() {
+                Self::show_history_dialog(&window, &event_recorder);
+            }
+        });
+        app.add_action(&history_action);
+
+        let block_client_action = gio::SimpleAction::new(
+            notifications::BLOCK_CLIENT_ACTION,
+            Some(glib::VariantTy::STRING),
+        );
+        let devices_page_for_block = devices_page.clone();
+        let toast_overlay_for_block = root_toast_overlay.clone();
+        block_client_action.connect_activate(move |_, parameter| {
+            let Some(mac) = parameter.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            let page = devices_page_for_block.clone();
+            let overlay = toast_overlay_for_block.clone();
+            glib::spawn_future_local(async move {
+                match page.set_device_blocked(&mac, true).await {
+                    Ok(()) => {
+                        common::show_toast(&overlay, &format!("{} blocked", mac));
+                        page.refresh_devices(false).await;
+                    }
+                    Err(e) => common::show_error(&overlay, "Failed to block device", &e),
+                }
+            });
+        });
+        app.add_action(&block_client_action);
+
+        let show_devices_action = gio::SimpleAction::new(notifications::SHOW_DEVICES_ACTION, None);
+        let window_weak = window.downgrade();
+        let view_stack_for_devices = view_stack.clone();
+        show_devices_action.connect_activate(move |_, _| {
+            if let Some(window) = window_weak.upgrade() {
+                if view_stack_for_devices.child_by_name("devices").is_some() {
+                    view_stack_for_devices.set_visible_child_name("devices");
+                }
+                window.present();
+            }
+        });
+        app.add_action(&show_devices_action);
+        let usage_recorder_for_tray = usage_recorder.clone();
+        window.connect_close_request(move |_| {
+            usage_recorder.flush_sync();
+            glib::Propagation::Proceed
+        });
+
+        // * Tray clicks and D-Bus requests share one handler; the tray is optional and while it
+        // * is shown, closing the window only hides it
+        let app_weak = app.downgrade();
+        let window_weak = window.downgrade();
+        let wifi_page_for_tray = wifi_page.clone();
+        let hotspot_page_for_tray = hotspot_page.clone();
+        let handle_action: Rc<dyn Fn(TrayAction)> = Rc::new(move |action| match action {
+            TrayAction::ShowWindow => {
+                if let Some(window) = window_weak.upgrade() {
+                    window.present();
+                }
+            }
+            TrayAction::SetWifiEnabled(enabled) => wifi_page_for_tray.set_wifi_enabled(enabled),
+            TrayAction::SetHotspotActive(active) => {
+                hotspot_page_for_tray.set_hotspot_active(active)
+            }
+            TrayAction::ConnectSaved(ssid) => {
+                if let Some(app) = app_weak.upgrade() {
+                    app.activate_action(
+                        quick_connect::CONNECT_SAVED_ACTION,
+                        Some(&ssid.to_variant()),
+                    );
+                }
+            }
+            TrayAction::Quit => {
+                usage_recorder_for_tray.flush_sync();
+                if let Some(app) = app_weak.upgrade() {
+                    app.quit();
+                }
+            }
+        });
+        let handle_tray_action = handle_action.clone();
+        let tray_icon = TrayIcon::new(move |action| handle_tray_action(action));
+        let dbus_service = DbusService::start(move |action| handle_action(action));
+        polling::forward_nm_events(move |_| dbus_service.emit_status_changed());
+        let tray_settings =
+            config::load_app_settings_sync(&config::app_settings_path()).unwrap_or_default();
+        tray_icon.set_enabled(tray_settings.show_tray_icon);
+        let tray_for_events = tray_icon.clone();
+        polling::forward_nm_events(move |_| tray_for_events.refresh());
+        let tray_for_close = tray_icon.clone();
+        window.connect_close_request(move |window| {
+            let close_to_tray = config::load_app_settings_sync(&config::app_settings_path())
+                .map(|settings| settings.close_to_tray)
+                .unwrap_or(true);
+            if tray_for_close.is_enabled() && close_to_tray {
+                window.set_visible(false);
+                return glib::Propagation::Stop;
+            }
+            glib::Propagation::Proceed
+        });
+
+        let connect_saved_action = gio::SimpleAction::new(
+            quick_connect::CONNECT_SAVED_ACTION,
+            Some(glib::VariantTy::STRING),
+        );
+        let wifi_page_for_connect = wifi_page.clone();
+        connect_saved_action.connect_activate(move |_, parameter| {
+            let Some(ssid) = parameter.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            quick_connect::invalidate_cache();
+            wifi_page_for_connect.connect_saved(&ssid);
+        });
+        app.add_action(&connect_saved_action);
+
         let no_network_page_for_settings = no_network_page.clone();
         let settings_action = gio::SimpleAction::new("settings", None);
         let window_weak = window.downgrade();
@@ -1136,6 +1444,7 @@ impl AdwNetworkWindow {
                     edit_modules_box: edit_modules_box.clone(),
                     add_module_btn: add_module_btn.clone(),
                     add_module_popover: add_module_popover.clone(),
+                    tray_icon: tray_icon.clone(),
                 });
             }
         });
@@ -1158,6 +1467,88 @@ impl AdwNetworkWindow {
         about.present(Some(window));
     }
 
+    fn show_usage_dialog(window: &adw::ApplicationWindow, recorder: &UsageRecorder) {
+        let dialog = adw::Dialog::builder()
+            .title("Data Usage")
+            .content_width(560)
+            .content_height(680)
+            .build();
+        common::make_dialog_responsive(&dialog, Some(window.upcast_ref()), 560, 680);
+
+        let page = UsagePage::new(recorder);
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        toolbar.set_content(Some(&page.widget));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(window));
+    }
+
+    fn show_history_dialog(window: &adw::ApplicationWindow, recorder: &EventRecorder) {
+        let dialog = adw::Dialog::builder()
+            .title("History")
+            .content_width(560)
+            .content_height(680)
+            .build();
+        common::make_dialog_responsive(&dialog, Some(window.upcast_ref()), 560, 680);
+
+        let page = HistoryPage::new(recorder);
+        let clear_button = gtk4::Button::builder()
+            .icon_name(icon_name(
+                "user-trash-symbolic",
+                &["edit-delete-symbolic"][..],
+            ))
+            .tooltip_text("Clear history")
+            .build();
+        let header = adw::HeaderBar::new();
+        header.pack_start(&clear_button);
+
+        let recorder = recorder.clone();
+        let page_for_clear = page.clone();
+        clear_button.connect_clicked(move |button| {
+            let recorder = recorder.clone();
+            let page = page_for_clear.clone();
+            let button = button.clone();
+            glib::spawn_future_local(async move {
+                if confirm_dialog(
+                    &button,
+                    "Clear History?",
+                    "All recorded network events will be deleted.",
+                    "Clear",
+                    true,
+                )
+                .await
+                {
+                    recorder.clear();
+                    page.refresh();
+                }
+            });
+        });
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&page.widget));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(window));
+    }
+
+    fn show_diagnostics_dialog(window: &adw::ApplicationWindow) {
+        let dialog = adw::Dialog::builder()
+            .title("Troubleshoot")
+            .content_width(560)
+            .content_height(720)
+            .build();
+        common::make_dialog_responsive(&dialog, Some(window.upcast_ref()), 560, 720);
+
+        let page = DiagnosticsPage::new();
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        toolbar.set_content(Some(&page.widget));
+        dialog.set_child(Some(&toolbar));
+        // * Closing the dialog mid-run shouldn't leave a traceroute going in the background
+        dialog.connect_closed(move |_| page.stop());
+        dialog.present(Some(window));
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn show_settings_window(ctx: SettingsWindowContext) {
         let SettingsWindowContext {
@@ -1178,6 +1569,7 @@ impl AdwNetworkWindow {
             edit_modules_box,
             add_module_btn,
             add_module_popover,
+            tray_icon,
         } = ctx;
         let window = &window;
         let style_manager = adw::StyleManager::default();
@@ -1191,6 +1583,7 @@ impl AdwNetworkWindow {
             }
         }
         let settings_state = Rc::new(RefCell::new(loaded_settings));
+        let settings_dialog = adw::PreferencesDialog::builder().title("Settings").build();
         let show_plain_json_warning_on_load =
             config::plain_json_warning_active(&settings_state.borrow());
 
@@ -1210,6 +1603,7 @@ impl AdwNetworkWindow {
 
         let style_manager_for_theme = style_manager.clone();
         let settings_state_for_theme = settings_state.clone();
+        let settings_dialog_for_theme = settings_dialog.clone();
         theme_combo.connect_selected_notify(move |row: &adw::ComboRow| {
             let scheme = Self::color_scheme_from_selection(row.selected());
             style_manager_for_theme.set_color_scheme(scheme);
@@ -1217,7 +1611,13 @@ impl AdwNetworkWindow {
             if settings_state_for_theme.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_theme"); }
             if let Ok(mut settings) = settings_state_for_theme.try_borrow_mut() {
                 settings.color_scheme = Self::setting_from_selection(row.selected()).to_string();
-                spawn_save_settings(&settings);
+                let settings_dialog_for_error = settings_dialog_for_theme.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    settings_dialog_for_error.add_toast(adw::Toast::new(&format!(
+                        "Failed to save appearance: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
             }
@@ -1257,6 +1657,7 @@ impl AdwNetworkWindow {
         });
 
         let settings_state_for_storage = settings_state.clone();
+        let settings_dialog_for_storage = settings_dialog.clone();
         let storage_update_guard = Rc::new(Cell::new(false));
         let storage_update_guard_for_signal = storage_update_guard.clone();
         storage_row.connect_selected_notify(move |row| {
@@ -1281,6 +1682,7 @@ impl AdwNetworkWindow {
                 let row_for_dialog = row.clone();
                 let settings_state_for_dialog = settings_state_for_storage.clone();
                 let storage_update_guard_for_dialog = storage_update_guard_for_signal.clone();
+                let settings_dialog_for_dialog = settings_dialog_for_storage.clone();
                 glib::spawn_future_local(async move {
                     let response = if let Some(parent) =
                         row_for_dialog.root().and_downcast::<gtk4::Window>()
@@ -1302,9 +1704,32 @@ impl AdwNetworkWindow {
 
                     if settings_state_for_dialog.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_dialog_continue"); }
                     if let Ok(mut settings) = settings_state_for_dialog.try_borrow_mut() {
+                        let previous = (
+                            settings.hotspot_password_storage.clone(),
+                            settings.plain_json_debug_opt_in,
+                        );
                         settings.hotspot_password_storage = config::HotspotPasswordStorage::PlainJson;
                         settings.plain_json_debug_opt_in = true;
-                        spawn_save_settings(&settings);
+                        let row_for_revert = row_for_dialog.clone();
+                        let settings_state_for_revert = settings_state_for_dialog.clone();
+                        let storage_update_guard_for_revert = storage_update_guard_for_dialog.clone();
+                        let settings_dialog_for_revert = settings_dialog_for_dialog.clone();
+                        spawn_save_settings_for_row(
+                            &settings,
+                            row_for_dialog.upcast_ref(),
+                            move |error| {
+                                Self::revert_password_storage_row(
+                                    &row_for_revert,
+                                    &settings_state_for_revert,
+                                    &storage_update_guard_for_revert,
+                                    previous,
+                                );
+                                settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                                    "Failed to save password storage: {}",
+                                    error
+                                )));
+                            },
+                        );
                     } else {
                         log::error!("Borrow conflict in UI state");
                     }
@@ -1326,9 +1751,28 @@ impl AdwNetworkWindow {
 
             if settings_state_for_storage.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_storage"); }
             if let Ok(mut settings) = settings_state_for_storage.try_borrow_mut() {
+                let previous = (
+                    settings.hotspot_password_storage.clone(),
+                    settings.plain_json_debug_opt_in,
+                );
                 settings.hotspot_password_storage = selected.clone();
                 settings.plain_json_debug_opt_in = false;
-                spawn_save_settings(&settings);
+                let row_for_revert = row.clone();
+                let settings_state_for_revert = settings_state_for_storage.clone();
+                let storage_update_guard_for_revert = storage_update_guard_for_signal.clone();
+                let settings_dialog_for_revert = settings_dialog_for_storage.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_password_storage_row(
+                        &row_for_revert,
+                        &settings_state_for_revert,
+                        &storage_update_guard_for_revert,
+                        previous,
+                    );
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save password storage: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
                 return;
@@ -1354,12 +1798,33 @@ impl AdwNetworkWindow {
         ));
 
         let settings_state_for_quota_reset = settings_state.clone();
+        let settings_dialog_for_quota_reset = settings_dialog.clone();
+        let quota_reset_guard = Rc::new(Cell::new(false));
         quota_reset_row.connect_selected_notify(move |row| {
+            if quota_reset_guard.get() {
+                return;
+            }
             if settings_state_for_quota_reset.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_quota_reset"); }
             if let Ok(mut settings) = settings_state_for_quota_reset.try_borrow_mut() {
+                let previous = settings.hotspot_quota_reset_policy.clone();
                 settings.hotspot_quota_reset_policy =
                     Self::quota_reset_policy_from_selection(row.selected());
-                spawn_save_settings(&settings);
+                let row_for_revert = row.clone();
+                let settings_state_for_revert = settings_state_for_quota_reset.clone();
+                let quota_reset_guard_for_revert = quota_reset_guard.clone();
+                let settings_dialog_for_revert = settings_dialog_for_quota_reset.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    if let Ok(mut settings) = settings_state_for_revert.try_borrow_mut() {
+                        settings.hotspot_quota_reset_policy = previous.clone();
+                    }
+                    quota_reset_guard_for_revert.set(true);
+                    row_for_revert.set_selected(Self::selection_from_quota_reset_policy(&previous));
+                    quota_reset_guard_for_revert.set(false);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save quota reset policy: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
             }
@@ -1367,6 +1832,85 @@ impl AdwNetworkWindow {
 
         storage_group.add(&quota_reset_row);
 
+        let hotspot_backend_model =
+            gtk4::StringList::new(&["NetworkManager", "hostapd + dnsmasq"][..]);
+        let hotspot_backend_row = adw::ComboRow::builder()
+            .title("Hotspot backend")
+            .subtitle("Use hostapd when NetworkManager can't run the adapter as an access point")
+            .model(&hotspot_backend_model)
+            .build();
+        hotspot_backend_row.set_selected(Self::selection_from_hotspot_backend(
+            settings_state.borrow().hotspot_backend,
+        ));
+
+        let settings_state_for_backend = settings_state.clone();
+        let settings_dialog_for_backend = settings_dialog.clone();
+        let hotspot_backend_guard = Rc::new(Cell::new(false));
+        hotspot_backend_row.connect_selected_notify(move |row| {
+            if hotspot_backend_guard.get() {
+                return;
+            }
+            if let Ok(mut settings) = settings_state_for_backend.try_borrow_mut() {
+                let previous = settings.hotspot_backend;
+                settings.hotspot_backend = Self::hotspot_backend_from_selection(row.selected());
+                let row_for_revert = row.clone();
+                let settings_state_for_revert = settings_state_for_backend.clone();
+                let hotspot_backend_guard_for_revert = hotspot_backend_guard.clone();
+                let settings_dialog_for_revert = settings_dialog_for_backend.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    if let Ok(mut settings) = settings_state_for_revert.try_borrow_mut() {
+                        settings.hotspot_backend = previous;
+                    }
+                    hotspot_backend_guard_for_revert.set(true);
+                    row_for_revert.set_selected(Self::selection_from_hotspot_backend(previous));
+                    hotspot_backend_guard_for_revert.set(false);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save hotspot backend: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        storage_group.add(&hotspot_backend_row);
+
+        let password_backup_row = adw::SwitchRow::builder()
+            .title("Back up hotspot password to keyring")
+            .subtitle("Keeps the last working password recoverable when changing storage")
+            .active(settings_state.borrow().backup_hotspot_password_to_keyring)
+            .build();
+
+        let settings_state_for_password_backup = settings_state.clone();
+        let settings_dialog_for_password_backup = settings_dialog.clone();
+        let revert_password_backup = Rc::new(Cell::new(false));
+        password_backup_row.connect_active_notify(move |row| {
+            let active = row.is_active();
+
+            if settings_state_for_password_backup.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_password_backup"); }
+            if let Ok(mut settings) = settings_state_for_password_backup.try_borrow_mut() {
+                settings.backup_hotspot_password_to_keyring = active;
+                if revert_password_backup.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_password_backup.clone();
+                let settings_dialog_for_revert = settings_dialog_for_password_backup.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save password backup setting: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        storage_group.add(&password_backup_row);
+
         let settings_state_for_switches = settings_state.clone();
         let auto_scan_row = adw::SwitchRow::builder()
             .title("Auto refresh networks")
@@ -1395,6 +1939,8 @@ impl AdwNetworkWindow {
         let prefs_for_auto_scan = prefs.clone();
         let app_state_for_auto_scan = app_state.clone();
         let settings_state_for_auto_scan = settings_state.clone();
+        let settings_dialog_for_auto_scan = settings_dialog.clone();
+        let revert_auto_scan = Rc::new(Cell::new(false));
         auto_scan_row.connect_active_notify(move |row| {
             let active = row.is_active();
             if prefs_for_auto_scan.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: prefs_for_auto_scan"); }
@@ -1411,7 +1957,19 @@ impl AdwNetworkWindow {
             if settings_state_for_auto_scan.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_auto_scan"); }
             if let Ok(mut settings) = settings_state_for_auto_scan.try_borrow_mut() {
                 settings.auto_scan = active;
-                spawn_save_settings(&settings);
+                if revert_auto_scan.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_auto_scan.clone();
+                let settings_dialog_for_revert = settings_dialog_for_auto_scan.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save auto refresh setting: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
             }
@@ -1420,6 +1978,8 @@ impl AdwNetworkWindow {
         let prefs_for_expand = prefs.clone();
         let app_state_for_expand = app_state.clone();
         let settings_state_for_expand = settings_state.clone();
+        let settings_dialog_for_expand = settings_dialog.clone();
+        let revert_expand = Rc::new(Cell::new(false));
         let wifi_for_expand = wifi_page.clone();
         expand_details_row.connect_active_notify(move |row| {
             let active = row.is_active();
@@ -1438,7 +1998,19 @@ impl AdwNetworkWindow {
             if settings_state_for_expand.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_expand"); }
             if let Ok(mut settings) = settings_state_for_expand.try_borrow_mut() {
                 settings.expand_connected_details = active;
-                spawn_save_settings(&settings);
+                if revert_expand.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_expand.clone();
+                let settings_dialog_for_revert = settings_dialog_for_expand.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save connection details setting: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
             }
@@ -1447,6 +2019,8 @@ impl AdwNetworkWindow {
         let prefs_for_nav_mode = prefs.clone();
         let app_state_for_nav_mode = app_state.clone();
         let settings_state_for_nav_mode = settings_state.clone();
+        let settings_dialog_for_nav_mode = settings_dialog.clone();
+        let revert_nav_mode = Rc::new(Cell::new(false));
         let wifi_stack_page_for_nav = wifi_stack_page.clone();
         let ethernet_stack_page_for_nav = ethernet_stack_page.clone();
         let hotspot_stack_page_for_nav = hotspot_stack_page.clone();
@@ -1478,7 +2052,228 @@ impl AdwNetworkWindow {
             if settings_state_for_nav_mode.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_nav_mode"); }
             if let Ok(mut settings) = settings_state_for_nav_mode.try_borrow_mut() {
                 settings.icons_only_navigation = active;
-                spawn_save_settings(&settings);
+                if revert_nav_mode.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_nav_mode.clone();
+                let settings_dialog_for_revert = settings_dialog_for_nav_mode.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save navigation style setting: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        let settings_state_for_switches = settings_state.clone();
+        let min_size_row = adw::SwitchRow::builder()
+            .title("Keep a usable minimum window size")
+            .subtitle("Prevent the window from shrinking until controls overlap")
+            .active(
+                settings_state_for_switches
+                    .borrow()
+                    .enforce_minimum_window_size,
+            )
+            .build();
+
+        let settings_state_for_min_size = settings_state.clone();
+        let settings_dialog_for_min_size = settings_dialog.clone();
+        let revert_min_size = Rc::new(Cell::new(false));
+        let window_for_min_size = window.clone();
+        min_size_row.connect_active_notify(move |row| {
+            let active = row.is_active();
+            Self::apply_minimum_window_size(&window_for_min_size, active);
+
+            if settings_state_for_min_size.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_min_size"); }
+            if let Ok(mut settings) = settings_state_for_min_size.try_borrow_mut() {
+                settings.enforce_minimum_window_size = active;
+                if revert_min_size.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_min_size.clone();
+                let settings_dialog_for_revert = settings_dialog_for_min_size.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save window size setting: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        let settings_state_for_switches = settings_state.clone();
+        let tray_row = adw::SwitchRow::builder()
+            .title("Show tray icon")
+            .subtitle("Quick Wi-Fi and hotspot toggles from the system tray")
+            .active(settings_state_for_switches.borrow().show_tray_icon)
+            .build();
+        let close_to_tray_row = adw::SwitchRow::builder()
+            .title("Keep running when closed")
+            .subtitle("Closing the window hides it to the tray; hotspot monitoring continues")
+            .active(settings_state_for_switches.borrow().close_to_tray)
+            .sensitive(settings_state_for_switches.borrow().show_tray_icon)
+            .build();
+
+        let settings_state_for_tray = settings_state.clone();
+        let settings_dialog_for_tray = settings_dialog.clone();
+        let revert_tray = Rc::new(Cell::new(false));
+        let close_to_tray_row_for_tray = close_to_tray_row.clone();
+        tray_row.connect_active_notify(move |row| {
+            let active = row.is_active();
+            tray_icon.set_enabled(active);
+            close_to_tray_row_for_tray.set_sensitive(active);
+
+            if let Ok(mut settings) = settings_state_for_tray.try_borrow_mut() {
+                settings.show_tray_icon = active;
+                if revert_tray.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_tray.clone();
+                let settings_dialog_for_revert = settings_dialog_for_tray.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save tray icon setting: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        let settings_state_for_close_to_tray = settings_state.clone();
+        let settings_dialog_for_close_to_tray = settings_dialog.clone();
+        let revert_close_to_tray = Rc::new(Cell::new(false));
+        close_to_tray_row.connect_active_notify(move |row| {
+            let active = row.is_active();
+            if let Ok(mut settings) = settings_state_for_close_to_tray.try_borrow_mut() {
+                settings.close_to_tray = active;
+                if revert_close_to_tray.get() {
+                    return;
+                }
+                let row_for_revert = row.clone();
+                let revert_guard = revert_close_to_tray.clone();
+                let settings_dialog_for_revert = settings_dialog_for_close_to_tray.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save close behavior setting: {}",
+                        error
+                    )));
+                });
+            } else {
+                log::error!("Borrow conflict in UI state");
+            }
+        });
+
+        type NotifyField = fn(&mut config::AppSettings) -> &mut bool;
+        let notifications_group = adw::PreferencesGroup::new();
+        notifications_group.set_title("Notifications");
+        notifications_group.set_description(Some(
+            "Shown while the window is in the background or hidden",
+        ));
+        let mut notify_rows: Vec<(adw::SwitchRow, NotifyField)> = Vec::new();
+        for (title, field) in [
+            ("Connected", (|s| &mut s.notify_on_connect) as NotifyField),
+            ("Disconnected", |s| &mut s.notify_on_disconnect),
+            ("New hotspot device", |s| &mut s.notify_on_hotspot_client),
+            ("Sign-in required", |s| &mut s.notify_on_captive_portal),
+        ] {
+            let row = adw::SwitchRow::builder()
+                .title(title)
+                .active(*field(&mut settings_state.borrow_mut()))
+                .build();
+            let settings_state_for_notify = settings_state.clone();
+            let settings_dialog_for_notify = settings_dialog.clone();
+            let revert_notify = Rc::new(Cell::new(false));
+            row.connect_active_notify(move |row| {
+                let active = row.is_active();
+                if let Ok(mut settings) = settings_state_for_notify.try_borrow_mut() {
+                    *field(&mut settings) = active;
+                    if revert_notify.get() {
+                        return;
+                    }
+                    let row_for_revert = row.clone();
+                    let revert_guard = revert_notify.clone();
+                    let settings_dialog_for_revert = settings_dialog_for_notify.clone();
+                    spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                        Self::revert_switch_row(&row_for_revert, &revert_guard, !active);
+                        settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                            "Failed to save notification setting: {}",
+                            error
+                        )));
+                    });
+                } else {
+                    log::error!("Borrow conflict in UI state");
+                }
+            });
+            notifications_group.add(&row);
+            notify_rows.push((row, field));
+        }
+
+        let status_pill_model = gtk4::StringList::new(
+            &[
+                "Show Wi-Fi page",
+                "Refresh all",
+                "Toggle connection details",
+                "Show connection details",
+            ][..],
+        );
+        let status_pill_row = adw::ComboRow::builder()
+            .title("Status pill click")
+            .subtitle("What happens when you click the connection status in the header")
+            .model(&status_pill_model)
+            .build();
+        status_pill_row.set_selected(Self::selection_from_status_pill_action(
+            settings_state.borrow().status_pill_action,
+        ));
+
+        let prefs_for_status_pill = prefs.clone();
+        let settings_state_for_status_pill = settings_state.clone();
+        let settings_dialog_for_status_pill = settings_dialog.clone();
+        let status_pill_guard = Rc::new(Cell::new(false));
+        status_pill_row.connect_selected_notify(move |row| {
+            let action = Self::status_pill_action_from_selection(row.selected());
+            if let Ok(mut prefs) = prefs_for_status_pill.try_borrow_mut() {
+                prefs.status_pill_action = action;
+            } else {
+                log::error!("Borrow conflict in UI state");
+                return;
+            }
+            if status_pill_guard.get() {
+                return;
+            }
+            if settings_state_for_status_pill.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_status_pill"); }
+            if let Ok(mut settings) = settings_state_for_status_pill.try_borrow_mut() {
+                let previous = settings.status_pill_action;
+                settings.status_pill_action = action;
+                let row_for_revert = row.clone();
+                let settings_state_for_revert = settings_state_for_status_pill.clone();
+                let status_pill_guard_for_revert = status_pill_guard.clone();
+                let settings_dialog_for_revert = settings_dialog_for_status_pill.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    if let Ok(mut settings) = settings_state_for_revert.try_borrow_mut() {
+                        settings.status_pill_action = previous;
+                    }
+                    status_pill_guard_for_revert.set(true);
+                    row_for_revert.set_selected(Self::selection_from_status_pill_action(previous));
+                    status_pill_guard_for_revert.set(false);
+                    settings_dialog_for_revert.add_toast(adw::Toast::new(&format!(
+                        "Failed to save status pill action: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
             }
@@ -1489,6 +2284,13 @@ impl AdwNetworkWindow {
         personalization_group.add(&auto_scan_row);
         personalization_group.add(&expand_details_row);
         personalization_group.add(&nav_icons_only_row);
+        personalization_group.add(&min_size_row);
+        personalization_group.add(&status_pill_row);
+
+        let background_group = adw::PreferencesGroup::new();
+        background_group.set_title("Background");
+        background_group.add(&tray_row);
+        background_group.add(&close_to_tray_row);
 
         let modules_group = adw::PreferencesGroup::new();
         modules_group.set_title("Modules");
@@ -1551,6 +2353,7 @@ impl AdwNetworkWindow {
         let add_module_btn_for_module_preset = add_module_btn.clone();
         let add_module_popover_for_module_preset = add_module_popover.clone();
         let module_order_row_for_preset = module_order_row.clone();
+        let settings_dialog_for_module_preset = settings_dialog.clone();
         module_preset_row.connect_selected_notify(move |row| {
             let no_network_page_for_module_preset = no_network_page_for_module_preset.clone();
             if module_rows_guard_for_preset.get() {
@@ -1585,7 +2388,13 @@ impl AdwNetworkWindow {
             }
             if let Ok(mut settings) = settings_state_for_module_preset.try_borrow_mut() {
                 next_layout.apply_to_settings(&mut settings);
-                spawn_save_settings(&settings);
+                let settings_dialog_for_error = settings_dialog_for_module_preset.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    settings_dialog_for_error.add_toast(adw::Toast::new(&format!(
+                        "Failed to save visible modules: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
                 return;
@@ -1641,6 +2450,7 @@ impl AdwNetworkWindow {
         let edit_modules_box_for_module_order = edit_modules_box.clone();
         let add_module_btn_for_module_order = add_module_btn.clone();
         let add_module_popover_for_module_order = add_module_popover.clone();
+        let settings_dialog_for_module_order = settings_dialog.clone();
         module_order_row.connect_selected_notify(move |row| {
             if module_rows_guard_for_order.get() {
                 return;
@@ -1661,7 +2471,13 @@ impl AdwNetworkWindow {
             }
             if let Ok(mut settings) = settings_state_for_module_order.try_borrow_mut() {
                 next_layout.apply_to_settings(&mut settings);
-                spawn_save_settings(&settings);
+                let settings_dialog_for_error = settings_dialog_for_module_order.clone();
+                spawn_save_settings_for_row(&settings, row.upcast_ref(), move |error| {
+                    settings_dialog_for_error.add_toast(adw::Toast::new(&format!(
+                        "Failed to save module order: {}",
+                        error
+                    )));
+                });
             } else {
                 log::error!("Borrow conflict in UI state");
                 return;
@@ -1719,6 +2535,8 @@ impl AdwNetworkWindow {
         let edit_modules_box_for_module_reset = edit_modules_box.clone();
         let add_module_btn_for_module_reset = add_module_btn.clone();
         let add_module_popover_for_module_reset = add_module_popover.clone();
+        let module_reset_factory_row_for_reset = module_reset_factory_row.clone();
+        let settings_dialog_for_module_reset = settings_dialog.clone();
         module_reset_factory_btn.connect_clicked(move |_| {
             let no_network_page_for_module_reset = no_network_page_for_module_reset.clone();
             let defaults = config::AppSettings::default();
@@ -1735,7 +2553,17 @@ impl AdwNetworkWindow {
             }
             if let Ok(mut settings) = settings_state_for_module_reset.try_borrow_mut() {
                 next_layout.apply_to_settings(&mut settings);
-                spawn_save_settings(&settings);
+                let settings_dialog_for_error = settings_dialog_for_module_reset.clone();
+                spawn_save_settings_for_row(
+                    &settings,
+                    &module_reset_factory_row_for_reset,
+                    move |error| {
+                        settings_dialog_for_error.add_toast(adw::Toast::new(&format!(
+                            "Failed to save module layout: {}",
+                            error
+                        )));
+                    },
+                );
             } else {
                 log::error!("Borrow conflict in UI state");
                 return;
@@ -1810,9 +2638,16 @@ impl AdwNetworkWindow {
         let theme_combo_for_reset = theme_combo.clone();
         let storage_row_for_reset = storage_row.clone();
         let quota_reset_row_for_reset = quota_reset_row.clone();
+        let hotspot_backend_row_for_reset = hotspot_backend_row.clone();
         let auto_scan_for_reset = auto_scan_row.clone();
         let expand_details_for_reset = expand_details_row.clone();
         let nav_icons_only_for_reset = nav_icons_only_row.clone();
+        let min_size_for_reset = min_size_row.clone();
+        let tray_for_reset = tray_row.clone();
+        let notify_rows_for_reset = notify_rows.clone();
+        let close_to_tray_for_reset = close_to_tray_row.clone();
+        let password_backup_for_reset = password_backup_row.clone();
+        let status_pill_for_reset = status_pill_row.clone();
         let style_manager_for_reset = style_manager.clone();
         let wifi_stack_page_for_reset = wifi_stack_page.clone();
         let ethernet_stack_page_for_reset = ethernet_stack_page.clone();
@@ -1820,11 +2655,21 @@ impl AdwNetworkWindow {
         let devices_stack_page_for_reset = devices_stack_page.clone();
         let profiles_stack_page_for_reset = profiles_stack_page.clone();
         let view_switcher_for_reset = view_switcher.clone();
-        reset_button.connect_clicked(move |_| {
+        let reset_row_for_reset = reset_row.clone();
+        let settings_dialog_for_reset = settings_dialog.clone();
+        let reset_to_defaults = Rc::new(move || {
             let defaults = config::AppSettings::default();
-            spawn_save_settings(&defaults);
+            let settings_dialog_for_error = settings_dialog_for_reset.clone();
+            spawn_save_settings_for_row(&defaults, &reset_row_for_reset, move |error| {
+                settings_dialog_for_error.add_toast(adw::Toast::new(&format!(
+                    "Failed to save default settings: {}",
+                    error
+                )));
+            });
 
-            if settings_state_for_reset.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_reset"); }
+            if settings_state_for_reset.try_borrow_mut().is_err() {
+                log::error!("Borrow conflict in UI state: Shared state borrow conflict: settings_state_for_reset");
+            }
             if let Ok(mut settings) = settings_state_for_reset.try_borrow_mut() {
                 *settings = defaults.clone();
             } else {
@@ -1832,7 +2677,11 @@ impl AdwNetworkWindow {
                 return;
             }
 
-            if prefs_for_reset.try_borrow_mut().is_err() { log::error!("Borrow conflict in UI state: Shared state borrow conflict: prefs_for_reset"); }
+            if prefs_for_reset.try_borrow_mut().is_err() {
+                log::error!(
+                    "Borrow conflict in UI state: Shared state borrow conflict: prefs_for_reset"
+                );
+            }
             if let Ok(mut prefs) = prefs_for_reset.try_borrow_mut() {
                 prefs.auto_scan = defaults.auto_scan;
                 prefs.expand_connected_details = defaults.expand_connected_details;
@@ -1855,10 +2704,24 @@ impl AdwNetworkWindow {
             quota_reset_row_for_reset.set_selected(Self::selection_from_quota_reset_policy(
                 &defaults.hotspot_quota_reset_policy,
             ));
+            hotspot_backend_row_for_reset.set_selected(Self::selection_from_hotspot_backend(
+                defaults.hotspot_backend,
+            ));
 
             auto_scan_for_reset.set_active(defaults.auto_scan);
             expand_details_for_reset.set_active(defaults.expand_connected_details);
             nav_icons_only_for_reset.set_active(defaults.icons_only_navigation);
+            min_size_for_reset.set_active(defaults.enforce_minimum_window_size);
+            tray_for_reset.set_active(defaults.show_tray_icon);
+            close_to_tray_for_reset.set_active(defaults.close_to_tray);
+            for (row, field) in &notify_rows_for_reset {
+                let mut defaults = defaults.clone();
+                row.set_active(*field(&mut defaults));
+            }
+            password_backup_for_reset.set_active(defaults.backup_hotspot_password_to_keyring);
+            status_pill_for_reset.set_selected(Self::selection_from_status_pill_action(
+                defaults.status_pill_action,
+            ));
             Self::apply_navigation_mode(
                 &wifi_stack_page_for_reset,
                 &ethernet_stack_page_for_reset,
@@ -1870,25 +2733,43 @@ impl AdwNetworkWindow {
             );
             wifi_for_reset.apply_expand_details_setting(defaults.expand_connected_details);
         });
+        reset_button.connect_clicked(move |button| {
+            let button = button.clone();
+            let reset_to_defaults = reset_to_defaults.clone();
+            glib::spawn_future_local(async move {
+                let confirmed = confirm_dialog(
+                    &button,
+                    "Reset Settings?",
+                    "All preferences will be restored to their defaults.",
+                    "Reset",
+                    true,
+                )
+                .await;
+                if confirmed {
+                    reset_to_defaults();
+                }
+            });
+        });
 
         let page = adw::PreferencesPage::new();
         page.set_title("Settings");
         page.add(&group);
         page.add(&storage_group);
         page.add(&personalization_group);
+        page.add(&background_group);
+        page.add(&notifications_group);
         page.add(&modules_group);
         page.add(&reset_group);
 
-        let settings = adw::PreferencesDialog::builder().title("Settings").build();
-        settings.add(&page);
+        settings_dialog.add(&page);
         // * Keep settings dialog responsive to main window resize.
         common::make_dialog_responsive(
-            settings.upcast_ref::<adw::Dialog>(),
+            settings_dialog.upcast_ref::<adw::Dialog>(),
             Some(window.upcast_ref::<gtk4::Window>()),
             720,
             560,
         );
-        settings.present(Some(window));
+        settings_dialog.present(Some(window));
         if show_plain_json_warning_on_load {
             // * Warn when insecure plain-JSON storage is already active on settings load.
             Self::show_plain_json_warning_dialog(window.upcast_ref::<gtk4::Window>());
@@ -2240,7 +3121,11 @@ impl AdwNetworkWindow {
         devices_page.set_visible(visible.devices);
         profiles_page.set_visible(visible.profiles);
 
-        let any_visible = visible.wifi || visible.ethernet || visible.hotspot || visible.devices || visible.profiles;
+        let any_visible = visible.wifi
+            || visible.ethernet
+            || visible.hotspot
+            || visible.devices
+            || visible.profiles;
         no_network_page.set_visible(!any_visible);
 
         let current_visible = view_stack
@@ -2328,7 +3213,7 @@ impl AdwNetworkWindow {
         }
     }
 
-    fn load_saved_theme() {
+    pub(crate) fn load_saved_theme() {
         let style_manager = adw::StyleManager::default();
         if let Ok(settings) = config::load_app_settings_sync(&config::app_settings_path()) {
             let scheme = match settings.color_scheme.as_str() {
@@ -2386,6 +3271,38 @@ impl AdwNetworkWindow {
         }
     }
 
+    fn hotspot_backend_from_selection(selected: u32) -> config::HotspotBackend {
+        match selected {
+            1 => config::HotspotBackend::Hostapd,
+            _ => config::HotspotBackend::NetworkManager,
+        }
+    }
+
+    fn selection_from_hotspot_backend(backend: config::HotspotBackend) -> u32 {
+        match backend {
+            config::HotspotBackend::NetworkManager => 0,
+            config::HotspotBackend::Hostapd => 1,
+        }
+    }
+
+    fn status_pill_action_from_selection(selected: u32) -> config::StatusPillAction {
+        match selected {
+            1 => config::StatusPillAction::RefreshAll,
+            2 => config::StatusPillAction::ToggleDetails,
+            3 => config::StatusPillAction::ShowConnectionDetails,
+            _ => config::StatusPillAction::ShowWifiPage,
+        }
+    }
+
+    fn selection_from_status_pill_action(action: config::StatusPillAction) -> u32 {
+        match action {
+            config::StatusPillAction::ShowWifiPage => 0,
+            config::StatusPillAction::RefreshAll => 1,
+            config::StatusPillAction::ToggleDetails => 2,
+            config::StatusPillAction::ShowConnectionDetails => 3,
+        }
+    }
+
     fn module_preset_selection(layout: &ModuleLayoutState) -> u32 {
         if !layout.customized {
             return 0;
@@ -2535,6 +3452,45 @@ impl AdwNetworkWindow {
         walk(root, tooltips, &mut idx);
     }
 
+    fn revert_password_storage_row(
+        row: &adw::ComboRow,
+        settings_state: &Rc<RefCell<config::AppSettings>>,
+        update_guard: &Rc<Cell<bool>>,
+        previous: (config::HotspotPasswordStorage, bool),
+    ) {
+        let (storage, plain_json_debug_opt_in) = previous;
+        if let Ok(mut settings) = settings_state.try_borrow_mut() {
+            settings.hotspot_password_storage = storage.clone();
+            settings.plain_json_debug_opt_in = plain_json_debug_opt_in;
+        } else {
+            log::error!("Borrow conflict in UI state");
+        }
+
+        update_guard.set(true);
+        row.set_selected(Self::selection_from_password_storage(&storage));
+        row.set_subtitle(if storage == config::HotspotPasswordStorage::PlainJson {
+            "(Highly insecure! Debug only)"
+        } else {
+            "Where hotspot passwords are stored"
+        });
+        update_guard.set(false);
+    }
+
+    fn revert_switch_row(row: &adw::SwitchRow, revert_guard: &Rc<Cell<bool>>, value: bool) {
+        // * Re-run the row handler so runtime state follows, but skip saving the reverted value.
+        revert_guard.set(true);
+        row.set_active(value);
+        revert_guard.set(false);
+    }
+
+    fn apply_minimum_window_size(window: &adw::ApplicationWindow, enforce: bool) {
+        if enforce {
+            window.set_size_request(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
+        } else {
+            window.set_size_request(-1, -1);
+        }
+    }
+
     fn load_css() {
         let provider = gtk4::CssProvider::new();
 
@@ -3078,6 +4034,25 @@ headerbar button.window-close {
     animation: fadeIn 220ms ease-out;
 }
 
+/* Scan diff highlights */
+@keyframes scanNew {
+    from { background: alpha(@success_color, 0.25); }
+    to { background: transparent; }
+}
+
+.scan-new {
+    animation: scanNew 1500ms ease-out;
+}
+
+@keyframes scanDeparted {
+    from { opacity: 0.6; }
+    to { opacity: 0; }
+}
+
+.scan-departed {
+    animation: scanDeparted 1500ms ease-in forwards;
+}
+
 @keyframes pulse {
     0% { transform: scale(1); opacity: 0.8; }
     50% { transform: scale(1.06); opacity: 1; }
@@ -3294,32 +4269,54 @@ statuspage.devices-empty image {
     }
 }
 
-fn spawn_save_settings(settings: &config::AppSettings) {
+// * Shows a spinner in the row while saving; on_failure lets the caller revert the control.
+fn spawn_save_settings_for_row<F>(
+    settings: &config::AppSettings,
+    row: &adw::ActionRow,
+    on_failure: F,
+) where
+    F: FnOnce(String) + 'static,
+{
     let path = config::app_settings_path();
     let settings = settings.clone();
+
+    let spinner = gtk4::Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    spinner.start();
+    row.add_suffix(&spinner);
+    row.set_sensitive(false);
+
+    let row = row.clone();
     glib::spawn_future_local(async move {
-        match tokio::task::spawn_blocking(move || config::save_app_settings_sync(&path, &settings)).await {
-            Ok(Ok(())) => (),
-            Ok(Err(e)) => log::warn!("Failed to save app settings: {}", e),
-            Err(e) => log::error!("spawn_blocking panicked: {}", e),
+        let result = match tokio::task::spawn_blocking(move || {
+            config::save_app_settings_sync(&path, &settings)
+        })
+        .await
+        {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(e) => {
+                log::error!("spawn_blocking panicked: {}", e);
+                Err(e.to_string())
+            }
+        };
+
+        row.remove(&spinner);
+        row.set_sensitive(true);
+        if let Err(e) = result {
+            log::warn!("Failed to save app settings: {}", e);
+            on_failure(e);
         }
     });
 }
 
-fn read_interface_bytes(iface: &str) -> Option<(u64, u64)> {
-    let rx_path = format!("/sys/class/net/{}/statistics/rx_bytes", iface);
-    let tx_path = format!("/sys/class/net/{}/statistics/tx_bytes", iface);
-    let rx = fs::read_to_string(rx_path)
-        .ok()?
-        .trim()
-        .parse::<u64>()
-        .ok()?;
-    let tx = fs::read_to_string(tx_path)
-        .ok()?
-        .trim()
-        .parse::<u64>()
-        .ok()?;
-    Some((rx, tx))
+// * Unknown means NM's check is disabled or pending; don't paint a working link as broken.
+fn connectivity_css_class(connectivity: nm::InternetConnectivity) -> &'static str {
+    match connectivity {
+        nm::InternetConnectivity::Full | nm::InternetConnectivity::Unknown => "status-online",
+        nm::InternetConnectivity::NoInternet
+        | nm::InternetConnectivity::Portal
+        | nm::InternetConnectivity::Limited => "status-offline",
+    }
 }
 
 fn format_speed(bytes_per_sec: u64) -> String {
@@ -0,0 +1,133 @@
+// * ./src/wireguard.rs
+
+use anyhow::{anyhow, Result};
+use tokio::process::Command;
+
+use crate::nm::command_output;
+use crate::traffic;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerStats {
+    pub public_key: String,
+    // * The address the peer was last heard from, which can differ from the configured one
+    pub endpoint: Option<String>,
+    // * Unix seconds; None until the first handshake
+    pub latest_handshake: Option<u64>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+// * Handshake data needs `wg` and usually root; byte counters come from sysfs without it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TunnelStats {
+    pub interface: String,
+    pub endpoint: Option<String>,
+    pub latest_handshake: Option<u64>,
+    pub handshake_known: bool,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+pub async fn read_tunnel_stats(iface: &str, configured_endpoint: Option<&str>) -> TunnelStats {
+    let mut stats = TunnelStats {
+        interface: iface.to_string(),
+        endpoint: configured_endpoint.map(str::to_string),
+        ..TunnelStats::default()
+    };
+
+    match read_peer_stats(iface).await {
+        Ok(peers) if !peers.is_empty() => {
+            stats.handshake_known = true;
+            stats.latest_handshake = peers.iter().filter_map(|p| p.latest_handshake).max();
+            stats.rx_bytes = peers.iter().map(|p| p.rx_bytes).sum();
+            stats.tx_bytes = peers.iter().map(|p| p.tx_bytes).sum();
+            if let Some(endpoint) = peers.iter().find_map(|p| p.endpoint.clone()) {
+                stats.endpoint = Some(endpoint);
+            }
+        }
+        result => {
+            if let Err(e) = result {
+                log::debug!("wg show {} unavailable: {}", iface, e);
+            }
+            if let Some((rx, tx)) = traffic::read_interface_bytes(iface) {
+                stats.rx_bytes = rx;
+                stats.tx_bytes = tx;
+            }
+        }
+    }
+
+    stats
+}
+
+pub async fn read_peer_stats(iface: &str) -> Result<Vec<PeerStats>> {
+    let output = command_output(Command::new("wg").args(["show", iface, "dump"])).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("wg show failed: {}", stderr));
+    }
+    Ok(parse_wg_dump(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// * The first line describes the interface itself; every following line is one peer.
+fn parse_wg_dump(text: &str) -> Vec<PeerStats> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 8 {
+                return None;
+            }
+            let endpoint = Some(fields[2])
+                .filter(|endpoint| *endpoint != "(none)")
+                .map(str::to_string);
+            let latest_handshake = fields[4].parse::<u64>().ok().filter(|secs| *secs > 0);
+            Some(PeerStats {
+                public_key: fields[0].to_string(),
+                endpoint,
+                latest_handshake,
+                rx_bytes: fields[5].parse().unwrap_or(0),
+                tx_bytes: fields[6].parse().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+pub fn format_handshake(latest_handshake: Option<u64>, now_secs: u64) -> String {
+    let Some(latest) = latest_handshake else {
+        return "Never".to_string();
+    };
+    match now_secs.saturating_sub(latest) {
+        0..=4 => "Just now".to_string(),
+        secs if secs < 60 => format!("{} seconds ago", secs),
+        secs if secs < 3600 => format!("{} min ago", secs / 60),
+        secs => format!("{} h ago", secs / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_peers_from_dump() {
+        let dump = "priv\tpub\t51820\toff\n\
+                    peerA\t(none)\t203.0.113.5:51820\t0.0.0.0/0\t1700000000\t1024\t2048\t25\n\
+                    peerB\t(none)\t(none)\t10.0.0.0/24\t0\t0\t0\toff\n";
+        let peers = parse_wg_dump(dump);
+
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].endpoint.as_deref(), Some("203.0.113.5:51820"));
+        assert_eq!(peers[0].latest_handshake, Some(1_700_000_000));
+        assert_eq!((peers[0].rx_bytes, peers[0].tx_bytes), (1024, 2048));
+        assert_eq!(peers[1].endpoint, None);
+        assert_eq!(peers[1].latest_handshake, None);
+    }
+
+    #[test]
+    fn formats_handshake_age() {
+        assert_eq!(format_handshake(None, 100), "Never");
+        assert_eq!(format_handshake(Some(98), 100), "Just now");
+        assert_eq!(format_handshake(Some(70), 100), "30 seconds ago");
+        assert_eq!(format_handshake(Some(100), 400), "5 min ago");
+    }
+}
@@ -0,0 +1,352 @@
+// * ./src/wpa_import.rs
+
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::nm;
+
+pub const DEFAULT_CONF_PATH: &str = "/etc/wpa_supplicant/wpa_supplicant.conf";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WpaSecurity {
+    Open,
+    Passphrase(String),
+    // * 64 hex characters, already derived from passphrase + SSID by wpa_passphrase
+    HexKey(String),
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WpaNetwork {
+    pub ssid: String,
+    pub security: WpaSecurity,
+    pub hidden: bool,
+    pub priority: Option<i32>,
+}
+
+impl WpaNetwork {
+    pub fn is_importable(&self) -> bool {
+        !matches!(self.security, WpaSecurity::Unsupported(_))
+    }
+
+    pub fn psk(&self) -> Option<&str> {
+        match &self.security {
+            WpaSecurity::Passphrase(psk) | WpaSecurity::HexKey(psk) => Some(psk),
+            _ => None,
+        }
+    }
+
+    pub fn security_label(&self) -> String {
+        match &self.security {
+            WpaSecurity::Open => "Open".to_string(),
+            WpaSecurity::Passphrase(_) => "WPA/WPA2 Personal".to_string(),
+            WpaSecurity::HexKey(_) => "WPA/WPA2 Personal (pre-hashed key)".to_string(),
+            WpaSecurity::Unsupported(reason) => format!("Not supported: {}", reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    Skip,
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub overwritten: usize,
+    pub skipped: usize,
+    pub failed: Vec<(String, String)>,
+}
+
+impl ImportSummary {
+    pub fn message(&self) -> String {
+        let mut parts = vec![format!("Imported {}", self.added)];
+        if self.overwritten > 0 {
+            parts.push(format!("replaced {}", self.overwritten));
+        }
+        if self.skipped > 0 {
+            parts.push(format!("skipped {}", self.skipped));
+        }
+        if !self.failed.is_empty() {
+            parts.push(format!("failed {}", self.failed.len()));
+        }
+        parts.join(", ")
+    }
+}
+
+pub fn read_conf(path: &Path) -> Result<Vec<WpaNetwork>> {
+    let content = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => anyhow!(
+            "{} is only readable by root; copy it somewhere readable first",
+            path.display()
+        ),
+        _ => anyhow!("Failed to read {}: {}", path.display(), e),
+    })?;
+
+    let networks = parse_wpa_supplicant_conf(&content);
+    if networks.is_empty() {
+        return Err(anyhow!("No network blocks found in {}", path.display()));
+    }
+    Ok(networks)
+}
+
+pub fn parse_wpa_supplicant_conf(content: &str) -> Vec<WpaNetwork> {
+    let mut networks: Vec<WpaNetwork> = Vec::new();
+    let mut block: Option<Vec<(String, String)>> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if block.is_none() {
+            let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+            if compact == "network={" {
+                block = Some(Vec::new());
+            }
+            continue;
+        }
+
+        if line == "}" {
+            if let Some(network) = block.take().and_then(|fields| network_from_fields(&fields)) {
+                // ? wpa_supplicant keeps duplicates apart; NM profiles are keyed by SSID here,
+                // ? so the first importable block wins and earlier broken ones give way to it
+                match networks.iter_mut().find(|n| n.ssid == network.ssid) {
+                    Some(existing) if !existing.is_importable() && network.is_importable() => {
+                        *existing = network;
+                    }
+                    Some(_) => {}
+                    None => networks.push(network),
+                }
+            }
+            continue;
+        }
+
+        if let (Some(fields), Some((key, value))) = (block.as_mut(), line.split_once('=')) {
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    networks
+}
+
+fn network_from_fields(fields: &[(String, String)]) -> Option<WpaNetwork> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let ssid = decode_ssid(field("ssid")?)?;
+    if ssid.is_empty() {
+        return None;
+    }
+
+    let key_mgmt = field("key_mgmt").map(|value| value.to_uppercase());
+    let security = match (key_mgmt.as_deref(), field("psk")) {
+        (Some("NONE"), _) if fields.iter().any(|(key, _)| key.starts_with("wep_key")) => {
+            WpaSecurity::Unsupported("WEP".to_string())
+        }
+        (Some("NONE"), _) => WpaSecurity::Open,
+        (Some(mgmt), Some(psk)) if mgmt.split_whitespace().any(|m| m == "WPA-PSK") => {
+            decode_psk(psk)
+        }
+        (None, Some(psk)) => decode_psk(psk),
+        (Some(mgmt), _) => WpaSecurity::Unsupported(mgmt.to_string()),
+        (None, None) => WpaSecurity::Unsupported("no key".to_string()),
+    };
+
+    Some(WpaNetwork {
+        ssid,
+        security,
+        hidden: field("scan_ssid") == Some("1"),
+        priority: field("priority").and_then(|value| value.parse().ok()),
+    })
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+}
+
+// * Quoted SSIDs are literal; unquoted ones are hex-encoded bytes.
+fn decode_ssid(value: &str) -> Option<String> {
+    if let Some(ssid) = unquote(value) {
+        return Some(ssid.to_string());
+    }
+
+    if !value.len().is_multiple_of(2) || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+fn decode_psk(value: &str) -> WpaSecurity {
+    if let Some(passphrase) = unquote(value) {
+        return if (8..=63).contains(&passphrase.len()) {
+            WpaSecurity::Passphrase(passphrase.to_string())
+        } else {
+            WpaSecurity::Unsupported("invalid passphrase length".to_string())
+        };
+    }
+
+    if value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        WpaSecurity::HexKey(value.to_lowercase())
+    } else {
+        WpaSecurity::Unsupported("malformed PSK".to_string())
+    }
+}
+
+pub async fn import_networks(networks: &[WpaNetwork], mode: ImportMode) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let saved: HashSet<String> = match nm::saved_wifi_ssids().await {
+        Ok(saved) => saved,
+        Err(e) => {
+            log::warn!("Could not list saved networks before import: {}", e);
+            HashSet::new()
+        }
+    };
+
+    for network in networks.iter().filter(|n| n.is_importable()) {
+        if mode == ImportMode::Skip && saved.contains(&network.ssid) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        match nm::save_wifi_connection(
+            &network.ssid,
+            network.psk(),
+            network.hidden,
+            network.priority,
+        )
+        .await
+        {
+            Ok(true) => summary.overwritten += 1,
+            Ok(false) => summary.added += 1,
+            Err(e) => {
+                log::error!("Failed to import {}: {}", network.ssid, e);
+                summary.failed.push((network.ssid.clone(), e.to_string()));
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+ctrl_interface=DIR=/var/run/wpa_supplicant GROUP=netdev
+update_config=1
+
+# home
+network={
+    ssid="Home Network"
+    psk="correct horse"
+    priority=5
+}
+
+network = {
+    ssid=4f6666696365
+    psk=0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0
+    scan_ssid=1
+}
+
+network={
+    ssid="Cafe"
+    key_mgmt=NONE
+}
+
+network={
+    ssid="Corp"
+    key_mgmt=WPA-EAP
+    eap=PEAP
+    identity="user"
+}
+"#;
+
+    #[test]
+    fn parses_quoted_and_hex_entries() {
+        let networks = parse_wpa_supplicant_conf(SAMPLE);
+        assert_eq!(networks.len(), 4);
+
+        assert_eq!(networks[0].ssid, "Home Network");
+        assert_eq!(
+            networks[0].security,
+            WpaSecurity::Passphrase("correct horse".to_string())
+        );
+        assert_eq!(networks[0].priority, Some(5));
+
+        assert_eq!(networks[1].ssid, "Office");
+        assert!(matches!(networks[1].security, WpaSecurity::HexKey(_)));
+        assert!(networks[1].hidden);
+
+        assert_eq!(networks[2].security, WpaSecurity::Open);
+        assert_eq!(networks[2].psk(), None);
+        assert!(!networks[3].is_importable());
+    }
+
+    #[test]
+    fn rejects_malformed_keys_and_keeps_the_first_valid_duplicate() {
+        let content = r#"
+network={
+    ssid="Short"
+    psk="1234"
+}
+network={
+    ssid="Short"
+    psk="long enough"
+}
+network={
+    ssid="Short"
+    psk="later duplicate"
+}
+network={
+    ssid="Wep"
+    key_mgmt=NONE
+    wep_key0="abcde"
+}
+network={
+    ssid="Broken"
+    psk=xyz
+}
+"#;
+        let networks = parse_wpa_supplicant_conf(content);
+        assert_eq!(networks.len(), 3);
+        assert_eq!(
+            networks[0].security,
+            WpaSecurity::Passphrase("long enough".to_string())
+        );
+        assert!(!networks[2].is_importable());
+        assert_eq!(
+            networks[1].security,
+            WpaSecurity::Unsupported("WEP".to_string())
+        );
+    }
+
+    #[test]
+    fn summarises_import_results() {
+        let summary = ImportSummary {
+            added: 2,
+            overwritten: 0,
+            skipped: 1,
+            failed: vec![("Cafe".to_string(), "boom".to_string())],
+        };
+        assert_eq!(summary.message(), "Imported 2, skipped 1, failed 1");
+    }
+}